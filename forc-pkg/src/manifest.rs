@@ -9,7 +9,10 @@ use std::{
     sync::Arc,
 };
 
-use sway_core::{fuel_prelude::fuel_tx, language::parsed::TreeType, parse_tree_type, BuildTarget};
+use sway_core::{
+    fuel_prelude::fuel_tx, language::parsed::TreeType, parse_tree_type, BuildTarget,
+    GasProfileFormat,
+};
 use sway_utils::constants;
 
 /// The name of a workspace member package.
@@ -215,6 +218,8 @@ pub struct BuildProfile {
     #[serde(default)]
     pub print_intermediate_asm: bool,
     #[serde(default)]
+    pub profile_static: Option<GasProfileFormat>,
+    #[serde(default)]
     pub terse: bool,
     #[serde(default)]
     pub time_phases: bool,
@@ -226,7 +231,12 @@ pub struct BuildProfile {
     pub json_abi_with_callpaths: bool,
     #[serde(default)]
     pub error_on_warnings: bool,
+    #[serde(default)]
+    pub verify_ir: bool,
+    #[serde(default)]
     pub reverse_results: bool,
+    #[serde(default)]
+    pub optimize: Option<bool>,
 }
 
 impl DependencyDetails {
@@ -629,6 +639,13 @@ impl PackageManifest {
         if build_profiles.get(BuildProfile::RELEASE).is_none() {
             build_profiles.insert(BuildProfile::RELEASE.into(), BuildProfile::release());
         }
+        // A user-written `release` profile replaces the default one wholesale, so it must not lose
+        // the optimization passes just because it doesn't mention `optimize`.
+        for (name, profile) in build_profiles.iter_mut() {
+            profile
+                .optimize
+                .get_or_insert(name.as_str() == BuildProfile::RELEASE);
+        }
     }
 
     /// Retrieve a reference to the dependency with the given name.
@@ -692,13 +709,16 @@ impl BuildProfile {
             print_ir: false,
             print_finalized_asm: false,
             print_intermediate_asm: false,
+            profile_static: None,
             terse: false,
             time_phases: false,
             metrics_outfile: None,
             include_tests: false,
             json_abi_with_callpaths: false,
             error_on_warnings: false,
+            verify_ir: false,
             reverse_results: false,
+            optimize: Some(false),
         }
     }
 
@@ -710,13 +730,16 @@ impl BuildProfile {
             print_ir: false,
             print_finalized_asm: false,
             print_intermediate_asm: false,
+            profile_static: None,
             terse: false,
             time_phases: false,
             metrics_outfile: None,
             include_tests: false,
             json_abi_with_callpaths: false,
             error_on_warnings: false,
+            verify_ir: false,
             reverse_results: false,
+            optimize: Some(true),
         }
     }
 }
@@ -1013,7 +1036,7 @@ pub fn find_dir_within(dir: &Path, pkg_name: &str) -> Option<PathBuf> {
 
 #[cfg(test)]
 mod tests {
-    use super::DependencyDetails;
+    use super::{BuildProfile, DependencyDetails, PackageManifest};
 
     #[test]
     fn test_invalid_dependency_details_mixed_together() {
@@ -1211,4 +1234,34 @@ mod tests {
         assert!(dependency_details_git_rev.validate().is_ok());
         assert!(dependency_details_ipfs.validate().is_ok());
     }
+
+    #[test]
+    fn test_user_release_profile_optimizes_by_default() {
+        let manifest_str = r#"
+            [project]
+            authors = ["Fuel Labs <contact@fuel.sh>"]
+            entry = "main.sw"
+            license = "Apache-2.0"
+            name = "test"
+
+            [build-profile.release]
+            terse = true
+
+            [build-profile.unoptimized-release]
+            terse = true
+            optimize = false
+
+            [build-profile.custom]
+            print-ir = true
+        "#;
+        let mut manifest: PackageManifest = toml::from_str(manifest_str).unwrap();
+        manifest.implicitly_include_default_build_profiles_if_missing();
+
+        let build_profiles = manifest.build_profile.unwrap();
+        let optimize = |name: &str| build_profiles[name].optimize;
+        assert_eq!(optimize(BuildProfile::RELEASE), Some(true));
+        assert_eq!(optimize(BuildProfile::DEBUG), Some(false));
+        assert_eq!(optimize("unoptimized-release"), Some(false));
+        assert_eq!(optimize("custom"), Some(false));
+    }
 }
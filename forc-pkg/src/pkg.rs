@@ -23,7 +23,10 @@ use std::{
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use sway_core::fuel_prelude::fuel_tx::ConsensusParameters;
 pub use sway_core::Programs;
@@ -32,7 +35,7 @@ use sway_core::{
         evm_json_abi,
         fuel_json_abi::{self, JsonAbiContext},
     },
-    asm_generation::ProgramABI,
+    asm_generation::{fuel::gas_profile::GasProfile, ProgramABI},
     decl_engine::DeclRefFunction,
     fuel_prelude::{
         fuel_crypto,
@@ -42,7 +45,7 @@ use sway_core::{
     semantic_analysis::namespace,
     source_map::SourceMap,
     transform::AttributeKind,
-    BuildTarget, CompileResult, Engines, FinalizedEntry,
+    BuildTarget, CompileResult, Engines, FinalizedEntry, GasProfileFormat,
 };
 use sway_error::{error::CompileError, warning::CompileWarning};
 use sway_types::{Ident, Span, Spanned};
@@ -110,6 +113,8 @@ pub struct BuiltPackage {
     ///
     /// For non-contract members, this is always `None`.
     pub bytecode_without_tests: Option<BuiltPackageBytecode>,
+    /// The static estimate of the gas used by the bytecode, when the build profile asks for it.
+    pub gas_profile: Option<GasProfile>,
 }
 
 /// The package descriptors that a `BuiltPackage` holds so that the source used for building the
@@ -179,6 +184,7 @@ pub struct CompiledPackage {
     pub namespace: namespace::Root,
     pub warnings: Vec<CompileWarning>,
     pub metrics: PerformanceData,
+    pub gas_profile: Option<GasProfile>,
 }
 
 /// Compiled contract dependency parts relevant to calculating a contract's ID.
@@ -262,6 +268,9 @@ pub struct PrintOpts {
     pub intermediate_asm: bool,
     /// Print the generated Sway IR (Intermediate Representation).
     pub ir: bool,
+    /// Write a static estimate of the gas used by each instruction, block and function of the
+    /// finalized ASM to the output directory, in the given format.
+    pub profile_static: Option<GasProfileFormat>,
     /// Output build errors and warnings in reverse order.
     pub reverse_order: bool,
 }
@@ -304,6 +313,8 @@ pub struct BuildOpts {
     pub metrics_outfile: Option<String>,
     /// Warnings must be treated as compiler errors.
     pub error_on_warnings: bool,
+    /// Verify the IR after every optimization pass.
+    pub verify_ir: bool,
     /// Include all test functions within the build.
     pub tests: bool,
     /// The set of options to filter by member project kind.
@@ -420,6 +431,11 @@ impl BuiltPackage {
         Ok(())
     }
 
+    /// The mapping from the instructions of the package's bytecode to their source locations.
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
     /// Writes debug_info (source_map) of the BuiltPackage to the given `path`.
     pub fn write_debug_info(&self, path: &Path) -> Result<()> {
         let source_map_json =
@@ -428,6 +444,22 @@ impl BuiltPackage {
         Ok(())
     }
 
+    /// Writes the static gas profile of the BuiltPackage, if it has one, to the given `path`.
+    pub fn write_gas_profile(&self, path: &Path, format: GasProfileFormat) -> Result<()> {
+        let Some(gas_profile) = &self.gas_profile else {
+            return Ok(());
+        };
+        match format {
+            GasProfileFormat::Text => fs::write(path, gas_profile.to_string())?,
+            GasProfileFormat::Json => {
+                let file = File::create(path)?;
+                serde_json::to_writer_pretty(&file, gas_profile)?;
+            }
+        }
+        info!("      Gas profile: {}", path.display());
+        Ok(())
+    }
+
     /// Writes BuiltPackage to `output_dir`.
     pub fn write_output(
         &self,
@@ -443,6 +475,11 @@ impl BuiltPackage {
 
         self.write_bytecode(&bin_path)?;
 
+        let source_map_path = output_dir
+            .join(format!("{pkg_name}-source-map"))
+            .with_extension("json");
+        self.write_debug_info(&source_map_path)?;
+
         let program_abi_stem = format!("{pkg_name}-abi");
         let program_abi_path = output_dir.join(program_abi_stem).with_extension("json");
         match &self.program_abi {
@@ -1565,6 +1602,8 @@ pub fn sway_build_config(
     .print_finalized_asm(build_profile.print_finalized_asm)
     .print_intermediate_asm(build_profile.print_intermediate_asm)
     .print_ir(build_profile.print_ir)
+    .verify_ir(build_profile.verify_ir)
+    .optimize(build_profile.optimize.unwrap_or(false))
     .include_tests(build_profile.include_tests)
     .time_phases(build_profile.time_phases)
     .metrics(build_profile.metrics_outfile.clone());
@@ -1859,6 +1898,10 @@ pub fn compile(
         .as_ref()
         .map(|asm| asm.0.entries.clone())
         .unwrap_or_default();
+    let gas_profile = profile
+        .profile_static
+        .and(asm_res.value.as_ref())
+        .and_then(|asm| asm.0.static_gas_profile());
     let entries = entries
         .iter()
         .map(|finalized_entry| PkgEntry::from_finalized_entry(finalized_entry, engines))
@@ -1916,6 +1959,7 @@ pub fn compile(
         namespace,
         warnings: bc_res.warnings,
         metrics,
+        gas_profile,
     };
     Ok(compiled_package)
 }
@@ -2027,6 +2071,7 @@ fn build_profile_from_opts(
         metrics_outfile,
         tests,
         error_on_warnings,
+        verify_ir,
         ..
     } = build_options;
     let mut selected_build_profile = BuildProfile::DEBUG;
@@ -2072,6 +2117,9 @@ fn build_profile_from_opts(
     profile.print_ir |= print.ir;
     profile.print_finalized_asm |= print.finalized_asm;
     profile.print_intermediate_asm |= print.intermediate_asm;
+    if profile.profile_static.is_none() {
+        profile.profile_static = print.profile_static;
+    }
     profile.terse |= pkg.terse;
     profile.time_phases |= time_phases;
     if profile.metrics_outfile.is_none() {
@@ -2080,6 +2128,7 @@ fn build_profile_from_opts(
     profile.include_tests |= tests;
     profile.json_abi_with_callpaths |= pkg.json_abi_with_callpaths;
     profile.error_on_warnings |= error_on_warnings;
+    profile.verify_ir |= verify_ir;
 
     Ok((selected_build_profile.to_string(), profile))
 }
@@ -2161,6 +2210,16 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
             built_package.write_debug_info(outfile.as_ref())?;
         }
         built_package.write_output(minify.clone(), &pkg_manifest.project.name, &output_dir)?;
+        if let Some(format) = build_profile.profile_static {
+            let extension = match format {
+                GasProfileFormat::Text => "txt",
+                GasProfileFormat::Json => "json",
+            };
+            let gas_profile_path = output_dir
+                .join(format!("{}-gas-profile", pkg_manifest.project.name))
+                .with_extension(extension);
+            built_package.write_gas_profile(&gas_profile_path, format)?;
+        }
         built_workspace.push(Arc::new(built_package));
     }
 
@@ -2260,9 +2319,9 @@ pub fn build(
         .iter()
         .filter(|node| required.contains(node))
     {
-        let mut source_map = SourceMap::new();
         let pkg = &plan.graph()[node];
         let manifest = &plan.manifest_map()[&pkg.id()];
+        let mut source_map = SourceMap::for_package(manifest.dir());
         let program_ty = manifest.program_type().ok();
 
         print_compiling(
@@ -2321,12 +2380,13 @@ pub fn build(
                 Err(errs) => return fail(&[], &errs),
             };
 
+            // The source map is kept for the bytecode with tests, so this build gets its own.
             let compiled_without_tests = compile(
                 &descriptor,
                 &profile,
                 &engines,
                 dep_namespace,
-                &mut source_map,
+                &mut SourceMap::for_package(manifest.dir()),
             )?;
 
             if let Some(outfile) = profile.metrics_outfile {
@@ -2429,6 +2489,7 @@ pub fn build(
             bytecode: compiled.bytecode,
             warnings: compiled.warnings,
             bytecode_without_tests,
+            gas_profile: compiled.gas_profile,
         };
 
         if outputs.contains(&node) {
@@ -2580,101 +2641,267 @@ pub fn check(
     engines: &Engines,
 ) -> anyhow::Result<Vec<CompileResult<Programs>>> {
     let mut lib_namespace_map = Default::default();
-    let mut source_map = SourceMap::new();
-    // During `check`, we don't compile so this stays empty.
-    let compiled_contract_deps = HashMap::new();
 
     let mut results = vec![];
     for (idx, &node) in plan.compilation_order.iter().enumerate() {
-        let pkg = &plan.graph[node];
-        let manifest = &plan.manifest_map()[&pkg.id()];
-
-        // This is necessary because `CONTRACT_ID` is a special constant that's injected into the
-        // compiler's namespace. Although we only know the contract id during building, we are
-        // inserting a dummy value here to avoid false error signals being reported in LSP.
-        // We only do this for the last node in the compilation order because previous nodes
-        // are dependencies.
-        //
-        // See this github issue for more context: https://github.com/FuelLabs/sway-vscode-plugin/issues/154
-        const DUMMY_CONTRACT_ID: &str =
-            "0x0000000000000000000000000000000000000000000000000000000000000000";
-        let contract_id_value =
-            (idx == plan.compilation_order.len() - 1).then(|| DUMMY_CONTRACT_ID.to_string());
-
-        let dep_namespace = dependency_namespace(
+        let programs_res = check_package(
+            plan,
+            idx,
             &lib_namespace_map,
-            &compiled_contract_deps,
-            &plan.graph,
-            node,
+            build_target,
+            terse_mode,
+            include_tests,
             engines,
-            contract_id_value,
-        )
-        .expect("failed to create dependency namespace");
+        )?;
+        let is_checked =
+            insert_lib_namespace(plan, node, &programs_res, &mut lib_namespace_map, engines)?;
+        results.push(programs_res);
+        if !is_checked {
+            return Ok(results);
+        }
+    }
 
-        let profile = BuildProfile {
-            terse: terse_mode,
-            ..BuildProfile::debug()
-        };
+    if results.is_empty() {
+        bail!("unable to check sway program: build plan contains no packages")
+    }
 
-        let build_config = sway_build_config(
-            manifest.dir(),
-            &manifest.entry_path(),
-            build_target,
-            &profile,
-        )?
-        .include_tests(include_tests);
+    Ok(results)
+}
 
-        let mut metrics = PerformanceData::default();
-        let programs_res = sway_core::compile_to_ast(
-            engines,
-            manifest.entry_string()?,
-            dep_namespace,
-            Some(&build_config),
-            &pkg.name,
-            &mut metrics,
-        );
+/// The dependencies checked by [check_with_cache], which are reused by later checks of the same
+/// build plan for as long as their source files don't change.
+#[derive(Debug, Default)]
+pub struct CheckCache {
+    /// The engines as they were right after the dependencies were checked.
+    engines: Engines,
+    /// The checked dependencies in compilation order, along with the fingerprints of their sources.
+    dependencies: Vec<(Pinned, SourceFingerprint, CompileResult<Programs>)>,
+}
 
-        let programs = match programs_res.value.as_ref() {
-            Some(programs) => programs,
-            _ => {
-                results.push(programs_res);
-                return Ok(results);
-            }
-        };
+/// The paths, sizes and modification times of the source files and manifest of a package, used to
+/// detect changes to the package.
+#[derive(Debug, PartialEq, Eq)]
+struct SourceFingerprint(Vec<(PathBuf, u64, Option<std::time::SystemTime>)>);
 
-        match programs.typed.as_ref() {
-            Some(typed_program) => {
-                if let TreeType::Library = typed_program.kind.tree_type() {
-                    let mut namespace = typed_program.root.namespace.clone();
-                    namespace.name = Some(Ident::new_no_span(pkg.name.clone()));
-                    namespace.span = Some(
-                        Span::new(
-                            manifest.entry_string()?,
-                            0,
-                            0,
-                            Some(engines.se().get_source_id(&manifest.entry_path())),
-                        )
-                        .unwrap(),
-                    );
-                    lib_namespace_map.insert(node, namespace.module().clone());
-                }
+impl SourceFingerprint {
+    fn of_package(manifest: &PackageManifestFile) -> Self {
+        let mut paths = sway_utils::helpers::get_sway_files(manifest.dir().to_path_buf());
+        paths.push(manifest.path().to_path_buf());
+        paths.sort();
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let metadata = fs::metadata(&path).ok();
+                let len = metadata.as_ref().map_or(0, |metadata| metadata.len());
+                let modified = metadata.and_then(|metadata| metadata.modified().ok());
+                (path, len, modified)
+            })
+            .collect();
+        SourceFingerprint(files)
+    }
+}
 
-                source_map.insert_dependency(manifest.dir());
+/// Compile the forc package like [check], reusing the dependencies checked by a previous call if
+/// none of their source files have changed since.
+///
+/// The project, which is the final package in the compilation order, is always checked again. As
+/// the checked dependencies are shared between calls, the project is checked with a copy of the
+/// engines that the dependencies were checked with, and this copy is returned along with the
+/// results.
+///
+/// Returns `None` if `cancel` is set before the check completes. The flag is checked between
+/// packages.
+///
+/// `on_dependency_checked` is called with the result of each dependency as soon as it is checked,
+/// before the packages depending on it are checked. It isn't called for reused dependencies.
+pub fn check_with_cache(
+    plan: &BuildPlan,
+    build_target: BuildTarget,
+    terse_mode: bool,
+    include_tests: bool,
+    cache: &mut CheckCache,
+    cancel: &AtomicBool,
+    mut on_dependency_checked: impl FnMut(&Engines, &CompileResult<Programs>),
+) -> anyhow::Result<Option<(Engines, Vec<CompileResult<Programs>>)>> {
+    let Some((_, dependencies)) = plan.compilation_order.split_last() else {
+        bail!("unable to check sway program: build plan contains no packages")
+    };
+    let fingerprints: Vec<_> = dependencies
+        .iter()
+        .map(|&node| {
+            let pkg = &plan.graph[node];
+            let manifest = &plan.manifest_map()[&pkg.id()];
+            (pkg.clone(), SourceFingerprint::of_package(manifest))
+        })
+        .collect();
+    let is_cached = cache.dependencies.len() == fingerprints.len()
+        && cache.dependencies.iter().zip(&fingerprints).all(
+            |((pkg, fingerprint, _), (new_pkg, new_fingerprint))| {
+                pkg == new_pkg && fingerprint == new_fingerprint
+            },
+        );
+
+    if !is_cached {
+        let engines = Engines::default();
+        let mut lib_namespace_map = Default::default();
+        let mut checked = vec![];
+        for (idx, (&node, (pkg, fingerprint))) in dependencies.iter().zip(fingerprints).enumerate()
+        {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
             }
-            None => {
+            let programs_res = check_package(
+                plan,
+                idx,
+                &lib_namespace_map,
+                build_target,
+                terse_mode,
+                include_tests,
+                &engines,
+            )?;
+            on_dependency_checked(&engines, &programs_res);
+            if !insert_lib_namespace(plan, node, &programs_res, &mut lib_namespace_map, &engines)? {
+                // Dependencies that fail to check aren't cached, so that they're checked again.
+                let mut results: Vec<_> = checked.into_iter().map(|(_, _, res)| res).collect();
                 results.push(programs_res);
-                return Ok(results);
+                return Ok(Some((engines, results)));
             }
+            checked.push((pkg, fingerprint, programs_res));
         }
-
-        results.push(programs_res)
+        *cache = CheckCache {
+            engines,
+            dependencies: checked,
+        };
     }
 
-    if results.is_empty() {
-        bail!("unable to check sway program: build plan contains no packages")
+    if cancel.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+    let engines = cache.engines.clone();
+    let mut lib_namespace_map = Default::default();
+    for (&node, (_, _, programs_res)) in dependencies.iter().zip(&cache.dependencies) {
+        insert_lib_namespace(plan, node, programs_res, &mut lib_namespace_map, &engines)?;
+    }
+    let programs_res = check_package(
+        plan,
+        dependencies.len(),
+        &lib_namespace_map,
+        build_target,
+        terse_mode,
+        include_tests,
+        &engines,
+    )?;
+    if cancel.load(Ordering::Relaxed) {
+        return Ok(None);
     }
 
-    Ok(results)
+    let mut results: Vec<_> = cache
+        .dependencies
+        .iter()
+        .map(|(_, _, res)| res.clone())
+        .collect();
+    results.push(programs_res);
+    Ok(Some((engines, results)))
+}
+
+/// Compile the package at position `idx` in the compilation order of `plan` to its lexed, parsed
+/// and typed programs, using the namespaces of the libraries that were checked before it.
+fn check_package(
+    plan: &BuildPlan,
+    idx: usize,
+    lib_namespace_map: &HashMap<NodeIx, namespace::Module>,
+    build_target: BuildTarget,
+    terse_mode: bool,
+    include_tests: bool,
+    engines: &Engines,
+) -> anyhow::Result<CompileResult<Programs>> {
+    let node = plan.compilation_order[idx];
+    let pkg = &plan.graph[node];
+    let manifest = &plan.manifest_map()[&pkg.id()];
+    // During `check`, we don't compile so this stays empty.
+    let compiled_contract_deps = HashMap::new();
+
+    // This is necessary because `CONTRACT_ID` is a special constant that's injected into the
+    // compiler's namespace. Although we only know the contract id during building, we are
+    // inserting a dummy value here to avoid false error signals being reported in LSP.
+    // We only do this for the last node in the compilation order because previous nodes
+    // are dependencies.
+    //
+    // See this github issue for more context: https://github.com/FuelLabs/sway-vscode-plugin/issues/154
+    const DUMMY_CONTRACT_ID: &str =
+        "0x0000000000000000000000000000000000000000000000000000000000000000";
+    let contract_id_value =
+        (idx == plan.compilation_order.len() - 1).then(|| DUMMY_CONTRACT_ID.to_string());
+
+    let dep_namespace = dependency_namespace(
+        lib_namespace_map,
+        &compiled_contract_deps,
+        &plan.graph,
+        node,
+        engines,
+        contract_id_value,
+    )
+    .expect("failed to create dependency namespace");
+
+    let profile = BuildProfile {
+        terse: terse_mode,
+        ..BuildProfile::debug()
+    };
+
+    let build_config = sway_build_config(
+        manifest.dir(),
+        &manifest.entry_path(),
+        build_target,
+        &profile,
+    )?
+    .include_tests(include_tests);
+
+    let mut metrics = PerformanceData::default();
+    Ok(sway_core::compile_to_ast(
+        engines,
+        manifest.entry_string()?,
+        dep_namespace,
+        Some(&build_config),
+        &pkg.name,
+        &mut metrics,
+    ))
+}
+
+/// Adds the namespace of the package `node` to `lib_namespace_map` if the package is a library.
+///
+/// Returns `false` if the package failed to type check, in which case the packages depending on it
+/// can't be checked.
+fn insert_lib_namespace(
+    plan: &BuildPlan,
+    node: NodeIx,
+    programs_res: &CompileResult<Programs>,
+    lib_namespace_map: &mut HashMap<NodeIx, namespace::Module>,
+    engines: &Engines,
+) -> anyhow::Result<bool> {
+    let Some(typed_program) = programs_res
+        .value
+        .as_ref()
+        .and_then(|programs| programs.typed.as_ref())
+    else {
+        return Ok(false);
+    };
+    if let TreeType::Library = typed_program.kind.tree_type() {
+        let pkg = &plan.graph[node];
+        let manifest = &plan.manifest_map()[&pkg.id()];
+        let mut namespace = typed_program.root.namespace.clone();
+        namespace.name = Some(Ident::new_no_span(pkg.name.clone()));
+        namespace.span = Some(
+            Span::new(
+                manifest.entry_string()?,
+                0,
+                0,
+                Some(engines.se().get_source_id(&manifest.entry_path())),
+            )
+            .unwrap(),
+        );
+        lib_namespace_map.insert(node, namespace.module().clone());
+    }
+    Ok(true)
 }
 
 /// Format an error message for an absent `Forc.toml`.
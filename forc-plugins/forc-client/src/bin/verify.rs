@@ -0,0 +1,16 @@
+use clap::Parser;
+use forc_tracing::init_tracing_subscriber;
+
+#[tokio::main]
+async fn main() {
+    init_tracing_subscriber(Default::default());
+    let command = forc_client::cmd::Verify::parse();
+    match forc_client::op::verify(command).await {
+        Ok(verification) if !verification.is_match() => std::process::exit(1),
+        Ok(_) => {}
+        Err(err) => {
+            tracing::error!("Error: {:?}", err);
+            std::process::exit(1);
+        }
+    }
+}
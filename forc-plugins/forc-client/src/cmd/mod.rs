@@ -1,7 +1,9 @@
 pub mod deploy;
 pub mod run;
 pub mod submit;
+pub mod verify;
 
 pub use deploy::Command as Deploy;
 pub use run::Command as Run;
 pub use submit::Command as Submit;
+pub use verify::Command as Verify;
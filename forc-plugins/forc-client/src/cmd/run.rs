@@ -1,5 +1,6 @@
 use clap::Parser;
 use fuel_crypto::SecretKey;
+use std::path::PathBuf;
 
 pub use super::submit::Network;
 pub use forc::cli::shared::{BuildOutput, BuildProfile, Minify, Pkg, Print};
@@ -32,12 +33,74 @@ pub struct Command {
     /// Hex string of data to input to script.
     #[clap(short, long)]
     pub data: Option<String>,
+    /// Arguments to pass into main function with forc run.
+    ///
+    /// Each argument is a single value as split by the shell, so quote values that contain
+    /// spaces, e.g. `--args "hello world"` for a `str[11]` argument.
+    ///
+    /// Struct fields are given either positionally, as in `(1, true)`, or by name, as in
+    /// `{"x": 1, "y": true}`.
+    #[clap(long, conflicts_with = "data")]
+    pub args: Option<Vec<String>>,
+    /// Arguments to pass into main function with forc run, given together in a single string,
+    /// e.g. `--args-raw "2 true"`.
+    ///
+    /// The string is split on whitespace, keeping quoted strings, arrays and structs whole.
+    #[clap(long, conflicts_with_all = &["data", "args"])]
+    pub args_raw: Option<String>,
+    /// Prompt for each argument of the main function by name and type, rather than passing them
+    /// with `--args`. Invalid values are prompted for again.
+    #[clap(long, conflicts_with_all = &["data", "args", "args-raw"])]
+    pub interactive: bool,
+    /// Accept arguments of types that aren't supported yet as `0x` prefixed hex, which is passed
+    /// through as their encoding without any checks. Only use this for prototyping, as values
+    /// which aren't valid encodings of their type make the script misbehave.
+    #[clap(long)]
+    pub raw_type_passthrough: bool,
+    /// Print the signature of the script's main function, as declared by its ABI, and exit
+    /// without running the script.
+    #[clap(long)]
+    pub show_signature: bool,
+    /// Print the functions of the script's ABI with their arguments and outputs, its logged types
+    /// and its configurables, and exit without running the script.
+    #[clap(long)]
+    pub describe_abi: bool,
+    /// Run the scripts listed in the given JSON batch file in order, rather than the script of
+    /// the current package, and print a JSON report of their results.
+    ///
+    /// The batch file is an array of entries of the form
+    /// `{"script": "<bytecode path>", "abi": "<ABI path>", "args": ["<arg>", ...]}`, with paths
+    /// relative to the batch file.
+    #[clap(
+        long,
+        conflicts_with_all = &["data", "args", "args-raw", "interactive", "show-signature"]
+    )]
+    pub batch: Option<PathBuf>,
+    /// Stop running a batch at the first entry which fails, rather than reporting the failure
+    /// and moving on to the next entry.
+    #[clap(long, requires = "batch")]
+    pub fail_fast: bool,
+    /// Build the package at `--path`, or in the current directory, before running a batch, so
+    /// that entries referring to its build artifacts don't run stale ones.
+    ///
+    /// Build errors abort the batch before any entry is run. Without `--batch`, the package is
+    /// always built before it is run.
+    #[clap(long, requires = "batch")]
+    pub build: bool,
     /// Only craft transaction and print it out.
     #[clap(long)]
     pub dry_run: bool,
     /// Pretty-print the outputs from the node.
     #[clap(long = "pretty-print", short = 'r')]
     pub pretty_print: bool,
+    /// Print only the receipts from the node as JSON, without the receipts formatted for reading
+    /// or the summary of the run.
+    #[clap(long)]
+    pub json: bool,
+    /// Succeed even if the script reverts or panics. Otherwise forc run fails with the revert
+    /// code or panic reason after printing the receipts.
+    #[clap(long)]
+    pub allow_revert: bool,
     /// 32-byte contract ID that will be called during the transaction.
     #[clap(long = "contract")]
     pub contract: Option<Vec<String>>,
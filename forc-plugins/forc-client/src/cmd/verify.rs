@@ -0,0 +1,37 @@
+use clap::Parser;
+use fuel_tx::ContractId;
+
+pub use forc::cli::shared::{BuildProfile, Pkg};
+
+/// Verify that a deployed contract was built from the local source.
+///
+/// Builds the contract with the dependencies pinned by its `Forc.lock`, in a copy of the source at
+/// a fixed path, and compares the code root of the resulting bytecode with the code root of the
+/// contract deployed at the given ID.
+///
+/// The bytecode doesn't record the compiler version it was built with, so the version of the
+/// local forc is printed instead, to compare with the version the contract was deployed with.
+#[derive(Debug, Default, Parser)]
+#[clap(bin_name = "forc verify", version)]
+pub struct Command {
+    #[clap(flatten)]
+    pub pkg: Pkg,
+    /// The build profile the contract was deployed with, as the bytecode differs between
+    /// profiles.
+    #[clap(flatten)]
+    pub build_profile: BuildProfile,
+    /// The ID of the deployed contract to verify against.
+    #[clap(long)]
+    pub contract_id: ContractId,
+    /// The URL of the Fuel node from which the deployed contract is fetched.
+    /// If unspecified, checks the manifest's `network` table, then falls back
+    /// to [`crate::default::NODE_URL`].
+    #[clap(long, alias = "node", env = "FUEL_NODE_URL")]
+    pub node_url: Option<String>,
+    /// The value of a configurable the contract was deployed with, as `NAME=VALUE`, e.g.
+    /// `--configurable FEE=5`. Values are given as for the arguments of `forc run`.
+    ///
+    /// Configurables which aren't given keep the values they are declared with.
+    #[clap(long = "configurable")]
+    pub configurables: Vec<String>,
+}
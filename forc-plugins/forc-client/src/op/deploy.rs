@@ -300,6 +300,7 @@ fn build_opts_from_cmd(cmd: &cmd::Deploy) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            profile_static: cmd.print.profile_static,
             reverse_order: cmd.print.reverse_order,
         },
         time_phases: cmd.print.time_phases,
@@ -311,6 +312,7 @@ fn build_opts_from_cmd(cmd: &cmd::Deploy) -> pkg::BuildOpts {
         build_profile: cmd.build_profile.build_profile.clone(),
         release: cmd.build_profile.release,
         error_on_warnings: cmd.build_profile.error_on_warnings,
+        verify_ir: cmd.build_profile.verify_ir,
         binary_outfile: cmd.build_output.bin_file.clone(),
         debug_outfile: cmd.build_output.debug_file.clone(),
         build_target: BuildTarget::default(),
@@ -331,8 +333,12 @@ mod test {
             .join("data");
 
         for entry in manifests_dir.read_dir().unwrap() {
-            let manifest =
-                PackageManifestFile::from_file(entry.unwrap().path().join("Forc.toml")).unwrap();
+            // Not every directory of test data is a package, as with batch files.
+            let manifest_path = entry.unwrap().path().join("Forc.toml");
+            if !manifest_path.is_file() {
+                continue;
+            }
+            let manifest = PackageManifestFile::from_file(manifest_path).unwrap();
             contract_to_manifest.insert(manifest.project_name().to_string(), manifest);
         }
 
@@ -1,7 +1,11 @@
 mod deploy;
 mod run;
 mod submit;
+mod verify;
 
 pub use deploy::deploy;
 pub use run::run;
 pub use submit::submit;
+pub use verify::{verify, Verification};
+
+pub use crate::util::encode::{ScriptCallBuilder, ScriptCallHandler, Type};
@@ -1,6 +1,7 @@
 use crate::{
     cmd,
     util::{
+        encode::ScriptCallHandler,
         pkg::built_pkgs,
         tx::{TransactionBuilderExt, WalletSelectionMode, TX_SUBMIT_TIMEOUT_MS},
     },
@@ -8,18 +9,205 @@ use crate::{
 use anyhow::{anyhow, bail, Context, Result};
 use forc_pkg::{self as pkg, fuel_core_not_running, PackageManifestFile};
 use forc_util::tx_utils::format_log_receipts;
+use fuel_abi_types::error_codes::ErrorSignal;
 use fuel_core_client::client::FuelClient;
-use fuel_tx::{ContractId, Transaction, TransactionBuilder};
+use fuel_tx::{
+    Bytes32, ConsensusParameters, ContractId, Receipt, ScriptExecutionResult, Transaction,
+    TransactionBuilder, UniqueIdentifier,
+};
 use pkg::BuiltPackage;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use sway_core::language::parsed::TreeType;
-use sway_core::BuildTarget;
+use sway_core::{asm_generation::ProgramABI, source_map::SourceMap, BuildTarget};
 use tokio::time::timeout;
 use tracing::info;
 
+#[derive(Debug)]
 pub struct RanScript {
     pub receipts: Vec<fuel_tx::Receipt>,
+    /// The summary of the run, if the script of a package was sent to the node and ran
+    /// successfully.
+    pub summary: Option<RunSummary>,
+}
+
+/// A one line summary of a successful script run, printed after its receipts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunSummary {
+    pub tx_id: Bytes32,
+    pub gas_used: u64,
+    /// The decoded output of the script, if its return type could be decoded.
+    pub output: Option<String>,
+}
+
+impl RunSummary {
+    /// Summarize the run of the transaction `tx_id` from its receipts, or `None` if the script
+    /// didn't run successfully.
+    pub fn new(
+        tx_id: Bytes32,
+        receipts: &[Receipt],
+        call_handler: Option<&ScriptCallHandler>,
+    ) -> Option<Self> {
+        let gas_used = receipts.iter().find_map(|receipt| match receipt {
+            Receipt::ScriptResult {
+                result: ScriptExecutionResult::Success,
+                gas_used,
+            } => Some(*gas_used),
+            _ => None,
+        })?;
+        let output = call_handler.and_then(|handler| handler.decode_output(receipts).ok());
+        Some(RunSummary {
+            tx_id,
+            gas_used,
+            output,
+        })
+    }
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transaction {:#x} succeeded using {} gas",
+            self.tx_id, self.gas_used
+        )?;
+        if let Some(output) = &self.output {
+            write!(f, ", returning {output}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The kinds of receipts which are highlighted when the receipts of a script are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptKind {
+    Panic,
+    Revert,
+    Log,
+    Return,
+    Other,
+}
+
+impl ReceiptKind {
+    fn of(receipt: &Receipt) -> Self {
+        match receipt {
+            Receipt::Panic { .. } => ReceiptKind::Panic,
+            Receipt::Revert { .. } => ReceiptKind::Revert,
+            Receipt::Log { .. } | Receipt::LogData { .. } => ReceiptKind::Log,
+            Receipt::Return { .. } | Receipt::ReturnData { .. } => ReceiptKind::Return,
+            _ => ReceiptKind::Other,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ReceiptKind::Panic => "Panic",
+            ReceiptKind::Revert => "Revert",
+            ReceiptKind::Log => "Log",
+            ReceiptKind::Return => "Return",
+            ReceiptKind::Other => "Receipt",
+        }
+    }
+
+    fn style(self) -> ansi_term::Style {
+        match self {
+            ReceiptKind::Panic => ansi_term::Colour::Red.bold(),
+            ReceiptKind::Revert => ansi_term::Colour::Yellow.bold(),
+            ReceiptKind::Log => ansi_term::Colour::Cyan.bold(),
+            ReceiptKind::Return => ansi_term::Colour::Green.bold(),
+            ReceiptKind::Other => ansi_term::Style::new().dimmed(),
+        }
+    }
+}
+
+/// A receipt rendered for printing, see [format_receipts].
+#[derive(Debug, PartialEq, Eq)]
+pub struct FormattedReceipt {
+    pub kind: ReceiptKind,
+    pub text: String,
+}
+
+impl fmt::Display for FormattedReceipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = format!("{:>7}", self.kind.label());
+        write!(f, "{} {}", self.kind.style().paint(label), self.text)
+    }
+}
+
+/// Classify the receipts of running a script and render their contents.
+///
+/// Logged values are decoded with the logged types of the script's ABI when `call_handler` is
+/// given, and shown as hex otherwise. Reverts and panics of the script itself are located in its
+/// source with `source_map` when given, and revert codes of the standard library are named.
+pub fn format_receipts(
+    receipts: &[Receipt],
+    call_handler: Option<&ScriptCallHandler>,
+    source_map: Option<&SourceMap>,
+) -> Vec<FormattedReceipt> {
+    let decode_log = |log_id: u64, data: &[u8]| {
+        call_handler
+            .and_then(|handler| handler.decode_log(log_id, data).ok())
+            .map(|value| format!("{value} (log ID {log_id})"))
+    };
+    receipts
+        .iter()
+        .map(|receipt| {
+            let text = match receipt {
+                Receipt::Panic {
+                    id, reason, pc, is, ..
+                } => {
+                    let location = source_location(source_map, id, *pc, *is);
+                    format!("{:?} at pc {pc:#x}{location}", reason.reason())
+                }
+                Receipt::Revert { id, ra, pc, is, .. } => {
+                    let location = source_location(source_map, id, *pc, *is);
+                    match ErrorSignal::try_from_revert_code(*ra) {
+                        Ok(signal) => format!("{ra:#x} at pc {pc:#x}{location} -- {signal}"),
+                        Err(_) => format!("{ra:#x} at pc {pc:#x}{location}"),
+                    }
+                }
+                Receipt::Log { ra, rb, .. } => decode_log(*rb, &ra.to_be_bytes())
+                    .unwrap_or_else(|| format!("{ra} (log ID {rb})")),
+                Receipt::LogData { rb, data, .. } => decode_log(*rb, data)
+                    .unwrap_or_else(|| format!("0x{} (log ID {rb})", hex::encode(data))),
+                Receipt::Return { val, .. } => val.to_string(),
+                Receipt::ReturnData { data, .. } => format!("0x{}", hex::encode(data)),
+                other => format!("{other:?}"),
+            };
+            FormattedReceipt {
+                kind: ReceiptKind::of(receipt),
+                text,
+            }
+        })
+        .collect()
+}
+
+/// Render where the instruction at `pc` is in the script's source as in ` in src/main.sw:3:5`,
+/// given that the instructions of the context it ran in start at `is`.
+///
+/// Instructions of called contracts are not in the script's source map, so nothing is rendered
+/// for them.
+fn source_location(source_map: Option<&SourceMap>, id: &ContractId, pc: u64, is: u64) -> String {
+    if *id != ContractId::zeroed() {
+        return String::new();
+    }
+    let location = source_map
+        .zip(pc.checked_sub(is))
+        .and_then(|(source_map, offset)| source_map.location(usize::try_from(offset / 4).ok()?));
+    match location {
+        Some(location) => format!(
+            " in {}:{}:{}",
+            location.path.display(),
+            location.start.line,
+            location.start.col
+        ),
+        None => String::new(),
+    }
 }
 
 /// Builds and runs script(s). If given path corresponds to a workspace, all runnable members will
@@ -28,15 +216,25 @@ pub struct RanScript {
 /// Upon success, returns the receipts of each script in the order they are executed.
 ///
 /// When running a single script, only that script's receipts are returned.
+///
+/// When a batch file is given with `--batch`, the scripts it lists are run instead, see
+/// [run_batch]. With `--build`, the package is built before any of them.
 pub async fn run(command: cmd::Run) -> Result<Vec<RanScript>> {
+    if let Some(batch_path) = &command.batch {
+        if command.build {
+            built_pkgs(&pkg_dir(&command)?, build_opts_from_cmd(&command))?;
+        }
+        let report = run_batch(&command, batch_path).await?;
+        info!("{}", serde_json::to_string_pretty(&report)?);
+        let failed = report.failed().count();
+        if failed > 0 {
+            bail!("{failed} of {} batch entries failed", report.entries.len());
+        }
+        return Ok(report.into_ran_scripts());
+    }
     let mut receipts = Vec::new();
-    let curr_dir = if let Some(path) = &command.pkg.path {
-        PathBuf::from(path)
-    } else {
-        std::env::current_dir().map_err(|e| anyhow!("{:?}", e))?
-    };
     let build_opts = build_opts_from_cmd(&command);
-    let built_pkgs_with_manifest = built_pkgs(&curr_dir, build_opts)?;
+    let built_pkgs_with_manifest = built_pkgs(&pkg_dir(&command)?, build_opts)?;
     for built in built_pkgs_with_manifest {
         if built
             .descriptor
@@ -52,20 +250,236 @@ pub async fn run(command: cmd::Run) -> Result<Vec<RanScript>> {
     Ok(receipts)
 }
 
+/// The directory of the package to build, given by `--path` or else the current directory.
+fn pkg_dir(command: &cmd::Run) -> Result<PathBuf> {
+    match &command.pkg.path {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => std::env::current_dir().map_err(|e| anyhow!("{:?}", e)),
+    }
+}
+
+/// A script listed in a batch file, see [run_batch].
+///
+/// Relative paths are resolved against the directory containing the batch file.
+#[derive(Debug, Deserialize)]
+pub struct BatchEntry {
+    /// Path to the compiled bytecode of the script.
+    pub script: PathBuf,
+    /// Path to the JSON ABI of the script.
+    pub abi: PathBuf,
+    /// Arguments to pass into the script's main function.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// The result of running one [BatchEntry].
+#[derive(Debug, Serialize)]
+pub struct BatchEntryReport {
+    pub script: PathBuf,
+    #[serde(flatten)]
+    pub outcome: BatchEntryOutcome,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchEntryOutcome {
+    Success {
+        receipts: Vec<Receipt>,
+        /// The decoded output of the script, if its return type could be decoded.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+    },
+    Failure {
+        error: String,
+    },
+}
+
+/// The results of running the entries of a batch file, in the order they were run.
+#[derive(Debug, Serialize)]
+pub struct BatchReport {
+    pub entries: Vec<BatchEntryReport>,
+}
+
+impl BatchReport {
+    /// The reports of the entries which failed to run.
+    pub fn failed(&self) -> impl Iterator<Item = &BatchEntryReport> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, BatchEntryOutcome::Failure { .. }))
+    }
+
+    fn into_ran_scripts(self) -> Vec<RanScript> {
+        self.entries
+            .into_iter()
+            .filter_map(|entry| match entry.outcome {
+                BatchEntryOutcome::Success { receipts, .. } => Some(RanScript {
+                    receipts,
+                    summary: None,
+                }),
+                BatchEntryOutcome::Failure { .. } => None,
+            })
+            .collect()
+    }
+}
+
+/// Read the entries of the JSON batch file at the given path, with their paths resolved against
+/// the directory containing the batch file.
+pub fn read_batch(path: &Path) -> Result<Vec<BatchEntry>> {
+    let batch = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read batch file at '{}'", path.display()))?;
+    let mut entries: Vec<BatchEntry> = serde_json::from_str(&batch)
+        .with_context(|| format!("failed to parse batch file at '{}'", path.display()))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for entry in &mut entries {
+        entry.script = base_dir.join(&entry.script);
+        entry.abi = base_dir.join(&entry.abi);
+    }
+    Ok(entries)
+}
+
+/// Runs each script listed in the batch file at the given path in order, and reports the result
+/// of each of them.
+///
+/// An entry which fails to run is reported without stopping the batch, unless `--fail-fast` is
+/// set, in which case no further entries are run.
+pub async fn run_batch(command: &cmd::Run, batch_path: &Path) -> Result<BatchReport> {
+    let node_url = command
+        .node_url
+        .as_deref()
+        .unwrap_or(crate::default::NODE_URL);
+    let mut entries = Vec::new();
+    for entry in read_batch(batch_path)? {
+        let outcome = match run_batch_entry(command, node_url, &entry).await {
+            Ok(outcome) => outcome,
+            Err(err) => BatchEntryOutcome::Failure {
+                error: format!("{err:#}"),
+            },
+        };
+        let failed = matches!(outcome, BatchEntryOutcome::Failure { .. });
+        entries.push(BatchEntryReport {
+            script: entry.script,
+            outcome,
+        });
+        if failed && command.fail_fast {
+            break;
+        }
+    }
+    Ok(BatchReport { entries })
+}
+
+async fn run_batch_entry(
+    command: &cmd::Run,
+    node_url: &str,
+    entry: &BatchEntry,
+) -> Result<BatchEntryOutcome> {
+    let bytecode = std::fs::read(&entry.script).with_context(|| {
+        format!(
+            "failed to read script bytecode at '{}'",
+            entry.script.display()
+        )
+    })?;
+    let call_handler = ScriptCallHandler::from_json_abi_path_with_options(
+        &entry.abi,
+        command.raw_type_passthrough,
+    )?;
+    let args = entry
+        .args
+        .iter()
+        .map(|arg| arg.as_str())
+        .collect::<Vec<_>>();
+    let script_data = call_handler.encode_arguments(&args)?.resolve(0);
+    let ran = run_script(
+        command,
+        node_url,
+        bytecode,
+        script_data,
+        Some(&call_handler),
+        None,
+    )
+    .await?;
+    let output = call_handler.decode_output(&ran.receipts).ok();
+    Ok(BatchEntryOutcome::Success {
+        receipts: ran.receipts,
+        output,
+    })
+}
+
 pub async fn run_pkg(
     command: &cmd::Run,
     manifest: &PackageManifestFile,
     compiled: &BuiltPackage,
 ) -> Result<RanScript> {
-    let input_data = command.data.as_deref().unwrap_or("");
-    let data = input_data.strip_prefix("0x").unwrap_or(input_data);
-    let script_data = hex::decode(data).expect("Invalid hex");
+    if command.describe_abi {
+        let description = ScriptCallHandler::describe_abi(&json_abi(compiled)?)?;
+        info!("{description}");
+        return Ok(RanScript {
+            receipts: vec![],
+            summary: None,
+        });
+    }
+    if command.show_signature {
+        let signature = main_call_handler(command, compiled)?.signature();
+        info!("{signature}");
+        return Ok(RanScript {
+            receipts: vec![],
+            summary: None,
+        });
+    }
+    let script_data = match (&command.args, &command.args_raw) {
+        (Some(args), _) => {
+            let main_arg_handler = main_call_handler(command, compiled)?;
+            let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>();
+            let unresolved_bytes = main_arg_handler.encode_arguments(&args)?;
+            unresolved_bytes.resolve(0)
+        }
+        (None, Some(raw_args)) => {
+            let main_arg_handler = main_call_handler(command, compiled)?;
+            let unresolved_bytes = main_arg_handler.encode_raw_arguments(raw_args)?;
+            unresolved_bytes.resolve(0)
+        }
+        (None, None) if command.interactive => {
+            let main_arg_handler = main_call_handler(command, compiled)?;
+            let unresolved_bytes = main_arg_handler
+                .prompt_arguments(&mut std::io::stdin().lock(), &mut std::io::stdout())?;
+            unresolved_bytes.resolve(0)
+        }
+        (None, None) => {
+            let input_data = command.data.as_deref().unwrap_or("");
+            let data = input_data.strip_prefix("0x").unwrap_or(input_data);
+            hex::decode(data).expect("Invalid hex")
+        }
+    };
 
     let node_url = command
         .node_url
         .as_deref()
         .or_else(|| manifest.network.as_ref().map(|nw| &nw.url[..]))
         .unwrap_or(crate::default::NODE_URL);
+    let call_handler = main_call_handler(command, compiled).ok();
+    run_script(
+        command,
+        node_url,
+        compiled.bytecode.bytes.clone(),
+        script_data,
+        call_handler.as_ref(),
+        Some(compiled.source_map()),
+    )
+    .await
+}
+
+/// Craft a transaction running the given script bytecode with the given script data, and send it
+/// to the node at `node_url`.
+///
+/// The output of the script is decoded with `call_handler` when given, and its reverts are
+/// located with `source_map` when given.
+async fn run_script(
+    command: &cmd::Run,
+    node_url: &str,
+    bytecode: Vec<u8>,
+    script_data: Vec<u8>,
+    call_handler: Option<&ScriptCallHandler>,
+    source_map: Option<&SourceMap>,
+) -> Result<RanScript> {
     let client = FuelClient::new(node_url)?;
     let contract_ids = command
         .contract
@@ -82,7 +496,7 @@ pub async fn run_pkg(
     } else {
         WalletSelectionMode::ForcWallet
     };
-    let tx = TransactionBuilder::script(compiled.bytecode.bytes.clone(), script_data)
+    let tx = TransactionBuilder::script(bytecode, script_data)
         .gas_limit(command.gas.limit)
         .gas_price(command.gas.price)
         .maturity(command.maturity.maturity.into())
@@ -96,18 +510,64 @@ pub async fn run_pkg(
         .await?;
     if command.dry_run {
         info!("{:?}", tx);
-        Ok(RanScript { receipts: vec![] })
+        Ok(RanScript {
+            receipts: vec![],
+            summary: None,
+        })
     } else {
-        let receipts =
-            try_send_tx(node_url, &tx.into(), command.pretty_print, command.simulate).await?;
-        Ok(RanScript { receipts })
+        let params: ConsensusParameters = client.chain_info().await?.consensus_parameters.into();
+        let tx_id = tx.id(&params);
+        let receipts = try_send_tx(node_url, &tx.into(), command.simulate).await?;
+        // Scripts that revert have no summary, and scripts returning types that can't be
+        // decoded, such as structs, have no output in it.
+        let summary = RunSummary::new(tx_id, &receipts, call_handler);
+        if command.json {
+            info!("{}", format_log_receipts(&receipts, command.pretty_print)?);
+        } else {
+            for receipt in format_receipts(&receipts, call_handler, source_map) {
+                info!("{receipt}");
+            }
+            if let Some(summary) = &summary {
+                info!("{summary}");
+            }
+        }
+        if !command.allow_revert {
+            let failure = receipts
+                .iter()
+                .find(|receipt| matches!(receipt, Receipt::Revert { .. } | Receipt::Panic { .. }));
+            if let Some(failure) = failure {
+                let formatted =
+                    format_receipts(std::slice::from_ref(failure), call_handler, source_map);
+                let kind = match formatted[0].kind {
+                    ReceiptKind::Panic => "panicked",
+                    _ => "reverted",
+                };
+                bail!("the script {kind} with {}", formatted[0].text);
+            }
+        }
+        Ok(RanScript { receipts, summary })
     }
 }
 
+/// Create the handler for the arguments and the output of the script's main function.
+fn main_call_handler(command: &cmd::Run, compiled: &BuiltPackage) -> Result<ScriptCallHandler> {
+    ScriptCallHandler::from_json_abi_str_with_options(
+        &json_abi(compiled)?,
+        command.raw_type_passthrough,
+    )
+}
+
+/// The JSON ABI of the compiled script.
+fn json_abi(compiled: &BuiltPackage) -> Result<String> {
+    let ProgramABI::Fuel(program_abi) = &compiled.program_abi else {
+        bail!("the ABI is only available for scripts targeting the FuelVM");
+    };
+    Ok(serde_json::to_string(program_abi)?)
+}
+
 async fn try_send_tx(
     node_url: &str,
     tx: &Transaction,
-    pretty_print: bool,
     simulate: bool,
 ) -> Result<Vec<fuel_tx::Receipt>> {
     let client = FuelClient::new(node_url)?;
@@ -115,7 +575,7 @@ async fn try_send_tx(
     match client.health().await {
         Ok(_) => timeout(
             Duration::from_millis(TX_SUBMIT_TIMEOUT_MS),
-            send_tx(&client, tx, pretty_print, simulate),
+            send_tx(&client, tx, simulate),
         )
         .await
         .with_context(|| format!("timeout waiting for {:?} to be included in a block", tx))?,
@@ -126,7 +586,6 @@ async fn try_send_tx(
 async fn send_tx(
     client: &FuelClient,
     tx: &Transaction,
-    pretty_print: bool,
     simulate: bool,
 ) -> Result<Vec<fuel_tx::Receipt>> {
     use fuels_accounts::provider::ClientExt;
@@ -143,13 +602,7 @@ async fn send_tx(
         }
     };
 
-    match outputs {
-        Ok(logs) => {
-            info!("{}", format_log_receipts(&logs, pretty_print)?);
-            Ok(logs)
-        }
-        Err(e) => bail!("{e}"),
-    }
+    Ok(outputs?)
 }
 
 fn build_opts_from_cmd(cmd: &cmd::Run) -> pkg::BuildOpts {
@@ -170,6 +623,7 @@ fn build_opts_from_cmd(cmd: &cmd::Run) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            profile_static: cmd.print.profile_static,
             reverse_order: cmd.print.reverse_order,
         },
         minify: pkg::MinifyOpts {
@@ -180,6 +634,7 @@ fn build_opts_from_cmd(cmd: &cmd::Run) -> pkg::BuildOpts {
         build_profile: cmd.build_profile.build_profile.clone(),
         release: cmd.build_profile.release,
         error_on_warnings: cmd.build_profile.error_on_warnings,
+        verify_ir: cmd.build_profile.verify_ir,
         time_phases: cmd.print.time_phases,
         metrics_outfile: cmd.print.metrics_outfile.clone(),
         binary_outfile: cmd.build_output.bin_file.clone(),
@@ -188,3 +643,260 @@ fn build_opts_from_cmd(cmd: &cmd::Run) -> pkg::BuildOpts {
         member_filter: pkg::MemberFilter::only_scripts(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use fuel_tx::Bytes32;
+    use fuel_vm::fuel_asm::{InstructionResult, PanicReason};
+
+    #[tokio::test]
+    #[ignore = "requires a running node at the default node URL"]
+    async fn run_script_returning_vec() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data/script_returning_vec");
+        let command =
+            cmd::Run::parse_from(["forc-run", "--path", path.to_str().unwrap(), "--unsigned"]);
+        let ran = run(command).await.unwrap();
+
+        let abi_path = path.join("out/debug/script_returning_vec-abi.json");
+        let call_handler = ScriptCallHandler::from_json_abi_path(&abi_path).unwrap();
+        let output = call_handler.decode_output(&ran[0].receipts).unwrap();
+        assert_eq!(output, "[1, 2, 3]");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running node at the default node URL"]
+    async fn run_script_prints_summary() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data/script_returning_vec");
+        let command =
+            cmd::Run::parse_from(["forc-run", "--path", path.to_str().unwrap(), "--unsigned"]);
+        let ran = run(command).await.unwrap();
+
+        let summary = ran[0].summary.as_ref().unwrap();
+        assert_ne!(summary.tx_id, Bytes32::zeroed());
+        let text = summary.to_string();
+        assert!(text.contains(&format!("{:#x}", summary.tx_id)));
+        assert!(text.ends_with("returning [1, 2, 3]"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running node at the default node URL"]
+    async fn run_script_fails_on_revert() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data/script_reverting");
+        let path = path.to_str().unwrap();
+        let command = cmd::Run::parse_from(["forc-run", "--path", path, "--unsigned"]);
+        let err = run(command).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("the script reverted with 0x2a at pc"));
+
+        let command =
+            cmd::Run::parse_from(["forc-run", "--path", path, "--unsigned", "--allow-revert"]);
+        let ran = run(command).await.unwrap();
+        assert!(ran[0]
+            .receipts
+            .iter()
+            .any(|receipt| matches!(receipt, Receipt::Revert { ra: 42, .. })));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running node at the default node URL"]
+    async fn run_batch_builds_first() {
+        let data = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data");
+        let path = data.join("script_returning_vec");
+        let batch_path = data.join("batch_build/batch.json");
+        let out_dir = path.join("out");
+        let _ = std::fs::remove_dir_all(&out_dir);
+
+        let command = cmd::Run::parse_from([
+            "forc-run",
+            "--path",
+            path.to_str().unwrap(),
+            "--batch",
+            batch_path.to_str().unwrap(),
+            "--build",
+            "--unsigned",
+        ]);
+        let ran = run(command).await.unwrap();
+        assert!(out_dir.join("debug/script_returning_vec.bin").is_file());
+
+        let abi_path = out_dir.join("debug/script_returning_vec-abi.json");
+        let call_handler = ScriptCallHandler::from_json_abi_path(&abi_path).unwrap();
+        let output = call_handler.decode_output(&ran[0].receipts).unwrap();
+        assert_eq!(output, "[1, 2, 3]");
+    }
+
+    #[tokio::test]
+    async fn run_batch_reports_each_entry() {
+        let batch_path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data/batch/batch.json");
+        let entries = read_batch(&batch_path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].args, ["42", "true"]);
+        assert!(entries[1].script.is_file());
+        assert!(entries[1].abi.is_file());
+
+        // Nothing listens at this node URL, so the second entry fails to run after its arguments
+        // are encoded, while the first entry fails to encode its arguments.
+        let batch = batch_path.to_str().unwrap();
+        let node_url = "http://127.0.0.1:1";
+        let args = [
+            "forc-run",
+            "--batch",
+            batch,
+            "--node-url",
+            node_url,
+            "--unsigned",
+        ];
+        let command = cmd::Run::parse_from(args);
+        let report = run_batch(&command, &batch_path).await.unwrap();
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.failed().count(), 2);
+        let errors = report
+            .entries
+            .iter()
+            .map(|entry| match &entry.outcome {
+                BatchEntryOutcome::Failure { error } => error.clone(),
+                BatchEntryOutcome::Success { .. } => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_ne!(errors[0], errors[1]);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["entries"][0]["status"], "failure");
+        assert_eq!(json["entries"][0]["error"], errors[0].as_str());
+
+        // With `--fail-fast` the batch stops at the first failing entry.
+        let command = cmd::Run::parse_from(args.into_iter().chain(["--fail-fast"]));
+        let report = run_batch(&command, &batch_path).await.unwrap();
+        assert_eq!(report.entries.len(), 1);
+    }
+
+    #[test]
+    fn format_receipts_classifies_and_decodes() {
+        let id = ContractId::zeroed();
+        let receipts = [
+            Receipt::log(id, 1, 0, 0, 0, 0x10, 0),
+            Receipt::log_data(id, 0, 3, 0, Bytes32::zeroed(), vec![0xab, 0xcd], 0x14, 0),
+            Receipt::revert(id, 0xffff_ffff_ffff_0000, 0x18, 0),
+            Receipt::panic(
+                id,
+                InstructionResult::error(PanicReason::MemoryOverflow, 0),
+                0x1c,
+                0,
+            ),
+            Receipt::ret(id, 1, 0x20, 0),
+            Receipt::return_data(id, 0, Bytes32::zeroed(), vec![1, 2], 0x24, 0),
+        ];
+
+        // Without an ABI the logged values are not decoded. Revert codes of the standard library
+        // are named.
+        let formatted = format_receipts(&receipts, None, None);
+        let require_signal = ErrorSignal::try_from_revert_code(0xffff_ffff_ffff_0000).unwrap();
+        let revert = format!("0xffffffffffff0000 at pc 0x18 -- {require_signal}");
+        let expected = [
+            (ReceiptKind::Log, "1 (log ID 0)"),
+            (ReceiptKind::Log, "0xabcd (log ID 3)"),
+            (ReceiptKind::Revert, revert.as_str()),
+            (ReceiptKind::Panic, "MemoryOverflow at pc 0x1c"),
+            (ReceiptKind::Return, "1"),
+            (ReceiptKind::Return, "0x0102"),
+        ];
+        let expected = expected
+            .into_iter()
+            .map(|(kind, text)| FormattedReceipt {
+                kind,
+                text: text.to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(formatted, expected);
+
+        let script_result = Receipt::script_result(fuel_tx::ScriptExecutionResult::Success, 0);
+        let formatted = format_receipts(&[script_result], None, None);
+        assert_eq!(formatted[0].kind, ReceiptKind::Other);
+
+        // Logs are decoded with the logged types of the script's ABI, and shown as hex when
+        // their log ID has no logged type.
+        let json_abi = serde_json::json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {"typeId": 1, "type": "bool", "components": [], "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [],
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [
+                {"logId": 0, "loggedType": {"name": "", "type": 1, "typeArguments": null}}
+            ],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let call_handler = ScriptCallHandler::from_json_abi_str(&json_abi).unwrap();
+        let formatted = format_receipts(&receipts[..2], Some(&call_handler), None);
+        assert_eq!(formatted[0].text, "true (log ID 0)");
+        assert_eq!(formatted[1].text, "0xabcd (log ID 3)");
+    }
+
+    #[test]
+    fn format_receipts_locates_script_reverts() {
+        let source_map: SourceMap = serde_json::from_value(serde_json::json!({
+            "version": SourceMap::VERSION,
+            "dependency_paths": [],
+            "paths": ["src/main.sw"],
+            "map": {
+                "6": {
+                    "path": 0,
+                    "range": {"start": 40, "end": 49},
+                    "start": {"line": 3, "col": 5},
+                    "end": {"line": 3, "col": 14}
+                }
+            }
+        }))
+        .unwrap();
+        let script = ContractId::zeroed();
+        let contract = ContractId::new([1; 32]);
+        let receipts = [
+            Receipt::revert(script, 42, 0x28, 0x10),
+            Receipt::revert(contract, 42, 0x28, 0x10),
+            Receipt::revert(script, 42, 0x2c, 0x10),
+        ];
+        let formatted = format_receipts(&receipts, None, Some(&source_map));
+        assert_eq!(formatted[0].text, "0x2a at pc 0x28 in src/main.sw:3:5");
+        // Contracts and instructions which aren't in the source map are not located.
+        assert_eq!(formatted[1].text, "0x2a at pc 0x28");
+        assert_eq!(formatted[2].text, "0x2a at pc 0x2c");
+    }
+
+    #[test]
+    fn run_summary_shows_tx_id_gas_and_output() {
+        let id = ContractId::zeroed();
+        let tx_id = Bytes32::new([0x11; 32]);
+        let receipts = [
+            Receipt::ret(id, 1, 0x20, 0),
+            Receipt::script_result(ScriptExecutionResult::Success, 42),
+        ];
+        let summary = RunSummary::new(tx_id, &receipts, None).unwrap();
+        assert_eq!(
+            summary.to_string(),
+            format!("Transaction 0x{} succeeded using 42 gas", "11".repeat(32))
+        );
+
+        let summary = RunSummary {
+            output: Some("1".to_string()),
+            ..summary
+        };
+        assert!(summary.to_string().ends_with("using 42 gas, returning 1"));
+
+        // Scripts which revert have no summary.
+        let receipts = [
+            Receipt::revert(id, 0, 0x18, 0),
+            Receipt::script_result(ScriptExecutionResult::Revert, 42),
+        ];
+        assert_eq!(RunSummary::new(tx_id, &receipts, None), None);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{
+    cmd,
+    util::{encode::override_configurables, pkg::built_pkgs},
+};
+use anyhow::{bail, Context, Result};
+use forc_pkg::{self as pkg, manifest::ManifestFile, BuiltPackage};
+use forc_util::DEFAULT_OUTPUT_DIRECTORY;
+use fuel_core_client::client::FuelClient;
+use fuel_tx::{Bytes32, Contract, ContractId};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use sway_core::{asm_generation::ProgramABI, language::parsed::TreeType, BuildTarget};
+use sway_utils::constants;
+use tracing::info;
+use walkdir::WalkDir;
+
+/// The outcome of comparing the bytecode built from the local source with the deployed bytecode.
+#[derive(Debug)]
+pub struct Verification {
+    pub local_root: Bytes32,
+    pub deployed_root: Bytes32,
+    pub local_size: usize,
+    pub deployed_size: usize,
+    /// The offset of the first byte at which the bytecodes differ, if they differ at all.
+    pub first_difference: Option<usize>,
+}
+
+impl Verification {
+    fn new(local: &[u8], deployed: &[u8]) -> Self {
+        let first_difference = local
+            .iter()
+            .zip(deployed)
+            .position(|(local, deployed)| local != deployed)
+            .or_else(|| (local.len() != deployed.len()).then_some(local.len().min(deployed.len())));
+        Self {
+            local_root: Contract::root_from_code(local),
+            deployed_root: Contract::root_from_code(deployed),
+            local_size: local.len(),
+            deployed_size: deployed.len(),
+            first_difference,
+        }
+    }
+
+    /// Returns `true` if the deployed contract has the code root of the local bytecode.
+    pub fn is_match(&self) -> bool {
+        self.local_root == self.deployed_root
+    }
+}
+
+/// Builds the contract at the given path and compares its bytecode with the bytecode of the
+/// contract deployed at the given ID, reporting whether they match.
+///
+/// The build always uses the dependencies pinned by the `Forc.lock`, and happens in a copy of the
+/// source at a fixed path, so that the same source always produces the same bytecode.
+pub async fn verify(command: cmd::Verify) -> Result<Verification> {
+    let compiled = build_contract(&command)?;
+    let manifest = &compiled.descriptor.manifest_file;
+
+    let mut bytecode = compiled.bytecode.bytes.clone();
+    if !command.configurables.is_empty() {
+        let ProgramABI::Fuel(program_abi) = &compiled.program_abi else {
+            bail!("configurables can only be given for contracts targeting the FuelVM");
+        };
+        let abi = serde_json::to_string(program_abi)?;
+        override_configurables(&abi, &mut bytecode, &command.configurables)?;
+    }
+
+    let node_url = command
+        .node_url
+        .as_deref()
+        .or_else(|| manifest.network.as_ref().map(|nw| &nw.url[..]))
+        .unwrap_or(crate::default::NODE_URL);
+    let contract_id = command.contract_id;
+    let deployed_bytecode = deployed_bytecode(node_url, &contract_id).await?;
+
+    let verification = Verification::new(&bytecode, &deployed_bytecode);
+    let pkg_name = manifest.project_name();
+    info!(
+        "Local {pkg_name}: {} bytes, code root 0x{}",
+        verification.local_size, verification.local_root
+    );
+    info!(
+        "Contract 0x{contract_id}: {} bytes, code root 0x{}",
+        verification.deployed_size, verification.deployed_root
+    );
+    // The bytecode doesn't record the compiler it was built with, so the best we can do is to
+    // name the one used here.
+    info!("Local forc version: {}", env!("CARGO_PKG_VERSION"));
+    if verification.is_match() {
+        info!("\nContract 0x{contract_id} was built from {pkg_name}.");
+    } else {
+        info!("\nContract 0x{contract_id} does NOT match {pkg_name}.");
+        if let Some(offset) = verification.first_difference {
+            info!("The bytecodes first differ at offset {offset}.");
+        }
+        info!(
+            "Make sure to use the same compiler version, build profile and configurables the \
+            contract was deployed with."
+        );
+    }
+    Ok(verification)
+}
+
+/// Builds the contract in the package at the path of `command`, or in the current directory, in a
+/// copy of the source at a fixed path, see [copy_to_build_dir].
+fn build_contract(command: &cmd::Verify) -> Result<Arc<BuiltPackage>> {
+    let curr_dir = if let Some(ref path) = command.pkg.path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir()?
+    };
+    let pkg_dir = copy_to_build_dir(&curr_dir)?;
+    let mut built_pkgs = built_pkgs(&pkg_dir, build_opts_from_cmd(command, &pkg_dir))?;
+    let compiled = match built_pkgs.pop() {
+        Some(compiled) if built_pkgs.is_empty() => compiled,
+        _ => bail!("`forc verify` verifies a single contract, not a workspace"),
+    };
+    compiled
+        .descriptor
+        .manifest_file
+        .check_program_type(vec![TreeType::Contract])?;
+    Ok(compiled)
+}
+
+/// Copies the package in `dir`, or the whole workspace if the package is a workspace member, to
+/// a fixed directory, and returns the directory of the package in the copy.
+///
+/// Dependencies given by a path outside of the copied directory are made to point to the
+/// original dependency with an absolute path. The `out` directories aren't copied.
+fn copy_to_build_dir(dir: &Path) -> Result<PathBuf> {
+    let manifest = ManifestFile::from_dir(dir)?;
+    let root = match &manifest {
+        ManifestFile::Package(pkg_manifest) => match pkg_manifest.workspace()? {
+            Some(workspace) => workspace.dir().to_path_buf(),
+            None => pkg_manifest.dir().to_path_buf(),
+        },
+        ManifestFile::Workspace(workspace) => workspace.dir().to_path_buf(),
+    }
+    .canonicalize()?;
+    let pkg_dir = manifest.dir().canonicalize()?;
+
+    let root_name = root.file_name().unwrap_or_default();
+    let build_root = std::env::temp_dir().join(BUILD_DIR_NAME).join(root_name);
+    if build_root.exists() {
+        std::fs::remove_dir_all(&build_root)?;
+    }
+    let entries = WalkDir::new(&root)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != DEFAULT_OUTPUT_DIRECTORY);
+    for entry in entries {
+        let entry = entry?;
+        let dest = build_root.join(entry.path().strip_prefix(&root)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest)?;
+        } else if entry.file_name() == constants::MANIFEST_FILE_NAME {
+            let manifest_dir = entry.path().parent().unwrap_or(&root);
+            let manifest = std::fs::read_to_string(entry.path())?;
+            let manifest = point_outside_paths_to_original(&manifest, manifest_dir, &root)
+                .with_context(|| format!("failed to copy {}", entry.path().display()))?;
+            std::fs::write(&dest, manifest)?;
+        } else {
+            std::fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(build_root.join(pkg_dir.strip_prefix(&root)?))
+}
+
+/// Replaces the relative paths of the dependencies and patches in `manifest`, the contents of a
+/// `Forc.toml` in `manifest_dir`, which lead outside of `root` with absolute paths.
+///
+/// The manifest is returned unchanged if no path is replaced.
+fn point_outside_paths_to_original(
+    manifest: &str,
+    manifest_dir: &Path,
+    root: &Path,
+) -> Result<String> {
+    let mut value: toml::Value = toml::from_str(manifest)?;
+    let mut dependencies = vec![];
+    if let Some(table) = value.as_table_mut() {
+        for (key, item) in table.iter_mut() {
+            match (key.as_str(), item.as_table_mut()) {
+                ("dependencies" | "contract-dependencies", Some(deps)) => {
+                    dependencies.extend(deps.iter_mut().map(|(_, dep)| dep))
+                }
+                ("patch", Some(patches)) => dependencies.extend(
+                    patches
+                        .iter_mut()
+                        .filter_map(|(_, deps)| deps.as_table_mut())
+                        .flat_map(|deps| deps.iter_mut().map(|(_, dep)| dep)),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    let mut replaced = false;
+    for path in dependencies
+        .into_iter()
+        .filter_map(|dep| dep.get_mut("path"))
+    {
+        let Some(dep_path) = path.as_str().map(Path::new) else {
+            continue;
+        };
+        if dep_path.is_absolute() {
+            continue;
+        }
+        let Ok(original) = manifest_dir.join(dep_path).canonicalize() else {
+            continue;
+        };
+        if !original.starts_with(root) {
+            *path = toml::Value::String(original.to_string_lossy().into_owned());
+            replaced = true;
+        }
+    }
+    if replaced {
+        Ok(toml::to_string(&value)?)
+    } else {
+        Ok(manifest.to_string())
+    }
+}
+
+/// Fetch the bytecode of the contract deployed at `contract_id` from the node at `node_url`.
+async fn deployed_bytecode(node_url: &str, contract_id: &ContractId) -> Result<Vec<u8>> {
+    let client = FuelClient::new(node_url)?;
+    let Some(deployed) = client.contract(&contract_id.to_string()).await? else {
+        bail!("no contract with ID 0x{contract_id} is deployed on {node_url}");
+    };
+    Ok(deployed.bytecode.into())
+}
+
+/// The directory in the temporary directory in which contracts are built, see
+/// [copy_to_build_dir].
+const BUILD_DIR_NAME: &str = "forc-verify";
+
+/// Build options for the contract copied to `pkg_dir` by [copy_to_build_dir].
+fn build_opts_from_cmd(cmd: &cmd::Verify, pkg_dir: &Path) -> pkg::BuildOpts {
+    pkg::BuildOpts {
+        pkg: pkg::PkgOpts {
+            path: Some(pkg_dir.to_string_lossy().into_owned()),
+            offline: cmd.pkg.offline,
+            terse: cmd.pkg.terse,
+            // Building with other dependencies than the pinned ones could change the bytecode.
+            locked: true,
+            output_directory: cmd.pkg.output_directory.clone(),
+            json_abi_with_callpaths: cmd.pkg.json_abi_with_callpaths,
+            ipfs_node: cmd.pkg.ipfs_node.clone().unwrap_or_default(),
+        },
+        build_profile: cmd.build_profile.build_profile.clone(),
+        release: cmd.build_profile.release,
+        error_on_warnings: cmd.build_profile.error_on_warnings,
+        verify_ir: cmd.build_profile.verify_ir,
+        build_target: BuildTarget::default(),
+        member_filter: pkg::MemberFilter::only_contracts(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::op::deploy;
+    use clap::Parser;
+
+    #[test]
+    fn verification_finds_first_difference() {
+        let verification = Verification::new(&[1, 2, 3, 4], &[1, 2, 3, 4]);
+        assert!(verification.is_match());
+        assert_eq!(verification.first_difference, None);
+
+        let verification = Verification::new(&[1, 2, 3, 4], &[1, 2, 5, 4]);
+        assert!(!verification.is_match());
+        assert_eq!(verification.first_difference, Some(2));
+
+        // A bytecode which is a prefix of the other differs where it ends.
+        let verification = Verification::new(&[1, 2, 3, 4], &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(!verification.is_match());
+        assert_eq!(verification.first_difference, Some(4));
+        assert_eq!(
+            (verification.local_size, verification.deployed_size),
+            (4, 8)
+        );
+    }
+
+    #[tokio::test]
+    async fn deployed_bytecode_reports_unreachable_node() {
+        // Nothing listens on port 1, so fetching fails before any contract is looked up.
+        let node_url = "http://127.0.0.1:1/graphql";
+        assert!(deployed_bytecode(node_url, &ContractId::default())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn build_does_not_depend_on_source_dir() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data/verifiable_contract");
+        let code_root_from = |dir: &Path| {
+            std::fs::create_dir_all(dir.join("src")).unwrap();
+            for file in ["Forc.toml", "Forc.lock", "src/main.sw"] {
+                std::fs::copy(path.join(file), dir.join(file)).unwrap();
+            }
+            let command = cmd::Verify::parse_from([
+                "forc-verify",
+                "--path",
+                dir.to_str().unwrap(),
+                "--contract-id",
+                &ContractId::default().to_string(),
+            ]);
+            let compiled = build_contract(&command).unwrap();
+            Contract::root_from_code(&compiled.bytecode.bytes)
+        };
+
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let nested_dir = second.path().join("nested").join("verifiable_contract");
+        assert_eq!(
+            code_root_from(&first.path().join("verifiable_contract")),
+            code_root_from(&nested_dir)
+        );
+    }
+
+    #[test]
+    fn outside_paths_point_to_original() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        for dir in ["workspace/member", "workspace/inside", "outside"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let manifest = r#"
+            [dependencies]
+            inside = { path = "../inside" }
+            outside = { path = "../../outside" }
+        "#;
+        let manifest = point_outside_paths_to_original(
+            manifest,
+            &root.join("workspace/member"),
+            &root.join("workspace"),
+        )
+        .unwrap();
+        let manifest: toml::Value = toml::from_str(&manifest).unwrap();
+        let path_of = |dep: &str| {
+            manifest["dependencies"][dep]["path"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(path_of("inside"), "../inside");
+        assert_eq!(path_of("outside"), root.join("outside").to_str().unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running node at the default node URL"]
+    async fn verify_deployed_contract() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/data/verifiable_contract");
+        let path = path.to_str().unwrap();
+        let command = cmd::Deploy::parse_from(["forc-deploy", "--path", path, "--unsigned"]);
+        let contract_id = deploy(command).await.unwrap()[0].id.to_string();
+
+        let command =
+            cmd::Verify::parse_from(["forc-verify", "--path", path, "--contract-id", &contract_id]);
+        assert!(verify(command).await.unwrap().is_match());
+
+        // Changing a single line of the source changes the bytecode.
+        let changed = tempfile::tempdir().unwrap();
+        std::fs::create_dir(changed.path().join("src")).unwrap();
+        for file in ["Forc.toml", "Forc.lock"] {
+            std::fs::copy(PathBuf::from(path).join(file), changed.path().join(file)).unwrap();
+        }
+        let source = std::fs::read_to_string(PathBuf::from(path).join("src/main.sw")).unwrap();
+        std::fs::write(
+            changed.path().join("src/main.sw"),
+            source.replace("42", "43"),
+        )
+        .unwrap();
+        let command = cmd::Verify::parse_from([
+            "forc-verify",
+            "--path",
+            changed.path().to_str().unwrap(),
+            "--contract-id",
+            &contract_id,
+        ]);
+        let verification = verify(command).await.unwrap();
+        assert!(!verification.is_match());
+        assert!(verification.first_difference.is_some());
+    }
+}
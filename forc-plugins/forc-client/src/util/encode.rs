@@ -1,7 +1,24 @@
-use std::str::FromStr;
+use std::{
+    fmt,
+    io::{BufRead, Write},
+    path::Path,
+    str::FromStr,
+};
 
-use fuel_abi_types::abi::full_program::FullTypeApplication;
+use anyhow::Context;
+use fuel_abi_types::abi::full_program::{FullProgramABI, FullTypeApplication};
+use fuel_tx::{ContractId, Receipt};
+use fuels_core::{
+    codec::{ABIDecoder, ABIEncoder},
+    types::{
+        bech32::{Bech32Address, FUEL_BECH32_HRP},
+        param_types::{ParamType, ReturnLocation},
+        unresolved_bytes::UnresolvedBytes,
+        StringToken,
+    },
+};
 use serde::{Deserialize, Deserializer, Serialize};
+use sway_core::language::Literal;
 
 /// A wrapper around fuels_core::types::Token, which enables serde de/serialization.
 #[derive(Debug, PartialEq)]
@@ -42,14 +59,194 @@ impl<'de> Deserialize<'de> for Token {
     }
 }
 
+/// The type of a script's main function argument.
+///
+/// More variants are added as new argument types become supported, so code outside of this
+/// crate should branch on the helper predicates such as [Type::is_integer] rather than
+/// matching exhaustively.
 #[derive(Debug, PartialEq, Eq)]
-pub(crate) enum Type {
+#[non_exhaustive]
+pub enum Type {
     Unit,
     U8,
     U16,
     U32,
     U64,
     Bool,
+    B256,
+    /// A `raw_ptr`, encoded as a word holding the address it points to.
+    RawPtr,
+    /// A string of the given length in bytes, as in `str[11]`.
+    String(usize),
+    /// An array of the element type with the given length.
+    Array(Box<Type>, usize),
+    /// A `Vec` of the element type. Only supported as the output type of the main function.
+    Vector(Box<Type>),
+    /// A `Bytes` value. Only supported as the output type of the main function.
+    Bytes,
+    /// A struct with the given name and the names and types of its fields, in declaration order.
+    Struct(String, Vec<(String, Type)>),
+    /// An argument type which isn't supported otherwise, with its JSON ABI type field. Values are
+    /// given as hex and passed through verbatim, see
+    /// [ScriptCallHandler::from_json_abi_str_with_options]. Also the output type of a main
+    /// function whose output can't be decoded.
+    Raw(String),
+}
+
+impl Type {
+    /// Returns `true` for the unsigned integer types.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Type::U8 | Type::U16 | Type::U32 | Type::U64)
+    }
+
+    /// Returns `true` for types made up of other types, such as structs, enums, tuples and arrays.
+    pub fn is_composite(&self) -> bool {
+        match self {
+            Type::Unit
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::Bool
+            | Type::B256
+            | Type::RawPtr
+            | Type::String(_)
+            | Type::Bytes
+            | Type::Raw(_) => false,
+            Type::Array(..) | Type::Vector(_) | Type::Struct(..) => true,
+        }
+    }
+
+    /// Returns the element type of arrays and vectors, or `None` for any other type.
+    pub fn element_type(&self) -> Option<&Type> {
+        match self {
+            Type::Array(element_type, _) | Type::Vector(element_type) => Some(element_type),
+            _ => None,
+        }
+    }
+
+    /// Returns the type field that the JSON ABI uses for this type.
+    ///
+    /// Unlike the [fmt::Display] representation, element types are not part of the type field
+    /// of arrays and vectors, which are described by the components and type arguments of the
+    /// type declaration instead, as in `[_; 3]` and `struct Vec`.
+    pub fn abi_string(&self) -> String {
+        match self {
+            Type::Array(_, len) => format!("[_; {len}]"),
+            Type::Vector(_) => "struct Vec".to_string(),
+            Type::Bytes => "struct Bytes".to_string(),
+            Type::RawPtr => "raw untyped ptr".to_string(),
+            Type::Struct(name, _) => format!("struct {name}"),
+            Type::Raw(type_field) => type_field.clone(),
+            Type::Unit
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::Bool
+            | Type::B256
+            | Type::String(_) => self.to_string(),
+        }
+    }
+
+    /// Returns the type the SDK uses to decode values of this type.
+    fn param_type(&self) -> ParamType {
+        match self {
+            Type::Unit => ParamType::Unit,
+            Type::U8 => ParamType::U8,
+            Type::U16 => ParamType::U16,
+            Type::U32 => ParamType::U32,
+            Type::U64 => ParamType::U64,
+            Type::Bool => ParamType::Bool,
+            Type::B256 => ParamType::B256,
+            Type::RawPtr => ParamType::U64,
+            Type::String(len) => ParamType::String(*len),
+            Type::Array(element_type, len) => {
+                ParamType::Array(Box::new(element_type.param_type()), *len)
+            }
+            Type::Vector(element_type) => ParamType::Vector(Box::new(element_type.param_type())),
+            Type::Bytes => ParamType::Bytes,
+            Type::Struct(_, fields) => ParamType::Struct {
+                fields: fields
+                    .iter()
+                    .map(|(_, field_type)| field_type.param_type())
+                    .collect(),
+                generics: vec![],
+            },
+            Type::Raw(_) => unreachable!("values of raw types are never decoded"),
+        }
+    }
+
+    /// Resolve the type of a type application from the ABI, including the element type and the
+    /// length of arrays.
+    fn from_type_application(value: &FullTypeApplication) -> anyhow::Result<Self> {
+        let type_field_string = &value.type_decl.type_field;
+        match type_field_string.as_str() {
+            "struct Vec" => {
+                let element = value
+                    .type_arguments
+                    .first()
+                    .ok_or_else(|| anyhow::anyhow!("vector type is missing its element type"))?;
+                let element_type = Type::from_type_application(element)?;
+                return Ok(Type::Vector(Box::new(element_type)));
+            }
+            "struct Bytes" => return Ok(Type::Bytes),
+            _ => {}
+        }
+        if let Some(name) = type_field_string.strip_prefix("struct ") {
+            let fields = value
+                .type_decl
+                .components
+                .iter()
+                .map(|field| Ok((field.name.clone(), Type::from_type_application(field)?)))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            return Ok(Type::Struct(name.to_string(), fields));
+        }
+        if !type_field_string.starts_with('[') {
+            return Type::from_str(type_field_string);
+        }
+        let element = value
+            .type_decl
+            .components
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("array type is missing its element type"))?;
+        let element_type = Type::from_type_application(element)?;
+        let len = Type::array_len(value)?;
+        Ok(Type::Array(Box::new(element_type), len))
+    }
+
+    /// Read the length of the array type application `value`.
+    ///
+    /// The length is either a literal in the type field, as in `[_; 3]`, or the name of a generic
+    /// parameter of the array declaration, as in `[_; N]`, that is resolved by the type argument
+    /// given for that parameter.
+    fn array_len(value: &FullTypeApplication) -> anyhow::Result<usize> {
+        let type_field_string = &value.type_decl.type_field;
+        let len_str = type_field_string
+            .strip_suffix(']')
+            .and_then(|s| s.rsplit_once(';'))
+            .map(|(_, len)| len.trim())
+            .ok_or_else(|| anyhow::anyhow!("invalid array type {type_field_string}"))?;
+        if let Ok(len) = len_str.parse::<usize>() {
+            return Ok(len);
+        }
+
+        let generic_field = format!("generic {len_str}");
+        let type_argument = value
+            .type_decl
+            .type_parameters
+            .iter()
+            .position(|param| param.type_field == generic_field)
+            .and_then(|index| value.type_arguments.get(index))
+            .ok_or_else(|| {
+                anyhow::anyhow!("array length {len_str} is not resolved by a type argument")
+            })?;
+        let len_arg = &type_argument.type_decl.type_field;
+        len_arg
+            .trim()
+            .parse::<usize>()
+            .with_context(|| format!("array length {len_str} is resolved to {len_arg}"))
+    }
 }
 
 impl TryFrom<&FullTypeApplication> for Type {
@@ -57,7 +254,43 @@ impl TryFrom<&FullTypeApplication> for Type {
 
     fn try_from(value: &FullTypeApplication) -> Result<Self, Self::Error> {
         let type_field_string = &value.type_decl.type_field;
-        Type::from_str(type_field_string)
+        Type::from_type_application(value).with_context(|| {
+            format!(
+                "argument '{}' has unsupported type '{}'",
+                value.name, type_field_string
+            )
+        })
+    }
+}
+
+impl TryFrom<&Literal> for Token {
+    type Error = anyhow::Error;
+
+    /// Convert a literal to the token of the corresponding type, as given by
+    /// [Token::from_type_and_value].
+    ///
+    /// Untyped numeric literals are rejected, since the width of their token can't be known.
+    fn try_from(literal: &Literal) -> anyhow::Result<Self> {
+        let (arg_type, value) = match literal {
+            Literal::U8(value) => (Type::U8, value.to_string()),
+            Literal::U16(value) => (Type::U16, value.to_string()),
+            Literal::U32(value) => (Type::U32, value.to_string()),
+            Literal::U64(value) => (Type::U64, value.to_string()),
+            Literal::Boolean(value) => (Type::Bool, value.to_string()),
+            Literal::B256(bytes) => (Type::B256, format!("0x{}", hex::encode(bytes))),
+            Literal::String(span) => {
+                // The value is used as is, rather than unquoted as command line values are.
+                let value = span.as_str();
+                return Ok(Token(fuels_core::types::Token::String(StringToken::new(
+                    value.to_string(),
+                    value.len(),
+                ))));
+            }
+            Literal::Numeric(_) => {
+                anyhow::bail!("numeric literal `{literal}` has no type to convert it to a token")
+            }
+        };
+        Token::from_type_and_value(&arg_type, &value)
     }
 }
 
@@ -66,34 +299,390 @@ impl Token {
     ///
     /// Generates an error if there is a mismatch between the type information and the provided
     /// value for that type.
-    #[allow(dead_code)]
     pub(crate) fn from_type_and_value(arg_type: &Type, value: &str) -> anyhow::Result<Self> {
         match arg_type {
-            Type::Unit => Ok(Token(fuels_core::types::Token::Unit)),
+            // A unit value takes up no bytes, but is still given as a value so that it keeps its
+            // position among the fields of a struct.
+            Type::Unit => match value.trim() {
+                "" | "()" => Ok(Token(fuels_core::types::Token::Unit)),
+                _ => anyhow::bail!("unit value must be empty or `()`, found `{value}`"),
+            },
             Type::U8 => {
-                let u8_val = value.parse::<u8>()?;
+                let u8_val = parse_integer::<u8>(value)?;
                 Ok(Token(fuels_core::types::Token::U8(u8_val)))
             }
             Type::U16 => {
-                let u16_val = value.parse::<u16>()?;
+                let u16_val = parse_integer::<u16>(value)?;
                 Ok(Token(fuels_core::types::Token::U16(u16_val)))
             }
             Type::U32 => {
-                let u32_val = value.parse::<u32>()?;
+                let u32_val = parse_integer::<u32>(value)?;
                 Ok(Token(fuels_core::types::Token::U32(u32_val)))
             }
             Type::U64 => {
-                let u64_val = value.parse::<u64>()?;
+                let u64_val = parse_integer::<u64>(value)?;
                 Ok(Token(fuels_core::types::Token::U64(u64_val)))
             }
             Type::Bool => {
                 let bool_val = value.parse::<bool>()?;
                 Ok(Token(fuels_core::types::Token::Bool(bool_val)))
             }
+            Type::B256 if value.starts_with(&format!("{FUEL_BECH32_HRP}1")) => {
+                let address = Bech32Address::from_str(value)
+                    .map_err(|e| anyhow::anyhow!("invalid bech32 address `{value}`: {e}"))?;
+                Ok(Token(fuels_core::types::Token::B256(*address.hash())))
+            }
+            Type::B256 if value.trim_start().starts_with('[') => {
+                // Big-endian bytes, as in `[0, 1, ..., 31]`.
+                let bytes = split_array_value(value)?
+                    .into_iter()
+                    .map(parse_integer::<u8>)
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let b256_val: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+                    anyhow::anyhow!("b256 value must have 32 bytes, found {}", bytes.len())
+                })?;
+                Ok(Token(fuels_core::types::Token::B256(b256_val)))
+            }
+            Type::B256 => {
+                let bytes = hex::decode(value.trim_start_matches("0x"))?;
+                let b256_val: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+                    anyhow::anyhow!("b256 value must be 32 bytes long, found {}", bytes.len())
+                })?;
+                Ok(Token(fuels_core::types::Token::B256(b256_val)))
+            }
+            Type::RawPtr => {
+                let ptr_val = parse_integer::<u64>(value)
+                    .map_err(|e| anyhow::anyhow!("invalid raw_ptr value `{value}`: {e}"))?;
+                Ok(Token(fuels_core::types::Token::U64(ptr_val)))
+            }
+            Type::String(len) => {
+                // The shell removes the quotes around arguments with spaces, but quotes that are
+                // part of the value, as in array elements, are removed here.
+                let string_val = unquote(value);
+                if string_val.len() != *len {
+                    anyhow::bail!(
+                        "string value must be {len} bytes long, found {}",
+                        string_val.len()
+                    );
+                }
+                Ok(Token(fuels_core::types::Token::String(StringToken::new(
+                    string_val.to_string(),
+                    *len,
+                ))))
+            }
+            Type::Array(element_type, len) => {
+                let elements = split_array_value(value)?;
+                if elements.len() != *len {
+                    anyhow::bail!(
+                        "array value must have {len} elements, found {}",
+                        elements.len()
+                    );
+                }
+                let tokens = elements
+                    .into_iter()
+                    .map(|element| Token::from_type_and_value(element_type, element).map(|t| t.0))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(Token(fuels_core::types::Token::Array(tokens)))
+            }
+            Type::Struct(name, fields) => {
+                let field_values = split_struct_value(name, fields, value)?;
+                let tokens = fields
+                    .iter()
+                    .zip(field_values)
+                    .map(|((_, field_type), field_value)| {
+                        Token::from_type_and_value(field_type, field_value).map(|t| t.0)
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(Token(fuels_core::types::Token::Struct(tokens)))
+            }
+            Type::Vector(_) | Type::Bytes => {
+                anyhow::bail!("{arg_type} arguments are not supported, only return values")
+            }
+            Type::Raw(type_field) => {
+                let bytes = hex::decode(value.trim().trim_start_matches("0x")).map_err(|e| {
+                    anyhow::anyhow!("invalid hex value for raw `{type_field}`: {e}")
+                })?;
+                // Every encoded value takes up a whole number of words.
+                if bytes.len() % 8 != 0 {
+                    anyhow::bail!(
+                        "raw `{type_field}` value must be a whole number of 8 byte words, found {} bytes",
+                        bytes.len()
+                    );
+                }
+                let words = bytes
+                    .chunks(8)
+                    .map(|word| {
+                        let word = word.try_into().expect("chunks are 8 bytes long");
+                        fuels_core::types::Token::U64(u64::from_be_bytes(word))
+                    })
+                    .collect();
+                Ok(Token(fuels_core::types::Token::Tuple(words)))
+            }
         }
     }
 }
 
+/// Render a decoded value the way values are given as arguments, as in `[1, 2, 3]`.
+fn format_token(token: &fuels_core::types::Token) -> String {
+    use fuels_core::types::Token;
+    let format_list = |tokens: &[Token]| {
+        tokens
+            .iter()
+            .map(format_token)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match token {
+        Token::Unit => "()".to_string(),
+        Token::U8(value) => value.to_string(),
+        Token::U16(value) => value.to_string(),
+        Token::U32(value) => value.to_string(),
+        Token::U64(value) => value.to_string(),
+        Token::U128(value) => value.to_string(),
+        Token::Bool(value) => value.to_string(),
+        Token::B256(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::String(string) => match string.get_encodable_str() {
+            Ok(string) => format!("\"{string}\""),
+            Err(_) => format!("{string:?}"),
+        },
+        Token::Array(tokens) | Token::Vector(tokens) => format!("[{}]", format_list(tokens)),
+        Token::Tuple(tokens) => format!("({})", format_list(tokens)),
+        Token::Struct(tokens) => format!("{{{}}}", format_list(tokens)),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Read the contents of the heap buffer of a returned `Vec` or `Bytes`, whose pointer, capacity and
+/// length are the words in `header`.
+fn heap_buffer(
+    param_type: &ParamType,
+    header: &[u8],
+    receipts: &[Receipt],
+) -> anyhow::Result<Vec<u8>> {
+    let word = |index: usize| {
+        let bytes = header.get(index * 8..(index + 1) * 8)?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    };
+    let (Some(ptr), Some(len)) = (word(0), word(2)) else {
+        anyhow::bail!(
+            "expected the pointer, capacity and length of a heap buffer, found {} bytes",
+            header.len()
+        );
+    };
+    if len == 0 {
+        return Ok(vec![]);
+    }
+    let element_size = param_type
+        .heap_inner_element_size()
+        .expect("heap types have an element size");
+    let size = usize::try_from(len)
+        .ok()
+        .and_then(|len| len.checked_mul(element_size))
+        .ok_or_else(|| anyhow::anyhow!("heap buffer length {len} is too large"))?;
+    let buffer = receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ReturnData {
+                ptr: data_ptr,
+                data,
+                ..
+            } if *data_ptr == ptr => Some(data),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("no receipt holds the heap buffer at {ptr:#x}"))?;
+    buffer.get(..size).map(<[u8]>::to_vec).ok_or_else(|| {
+        anyhow::anyhow!(
+            "heap buffer at {ptr:#x} must be {size} bytes long, found {}",
+            buffer.len()
+        )
+    })
+}
+
+/// Remove one pair of double quotes around `value`, if there are any.
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Parse an integer value given either in decimal or, with a `0x` prefix, in hexadecimal.
+fn parse_integer<T>(value: &str) -> anyhow::Result<T>
+where
+    T: TryFrom<u64> + FromStr<Err = std::num::ParseIntError>,
+{
+    let value = value.trim();
+    match value.strip_prefix("0x") {
+        Some(hex) => {
+            let int_val = u64::from_str_radix(hex, 16)
+                .map_err(|e| anyhow::anyhow!("invalid hex integer `{value}`: {e}"))?;
+            T::try_from(int_val)
+                .map_err(|_| anyhow::anyhow!("hex integer `{value}` is out of range for its type"))
+        }
+        None => Ok(value.parse::<T>()?),
+    }
+}
+
+/// Split the arguments given in a single string such as `2 true` on whitespace, keeping quoted
+/// strings, arrays and structs whole.
+fn split_raw_arguments(raw_args: &str) -> anyhow::Result<Vec<&str>> {
+    let mut args = vec![];
+    let mut depth = 0;
+    let mut start = None;
+    let mut in_string = false;
+    for (i, c) in raw_args.char_indices() {
+        match c {
+            _ if c.is_whitespace() && !in_string && depth == 0 => {
+                if let Some(start) = start.take() {
+                    args.push(&raw_args[start..i]);
+                }
+                continue;
+            }
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '[' | '{' | '(' => depth += 1,
+            ']' | '}' | ')' => depth -= 1,
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if in_string {
+        anyhow::bail!("unterminated string in arguments: {raw_args}");
+    }
+    if let Some(start) = start {
+        args.push(&raw_args[start..]);
+    }
+    Ok(args)
+}
+
+/// Split an array value such as `[1, 2, 3]` into its elements, keeping nested arrays, structs
+/// and quoted strings whole.
+fn split_array_value(value: &str) -> anyhow::Result<Vec<&str>> {
+    let inner = value
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| anyhow::anyhow!("array value must be enclosed in brackets: {value}"))?;
+    split_elements(inner)
+}
+
+/// Split the value of struct `name` into the values of its `fields`, in declaration order.
+///
+/// The fields are given either positionally, as in `(1, true)` or `{1, true}`, or by name as in
+/// the JSON object `{"x": 1, "y": true}`. The value of a struct without fields is `{}` or `()`.
+fn split_struct_value<'a>(
+    name: &str,
+    fields: &[(String, Type)],
+    value: &'a str,
+) -> anyhow::Result<Vec<&'a str>> {
+    let trimmed = value.trim();
+    let elements = match trimmed.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => split_elements(inner)?,
+        None => {
+            let inner = trimmed
+                .strip_prefix('{')
+                .and_then(|s| s.strip_suffix('}'))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "struct value must be enclosed in braces or parentheses: {value}"
+                    )
+                })?;
+            let elements = split_elements(inner)?;
+            if elements
+                .iter()
+                .any(|element| split_named_field(element).is_some())
+            {
+                return named_field_values(name, fields, elements);
+            }
+            elements
+        }
+    };
+    if elements.len() != fields.len() {
+        anyhow::bail!(
+            "struct {name} value must have {} fields, found {}",
+            fields.len(),
+            elements.len()
+        );
+    }
+    Ok(elements)
+}
+
+/// Put the values of the named fields `elements` of struct `name` into declaration order.
+fn named_field_values<'a>(
+    name: &str,
+    fields: &[(String, Type)],
+    elements: Vec<&'a str>,
+) -> anyhow::Result<Vec<&'a str>> {
+    let mut values = vec![None; fields.len()];
+    for element in elements {
+        let (field_name, field_value) = split_named_field(element).ok_or_else(|| {
+            anyhow::anyhow!("struct {name} value mixes named and positional fields at `{element}`")
+        })?;
+        let index = fields
+            .iter()
+            .position(|(declared_name, _)| declared_name == field_name)
+            .ok_or_else(|| anyhow::anyhow!("struct {name} has no field `{field_name}`"))?;
+        if values[index].replace(field_value).is_some() {
+            anyhow::bail!("struct {name} field `{field_name}` is given more than once");
+        }
+    }
+    fields
+        .iter()
+        .zip(values)
+        .map(|((field_name, _), value)| {
+            value.ok_or_else(|| {
+                anyhow::anyhow!("struct {name} value is missing field `{field_name}`")
+            })
+        })
+        .collect()
+}
+
+/// Split a named struct field such as `"x": 1` into its unquoted name and its value.
+fn split_named_field(element: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    let mut in_string = false;
+    for (i, c) in element.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '[' | '{' | '(' => depth += 1,
+            ']' | '}' | ')' => depth -= 1,
+            ':' if depth == 0 => {
+                return Some((unquote(element[..i].trim()), element[i + 1..].trim()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split the comma separated elements of an array or struct value with its delimiters removed.
+fn split_elements(inner: &str) -> anyhow::Result<Vec<&str>> {
+    if inner.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let mut elements = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let mut in_string = false;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '[' | '{' | '(' => depth += 1,
+            ']' | '}' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                elements.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    elements.push(inner[start..].trim());
+    Ok(elements)
+}
+
 impl FromStr for Type {
     type Err = anyhow::Error;
 
@@ -105,14 +694,544 @@ impl FromStr for Type {
             "u32" => Ok(Type::U32),
             "u64" => Ok(Type::U64),
             "bool" => Ok(Type::Bool),
-            other => anyhow::bail!("{other} type is not supported."),
+            "b256" => Ok(Type::B256),
+            // The JSON ABI names the type `raw untyped ptr`.
+            "raw_ptr" | "raw untyped ptr" => Ok(Type::RawPtr),
+            "Bytes" => Ok(Type::Bytes),
+            other => {
+                if let Some(len) = other
+                    .strip_prefix("str[")
+                    .and_then(|s| s.strip_suffix(']'))
+                    .and_then(|len| len.trim().parse::<usize>().ok())
+                {
+                    return Ok(Type::String(len));
+                }
+                if let Some(element) = other.strip_prefix("Vec<").and_then(|s| s.strip_suffix('>'))
+                {
+                    return Ok(Type::Vector(Box::new(Type::from_str(element.trim())?)));
+                }
+                // Arrays with a known element type and length, as in `[u64; 3]`.
+                let array = other
+                    .strip_prefix('[')
+                    .and_then(|s| s.strip_suffix(']'))
+                    .and_then(|s| s.rsplit_once(';'))
+                    .and_then(|(element, len)| {
+                        let len = len.trim().parse::<usize>().ok()?;
+                        let element_type = Type::from_str(element.trim()).ok()?;
+                        Some(Type::Array(Box::new(element_type), len))
+                    });
+                array.ok_or_else(|| anyhow::anyhow!("{other} type is not supported."))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let type_str = match self {
+            Type::Array(element_type, len) => return write!(f, "[{element_type}; {len}]"),
+            Type::String(len) => return write!(f, "str[{len}]"),
+            Type::Vector(element_type) => return write!(f, "Vec<{element_type}>"),
+            Type::Struct(name, _) | Type::Raw(name) => name.as_str(),
+            Type::Unit => "()",
+            Type::U8 => "u8",
+            Type::U16 => "u16",
+            Type::U32 => "u32",
+            Type::U64 => "u64",
+            Type::Bool => "bool",
+            Type::B256 => "b256",
+            Type::RawPtr => "raw_ptr",
+            Type::Bytes => "Bytes",
+        };
+        write!(f, "{type_str}")
+    }
+}
+
+/// Parse the JSON ABI generated by `forc build`.
+fn parse_abi(abi: &str) -> anyhow::Result<FullProgramABI> {
+    // Editors may save the ABI file with a byte order mark or trailing newlines.
+    let abi = abi.strip_prefix('\u{feff}').unwrap_or(abi).trim();
+    FullProgramABI::from_json_abi(abi).map_err(|e| {
+        anyhow::anyhow!(
+            "invalid ABI JSON: {e}. Make sure the file is the JSON ABI generated by `forc build`"
+        )
+    })
+}
+
+/// Write the values of the given configurables, each given as `NAME=VALUE`, into `bytecode` at
+/// the offsets the JSON ABI `abi` declares for them.
+///
+/// Values are given as for the arguments of the main function, see [Token::from_type_and_value].
+pub(crate) fn override_configurables(
+    abi: &str,
+    bytecode: &mut [u8],
+    overrides: &[String],
+) -> anyhow::Result<()> {
+    let parsed_abi = parse_abi(abi)?;
+    for configurable_override in overrides {
+        let (name, value) = configurable_override.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("invalid configurable `{configurable_override}`, expected `NAME=VALUE`")
+        })?;
+        let configurable = parsed_abi
+            .configurables
+            .iter()
+            .find(|configurable| configurable.name == name)
+            .ok_or_else(|| anyhow::anyhow!("the ABI has no configurable named `{name}`"))?;
+        let ty = Type::try_from(&configurable.application)
+            .with_context(|| format!("configurable `{name}` can't be overridden"))?;
+        let token = Token::from_type_and_value(&ty, value)
+            .with_context(|| format!("invalid value for configurable `{name}`"))?;
+        let encoded = ABIEncoder::encode(&[token.0])?.resolve(0);
+        let offset = configurable.offset as usize;
+        let target = bytecode
+            .get_mut(offset..offset + encoded.len())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "configurable `{name}` at offset {offset} is outside of the bytecode"
+                )
+            })?;
+        target.copy_from_slice(&encoded);
+    }
+    Ok(())
+}
+
+/// The name of the type of a type application from the ABI as declared, with its type arguments
+/// as in `struct Vec<u64>`.
+fn abi_type_name(application: &FullTypeApplication) -> String {
+    let type_field = &application.type_decl.type_field;
+    if application.type_arguments.is_empty() {
+        return type_field.clone();
+    }
+    let type_arguments = application
+        .type_arguments
+        .iter()
+        .map(abi_type_name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{type_field}<{type_arguments}>")
+}
+
+/// Handles the encoding of script arguments.
+#[derive(Debug)]
+pub struct ScriptCallHandler {
+    main_arg_names: Vec<String>,
+    main_arg_types: Vec<Type>,
+    main_output_type: Type,
+    /// The types of the values the script logs, by their log ID. Logged types which aren't
+    /// supported are left out, so their logs are not decoded.
+    logged_types: Vec<(u64, Type)>,
+}
+
+impl ScriptCallHandler {
+    const MAIN_KEYWORD: &str = "main";
+
+    /// Generate a new call handler for calling script main function from the json abi.
+    ///
+    /// Provided json abi is used for determining the argument types, this is required as the data
+    /// encoding is requiring the type of the data. The output type of the main function is kept
+    /// as well, see [ScriptCallHandler::output_type].
+    pub fn from_json_abi_str(abi: &str) -> anyhow::Result<Self> {
+        Self::from_json_abi_str_with_options(abi, false)
+    }
+
+    /// Generate a new call handler as with [ScriptCallHandler::from_json_abi_str].
+    ///
+    /// With `raw_type_passthrough`, arguments of unsupported types don't make this fail. Their
+    /// type becomes [Type::Raw] instead, and their values are encoded from hex without any checks.
+    pub fn from_json_abi_str_with_options(
+        abi: &str,
+        raw_type_passthrough: bool,
+    ) -> anyhow::Result<Self> {
+        let parsed_abi = parse_abi(abi)?;
+        if parsed_abi.functions.is_empty() {
+            anyhow::bail!("ABI contains no functions");
+        }
+        let main_func = parsed_abi
+            .functions
+            .iter()
+            .find(|abi_func| abi_func.name() == Self::MAIN_KEYWORD)
+            .ok_or_else(|| anyhow::anyhow!("`main` function not found in the given ABI"))?;
+        let main_arg_names = main_func
+            .inputs()
+            .iter()
+            .map(|input| input.name.clone())
+            .collect();
+        let main_arg_types = main_func
+            .inputs()
+            .iter()
+            .map(|input| match Type::try_from(input) {
+                Err(_) if raw_type_passthrough => {
+                    let type_field = &input.type_decl.type_field;
+                    tracing::warn!(
+                        "argument '{}' has unsupported type '{type_field}', its value is passed \
+                        through as raw hex without checking that it is a valid '{type_field}'",
+                        input.name
+                    );
+                    Ok(Type::Raw(type_field.clone()))
+                }
+                result => result,
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        // Output types which can't be decoded, such as enums and tuples, don't keep the script
+        // from being called, only its output from being decoded.
+        let main_output_type = Type::from_type_application(main_func.output())
+            .unwrap_or_else(|_| Type::Raw(main_func.output().type_decl.type_field.clone()));
+        let logged_types = parsed_abi
+            .logged_types
+            .iter()
+            .filter_map(|logged_type| {
+                let ty = Type::from_type_application(&logged_type.application).ok()?;
+                Some((logged_type.log_id, ty))
+            })
+            .collect();
+
+        Ok(Self {
+            main_arg_names,
+            main_arg_types,
+            main_output_type,
+            logged_types,
+        })
+    }
+
+    /// Generate a new call handler for calling script main function from the json abi file at
+    /// the given path, see [ScriptCallHandler::from_json_abi_str].
+    pub fn from_json_abi_path(path: &Path) -> anyhow::Result<Self> {
+        Self::from_json_abi_path_with_options(path, false)
+    }
+
+    /// Generate a new call handler from the json abi file at the given path, see
+    /// [ScriptCallHandler::from_json_abi_str_with_options].
+    pub fn from_json_abi_path_with_options(
+        path: &Path,
+        raw_type_passthrough: bool,
+    ) -> anyhow::Result<Self> {
+        let abi = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read ABI file at '{}'", path.display()))?;
+        Self::from_json_abi_str_with_options(&abi, raw_type_passthrough)
+    }
+
+    /// Describe every function of the JSON ABI with its argument names and types and its output
+    /// type, followed by the logged types and the configurables, one per line.
+    ///
+    /// Items are listed in the order of the ABI, so the description of an ABI doesn't change
+    /// between runs.
+    pub fn describe_abi(abi: &str) -> anyhow::Result<String> {
+        let parsed_abi = parse_abi(abi)?;
+        let mut lines = vec!["functions:".to_string()];
+        lines.extend(parsed_abi.functions.iter().map(|function| {
+            let inputs = function
+                .inputs()
+                .iter()
+                .map(|input| format!("{}: {}", input.name, abi_type_name(input)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let output = abi_type_name(function.output());
+            format!("    {}({inputs}) -> {output}", function.name())
+        }));
+        lines.push("logged types:".to_string());
+        if parsed_abi.logged_types.is_empty() {
+            lines.push("    none".to_string());
+        }
+        lines.extend(parsed_abi.logged_types.iter().map(|logged_type| {
+            let type_name = abi_type_name(&logged_type.application);
+            format!("    log ID {}: {type_name}", logged_type.log_id)
+        }));
+        lines.push("configurables:".to_string());
+        if parsed_abi.configurables.is_empty() {
+            lines.push("    none".to_string());
+        }
+        lines.extend(parsed_abi.configurables.iter().map(|configurable| {
+            let type_name = abi_type_name(&configurable.application);
+            format!(
+                "    {}: {type_name} at offset {}",
+                configurable.name, configurable.offset
+            )
+        }));
+        Ok(lines.join("\n"))
+    }
+
+    /// The type returned by the script's main function.
+    pub fn output_type(&self) -> &Type {
+        &self.main_output_type
+    }
+
+    /// The signature of the script's main function, as in `main(u64, bool) -> ()`.
+    pub fn signature(&self) -> String {
+        let arg_types = self
+            .main_arg_types
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("main({arg_types}) -> {}", self.main_output_type)
+    }
+
+    /// Encode the provided values with script's main argument types.
+    ///
+    /// Returns an error if the provided value count does not match the number of arguments.
+    pub fn encode_arguments(&self, values: &[&str]) -> anyhow::Result<UnresolvedBytes> {
+        let main_arg_types = &self.main_arg_types;
+        let expected_arg_count = main_arg_types.len();
+        let provided_arg_count = values.len();
+
+        if expected_arg_count != provided_arg_count {
+            anyhow::bail!(
+                "main function takes {expected_arg_count} arguments, {provided_arg_count} provided"
+            );
+        }
+
+        let tokens = main_arg_types
+            .iter()
+            .zip(values.iter())
+            .map(|(ty, val)| Token::from_type_and_value(ty, val).map(|token| token.0))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let abi_encoder = ABIEncoder::encode(&tokens)?;
+        Ok(abi_encoder)
+    }
+
+    /// Encode the arguments of the main function given together in a single string, as in
+    /// `2 true`. See [split_raw_arguments] for how the string is split.
+    pub fn encode_raw_arguments(&self, raw_args: &str) -> anyhow::Result<UnresolvedBytes> {
+        let args = split_raw_arguments(raw_args)?;
+        self.encode_arguments(&args)
+    }
+
+    /// Decode the hex encoded arguments of the main function, as produced by [Self::encode_arguments],
+    /// back into one value per argument rendered as in `[1, 2, 3]`.
+    pub fn decode_arguments(&self, hex: &str) -> anyhow::Result<Vec<String>> {
+        if let Some(raw_type) = self
+            .main_arg_types
+            .iter()
+            .find(|ty| matches!(ty, Type::Raw(_)))
+        {
+            anyhow::bail!("arguments of raw type `{raw_type}` can't be decoded");
+        }
+        let bytes = hex::decode(hex.trim().trim_start_matches("0x"))
+            .map_err(|e| anyhow::anyhow!("invalid hex encoded arguments: {e}"))?;
+        let param_types = self
+            .main_arg_types
+            .iter()
+            .map(Type::param_type)
+            .collect::<Vec<_>>();
+        let tokens = ABIDecoder::decode(&param_types, &bytes)?;
+        Ok(tokens.iter().map(format_token).collect())
+    }
+
+    /// Prompt for each argument of the main function by name and type, as in `amount: u64 > `,
+    /// then encode the values read from `input`.
+    ///
+    /// A value which doesn't match the type of its argument is reported to `output` and the
+    /// argument is prompted for again.
+    pub fn prompt_arguments<R: BufRead, W: Write>(
+        &self,
+        input: &mut R,
+        output: &mut W,
+    ) -> anyhow::Result<UnresolvedBytes> {
+        let mut tokens = vec![];
+        for (name, ty) in self.main_arg_names.iter().zip(&self.main_arg_types) {
+            let token = loop {
+                write!(output, "{name}: {ty} > ")?;
+                output.flush()?;
+                let mut line = String::new();
+                if input.read_line(&mut line)? == 0 {
+                    anyhow::bail!("no value provided for argument `{name}`");
+                }
+                let value = line.trim_end_matches(['\r', '\n']);
+                match Token::from_type_and_value(ty, value) {
+                    Ok(token) => break token,
+                    Err(e) => writeln!(output, "invalid value for `{name}`: {e}")?,
+                }
+            };
+            tokens.push(token.0);
+        }
+        let abi_encoder = ABIEncoder::encode(&tokens)?;
+        Ok(abi_encoder)
+    }
+
+    /// Decode the value returned by the script's main function from the receipts of running it,
+    /// rendered as in `[1, 2, 3]`.
+    ///
+    /// Values that fit in a register are returned in a `Return` receipt and other values in a
+    /// `ReturnData` receipt. For `Vec` and `Bytes`, that receipt only holds the pointer, capacity
+    /// and length of the heap buffer, whose contents are in the `ReturnData` receipt for that
+    /// pointer.
+    ///
+    /// Fails for output types which can't be decoded, see [Type::Raw].
+    pub fn decode_output(&self, receipts: &[Receipt]) -> anyhow::Result<String> {
+        if let Type::Raw(type_field) = &self.main_output_type {
+            anyhow::bail!("output of type `{type_field}` can't be decoded");
+        }
+        // Scripts return with the zeroed contract ID.
+        let script_id = ContractId::zeroed();
+        let param_type = self.main_output_type.param_type();
+        let data = match param_type.get_return_location() {
+            ReturnLocation::Return => receipts.iter().find_map(|receipt| match receipt {
+                Receipt::Return { id, val, .. } if *id == script_id => {
+                    Some(val.to_be_bytes().to_vec())
+                }
+                _ => None,
+            }),
+            ReturnLocation::ReturnData => receipts.iter().find_map(|receipt| match receipt {
+                Receipt::ReturnData { id, data, .. } if *id == script_id => Some(data.clone()),
+                _ => None,
+            }),
+        }
+        .ok_or_else(|| anyhow::anyhow!("the script did not return a value"))?;
+        let data = if param_type.is_vm_heap_type() {
+            heap_buffer(&param_type, &data, receipts)?
+        } else {
+            data
+        };
+        let token = ABIDecoder::decode_single(&param_type, &data)?;
+        Ok(format_token(&token))
+    }
+
+    /// Decode the data of a `Log` or `LogData` receipt with the given log ID, rendered as in
+    /// `[1, 2, 3]`.
+    ///
+    /// For `Log` receipts, the data is the logged register value.
+    pub fn decode_log(&self, log_id: u64, data: &[u8]) -> anyhow::Result<String> {
+        let log_type = self
+            .logged_types
+            .iter()
+            .find_map(|(id, ty)| (*id == log_id).then_some(ty))
+            .ok_or_else(|| anyhow::anyhow!("no logged type with log ID {log_id}"))?;
+        let param_type = log_type.param_type();
+        // Only the pointer, capacity and length of a logged heap type are in the receipt.
+        if param_type.is_vm_heap_type() {
+            anyhow::bail!("logged {log_type} values can't be decoded");
         }
+        let token = ABIDecoder::decode_single(&param_type, data)?;
+        Ok(format_token(&token))
+    }
+
+    /// Start building the script's main arguments from typed values, rather than from strings.
+    pub fn call_builder(&self) -> ScriptCallBuilder<'_> {
+        ScriptCallBuilder {
+            main_arg_types: &self.main_arg_types,
+            args: vec![],
+        }
+    }
+}
+
+/// Builds the encoded arguments of a script's main function from typed Rust values.
+///
+/// The pushed values are validated against the types and the order of the main function's
+/// arguments when the arguments are built.
+#[derive(Debug)]
+pub struct ScriptCallBuilder<'a> {
+    main_arg_types: &'a [Type],
+    args: Vec<(Type, fuels_core::types::Token)>,
+}
+
+impl ScriptCallBuilder<'_> {
+    pub fn push_unit(self) -> Self {
+        self.push(Type::Unit, fuels_core::types::Token::Unit)
+    }
+
+    pub fn push_u8(self, value: u8) -> Self {
+        self.push(Type::U8, fuels_core::types::Token::U8(value))
+    }
+
+    pub fn push_u16(self, value: u16) -> Self {
+        self.push(Type::U16, fuels_core::types::Token::U16(value))
+    }
+
+    pub fn push_u32(self, value: u32) -> Self {
+        self.push(Type::U32, fuels_core::types::Token::U32(value))
+    }
+
+    pub fn push_u64(self, value: u64) -> Self {
+        self.push(Type::U64, fuels_core::types::Token::U64(value))
+    }
+
+    pub fn push_bool(self, value: bool) -> Self {
+        self.push(Type::Bool, fuels_core::types::Token::Bool(value))
+    }
+
+    pub fn push_b256(self, value: [u8; 32]) -> Self {
+        self.push(Type::B256, fuels_core::types::Token::B256(value))
+    }
+
+    fn push(mut self, ty: Type, token: fuels_core::types::Token) -> Self {
+        self.args.push((ty, token));
+        self
+    }
+
+    /// Encode the pushed values.
+    ///
+    /// Returns an error if the number of pushed values or any of their types does not match the
+    /// script's main arguments.
+    pub fn build(self) -> anyhow::Result<UnresolvedBytes> {
+        let expected_arg_count = self.main_arg_types.len();
+        let provided_arg_count = self.args.len();
+        if expected_arg_count != provided_arg_count {
+            anyhow::bail!(
+                "main function takes {expected_arg_count} arguments, {provided_arg_count} provided"
+            );
+        }
+
+        let mut tokens = Vec::with_capacity(provided_arg_count);
+        for (index, (expected_type, (provided_type, token))) in
+            self.main_arg_types.iter().zip(self.args).enumerate()
+        {
+            if *expected_type != provided_type {
+                anyhow::bail!(
+                    "main function argument {index} is of type {expected_type}, {provided_type} provided"
+                );
+            }
+            tokens.push(token);
+        }
+
+        let abi_encoder = ABIEncoder::encode(&tokens)?;
+        Ok(abi_encoder)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fuel_abi_types::abi::full_program::FullTypeDeclaration;
+    use serde_json::json;
+
+    /// Builds the JSON ABI of a script whose `main` function takes arguments with the given
+    /// names and types, and returns `()`.
+    fn abi_with_main(args: &[(&str, &str)]) -> String {
+        let mut types = vec!["()"];
+        for (_, type_str) in args {
+            if !types.contains(type_str) {
+                types.push(type_str);
+            }
+        }
+        let type_id = |type_str: &str| types.iter().position(|ty| *ty == type_str).unwrap();
+        let inputs: Vec<_> = args
+            .iter()
+            .map(|(name, type_str)| {
+                json!({"name": name, "type": type_id(type_str), "typeArguments": null})
+            })
+            .collect();
+        let type_decls: Vec<_> = types
+            .iter()
+            .enumerate()
+            .map(|(type_id, type_str)| {
+                json!({"typeId": type_id, "type": type_str, "components": [], "typeParameters": null})
+            })
+            .collect();
+        json!({
+            "types": type_decls,
+            "functions": [{
+                "inputs": inputs,
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string()
+    }
 
     #[test]
     fn test_token_generation_success() {
@@ -134,6 +1253,71 @@ mod tests {
         assert_eq!(generated_tokens, expected_tokens)
     }
 
+    #[test]
+    fn test_token_from_literal() {
+        let b256 = [0xab; 32];
+        let literals = [
+            Literal::U8(1),
+            Literal::U64(u64::MAX),
+            Literal::Boolean(true),
+            Literal::B256(b256),
+        ];
+        let tokens = literals
+            .iter()
+            .map(|literal| Token::try_from(literal).unwrap())
+            .collect::<Vec<_>>();
+        let expected_tokens = [
+            Token(fuels_core::types::Token::U8(1)),
+            Token(fuels_core::types::Token::U64(u64::MAX)),
+            Token(fuels_core::types::Token::Bool(true)),
+            Token(fuels_core::types::Token::B256(b256)),
+        ];
+        assert_eq!(tokens, expected_tokens);
+
+        let err = Token::try_from(&Literal::Numeric(1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "numeric literal `1` has no type to convert it to a token"
+        );
+    }
+
+    #[test]
+    fn test_main_prompt_arguments() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        // The first value for `test_u8` is out of range, so it is prompted for again.
+        let mut input = "256\n2\ntrue\n".as_bytes();
+        let mut output = vec![];
+
+        let encoded_bytes = call_handler
+            .prompt_arguments(&mut input, &mut output)
+            .unwrap()
+            .resolve(0);
+        let expected_bytes = vec![
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 2u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 1u8,
+        ];
+        assert_eq!(encoded_bytes, expected_bytes);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("test_u8: u8 > invalid value for `test_u8`"));
+        assert!(output.ends_with("test_u8: u8 > test_bool: bool > "));
+    }
+
+    #[test]
+    fn test_main_prompt_arguments_end_of_input() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        let mut input = "2\n".as_bytes();
+
+        let err = call_handler
+            .prompt_arguments(&mut input, &mut std::io::sink())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no value provided for argument `test_bool`"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_token_generation_fail_type_mismatch() {
@@ -142,7 +1326,7 @@ mod tests {
 
     #[test]
     fn test_type_generation_success() {
-        let possible_type_list = ["()", "u8", "u16", "u32", "u64", "bool"];
+        let possible_type_list = ["()", "u8", "u16", "u32", "u64", "bool", "b256"];
         let types = possible_type_list
             .iter()
             .map(|type_str| Type::from_str(type_str))
@@ -156,6 +1340,7 @@ mod tests {
             Type::U32,
             Type::U64,
             Type::Bool,
+            Type::B256,
         ];
         assert_eq!(types, expected_types)
     }
@@ -166,4 +1351,1207 @@ mod tests {
         let invalid_type_str = "u2";
         Type::from_str(invalid_type_str).unwrap();
     }
+
+    #[test]
+    fn test_type_generation_fail_reports_argument() {
+        let type_application = FullTypeApplication {
+            name: "foo".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: "enum SomeEnum".to_string(),
+                components: vec![],
+                type_parameters: vec![],
+            },
+            type_arguments: vec![],
+        };
+        let err = Type::try_from(&type_application).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 'foo' has unsupported type 'enum SomeEnum'"
+        );
+    }
+
+    fn type_application(
+        name: &str,
+        type_field: &str,
+        components: Vec<FullTypeApplication>,
+        type_arguments: Vec<FullTypeApplication>,
+    ) -> FullTypeApplication {
+        FullTypeApplication {
+            name: name.to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: type_field.to_string(),
+                components,
+                type_parameters: vec![],
+            },
+            type_arguments,
+        }
+    }
+
+    #[test]
+    fn test_type_from_abi_scalar_types() {
+        let cases = [
+            ("()", Type::Unit),
+            ("u8", Type::U8),
+            ("u16", Type::U16),
+            ("u32", Type::U32),
+            ("u64", Type::U64),
+            ("bool", Type::Bool),
+            ("b256", Type::B256),
+            ("raw untyped ptr", Type::RawPtr),
+            ("str[5]", Type::String(5)),
+        ];
+        for (type_field, expected) in cases {
+            let application = type_application("arg", type_field, vec![], vec![]);
+            assert_eq!(
+                Type::try_from(&application).unwrap(),
+                expected,
+                "{type_field}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_type_from_abi_composite_types() {
+        let u64_application = || type_application("", "u64", vec![], vec![]);
+        let array = type_application(
+            "arg",
+            "[_; 3]",
+            vec![type_application("__array_element", "u64", vec![], vec![])],
+            vec![],
+        );
+        assert_eq!(
+            Type::try_from(&array).unwrap(),
+            Type::Array(Box::new(Type::U64), 3)
+        );
+
+        let vector = type_application("arg", "struct Vec", vec![], vec![u64_application()]);
+        assert_eq!(
+            Type::try_from(&vector).unwrap(),
+            Type::Vector(Box::new(Type::U64))
+        );
+
+        let bytes = type_application("arg", "struct Bytes", vec![], vec![]);
+        assert_eq!(Type::try_from(&bytes).unwrap(), Type::Bytes);
+
+        let point = type_application(
+            "arg",
+            "struct Point",
+            vec![
+                type_application("x", "u64", vec![], vec![]),
+                type_application("y", "bool", vec![], vec![]),
+                type_application("tags", "[_; 2]", vec![u64_application()], vec![]),
+            ],
+            vec![],
+        );
+        assert_eq!(
+            Type::try_from(&point).unwrap(),
+            Type::Struct(
+                "Point".to_string(),
+                vec![
+                    ("x".to_string(), Type::U64),
+                    ("y".to_string(), Type::Bool),
+                    ("tags".to_string(), Type::Array(Box::new(Type::U64), 2)),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_type_element_type() {
+        let vector = Type::Vector(Box::new(Type::U64));
+        assert_eq!(vector.element_type(), Some(&Type::U64));
+
+        let array = Type::Array(Box::new(Type::Bool), 3);
+        assert_eq!(array.element_type(), Some(&Type::Bool));
+
+        assert_eq!(Type::U8.element_type(), None);
+    }
+
+    #[test]
+    #[ignore = "tuple arguments are not supported yet"]
+    fn test_type_from_abi_tuple_type() {
+        // Tuples should map to a tuple of the types of their elements, in order, once there is a
+        // `Type` variant for them.
+        let tuple = type_application(
+            "arg",
+            "(_, _)",
+            vec![
+                type_application("__tuple_element", "u64", vec![], vec![]),
+                type_application("__tuple_element", "bool", vec![], vec![]),
+            ],
+            vec![],
+        );
+        assert!(Type::try_from(&tuple).is_ok());
+    }
+
+    #[test]
+    fn test_token_generation_struct() {
+        let empty_struct = Type::Struct("Empty".to_string(), vec![]);
+        assert_eq!(
+            Token::from_type_and_value(&empty_struct, "{}").unwrap(),
+            Token(fuels_core::types::Token::Struct(vec![]))
+        );
+        assert_eq!(
+            Token::from_type_and_value(&empty_struct, " { } ").unwrap(),
+            Token(fuels_core::types::Token::Struct(vec![]))
+        );
+
+        let point = Type::Struct(
+            "Point".to_string(),
+            vec![("x".to_string(), Type::U64), ("y".to_string(), Type::Bool)],
+        );
+        assert_eq!(
+            Token::from_type_and_value(&point, "{1, true}").unwrap(),
+            Token(fuels_core::types::Token::Struct(vec![
+                fuels_core::types::Token::U64(1),
+                fuels_core::types::Token::Bool(true),
+            ]))
+        );
+        let err = Token::from_type_and_value(&empty_struct, "{1}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "struct Empty value must have 0 fields, found 1"
+        );
+    }
+
+    #[test]
+    fn test_token_generation_unit() {
+        for value in ["", "()", " () "] {
+            assert_eq!(
+                Token::from_type_and_value(&Type::Unit, value).unwrap(),
+                Token(fuels_core::types::Token::Unit)
+            );
+        }
+        let err = Token::from_type_and_value(&Type::Unit, "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unit value must be empty or `()`, found `1`"
+        );
+
+        let padded = Type::Struct(
+            "Padded".to_string(),
+            vec![("a".to_string(), Type::U64), ("b".to_string(), Type::Unit)],
+        );
+        let expected = Token(fuels_core::types::Token::Struct(vec![
+            fuels_core::types::Token::U64(1),
+            fuels_core::types::Token::Unit,
+        ]));
+        for value in ["{1, ()}", "(1, )", r#"{"b": (), "a": 1}"#] {
+            assert_eq!(
+                Token::from_type_and_value(&padded, value).unwrap(),
+                expected
+            );
+        }
+        let err = Token::from_type_and_value(&padded, "{1}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "struct Padded value must have 2 fields, found 1"
+        );
+    }
+
+    #[test]
+    fn test_token_generation_struct_positional_and_named() {
+        let point = Type::Struct(
+            "Point".to_string(),
+            vec![("x".to_string(), Type::U64), ("y".to_string(), Type::Bool)],
+        );
+        let expected = Token(fuels_core::types::Token::Struct(vec![
+            fuels_core::types::Token::U64(1),
+            fuels_core::types::Token::Bool(true),
+        ]));
+        for value in [
+            "(1, true)",
+            "{1, true}",
+            r#"{"x": 1, "y": true}"#,
+            r#"{"y": true, "x": 1}"#,
+            "{x: 1, y: true}",
+        ] {
+            assert_eq!(Token::from_type_and_value(&point, value).unwrap(), expected);
+        }
+        let empty_struct = Type::Struct("Empty".to_string(), vec![]);
+        assert_eq!(
+            Token::from_type_and_value(&empty_struct, "()").unwrap(),
+            Token(fuels_core::types::Token::Struct(vec![]))
+        );
+
+        let err = Token::from_type_and_value(&point, "(1)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "struct Point value must have 2 fields, found 1"
+        );
+        let err = Token::from_type_and_value(&point, r#"{"x": 1}"#).unwrap_err();
+        assert_eq!(err.to_string(), "struct Point value is missing field `y`");
+        let err = Token::from_type_and_value(&point, r#"{"x": 1, "z": true}"#).unwrap_err();
+        assert_eq!(err.to_string(), "struct Point has no field `z`");
+        let err = Token::from_type_and_value(&point, r#"{"x": 1, "x": 2}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "struct Point field `x` is given more than once"
+        );
+        let err = Token::from_type_and_value(&point, r#"{"x": 1, true}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "struct Point value mixes named and positional fields at `true`"
+        );
+    }
+
+    #[test]
+    fn test_main_encoding_struct_positional_and_named() {
+        let test_json_abi = json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {
+                    "typeId": 1,
+                    "type": "struct Point",
+                    "components": [
+                        {"name": "x", "type": 2, "typeArguments": null},
+                        {"name": "y", "type": 3, "typeArguments": null}
+                    ],
+                    "typeParameters": null
+                },
+                {"typeId": 2, "type": "u64", "components": null, "typeParameters": null},
+                {"typeId": 3, "type": "bool", "components": null, "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [{"name": "point", "type": 1, "typeArguments": null}],
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        let positional = call_handler
+            .encode_arguments(&["(7, true)"])
+            .unwrap()
+            .resolve(0);
+        let named = call_handler
+            .encode_arguments(&[r#"{"y": true, "x": 7}"#])
+            .unwrap()
+            .resolve(0);
+        assert_eq!(positional, named);
+        let raw = call_handler
+            .encode_raw_arguments(r#"{"x": 7, "y": true}"#)
+            .unwrap()
+            .resolve(0);
+        assert_eq!(positional, raw);
+    }
+
+    #[test]
+    fn test_main_encoding_empty_struct() {
+        let test_json_abi = abi_with_main(&[("empty", "struct Empty"), ("test_u8", "u8")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(call_handler.signature(), "main(Empty, u8) -> ()");
+
+        // A struct without fields takes up no bytes in the encoded arguments.
+        let expected_bytes = vec![0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 2u8];
+        let encoded_bytes = call_handler
+            .encode_arguments(&["{}", "2"])
+            .unwrap()
+            .resolve(0);
+        assert_eq!(encoded_bytes, expected_bytes);
+        let encoded_bytes = call_handler
+            .encode_raw_arguments("{} 2")
+            .unwrap()
+            .resolve(0);
+        assert_eq!(encoded_bytes, expected_bytes);
+    }
+
+    #[test]
+    fn test_main_encoding_struct_with_unit_field() {
+        let test_json_abi = json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {
+                    "typeId": 1,
+                    "type": "struct Padded",
+                    "components": [
+                        {"name": "a", "type": 2, "typeArguments": null},
+                        {"name": "b", "type": 0, "typeArguments": null}
+                    ],
+                    "typeParameters": null
+                },
+                {"typeId": 2, "type": "u64", "components": null, "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [{"name": "padded", "type": 1, "typeArguments": null}],
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        // The unit field keeps its place after `a`, encoded however the ABI encodes `()`.
+        let expected_bytes = ABIEncoder::encode(&[fuels_core::types::Token::Struct(vec![
+            fuels_core::types::Token::U64(7),
+            fuels_core::types::Token::Unit,
+        ])])
+        .unwrap()
+        .resolve(0);
+        assert_eq!(
+            &expected_bytes[..8],
+            &[0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 7u8]
+        );
+        for value in ["{7, ()}", "(7, )", r#"{"a": 7, "b": ()}"#] {
+            let encoded_bytes = call_handler.encode_arguments(&[value]).unwrap().resolve(0);
+            assert_eq!(encoded_bytes, expected_bytes);
+        }
+    }
+
+    #[test]
+    fn test_main_encoding_success() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        let values = ["2", "true"];
+
+        let encoded_bytes = call_handler.encode_arguments(&values).unwrap().resolve(0);
+        let expected_bytes = vec![
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 2u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 1u8,
+        ];
+        assert_eq!(encoded_bytes, expected_bytes);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_main_encoding_fail_arg_type_mismatch() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        // The `bool` argument is given a non-boolean value.
+        let values = ["2", "12"];
+
+        call_handler.encode_arguments(&values).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "main function takes 2 arguments, 1 provided")]
+    fn test_main_encoding_fail_arg_count_mismatch() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        // The script takes 2 arguments, only 1 is provided.
+        let values = ["2"];
+
+        call_handler.encode_arguments(&values).unwrap();
+    }
+
+    #[test]
+    fn test_main_output_type() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(call_handler.output_type(), &Type::Unit);
+    }
+
+    #[test]
+    fn test_main_option_output() {
+        let test_json_abi = json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {
+                    "typeId": 1,
+                    "type": "enum Option",
+                    "components": [
+                        {"name": "None", "type": 0, "typeArguments": null},
+                        {"name": "Some", "type": 2, "typeArguments": null}
+                    ],
+                    "typeParameters": [2]
+                },
+                {"typeId": 2, "type": "generic T", "components": null, "typeParameters": null},
+                {"typeId": 3, "type": "u64", "components": null, "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [{"name": "value", "type": 3, "typeArguments": null}],
+                "name": "main",
+                "output": {
+                    "name": "",
+                    "type": 1,
+                    "typeArguments": [{"name": "", "type": 3, "typeArguments": null}]
+                },
+                "attributes": null
+            }],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(
+            call_handler.output_type(),
+            &Type::Raw("enum Option".to_string())
+        );
+        assert_eq!(call_handler.signature(), "main(u64) -> enum Option");
+        assert!(call_handler.encode_arguments(&["42"]).is_ok());
+
+        let receipts = [Receipt::ret(ContractId::zeroed(), 0, 0, 0)];
+        let err = call_handler.decode_output(&receipts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "output of type `enum Option` can't be decoded"
+        );
+    }
+
+    #[test]
+    fn test_main_signature() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(call_handler.signature(), "main(u8, bool) -> ()");
+
+        let call_handler = ScriptCallHandler::from_json_abi_str(&abi_with_vec_output()).unwrap();
+        assert_eq!(call_handler.signature(), "main() -> Vec<u64>");
+    }
+
+    #[test]
+    fn test_describe_abi() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let description = ScriptCallHandler::describe_abi(&test_json_abi).unwrap();
+        assert_eq!(
+            description,
+            "functions:\n    main(test_u8: u8, test_bool: bool) -> ()\nlogged types:\n    none\n\
+            configurables:\n    none"
+        );
+
+        let test_json_abi = json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {"typeId": 1, "type": "u64", "components": null, "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [{"name": "amount", "type": 1, "typeArguments": null}],
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [
+                {"logId": 0, "loggedType": {"name": "", "type": 1, "typeArguments": null}}
+            ],
+            "messagesTypes": [],
+            "configurables": [
+                {
+                    "name": "FEE",
+                    "configurableType": {"name": "", "type": 1, "typeArguments": null},
+                    "offset": 120
+                }
+            ]
+        })
+        .to_string();
+        let description = ScriptCallHandler::describe_abi(&test_json_abi).unwrap();
+        assert_eq!(
+            description.lines().collect::<Vec<_>>(),
+            [
+                "functions:",
+                "    main(amount: u64) -> ()",
+                "logged types:",
+                "    log ID 0: u64",
+                "configurables:",
+                "    FEE: u64 at offset 120",
+            ]
+        );
+
+        // Type arguments are part of the described types.
+        let description = ScriptCallHandler::describe_abi(&abi_with_vec_output()).unwrap();
+        assert!(description.contains("    main() -> struct Vec<u64>\n"));
+    }
+
+    #[test]
+    fn test_override_configurables() {
+        let abi = json!({
+            "types": [
+                {"typeId": 0, "type": "u64", "components": null, "typeParameters": null},
+                {"typeId": 1, "type": "bool", "components": null, "typeParameters": null}
+            ],
+            "functions": [],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": [
+                {
+                    "name": "FEE",
+                    "configurableType": {"name": "", "type": 0, "typeArguments": null},
+                    "offset": 8
+                },
+                {
+                    "name": "ENABLED",
+                    "configurableType": {"name": "", "type": 1, "typeArguments": null},
+                    "offset": 16
+                }
+            ]
+        })
+        .to_string();
+
+        let mut bytecode = vec![0u8; 24];
+        let overrides = ["FEE=258".to_string(), "ENABLED=true".to_string()];
+        override_configurables(&abi, &mut bytecode, &overrides).unwrap();
+        let mut expected = vec![0u8; 24];
+        expected[14..16].copy_from_slice(&[1, 2]);
+        expected[23] = 1;
+        assert_eq!(bytecode, expected);
+
+        let unknown = ["LIMIT=1".to_string()];
+        let err = override_configurables(&abi, &mut bytecode, &unknown).unwrap_err();
+        assert_eq!(err.to_string(), "the ABI has no configurable named `LIMIT`");
+
+        let malformed = ["FEE".to_string()];
+        let err = override_configurables(&abi, &mut bytecode, &malformed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid configurable `FEE`, expected `NAME=VALUE`"
+        );
+
+        let mut short_bytecode = vec![0u8; 12];
+        let overrides = ["FEE=1".to_string()];
+        let err = override_configurables(&abi, &mut short_bytecode, &overrides).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "configurable `FEE` at offset 8 is outside of the bytecode"
+        );
+    }
+
+    #[test]
+    fn test_main_abi_with_bom_and_whitespace() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8")]);
+        let test_json_abi = format!("\u{feff}{test_json_abi}\n\n");
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        let encoded_bytes = call_handler.encode_arguments(&["2"]).unwrap().resolve(0);
+        assert_eq!(encoded_bytes, vec![0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 2u8]);
+    }
+
+    #[test]
+    fn test_main_invalid_abi_json() {
+        let err = ScriptCallHandler::from_json_abi_str("{ \"types\": [").unwrap_err();
+        assert!(err.to_string().starts_with("invalid ABI JSON: "));
+    }
+
+    #[test]
+    fn test_main_from_json_abi_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let abi_path = dir.path().join("script-abi.json");
+        std::fs::write(&abi_path, abi_with_main(&[("test_u8", "u8")])).unwrap();
+        let call_handler = ScriptCallHandler::from_json_abi_path(&abi_path).unwrap();
+        let encoded_bytes = call_handler.encode_arguments(&["2"]).unwrap().resolve(0);
+        assert_eq!(encoded_bytes, vec![0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 2u8]);
+
+        let missing_path = dir.path().join("missing-abi.json");
+        let err = ScriptCallHandler::from_json_abi_path(&missing_path).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("failed to read ABI file at '{}'", missing_path.display())
+        );
+    }
+
+    #[test]
+    fn test_main_abi_without_functions() {
+        let test_json_abi = json!({
+            "types": [],
+            "functions": [],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let err = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap_err();
+        assert_eq!(err.to_string(), "ABI contains no functions");
+    }
+
+    #[test]
+    fn test_main_unsupported_arg_type_reports_argument() {
+        let test_json_abi = abi_with_main(&[("foo", "enum SomeEnum")]);
+        let err = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 'foo' has unsupported type 'enum SomeEnum'"
+        );
+    }
+
+    #[test]
+    fn test_main_raw_type_passthrough() {
+        let test_json_abi = abi_with_main(&[("foo", "enum SomeEnum"), ("test_u8", "u8")]);
+        let call_handler =
+            ScriptCallHandler::from_json_abi_str_with_options(&test_json_abi, true).unwrap();
+        assert_eq!(call_handler.signature(), "main(enum SomeEnum, u8) -> ()");
+
+        // The raw value is encoded verbatim, followed by the encoding of the other arguments.
+        let raw_value = "0x00000000000000010000000000000005";
+        let encoded_bytes = call_handler
+            .encode_arguments(&[raw_value, "2"])
+            .unwrap()
+            .resolve(0);
+        let expected_bytes = vec![
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 1u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 5u8, 0u8,
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 2u8,
+        ];
+        assert_eq!(encoded_bytes, expected_bytes);
+
+        let err = call_handler.encode_arguments(&["0x0001", "2"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "raw `enum SomeEnum` value must be a whole number of 8 byte words, found 2 bytes"
+        );
+        let err = call_handler.decode_arguments("0x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "arguments of raw type `enum SomeEnum` can't be decoded"
+        );
+    }
+
+    #[test]
+    fn test_main_builder_success() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        let built_bytes = call_handler
+            .call_builder()
+            .push_u8(2)
+            .push_bool(true)
+            .build()
+            .unwrap()
+            .resolve(0);
+        let encoded_bytes = call_handler
+            .encode_arguments(&["2", "true"])
+            .unwrap()
+            .resolve(0);
+        assert_eq!(built_bytes, encoded_bytes);
+    }
+
+    #[test]
+    fn test_main_decode_arguments() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        let encoded_bytes = call_handler
+            .encode_arguments(&["2", "true"])
+            .unwrap()
+            .resolve(0);
+        let encoded_hex = format!("0x{}", hex::encode(encoded_bytes));
+        assert_eq!(
+            call_handler.decode_arguments(&encoded_hex).unwrap(),
+            vec!["2", "true"]
+        );
+        assert_eq!(
+            call_handler
+                .decode_arguments(encoded_hex.trim_start_matches("0x"))
+                .unwrap(),
+            vec!["2", "true"]
+        );
+
+        let err = call_handler.decode_arguments("0xzz").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid hex encoded arguments:"));
+    }
+
+    #[test]
+    fn test_encode_raw_arguments() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        assert_eq!(split_raw_arguments("2 true").unwrap(), vec!["2", "true"]);
+        let encoded_bytes = call_handler
+            .encode_raw_arguments("2 true")
+            .unwrap()
+            .resolve(0);
+        let expected_bytes = vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1];
+        assert_eq!(encoded_bytes, expected_bytes);
+    }
+
+    #[test]
+    fn test_split_raw_arguments_keeps_strings_and_arrays() {
+        assert_eq!(
+            split_raw_arguments(r#"  "hello world"  [1, 2]	3 "#).unwrap(),
+            vec![r#""hello world""#, "[1, 2]", "3"]
+        );
+        assert!(split_raw_arguments("").unwrap().is_empty());
+        let err = split_raw_arguments(r#""hello"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"unterminated string in arguments: "hello"#
+        );
+    }
+
+    #[test]
+    fn test_main_builder_fail_arg_type_mismatch() {
+        let test_json_abi = abi_with_main(&[("test_u8", "u8"), ("test_bool", "bool")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        // The arguments are pushed in the wrong order.
+        let err = call_handler
+            .call_builder()
+            .push_bool(true)
+            .push_u8(2)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "main function argument 0 is of type u8, bool provided"
+        );
+
+        let err = call_handler
+            .call_builder()
+            .push_u8(2)
+            .push_bool(true)
+            .push_b256([0; 32])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "main function takes 2 arguments, 3 provided"
+        );
+    }
+
+    #[test]
+    fn test_type_predicates() {
+        for ty in [Type::U8, Type::U16, Type::U32, Type::U64] {
+            assert!(ty.is_integer(), "{ty} is an integer");
+            assert!(!ty.is_composite(), "{ty} is not composite");
+        }
+        for ty in [Type::Unit, Type::Bool, Type::B256] {
+            assert!(!ty.is_integer(), "{ty} is not an integer");
+            assert!(!ty.is_composite(), "{ty} is not composite");
+        }
+        let array = Type::Array(Box::new(Type::U64), 3);
+        assert!(!array.is_integer(), "{array} is not an integer");
+        assert!(array.is_composite(), "{array} is composite");
+    }
+
+    #[test]
+    fn test_type_abi_string() {
+        assert_eq!(Type::Unit.abi_string(), "()");
+        assert_eq!(Type::U8.abi_string(), "u8");
+        assert_eq!(Type::U64.abi_string(), "u64");
+        assert_eq!(Type::Bool.abi_string(), "bool");
+        assert_eq!(Type::B256.abi_string(), "b256");
+        assert_eq!(Type::String(5).abi_string(), "str[5]");
+        assert_eq!(Type::Array(Box::new(Type::U8), 3).abi_string(), "[_; 3]");
+        assert_eq!(
+            Type::Array(Box::new(Type::Array(Box::new(Type::Bool), 2)), 4).abi_string(),
+            "[_; 4]"
+        );
+        assert_eq!(Type::Vector(Box::new(Type::U64)).abi_string(), "struct Vec");
+        assert_eq!(Type::Bytes.abi_string(), "struct Bytes");
+    }
+
+    #[test]
+    fn test_type_abi_string_matches_type_field() {
+        let abi = abi_with_main(&[("a", "u8"), ("b", "str[3]"), ("c", "bool")]);
+        let handler = ScriptCallHandler::from_json_abi_str(&abi).unwrap();
+        for (arg, type_field) in handler.main_arg_types.iter().zip(["u8", "str[3]", "bool"]) {
+            assert_eq!(arg.abi_string(), type_field);
+        }
+
+        let handler = ScriptCallHandler::from_json_abi_str(&abi_with_vec_output()).unwrap();
+        assert_eq!(handler.output_type().abi_string(), "struct Vec");
+    }
+
+    #[test]
+    fn test_b256_token_generation() {
+        let value = format!("0x{}", "01".repeat(32));
+        assert_eq!(
+            Token::from_type_and_value(&Type::B256, &value).unwrap(),
+            Token(fuels_core::types::Token::B256([1; 32]))
+        );
+        assert!(Token::from_type_and_value(&Type::B256, "0x0101").is_err());
+    }
+
+    #[test]
+    fn test_b256_token_generation_from_byte_list() {
+        let bytes = (0..32u8).collect::<Vec<_>>();
+        let byte_list = format!(
+            "[{}]",
+            bytes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let hex = format!("0x{}", hex::encode(&bytes));
+        assert_eq!(
+            Token::from_type_and_value(&Type::B256, &byte_list).unwrap(),
+            Token::from_type_and_value(&Type::B256, &hex).unwrap()
+        );
+
+        let err = Token::from_type_and_value(&Type::B256, "[1, 2, 3]").unwrap_err();
+        assert_eq!(err.to_string(), "b256 value must have 32 bytes, found 3");
+        let too_large = format!("[256{}]", ", 0".repeat(31));
+        assert!(Token::from_type_and_value(&Type::B256, &too_large).is_err());
+    }
+
+    #[test]
+    fn test_b256_token_generation_from_bech32() {
+        let address = "fuel1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqs45npag";
+        assert_eq!(
+            Token::from_type_and_value(&Type::B256, address).unwrap(),
+            Token(fuels_core::types::Token::B256([1; 32]))
+        );
+
+        // The last character of the checksum is changed.
+        let invalid = "fuel1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqs45npah";
+        let err = Token::from_type_and_value(&Type::B256, invalid).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with(&format!("invalid bech32 address `{invalid}`")));
+    }
+
+    #[test]
+    fn test_raw_ptr_type_generation() {
+        assert_eq!(Type::from_str("raw_ptr").unwrap(), Type::RawPtr);
+        assert_eq!(Type::from_str("raw untyped ptr").unwrap(), Type::RawPtr);
+        assert_eq!(Type::RawPtr.to_string(), "raw_ptr");
+        assert_eq!(Type::RawPtr.abi_string(), "raw untyped ptr");
+        assert!(!Type::RawPtr.is_integer());
+        assert!(!Type::RawPtr.is_composite());
+    }
+
+    #[test]
+    fn test_main_encoding_raw_ptr() {
+        let test_json_abi = abi_with_main(&[("ptr", "raw untyped ptr")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(call_handler.signature(), "main(raw_ptr) -> ()");
+
+        let expected_bytes = vec![0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0x10u8, 0u8];
+        for value in ["4096", "0x1000"] {
+            let encoded_bytes = call_handler.encode_arguments(&[value]).unwrap().resolve(0);
+            assert_eq!(encoded_bytes, expected_bytes);
+        }
+
+        for value in ["-1", "0x", "18446744073709551616", "ptr"] {
+            let err = call_handler.encode_arguments(&[value]).unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with(&format!("invalid raw_ptr value `{value}`")),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let test_json_abi = json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {"typeId": 1, "type": "u64", "components": [], "typeParameters": null},
+                {"typeId": 2, "type": "enum SomeEnum", "components": [], "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [],
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [
+                {"logId": 0, "loggedType": {"name": "", "type": 1, "typeArguments": null}},
+                {"logId": 1, "loggedType": {"name": "", "type": 2, "typeArguments": null}}
+            ],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+
+        assert_eq!(
+            call_handler.decode_log(0, &42u64.to_be_bytes()).unwrap(),
+            "42"
+        );
+        // Logs of unsupported types and unknown log IDs are not decoded.
+        let err = call_handler.decode_log(1, &[0; 8]).unwrap_err();
+        assert_eq!(err.to_string(), "no logged type with log ID 1");
+        assert!(call_handler.decode_log(2, &[0; 8]).is_err());
+    }
+
+    #[test]
+    fn test_array_type_generation() {
+        assert_eq!(
+            Type::from_str("[u64; 3]").unwrap(),
+            Type::Array(Box::new(Type::U64), 3)
+        );
+        assert_eq!(Type::Array(Box::new(Type::U8), 2).to_string(), "[u8; 2]");
+
+        let type_application = FullTypeApplication {
+            name: "foo".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: "[_; 2]".to_string(),
+                components: vec![FullTypeApplication {
+                    name: "__array_element".to_string(),
+                    type_decl: FullTypeDeclaration {
+                        type_field: "u8".to_string(),
+                        components: vec![],
+                        type_parameters: vec![],
+                    },
+                    type_arguments: vec![],
+                }],
+                type_parameters: vec![],
+            },
+            type_arguments: vec![],
+        };
+        assert_eq!(
+            Type::try_from(&type_application).unwrap(),
+            Type::Array(Box::new(Type::U8), 2)
+        );
+    }
+
+    #[test]
+    fn test_string_type_generation() {
+        assert_eq!(Type::from_str("str[11]").unwrap(), Type::String(11));
+        assert_eq!(Type::String(11).to_string(), "str[11]");
+        assert!(!Type::String(11).is_composite());
+    }
+
+    #[test]
+    fn test_main_string_argument_with_spaces() {
+        let test_json_abi = abi_with_main(&[("greeting", "str[11]")]);
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        let mut expected_bytes = b"hello world".to_vec();
+        expected_bytes.resize(16, 0);
+
+        // `--args "hello world"` reaches the handler without the quotes, as a single argument.
+        let encoded_bytes = call_handler
+            .encode_arguments(&["hello world"])
+            .unwrap()
+            .resolve(0);
+        assert_eq!(encoded_bytes, expected_bytes);
+
+        // Quotes that are part of the value are removed.
+        let encoded_bytes = call_handler
+            .encode_arguments(&["\"hello world\""])
+            .unwrap()
+            .resolve(0);
+        assert_eq!(encoded_bytes, expected_bytes);
+
+        let err = call_handler.encode_arguments(&["hello"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "string value must be 11 bytes long, found 5"
+        );
+    }
+
+    #[test]
+    fn test_string_array_elements_keep_commas_and_spaces() {
+        assert_eq!(
+            split_array_value(r#"["a, b", "c]", "d"]"#).unwrap(),
+            vec![r#""a, b""#, r#""c]""#, r#""d""#]
+        );
+        let token = Token::from_type_and_value(
+            &Type::Array(Box::new(Type::String(4)), 2),
+            r#"["a, b", "c de"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            token,
+            Token(fuels_core::types::Token::Array(vec![
+                fuels_core::types::Token::String(StringToken::new("a, b".to_string(), 4)),
+                fuels_core::types::Token::String(StringToken::new("c de".to_string(), 4)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_array_elements_mix_hex_and_decimal() {
+        let token =
+            Token::from_type_and_value(&Type::Array(Box::new(Type::U8), 3), "[0x0a, 10, 0xff]")
+                .unwrap();
+        assert_eq!(
+            token,
+            Token(fuels_core::types::Token::Array(vec![
+                fuels_core::types::Token::U8(10),
+                fuels_core::types::Token::U8(10),
+                fuels_core::types::Token::U8(255),
+            ]))
+        );
+
+        let err = Token::from_type_and_value(&Type::Array(Box::new(Type::U8), 2), "[1, 0x100]")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "hex integer `0x100` is out of range for its type"
+        );
+    }
+
+    #[test]
+    fn test_main_array_len_from_type_arguments() {
+        // The length of the `[_; N]` array is given by the type argument for `N`.
+        let test_json_abi = json!({
+            "types": [
+                {"typeId": 0, "type": "()", "components": [], "typeParameters": null},
+                {
+                    "typeId": 1,
+                    "type": "[_; N]",
+                    "components": [{"name": "__array_element", "type": 2, "typeArguments": null}],
+                    "typeParameters": [3]
+                },
+                {"typeId": 2, "type": "u64", "components": null, "typeParameters": null},
+                {"typeId": 3, "type": "generic N", "components": null, "typeParameters": null},
+                {"typeId": 4, "type": "3", "components": null, "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [{
+                    "name": "values",
+                    "type": 1,
+                    "typeArguments": [{"name": "", "type": 4, "typeArguments": null}]
+                }],
+                "name": "main",
+                "output": {"name": "", "type": 0, "typeArguments": null},
+                "attributes": null
+            }],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string();
+        let call_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(
+            call_handler.main_arg_types,
+            vec![Type::Array(Box::new(Type::U64), 3)]
+        );
+
+        let encoded_bytes = call_handler
+            .encode_arguments(&["[1, 2, 3]"])
+            .unwrap()
+            .resolve(0);
+        let expected_bytes: Vec<u8> = [1u64, 2, 3]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        assert_eq!(encoded_bytes, expected_bytes);
+
+        let err = call_handler.encode_arguments(&["[1, 2]"]).unwrap_err();
+        assert_eq!(err.to_string(), "array value must have 3 elements, found 2");
+    }
+
+    /// Builds the JSON ABI of a script whose `main` function takes no arguments and returns
+    /// `Vec<u64>`, as generated by `forc build`.
+    fn abi_with_vec_output() -> String {
+        json!({
+            "types": [
+                {"typeId": 0, "type": "generic T", "components": null, "typeParameters": null},
+                {"typeId": 1, "type": "raw untyped ptr", "components": null, "typeParameters": null},
+                {
+                    "typeId": 2,
+                    "type": "struct RawVec",
+                    "components": [
+                        {"name": "ptr", "type": 1, "typeArguments": null},
+                        {"name": "cap", "type": 4, "typeArguments": null}
+                    ],
+                    "typeParameters": [0]
+                },
+                {
+                    "typeId": 3,
+                    "type": "struct Vec",
+                    "components": [
+                        {
+                            "name": "buf",
+                            "type": 2,
+                            "typeArguments": [{"name": "", "type": 0, "typeArguments": null}]
+                        },
+                        {"name": "len", "type": 4, "typeArguments": null}
+                    ],
+                    "typeParameters": [0]
+                },
+                {"typeId": 4, "type": "u64", "components": null, "typeParameters": null}
+            ],
+            "functions": [{
+                "inputs": [],
+                "name": "main",
+                "output": {
+                    "name": "",
+                    "type": 3,
+                    "typeArguments": [{"name": "", "type": 4, "typeArguments": null}]
+                },
+                "attributes": null
+            }],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        })
+        .to_string()
+    }
+
+    /// The receipts of a script returning a heap value with the given buffer contents: the
+    /// pointer, capacity and length of the buffer, followed by the buffer itself.
+    fn heap_return_receipts(len: u64, buffer: Vec<u8>) -> Vec<Receipt> {
+        let buffer_ptr = 0x4000;
+        let header: Vec<u8> = [buffer_ptr, 4, len]
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .collect();
+        let script_id = ContractId::zeroed();
+        vec![
+            Receipt::return_data(script_id, 0x1000, Default::default(), header, 0, 0),
+            Receipt::return_data(script_id, buffer_ptr, Default::default(), buffer, 0, 0),
+            Receipt::ret(script_id, 1, 0, 0),
+        ]
+    }
+
+    #[test]
+    fn test_vec_and_bytes_type_generation() {
+        let call_handler = ScriptCallHandler::from_json_abi_str(&abi_with_vec_output()).unwrap();
+        let vec_type = Type::Vector(Box::new(Type::U64));
+        assert_eq!(call_handler.output_type(), &vec_type);
+        assert_eq!(vec_type.to_string(), "Vec<u64>");
+        assert_eq!(Type::from_str("Vec<u64>").unwrap(), vec_type);
+        assert_eq!(Type::from_str("Bytes").unwrap(), Type::Bytes);
+        assert!(vec_type.is_composite());
+        assert!(!Type::Bytes.is_composite());
+
+        let err = Token::from_type_and_value(&vec_type, "[1, 2]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Vec<u64> arguments are not supported, only return values"
+        );
+    }
+
+    #[test]
+    fn test_decode_vec_output() {
+        let call_handler = ScriptCallHandler::from_json_abi_str(&abi_with_vec_output()).unwrap();
+        // The buffer has room for 4 elements, of which 3 are used.
+        let buffer: Vec<u8> = [1u64, 2, 3, 0]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        let receipts = heap_return_receipts(3, buffer);
+        assert_eq!(call_handler.decode_output(&receipts).unwrap(), "[1, 2, 3]");
+
+        let receipts = heap_return_receipts(0, vec![]);
+        assert_eq!(call_handler.decode_output(&receipts).unwrap(), "[]");
+
+        // Without the receipt of the buffer, the elements are unknown.
+        let receipts = heap_return_receipts(3, vec![])[..1].to_vec();
+        let err = call_handler.decode_output(&receipts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no receipt holds the heap buffer at 0x4000"
+        );
+    }
+
+    #[test]
+    fn test_decode_bytes_and_word_outputs() {
+        let bytes_handler = ScriptCallHandler {
+            main_arg_names: vec![],
+            main_arg_types: vec![],
+            main_output_type: Type::Bytes,
+            logged_types: vec![],
+        };
+        let receipts = heap_return_receipts(5, b"hello\0\0\0".to_vec());
+        assert_eq!(
+            bytes_handler.decode_output(&receipts).unwrap(),
+            "0x68656c6c6f"
+        );
+
+        let test_json_abi = abi_with_main(&[]);
+        let unit_handler = ScriptCallHandler::from_json_abi_str(&test_json_abi).unwrap();
+        assert_eq!(unit_handler.decode_output(&receipts).unwrap(), "()");
+        let u64_handler = ScriptCallHandler {
+            main_arg_names: vec![],
+            main_arg_types: vec![],
+            main_output_type: Type::U64,
+            logged_types: vec![],
+        };
+        let receipts = vec![Receipt::ret(ContractId::zeroed(), 42, 0, 0)];
+        assert_eq!(u64_handler.decode_output(&receipts).unwrap(), "42");
+        let err = u64_handler.decode_output(&[]).unwrap_err();
+        assert_eq!(err.to_string(), "the script did not return a value");
+    }
 }
@@ -0,0 +1,9 @@
+script;
+
+fn main() -> Vec<u64> {
+    let mut values = Vec::new();
+    values.push(1);
+    values.push(2);
+    values.push(3);
+    values
+}
@@ -0,0 +1,5 @@
+script;
+
+fn main() {
+    __revert(42);
+}
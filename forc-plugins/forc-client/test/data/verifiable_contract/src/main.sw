@@ -0,0 +1,11 @@
+contract;
+
+abi Answer {
+    fn answer() -> u64;
+}
+
+impl Answer for Contract {
+    fn answer() -> u64 {
+        42
+    }
+}
@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use forc_pkg::source::IPFSNode;
 
 #[derive(Debug, Parser)]
@@ -29,12 +29,35 @@ pub struct Command {
     /// needs to be updated, Forc will exit with an error
     #[clap(long)]
     pub locked: bool,
-    /// Do not build documentation for dependencies.
+    /// Do not build documentation for dependencies, only for the package or the members of
+    /// the workspace. Types from dependencies are then not linked.
     #[clap(long)]
     pub no_deps: bool,
+    /// Treat warnings from building the documentation, like links to items that can't be
+    /// resolved, as errors.
+    #[clap(long)]
+    pub deny_warnings: bool,
+    /// The format to write the documentation in.
+    ///
+    /// `md` writes a markdown file for each module, and `json` writes the documentation of
+    /// each package to a single JSON file, for custom renderers.
+    #[clap(long, value_enum, default_value_t = DocFormat::default())]
+    pub format: DocFormat,
     /// The IPFS Node to use for fetching IPFS sources.
     ///
     /// Possible values: PUBLIC, LOCAL, <GATEWAY_URL>
     #[clap(long)]
     pub ipfs_node: Option<IPFSNode>,
 }
+
+/// The formats that the documentation can be written in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum DocFormat {
+    /// HTML pages along with the assets and the search index they load.
+    #[default]
+    Html,
+    /// A markdown file for each module.
+    Md,
+    /// A JSON file for each package.
+    Json,
+}
@@ -0,0 +1,429 @@
+//! Resolve links to other items in doc comments, like [`Item`] or [`module::Item`], to the
+//! pages of the items.
+use crate::{
+    doc::{module::ModuleInfo, Documentation},
+    render::{
+        constant::IDENTITY,
+        search::{SearchIndex, METHOD_KIND},
+        util::format::docstring::docs_to_html_string,
+    },
+};
+use sway_core::{
+    decl_engine::DeclEngine,
+    language::ty::TyProgram,
+    namespace::Module,
+    transform::{AttributeKind, AttributesMap},
+};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::{Ident, Spanned};
+
+/// The prefixes that restrict a link to items of some kinds, e.g. `struct@Item`, along with the
+/// kinds of the items they match.
+const DISAMBIGUATORS: &[(&str, &[&str])] = &[
+    ("struct", &["struct"]),
+    ("enum", &["enum"]),
+    ("trait", &["trait"]),
+    ("abi", &["abi"]),
+    ("fn", &["fn", METHOD_KIND]),
+    ("method", &[METHOD_KIND]),
+    ("const", &["constant"]),
+    ("constant", &["constant"]),
+];
+/// The kinds of items that a link ending in `()` matches.
+const FN_KINDS: &[&str] = &["fn", METHOD_KIND];
+/// The kinds of items that have pages their methods can be linked to.
+const TYPE_KINDS: &[&str] = &["struct", "enum"];
+
+impl Documentation {
+    /// Rewrite the links to other items in the doc comments of the documented items so that they
+    /// point to the pages of the items, which are looked up in the [SearchIndex].
+    ///
+    /// Links that can't be resolved are left as they are, and a warning is returned for each of
+    /// them.
+    pub(crate) fn resolve_intra_doc_links(
+        &mut self,
+        decl_engine: &DeclEngine,
+        typed_program: &TyProgram,
+        search_index: &SearchIndex,
+    ) -> Vec<CompileWarning> {
+        let mut warnings = vec![];
+        for doc in &mut self.0 {
+            let Some(attributes) = doc.attributes(decl_engine) else {
+                continue;
+            };
+            // The first module prefix is the project name, which isn't part of the namespace.
+            let module_path: Vec<Ident> = doc
+                .module_info
+                .module_prefixes
+                .iter()
+                .skip(1)
+                .map(|prefix| Ident::new_no_span(prefix.clone()))
+                .collect();
+            let scope = LinkScope {
+                search_index,
+                module_prefixes: &doc.module_info.module_prefixes,
+                module: typed_program
+                    .root
+                    .namespace
+                    .module()
+                    .submodule(&module_path),
+            };
+            if let Some(html) = scope.rewrite_docs(&attributes, &doc.module_info, &mut warnings) {
+                doc.item_body.attrs_opt = Some(html.clone());
+                doc.raw_attributes = Some(html);
+            }
+        }
+        warnings
+    }
+}
+
+/// A link in a doc comment that refers to an item by its path.
+#[derive(Debug, PartialEq, Eq)]
+struct IntraDocLink<'a> {
+    /// The destination of the link as it is written, without surrounding backticks.
+    destination: &'a str,
+    /// The segments of the path of the item.
+    path: Vec<&'a str>,
+    /// The kinds of items the link is restricted to, if it has a disambiguator.
+    kinds: Option<&'static [&'static str]>,
+}
+impl<'a> IntraDocLink<'a> {
+    /// Parse the destination of a link, e.g. `` `module::Item` `` or `struct@Item`.
+    ///
+    /// Returns `None` if the destination isn't the path of an item, like a URL.
+    fn parse(destination: &'a str) -> Option<Self> {
+        let destination = destination.trim();
+        let destination = destination
+            .strip_prefix('`')
+            .and_then(|destination| destination.strip_suffix('`'))
+            .unwrap_or(destination);
+        let (kinds, path) = match destination.split_once('@') {
+            Some((disambiguator, path)) => {
+                let (_, kinds) = DISAMBIGUATORS
+                    .iter()
+                    .find(|(name, _)| *name == disambiguator)?;
+                (Some(*kinds), path)
+            }
+            None => (None, destination),
+        };
+        let (kinds, path) = match path.strip_suffix("()") {
+            Some(path) => (kinds.or(Some(FN_KINDS)), path),
+            None => (kinds, path),
+        };
+        let path: Vec<&str> = path.split("::").collect();
+        path.iter()
+            .all(|segment| is_identifier(segment))
+            .then_some(IntraDocLink {
+                destination,
+                path,
+                kinds,
+            })
+    }
+}
+fn is_identifier(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The items that the doc comments of an item in some module can link to.
+struct LinkScope<'a> {
+    search_index: &'a SearchIndex,
+    /// The module prefixes of the documented item, starting with the project name.
+    module_prefixes: &'a [String],
+    /// The namespace of the module, used to follow the items imported into it.
+    module: Option<&'a Module>,
+}
+impl LinkScope<'_> {
+    /// Render the doc comments in `attributes` to HTML with the links to items rewritten.
+    ///
+    /// Returns `None` if the doc comments don't link to any item that could be resolved.
+    fn rewrite_docs(
+        &self,
+        attributes: &AttributesMap,
+        module_info: &ModuleInfo,
+        warnings: &mut Vec<CompileWarning>,
+    ) -> Option<String> {
+        let mut docs = String::new();
+        let mut is_rewritten = false;
+        let mut in_code_block = false;
+        for arg in attributes
+            .get(&AttributeKind::DocComment)?
+            .iter()
+            .flat_map(|attribute| &attribute.args)
+        {
+            let line = arg.name.as_str();
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if in_code_block {
+                docs.push_str(line);
+            } else {
+                let rewritten = rewrite_line(line, |link| {
+                    let href = self.resolve(link);
+                    if href.is_none() {
+                        warnings.push(CompileWarning {
+                            span: arg.name.span(),
+                            warning_content: Warning::UnresolvedDocLink {
+                                link: link.destination.to_string(),
+                            },
+                        });
+                    }
+                    href.map(|href| module_info.to_html_shorthand_path_string(&href))
+                });
+                is_rewritten |= rewritten != line;
+                docs.push_str(&rewritten);
+            }
+            docs.push('\n');
+        }
+        is_rewritten.then(|| docs_to_html_string(&docs))
+    }
+    /// Find the path of the page of the linked item, relative to the documentation root.
+    fn resolve(&self, link: &IntraDocLink) -> Option<String> {
+        self.candidates(&link.path)
+            .iter()
+            .find_map(|path| self.find(path, link.kinds))
+    }
+    /// The qualified names that a path could refer to, in the order they are looked up in.
+    ///
+    /// A path is looked up as an item imported into the module, relative to the module,
+    /// relative to the package root, and as a fully qualified path, e.g. to an item in a
+    /// dependency. Paths starting with `crate`, `self` or `super` are only looked up relative
+    /// to the package root, the module or its parent respectively.
+    fn candidates(&self, path: &[&str]) -> Vec<Vec<String>> {
+        let join = |prefixes: &[String], segments: &[&str]| -> Vec<String> {
+            prefixes
+                .iter()
+                .cloned()
+                .chain(segments.iter().map(|segment| segment.to_string()))
+                .collect()
+        };
+        let project_name = &self.module_prefixes[..1];
+        let (first, rest) = path.split_first().expect("paths have at least one segment");
+        match *first {
+            "crate" => vec![join(project_name, rest)],
+            "self" => vec![join(self.module_prefixes, rest)],
+            "super" => {
+                let parent_len = (self.module_prefixes.len() - 1).max(1);
+                vec![join(&self.module_prefixes[..parent_len], rest)]
+            }
+            _ => {
+                let mut candidates = vec![];
+                let imported_path = self.module.and_then(|module| {
+                    module.use_synonym_path(&Ident::new_no_span(first.to_string()))
+                });
+                if let Some(imported_path) = imported_path {
+                    let imported_path: Vec<String> = imported_path
+                        .iter()
+                        .map(|ident| ident.as_str().to_string())
+                        .collect();
+                    // Items of the package itself are imported by their path from its root.
+                    let mut local_path = project_name.to_vec();
+                    local_path.extend(imported_path.iter().cloned());
+                    candidates.push(join(&imported_path, rest));
+                    candidates.push(join(&local_path, rest));
+                }
+                candidates.push(join(self.module_prefixes, path));
+                candidates.push(join(project_name, path));
+                candidates.push(join(&[], path));
+                candidates
+            }
+        }
+    }
+    fn find(&self, path: &[String], kinds: Option<&[&str]>) -> Option<String> {
+        if let Some(entry) = self.search_index.find(&path.join("::"), kinds) {
+            return Some(entry.href.clone());
+        }
+        // Methods that aren't in the index are linked to on the page of their type.
+        let (method, type_path) = path.split_last()?;
+        if type_path.is_empty() || kinds.map_or(false, |kinds| !kinds.contains(&METHOD_KIND)) {
+            return None;
+        }
+        self.search_index
+            .find(&type_path.join("::"), Some(TYPE_KINDS))
+            .map(|entry| format!("{}{IDENTITY}method.{method}", entry.href))
+    }
+}
+
+/// Rewrite the links to items in a line of markdown so that they point to the pages of the
+/// items, both as the destination of a link, as in `[the item](module::Item)`, and as a bare
+/// bracketed path, as in [`module::Item`].
+///
+/// `resolve` returns the path to the page of a linked item, or `None` if the link can't be
+/// resolved, in which case it is left as it is. Brackets in code spans aren't taken for links.
+fn rewrite_line(line: &str, mut resolve: impl FnMut(&IntraDocLink) -> Option<String>) -> String {
+    let mut rewritten = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find(|c| c == '`' || c == '[') {
+        rewritten.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('`') {
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            let end = rest[ticks..]
+                .find(&rest[..ticks])
+                .map_or(rest.len(), |end| ticks + end + ticks);
+            rewritten.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let escaped = rewritten.ends_with('\\');
+        let Some(close) = rest.find(']').filter(|_| !escaped) else {
+            rewritten.push('[');
+            rest = &rest[1..];
+            continue;
+        };
+        let text = &rest[1..close];
+        if text.contains('[') {
+            rewritten.push('[');
+            rest = &rest[1..];
+            continue;
+        }
+        let after = &rest[close + 1..];
+
+        if let Some(destination) = after.strip_prefix('(') {
+            let Some(destination_end) = destination.find(')') else {
+                rewritten.push_str(&rest[..=close]);
+                rest = after;
+                continue;
+            };
+            let link_end = close + 1 + destination_end + 2;
+            match IntraDocLink::parse(&destination[..destination_end])
+                .and_then(|link| resolve(&link))
+            {
+                Some(href) => rewritten.push_str(&format!("[{text}]({href})")),
+                None => rewritten.push_str(&rest[..link_end]),
+            }
+            rest = &rest[link_end..];
+        } else if after.starts_with('[') {
+            // Reference links are left as they are, along with their label.
+            let link_end = close + 1 + after.find(']').map_or(0, |end| end + 1);
+            rewritten.push_str(&rest[..link_end]);
+            rest = &rest[link_end..];
+        } else if after.starts_with(':') {
+            // Link reference definitions.
+            rewritten.push_str(&rest[..=close]);
+            rest = after;
+        } else {
+            match IntraDocLink::parse(text).and_then(|link| resolve(&link)) {
+                Some(href) => rewritten.push_str(&format!("[{text}]({href})")),
+                None => rewritten.push_str(&rest[..=close]),
+            }
+            rest = after;
+        }
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rewrite_line, IntraDocLink};
+    use crate::{doc::Documentation, render::search::SearchIndex, tests::compile_fixture};
+    use sway_error::warning::Warning;
+
+    #[test]
+    fn test_parse_intra_doc_link() {
+        let link = IntraDocLink::parse("`module::Item`").unwrap();
+        assert_eq!(link.destination, "module::Item");
+        assert_eq!(link.path, ["module", "Item"]);
+        assert_eq!(link.kinds, None);
+
+        let link = IntraDocLink::parse("struct@Item").unwrap();
+        assert_eq!(link.path, ["Item"]);
+        assert_eq!(link.kinds, Some(&["struct"][..]));
+
+        let link = IntraDocLink::parse("Item::method()").unwrap();
+        assert_eq!(link.path, ["Item", "method"]);
+        assert_eq!(link.kinds, Some(&["fn", "method"][..]));
+
+        assert_eq!(IntraDocLink::parse("https://fuel.network"), None);
+        assert_eq!(IntraDocLink::parse("T; 3"), None);
+        assert_eq!(IntraDocLink::parse("unknown@Item"), None);
+    }
+
+    #[test]
+    fn test_rewrite_line() {
+        let resolve = |link: &IntraDocLink| {
+            (link.path.last() != Some(&"Missing")).then(|| format!("{}.html", link.path.join("/")))
+        };
+        assert_eq!(
+            rewrite_line("Uses [`Foo`] and [bar::Baz].", resolve),
+            "Uses [`Foo`](Foo.html) and [bar::Baz](bar/Baz.html)."
+        );
+        assert_eq!(
+            rewrite_line(
+                "See [the type](`Foo`) or [docs](https://fuel.network).",
+                resolve
+            ),
+            "See [the type](Foo.html) or [docs](https://fuel.network)."
+        );
+        assert_eq!(
+            rewrite_line("Not [`Missing`], `[Foo]`, \\[Foo] or [T; 3].", resolve),
+            "Not [`Missing`], `[Foo]`, \\[Foo] or [T; 3]."
+        );
+        assert_eq!(
+            rewrite_line("[Foo][ref] and [^note]", resolve),
+            "[Foo][ref] and [^note]"
+        );
+    }
+
+    #[test]
+    fn test_resolve_intra_doc_links() {
+        let fixture = compile_fixture("intra_doc_links");
+        let mut search_index = SearchIndex::default();
+        for (project_name, ty_program) in &fixture.dependencies {
+            let docs = Documentation::from_ty_program(
+                fixture.engines.de(),
+                project_name,
+                ty_program,
+                false,
+            )
+            .unwrap();
+            search_index.extend_from_docs(&docs);
+        }
+        let mut docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            fixture.pkg_manifest.project_name(),
+            &fixture.ty_program,
+            false,
+        )
+        .unwrap();
+        search_index.extend_from_docs(&docs);
+        let warnings =
+            docs.resolve_intra_doc_links(fixture.engines.de(), &fixture.ty_program, &search_index);
+
+        let doc = docs
+            .0
+            .iter()
+            .find(|doc| doc.item_header.item_name.as_str() == "translate")
+            .unwrap();
+        let html = doc.item_body.attrs_opt.as_ref().unwrap();
+        // An item of a dependency, imported into the module.
+        assert!(html.contains(
+            r#"<a href="../intra_doc_links_dep/struct.Point.html"><code>Point</code></a>"#
+        ));
+        // An item of a submodule, by its path from the module.
+        assert!(html.contains(
+            r#"<a href="../intra_doc_links/geometry/struct.Offset.html">the offset</a>"#
+        ));
+        // A method of a trait.
+        assert!(
+            html.contains(r#"href="../intra_doc_links/geometry/trait.Area.html#tymethod.area""#)
+        );
+        // The broken link is left as it is.
+        assert!(html.contains("[<code>Missing</code>]"));
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].warning_content,
+            Warning::UnresolvedDocLink {
+                link: "Missing".to_string()
+            }
+        );
+        assert!(warnings[0].span.as_str().contains("[`Missing`]"));
+    }
+}
@@ -1,16 +1,25 @@
 use crate::{
     doc::{descriptor::Descriptor, module::ModuleInfo},
-    render::{item::components::*, link::DocLink, util::format::docstring::*},
+    render::{
+        item::{components::*, context::DocImplTrait},
+        link::DocLink,
+        util::format::docstring::*,
+    },
 };
 use anyhow::Result;
 use std::option::Option;
 use sway_core::{
     decl_engine::DeclEngine,
-    language::ty::{TyAstNodeContent, TyDecl, TyImplTrait, TyModule, TyProgram, TySubmodule},
+    language::ty::{
+        AbiDecl, ConstantDecl, EnumDecl, FunctionDecl, StorageDecl, StructDecl, TraitDecl,
+        TyAstNodeContent, TyDecl, TyImplTrait, TyModule, TyProgram, TySubmodule,
+    },
+    transform::AttributesMap,
 };
 use sway_types::Spanned;
 
 mod descriptor;
+mod intra_doc_link;
 pub mod module;
 
 #[derive(Default)]
@@ -56,18 +65,53 @@ impl Documentation {
         // currently this compares the spans as str, but this needs to change
         // to compare the actual types
         if !impl_traits.is_empty() {
+            let trait_docs: Vec<(String, ModuleInfo, String)> = docs
+                .0
+                .iter()
+                .filter_map(|doc| match doc.item_body.ty_decl {
+                    TyDecl::TraitDecl(ref trait_decl) => Some((
+                        trait_decl.name.as_str().to_owned(),
+                        doc.module_info.clone(),
+                        doc.html_filename(),
+                    )),
+                    _ => None,
+                })
+                .collect();
+
             for doc in &mut docs.0 {
-                let mut impl_vec: Vec<TyImplTrait> = Vec::new();
+                let mut impl_vec: Vec<DocImplTrait> = Vec::new();
+                let mut implementor_vec: Vec<DocImplTrait> = Vec::new();
+                // Link the implemented trait if it is documented in this package.
+                let doc_impl_trait = |impl_trait: &TyImplTrait| DocImplTrait {
+                    impl_trait: impl_trait.clone(),
+                    trait_link: trait_docs
+                        .iter()
+                        .find(|(name, ..)| name == impl_trait.trait_name.suffix.as_str())
+                        .map(|(_, module_info, html_filename)| {
+                            doc.module_info.to_html_shorthand_path_string(&format!(
+                                "{}/{html_filename}",
+                                module_info.module_prefixes.join("/")
+                            ))
+                        }),
+                };
 
                 match doc.item_body.ty_decl {
-                    TyDecl::StructDecl(ref struct_decl) => {
+                    TyDecl::StructDecl(StructDecl { ref name, .. })
+                    | TyDecl::EnumDecl(EnumDecl { ref name, .. }) => {
                         for impl_trait in &impl_traits {
-                            if struct_decl.name.as_str()
-                                == impl_trait.implementing_for.span.as_str()
-                                && struct_decl.name.as_str()
-                                    != impl_trait.trait_name.suffix.span().as_str()
+                            if name.as_str() == impl_trait.implementing_for.span.as_str()
+                                && name.as_str() != impl_trait.trait_name.suffix.span().as_str()
                             {
-                                impl_vec.push(impl_trait.clone());
+                                impl_vec.push(doc_impl_trait(impl_trait));
+                            }
+                        }
+                    }
+                    TyDecl::TraitDecl(TraitDecl { ref name, .. }) => {
+                        // This includes blanket implementations, which are not listed on the
+                        // pages of the types they apply to.
+                        for impl_trait in &impl_traits {
+                            if name.as_str() == impl_trait.trait_name.suffix.as_str() {
+                                implementor_vec.push(doc_impl_trait(impl_trait));
                             }
                         }
                     }
@@ -77,6 +121,9 @@ impl Documentation {
                 if !impl_vec.is_empty() {
                     doc.item_body.item_context.impl_traits = Some(impl_vec);
                 }
+                if !implementor_vec.is_empty() {
+                    doc.item_body.item_context.implementors = Some(implementor_vec);
+                }
             }
         }
 
@@ -186,4 +233,28 @@ impl Document {
     fn preview_opt(&self) -> Option<String> {
         create_preview(self.raw_attributes.clone())
     }
+    /// The attributes of the documented declaration, if it has any.
+    pub(crate) fn attributes(&self, decl_engine: &DeclEngine) -> Option<AttributesMap> {
+        let attributes = match &self.item_body.ty_decl {
+            TyDecl::StructDecl(StructDecl { decl_id, .. }) => {
+                decl_engine.get_struct(decl_id).attributes
+            }
+            TyDecl::EnumDecl(EnumDecl { decl_id, .. }) => decl_engine.get_enum(decl_id).attributes,
+            TyDecl::TraitDecl(TraitDecl { decl_id, .. }) => {
+                decl_engine.get_trait(decl_id).attributes
+            }
+            TyDecl::AbiDecl(AbiDecl { decl_id, .. }) => decl_engine.get_abi(decl_id).attributes,
+            TyDecl::StorageDecl(StorageDecl { decl_id, .. }) => {
+                decl_engine.get_storage(decl_id).attributes
+            }
+            TyDecl::FunctionDecl(FunctionDecl { decl_id, .. }) => {
+                decl_engine.get_function(decl_id).attributes
+            }
+            TyDecl::ConstantDecl(ConstantDecl { decl_id, .. }) => {
+                decl_engine.get_constant(decl_id).attributes
+            }
+            _ => return None,
+        };
+        (!attributes.is_empty()).then_some(attributes)
+    }
 }
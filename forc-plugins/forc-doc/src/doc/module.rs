@@ -127,6 +127,16 @@ impl ModuleInfo {
         file_name: &str,
         current_module_info: &ModuleInfo,
     ) -> Result<String> {
+        // Items of other packages are reached from the root of the documentation.
+        let in_other_package = matches!(
+            (self.module_prefixes.first(), current_module_info.module_prefixes.first()),
+            (Some(project_name), Some(current_project_name)) if project_name != current_project_name
+        );
+        if in_other_package {
+            let mut new_path = current_module_info.to_html_path_prefix();
+            write!(new_path, "{}/{}", self.module_prefixes.join("/"), file_name)?;
+            return Ok(new_path);
+        }
         let mut mid = 0; // the index to split the module_info from call_path at
         let mut offset = 0; // the number of directories to go back
         let mut next_location_iter = self.module_prefixes.iter().rev().enumerate().peekable();
@@ -206,4 +216,16 @@ mod tests {
         let project_opt = module_info.parent();
         assert_eq!(None, project_opt);
     }
+
+    #[test]
+    fn test_file_path_from_location_in_other_package() {
+        let current_module_info =
+            ModuleInfo::from_ty_module(vec!["member_a".to_string(), "tokens".to_string()], None);
+        let module_info =
+            ModuleInfo::from_ty_module(vec!["member_b".to_string(), "tokens".to_string()], None);
+        let file_path = module_info
+            .file_path_from_location("struct.Token.html", &current_module_info)
+            .unwrap();
+        assert_eq!(file_path, "../../member_b/tokens/struct.Token.html");
+    }
 }
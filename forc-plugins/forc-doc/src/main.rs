@@ -1,25 +1,38 @@
 use crate::{
-    doc::Documentation,
-    render::{constant::INDEX_FILENAME, RenderedDocumentation},
+    doc::{module::ModuleInfo, Documentation},
+    render::{
+        constant::{INDEX_FILENAME, SEARCH_INDEX_FILENAME},
+        contract::ContractInterface,
+        json::JsonDocumentation,
+        markdown::render_markdown,
+        search::SearchIndex,
+        source::render_source_pages,
+        RenderedDocument, RenderedDocumentation,
+    },
 };
 use anyhow::{bail, Result};
 use clap::Parser;
-use cli::Command;
+use cli::{Command, DocFormat};
 use colored::*;
 use forc_pkg as pkg;
 use forc_util::default_output_directory;
 use include_dir::{include_dir, Dir};
 use pkg::{manifest::ManifestFile, PackageManifestFile};
 use std::{
+    collections::BTreeSet,
     path::Path,
     process::Command as Process,
     {fs, path::PathBuf},
 };
 use sway_core::{language::ty::TyProgram, BuildTarget, Engines};
+use sway_error::warning::CompileWarning;
+use sway_types::SourceEngine;
 
 mod cli;
 mod doc;
 mod render;
+#[cfg(test)]
+mod tests;
 
 pub(crate) const ASSETS_DIR_NAME: &str = "static.files";
 
@@ -28,12 +41,41 @@ pub(crate) const ASSETS_DIR_NAME: &str = "static.files";
 struct RenderPlan<'e> {
     document_private_items: bool,
     engines: &'e Engines,
+    /// The directory of the Sway files of the package, used to link items to their source.
+    source_root: PathBuf,
+    /// The packages documented alongside this one, if only items of those packages can be
+    /// linked to. Items of other packages, such as dependencies with `--no-deps`, are not
+    /// linked so that there are no broken links.
+    documented_packages: Option<BTreeSet<String>>,
 }
 impl<'e> RenderPlan<'e> {
-    fn new(document_private_items: bool, engines: &'e Engines) -> RenderPlan<'e> {
+    fn new(
+        document_private_items: bool,
+        engines: &'e Engines,
+        source_root: PathBuf,
+    ) -> RenderPlan<'e> {
         Self {
             document_private_items,
             engines,
+            source_root,
+            documented_packages: None,
+        }
+    }
+    /// Only link to the items of the given packages.
+    fn with_documented_packages(self, documented_packages: BTreeSet<String>) -> RenderPlan<'e> {
+        Self {
+            documented_packages: Some(documented_packages),
+            ..self
+        }
+    }
+    /// Whether the documentation of the module, and the items in it, has been built.
+    fn is_documented(&self, module_info: &ModuleInfo) -> bool {
+        match (
+            &self.documented_packages,
+            module_info.module_prefixes.first(),
+        ) {
+            (Some(packages), Some(project_name)) => packages.contains(project_name),
+            _ => true,
         }
     }
 }
@@ -42,11 +84,16 @@ struct ProgramInfo<'a> {
     engines: &'a Engines,
     manifest: &'a ManifestFile,
     pkg_manifest: &'a PackageManifestFile,
+    /// The packages whose documentation is built, see [RenderPlan::documented_packages].
+    documented_packages: &'a BTreeSet<String>,
 }
 
 /// Main method for `forc doc`.
 pub fn main() -> Result<()> {
     let build_instructions = Command::parse();
+    if build_instructions.open && build_instructions.format != DocFormat::Html {
+        bail!("`--open` is only supported for documentation in the html format");
+    }
 
     // get manifest directory
     let dir = if let Some(ref path) = build_instructions.manifest_path {
@@ -55,11 +102,6 @@ pub fn main() -> Result<()> {
         std::env::current_dir()?
     };
     let manifest = ManifestFile::from_dir(&dir)?;
-    let pkg_manifest = if let ManifestFile::Package(pkg_manifest) = &manifest {
-        pkg_manifest
-    } else {
-        bail!("forc-doc does not support workspaces.")
-    };
 
     // create doc path
     const DOC_DIR_NAME: &str = "doc";
@@ -70,16 +112,22 @@ pub fn main() -> Result<()> {
     }
     fs::create_dir_all(&doc_path)?;
 
+    let member_manifests = manifest.member_manifests()?;
+    // The packages that are documented even with `--no-deps`.
+    let members: BTreeSet<String> = match &manifest {
+        ManifestFile::Package(pkg_manifest) => {
+            BTreeSet::from([pkg_manifest.project_name().to_string()])
+        }
+        ManifestFile::Workspace(_) => member_manifests.keys().cloned().collect(),
+    };
     println!(
         "   {} {} ({})",
         "Compiling".bold().yellow(),
-        pkg_manifest.project_name(),
+        members.iter().cloned().collect::<Vec<_>>().join(", "),
         manifest.dir().to_string_lossy()
     );
 
-    let member_manifests = manifest.member_manifests()?;
     let lock_path = manifest.lock_path()?;
-
     let ipfs_node = build_instructions.ipfs_node.clone().unwrap_or_default();
     let plan = pkg::BuildPlan::from_lock_and_manifests(
         &lock_path,
@@ -90,8 +138,9 @@ pub fn main() -> Result<()> {
     )?;
 
     let engines = Engines::default();
+    let mut search_index = SearchIndex::default();
     let tests_enabled = build_instructions.document_private_items;
-    let mut compile_results = pkg::check(
+    let compile_results = pkg::check(
         &plan,
         BuildTarget::default(),
         build_instructions.silent,
@@ -99,54 +148,58 @@ pub fn main() -> Result<()> {
         &engines,
     )?;
 
-    if !build_instructions.no_deps {
-        let order = plan.compilation_order();
-        let graph = plan.graph();
-        let manifest_map = plan.manifest_map();
-
-        for (node, compile_result) in order.iter().zip(compile_results) {
-            let id = &graph[*node].id();
-
-            if let Some(pkg_manifest_file) = manifest_map.get(id) {
-                let manifest_file = ManifestFile::from_dir(pkg_manifest_file.path())?;
-                let ty_program = match compile_result.value.and_then(|programs| programs.typed) {
-                    Some(ty_program) => ty_program,
-                    _ => bail!(
-                        "documentation could not be built from manifest located at '{}'",
-                        pkg_manifest_file.path().display()
-                    ),
-                };
-                let program_info = ProgramInfo {
-                    ty_program,
-                    engines: &engines,
-                    manifest: &manifest_file,
-                    pkg_manifest: pkg_manifest_file,
-                };
-
-                build_docs(program_info, &doc_path, &build_instructions)?;
-            }
+    // Packages are documented in the order they were compiled, so that the items of dependencies
+    // are in the search index by the time the intra doc links of their dependents are resolved.
+    let order = plan.compilation_order();
+    let graph = plan.graph();
+    let manifest_map = plan.manifest_map();
+    let mut programs = vec![];
+    for (node, compile_result) in order.iter().zip(compile_results) {
+        let id = &graph[*node].id();
+        let Some(pkg_manifest_file) = manifest_map.get(id) else {
+            continue;
+        };
+        if build_instructions.no_deps && !members.contains(pkg_manifest_file.project_name()) {
+            continue;
         }
-    } else {
-        let ty_program = match compile_results
-            .pop()
-            .and_then(|compilation| compilation.value)
-            .and_then(|programs| programs.typed)
-        {
+        let ty_program = match compile_result.value.and_then(|programs| programs.typed) {
             Some(ty_program) => ty_program,
             _ => bail!(
                 "documentation could not be built from manifest located at '{}'",
-                pkg_manifest.path().display()
+                pkg_manifest_file.path().display()
             ),
         };
+        programs.push((pkg_manifest_file, ty_program));
+    }
+    let documented_packages: BTreeSet<String> = programs
+        .iter()
+        .map(|(pkg_manifest_file, _)| pkg_manifest_file.project_name().to_string())
+        .collect();
+
+    for (pkg_manifest_file, ty_program) in programs {
+        let manifest_file = ManifestFile::from_dir(pkg_manifest_file.path())?;
         let program_info = ProgramInfo {
             ty_program,
             engines: &engines,
-            manifest: &manifest,
-            pkg_manifest,
+            manifest: &manifest_file,
+            pkg_manifest: pkg_manifest_file,
+            documented_packages: &documented_packages,
         };
-        build_docs(program_info, &doc_path, &build_instructions)?;
+        build_docs(
+            program_info,
+            &doc_path,
+            &build_instructions,
+            &mut search_index,
+        )?;
     }
 
+    if build_instructions.format != DocFormat::Html {
+        return Ok(());
+    }
+
+    // The index of all documented items, shared by the packages
+    fs::write(doc_path.join(SEARCH_INDEX_FILENAME), search_index.to_js()?)?;
+
     // CSS, icons and logos
     static ASSETS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/static.files");
     let assets_path = doc_path.join(ASSETS_DIR_NAME);
@@ -160,13 +213,25 @@ pub fn main() -> Result<()> {
     let sway_hjs = std::include_bytes!("static.files/highlight.js");
     fs::write(assets_path.join(SWAY_HJS_FILENAME), sway_hjs)?;
 
+    // The landing page of a workspace, which lists the documented packages
+    if let ManifestFile::Workspace(_) = &manifest {
+        let (workspace_members, dependencies) = documented_packages
+            .into_iter()
+            .partition(|name| members.contains(name));
+        let workspace_index = RenderedDocument::workspace_index(workspace_members, dependencies)?;
+        write_content(RenderedDocumentation(vec![workspace_index]), &doc_path)?;
+    }
+
     // check if the user wants to open the doc in the browser
     // if opening in the browser fails, attempt to open using a file explorer
     if build_instructions.open {
         const BROWSER_ENV_VAR: &str = "BROWSER";
-        let path = doc_path
-            .join(pkg_manifest.project_name())
-            .join(INDEX_FILENAME);
+        let path = match &manifest {
+            ManifestFile::Package(pkg_manifest) => doc_path
+                .join(pkg_manifest.project_name())
+                .join(INDEX_FILENAME),
+            ManifestFile::Workspace(_) => doc_path.join(INDEX_FILENAME),
+        };
         let default_browser_opt = std::env::var_os(BROWSER_ENV_VAR);
         match default_browser_opt {
             Some(def_browser) => {
@@ -194,9 +259,13 @@ fn build_docs(
     program_info: ProgramInfo,
     doc_path: &Path,
     build_instructions: &Command,
+    search_index: &mut SearchIndex,
 ) -> Result<()> {
     let Command {
         document_private_items,
+        silent,
+        deny_warnings,
+        format,
         ..
     } = *build_instructions;
     let ProgramInfo {
@@ -204,6 +273,7 @@ fn build_docs(
         engines,
         manifest,
         pkg_manifest,
+        documented_packages,
     } = program_info;
 
     println!(
@@ -213,27 +283,83 @@ fn build_docs(
         manifest.dir().to_string_lossy()
     );
 
-    let raw_docs = Documentation::from_ty_program(
+    let mut raw_docs = Documentation::from_ty_program(
         engines.de(),
         pkg_manifest.project_name(),
         &ty_program,
         document_private_items,
     )?;
-    let root_attributes =
-        (!ty_program.root.attributes.is_empty()).then_some(ty_program.root.attributes);
+    search_index.extend_from_docs(&raw_docs);
+    // link the items that doc comments refer to, which may be in dependencies
+    let warnings = raw_docs.resolve_intra_doc_links(engines.de(), &ty_program, search_index);
+    if !silent {
+        for warning in &warnings {
+            print_warning(engines.se(), warning);
+        }
+    }
+    if deny_warnings && !warnings.is_empty() {
+        bail!(
+            "documentation for {} has {} warning(s), which are denied",
+            pkg_manifest.project_name(),
+            warnings.len()
+        );
+    }
     let forc_version = pkg_manifest
         .project
         .forc_version
         .as_ref()
         .map(|ver| format!("Forc v{}.{}.{}", ver.major, ver.minor, ver.patch));
+    let source_root = pkg_manifest
+        .entry_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let render_plan = RenderPlan::new(document_private_items, engines, source_root)
+        .with_documented_packages(documented_packages.clone());
+    match format {
+        DocFormat::Html => {}
+        DocFormat::Md | DocFormat::Json => {
+            let json_docs = JsonDocumentation::from_raw_docs(
+                &raw_docs,
+                &ty_program,
+                pkg_manifest.project_name(),
+                &render_plan,
+            );
+            if format == DocFormat::Json {
+                fs::write(
+                    doc_path.join(format!("{}.json", pkg_manifest.project_name())),
+                    serde_json::to_string_pretty(&json_docs)?,
+                )?;
+            } else {
+                for file in render_markdown(&json_docs) {
+                    let path = doc_path.join(file.path);
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(path, file.contents)?;
+                }
+            }
+            println!("    {}", "Finished".bold().yellow());
+            return Ok(());
+        }
+    }
+    let contract_interface = ContractInterface::from_ty_program(engines, &ty_program);
+    let root_attributes =
+        (!ty_program.root.attributes.is_empty()).then_some(ty_program.root.attributes);
     // render docs to HTML
-    let rendered_docs = RenderedDocumentation::from_raw_docs(
+    let mut rendered_docs = RenderedDocumentation::from_raw_docs(
         raw_docs,
-        RenderPlan::new(document_private_items, engines),
+        render_plan.clone(),
         root_attributes,
         ty_program.kind,
         forc_version,
+        contract_interface,
     )?;
+    // render the source files the docs link to
+    rendered_docs.0.extend(render_source_pages(
+        pkg_manifest.project_name(),
+        render_plan,
+    )?);
 
     // write file contents to doc folder
     write_content(rendered_docs, doc_path)?;
@@ -242,6 +368,24 @@ fn build_docs(
     Ok(())
 }
 
+/// Print a warning from building the documentation along with the location it refers to.
+fn print_warning(source_engine: &SourceEngine, warning: &CompileWarning) {
+    let path = warning
+        .source_id()
+        .map(|source_id| source_engine.get_path(&source_id))
+        .unwrap_or_default();
+    let (start, _) = warning.span.line_col();
+    println!(
+        "{}: {}\n   {} {}:{}:{}",
+        "warning".bold().yellow(),
+        warning.to_friendly_warning_string(),
+        "-->".bold(),
+        path.display(),
+        start.line,
+        start.col
+    );
+}
+
 fn write_content(rendered_docs: RenderedDocumentation, doc_path: &Path) -> Result<()> {
     for doc in rendered_docs.0 {
         let mut doc_path = doc_path.to_path_buf();
@@ -1,3 +1,4 @@
 pub(crate) const ALL_DOC_FILENAME: &str = "all.html";
 pub(crate) const INDEX_FILENAME: &str = "index.html";
 pub(crate) const IDENTITY: &str = "#";
+pub(crate) const SEARCH_INDEX_FILENAME: &str = "search-index.js";
@@ -0,0 +1,439 @@
+//! The storage, configurables and ABI of a contract, shown on the page of its root module.
+use crate::{
+    render::{constant::IDENTITY, util::format::docstring::DocStrings, Renderable},
+    RenderPlan,
+};
+use anyhow::Result;
+use horrorshow::{box_html, Raw, RenderBox};
+use sway_core::{
+    decl_engine::{DeclEngine, InterfaceDeclId},
+    ir_generation::storage::get_storage_key,
+    language::ty::{
+        self, TyAbiDecl, TyAstNodeContent, TyConstantDecl, TyFunctionDecl, TyProgram,
+        TyProgramKind, TyStorageField, TyTraitInterfaceItem,
+    },
+    transform::{AttributeKind, AttributesMap},
+    Engines, TypeInfo,
+};
+use sway_types::state::StateIndex;
+
+/// The interface of a contract that callers and deployers work with.
+#[derive(Clone, Debug)]
+pub(crate) struct ContractInterface {
+    pub(crate) storage_fields: Vec<DocStorageField>,
+    pub(crate) configurables: Vec<DocConfigurable>,
+    pub(crate) abi: Option<DocAbi>,
+}
+impl ContractInterface {
+    /// Gather the interface of a contract from its [TyProgram].
+    ///
+    /// Returns `None` if the program isn't a contract.
+    pub(crate) fn from_ty_program(
+        engines: &Engines,
+        typed_program: &TyProgram,
+    ) -> Option<ContractInterface> {
+        let TyProgramKind::Contract { abi_entries } = &typed_program.kind else {
+            return None;
+        };
+        let decl_engine = engines.de();
+
+        let storage_fields = typed_program
+            .root
+            .namespace
+            .module()
+            .get_declared_storage(decl_engine)
+            .map(|storage_decl| {
+                storage_decl
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, field)| DocStorageField::new(ix, field))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut configurables = Vec::new();
+        let mut abi_decl = None;
+        for ast_node in &typed_program.root.all_nodes {
+            match ast_node.content {
+                TyAstNodeContent::Declaration(ty::TyDecl::ConstantDecl(ty::ConstantDecl {
+                    ref decl_id,
+                    ..
+                })) => {
+                    let const_decl = decl_engine.get_constant(decl_id);
+                    if const_decl.is_configurable {
+                        configurables.push(DocConfigurable::new(&const_decl));
+                    }
+                }
+                TyAstNodeContent::Declaration(ty::TyDecl::ImplTrait(ty::ImplTrait {
+                    ref decl_id,
+                    ..
+                })) => {
+                    let impl_trait = decl_engine.get_impl_trait(decl_id);
+                    if let Some(InterfaceDeclId::Abi(abi_id)) = impl_trait
+                        .trait_decl_ref
+                        .as_ref()
+                        .map(|decl_ref| decl_ref.id())
+                    {
+                        abi_decl = Some(decl_engine.get_abi(abi_id));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let abi = abi_decl.map(|abi_decl| DocAbi {
+            name: abi_decl.name.as_str().to_string(),
+            entries: abi_entries
+                .iter()
+                .map(|fn_decl| DocAbiEntry::new(engines, fn_decl, &abi_decl))
+                .collect(),
+        });
+
+        Some(ContractInterface {
+            storage_fields,
+            configurables,
+            abi,
+        })
+    }
+}
+impl Renderable for ContractInterface {
+    fn render(self, _render_plan: RenderPlan) -> Result<Box<dyn RenderBox>> {
+        let ContractInterface {
+            storage_fields,
+            configurables,
+            abi,
+        } = self;
+        Ok(box_html! {
+            @ if !storage_fields.is_empty() {
+                h2(id="storage", class="storage small-section-header") {
+                    : "Storage";
+                    a(class="anchor", href=format!("{IDENTITY}storage"));
+                }
+                @ for field in storage_fields {
+                    : field.render();
+                }
+            }
+            @ if !configurables.is_empty() {
+                h2(id="configurables", class="configurables small-section-header") {
+                    : "Configurables";
+                    a(class="anchor", href=format!("{IDENTITY}configurables"));
+                }
+                @ for configurable in configurables {
+                    : configurable.render();
+                }
+            }
+            @ if let Some(abi) = abi {
+                h2(id="abi", class="abi small-section-header") {
+                    : format!("ABI: {}", abi.name);
+                    a(class="anchor", href=format!("{IDENTITY}abi"));
+                }
+                div(class="methods") {
+                    @ for entry in abi.entries {
+                        : entry.render();
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// A field of the storage of a contract.
+#[derive(Clone, Debug)]
+pub(crate) struct DocStorageField {
+    pub(crate) name: String,
+    pub(crate) type_str: String,
+    pub(crate) initializer: String,
+    /// The key of the first storage slot of the field, derived from its position in the
+    /// storage declaration.
+    pub(crate) slot_key: String,
+    pub(crate) attrs_opt: Option<String>,
+}
+impl DocStorageField {
+    fn new(ix: usize, field: &TyStorageField) -> Self {
+        let slot_key = get_storage_key::<u64>(&StateIndex::new(ix), &[]);
+        Self {
+            name: field.name.as_str().to_string(),
+            type_str: field.type_argument.span.as_str().to_string(),
+            initializer: field.initializer.span.as_str().to_string(),
+            slot_key: format!("{slot_key:#x}"),
+            attrs_opt: docs_opt(&field.attributes),
+        }
+    }
+    fn render(self) -> Box<dyn RenderBox> {
+        let storage_field_id = format!("storagefield.{}", self.name);
+        box_html! {
+            span(id=&storage_field_id, class="storagefield small-section-header") {
+                a(class="anchor field", href=format!("{IDENTITY}{storage_field_id}"));
+                code {
+                    : format!("{}: {} = {}", self.name, self.type_str, self.initializer);
+                }
+            }
+            div(class="item-info") {
+                : "Slot: ";
+                code { : self.slot_key; }
+            }
+            @ if let Some(attrs) = self.attrs_opt {
+                div(class="docblock") {
+                    : Raw(attrs);
+                }
+            }
+        }
+    }
+}
+
+/// A configurable constant of a contract.
+#[derive(Clone, Debug)]
+pub(crate) struct DocConfigurable {
+    pub(crate) name: String,
+    pub(crate) type_str: String,
+    pub(crate) default_opt: Option<String>,
+    pub(crate) attrs_opt: Option<String>,
+}
+impl DocConfigurable {
+    fn new(const_decl: &TyConstantDecl) -> Self {
+        Self {
+            name: const_decl.call_path.suffix.as_str().to_string(),
+            type_str: const_decl.type_ascription.span.as_str().to_string(),
+            default_opt: const_decl
+                .value
+                .as_ref()
+                .map(|value| value.span.as_str().to_string()),
+            attrs_opt: docs_opt(&const_decl.attributes),
+        }
+    }
+    fn render(self) -> Box<dyn RenderBox> {
+        let configurable_id = format!("configurable.{}", self.name);
+        let declaration = match &self.default_opt {
+            Some(default) => format!("{}: {} = {default}", self.name, self.type_str),
+            None => format!("{}: {}", self.name, self.type_str),
+        };
+        box_html! {
+            span(id=&configurable_id, class="configurable small-section-header") {
+                a(class="anchor field", href=format!("{IDENTITY}{configurable_id}"));
+                code { : declaration; }
+            }
+            @ if let Some(attrs) = self.attrs_opt {
+                div(class="docblock") {
+                    : Raw(attrs);
+                }
+            }
+        }
+    }
+}
+
+/// The ABI that a contract implements.
+#[derive(Clone, Debug)]
+pub(crate) struct DocAbi {
+    pub(crate) name: String,
+    pub(crate) entries: Vec<DocAbiEntry>,
+}
+
+/// An entry function of the ABI of a contract.
+#[derive(Clone, Debug)]
+pub(crate) struct DocAbiEntry {
+    pub(crate) name: String,
+    pub(crate) signature: String,
+    /// The function selector that calls to the entry function are dispatched by.
+    pub(crate) selector: String,
+    /// The attributes of the entry function other than its doc comments, e.g. `#[payable]`.
+    pub(crate) attributes: Vec<String>,
+    pub(crate) attrs_opt: Option<String>,
+}
+impl DocAbiEntry {
+    /// The doc comments of the entry function are taken from its implementation, or from its
+    /// declaration in the ABI if the implementation has none.
+    fn new(engines: &Engines, fn_decl: &TyFunctionDecl, abi_decl: &TyAbiDecl) -> Self {
+        let selector = fn_decl
+            .to_fn_selector_value(engines)
+            .value
+            .map(|selector| {
+                selector
+                    .iter()
+                    .fold("0x".to_string(), |acc, byte| format!("{acc}{byte:02x}"))
+            })
+            .unwrap_or_default();
+        let attrs_opt = docs_opt(&fn_decl.attributes).or_else(|| {
+            abi_method_attributes(engines.de(), abi_decl, fn_decl.name.as_str())
+                .and_then(|attributes| docs_opt(&attributes))
+        });
+        Self {
+            name: fn_decl.name.as_str().to_string(),
+            signature: fn_signature(engines, fn_decl),
+            selector,
+            attributes: code_attributes(&fn_decl.attributes),
+            attrs_opt,
+        }
+    }
+    fn render(self) -> Box<dyn RenderBox> {
+        let method_id = format!("abimethod.{}", self.name);
+        box_html! {
+            div(id=&method_id, class="method") {
+                a(href=format!("{IDENTITY}{method_id}"), class="anchor");
+                @ for attribute in self.attributes {
+                    div(class="code-attribute") { : attribute; }
+                }
+                h4(class="code-header") { : self.signature; }
+            }
+            div(class="item-info") {
+                : "Selector: ";
+                code { : self.selector; }
+            }
+            @ if let Some(attrs) = self.attrs_opt {
+                div(class="docblock") {
+                    : Raw(attrs);
+                }
+            }
+        }
+    }
+}
+
+/// The attributes of the method named `name` in the interface surface of an ABI.
+fn abi_method_attributes(
+    decl_engine: &DeclEngine,
+    abi_decl: &TyAbiDecl,
+    name: &str,
+) -> Option<AttributesMap> {
+    abi_decl
+        .interface_surface
+        .iter()
+        .find_map(|item| match item {
+            TyTraitInterfaceItem::TraitFn(decl_ref) => {
+                let trait_fn = decl_engine.get_trait_fn(decl_ref);
+                (trait_fn.name.as_str() == name).then_some(trait_fn.attributes)
+            }
+            TyTraitInterfaceItem::Constant(_) => None,
+        })
+}
+
+/// The signature of a function as written in its declaration, e.g.
+/// `fn transfer(amount: u64, to: Identity) -> bool`.
+fn fn_signature(engines: &Engines, fn_decl: &TyFunctionDecl) -> String {
+    let params = fn_decl
+        .parameters
+        .iter()
+        .map(|param| {
+            let mut param_str = String::new();
+            if param.is_reference {
+                param_str.push_str("ref ");
+            }
+            if param.is_mutable {
+                param_str.push_str("mut ");
+            }
+            if param.is_self() {
+                param_str.push_str("self");
+            } else {
+                param_str.push_str(&format!(
+                    "{}: {}",
+                    param.name.as_str(),
+                    param.type_argument.span.as_str()
+                ));
+            }
+            param_str
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut signature = format!("fn {}({params})", fn_decl.name.as_str());
+    let returns_unit = matches!(
+        engines.te().get(fn_decl.return_type.type_id),
+        TypeInfo::Tuple(ref fields) if fields.is_empty()
+    );
+    if !returns_unit {
+        signature.push_str(&format!(" -> {}", fn_decl.return_type.span.as_str()));
+    }
+    signature
+}
+
+/// The attributes other than doc comments, as they are written in the source, e.g.
+/// `#[storage(read, write)]`. They are sorted so that they are rendered in the same order on
+/// every build.
+fn code_attributes(attributes: &AttributesMap) -> Vec<String> {
+    let mut code_attributes: Vec<String> = attributes
+        .iter()
+        .filter(|(kind, _)| **kind != AttributeKind::DocComment)
+        .flat_map(|(_, attributes)| attributes)
+        .map(|attribute| {
+            let args = attribute
+                .args
+                .iter()
+                .map(|arg| arg.name.as_str())
+                .collect::<Vec<_>>();
+            if args.is_empty() {
+                format!("#[{}]", attribute.name.as_str())
+            } else {
+                format!("#[{}({})]", attribute.name.as_str(), args.join(", "))
+            }
+        })
+        .collect();
+    code_attributes.sort();
+    code_attributes
+}
+
+/// The doc comments in `attributes` rendered to HTML, if there are any.
+fn docs_opt(attributes: &AttributesMap) -> Option<String> {
+    attributes
+        .contains_key(&AttributeKind::DocComment)
+        .then(|| attributes.to_html_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContractInterface;
+    use crate::{render::Renderable, tests::compile_fixture, RenderPlan};
+    use horrorshow::Template;
+
+    #[test]
+    fn test_contract_interface_rendering() {
+        let fixture = compile_fixture("contract_interface");
+        let contract_interface =
+            ContractInterface::from_ty_program(&fixture.engines, &fixture.ty_program).unwrap();
+        let render_plan = RenderPlan::new(false, &fixture.engines, fixture.source_root());
+        let html = contract_interface
+            .render(render_plan)
+            .unwrap()
+            .into_string()
+            .unwrap();
+
+        // The first storage field is stored at the slot of `sha256("storage_0")`.
+        assert!(html.contains(concat!(
+            r#"<span id="storagefield.count" class="storagefield small-section-header">"#,
+            r##"<a class="anchor field" href="#storagefield.count"></a>"##,
+            "<code>count: u64 = 0</code></span>",
+            r#"<div class="item-info">Slot: "#,
+            "<code>0xf383b0ce51358be57daa3b725fe44acdb2d880604e367199080b4379c41bb6ed</code></div>",
+            r#"<div class="docblock"><p>The number of times the counter was incremented.</p>"#,
+        )));
+        assert!(html.contains("<code>enabled: bool = true</code>"));
+        assert!(html.contains(
+            "<code>0xde9090cb50e71c2588c773487d1da7066d0c719849a7e58dc8b6397a25c567c0</code>"
+        ));
+
+        assert!(html.contains(concat!(
+            r#"<span id="configurable.MAX_INCREMENT" class="configurable small-section-header">"#,
+            r##"<a class="anchor field" href="#configurable.MAX_INCREMENT"></a>"##,
+            "<code>MAX_INCREMENT: u64 = 10</code></span>",
+        )));
+
+        assert!(html.contains("ABI: Counter"));
+        assert!(html.contains(concat!(
+            r#"<div class="code-attribute">#[storage(read, write)]</div>"#,
+            r#"<h4 class="code-header">fn increment(amount: u64) -&gt; u64</h4></div>"#,
+            r#"<div class="item-info">Selector: <code>0xe543c666</code></div>"#,
+        )));
+        // The docs of the entry are taken from the ABI declaration.
+        assert!(html.contains("<p>Increments the counter by <code>amount</code>"));
+        assert!(html.contains(concat!(
+            r#"<div class="code-attribute">#[storage(write)]</div>"#,
+            r#"<h4 class="code-header">fn reset()</h4></div>"#,
+            r#"<div class="item-info">Selector: <code>0xd493d79b</code></div>"#,
+        )));
+    }
+
+    #[test]
+    fn test_library_has_no_contract_interface() {
+        let fixture = compile_fixture("impl_traits");
+        assert!(
+            ContractInterface::from_ty_program(&fixture.engines, &fixture.ty_program).is_none()
+        );
+    }
+}
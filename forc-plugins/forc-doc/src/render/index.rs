@@ -1,6 +1,13 @@
 use crate::{
     doc::module::ModuleInfo,
-    render::{constant::IDENTITY, link::DocLinks, sidebar::*, BlockTitle, DocStyle, Renderable},
+    render::{
+        constant::{IDENTITY, INDEX_FILENAME},
+        contract::ContractInterface,
+        link::DocLinks,
+        search::generate_searchbar,
+        sidebar::*,
+        BlockTitle, DocStyle, Renderable,
+    },
     RenderPlan, ASSETS_DIR_NAME,
 };
 use anyhow::Result;
@@ -36,6 +43,7 @@ impl Renderable for AllDocIndex {
     fn render(self, render_plan: RenderPlan) -> Result<Box<dyn RenderBox>> {
         let doc_links = self.all_docs.clone().render(render_plan.clone())?;
         let sidebar = self.sidebar().render(render_plan)?;
+        let searchbar = generate_searchbar(&self.project_name);
         Ok(box_html! {
             head {
                 meta(charset="utf-8");
@@ -57,27 +65,7 @@ impl Renderable for AllDocIndex {
                 : sidebar;
                 main {
                     div(class="width-limiter") {
-                        // div(class="sub-container") {
-                        //     nav(class="sub") {
-                        //         form(class="search-form") {
-                        //             div(class="search-container") {
-                        //                 span;
-                        //                 input(
-                        //                     class="search-input",
-                        //                     name="search",
-                        //                     autocomplete="off",
-                        //                     spellcheck="false",
-                        //                     // TODO: Add functionality.
-                        //                     placeholder="Searchbar unimplemented, see issue #3480...",
-                        //                     type="search"
-                        //                 );
-                        //                 div(id="help-button", title="help", tabindex="-1") {
-                        //                     button(type="button") { : "?" }
-                        //                 }
-                        //             }
-                        //         }
-                        //     }
-                        // }
+                        : searchbar;
                         section(id="main-content", class="content") {
                             h1(class="fqn") {
                                 span(class="in-band") { : "List of all items" }
@@ -101,17 +89,21 @@ pub(crate) struct ModuleIndex {
     version_opt: Option<String>,
     module_info: ModuleInfo,
     module_docs: DocLinks,
+    /// used only for the root module of a contract
+    contract_interface_opt: Option<ContractInterface>,
 }
 impl ModuleIndex {
     pub(crate) fn new(
         version_opt: Option<String>,
         module_info: ModuleInfo,
         module_docs: DocLinks,
+        contract_interface_opt: Option<ContractInterface>,
     ) -> Self {
         Self {
             version_opt,
             module_info,
             module_docs,
+            contract_interface_opt,
         }
     }
 }
@@ -132,6 +124,10 @@ impl SidebarNav for ModuleIndex {
 impl Renderable for ModuleIndex {
     fn render(self, render_plan: RenderPlan) -> Result<Box<dyn RenderBox>> {
         let doc_links = self.module_docs.clone().render(render_plan.clone())?;
+        let contract_interface = match self.contract_interface_opt.clone() {
+            Some(contract_interface) => Some(contract_interface.render(render_plan.clone())?),
+            None => None,
+        };
         let sidebar = self.sidebar().render(render_plan)?;
         let title_prefix = match self.module_docs.style {
             DocStyle::ProjectIndex(ref program_type) => format!("{program_type} "),
@@ -159,6 +155,7 @@ impl Renderable for ModuleIndex {
             .to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/ayu.min.css"));
         let mut rendered_module_anchors = self.module_info.get_anchors()?;
         rendered_module_anchors.pop();
+        let searchbar = generate_searchbar(&self.module_info);
 
         Ok(box_html! {
             head {
@@ -184,27 +181,7 @@ impl Renderable for ModuleIndex {
                 : sidebar;
                 main {
                     div(class="width-limiter") {
-                        // div(class="sub-container") {
-                        //     nav(class="sub") {
-                        //         form(class="search-form") {
-                        //             div(class="search-container") {
-                        //                 span;
-                        //                 input(
-                        //                     class="search-input",
-                        //                     name="search",
-                        //                     autocomplete="off",
-                        //                     spellcheck="false",
-                        //                     // TODO: Add functionality.
-                        //                     placeholder="Searchbar unimplemented, see issue #3480...",
-                        //                     type="search"
-                        //                 );
-                        //                 div(id="help-button", title="help", tabindex="-1") {
-                        //                     button(type="button") { : "?" }
-                        //                 }
-                        //             }
-                        //         }
-                        //     }
-                        // }
+                        : searchbar;
                         section(id="main-content", class="content") {
                             div(class="main-heading") {
                                 h1(class="fqn") {
@@ -230,6 +207,9 @@ impl Renderable for ModuleIndex {
                                 }
                             }
                             : doc_links;
+                            @ if let Some(contract_interface) = contract_interface {
+                                : contract_interface;
+                            }
                         }
                     }
                 }
@@ -241,3 +221,78 @@ impl Renderable for ModuleIndex {
         })
     }
 }
+
+/// The landing page of the documentation of a workspace, at the root of the documentation.
+pub(crate) struct WorkspaceIndex {
+    /// The members of the workspace that are documented.
+    members: Vec<String>,
+    /// The dependencies of the members that are documented.
+    dependencies: Vec<String>,
+}
+impl WorkspaceIndex {
+    pub(crate) fn new(members: Vec<String>, dependencies: Vec<String>) -> Self {
+        Self {
+            members,
+            dependencies,
+        }
+    }
+    /// The landing page doesn't belong to a package, so unlike [Renderable] it doesn't need a
+    /// [RenderPlan].
+    pub(crate) fn render(self) -> Result<Box<dyn RenderBox>> {
+        let searchbar = generate_searchbar(&ModuleInfo::from_ty_module(vec![], None));
+        let sections = [
+            ("Workspace Members", self.members),
+            ("Dependencies", self.dependencies),
+        ];
+        Ok(box_html! {
+            head {
+                meta(charset="utf-8");
+                meta(name="viewport", content="width=device-width, initial-scale=1.0");
+                meta(name="generator", content="swaydoc");
+                meta(
+                    name="description",
+                    content="List of all packages in this workspace"
+                );
+                meta(name="keywords", content="sway, swaylang, sway-lang");
+                link(rel="icon", href=format!("{ASSETS_DIR_NAME}/sway-logo.svg"));
+                title: "List of all packages in this workspace";
+                link(rel="stylesheet", type="text/css", href=format!("{ASSETS_DIR_NAME}/normalize.css"));
+                link(rel="stylesheet", type="text/css", href=format!("{ASSETS_DIR_NAME}/swaydoc.css"), id="mainThemeStyle");
+                link(rel="stylesheet", type="text/css", href=format!("{ASSETS_DIR_NAME}/ayu.css"));
+                link(rel="stylesheet", href=format!("{ASSETS_DIR_NAME}/ayu.min.css"));
+            }
+            body(class="swaydoc mod") {
+                main {
+                    div(class="width-limiter") {
+                        : searchbar;
+                        section(id="main-content", class="content") {
+                            h1(class="fqn") {
+                                span(class="in-band") { : "List of all packages" }
+                            }
+                            @ for (title, packages) in sections {
+                                @ if !packages.is_empty() {
+                                    h2(class="small-section-header") { : title; }
+                                    div(class="item-table") {
+                                        @ for package in packages {
+                                            div(class="item-row") {
+                                                div(class="item-left module-item") {
+                                                    a(class="mod", href=format!("{package}/{INDEX_FILENAME}")) {
+                                                        : package;
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                script(src=format!("{ASSETS_DIR_NAME}/highlight.js"));
+                script {
+                    : "hljs.highlightAll();";
+                }
+            }
+        })
+    }
+}
@@ -1,15 +1,15 @@
 use crate::{
     doc::module::ModuleInfo,
     render::{
-        constant::IDENTITY, item::context::ItemContext, sidebar::*, title::DocBlockTitle, DocStyle,
-        Renderable,
+        constant::IDENTITY, item::context::ItemContext, search::generate_searchbar, sidebar::*,
+        source::source_link, title::DocBlockTitle, DocStyle, Renderable,
     },
     RenderPlan, ASSETS_DIR_NAME,
 };
 use anyhow::Result;
 use horrorshow::{box_html, Raw, RenderBox};
 use sway_core::language::ty::TyDecl;
-use sway_types::BaseIdent;
+use sway_types::{BaseIdent, Spanned};
 
 /// All necessary components to render the header portion of
 /// the item html doc.
@@ -109,11 +109,14 @@ impl Renderable for ItemBody {
         let block_title = ty_decl.as_block_title();
         let sidebar = sidebar.render(render_plan.clone())?;
         let item_context = (item_context.context_opt.is_some()
-            || item_context.impl_traits.is_some())
+            || item_context.impl_traits.is_some()
+            || item_context.implementors.is_some())
         .then(|| -> Result<Box<dyn RenderBox>> { item_context.render(render_plan.clone()) });
         let sway_hjs =
             module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/highlight.js"));
         let rendered_module_anchors = module_info.get_anchors()?;
+        let source_link = source_link(&ty_decl.span(), &module_info, &render_plan);
+        let searchbar = generate_searchbar(&module_info);
 
         Ok(box_html! {
             body(class=format!("swaydoc {decl_ty}")) {
@@ -121,27 +124,7 @@ impl Renderable for ItemBody {
                 // this is the main code block
                 main {
                     div(class="width-limiter") {
-                        // div(class="sub-container") {
-                        //     nav(class="sub") {
-                        //         form(class="search-form") {
-                        //             div(class="search-container") {
-                        //                 span;
-                        //                 input(
-                        //                     class="search-input",
-                        //                     name="search",
-                        //                     autocomplete="off",
-                        //                     spellcheck="false",
-                        //                     // TODO: https://github.com/FuelLabs/sway/issues/3480
-                        //                     placeholder="Searchbar unimplemented, see issue #3480...",
-                        //                     type="search"
-                        //                 );
-                        //                 div(id="help-button", title="help", tabindex="-1") {
-                        //                     button(type="button") { : "?" }
-                        //                 }
-                        //             }
-                        //         }
-                        //     }
-                        // }
+                        : searchbar;
                         section(id="main-content", class="content") {
                             div(class="main-heading") {
                                 h1(class="fqn") {
@@ -155,6 +138,11 @@ impl Renderable for ItemBody {
                                         }
                                     }
                                 }
+                                @ if source_link.is_some() {
+                                    span(class="out-of-band") {
+                                        a(class="srclink", href=source_link.unwrap()) { : "[src]"; }
+                                    }
+                                }
                             }
                             div(class="docblock item-decl") {
                                 pre(class=format!("sway {}", &decl_ty)) {
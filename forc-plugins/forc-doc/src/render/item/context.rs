@@ -33,7 +33,7 @@ use sway_core::language::ty::{
 #[derive(Clone, Debug)]
 pub(crate) struct Context {
     module_info: ModuleInfo,
-    context_type: ContextType,
+    pub(crate) context_type: ContextType,
 }
 impl Context {
     pub(crate) fn new(module_info: ModuleInfo, context_type: ContextType) -> Self {
@@ -264,7 +264,9 @@ pub(crate) struct ItemContext {
     /// [Context] can be fields on a struct, variants of an enum, etc.
     pub(crate) context_opt: Option<Context>,
     /// The traits implemented for this type.
-    pub(crate) impl_traits: Option<Vec<TyImplTrait>>,
+    pub(crate) impl_traits: Option<Vec<DocImplTrait>>,
+    /// The implementations of this trait, including blanket implementations.
+    pub(crate) implementors: Option<Vec<DocImplTrait>>,
     // TODO: All other Implementation types, eg
     // implementations on foreign types, method implementations, etc.
 }
@@ -376,6 +378,17 @@ impl Renderable for ItemContext {
             None => None,
         };
 
+        let implementors = match self.implementors {
+            Some(implementors) => {
+                let mut implementor_vec: Vec<_> = Vec::new();
+                for implementor in implementors {
+                    implementor_vec.push(implementor.render_implementor()?)
+                }
+                Some(implementor_vec)
+            }
+            None => None,
+        };
+
         Ok(box_html! {
             @ if let Some(context) = context_opt {
                 : Raw(context);
@@ -391,38 +404,117 @@ impl Renderable for ItemContext {
                     }
                 }
             }
+            @ if implementors.is_some() {
+                h2(id="implementors", class="small-section-header") {
+                    : "Implementors";
+                    a(href=format!("{IDENTITY}implementors"), class="anchor");
+                }
+                div(id="implementors-list") {
+                    @ for implementor in implementors.unwrap() {
+                        : implementor;
+                    }
+                }
+            }
         })
     }
 }
-impl Renderable for TyImplTrait {
-    fn render(self, render_plan: RenderPlan) -> Result<Box<dyn RenderBox>> {
-        let TyImplTrait {
-            trait_name,
-            impl_type_parameters: _,
-            trait_type_arguments: _,
-            items,
-            trait_decl_ref: _,
-            implementing_for,
-            ..
-        } = self;
-
-        let mut rendered_items = Vec::new();
-        for item in items {
-            rendered_items.push(item.render(render_plan.clone())?)
+/// A trait implementation as it is listed on the page of the implementing type or of the trait.
+#[derive(Clone, Debug)]
+pub(crate) struct DocImplTrait {
+    pub(crate) impl_trait: TyImplTrait,
+    /// The path to the page of the implemented trait from the page the implementation is listed
+    /// on, if the trait is documented in the same package.
+    pub(crate) trait_link: Option<String>,
+}
+impl DocImplTrait {
+    /// The generic parameters and the where clause of the implementation, as written in the
+    /// source, e.g. `<T>` and `where T: Foo`.
+    fn generics_and_where_clause(&self) -> (String, String) {
+        let span = self.impl_trait.span.as_str();
+        let header = span
+            .split('{')
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut generics = String::new();
+        if let Some(rest) = header.strip_prefix("impl<") {
+            let mut depth = 1;
+            generics.push('<');
+            for c in rest.chars() {
+                generics.push(c);
+                match c {
+                    '<' => depth += 1,
+                    '>' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
         }
-
-        let impl_for = box_html! {
-                div(id=format!("impl-{}", trait_name.suffix.as_str()), class="impl has-srclink") {
-                a(href=format!("{IDENTITY}impl-{}", trait_name.suffix.as_str()), class="anchor");
+        let where_clause = header
+            .find(" where ")
+            .map(|index| header[index + 1..].to_string())
+            .unwrap_or_default();
+        (generics, where_clause)
+    }
+    /// The header of the implementation as plain text, e.g. `impl<T> Foo for T where T: Bar`.
+    pub(crate) fn header_str(&self) -> String {
+        let (generics, where_clause) = self.generics_and_where_clause();
+        let mut header = format!(
+            "impl{generics} {} for {}",
+            self.impl_trait.trait_name.suffix.as_str(),
+            self.impl_trait.implementing_for.span.as_str()
+        );
+        if !where_clause.is_empty() {
+            header.push_str(&format!(" {where_clause}"));
+        }
+        header
+    }
+    /// Render the header of the implementation, linking to the implemented trait.
+    fn render_header(&self) -> Result<String> {
+        let trait_name = self.impl_trait.trait_name.suffix.as_str().to_string();
+        let implementing_for = self.impl_trait.implementing_for.span.as_str().to_string();
+        let (generics, where_clause) = self.generics_and_where_clause();
+        let trait_link = self.trait_link.clone();
+        Ok(box_html! {
+            div(id=format!("impl-{trait_name}-for-{implementing_for}"), class="impl has-srclink") {
+                a(href=format!("{IDENTITY}impl-{trait_name}-for-{implementing_for}"), class="anchor");
                 h3(class="code-header in-band") {
-                    : "impl ";
-                    : trait_name.suffix.as_str(); // TODO: add links
-                    : " for ";
-                    : implementing_for.span.as_str();
+                    : format!("impl{generics} ");
+                    @ if trait_link.is_some() {
+                        a(class="trait", href=trait_link.unwrap()) { : &trait_name; }
+                    } else {
+                        : &trait_name;
+                    }
+                    : format!(" for {implementing_for}");
+                    @ if !where_clause.is_empty() {
+                        : format!(" {where_clause}");
+                    }
                 }
             }
         }
-        .into_string()?;
+        .into_string()?)
+    }
+    /// Render the implementation as it is listed in the implementors of the trait, which
+    /// leaves out its methods.
+    fn render_implementor(self) -> Result<Box<dyn RenderBox>> {
+        let header = self.render_header()?;
+        Ok(box_html! {
+            : Raw(header);
+        })
+    }
+}
+impl Renderable for DocImplTrait {
+    fn render(self, render_plan: RenderPlan) -> Result<Box<dyn RenderBox>> {
+        let impl_for = self.render_header()?;
+
+        let mut rendered_items = Vec::new();
+        for item in self.impl_trait.items {
+            rendered_items.push(item.render(render_plan.clone())?)
+        }
 
         Ok(box_html! {
             // check if the implementation has methods
@@ -579,3 +671,119 @@ impl DocBlockTitle for ContextType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::Documentation,
+        render::Renderable,
+        tests::{compile_fixture, compile_workspace_fixture, Fixture},
+        RenderPlan,
+    };
+    use horrorshow::Template;
+    use std::{collections::BTreeSet, path::PathBuf};
+
+    /// Render the context section of the page of the item named `item_name`.
+    fn render_item_context(fixture: &Fixture, item_name: &str) -> String {
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            fixture.pkg_manifest.project_name(),
+            &fixture.ty_program,
+            false,
+        )
+        .unwrap();
+        let doc = docs
+            .0
+            .into_iter()
+            .find(|doc| doc.item_header.item_name.as_str() == item_name)
+            .unwrap();
+        let render_plan = RenderPlan::new(false, &fixture.engines, fixture.source_root());
+        doc.item_body
+            .item_context
+            .render(render_plan)
+            .unwrap()
+            .into_string()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_trait_implementations_on_type_page() {
+        let fixture = compile_fixture("trait_impls");
+        let html = render_item_context(&fixture, "Square");
+        assert!(html.contains(
+            r#"<h3 class="code-header in-band">impl <a class="trait" href="../trait_impls/trait.Shape.html">Shape</a> for Square</h3>"#
+        ));
+        assert!(html.contains(r#"<div id="method.sides" class="method trait-impl">"#));
+        // Blanket implementations are only listed on the page of the trait.
+        assert!(!html.contains("Describe"));
+    }
+
+    #[test]
+    fn test_implementors_on_trait_page() {
+        let fixture = compile_fixture("trait_impls");
+        let html = render_item_context(&fixture, "Shape");
+        let square = html
+            .find(r#"<h3 class="code-header in-band">impl <a class="trait" href="../trait_impls/trait.Shape.html">Shape</a> for Square</h3>"#)
+            .unwrap();
+        let triangle = html
+            .find(r#"<h3 class="code-header in-band">impl <a class="trait" href="../trait_impls/trait.Shape.html">Shape</a> for Triangle</h3>"#)
+            .unwrap();
+        assert!(square < triangle);
+        // Implementors are listed without their methods.
+        assert!(!html.contains("trait-impl"));
+
+        let html = render_item_context(&fixture, "Describe");
+        assert!(html.contains(
+            r#"<h3 class="code-header in-band">impl&lt;T&gt; <a class="trait" href="../trait_impls/trait.Describe.html">Describe</a> for T where T: Shape</h3>"#
+        ));
+    }
+
+    #[test]
+    fn test_struct_field_links_to_other_workspace_member() {
+        let fixture = compile_workspace_fixture("workspace_docs");
+        let (pkg_manifest, ty_program) = fixture
+            .members
+            .iter()
+            .find(|(pkg_manifest, _)| pkg_manifest.project_name() == "member_a")
+            .unwrap();
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            pkg_manifest.project_name(),
+            ty_program,
+            false,
+        )
+        .unwrap();
+        let wallet = docs
+            .0
+            .into_iter()
+            .find(|doc| doc.item_header.item_name.as_str() == "Wallet")
+            .unwrap();
+        let render_context = |documented_packages: &[&str]| {
+            let documented_packages: BTreeSet<String> = documented_packages
+                .iter()
+                .map(|name| name.to_string())
+                .collect();
+            let render_plan = RenderPlan::new(false, &fixture.engines, PathBuf::new())
+                .with_documented_packages(documented_packages);
+            wallet
+                .item_body
+                .item_context
+                .clone()
+                .render(render_plan)
+                .unwrap()
+                .into_string()
+                .unwrap()
+        };
+
+        let html = render_context(&["member_a", "member_b"]);
+        assert!(
+            html.contains(r#"<a class="struct" href="../member_b/struct.Token.html">Token</a>"#)
+        );
+
+        // Without the docs of `member_b`, as with `--no-deps` outside of the workspace, the type
+        // isn't linked rather than linking to a missing page.
+        let html = render_context(&["member_a"]);
+        assert!(html.contains("Token"));
+        assert!(!html.contains("member_b/struct.Token.html"));
+    }
+}
@@ -51,12 +51,14 @@ pub(crate) fn render_type_anchor(
         }
         TypeInfo::Enum(decl_ref) => {
             let enum_decl = render_plan.engines.de().get_enum(&decl_ref);
-            if !render_plan.document_private_items && enum_decl.visibility.is_private() {
+            let module_info = ModuleInfo::from_call_path(enum_decl.call_path);
+            if (!render_plan.document_private_items && enum_decl.visibility.is_private())
+                || !render_plan.is_documented(&module_info)
+            {
                 Ok(box_html! {
                     : decl_ref.name().clone().as_str();
                 })
             } else {
-                let module_info = ModuleInfo::from_call_path(enum_decl.call_path);
                 let file_name = format!("enum.{}.html", decl_ref.name().clone().as_str());
                 let href = module_info.file_path_from_location(&file_name, current_module_info)?;
                 Ok(box_html! {
@@ -68,12 +70,14 @@ pub(crate) fn render_type_anchor(
         }
         TypeInfo::Struct(decl_ref) => {
             let struct_decl = render_plan.engines.de().get_struct(&decl_ref);
-            if !render_plan.document_private_items && struct_decl.visibility.is_private() {
+            let module_info = ModuleInfo::from_call_path(struct_decl.call_path);
+            if (!render_plan.document_private_items && struct_decl.visibility.is_private())
+                || !render_plan.is_documented(&module_info)
+            {
                 Ok(box_html! {
                     : decl_ref.name().clone().as_str();
                 })
             } else {
-                let module_info = ModuleInfo::from_call_path(struct_decl.call_path);
                 let file_name = format!("struct.{}.html", decl_ref.name().clone().as_str());
                 let href = module_info.file_path_from_location(&file_name, current_module_info)?;
                 Ok(box_html! {
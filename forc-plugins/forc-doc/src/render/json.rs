@@ -0,0 +1,475 @@
+//! The documentation of a package as a JSON document, for custom renderers.
+//!
+//! The schema is made of the types in this module and is stable within a
+//! [JSON_FORMAT_VERSION]. Every field is always present, with `null` for a missing value:
+//!
+//! ```json
+//! {
+//!   "format_version": 1,
+//!   "name": "package_name",
+//!   "program_kind": "library",
+//!   "modules": [{
+//!     "path": ["package_name", "module"],
+//!     "docs": "Markdown of the module's doc comments, or null",
+//!     "items": [{
+//!       "name": "Item",
+//!       "kind": "struct",
+//!       "signature": "pub struct Item {\n    field: u64,\n}",
+//!       "docs": "Markdown of the item's doc comments, or null",
+//!       "source": {
+//!         "file": "module.sw",
+//!         "start": { "line": 3, "col": 1 },
+//!         "end": { "line": 5, "col": 2 }
+//!       },
+//!       "members": [{ "kind": "field", "name": "field", "signature": "field: u64", "docs": null }],
+//!       "impls": [{ "trait_name": "Trait", "signature": "impl Trait for Item", "methods": ["method"] }],
+//!       "implementors": []
+//!     }]
+//!   }]
+//! }
+//! ```
+use crate::{
+    doc::{module::ModulePrefixes, Document, Documentation},
+    render::item::context::{ContextType, DocImplTrait},
+    RenderPlan,
+};
+use serde::{Deserialize, Serialize};
+use sway_core::{
+    language::ty::{TyModule, TyProgram, TyTraitFn, TyTraitItem},
+    transform::{AttributeKind, AttributesMap},
+    TypeInfo,
+};
+use sway_lsp::utils::markdown::format_docs;
+use sway_types::{Span, Spanned};
+
+/// The version of the schema of the JSON documentation. It is increased whenever a field is
+/// removed or changes its meaning, so that renderers can reject documentation they don't
+/// understand.
+pub(crate) const JSON_FORMAT_VERSION: u32 = 1;
+
+/// The documentation of a package.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonDocumentation {
+    /// The [JSON_FORMAT_VERSION] the documentation was written with.
+    pub(crate) format_version: u32,
+    /// The name of the package.
+    pub(crate) name: String,
+    /// The kind of the program, one of `library`, `contract`, `script` or `predicate`.
+    pub(crate) program_kind: String,
+    /// The modules of the package, starting with the root module, each followed by its
+    /// submodules.
+    pub(crate) modules: Vec<JsonModule>,
+}
+
+/// A module and the items declared in it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonModule {
+    /// The path of the module, starting with the package name.
+    pub(crate) path: ModulePrefixes,
+    /// The markdown of the doc comments of the module.
+    pub(crate) docs: Option<String>,
+    pub(crate) items: Vec<JsonItem>,
+}
+
+/// A documented item, such as a struct or a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonItem {
+    pub(crate) name: String,
+    /// The kind of the item, one of `struct`, `enum`, `trait`, `abi`, `contract_storage`,
+    /// `fn` or `constant`.
+    pub(crate) kind: String,
+    /// The declaration of the item as it is shown in the documentation, without the bodies of
+    /// functions.
+    pub(crate) signature: String,
+    /// The markdown of the doc comments of the item.
+    pub(crate) docs: Option<String>,
+    /// Where the item is declared in the sources of the package, if it is.
+    pub(crate) source: Option<JsonSource>,
+    /// The fields of structs and storage, the variants of enums or the required methods of
+    /// traits and ABIs.
+    pub(crate) members: Vec<JsonMember>,
+    /// The traits implemented for a struct or an enum.
+    pub(crate) impls: Vec<JsonImpl>,
+    /// The implementations of a trait.
+    pub(crate) implementors: Vec<JsonImpl>,
+}
+
+/// A field, variant or required method of an item.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonMember {
+    /// The kind of the member, one of `field`, `variant` or `method`.
+    pub(crate) kind: String,
+    pub(crate) name: String,
+    /// The member as it is declared, e.g. `field: u64` or `fn method(self) -> u64`.
+    pub(crate) signature: String,
+    /// The markdown of the doc comments of the member.
+    pub(crate) docs: Option<String>,
+}
+
+/// An implementation of a trait.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonImpl {
+    pub(crate) trait_name: String,
+    /// The header of the implementation, e.g. `impl<T> Trait for T where T: Other`.
+    pub(crate) signature: String,
+    /// The names of the methods the implementation defines.
+    pub(crate) methods: Vec<String>,
+}
+
+/// The location of a declaration in the sources of a package.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonSource {
+    /// The path of the file, relative to the directory of the entry file of the package.
+    pub(crate) file: String,
+    pub(crate) start: JsonPosition,
+    pub(crate) end: JsonPosition,
+}
+
+/// A position in a source file. Lines and columns are counted in characters, starting at 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JsonPosition {
+    pub(crate) line: usize,
+    pub(crate) col: usize,
+}
+
+impl JsonDocumentation {
+    /// Gather the [JsonDocumentation] of a program from its [Documentation], which already
+    /// leaves out the private items unless they are documented.
+    pub(crate) fn from_raw_docs(
+        raw_docs: &Documentation,
+        typed_program: &TyProgram,
+        project_name: &str,
+        render_plan: &RenderPlan,
+    ) -> Self {
+        let mut modules = vec![];
+        collect_modules(
+            vec![project_name.to_string()],
+            &typed_program.root,
+            &mut modules,
+        );
+        for doc in &raw_docs.0 {
+            if let Some(module) = modules
+                .iter_mut()
+                .find(|module| module.path == doc.module_info.module_prefixes)
+            {
+                module.items.push(JsonItem::from_doc(doc, render_plan));
+            }
+        }
+        Self {
+            format_version: JSON_FORMAT_VERSION,
+            name: project_name.to_string(),
+            program_kind: typed_program.kind.as_title_str().to_lowercase(),
+            modules,
+        }
+    }
+}
+
+/// Add the module at `path` and its submodules to `modules`, without their items.
+fn collect_modules(path: ModulePrefixes, ty_module: &TyModule, modules: &mut Vec<JsonModule>) {
+    modules.push(JsonModule {
+        path: path.clone(),
+        docs: docs_to_markdown(&ty_module.attributes),
+        items: vec![],
+    });
+    for (_, submodule) in &ty_module.submodules {
+        let mut submodule_path = path.clone();
+        submodule_path.push(submodule.mod_name_span.as_str().to_string());
+        collect_modules(submodule_path, &submodule.module, modules);
+    }
+}
+
+impl JsonItem {
+    fn from_doc(doc: &Document, render_plan: &RenderPlan) -> Self {
+        let engines = render_plan.engines;
+        let item_context = &doc.item_body.item_context;
+        let members = match item_context
+            .context_opt
+            .as_ref()
+            .map(|context| &context.context_type)
+        {
+            Some(ContextType::StructFields(fields)) => fields
+                .iter()
+                .map(|field| JsonMember {
+                    kind: "field".to_string(),
+                    name: field.name.as_str().to_string(),
+                    signature: format!(
+                        "{}: {}",
+                        field.name.as_str(),
+                        field.type_argument.span.as_str()
+                    ),
+                    docs: docs_to_markdown(&field.attributes),
+                })
+                .collect(),
+            Some(ContextType::StorageFields(fields)) => fields
+                .iter()
+                .map(|field| JsonMember {
+                    kind: "field".to_string(),
+                    name: field.name.as_str().to_string(),
+                    signature: format!(
+                        "{}: {} = {}",
+                        field.name.as_str(),
+                        field.type_argument.span.as_str(),
+                        field.initializer.span.as_str()
+                    ),
+                    docs: docs_to_markdown(&field.attributes),
+                })
+                .collect(),
+            Some(ContextType::EnumVariants(variants)) => variants
+                .iter()
+                .map(|variant| JsonMember {
+                    kind: "variant".to_string(),
+                    name: variant.name.as_str().to_string(),
+                    signature: format!(
+                        "{}: {}",
+                        variant.name.as_str(),
+                        variant.type_argument.span.as_str()
+                    ),
+                    docs: docs_to_markdown(&variant.attributes),
+                })
+                .collect(),
+            Some(ContextType::RequiredMethods(methods)) => methods
+                .iter()
+                .map(|method| JsonMember {
+                    kind: "method".to_string(),
+                    name: method.name.as_str().to_string(),
+                    signature: trait_fn_signature(render_plan, method),
+                    docs: docs_to_markdown(&method.attributes),
+                })
+                .collect(),
+            None => vec![],
+        };
+        let impls = |impl_traits: &Option<Vec<DocImplTrait>>| {
+            impl_traits
+                .iter()
+                .flatten()
+                .map(JsonImpl::from_doc_impl_trait)
+                .collect()
+        };
+
+        Self {
+            name: doc.item_header.item_name.as_str().to_string(),
+            kind: doc.item_body.ty_decl.doc_name().to_string(),
+            signature: doc.item_body.code_str.trim().to_string(),
+            docs: doc
+                .attributes(engines.de())
+                .and_then(|attributes| docs_to_markdown(&attributes)),
+            source: JsonSource::from_span(&doc.item_body.ty_decl.span(), render_plan),
+            members,
+            impls: impls(&item_context.impl_traits),
+            implementors: impls(&item_context.implementors),
+        }
+    }
+}
+
+impl JsonImpl {
+    fn from_doc_impl_trait(doc_impl_trait: &DocImplTrait) -> Self {
+        Self {
+            trait_name: doc_impl_trait
+                .impl_trait
+                .trait_name
+                .suffix
+                .as_str()
+                .to_string(),
+            signature: doc_impl_trait.header_str(),
+            methods: doc_impl_trait
+                .impl_trait
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    TyTraitItem::Fn(decl_ref) => Some(decl_ref.name().as_str().to_string()),
+                    TyTraitItem::Constant(_) => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl JsonSource {
+    /// Returns `None` if the span is not part of the sources of the package being documented.
+    fn from_span(span: &Span, render_plan: &RenderPlan) -> Option<Self> {
+        let path = render_plan.engines.se().get_path(span.source_id()?);
+        let relative_path = path.strip_prefix(&render_plan.source_root).ok()?;
+        let (start, end) = span.line_col();
+        Some(Self {
+            file: relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            start: JsonPosition {
+                line: start.line,
+                col: start.col,
+            },
+            end: JsonPosition {
+                line: end.line,
+                col: end.col,
+            },
+        })
+    }
+}
+
+/// The signature of a required method, e.g. `fn method(self, value: u64) -> u64`.
+///
+/// The return type is left out if it is `()`.
+fn trait_fn_signature(render_plan: &RenderPlan, method: &TyTraitFn) -> String {
+    let params = method
+        .parameters
+        .iter()
+        .map(|param| {
+            let mut param_str = String::new();
+            if param.is_reference {
+                param_str.push_str("ref ");
+            }
+            if param.is_mutable {
+                param_str.push_str("mut ");
+            }
+            if param.is_self() {
+                param_str.push_str("self");
+            } else {
+                param_str.push_str(&format!(
+                    "{}: {}",
+                    param.name.as_str(),
+                    param.type_argument.span.as_str()
+                ));
+            }
+            param_str
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut signature = format!("fn {}({params})", method.name.as_str());
+    let returns_unit = matches!(
+        render_plan.engines.te().get(method.return_type.type_id),
+        TypeInfo::Tuple(ref fields) if fields.is_empty()
+    );
+    if !returns_unit {
+        signature.push_str(&format!(" -> {}", method.return_type.span.as_str()));
+    }
+    signature
+}
+
+/// The markdown of the doc comments in `attributes`, with the space that follows `///` removed
+/// from each line.
+///
+/// Returns `None` if there are no doc comments.
+pub(crate) fn docs_to_markdown(attributes: &AttributesMap) -> Option<String> {
+    let lines: Vec<&str> = attributes
+        .get(&AttributeKind::DocComment)?
+        .iter()
+        .flat_map(|attribute| &attribute.args)
+        .map(|arg| {
+            let line = arg.name.as_str();
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect();
+    let docs = format_docs(&lines.join("\n")).trim().to_string();
+    (!docs.is_empty()).then_some(docs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonDocumentation, JSON_FORMAT_VERSION};
+    use crate::{doc::Documentation, tests::compile_fixture, RenderPlan};
+
+    fn json_docs_for(fixture: &str, document_private_items: bool) -> JsonDocumentation {
+        let fixture = compile_fixture(fixture);
+        let project_name = fixture.pkg_manifest.project_name();
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            project_name,
+            &fixture.ty_program,
+            document_private_items,
+        )
+        .unwrap();
+        let render_plan = RenderPlan::new(
+            document_private_items,
+            &fixture.engines,
+            fixture.source_root(),
+        );
+        JsonDocumentation::from_raw_docs(&docs, &fixture.ty_program, project_name, &render_plan)
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let json_docs = json_docs_for("markdown_docs", false);
+        let json = serde_json::to_value(&json_docs).unwrap();
+
+        assert_eq!(json["format_version"], JSON_FORMAT_VERSION);
+        assert_eq!(json["name"], "markdown_docs");
+        assert_eq!(json["program_kind"], "library");
+
+        let root = &json["modules"][0];
+        assert_eq!(root["path"], serde_json::json!(["markdown_docs"]));
+        assert_eq!(root["docs"], "Shapes and the traits that describe them.");
+        let shapes = &json["modules"][1];
+        assert_eq!(
+            shapes["path"],
+            serde_json::json!(["markdown_docs", "shapes"])
+        );
+
+        let square = shapes["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["name"] == "Square")
+            .unwrap();
+        assert_eq!(square["kind"], "struct");
+        assert_eq!(
+            square["signature"],
+            "pub struct Square {\n    side: u64,\n}"
+        );
+        assert_eq!(square["docs"], "A shape with four equal sides.");
+        assert_eq!(
+            square["source"],
+            serde_json::json!({
+                "file": "shapes.sw",
+                "start": { "line": 4, "col": 1 },
+                "end": { "line": 6, "col": 2 },
+            })
+        );
+        assert_eq!(
+            square["members"],
+            serde_json::json!([{
+                "kind": "field",
+                "name": "side",
+                "signature": "side: u64",
+                "docs": null,
+            }])
+        );
+        assert_eq!(
+            square["impls"],
+            serde_json::json!([{
+                "trait_name": "Shape",
+                "signature": "impl Shape for Square",
+                "methods": ["sides"],
+            }])
+        );
+        assert_eq!(square["implementors"], serde_json::json!([]));
+
+        // Every field is written, even if it has no value.
+        let fields = ["docs", "source", "members", "impls", "implementors"];
+        for item in shapes["items"].as_array().unwrap() {
+            for field in fields {
+                assert!(item.get(field).is_some(), "missing `{field}` in {item}");
+            }
+        }
+
+        let deserialized: JsonDocumentation = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, json_docs);
+    }
+
+    #[test]
+    fn test_json_private_items() {
+        let item_names = |json_docs: &JsonDocumentation| -> Vec<String> {
+            json_docs
+                .modules
+                .iter()
+                .flat_map(|module| &module.items)
+                .map(|item| item.name.clone())
+                .collect()
+        };
+        let public = json_docs_for("markdown_docs", false);
+        assert!(!item_names(&public).contains(&"area".to_string()));
+        let private = json_docs_for("markdown_docs", true);
+        assert!(item_names(&private).contains(&"area".to_string()));
+    }
+}
@@ -0,0 +1,193 @@
+//! The documentation of a package as markdown, with a file for each module.
+use crate::{
+    doc::module::ModulePrefixes,
+    render::{
+        json::{JsonDocumentation, JsonImpl, JsonItem, JsonMember, JsonModule},
+        title::BlockTitle,
+    },
+};
+use std::{collections::BTreeMap, fmt::Write, path::PathBuf};
+
+/// The name of the markdown file of each module, in the directory of the module.
+pub(crate) const MARKDOWN_INDEX_FILENAME: &str = "index.md";
+
+/// A module's documentation rendered to markdown.
+#[derive(Debug)]
+pub(crate) struct MarkdownFile {
+    /// The path of the file, relative to the documentation root, e.g. `project/module/index.md`.
+    pub(crate) path: PathBuf,
+    pub(crate) contents: String,
+}
+
+/// Render every module of the documentation to a markdown file, laid out in the same
+/// directories as the HTML pages.
+pub(crate) fn render_markdown(json_docs: &JsonDocumentation) -> Vec<MarkdownFile> {
+    json_docs
+        .modules
+        .iter()
+        .map(|module| {
+            let mut path: PathBuf = module.path.iter().collect();
+            path.push(MARKDOWN_INDEX_FILENAME);
+            MarkdownFile {
+                path,
+                contents: render_module(json_docs, module),
+            }
+        })
+        .collect()
+}
+
+fn render_module(json_docs: &JsonDocumentation, module: &JsonModule) -> String {
+    let mut md = String::new();
+    if module.path.len() == 1 {
+        let mut program_kind = json_docs.program_kind.chars();
+        let program_kind = program_kind
+            .next()
+            .map(|first| first.to_uppercase().chain(program_kind).collect::<String>())
+            .unwrap_or_default();
+        writeln!(md, "# {program_kind} {}", json_docs.name).unwrap();
+    } else {
+        writeln!(md, "# Module {}", module.path.join("::")).unwrap();
+    }
+    if let Some(docs) = &module.docs {
+        write!(md, "\n{docs}\n").unwrap();
+    }
+
+    let submodules: Vec<&ModulePrefixes> = json_docs
+        .modules
+        .iter()
+        .map(|submodule| &submodule.path)
+        .filter(|path| path.len() == module.path.len() + 1 && path.starts_with(&module.path))
+        .collect();
+    if !submodules.is_empty() {
+        write!(md, "\n## {}\n\n", BlockTitle::Modules.as_str()).unwrap();
+        for path in submodules {
+            let name = path.last().expect("submodules have a name");
+            writeln!(md, "- [{name}]({name}/{MARKDOWN_INDEX_FILENAME})").unwrap();
+        }
+    }
+
+    // Items are listed in the same sections as on the HTML index of the module.
+    let mut sections: BTreeMap<BlockTitle, Vec<&JsonItem>> = BTreeMap::new();
+    for item in &module.items {
+        sections
+            .entry(block_title(&item.kind))
+            .or_default()
+            .push(item);
+    }
+    for (title, items) in sections {
+        write!(md, "\n## {}\n", title.as_str()).unwrap();
+        for item in items {
+            render_item(&mut md, item);
+        }
+    }
+    md
+}
+
+fn render_item(md: &mut String, item: &JsonItem) {
+    write!(
+        md,
+        "\n### {}\n\n```sway\n{}\n```\n",
+        item.name, item.signature
+    )
+    .unwrap();
+    if let Some(docs) = &item.docs {
+        write!(md, "\n{docs}\n").unwrap();
+    }
+    if let Some(member) = item.members.first() {
+        write!(md, "\n#### {}\n\n", member_title(member).as_str()).unwrap();
+        for member in &item.members {
+            writeln!(md, "- `{}`", member.signature).unwrap();
+            if let Some(docs) = &member.docs {
+                write!(md, "\n{}\n", indent(docs)).unwrap();
+            }
+        }
+    }
+    render_impls(md, "Trait Implementations", &item.impls);
+    render_impls(md, "Implementors", &item.implementors);
+}
+
+fn render_impls(md: &mut String, title: &str, impls: &[JsonImpl]) {
+    if impls.is_empty() {
+        return;
+    }
+    write!(md, "\n#### {title}\n\n").unwrap();
+    for json_impl in impls {
+        writeln!(md, "- `{}`", json_impl.signature).unwrap();
+    }
+}
+
+/// The section of the module an item of `kind` is listed in.
+fn block_title(kind: &str) -> BlockTitle {
+    match kind {
+        "struct" => BlockTitle::Structs,
+        "enum" => BlockTitle::Enums,
+        "trait" => BlockTitle::Traits,
+        "abi" => BlockTitle::Abi,
+        "contract_storage" => BlockTitle::ContractStorage,
+        "constant" => BlockTitle::Constants,
+        _ => BlockTitle::Functions,
+    }
+}
+
+fn member_title(member: &JsonMember) -> BlockTitle {
+    match member.kind.as_str() {
+        "variant" => BlockTitle::Variants,
+        "method" => BlockTitle::RequiredMethods,
+        _ => BlockTitle::Fields,
+    }
+}
+
+/// Indent the lines of some docs so that they belong to the list item above them.
+fn indent(docs: &str) -> String {
+    docs.lines()
+        .map(|line| match line.is_empty() {
+            true => String::new(),
+            false => format!("  {line}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_markdown;
+    use crate::{
+        doc::Documentation, render::json::JsonDocumentation, tests::compile_fixture, RenderPlan,
+    };
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_markdown_matches_golden_files() {
+        let fixture = compile_fixture("markdown_docs");
+        let project_name = fixture.pkg_manifest.project_name();
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            project_name,
+            &fixture.ty_program,
+            false,
+        )
+        .unwrap();
+        let render_plan = RenderPlan::new(false, &fixture.engines, fixture.source_root());
+        let json_docs = JsonDocumentation::from_raw_docs(
+            &docs,
+            &fixture.ty_program,
+            project_name,
+            &render_plan,
+        );
+
+        let golden_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/tests/golden");
+        let files = render_markdown(&json_docs);
+        let paths: Vec<&Path> = files.iter().map(|file| file.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("markdown_docs/index.md"),
+                Path::new("markdown_docs/shapes/index.md"),
+            ]
+        );
+        for file in files {
+            let golden = std::fs::read_to_string(golden_dir.join(&file.path)).unwrap();
+            assert_eq!(file.contents, golden, "{} differs", file.path.display());
+        }
+    }
+}
@@ -5,7 +5,8 @@ use crate::{
     },
     render::{
         constant::{ALL_DOC_FILENAME, INDEX_FILENAME},
-        index::{AllDocIndex, ModuleIndex},
+        contract::ContractInterface,
+        index::{AllDocIndex, ModuleIndex, WorkspaceIndex},
         link::*,
         title::*,
         util::format::docstring::DocStrings,
@@ -19,10 +20,15 @@ use sway_core::{language::ty::TyProgramKind, transform::AttributesMap};
 use sway_types::BaseIdent;
 
 pub mod constant;
+pub mod contract;
 mod index;
 pub mod item;
+pub mod json;
 pub mod link;
+pub mod markdown;
+pub mod search;
 mod sidebar;
+pub mod source;
 mod title;
 pub mod util;
 
@@ -55,6 +61,16 @@ impl RenderedDocument {
             file_contents: HTMLString::from_rendered_content(doc.clone().render(render_plan)?)?,
         })
     }
+    /// The landing page of the documentation of a workspace, which lists the packages that are
+    /// documented.
+    pub(crate) fn workspace_index(members: Vec<String>, dependencies: Vec<String>) -> Result<Self> {
+        let index = WorkspaceIndex::new(members, dependencies);
+        Ok(Self {
+            module_info: ModuleInfo::from_ty_module(vec![], None),
+            html_filename: INDEX_FILENAME.to_string(),
+            file_contents: HTMLString::from_rendered_content(index.render()?)?,
+        })
+    }
 }
 
 #[derive(Default)]
@@ -68,6 +84,7 @@ impl RenderedDocumentation {
         root_attributes: Option<AttributesMap>,
         program_kind: TyProgramKind,
         forc_version: Option<String>,
+        contract_interface: Option<ContractInterface>,
     ) -> Result<RenderedDocumentation> {
         let mut rendered_docs: RenderedDocumentation = Default::default();
         let root_module = match raw_docs.0.first() {
@@ -110,6 +127,7 @@ impl RenderedDocumentation {
                             style: DocStyle::ProjectIndex(program_kind.as_title_str().to_string()),
                             links: doc_links.to_owned(),
                         },
+                        contract_interface,
                     )
                     .render(render_plan.clone())?,
                 )?,
@@ -140,6 +158,7 @@ impl RenderedDocumentation {
                                     style: DocStyle::ModuleIndex,
                                     links: doc_links.to_owned(),
                                 },
+                                None,
                             )
                             .render(render_plan.clone())?,
                         )?,
@@ -157,6 +176,7 @@ impl RenderedDocumentation {
                                         style: DocStyle::ModuleIndex,
                                         links: doc_links.to_owned(),
                                     },
+                                    None,
                                 )
                                 .render(render_plan.clone())?,
                             )?,
@@ -0,0 +1,245 @@
+//! The search index of the documentation and the search bar that filters it.
+use crate::{
+    doc::{module::ModuleInfo, Documentation},
+    render::{
+        constant::{IDENTITY, SEARCH_INDEX_FILENAME},
+        item::context::ContextType,
+        util::format::docstring::DocStrings,
+    },
+    ASSETS_DIR_NAME,
+};
+use anyhow::Result;
+use horrorshow::{box_html, RenderBox};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The name of the script that filters the [SearchIndex] as the user types in the search bar.
+const SEARCH_SCRIPT_FILENAME: &str = "swaydoc-search.js";
+/// The kind of the entries of trait methods.
+pub(crate) const METHOD_KIND: &str = "method";
+
+/// A documented item as it appears in the search results.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct SearchEntry {
+    /// The module qualified name of the item, e.g. `project_name::module::Item`.
+    pub(crate) name: String,
+    /// The kind of the item, e.g. `struct` or `method`.
+    pub(crate) kind: String,
+    /// The path to the page of the item, relative to the documentation root.
+    pub(crate) href: String,
+    /// The first sentence of the documentation of the item.
+    pub(crate) description: String,
+}
+
+/// All documented items of the programs in the documentation.
+///
+/// The entries are keyed by their qualified name and kind so that an item that is gathered
+/// more than once only appears once, and so that the index is written in the same order on
+/// every build.
+#[derive(Default)]
+pub(crate) struct SearchIndex(BTreeMap<(String, String), SearchEntry>);
+impl SearchIndex {
+    /// Add the items of a program's [Documentation], including the methods of its traits.
+    pub(crate) fn extend_from_docs(&mut self, docs: &Documentation) {
+        for doc in &docs.0 {
+            let module_info = &doc.module_info;
+            let item_name = doc.item_header.item_name.as_str();
+            let name = format!("{}::{item_name}", module_info.module_prefixes.join("::"));
+            let href = format!(
+                "{}/{}",
+                module_info.module_prefixes.join("/"),
+                doc.html_filename()
+            );
+
+            if let Some(ContextType::RequiredMethods(methods)) = doc
+                .item_body
+                .item_context
+                .context_opt
+                .as_ref()
+                .map(|context| &context.context_type)
+            {
+                for method in methods {
+                    let method_description =
+                        (!method.attributes.is_empty()).then(|| method.attributes.to_html_string());
+                    self.insert(SearchEntry {
+                        name: format!("{name}::{}", method.name.as_str()),
+                        kind: METHOD_KIND.to_string(),
+                        href: format!("{href}{IDENTITY}tymethod.{}", method.name.as_str()),
+                        description: first_sentence(method_description.as_deref()),
+                    });
+                }
+            }
+
+            self.insert(SearchEntry {
+                name,
+                kind: doc.item_body.ty_decl.doc_name().to_string(),
+                href,
+                description: first_sentence(doc.raw_attributes.as_deref()),
+            });
+        }
+    }
+    fn insert(&mut self, entry: SearchEntry) {
+        self.0
+            .entry((entry.name.clone(), entry.kind.clone()))
+            .or_insert(entry);
+    }
+    /// Find the entry of the item with the qualified `name`, if its kind is one of `kinds`.
+    ///
+    /// An item of any kind matches if `kinds` is `None`.
+    pub(crate) fn find(&self, name: &str, kinds: Option<&[&str]>) -> Option<&SearchEntry> {
+        self.0
+            .range((name.to_string(), String::new())..)
+            .take_while(|((entry_name, _), _)| entry_name == name)
+            .map(|(_, entry)| entry)
+            .find(|entry| kinds.map_or(true, |kinds| kinds.contains(&entry.kind.as_str())))
+    }
+    /// The entries of the index, sorted by their qualified name.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &SearchEntry> {
+        self.0.values()
+    }
+    /// Serialize the index to a JSON array of its entries.
+    pub(crate) fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.entries().collect::<Vec<_>>())?)
+    }
+    /// Create the contents of the `search-index.js` file that is loaded by every page.
+    pub(crate) fn to_js(&self) -> Result<String> {
+        Ok(format!("var SEARCH_INDEX = {};\n", self.to_json()?))
+    }
+}
+
+/// The plain text of the first sentence of some documentation rendered to HTML.
+fn first_sentence(html: Option<&str>) -> String {
+    let Some(html) = html else {
+        return String::new();
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if in_tag => {}
+            _ => text.push(c),
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.find(". ") {
+        Some(index) => text[..=index].to_string(),
+        None => text,
+    }
+}
+
+/// Create the search bar and the section its results are listed in, along with the scripts
+/// that load the [SearchIndex] and filter it.
+pub(crate) fn generate_searchbar(module_info: &ModuleInfo) -> Box<dyn RenderBox> {
+    let search_index = module_info.to_html_shorthand_path_string(SEARCH_INDEX_FILENAME);
+    let search_script = module_info
+        .to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/{SEARCH_SCRIPT_FILENAME}"));
+    box_html! {
+        div(class="sub-container") {
+            nav(class="sub") {
+                form(class="search-form", id="search-form") {
+                    div(class="search-container") {
+                        span;
+                        input(
+                            class="search-input",
+                            id="search-input",
+                            name="search",
+                            autocomplete="off",
+                            spellcheck="false",
+                            placeholder="Search the documentation...",
+                            type="search"
+                        );
+                    }
+                }
+            }
+        }
+        section(id="search", class="content search-results");
+        script(src=search_index);
+        script(src=search_script);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{first_sentence, SearchIndex};
+    use crate::{doc::Documentation, tests::compile_fixture};
+
+    fn search_index_for(fixture: &str) -> SearchIndex {
+        let fixture = compile_fixture(fixture);
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            fixture.pkg_manifest.project_name(),
+            &fixture.ty_program,
+            false,
+        )
+        .unwrap();
+        let mut search_index = SearchIndex::default();
+        search_index.extend_from_docs(&docs);
+        search_index
+    }
+
+    #[test]
+    fn test_search_index_entries() {
+        let search_index = search_index_for("impl_traits");
+        let json: serde_json::Value =
+            serde_json::from_str(&search_index.to_json().unwrap()).unwrap();
+        let entries = json.as_array().unwrap();
+        let entry = |name: &str| {
+            entries
+                .iter()
+                .find(|entry| entry["name"] == name)
+                .unwrap_or_else(|| panic!("missing search index entry for `{name}`"))
+        };
+
+        assert_eq!(entry("impl_traits::bar::Bar")["kind"], "struct");
+        assert_eq!(
+            entry("impl_traits::bar::Bar")["href"],
+            "impl_traits/bar/struct.Bar.html"
+        );
+        assert_eq!(entry("impl_traits::foo::Foo")["kind"], "trait");
+        assert_eq!(entry("impl_traits::foo::Baz")["kind"], "trait");
+
+        let method = entry("impl_traits::foo::Foo::foo");
+        assert_eq!(method["kind"], "method");
+        assert_eq!(
+            method["href"],
+            "impl_traits/foo/trait.Foo.html#tymethod.foo"
+        );
+        // Descriptions are rendered with smart punctuation.
+        assert_eq!(method["description"], "something about foo…");
+    }
+
+    #[test]
+    fn test_search_index_names_are_module_qualified() {
+        let search_index = search_index_for("nested_subdirs");
+        let names: Vec<_> = search_index
+            .entries()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "nested_subdirs::sub_dir::folder::lib_one::Foo",
+                "nested_subdirs::sub_dir::folder::lib_two::Bar",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_index_is_deterministic() {
+        let first = search_index_for("nested_subdirs").to_js().unwrap();
+        let second = search_index_for("nested_subdirs").to_js().unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_first_sentence() {
+        assert_eq!(
+            first_sentence(Some("<p>Does a thing. Then\nanother.</p>\n")),
+            "Does a thing."
+        );
+        assert_eq!(first_sentence(Some("<p>No period</p>")), "No period");
+        assert_eq!(first_sentence(None), "");
+    }
+}
@@ -0,0 +1,250 @@
+//! Pages with the source code of a package, linked to from the items defined in it.
+use crate::{
+    doc::module::ModuleInfo,
+    render::{HTMLString, RenderedDocument},
+    RenderPlan, ASSETS_DIR_NAME,
+};
+use anyhow::Result;
+use horrorshow::{box_html, RenderBox};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use sway_types::Span;
+
+/// The directory of the documentation root that the source pages of each package are put in.
+pub(crate) const SOURCE_DIR_NAME: &str = "src";
+const SWAY_FILE_EXTENSION: &str = "sw";
+
+/// Create the path to the line that `span` starts at in its source page, relative to the page
+/// of an item in the module of `module_info`.
+///
+/// Returns `None` if the span is not part of the sources of the package being documented.
+pub(crate) fn source_link(
+    span: &Span,
+    module_info: &ModuleInfo,
+    render_plan: &RenderPlan,
+) -> Option<String> {
+    let path = render_plan.engines.se().get_path(span.source_id()?);
+    let relative_path = path.strip_prefix(&render_plan.source_root).ok()?;
+    // Lines are counted over the characters of the source, so multi-byte characters
+    // before the span don't shift the anchor.
+    let (line, _) = span.start_pos().line_col();
+    Some(module_info.to_html_shorthand_path_string(&format!(
+        "{}#{line}",
+        source_page_path(module_info.project_name(), relative_path)
+    )))
+}
+
+/// The path of the source page of the file at `relative_path` in the sources of a package,
+/// relative to the documentation root.
+///
+/// Example: `src/project_name/module/file_name.sw.html`
+fn source_page_path(project_name: &str, relative_path: &Path) -> String {
+    let mut prefixes = source_page_prefixes(project_name, relative_path);
+    prefixes.push(source_page_filename(relative_path));
+    prefixes.join("/")
+}
+fn source_page_prefixes(project_name: &str, relative_path: &Path) -> Vec<String> {
+    let mut prefixes = vec![SOURCE_DIR_NAME.to_string(), project_name.to_string()];
+    if let Some(parent) = relative_path.parent() {
+        prefixes.extend(
+            parent
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+        );
+    }
+    prefixes
+}
+fn source_page_filename(relative_path: &Path) -> String {
+    let file_name = relative_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    format!("{file_name}.html")
+}
+
+/// Render a source page for every Sway file in the sources of the package.
+pub(crate) fn render_source_pages(
+    project_name: &str,
+    render_plan: RenderPlan,
+) -> Result<Vec<RenderedDocument>> {
+    let mut source_files = vec![];
+    collect_source_files(&render_plan.source_root, &mut source_files)?;
+    // Sort the files so the pages are written in the same order on every build.
+    source_files.sort();
+
+    let mut source_pages = Vec::with_capacity(source_files.len());
+    for path in source_files {
+        let relative_path = path.strip_prefix(&render_plan.source_root)?;
+        let module_info =
+            ModuleInfo::from_ty_module(source_page_prefixes(project_name, relative_path), None);
+        let source_page = SourcePage {
+            module_info: module_info.clone(),
+            file_name: relative_path.to_string_lossy().into_owned(),
+            source: fs::read_to_string(&path)?,
+        };
+        source_pages.push(RenderedDocument {
+            module_info,
+            html_filename: source_page_filename(relative_path),
+            file_contents: HTMLString::from_rendered_content(source_page.render())?,
+        });
+    }
+    Ok(source_pages)
+}
+fn collect_source_files(dir: &Path, source_files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_source_files(&path, source_files)?;
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some(SWAY_FILE_EXTENSION) {
+            source_files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The source code of a file, with an anchor for each of its lines.
+struct SourcePage {
+    module_info: ModuleInfo,
+    file_name: String,
+    source: String,
+}
+impl SourcePage {
+    fn render(self) -> Box<dyn RenderBox> {
+        let SourcePage {
+            module_info,
+            file_name,
+            source,
+        } = self;
+        let line_count = source.lines().count();
+
+        let favicon =
+            module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/sway-logo.svg"));
+        let normalize =
+            module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/normalize.css"));
+        let swaydoc =
+            module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/swaydoc.css"));
+        let ayu = module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/ayu.css"));
+        let sway_hjs =
+            module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/highlight.js"));
+        let ayu_hjs =
+            module_info.to_html_shorthand_path_string(&format!("{ASSETS_DIR_NAME}/ayu.min.css"));
+
+        box_html! {
+            head {
+                meta(charset="utf-8");
+                meta(name="viewport", content="width=device-width, initial-scale=1.0");
+                meta(name="generator", content="swaydoc");
+                meta(
+                    name="description",
+                    content=format!("Source of the Sway file `{file_name}`.")
+                );
+                meta(name="keywords", content="sway, swaylang, sway-lang");
+                link(rel="icon", href=favicon);
+                title: format!("{file_name} - source");
+                link(rel="stylesheet", type="text/css", href=normalize);
+                link(rel="stylesheet", type="text/css", href=swaydoc, id="mainThemeStyle");
+                link(rel="stylesheet", type="text/css", href=ayu);
+                link(rel="stylesheet", href=ayu_hjs);
+            }
+            body(class="swaydoc source") {
+                main {
+                    div(class="width-limiter") {
+                        section(id="main-content", class="content") {
+                            div(class="example-wrap") {
+                                pre(class="line-numbers") {
+                                    @ for line in 1..=line_count {
+                                        span(id=line.to_string()) { : line; }
+                                        : "\n";
+                                    }
+                                }
+                                pre(class="sway") {
+                                    code { : source; }
+                                }
+                            }
+                        }
+                    }
+                }
+                script(src=sway_hjs);
+                script {
+                    : "hljs.highlightAll();";
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{source_link, source_page_path};
+    use crate::{doc::Documentation, tests::compile_fixture, RenderPlan};
+    use std::path::Path;
+    use sway_types::Spanned;
+
+    #[test]
+    fn test_source_page_path() {
+        assert_eq!(
+            source_page_path("project_name", Path::new("module/file_name.sw")),
+            "src/project_name/module/file_name.sw.html"
+        );
+        assert_eq!(
+            source_page_path("project_name", Path::new("lib.sw")),
+            "src/project_name/lib.sw.html"
+        );
+    }
+
+    #[test]
+    fn test_source_link_anchors_definition_line() {
+        let fixture = compile_fixture("impl_traits");
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            fixture.pkg_manifest.project_name(),
+            &fixture.ty_program,
+            false,
+        )
+        .unwrap();
+        let render_plan = RenderPlan::new(false, &fixture.engines, fixture.source_root());
+        let doc = docs
+            .0
+            .iter()
+            .find(|doc| doc.item_header.item_name.as_str() == "Bar")
+            .unwrap();
+        assert_eq!(
+            source_link(
+                &doc.item_body.ty_decl.span(),
+                &doc.module_info,
+                &render_plan
+            )
+            .unwrap(),
+            "../../src/impl_traits/bar.sw.html#5"
+        );
+    }
+
+    #[test]
+    fn test_source_link_line_after_multi_byte_chars() {
+        let fixture = compile_fixture("multi_byte_source");
+        let docs = Documentation::from_ty_program(
+            fixture.engines.de(),
+            fixture.pkg_manifest.project_name(),
+            &fixture.ty_program,
+            false,
+        )
+        .unwrap();
+        let render_plan = RenderPlan::new(false, &fixture.engines, fixture.source_root());
+        let doc = docs
+            .0
+            .iter()
+            .find(|doc| doc.item_header.item_name.as_str() == "Size")
+            .unwrap();
+        assert_eq!(
+            source_link(
+                &doc.item_body.ty_decl.span(),
+                &doc.module_info,
+                &render_plan
+            )
+            .unwrap(),
+            "../src/multi_byte_source/lib.sw.html#6"
+        );
+    }
+}
@@ -11,18 +11,7 @@ pub(crate) trait DocStrings {
 /// Creates an HTML String from an [AttributesMap]
 impl DocStrings for AttributesMap {
     fn to_html_string(&self) -> String {
-        let docs = self.to_raw_string();
-
-        let mut options = ComrakOptions::default();
-        options.render.hardbreaks = true;
-        options.extension.strikethrough = true;
-        options.extension.table = true;
-        options.extension.autolink = true;
-        options.extension.superscript = true;
-        options.extension.footnotes = true;
-        options.parse.smart = true;
-        options.parse.default_info_string = Some(SWAY_FILEINE.into());
-        markdown_to_html(&format_docs(&docs), &options)
+        docs_to_html_string(&self.to_raw_string())
     }
     fn to_raw_string(&self) -> String {
         let attributes = self.get(&AttributeKind::DocComment);
@@ -39,6 +28,20 @@ impl DocStrings for AttributesMap {
     }
 }
 
+/// Creates an HTML String from the markdown of doc comments.
+pub(crate) fn docs_to_html_string(docs: &str) -> String {
+    let mut options = ComrakOptions::default();
+    options.render.hardbreaks = true;
+    options.extension.strikethrough = true;
+    options.extension.table = true;
+    options.extension.autolink = true;
+    options.extension.superscript = true;
+    options.extension.footnotes = true;
+    options.parse.smart = true;
+    options.parse.default_info_string = Some(SWAY_FILEINE.into());
+    markdown_to_html(&format_docs(docs), &options)
+}
+
 /// Create a docstring preview from raw html attributes.
 ///
 /// Returns `None` if there are no attributes.
@@ -0,0 +1,79 @@
+// Filters the search index of the documentation as the user types in the search bar.
+//
+// The index is loaded from `search-index.js` at the documentation root, which defines
+// `SEARCH_INDEX` as a list of `{ name, kind, href, description }` entries.
+(function () {
+  "use strict";
+
+  var MAX_RESULTS = 50;
+  // This script lives in `static.files/` under the documentation root.
+  var rootPath = document.currentScript.src.replace(/static\.files\/[^/]*$/, "");
+
+  function createResult(entry) {
+    var link = document.createElement("a");
+    link.href = rootPath + entry.href;
+    var row = document.createElement("div");
+    var name = document.createElement("div");
+    name.className = "result-name";
+    var kind = document.createElement("span");
+    kind.className = "typename";
+    kind.textContent = entry.kind + " ";
+    var path = document.createElement("span");
+    path.className = entry.kind;
+    path.textContent = entry.name;
+    name.appendChild(kind);
+    name.appendChild(path);
+    var description = document.createElement("div");
+    description.className = "desc";
+    var descriptionText = document.createElement("span");
+    descriptionText.textContent = entry.description;
+    description.appendChild(descriptionText);
+    row.appendChild(name);
+    row.appendChild(description);
+    link.appendChild(row);
+    return link;
+  }
+
+  function search(query) {
+    var terms = query.toLowerCase().split(/\s+/).filter(Boolean);
+    return SEARCH_INDEX.filter(function (entry) {
+      var name = entry.name.toLowerCase();
+      return terms.every(function (term) {
+        return name.indexOf(term) !== -1;
+      });
+    });
+  }
+
+  window.addEventListener("DOMContentLoaded", function () {
+    var form = document.getElementById("search-form");
+    var input = document.getElementById("search-input");
+    var results = document.getElementById("search");
+    var mainContent = document.getElementById("main-content");
+    if (!form || !input || !results || typeof SEARCH_INDEX === "undefined") {
+      return;
+    }
+
+    form.addEventListener("submit", function (event) {
+      event.preventDefault();
+    });
+    input.addEventListener("input", function () {
+      var query = input.value.trim();
+      results.textContent = "";
+      if (query === "") {
+        results.classList.remove("active");
+        mainContent.style.display = "";
+        return;
+      }
+      var matches = search(query);
+      var title = document.createElement("h1");
+      title.className = "search-results-title";
+      title.textContent = matches.length === 0 ? "No results" : "Results for " + query;
+      results.appendChild(title);
+      matches.slice(0, MAX_RESULTS).forEach(function (entry) {
+        results.appendChild(createResult(entry));
+      });
+      results.classList.add("active");
+      mainContent.style.display = "none";
+    });
+  });
+})();
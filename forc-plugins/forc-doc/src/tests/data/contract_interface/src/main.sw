@@ -0,0 +1,37 @@
+contract;
+
+configurable {
+    /// The largest amount the counter can be incremented by at once.
+    MAX_INCREMENT: u64 = 10,
+}
+
+storage {
+    /// The number of times the counter was incremented.
+    count: u64 = 0,
+    /// Whether the counter can be incremented.
+    enabled: bool = true,
+}
+
+abi Counter {
+    /// Increments the counter by `amount` and returns the new count.
+    #[storage(read, write)]
+    fn increment(amount: u64) -> u64;
+
+    /// Sets the counter back to zero.
+    #[storage(write)]
+    fn reset();
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment(amount: u64) -> u64 {
+        let count = storage.count.read() + amount;
+        storage.count.write(count);
+        count
+    }
+
+    #[storage(write)]
+    fn reset() {
+        storage.count.write(0);
+    }
+}
@@ -0,0 +1,12 @@
+library;
+
+/// A distance to move a point by.
+pub struct Offset {
+    dx: u64,
+    dy: u64,
+}
+
+pub trait Area {
+    /// The area of the shape.
+    fn area(self) -> u64;
+}
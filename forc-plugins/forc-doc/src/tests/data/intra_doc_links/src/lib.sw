@@ -0,0 +1,12 @@
+library;
+
+mod geometry;
+
+use intra_doc_links_dep::Point;
+
+/// Moves a [`Point`] by [the offset](geometry::Offset).
+///
+/// The area of a shape is given by [`geometry::Area::area`], while [`Missing`] doesn't exist.
+pub fn translate(point: Point) -> Point {
+    point
+}
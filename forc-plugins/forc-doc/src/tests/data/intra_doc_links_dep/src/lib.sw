@@ -0,0 +1,7 @@
+library;
+
+/// A point in the plane.
+pub struct Point {
+    x: u64,
+    y: u64,
+}
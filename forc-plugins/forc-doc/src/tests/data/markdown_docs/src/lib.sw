@@ -0,0 +1,4 @@
+//! Shapes and the traits that describe them.
+library;
+
+mod shapes;
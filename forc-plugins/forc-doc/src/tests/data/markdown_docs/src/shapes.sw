@@ -0,0 +1,24 @@
+library;
+
+/// A shape with four equal sides.
+pub struct Square {
+    side: u64,
+}
+
+/// Something with a number of sides.
+///
+/// Implemented by every shape.
+pub trait Shape {
+    fn sides(self) -> u64;
+}
+
+impl Shape for Square {
+    fn sides(self) -> u64 {
+        4
+    }
+}
+
+/// The area of a square.
+fn area(square: Square) -> u64 {
+    square.side * square.side
+}
@@ -0,0 +1,8 @@
+library;
+
+// Größe, Ünïcödé and 📦 are made of multi-byte characters,
+// which must not shift the anchors of the lines below them.
+
+pub struct Size {
+    value: u64,
+}
@@ -0,0 +1,31 @@
+library;
+
+pub trait Shape {
+    fn sides(self) -> u64;
+}
+
+pub trait Describe {
+    fn describe(self) -> u64;
+}
+
+pub struct Square {}
+
+pub struct Triangle {}
+
+impl Shape for Square {
+    fn sides(self) -> u64 {
+        4
+    }
+}
+
+impl Shape for Triangle {
+    fn sides(self) -> u64 {
+        3
+    }
+}
+
+impl<T> Describe for T where T: Shape {
+    fn describe(self) -> u64 {
+        self.sides()
+    }
+}
@@ -0,0 +1,8 @@
+library;
+
+use member_b::Token;
+
+/// Holds a [`Token`] of the other member.
+pub struct Wallet {
+    token: Token,
+}
@@ -0,0 +1,6 @@
+library;
+
+/// A token, used by the other member of the workspace.
+pub struct Token {
+    amount: u64,
+}
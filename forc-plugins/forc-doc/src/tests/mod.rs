@@ -0,0 +1,107 @@
+//! Helpers shared by the tests, which build documentation for the packages in `data`.
+use forc_pkg::{self as pkg, manifest::ManifestFile, source::IPFSNode, PackageManifestFile};
+use std::path::{Path, PathBuf};
+use sway_core::{language::ty::TyProgram, BuildTarget, Engines};
+
+/// A package from `data` that has been type checked.
+pub(crate) struct Fixture {
+    pub(crate) engines: Engines,
+    pub(crate) ty_program: TyProgram,
+    pub(crate) pkg_manifest: PackageManifestFile,
+    /// The type checked dependencies of the package along with their names, in the order they
+    /// were compiled in.
+    pub(crate) dependencies: Vec<(String, TyProgram)>,
+}
+impl Fixture {
+    /// The directory that the entry file of the package is in.
+    pub(crate) fn source_root(&self) -> PathBuf {
+        self.pkg_manifest
+            .entry_path()
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+}
+
+/// Type check the package in `data` named `name`.
+pub(crate) fn compile_fixture(name: &str) -> Fixture {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/data")
+        .join(name);
+    let manifest = ManifestFile::from_dir(&dir).unwrap();
+    let ManifestFile::Package(pkg_manifest) = &manifest else {
+        panic!("fixture `{name}` is not a package");
+    };
+    let plan = pkg::BuildPlan::from_lock_and_manifests(
+        &manifest.lock_path().unwrap(),
+        &manifest.member_manifests().unwrap(),
+        false,
+        true,
+        IPFSNode::default(),
+    )
+    .unwrap();
+    let engines = Engines::default();
+    let compile_results = pkg::check(&plan, BuildTarget::default(), true, false, &engines).unwrap();
+    let mut programs: Vec<(String, TyProgram)> = plan
+        .compilation_order()
+        .iter()
+        .zip(compile_results)
+        .map(|(node, compilation)| {
+            let ty_program = compilation
+                .value
+                .and_then(|programs| programs.typed)
+                .unwrap();
+            (plan.graph()[*node].name.clone(), ty_program)
+        })
+        .collect();
+    let (_, ty_program) = programs.pop().unwrap();
+    Fixture {
+        engines,
+        ty_program,
+        pkg_manifest: *pkg_manifest.clone(),
+        dependencies: programs,
+    }
+}
+
+/// The members of a workspace from `data` that have been type checked.
+pub(crate) struct WorkspaceFixture {
+    pub(crate) engines: Engines,
+    /// The type checked members along with their manifests, in the order they were compiled in.
+    pub(crate) members: Vec<(PackageManifestFile, TyProgram)>,
+}
+
+/// Type check the workspace in `data` named `name`.
+pub(crate) fn compile_workspace_fixture(name: &str) -> WorkspaceFixture {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/data")
+        .join(name);
+    let manifest = ManifestFile::from_dir(&dir).unwrap();
+    let ManifestFile::Workspace(_) = &manifest else {
+        panic!("fixture `{name}` is not a workspace");
+    };
+    let member_manifests = manifest.member_manifests().unwrap();
+    let plan = pkg::BuildPlan::from_lock_and_manifests(
+        &manifest.lock_path().unwrap(),
+        &member_manifests,
+        false,
+        true,
+        IPFSNode::default(),
+    )
+    .unwrap();
+    let engines = Engines::default();
+    let compile_results = pkg::check(&plan, BuildTarget::default(), true, false, &engines).unwrap();
+    let members = plan
+        .compilation_order()
+        .iter()
+        .zip(compile_results)
+        .filter_map(|(node, compilation)| {
+            let pkg_manifest = member_manifests.get(&plan.graph()[*node].name)?;
+            let ty_program = compilation
+                .value
+                .and_then(|programs| programs.typed)
+                .unwrap();
+            Some((pkg_manifest.clone(), ty_program))
+        })
+        .collect();
+    WorkspaceFixture { engines, members }
+}
@@ -1,19 +1,20 @@
 //! A `forc` plugin for running the Sway code formatter.
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use clap::Parser;
 use forc_pkg::{manifest::ManifestFile, WorkspaceManifestFile};
-use prettydiff::{basic::DiffOp, diff_lines};
+use similar::TextDiff;
 use std::{
     default::Default,
     fs,
+    io::{self, Read, Write},
     path::{Path, PathBuf},
     sync::Arc,
 };
 use taplo::formatter as taplo_fmt;
 use tracing::{error, info};
 
-use forc_tracing::{init_tracing_subscriber, println_green, println_red};
+use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
 use forc_util::{find_parent_manifest_dir, is_sway_file};
 use sway_core::{BuildConfig, BuildTarget};
 use sway_utils::{constants, get_sway_files};
@@ -29,27 +30,63 @@ pub struct App {
     /// Run in 'check' mode.
     ///
     /// - Exits with `0` if input is formatted correctly.
-    /// - Exits with `1` and prints a diff if formatting is required.
+    /// - Prints a unified diff for every file requiring formatting, and exits with the number of
+    ///   such files (saturating at `255`).
     #[clap(short, long)]
     pub check: bool,
+    /// The number of unchanged lines to show around each change in the `--check` diffs.
+    #[clap(long, default_value = "3")]
+    pub context: usize,
     /// Path to the project, if not specified, current working directory will be used.
     #[clap(short, long)]
     pub path: Option<String>,
     /// Formats a single .sw file with the default settings.
     /// If not specified, current working directory will be formatted using a Forc.toml configuration.
+    #[clap(conflicts_with = "stdin")]
     pub file: Option<String>,
+    /// Read Sway source from stdin and write the formatted result to stdout.
+    ///
+    /// Combined with `--check`, a unified diff is printed instead of the formatted source.
+    #[clap(long)]
+    pub stdin: bool,
+    /// The path of the file being formatted through `--stdin`. The file does not need to exist;
+    /// it is used to discover the nearest `swayfmt.toml` and to give context to error messages.
+    #[clap(long, requires = "stdin")]
+    pub stdin_path: Option<String>,
+    /// Only format (or check) the given files, e.g. the files staged in a pre-commit hook.
+    ///
+    /// Both `.sw` files and `Forc.toml` manifests are accepted. Every file must be located within
+    /// the package or workspace being formatted.
+    #[clap(long, multiple_values = true, conflicts_with_all = &["file", "stdin"])]
+    pub files: Vec<String>,
 }
 
 fn main() {
-    init_tracing_subscriber(Default::default());
-    if let Err(err) = run() {
-        error!("Error: {:?}", err);
-        std::process::exit(1);
+    let app = App::parse();
+    // When formatting from stdin, stdout is reserved for the formatted source.
+    let writer_mode = app.stdin.then_some(TracingWriterMode::Stderr);
+    init_tracing_subscriber(TracingSubscriberOptions {
+        writer_mode,
+        ..Default::default()
+    });
+    match run(app) {
+        Ok(0) => (),
+        Ok(violations) => {
+            error!("{violations} file(s) contain formatting violations.");
+            std::process::exit(violations.min(u8::MAX as usize) as i32);
+        }
+        Err(err) => {
+            error!("Error: {:?}", err);
+            std::process::exit(1);
+        }
     }
 }
 
-fn run() -> Result<()> {
-    let app = App::parse();
+/// Runs the formatter, returning the number of files with formatting violations in check mode.
+fn run(app: App) -> Result<usize> {
+    if app.stdin {
+        return format_stdin(&app);
+    }
 
     let dir = match app.path.as_ref() {
         Some(path) => PathBuf::from(path),
@@ -66,8 +103,8 @@ fn run() -> Result<()> {
             .map(|path| path.join(constants::MANIFEST_FILE_NAME));
 
         if is_sway_file(file_path) {
-            format_file(&app, file_path.to_path_buf(), manifest_file, &mut formatter)?;
-            return Ok(());
+            let edited = format_file(&app, file_path.to_path_buf(), manifest_file, &mut formatter)?;
+            return Ok(usize::from(edited));
         }
 
         bail!(
@@ -78,16 +115,111 @@ fn run() -> Result<()> {
 
     let manifest_file = forc_pkg::manifest::ManifestFile::from_dir(&dir)?;
 
+    if !app.files.is_empty() {
+        let cwd = std::env::current_dir()?;
+        let files = files_within_dir(manifest_file.dir(), &cwd, &app.files)?;
+        return format_files(&app, files);
+    }
+
     match manifest_file {
-        ManifestFile::Workspace(ws) => {
-            format_workspace_at_dir(&app, &ws, &dir)?;
-        }
-        ManifestFile::Package(_) => {
-            format_pkg_at_dir(&app, &dir, &mut formatter)?;
+        ManifestFile::Workspace(ws) => format_workspace_at_dir(&app, &ws, &dir),
+        ManifestFile::Package(_) => format_pkg_at_dir(&app, &dir, &mut formatter),
+    }
+}
+
+/// Resolve the given file paths relative to `cwd`, rejecting any that are not within `root_dir`.
+fn files_within_dir(root_dir: &Path, cwd: &Path, files: &[String]) -> Result<Vec<PathBuf>> {
+    let root_dir = root_dir.canonicalize()?;
+    files
+        .iter()
+        .map(|file| {
+            let path = cwd.join(file);
+            let path = path
+                .canonicalize()
+                .map_err(|e| anyhow!("Could not read file {}: {e}", path.display()))?;
+            if !path.starts_with(&root_dir) {
+                bail!(
+                    "'{}' is outside of the workspace at '{}'",
+                    file,
+                    root_dir.display()
+                );
+            }
+            if !is_sway_file(&path) && !is_manifest_file(&path) {
+                bail!("'{}' is neither a Sway file nor a Forc manifest", file);
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Format an explicit list of files, each with the configuration of its nearest package.
+fn format_files(app: &App, files: Vec<PathBuf>) -> Result<usize> {
+    let mut violations = 0;
+    for file in files {
+        let edited = if is_manifest_file(&file) {
+            format_manifest(app, file)?
+        } else {
+            let dir = file.parent().expect("a file always has a parent directory");
+            let mut formatter = Formatter::from_dir(dir)?;
+            let manifest_file =
+                find_parent_manifest_dir(dir).map(|path| path.join(constants::MANIFEST_FILE_NAME));
+            format_file(app, file, manifest_file, &mut formatter)?
+        };
+        violations += usize::from(edited);
+    }
+    Ok(violations)
+}
+
+fn is_manifest_file(path: &Path) -> bool {
+    path.file_name().and_then(|name| name.to_str()) == Some(constants::MANIFEST_FILE_NAME)
+}
+
+/// Format source read from stdin, writing the result (or a unified diff in check mode) to stdout.
+fn format_stdin(app: &App) -> Result<usize> {
+    let cwd = std::env::current_dir()?;
+    let virtual_path = app.stdin_path.as_ref().map(|path| cwd.join(path));
+    let label = app.stdin_path.as_deref().unwrap_or("<stdin>");
+
+    // Use the nearest existing ancestor of the virtual path to discover the config.
+    let config_dir = match &virtual_path {
+        Some(path) => path
+            .ancestors()
+            .skip(1)
+            .find(|dir| dir.is_dir())
+            .map(Path::to_path_buf)
+            .unwrap_or(cwd),
+        None => app.path.as_ref().map(PathBuf::from).unwrap_or(cwd),
+    };
+    let mut formatter = Formatter::from_dir(&config_dir)?;
+
+    let mut src = String::new();
+    io::stdin().read_to_string(&mut src)?;
+    let src: Arc<str> = Arc::from(src);
+
+    let build_config = virtual_path.as_ref().and_then(|path| {
+        let manifest_dir = find_parent_manifest_dir(&config_dir)?;
+        Some(BuildConfig::root_from_file_name_and_manifest_path(
+            path.clone(),
+            manifest_dir.join(constants::MANIFEST_FILE_NAME),
+            BuildTarget::default(),
+        ))
+    });
+    let formatted = match formatter.format(src.clone(), build_config.as_ref()) {
+        Ok(formatted) => formatted,
+        Err(err) => bail!("Failed to format {label}: {err}"),
+    };
+
+    let mut stdout = io::stdout();
+    if app.check {
+        if *src != formatted {
+            stdout.write_all(unified_diff(&src, &formatted, label, app.context).as_bytes())?;
+            return Ok(1);
         }
+    } else {
+        stdout.write_all(formatted.as_bytes())?;
     }
 
-    Ok(())
+    Ok(0)
 }
 
 /// Recursively get a Vec<PathBuf> of subdirectories that contains a Forc.toml.
@@ -139,8 +271,15 @@ fn format_file(
             Ok(formatted_content) => {
                 if app.check {
                     if *file_content != formatted_content {
-                        info!("File was edited by formatter: \n{:?}\n", file);
-                        display_file_diff(&file_content, &formatted_content)?;
+                        info!(
+                            "{}",
+                            unified_diff(
+                                &file_content,
+                                &formatted_content,
+                                &file.display().to_string(),
+                                app.context,
+                            )
+                        );
                         edited = true;
                     }
                 } else {
@@ -163,8 +302,14 @@ fn format_file(
 }
 
 /// Format the workspace at the given directory.
-fn format_workspace_at_dir(app: &App, workspace: &WorkspaceManifestFile, dir: &Path) -> Result<()> {
-    let mut contains_edits = false;
+///
+/// Returns the number of files with formatting violations in check mode.
+fn format_workspace_at_dir(
+    app: &App,
+    workspace: &WorkspaceManifestFile,
+    dir: &Path,
+) -> Result<usize> {
+    let mut violations = 0;
     let mut formatter = Formatter::from_dir(dir)?;
     let mut members = vec![];
 
@@ -178,12 +323,12 @@ fn format_workspace_at_dir(app: &App, workspace: &WorkspaceManifestFile, dir: &P
         for entry in read_dir.filter_map(|res| res.ok()) {
             let path = entry.path();
             if is_sway_file(&path) {
-                format_file(
+                violations += usize::from(format_file(
                     app,
                     path,
                     Some(workspace.dir().to_path_buf()),
                     &mut formatter,
-                )?;
+                )?);
             }
         }
     }
@@ -199,20 +344,15 @@ fn format_workspace_at_dir(app: &App, workspace: &WorkspaceManifestFile, dir: &P
             // In order of priority: member > workspace > default.
             formatter = Formatter::from_dir(&sub_dir)?;
         }
-        format_pkg_at_dir(app, &sub_dir, &mut formatter)?;
+        violations += format_pkg_at_dir(app, &sub_dir, &mut formatter)?;
     }
 
     let manifest_file = dir.join(constants::MANIFEST_FILE_NAME);
 
     // Finally, format the root manifest using taplo formatter
-    contains_edits |= format_manifest(app, manifest_file)?;
+    violations += usize::from(format_manifest(app, manifest_file)?);
 
-    if app.check && contains_edits {
-        // One or more files are not formatted, exit with error
-        bail!("Files contain formatting violations.");
-    }
-
-    Ok(())
+    Ok(violations)
 }
 
 /// Format the given manifest at a path.
@@ -232,11 +372,15 @@ fn format_manifest(app: &App, manifest_file: PathBuf) -> Result<bool> {
             write_file_formatted(&manifest_file, &formatted_content)?;
         } else if formatted_content != manifest_content {
             edited = true;
-            error!(
-                "Improperly formatted manifest file: {}",
-                manifest_file.display()
+            info!(
+                "{}",
+                unified_diff(
+                    &manifest_content,
+                    &formatted_content,
+                    &manifest_file.display().to_string(),
+                    app.context,
+                )
             );
-            display_file_diff(&manifest_content, &formatted_content)?;
         } else {
             info!(
                 "Manifest Forc.toml formatted correctly: {}",
@@ -251,69 +395,46 @@ fn format_manifest(app: &App, manifest_file: PathBuf) -> Result<bool> {
 }
 
 /// Format the package at the given directory.
-fn format_pkg_at_dir(app: &App, dir: &Path, formatter: &mut Formatter) -> Result<()> {
+///
+/// Returns the number of files with formatting violations in check mode.
+fn format_pkg_at_dir(app: &App, dir: &Path, formatter: &mut Formatter) -> Result<usize> {
     match find_parent_manifest_dir(dir) {
         Some(path) => {
             let manifest_path = path.clone();
             let manifest_file = manifest_path.join(constants::MANIFEST_FILE_NAME);
             let files = get_sway_files(path);
-            let mut contains_edits = false;
+            let mut violations = 0;
 
             for file in files {
-                contains_edits |= format_file(app, file, Some(manifest_file.clone()), formatter)?;
+                violations += usize::from(format_file(
+                    app,
+                    file,
+                    Some(manifest_file.clone()),
+                    formatter,
+                )?);
             }
             // format manifest using taplo formatter
-            contains_edits |= format_manifest(app, manifest_file)?;
+            violations += usize::from(format_manifest(app, manifest_file)?);
 
-            if app.check && contains_edits {
-                // One or more files are not formatted, exit with error
-                bail!("Files contain formatting violations.");
-            }
-
-            Ok(())
+            Ok(violations)
         }
         _ => bail!("Manifest file does not exist"),
     }
 }
 
-fn display_file_diff(file_content: &str, formatted_content: &str) -> Result<()> {
-    let changeset = diff_lines(file_content, formatted_content);
-    let mut count_of_updates = 0;
-    for diff in changeset.diff() {
-        // max 100 updates
-        if count_of_updates >= 100 {
-            break;
-        }
-        match diff {
-            DiffOp::Equal(old) => {
-                for o in old {
-                    info!("{}", o)
-                }
-            }
-            DiffOp::Insert(new) => {
-                count_of_updates += 1;
-                for n in new {
-                    println_green(&format!("+{n}"));
-                }
-            }
-            DiffOp::Remove(old) => {
-                count_of_updates += 1;
-                for o in old {
-                    println_red(&format!("-{o}"));
-                }
-            }
-            DiffOp::Replace(old, new) => {
-                count_of_updates += 1;
-                for o in old {
-                    println_red(&format!("-{o}"));
-                }
-                for n in new {
-                    println_green(&format!("+{n}"));
-                }
-            }
-        }
-    }
-    Ok(())
+/// Render a unified diff between the original and formatted contents of the file at `label`,
+/// showing `context` unchanged lines around each change.
+fn unified_diff(
+    file_content: &str,
+    formatted_content: &str,
+    label: &str,
+    context: usize,
+) -> String {
+    TextDiff::from_lines(file_content, formatted_content)
+        .unified_diff()
+        .context_radius(context)
+        .header(label, label)
+        .to_string()
 }
 
 fn write_file_formatted(file: &Path, formatted_content: &str) -> Result<()> {
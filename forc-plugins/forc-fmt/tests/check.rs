@@ -0,0 +1,130 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// A workspace with a correctly formatted member and a member with one mis-formatted function.
+fn fixture_workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspace")
+}
+
+fn forc_fmt(args: &[&str], cwd: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_forc-fmt"))
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .unwrap()
+}
+
+/// Copy the fixture workspace so that it may be formatted in place.
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let dest = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &dest);
+        } else {
+            fs::copy(&path, &dest).unwrap();
+        }
+    }
+}
+
+#[test]
+fn check_prints_unified_diff_of_misformatted_member() {
+    let output = forc_fmt(&["--check"], &fixture_workspace());
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let misformatted = fixture_workspace().join("unformatted/src/lib.sw");
+    let expected_diff = format!(
+        "--- {path}\n+++ {path}\n@@ -4,4 +4,6 @@\n     1\n }}\n \n-pub fn bar( x:u64)->u64{{ x }}\n+pub fn bar(x: u64) -> u64 {{\n+    x\n+}}\n",
+        path = misformatted.display()
+    );
+    assert!(stdout.contains(&expected_diff));
+    assert!(!stdout.contains("/formatted/src/lib.sw\n+++"));
+}
+
+#[test]
+fn check_diff_context_is_configurable() {
+    let output = forc_fmt(&["--check", "--context", "0"], &fixture_workspace());
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(
+        "@@ -7 +7,3 @@\n-pub fn bar( x:u64)->u64{ x }\n+pub fn bar(x: u64) -> u64 {\n+    x\n+}\n"
+    ));
+}
+
+#[test]
+fn check_exit_status_counts_misformatted_files() {
+    let dir = tempfile::tempdir().unwrap();
+    copy_dir(&fixture_workspace(), dir.path());
+    fs::copy(
+        dir.path().join("unformatted/src/lib.sw"),
+        dir.path().join("formatted/src/other.sw"),
+    )
+    .unwrap();
+    let output = forc_fmt(&["--check"], dir.path());
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn files_restricts_checked_files() {
+    let output = forc_fmt(
+        &["--check", "--files", "formatted/src/lib.sw", "Forc.toml"],
+        &fixture_workspace(),
+    );
+    assert!(output.status.success());
+
+    let output = forc_fmt(
+        &[
+            "--check",
+            "--files",
+            "formatted/src/lib.sw",
+            "unformatted/src/lib.sw",
+        ],
+        &fixture_workspace(),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("unformatted/src/lib.sw\n+++"));
+}
+
+#[test]
+fn files_only_formats_given_files() {
+    let dir = tempfile::tempdir().unwrap();
+    copy_dir(&fixture_workspace(), dir.path());
+    let misformatted = dir.path().join("unformatted/src/lib.sw");
+    let copy = dir.path().join("unformatted/src/copy.sw");
+    fs::copy(&misformatted, &copy).unwrap();
+    let before = fs::read_to_string(&copy).unwrap();
+
+    let output = forc_fmt(&["--files", "unformatted/src/lib.sw"], dir.path());
+    assert!(output.status.success());
+    assert!(fs::read_to_string(&misformatted)
+        .unwrap()
+        .ends_with("pub fn bar(x: u64) -> u64 {\n    x\n}\n"));
+    assert_eq!(fs::read_to_string(&copy).unwrap(), before);
+}
+
+#[test]
+fn files_outside_workspace_are_rejected() {
+    let outside = tempfile::tempdir().unwrap();
+    let outside_file = outside.path().join("lib.sw");
+    fs::write(&outside_file, "library;\n").unwrap();
+    let output = forc_fmt(
+        &["--check", "--files", outside_file.to_str().unwrap()],
+        &fixture_workspace(),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("is outside of the workspace"));
+
+    let output = forc_fmt(
+        &["--check", "--files", "../workspace/../../check.rs"],
+        &fixture_workspace(),
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("is outside of the workspace"));
+}
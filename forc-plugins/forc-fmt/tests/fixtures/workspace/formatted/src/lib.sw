@@ -0,0 +1,5 @@
+library;
+
+pub fn foo() -> u64 {
+    1
+}
@@ -0,0 +1,7 @@
+library;
+
+pub fn foo() -> u64 {
+    1
+}
+
+pub fn bar( x:u64)->u64{ x }
@@ -0,0 +1,95 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+const UNFORMATTED: &str = r#"library;
+fn   foo( x:u64)->u64{x}
+"#;
+
+const FORMATTED: &str = r#"library;
+fn foo(x: u64) -> u64 {
+    x
+}
+"#;
+
+fn forc_fmt(args: &[&str], cwd: &Path, input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_forc-fmt"))
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn stdin_is_formatted_to_stdout() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = forc_fmt(&["--stdin"], dir.path(), UNFORMATTED);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), FORMATTED);
+}
+
+#[test]
+fn stdin_check_prints_unified_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = forc_fmt(
+        &["--stdin", "--stdin-path", "src/lib.sw", "--check"],
+        dir.path(),
+        UNFORMATTED,
+    );
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("--- src/lib.sw\n+++ src/lib.sw\n"));
+    assert!(stdout.contains("\n-fn   foo( x:u64)->u64{x}\n"));
+    assert!(stdout.contains("\n+fn foo(x: u64) -> u64 {\n"));
+
+    let output = forc_fmt(&["--stdin", "--check"], dir.path(), FORMATTED);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn stdin_parse_failure_reports_on_stderr() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = forc_fmt(
+        &["--stdin", "--stdin-path", "src/main.sw"],
+        dir.path(),
+        "library; fn foo( {",
+    );
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Failed to format src/main.sw"));
+}
+
+#[test]
+fn stdin_path_discovers_nearest_config() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("swayfmt.toml"),
+        "[whitespace]\ntab_spaces = 2\n",
+    )
+    .unwrap();
+    // The virtual file's own directory does not exist, the config is found through its ancestors.
+    let output = forc_fmt(
+        &["--stdin", "--stdin-path", "src/nested/lib.sw"],
+        dir.path(),
+        UNFORMATTED,
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        FORMATTED.replace("    x", "  x")
+    );
+}
@@ -0,0 +1,532 @@
+//! Examples in doc comments, compiled as tests so that they don't fall out of date.
+//!
+//! Every fenced code block in the doc comments of a package that is tagged `sway`, or not
+//! tagged at all, is a doc test. Blocks tagged `ignore` are skipped, and blocks tagged
+//! `should_not_compile` pass only if they fail to compile.
+//!
+//! Lines of a block that start with `# ` are hidden from the rendered documentation, but are
+//! still compiled, and `##` at the start of a line stands for a literal `#`.
+//!
+//! An example that doesn't declare its program kind is compiled as a script that depends on
+//! the documented package. Its `use` statements are kept at the top of the script, and the rest
+//! of it is wrapped in `fn main() { ... }` unless it declares `main` itself.
+use crate::TestFilter;
+use forc_pkg::{self as pkg, manifest::ManifestFile, source::IPFSNode, PackageManifestFile};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use sway_core::{BuildTarget, Engines};
+use sway_types::Spanned;
+use sway_utils::constants::MANIFEST_FILE_NAME;
+
+/// The statements that declare the kind of a program, which make an example a complete program
+/// rather than a snippet.
+const PROGRAM_KINDS: [&str; 4] = ["script;", "contract;", "predicate;", "library;"];
+const SWAY_FILE_EXTENSION: &str = "sw";
+/// The entry file of the package an example is compiled in.
+const DOC_TEST_ENTRY: &str = "main.sw";
+
+/// What is required of an example for its doc test to pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocTestExpectation {
+    /// The example compiles.
+    Compile,
+    /// The example fails to compile, for blocks tagged `should_not_compile`.
+    ShouldNotCompile,
+}
+
+/// A fenced Sway code block in a doc comment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocTest {
+    /// The name of the test, made of the path of the file relative to the package and the line
+    /// the block starts on, e.g. `src/lib.sw - line 12`.
+    pub name: String,
+    /// The file that contains the doc comment.
+    pub file_path: Arc<PathBuf>,
+    /// The line of the opening fence of the block.
+    pub line_number: usize,
+    /// The code of the block with hidden lines included, along with the line of the file that
+    /// each line of code is on.
+    pub lines: Vec<(usize, String)>,
+    pub expectation: DocTestExpectation,
+    /// Whether the block is tagged `ignore`, in which case it isn't compiled.
+    pub ignored: bool,
+}
+
+/// The result of compiling a single doc test.
+#[derive(Debug)]
+pub struct DocTestResult {
+    /// The name of the test, see [DocTest::name].
+    pub name: String,
+    /// The file that contains the doc comment.
+    pub file_path: Arc<PathBuf>,
+    /// The line of the opening fence of the block.
+    pub line_number: usize,
+    /// The time taken to compile the example.
+    pub duration: Duration,
+    /// The reason the test failed, which is either the first compile error of the example or
+    /// that it compiled although it should not have.
+    pub failure: Option<String>,
+}
+
+/// The set of options used to compile doc tests.
+#[derive(Default, Clone)]
+pub struct DocTestOpts {
+    /// Only use previously downloaded dependencies.
+    pub offline: bool,
+    /// The IPFS node to fetch IPFS dependencies from.
+    pub ipfs_node: IPFSNode,
+}
+
+impl DocTestResult {
+    /// Whether or not the test passed.
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+impl DocTest {
+    /// The source of the program the example is compiled as, along with the line of the file
+    /// that each line of the program comes from, if it does.
+    fn program(&self) -> Vec<(Option<usize>, String)> {
+        let lines = self
+            .lines
+            .iter()
+            .map(|(line_number, line)| (Some(*line_number), line.clone()));
+        let is_program = self
+            .lines
+            .iter()
+            .map(|(_, line)| line.trim())
+            .find(|line| !line.is_empty())
+            .map_or(false, |line| PROGRAM_KINDS.contains(&line));
+        if is_program {
+            return lines.collect();
+        }
+
+        // Split the `use` statements, which may span several lines, from the rest of the code.
+        let mut uses = vec![];
+        let mut body = vec![];
+        let mut in_use = false;
+        for (line_number, line) in lines {
+            let trimmed = line.trim();
+            if in_use || trimmed.starts_with("use ") {
+                in_use = !trimmed.ends_with(';');
+                uses.push((line_number, line));
+            } else {
+                body.push((line_number, line));
+            }
+        }
+
+        let mut program = vec![(None, "script;".to_string()), (None, String::new())];
+        program.extend(uses);
+        if body.iter().any(|(_, line)| line.contains("fn main(")) {
+            program.extend(body);
+        } else {
+            program.push((None, "fn main() {".to_string()));
+            program.extend(
+                body.into_iter()
+                    .map(|(line_number, line)| (line_number, format!("    {line}"))),
+            );
+            program.push((None, "}".to_string()));
+        }
+        program
+    }
+}
+
+/// Find the doc tests in the Sway files of a package.
+pub fn collect_doc_tests(pkg_manifest: &PackageManifestFile) -> anyhow::Result<Vec<DocTest>> {
+    let mut source_files = vec![];
+    if let Some(source_dir) = pkg_manifest.entry_path().parent() {
+        collect_source_files(source_dir, &mut source_files)?;
+    }
+    // Sort the files so the tests are found in the same order on every run.
+    source_files.sort();
+
+    let mut doc_tests = vec![];
+    for path in source_files {
+        let source = fs::read_to_string(&path)?;
+        let relative_path = path
+            .strip_prefix(pkg_manifest.dir())
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        doc_tests.extend(extract_doc_tests(&source, Arc::new(path), &relative_path));
+    }
+    Ok(doc_tests)
+}
+
+fn collect_source_files(dir: &Path, source_files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_source_files(&path, source_files)?;
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some(SWAY_FILE_EXTENSION) {
+            source_files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Find the doc tests in the doc comments of a Sway file, naming them after `file_name`.
+///
+/// A code block that isn't closed before its doc comment ends is not a test.
+pub fn extract_doc_tests(source: &str, file_path: Arc<PathBuf>, file_name: &str) -> Vec<DocTest> {
+    let mut doc_tests = vec![];
+    // The open code block, if any, and whether it is a Sway block.
+    let mut open_block: Option<(DocTest, bool)> = None;
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let Some(doc_line) = doc_comment_line(line) else {
+            open_block = None;
+            continue;
+        };
+        let fence_info = ["```", "~~~"]
+            .into_iter()
+            .find_map(|fence| doc_line.trim_start().strip_prefix(fence));
+        match (open_block.take(), fence_info) {
+            (Some((doc_test, is_sway)), Some(_)) => {
+                if is_sway {
+                    doc_tests.push(doc_test);
+                }
+            }
+            (Some((mut doc_test, is_sway)), None) => {
+                doc_test
+                    .lines
+                    .push((line_number, code_line(doc_line).to_string()));
+                open_block = Some((doc_test, is_sway));
+            }
+            (None, Some(info)) => {
+                let tags = parse_fence_info(info);
+                let (expectation, ignored) = tags.unwrap_or((DocTestExpectation::Compile, false));
+                let doc_test = DocTest {
+                    name: format!("{file_name} - line {line_number}"),
+                    file_path: file_path.clone(),
+                    line_number,
+                    lines: vec![],
+                    expectation,
+                    ignored,
+                };
+                open_block = Some((doc_test, tags.is_some()));
+            }
+            (None, None) => {}
+        }
+    }
+    doc_tests
+}
+
+/// The content of a line of a doc comment, without the `///` or `//!` and the space that
+/// follows it.
+///
+/// Returns `None` if the line isn't part of a doc comment.
+fn doc_comment_line(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    // Comments that start with more than three slashes are not doc comments.
+    if trimmed.starts_with("////") {
+        return None;
+    }
+    let content = trimmed
+        .strip_prefix("///")
+        .or_else(|| trimmed.strip_prefix("//!"))?;
+    Some(content.strip_prefix(' ').unwrap_or(content))
+}
+
+/// Parse the tags after the opening fence of a code block, e.g. `sway, ignore`.
+///
+/// Returns `None` if the block is not Sway code, as in ```` ```rust ````.
+fn parse_fence_info(info: &str) -> Option<(DocTestExpectation, bool)> {
+    let mut expectation = DocTestExpectation::Compile;
+    let mut ignored = false;
+    let tags = info
+        .split(|c| matches!(c, ',' | ' ' | '\t'))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty());
+    for tag in tags {
+        match tag {
+            "sway" => {}
+            "ignore" => ignored = true,
+            "should_not_compile" => expectation = DocTestExpectation::ShouldNotCompile,
+            _ => return None,
+        }
+    }
+    Some((expectation, ignored))
+}
+
+/// The code on a line of a code block, with the `#` of a hidden line removed.
+fn code_line(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    if trimmed == "#" {
+        ""
+    } else if let Some(hidden) = trimmed
+        .strip_prefix("# ")
+        .or_else(|| trimmed.strip_prefix("#\t"))
+    {
+        hidden
+    } else if trimmed.starts_with("##") {
+        &trimmed[1..]
+    } else {
+        line
+    }
+}
+
+/// Compile the doc tests of a package that aren't ignored and match the filter.
+pub fn run_doc_tests(
+    pkg_manifest: &PackageManifestFile,
+    doc_tests: &[DocTest],
+    test_filter: Option<&TestFilter>,
+    opts: &DocTestOpts,
+) -> anyhow::Result<Vec<DocTestResult>> {
+    let manifest = doc_test_manifest(pkg_manifest)?;
+    doc_tests
+        .iter()
+        .filter(|doc_test| !doc_test.ignored)
+        .filter(|doc_test| test_filter.map_or(true, |filter| filter.filter(&doc_test.name)))
+        .map(|doc_test| {
+            let start = Instant::now();
+            let compile_error = compile_doc_test(doc_test, &manifest, opts)?;
+            let failure = match (doc_test.expectation, compile_error) {
+                (DocTestExpectation::Compile, compile_error) => compile_error,
+                (DocTestExpectation::ShouldNotCompile, Some(_)) => None,
+                (DocTestExpectation::ShouldNotCompile, None) => {
+                    Some("the example compiled, but it should not have".to_string())
+                }
+            };
+            Ok(DocTestResult {
+                name: doc_test.name.clone(),
+                file_path: doc_test.file_path.clone(),
+                line_number: doc_test.line_number,
+                duration: start.elapsed(),
+                failure,
+            })
+        })
+        .collect()
+}
+
+/// The manifest of the package the examples are compiled in, which depends on the documented
+/// package.
+///
+/// If the documented package doesn't use the implicit `std`, its own `core` and `std`
+/// dependencies are used instead.
+fn doc_test_manifest(pkg_manifest: &PackageManifestFile) -> anyhow::Result<String> {
+    let pkg_dir = pkg_manifest.dir();
+    let implicit_std = pkg_manifest.project.implicit_std.unwrap_or(true);
+
+    let mut dependencies = toml::value::Table::new();
+    let mut documented_pkg = toml::value::Table::new();
+    documented_pkg.insert(
+        "path".to_string(),
+        toml::Value::String(pkg_dir.display().to_string()),
+    );
+    dependencies.insert(
+        pkg_manifest.project.name.clone(),
+        toml::Value::Table(documented_pkg),
+    );
+    if !implicit_std {
+        for (name, dependency) in pkg_manifest.dependencies.iter().flatten() {
+            if name != "core" && name != "std" {
+                continue;
+            }
+            let mut dependency = toml::Value::try_from(dependency)?;
+            // Path dependencies are relative to the directory of the documented package.
+            if let Some(toml::Value::String(path)) = dependency.get_mut("path") {
+                *path = pkg_dir.join(&*path).display().to_string();
+            }
+            dependencies.insert(name.clone(), dependency);
+        }
+    }
+
+    let mut project = toml::value::Table::new();
+    project.insert(
+        "name".to_string(),
+        toml::Value::String(format!("{}_doc_test", pkg_manifest.project.name)),
+    );
+    project.insert(
+        "entry".to_string(),
+        toml::Value::String(DOC_TEST_ENTRY.to_string()),
+    );
+    project.insert(
+        "license".to_string(),
+        toml::Value::String(pkg_manifest.project.license.clone()),
+    );
+    project.insert(
+        "implicit-std".to_string(),
+        toml::Value::Boolean(implicit_std),
+    );
+
+    let mut manifest = toml::value::Table::new();
+    manifest.insert("project".to_string(), toml::Value::Table(project));
+    manifest.insert("dependencies".to_string(), toml::Value::Table(dependencies));
+    Ok(toml::to_string(&manifest)?)
+}
+
+/// Compile the example of a doc test in a package of its own.
+///
+/// Returns the first compile error, along with where it is, if the example fails to compile.
+fn compile_doc_test(
+    doc_test: &DocTest,
+    manifest: &str,
+    opts: &DocTestOpts,
+) -> anyhow::Result<Option<String>> {
+    let dir = tempfile::tempdir()?;
+    let source_dir = dir.path().join("src");
+    fs::create_dir_all(&source_dir)?;
+    fs::write(dir.path().join(MANIFEST_FILE_NAME), manifest)?;
+    let program = doc_test.program();
+    let source = program
+        .iter()
+        .map(|(_, line)| line.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let entry_path = source_dir.join(DOC_TEST_ENTRY);
+    fs::write(&entry_path, source)?;
+
+    let manifest_file = ManifestFile::from_dir(dir.path())?;
+    let plan = pkg::BuildPlan::from_lock_and_manifests(
+        &manifest_file.lock_path()?,
+        &manifest_file.member_manifests()?,
+        false,
+        opts.offline,
+        opts.ipfs_node.clone(),
+    )?;
+    let engines = Engines::default();
+    let results = pkg::check(&plan, BuildTarget::default(), true, false, &engines)?;
+    let Some(error) = results.iter().flat_map(|result| &result.errors).next() else {
+        return Ok(None);
+    };
+
+    // Errors in the example point to the doc comment, and errors in the documented package or
+    // its dependencies to their own files.
+    let span = error.span();
+    let (start, _) = span.line_col();
+    let path = span
+        .source_id()
+        .map(|source_id| engines.se().get_path(source_id));
+    let location = match path {
+        Some(path) if path.ends_with(Path::new("src").join(DOC_TEST_ENTRY)) => program
+            .get(start.line.saturating_sub(1))
+            .and_then(|(line_number, _)| *line_number)
+            .map_or_else(
+                || format!("{}:{}", doc_test.file_path.display(), doc_test.line_number),
+                |line_number| format!("{}:{line_number}", doc_test.file_path.display()),
+            ),
+        Some(path) => format!("{}:{}", path.display(), start.line),
+        None => format!("{}:{}", doc_test.file_path.display(), doc_test.line_number),
+    };
+    Ok(Some(format!("{error}\n          --> {location}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_doc_tests, DocTestExpectation};
+    use std::{path::PathBuf, sync::Arc};
+
+    const SOURCE: &str = r#"library;
+
+/// Adds one.
+///
+/// ```sway
+/// # use my_lib::add_one;
+/// let x = add_one(1);
+/// assert(x == 2);
+/// ```
+///
+/// ```ignore
+/// let y = add_one(true);
+/// ```
+///
+/// ```should_not_compile
+/// let z: bool = add_one(1);
+/// ```
+///
+/// ```text
+/// not sway
+/// ```
+pub fn add_one(x: u64) -> u64 {
+    x + 1
+}
+
+//// ```
+//// not a doc comment
+//// ```
+"#;
+
+    #[test]
+    fn test_extract_doc_tests() {
+        let file_path = Arc::new(PathBuf::from("src/lib.sw"));
+        let doc_tests = extract_doc_tests(SOURCE, file_path, "src/lib.sw");
+        let summary: Vec<_> = doc_tests
+            .iter()
+            .map(|doc_test| {
+                (
+                    doc_test.name.as_str(),
+                    doc_test.expectation,
+                    doc_test.ignored,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("src/lib.sw - line 5", DocTestExpectation::Compile, false),
+                ("src/lib.sw - line 11", DocTestExpectation::Compile, true),
+                (
+                    "src/lib.sw - line 15",
+                    DocTestExpectation::ShouldNotCompile,
+                    false
+                ),
+            ]
+        );
+        assert_eq!(
+            doc_tests[0].lines,
+            [
+                (6, "use my_lib::add_one;".to_string()),
+                (7, "let x = add_one(1);".to_string()),
+                (8, "assert(x == 2);".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_doc_test_program_wraps_snippets() {
+        let file_path = Arc::new(PathBuf::from("src/lib.sw"));
+        let doc_tests = extract_doc_tests(SOURCE, file_path, "src/lib.sw");
+        let program: Vec<_> = doc_tests[0]
+            .program()
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect();
+        assert_eq!(
+            program.join("\n"),
+            "script;\n\nuse my_lib::add_one;\nfn main() {\n    let x = add_one(1);\n    assert(x == 2);\n}"
+        );
+    }
+
+    #[test]
+    fn test_doc_test_program_keeps_programs() {
+        let source = "/// ```\n/// script;\n///\n/// fn main() {}\n/// ```\n";
+        let doc_tests = extract_doc_tests(source, Arc::new(PathBuf::new()), "src/lib.sw");
+        assert_eq!(
+            doc_tests[0].program(),
+            [
+                (Some(2), "script;".to_string()),
+                (Some(3), String::new()),
+                (Some(4), "fn main() {}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hidden_lines() {
+        let source =
+            "/// ```\n/// #\n/// # let a = 1;\n/// ##[storage(read)]\n/// #[test]\n/// ```\n";
+        let doc_tests = extract_doc_tests(source, Arc::new(PathBuf::new()), "src/lib.sw");
+        let lines: Vec<_> = doc_tests[0]
+            .lines
+            .iter()
+            .map(|(_, line)| line.as_str())
+            .collect();
+        assert_eq!(lines, ["", "let a = 1;", "#[storage(read)]", "#[test]"]);
+    }
+}
@@ -1,3 +1,9 @@
+mod doc_test;
+
+pub use doc_test::{
+    collect_doc_tests, extract_doc_tests, run_doc_tests, DocTest, DocTestExpectation, DocTestOpts,
+    DocTestResult,
+};
 use forc_pkg as pkg;
 use fuel_abi_types::error_codes::ErrorSignal;
 use fuel_tx as tx;
@@ -9,7 +15,7 @@ use pkg::{Built, BuiltPackage};
 use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
 use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
-use sway_core::BuildTarget;
+use sway_core::{source_map::SourceLocation, BuildTarget};
 use sway_types::Span;
 
 /// The result of a `forc test` invocation.
@@ -63,6 +69,9 @@ pub struct TestResult {
     pub logs: Vec<fuel_tx::Receipt>,
     /// Gas used while executing this test.
     pub gas_used: u64,
+    /// Where the test reverted, if it did so within its own bytecode: the source location of the
+    /// reverting instruction, followed by the calls it was inlined from, innermost first.
+    pub revert_locations: Vec<SourceLocation>,
 }
 
 const TEST_METADATA_SEED: u64 = 0x7E57u64;
@@ -160,6 +169,8 @@ pub struct Opts {
     pub release: bool,
     /// Should warnings be treated as errors?
     pub error_on_warnings: bool,
+    /// Verify the IR after every optimization pass.
+    pub verify_ir: bool,
     /// Output the time elapsed over each part of the compilation process.
     pub time_phases: bool,
     /// Output compilation metrics into file.
@@ -434,6 +445,30 @@ impl<'a> PackageTests {
                             anyhow::anyhow!("missing used gas information from test execution")
                         })?;
 
+                    // Find where the test reverted, unless it was within a contract it called,
+                    // whose instructions aren't in this package's source map.
+                    let revert_locations = receipts
+                        .iter()
+                        .find_map(|receipt| match receipt {
+                            tx::Receipt::Revert { id, pc, is, .. }
+                            | tx::Receipt::Panic { id, pc, is, .. }
+                                if *id == tx::ContractId::zeroed() =>
+                            {
+                                Some((pc - is) as usize / Instruction::SIZE)
+                            }
+                            _ => None,
+                        })
+                        .and_then(|offset| {
+                            let source_map = pkg_with_tests.source_map();
+                            let location = source_map.location(offset)?;
+                            Some(
+                                std::iter::once(location)
+                                    .chain(source_map.inlined_at(offset))
+                                    .collect(),
+                            )
+                        })
+                        .unwrap_or_default();
+
                     // Only retain `Log` and `LogData` receipts.
                     let logs = receipts
                         .into_iter()
@@ -455,6 +490,7 @@ impl<'a> PackageTests {
                         condition,
                         logs,
                         gas_used,
+                        revert_locations,
                     })
                 })
                 .collect::<anyhow::Result<_>>()
@@ -501,6 +537,7 @@ impl Opts {
             build_profile: self.build_profile,
             release: self.release,
             error_on_warnings: self.error_on_warnings,
+            verify_ir: self.verify_ir,
             time_phases: self.time_phases,
             metrics_outfile: self.metrics_outfile,
             tests: true,
@@ -831,6 +868,9 @@ fn exec_test(
 mod tests {
     use std::path::PathBuf;
 
+    use fuel_vm::prelude::Instruction;
+    use sway_core::source_map::SourceMap;
+
     use crate::{build, BuiltTests, Opts, TestFilter, TestResult};
 
     /// Name of the folder containing required data for tests to run, such as an example forc
@@ -838,14 +878,22 @@ mod tests {
     const TEST_DATA_FOLDER_NAME: &str = "test_data";
     /// Name of the library package in the "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
     const TEST_LIBRARY_PACKAGE_NAME: &str = "test_library";
+    /// Name of the library package whose test reverts within an inlined function.
+    const TEST_REVERT_LIBRARY_PACKAGE_NAME: &str = "test_revert_library";
 
     /// Build the tests in the test library located at
     /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME/TEST_LIBRARY_PACKAGE_NAME".
     fn test_library_built_tests() -> anyhow::Result<BuiltTests> {
+        built_tests(TEST_LIBRARY_PACKAGE_NAME)
+    }
+
+    /// Build the tests in the package located at
+    /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME/`package_name`".
+    fn built_tests(package_name: &str) -> anyhow::Result<BuiltTests> {
         let cargo_manifest_dir = env!("CARGO_MANIFEST_DIR");
         let library_package_dir = PathBuf::from(cargo_manifest_dir)
             .join(TEST_DATA_FOLDER_NAME)
-            .join(TEST_LIBRARY_PACKAGE_NAME);
+            .join(package_name);
         let library_package_dir_string = library_package_dir.to_string_lossy().to_string();
         let build_options = Opts {
             pkg: forc_pkg::PkgOpts {
@@ -949,4 +997,44 @@ mod tests {
 
         assert_eq!(tested_package_test_count, 2)
     }
+
+    #[test]
+    fn test_source_map_round_trip() {
+        let BuiltTests::Package(pkg_tests) = built_tests(TEST_REVERT_LIBRARY_PACKAGE_NAME).unwrap()
+        else {
+            unreachable!("test_revert_library is a package, not a workspace.")
+        };
+        let built = pkg_tests.built_pkg_with_tests();
+        let json = serde_json::to_vec(built.source_map()).unwrap();
+        let source_map: SourceMap = serde_json::from_slice(&json).unwrap();
+        assert_eq!(source_map.version, SourceMap::VERSION);
+
+        // The test's revert is inlined from `fail`, so its instructions map to the `__revert` on
+        // line 5 with the call to `fail` on line 10 as where they were inlined.
+        let lib_path = PathBuf::from("src/lib.sw");
+        let entry_offset = built.bytecode.entries[0].finalized.imm as usize;
+        let num_instructions = built.bytecode.bytes.len() / Instruction::SIZE;
+        let revert_offset = (entry_offset..num_instructions)
+            .find(|offset| {
+                source_map
+                    .location(*offset)
+                    .map_or(false, |location| location.start.line == 5)
+            })
+            .expect("no instruction maps to the revert");
+        let location = source_map.location(revert_offset).unwrap();
+        assert_eq!(location.path, lib_path);
+        let inlined_at = source_map.inlined_at(revert_offset);
+        assert_eq!(inlined_at.len(), 1);
+        assert_eq!(inlined_at[0].path, lib_path);
+        assert_eq!(inlined_at[0].start.line, 10);
+
+        // The test runner reports the same locations for where the test reverted.
+        let test_runners = rayon::ThreadPoolBuilder::new().build().unwrap();
+        let test_results = pkg_tests.run_tests(&test_runners, None).unwrap().tests;
+        assert!(test_results[0].passed());
+        assert_eq!(
+            test_results[0].revert_locations,
+            [location, inlined_at[0].clone()]
+        );
+    }
 }
@@ -0,0 +1,11 @@
+library;
+
+#[inline(always)]
+fn fail(code: u64) {
+    __revert(code)
+}
+
+#[test(should_revert = "42")]
+fn test_fail() {
+    fail(42)
+}
@@ -441,6 +441,14 @@ fn format_err(source_engine: &SourceEngine, err: &CompileError) {
     let mut end_pos = span.end();
 
     let friendly_str = maybe_uwuify(&format!("{err}"));
+    let related = err.related_spans();
+    let related_paths: Vec<_> = related
+        .iter()
+        .map(|(span, _)| {
+            span.source_id()
+                .map(|id| source_engine.get_path(id).to_string_lossy().into_owned())
+        })
+        .collect();
     let (snippet_title, snippet_slices) = if start_pos < end_pos {
         let title = Some(Annotation {
             label: None,
@@ -450,7 +458,7 @@ fn format_err(source_engine: &SourceEngine, err: &CompileError) {
 
         let (mut start, end) = err.span().line_col();
         let input = construct_window(&mut start, end, &mut start_pos, &mut end_pos, input);
-        let slices = vec![Slice {
+        let mut slices = vec![Slice {
             source: input,
             line_start: start.line,
             origin: path_str.as_deref(),
@@ -461,6 +469,34 @@ fn format_err(source_engine: &SourceEngine, err: &CompileError) {
                 range: (start_pos, end_pos),
             }],
         }];
+        slices.extend(
+            related
+                .iter()
+                .zip(&related_paths)
+                .filter(|((span, _), _)| span.start() < span.end())
+                .map(|((span, label), path)| {
+                    let (mut start_pos, mut end_pos) = (span.start(), span.end());
+                    let (mut start, end) = span.line_col();
+                    let input = construct_window(
+                        &mut start,
+                        end,
+                        &mut start_pos,
+                        &mut end_pos,
+                        span.input(),
+                    );
+                    Slice {
+                        source: input,
+                        line_start: start.line,
+                        origin: path.as_deref(),
+                        fold: false,
+                        annotations: vec![SourceAnnotation {
+                            label,
+                            annotation_type: AnnotationType::Note,
+                            range: (start_pos, end_pos),
+                        }],
+                    }
+                }),
+        );
 
         (title, slices)
     } else {
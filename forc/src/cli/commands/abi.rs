@@ -0,0 +1,48 @@
+use crate::ops::forc_abi_diff;
+use clap::{Parser, Subcommand};
+use forc_util::{ForcError, ForcResult};
+use std::path::PathBuf;
+
+/// Inspect the JSON ABIs generated by `forc build`.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[clap(subcommand)]
+    pub command: AbiSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AbiSubcommand {
+    Diff(DiffCommand),
+}
+
+/// Compare two JSON ABIs and report the functions, logged types and configurables which were
+/// added, removed or changed.
+///
+/// Types are compared by their structure, so building the same program twice never reports
+/// changes. Changes other than additions may break users of the old ABI, and make the command
+/// exit with code 2 so that it can gate upgrades in CI.
+#[derive(Debug, Parser)]
+pub struct DiffCommand {
+    /// Path to the old JSON ABI.
+    pub old: PathBuf,
+    /// Path to the new JSON ABI.
+    pub new: PathBuf,
+    /// Print the changes as a JSON report.
+    #[clap(long)]
+    pub json: bool,
+}
+
+pub(crate) fn exec(command: Command) -> ForcResult<()> {
+    match command.command {
+        AbiSubcommand::Diff(command) => {
+            if forc_abi_diff::abi_diff(&command)? {
+                Ok(())
+            } else {
+                let forc_error: ForcError =
+                    "The new ABI is not compatible with the old one.".into();
+                const INCOMPATIBLE_ABI_EXIT_CODE: u8 = 2;
+                Err(forc_error.exit_code(INCOMPATIBLE_ABI_EXIT_CODE))
+            }
+        }
+    }
+}
@@ -8,6 +8,9 @@ use forc_util::ForcResult;
 ///
 /// - `script`, `predicate` and `contract` projects will produce their bytecode in binary format `<project-name>.bin`.
 ///
+/// - All projects will produce a source map in JSON format `<project-name>-source-map.json`, mapping
+/// each instruction of the bytecode to the source it was compiled from.
+///
 /// - `script` projects will also produce a file containing the hash of the bytecode binary
 /// `<project-name>-bin-hash` (using `fuel_cypto::Hasher`).
 ///
@@ -1,3 +1,4 @@
+pub mod abi;
 pub mod addr2line;
 pub mod build;
 pub mod check;
@@ -2,7 +2,7 @@ use crate::cli;
 use ansi_term::Colour;
 use clap::Parser;
 use forc_pkg as pkg;
-use forc_test::{TestFilter, TestRunnerCount, TestedPackage};
+use forc_test::{DocTestOpts, DocTestResult, TestFilter, TestRunnerCount, TestedPackage};
 use forc_util::{tx_utils::format_log_receipts, ForcError, ForcResult};
 use tracing::info;
 
@@ -23,6 +23,9 @@ use tracing::info;
 /// Upon successful compilation, test scripts are executed to their completion. A test is
 /// considered a failure in the case that a revert (`rvrt`) instruction is encountered during
 /// execution. Otherwise, it is considered a success.
+///
+/// With `--doc`, the fenced Sway code blocks in doc comments are compiled as tests instead. Blocks
+/// tagged `ignore` are skipped, and blocks tagged `should_not_compile` must fail to compile.
 #[derive(Debug, Parser)]
 pub struct Command {
     #[clap(flatten)]
@@ -38,6 +41,9 @@ pub struct Command {
     /// Number of threads to utilize when running the tests. By default, this is the number of
     /// threads available in your system.
     pub test_threads: Option<usize>,
+    #[clap(long)]
+    /// Compile the examples in doc comments as tests instead of running the unit tests.
+    pub doc: bool,
 }
 
 /// The set of options provided for controlling output of a test.
@@ -63,6 +69,9 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
         filter_phrase,
         exact_match: cmd.filter_exact,
     });
+    if cmd.doc {
+        return exec_doc_tests(&cmd, test_filter.as_ref());
+    }
     let opts = opts_from_cmd(cmd);
     let built_tests = forc_test::build(opts)?;
     let start = std::time::Instant::now();
@@ -96,6 +105,10 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
         }
     };
 
+    tests_result(all_tests_passed)
+}
+
+fn tests_result(all_tests_passed: bool) -> ForcResult<()> {
     if all_tests_passed {
         Ok(())
     } else {
@@ -105,6 +118,92 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
     }
 }
 
+fn exec_doc_tests(cmd: &Command, test_filter: Option<&TestFilter>) -> ForcResult<()> {
+    let dir = match &cmd.build.pkg.path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let manifest_file = pkg::manifest::ManifestFile::from_dir(&dir)?;
+    let member_manifests = match &manifest_file {
+        pkg::manifest::ManifestFile::Package(pkg_manifest) => vec![(**pkg_manifest).clone()],
+        pkg::manifest::ManifestFile::Workspace(_) => {
+            manifest_file.member_manifests()?.into_values().collect()
+        }
+    };
+    let opts = DocTestOpts {
+        offline: cmd.build.pkg.offline,
+        ipfs_node: cmd.build.pkg.ipfs_node.clone().unwrap_or_default(),
+    };
+
+    let start = std::time::Instant::now();
+    let mut all_tests_passed = true;
+    for pkg_manifest in &member_manifests {
+        let doc_tests = forc_test::collect_doc_tests(pkg_manifest)?;
+        let results = forc_test::run_doc_tests(pkg_manifest, &doc_tests, test_filter, &opts)?;
+        let num_tests_ignored = doc_tests.len() - results.len();
+        if member_manifests.len() > 1 {
+            info!("\n   tested -- {}\n", pkg_manifest.project.name);
+        }
+        info!(
+            "   Running {} doc {}, filtered {} {}",
+            results.len(),
+            formatted_test_count_string(&results.len()),
+            num_tests_ignored,
+            formatted_test_count_string(&num_tests_ignored)
+        );
+        print_doc_test_results(&results);
+        all_tests_passed &= results.iter().all(DocTestResult::passed);
+    }
+    if member_manifests.len() > 1 {
+        info!("\n   Finished in {:?}", start.elapsed());
+    }
+
+    tests_result(all_tests_passed)
+}
+
+fn print_doc_test_results(results: &[DocTestResult]) {
+    let succeeded = results.iter().filter(|t| t.passed()).count();
+    let failed = results.len() - succeeded;
+    for result in results {
+        let (state, color) = match result.passed() {
+            true => ("ok", Colour::Green),
+            false => ("FAILED", Colour::Red),
+        };
+        info!(
+            "      test {} ... {} ({:?})",
+            result.name,
+            color.paint(state),
+            result.duration
+        );
+    }
+    let (state, color) = match failed == 0 {
+        true => ("OK", Colour::Green),
+        false => ("FAILED", Colour::Red),
+    };
+    if failed != 0 {
+        info!("\n   failures:");
+        for result in results.iter().filter(|t| !t.passed()) {
+            info!(
+                "      - test {}, {:?}:{} ",
+                result.name, result.file_path, result.line_number
+            );
+            if let Some(failure) = &result.failure {
+                info!("        {failure}");
+            }
+        }
+        info!("\n");
+    }
+
+    let durations: std::time::Duration = results.iter().map(|result| result.duration).sum();
+    info!(
+        "   Result: {}. {} passed. {} failed. Finished in {:?}.",
+        color.paint(state),
+        succeeded,
+        failed,
+        durations
+    );
+}
+
 fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> ForcResult<()> {
     let succeeded = pkg.tests.iter().filter(|t| t.passed()).count();
     let failed = pkg.tests.len() - succeeded;
@@ -162,6 +261,14 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
                 }
                 info!("{failed_info_str}");
             }
+            if let Some((location, inlined_at)) = failed_test.revert_locations.split_first() {
+                let (path, start) = (location.path.display(), location.start);
+                info!("        reverted at {}:{}:{}", path, start.line, start.col);
+                for location in inlined_at {
+                    let (path, start) = (location.path.display(), location.start);
+                    info!("          inlined at {}:{}:{}", path, start.line, start.col);
+                }
+            }
             info!("        Logs: {}", formatted_logs);
         }
         info!("\n");
@@ -201,6 +308,7 @@ fn opts_from_cmd(cmd: Command) -> forc_test::Opts {
             finalized_asm: cmd.build.print.finalized_asm,
             intermediate_asm: cmd.build.print.intermediate_asm,
             ir: cmd.build.print.ir,
+            profile_static: cmd.build.print.profile_static,
             reverse_order: cmd.build.print.reverse_order,
         },
         time_phases: cmd.build.print.time_phases,
@@ -212,6 +320,7 @@ fn opts_from_cmd(cmd: Command) -> forc_test::Opts {
         build_profile: cmd.build.profile.build_profile,
         release: cmd.build.profile.release,
         error_on_warnings: cmd.build.profile.error_on_warnings,
+        verify_ir: cmd.build.profile.verify_ir,
         binary_outfile: cmd.build.output.bin_file,
         debug_outfile: cmd.build.output.debug_file,
         build_target: cmd.build.build_target,
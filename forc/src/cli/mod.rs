@@ -1,9 +1,10 @@
 use std::str::FromStr;
 
 use self::commands::{
-    addr2line, build, check, clean, completions, contract_id, init, new, parse_bytecode, plugins,
-    predicate_root, template, test, update,
+    abi, addr2line, build, check, clean, completions, contract_id, init, new, parse_bytecode,
+    plugins, predicate_root, template, test, update,
 };
+pub use abi::{Command as AbiCommand, DiffCommand as AbiDiffCommand};
 use addr2line::Command as Addr2LineCommand;
 use anyhow::anyhow;
 pub use build::Command as BuildCommand;
@@ -50,6 +51,7 @@ struct Opt {
 
 #[derive(Subcommand, Debug)]
 enum Forc {
+    Abi(AbiCommand),
     #[clap(name = "addr2line")]
     Addr2Line(Addr2LineCommand),
     #[clap(visible_alias = "b")]
@@ -91,6 +93,7 @@ pub async fn run_cli() -> ForcResult<()> {
     init_tracing_subscriber(tracing_options);
 
     match opt.command {
+        Forc::Abi(command) => abi::exec(command),
         Forc::Addr2Line(command) => addr2line::exec(command),
         Forc::Build(command) => build::exec(command),
         Forc::Check(command) => check::exec(command),
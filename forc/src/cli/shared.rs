@@ -2,7 +2,7 @@
 
 use clap::{Args, Parser};
 use forc_pkg::source::IPFSNode;
-use sway_core::BuildTarget;
+use sway_core::{BuildTarget, GasProfileFormat};
 
 /// Args that can be shared between all commands that `build` a package. E.g. `build`, `test`,
 /// `deploy`.
@@ -50,6 +50,10 @@ pub struct BuildProfile {
     /// Treat warnings as errors.
     #[clap(long)]
     pub error_on_warnings: bool,
+    /// Verify the IR after every optimization pass, failing with the name of the pass which left
+    /// it invalid. This is always done by debug builds of the compiler.
+    #[clap(long)]
+    pub verify_ir: bool,
 }
 
 /// Options related to printing stages of compiler output.
@@ -83,6 +87,20 @@ pub struct Print {
     /// Print the generated Sway IR (Intermediate Representation).
     #[clap(long)]
     pub ir: bool,
+    /// Write a static estimate of the gas used by each instruction, block and function of the
+    /// finalized ASM, without running it, to `<name>-gas-profile.txt` or `.json` in the output
+    /// directory.
+    ///
+    /// Instructions whose cost depends on the memory or storage they touch are flagged as
+    /// dynamic, and the blocks of loops are costed per iteration.
+    #[clap(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        min_values = 0,
+        default_missing_value = "text"
+    )]
+    pub profile_static: Option<GasProfileFormat>,
     /// Output the time elapsed over each part of the compilation process.
     #[clap(long)]
     pub time_phases: bool,
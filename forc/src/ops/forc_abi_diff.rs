@@ -0,0 +1,381 @@
+use crate::cli::AbiDiffCommand;
+use anyhow::{anyhow, Context, Result};
+use fuel_abi_types::abi::full_program::{FullABIFunction, FullProgramABI, FullTypeApplication};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, path::Path};
+use tracing::info;
+
+/// A difference between an old and a new JSON ABI.
+///
+/// Types are compared by their structure rather than by their IDs, which change between builds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AbiChange {
+    FunctionAdded {
+        signature: String,
+    },
+    FunctionRemoved {
+        signature: String,
+    },
+    FunctionChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+    LoggedTypeAdded {
+        log_id: u64,
+        logged_type: String,
+    },
+    LoggedTypeRemoved {
+        log_id: u64,
+        logged_type: String,
+    },
+    LoggedTypeChanged {
+        log_id: u64,
+        old: String,
+        new: String,
+    },
+    ConfigurableAdded {
+        configurable: String,
+    },
+    ConfigurableRemoved {
+        configurable: String,
+    },
+    ConfigurableChanged {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl AbiChange {
+    /// Returns `true` for changes which may break users of the old ABI. Only additions are
+    /// compatible.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self,
+            AbiChange::FunctionAdded { .. }
+                | AbiChange::LoggedTypeAdded { .. }
+                | AbiChange::ConfigurableAdded { .. }
+        )
+    }
+}
+
+impl fmt::Display for AbiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiChange::FunctionAdded { signature } => write!(f, "added function `{signature}`"),
+            AbiChange::FunctionRemoved { signature } => {
+                write!(f, "removed function `{signature}`")
+            }
+            AbiChange::FunctionChanged { name, old, new } => {
+                write!(f, "changed function `{name}` from `{old}` to `{new}`")
+            }
+            AbiChange::LoggedTypeAdded {
+                log_id,
+                logged_type,
+            } => write!(f, "added logged type `{logged_type}` with log ID {log_id}"),
+            AbiChange::LoggedTypeRemoved {
+                log_id,
+                logged_type,
+            } => write!(
+                f,
+                "removed logged type `{logged_type}` with log ID {log_id}"
+            ),
+            AbiChange::LoggedTypeChanged { log_id, old, new } => {
+                write!(
+                    f,
+                    "changed logged type with log ID {log_id} from `{old}` to `{new}`"
+                )
+            }
+            AbiChange::ConfigurableAdded { configurable } => {
+                write!(f, "added configurable `{configurable}`")
+            }
+            AbiChange::ConfigurableRemoved { configurable } => {
+                write!(f, "removed configurable `{configurable}`")
+            }
+            AbiChange::ConfigurableChanged { name, old, new } => {
+                write!(f, "changed configurable `{name}` from `{old}` to `{new}`")
+            }
+        }
+    }
+}
+
+/// The differences between an old and a new JSON ABI.
+#[derive(Debug, Default, Serialize)]
+pub struct AbiDiff {
+    pub changes: Vec<AbiChange>,
+}
+
+impl AbiDiff {
+    /// Compare the functions, logged types and configurables of two ABIs, by function name, log
+    /// ID and configurable name respectively.
+    pub fn between(old: &FullProgramABI, new: &FullProgramABI) -> Self {
+        let mut changes = vec![];
+
+        let functions = |abi: &FullProgramABI| {
+            abi.functions
+                .iter()
+                .map(|function| (function.name().to_string(), function_signature(function)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        for (name, change) in compare(functions(old), functions(new)) {
+            changes.push(match change {
+                Compared::Added(signature) => AbiChange::FunctionAdded { signature },
+                Compared::Removed(signature) => AbiChange::FunctionRemoved { signature },
+                Compared::Changed(old, new) => AbiChange::FunctionChanged { name, old, new },
+            });
+        }
+
+        let logged_types = |abi: &FullProgramABI| {
+            abi.logged_types
+                .iter()
+                .map(|logged_type| (logged_type.log_id, type_structure(&logged_type.application)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        for (log_id, change) in compare(logged_types(old), logged_types(new)) {
+            changes.push(match change {
+                Compared::Added(logged_type) => AbiChange::LoggedTypeAdded {
+                    log_id,
+                    logged_type,
+                },
+                Compared::Removed(logged_type) => AbiChange::LoggedTypeRemoved {
+                    log_id,
+                    logged_type,
+                },
+                Compared::Changed(old, new) => AbiChange::LoggedTypeChanged { log_id, old, new },
+            });
+        }
+
+        let configurables = |abi: &FullProgramABI| {
+            abi.configurables
+                .iter()
+                .map(|configurable| {
+                    let type_structure = type_structure(&configurable.application);
+                    let layout = format!(
+                        "{}: {type_structure} at offset {}",
+                        configurable.name, configurable.offset
+                    );
+                    (configurable.name.clone(), layout)
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        for (name, change) in compare(configurables(old), configurables(new)) {
+            changes.push(match change {
+                Compared::Added(configurable) => AbiChange::ConfigurableAdded { configurable },
+                Compared::Removed(configurable) => AbiChange::ConfigurableRemoved { configurable },
+                Compared::Changed(old, new) => AbiChange::ConfigurableChanged { name, old, new },
+            });
+        }
+
+        Self { changes }
+    }
+
+    /// Returns `true` if none of the changes may break users of the old ABI.
+    pub fn is_compatible(&self) -> bool {
+        !self.changes.iter().any(AbiChange::is_breaking)
+    }
+}
+
+/// How an item differs between the old and the new ABI.
+enum Compared {
+    Added(String),
+    Removed(String),
+    Changed(String, String),
+}
+
+/// Compare the descriptions of the items of the old and the new ABI, in the order of their keys.
+fn compare<K: Ord>(mut old: BTreeMap<K, String>, new: BTreeMap<K, String>) -> Vec<(K, Compared)> {
+    let mut compared = vec![];
+    for (key, new_item) in new {
+        match old.remove(&key) {
+            None => compared.push((key, Compared::Added(new_item))),
+            Some(old_item) if old_item != new_item => {
+                compared.push((key, Compared::Changed(old_item, new_item)))
+            }
+            Some(_) => {}
+        }
+    }
+    compared.extend(
+        old.into_iter()
+            .map(|(key, old_item)| (key, Compared::Removed(old_item))),
+    );
+    compared
+}
+
+/// The signature of a function with the structure of its argument and output types, as in
+/// `transfer(to: struct Point { x: u64, y: u64 }, amount: u64) -> bool`.
+fn function_signature(function: &FullABIFunction) -> String {
+    let inputs = function
+        .inputs()
+        .iter()
+        .map(|input| format!("{}: {}", input.name, type_structure(input)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let output = type_structure(function.output());
+    format!("{}({inputs}) -> {output}", function.name())
+}
+
+/// The structure of a type application, which is the same however its types are numbered.
+///
+/// Components are listed with their names and types, as in `struct Point { x: u64, y: u64 }`,
+/// followed by type arguments, as in `struct Vec { buf: ..., len: u64 }<u8>`.
+fn type_structure(application: &FullTypeApplication) -> String {
+    let type_decl = &application.type_decl;
+    let mut structure = type_decl.type_field.clone();
+    if !type_decl.components.is_empty() {
+        let components = type_decl
+            .components
+            .iter()
+            .map(|component| format!("{}: {}", component.name, type_structure(component)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        structure.push_str(&format!(" {{ {components} }}"));
+    }
+    if !application.type_arguments.is_empty() {
+        let type_arguments = application
+            .type_arguments
+            .iter()
+            .map(type_structure)
+            .collect::<Vec<_>>()
+            .join(", ");
+        structure.push_str(&format!("<{type_arguments}>"));
+    }
+    structure
+}
+
+/// Read the JSON ABI generated by `forc build` at `path`.
+fn read_abi(path: &Path) -> Result<FullProgramABI> {
+    let abi = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read ABI file at '{}'", path.display()))?;
+    FullProgramABI::from_json_abi(&abi)
+        .map_err(|e| anyhow!("invalid ABI JSON at '{}': {e}", path.display()))
+}
+
+/// Print the differences between the old and the new ABI of `command`, returning whether the new
+/// ABI is compatible with the old one.
+pub fn abi_diff(command: &AbiDiffCommand) -> Result<bool> {
+    let old = read_abi(&command.old)?;
+    let new = read_abi(&command.new)?;
+    let diff = AbiDiff::between(&old, &new);
+    if command.json {
+        info!("{}", serde_json::to_string_pretty(&diff)?);
+    } else if diff.changes.is_empty() {
+        info!("The ABIs are identical.");
+    } else {
+        for change in &diff.changes {
+            let marker = if change.is_breaking() { "!" } else { " " };
+            info!("{marker} {change}");
+        }
+    }
+    Ok(diff.is_compatible())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> FullProgramABI {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/abi_diff")
+            .join(name)
+            .with_extension("json");
+        read_abi(&path).unwrap()
+    }
+
+    fn diff_from_base(name: &str) -> AbiDiff {
+        AbiDiff::between(&fixture("base"), &fixture(name))
+    }
+
+    #[test]
+    fn renumbered_types_are_identical() {
+        let diff = diff_from_base("renumbered");
+        assert_eq!(diff.changes, vec![]);
+        assert!(diff.is_compatible());
+    }
+
+    #[test]
+    fn added_function_is_compatible() {
+        let diff = diff_from_base("function_added");
+        assert_eq!(
+            diff.changes,
+            vec![AbiChange::FunctionAdded {
+                signature: "mint(amount: u64) -> ()".to_string(),
+            }]
+        );
+        assert!(diff.is_compatible());
+    }
+
+    #[test]
+    fn removed_function_is_breaking() {
+        let diff = diff_from_base("function_removed");
+        assert_eq!(
+            diff.changes,
+            vec![AbiChange::FunctionRemoved {
+                signature: "balance() -> u64".to_string(),
+            }]
+        );
+        assert!(!diff.is_compatible());
+
+        // The other way around, it is an added function.
+        let diff = AbiDiff::between(&fixture("function_removed"), &fixture("base"));
+        assert!(diff.is_compatible());
+    }
+
+    #[test]
+    fn changed_argument_structure_is_breaking() {
+        let diff = diff_from_base("function_changed");
+        assert_eq!(
+            diff.changes,
+            vec![AbiChange::FunctionChanged {
+                name: "transfer".to_string(),
+                old: "transfer(to: struct Point { x: u64, y: u64 }, amount: u64) -> bool"
+                    .to_string(),
+                new: "transfer(to: struct Point { x: u64, y: bool }, amount: u64) -> bool"
+                    .to_string(),
+            }]
+        );
+        assert!(!diff.is_compatible());
+    }
+
+    #[test]
+    fn changed_logged_type_is_breaking() {
+        let diff = diff_from_base("logged_type_changed");
+        assert_eq!(
+            diff.changes,
+            vec![AbiChange::LoggedTypeChanged {
+                log_id: 0,
+                old: "u64".to_string(),
+                new: "bool".to_string(),
+            }]
+        );
+        assert!(!diff.is_compatible());
+    }
+
+    #[test]
+    fn moved_configurable_is_breaking() {
+        let diff = diff_from_base("configurable_changed");
+        assert_eq!(
+            diff.changes,
+            vec![AbiChange::ConfigurableChanged {
+                name: "FEE".to_string(),
+                old: "FEE: u64 at offset 120".to_string(),
+                new: "FEE: u64 at offset 128".to_string(),
+            }]
+        );
+        assert!(!diff.is_compatible());
+    }
+
+    #[test]
+    fn json_report_tags_changes_by_kind() {
+        let diff = diff_from_base("function_added");
+        let report = serde_json::to_value(&diff).unwrap();
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "changes": [{"kind": "function_added", "signature": "mint(amount: u64) -> ()"}]
+            })
+        );
+    }
+}
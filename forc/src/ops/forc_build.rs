@@ -26,6 +26,7 @@ fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
             finalized_asm: cmd.build.print.finalized_asm,
             intermediate_asm: cmd.build.print.intermediate_asm,
             ir: cmd.build.print.ir,
+            profile_static: cmd.build.print.profile_static,
             reverse_order: cmd.build.print.reverse_order,
         },
         time_phases: cmd.build.print.time_phases,
@@ -37,6 +38,7 @@ fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
         build_profile: cmd.build.profile.build_profile,
         release: cmd.build.profile.release,
         error_on_warnings: cmd.build.profile.error_on_warnings,
+        verify_ir: cmd.build.profile.verify_ir,
         binary_outfile: cmd.build.output.bin_file,
         debug_outfile: cmd.build.output.debug_file,
         build_target: cmd.build.build_target,
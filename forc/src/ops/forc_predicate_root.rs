@@ -30,6 +30,7 @@ fn build_opts_from_cmd(cmd: PredicateRootCommand) -> pkg::BuildOpts {
             finalized_asm: cmd.print.finalized_asm,
             intermediate_asm: cmd.print.intermediate_asm,
             ir: cmd.print.ir,
+            profile_static: cmd.print.profile_static,
             reverse_order: cmd.print.reverse_order,
         },
         time_phases: cmd.print.time_phases,
@@ -41,6 +42,7 @@ fn build_opts_from_cmd(cmd: PredicateRootCommand) -> pkg::BuildOpts {
         build_profile: cmd.build_profile.build_profile.clone(),
         release: cmd.build_profile.release,
         error_on_warnings: cmd.build_profile.error_on_warnings,
+        verify_ir: cmd.build_profile.verify_ir,
         binary_outfile: cmd.build_output.bin_file.clone(),
         debug_outfile: cmd.build_output.debug_file,
         build_target: BuildTarget::default(),
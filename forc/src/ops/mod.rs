@@ -1,3 +1,4 @@
+pub mod forc_abi_diff;
 pub mod forc_build;
 pub mod forc_check;
 pub mod forc_clean;
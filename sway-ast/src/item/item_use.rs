@@ -46,3 +46,16 @@ pub enum UseTree {
         spans: Box<[Span]>,
     },
 }
+
+impl Spanned for UseTree {
+    fn span(&self) -> Span {
+        match self {
+            UseTree::Group { imports } => imports.span(),
+            UseTree::Name { name } => name.span(),
+            UseTree::Rename { name, alias, .. } => Span::join(name.span(), alias.span()),
+            UseTree::Glob { star_token } => star_token.span(),
+            UseTree::Path { prefix, suffix, .. } => Span::join(prefix.span(), suffix.span()),
+            UseTree::Error { spans } => Span::join_all(spans.iter().cloned()),
+        }
+    }
+}
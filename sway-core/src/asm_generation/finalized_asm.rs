@@ -1,7 +1,7 @@
 use super::instruction_set::InstructionSet;
 use super::ToMidenBytecode;
 use super::{
-    fuel::{checks, data_section::DataSection},
+    fuel::{checks, data_section::DataSection, gas_profile::GasProfile},
     ProgramABI, ProgramKind,
 };
 use crate::asm_lang::allocated_ops::{AllocatedOp, AllocatedOpcode};
@@ -25,6 +25,9 @@ pub struct FinalizedAsm {
     pub program_section: InstructionSet,
     pub program_kind: ProgramKind,
     pub entries: Vec<FinalizedEntry>,
+    /// The name and immediate instruction offset of each function, in the order they were
+    /// compiled.
+    pub functions: Vec<(String, u64)>,
     pub abi: Option<ProgramABI>,
 }
 
@@ -49,6 +52,17 @@ pub struct CompiledBytecode {
 }
 
 impl FinalizedAsm {
+    /// Estimate the gas used by each instruction, block and function of a Fuel program without
+    /// running it.  Returns `None` for other targets.
+    pub fn static_gas_profile(&self) -> Option<GasProfile> {
+        match &self.program_section {
+            InstructionSet::Fuel { ops } => {
+                Some(GasProfile::new(ops, &self.data_section, &self.functions))
+            }
+            _ => None,
+        }
+    }
+
     pub(crate) fn to_bytecode_mut(
         &mut self,
         source_map: &mut SourceMap,
@@ -143,6 +157,7 @@ fn to_bytecode_mut(
     let mut half_word_ix = 0;
     for op in ops.iter() {
         let span = op.owning_span.clone();
+        let inlined_at = &op.inlined_at;
         let op = op.to_fuel_asm(offset_to_data_section_in_bytes, data_section);
         match op {
             Either::Right(data) => {
@@ -157,7 +172,7 @@ fn to_bytecode_mut(
                 }
                 for op in ops {
                     if let Some(span) = &span {
-                        source_map.insert(source_engine, half_word_ix, span);
+                        source_map.insert(source_engine, half_word_ix, span, inlined_at);
                     }
                     let read_range_upper_bound =
                         core::cmp::min(half_word_ix * 4 + std::mem::size_of_val(&op), buf.len());
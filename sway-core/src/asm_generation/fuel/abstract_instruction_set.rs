@@ -50,6 +50,7 @@ impl AbstractInstructionSet {
                 opcode: Either::Left(VirtualOp::NOOP),
                 comment: "removed redundant JUMP".into(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             };
         }
 
@@ -103,6 +104,7 @@ impl AbstractInstructionSet {
                     opcode: Either::Left(VirtualOp::NOOP),
                     comment: "removed redundant MOVE".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 };
             }
         }
@@ -197,11 +199,13 @@ impl RealizedAbstractInstructionSet {
                      opcode,
                      comment,
                      owning_span,
+                     inlined_at,
                  }| {
                     AllocatedOp {
                         opcode,
                         comment,
                         owning_span,
+                        inlined_at,
                     }
                 },
             )
@@ -212,6 +216,7 @@ impl RealizedAbstractInstructionSet {
                 opcode: AllocatedOpcode::NOOP,
                 comment: "word-alignment of data section".into(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
         }
 
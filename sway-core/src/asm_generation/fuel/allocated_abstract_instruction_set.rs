@@ -100,6 +100,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "save base stack value".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                     new_ops.push(AllocatedAbstractOp {
                         opcode: Either::Left(AllocatedOpcode::CFEI(
@@ -107,6 +108,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "reserve space for saved registers".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
 
                     regs.into_iter().enumerate().for_each(|(idx, reg)| {
@@ -119,6 +121,7 @@ impl AllocatedAbstractInstructionSet {
                             opcode: Either::Left(store_op),
                             comment: format!("save {reg}"),
                             owning_span: None,
+                            inlined_at: Vec::new(),
                         });
                     })
                 }
@@ -141,6 +144,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "save base stack value".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
 
                     regs.into_iter().enumerate().for_each(|(idx, reg)| {
@@ -153,6 +157,7 @@ impl AllocatedAbstractInstructionSet {
                             opcode: Either::Left(load_op),
                             comment: format!("restore {reg}"),
                             owning_span: None,
+                            inlined_at: Vec::new(),
                         });
                     });
 
@@ -162,6 +167,7 @@ impl AllocatedAbstractInstructionSet {
                         )),
                         comment: "recover space from saved registers".into(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                 }
 
@@ -262,6 +268,7 @@ impl AllocatedAbstractInstructionSet {
             opcode: Either::Right(opcode),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         };
 
         // Use a large number for our new moved labels, one which shouldn't be in the existing ops.
@@ -351,12 +358,14 @@ impl AllocatedAbstractInstructionSet {
             opcode,
             comment,
             owning_span,
+            inlined_at,
         } in self.ops.clone().into_iter()
         {
             match opcode {
                 Either::Left(op) => realized_ops.push(RealizedOp {
                     opcode: op,
                     owning_span,
+                    inlined_at,
                     comment,
                 }),
                 Either::Right(org_op) => match org_op {
@@ -368,6 +377,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::JI(imm),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
@@ -379,6 +389,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::JNEI(r1, r2, imm),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
@@ -390,9 +401,55 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::JNZI(r1, imm),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
+                    ControlFlowOp::JumpTable(r1, ref labs) => {
+                        // The table of jumps directly follows the computed jump, so its offset is
+                        // found from the program counter rather than a label, which keeps it
+                        // correct when the block is moved by relocate_control_flow().
+                        let scratch = AllocatedRegister::Constant(ConstantRegister::Scratch);
+                        let dispatch = [
+                            AllocatedOpcode::SUB(
+                                scratch.clone(),
+                                AllocatedRegister::Constant(ConstantRegister::ProgramCounter),
+                                AllocatedRegister::Constant(ConstantRegister::InstructionStart),
+                            ),
+                            AllocatedOpcode::SRLI(
+                                scratch.clone(),
+                                scratch.clone(),
+                                VirtualImmediate12 { value: 2 },
+                            ),
+                            AllocatedOpcode::ADD(scratch.clone(), scratch.clone(), r1),
+                            AllocatedOpcode::ADDI(
+                                scratch.clone(),
+                                scratch.clone(),
+                                VirtualImmediate12 {
+                                    value: consts::JUMP_TABLE_DISPATCH_LEN as u16,
+                                },
+                            ),
+                            AllocatedOpcode::JMP(scratch),
+                        ];
+                        realized_ops.extend(dispatch.into_iter().map(|opcode| RealizedOp {
+                            opcode,
+                            owning_span: owning_span.clone(),
+                            inlined_at: inlined_at.clone(),
+                            comment: comment.clone(),
+                        }));
+                        for lab in labs {
+                            let imm = VirtualImmediate24::new_unchecked(
+                                label_offsets.get(lab).unwrap().offs,
+                                "Programs with more than 2^24 labels are unsupported right now",
+                            );
+                            realized_ops.push(RealizedOp {
+                                opcode: AllocatedOpcode::JI(imm),
+                                owning_span: owning_span.clone(),
+                                inlined_at: inlined_at.clone(),
+                                comment: "jump table entry".into(),
+                            });
+                        }
+                    }
                     ControlFlowOp::MoveAddress(r1, ref lab) => {
                         let imm = VirtualImmediate18::new_unchecked(
                             label_offsets.get(lab).unwrap().offs,
@@ -401,6 +458,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::MOVI(r1, imm),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
@@ -408,6 +466,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::DataSectionOffsetPlaceholder,
                             owning_span: None,
+                            inlined_at: Vec::new(),
                             comment: String::new(),
                         });
                     }
@@ -418,6 +477,7 @@ impl AllocatedAbstractInstructionSet {
                         realized_ops.push(RealizedOp {
                             opcode: AllocatedOpcode::LWDataId(r1, data_id),
                             owning_span,
+                            inlined_at,
                             comment,
                         });
                     }
@@ -538,6 +598,11 @@ impl AllocatedAbstractInstructionSet {
                     cur_offset += 1;
                 }
 
+                // The computed jump and then one jump per table entry.
+                Either::Right(JumpTable(_, ref labs)) => {
+                    cur_offset += consts::JUMP_TABLE_DISPATCH_LEN + labs.len() as u64;
+                }
+
                 Either::Right(Comment) => (),
 
                 Either::Right(DataSectionOffsetPlaceholder) => {
@@ -599,6 +664,7 @@ impl AllocatedAbstractInstructionSet {
                                 )),
                                 comment: String::new(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
 
                             // Jump to $tmp.
@@ -623,6 +689,7 @@ impl AllocatedAbstractInstructionSet {
                                 )),
                                 comment: String::new(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
 
                             // JNE r1 r2 $tmp.
@@ -650,6 +717,7 @@ impl AllocatedAbstractInstructionSet {
                                 )),
                                 comment: String::new(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
 
                             // JNE r1 $zero $tmp.
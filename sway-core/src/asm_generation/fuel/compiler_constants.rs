@@ -36,5 +36,9 @@ pub(crate) const EIGHTEEN_BITS: u64 = 0b11_1111_1111_1111_1111;
 pub(crate) const TWELVE_BITS: u64 = 0b1111_1111_1111;
 pub(crate) const SIX_BITS: u64 = 0b11_1111;
 
+/// The number of instructions which compute the destination of a jump table, before the table
+/// of jumps itself.
+pub(crate) const JUMP_TABLE_DISPATCH_LEN: u64 = 5;
+
 /// Some arbitrary values used for error codes.
 pub(crate) const MISMATCHED_SELECTOR_REVERT_CODE: u32 = 123;
@@ -3,7 +3,7 @@ use crate::asm_generation::from_ir::ir_type_size_in_bytes;
 use sway_ir::{Constant, ConstantValue, Context};
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Write},
 };
 
@@ -143,28 +143,10 @@ impl Entry {
         matches!(self.value, Datum::Word(_))
     }
 
-    pub(crate) fn equiv(&self, entry: &Entry) -> bool {
-        fn equiv_data(lhs: &Datum, rhs: &Datum) -> bool {
-            match (lhs, rhs) {
-                (Datum::Word(l), Datum::Word(r)) => l == r,
-                (Datum::ByteArray(l), Datum::ByteArray(r)) => l == r,
-
-                (Datum::Collection(l), Datum::Collection(r)) => {
-                    l.len() == r.len()
-                        && l.iter()
-                            .zip(r.iter())
-                            .all(|(l, r)| equiv_data(&l.value, &r.value))
-                }
-
-                _ => false,
-            }
-        }
-
-        // If this corresponds to a configuration-time constants, then the entry names will be
-        // available (i.e. `Some(..)`) and they must be the same before we can merge the two
-        // entries. Otherwise, `self.name` and `entry.name` will be `None` in which case we're also
-        // allowed to merge the two entries (if their values are equivalent of course).
-        equiv_data(&self.value, &entry.value) && self.name == entry.name
+    /// The key by which unnamed entries are pooled: their bytes, which are padded to whole words
+    /// and so are equally aligned, and whether they are loaded by value or by address.
+    fn pool_key(&self) -> (Vec<u8>, bool) {
+        (self.to_bytes(), self.has_copy_type())
     }
 }
 
@@ -183,6 +165,8 @@ pub struct DataSection {
     /// the data to be put in the data section of the asm
     pub value_pairs: Vec<Entry>,
     pub config_map: BTreeMap<String, u32>,
+    /// The index of each unnamed entry by its content, so that equal data is stored once.
+    pool: HashMap<(Vec<u8>, bool), u32>,
 }
 
 impl DataSection {
@@ -232,18 +216,29 @@ impl DataSection {
     /// Given any data in the form of a [Literal] (using this type mainly because it includes type
     /// information and debug spans), insert it into the data section and return its offset as a
     /// [DataId].
+    ///
+    /// Unnamed entries with the same content share an id.  Named entries are configuration-time
+    /// constants whose values are replaced when deploying, so they are only shared with an entry
+    /// of the same name and never with each other or with unnamed entries.
     pub(crate) fn insert_data_value(&mut self, new_entry: Entry) -> DataId {
-        // if there is an identical data value, use the same id
-        match self
-            .value_pairs
-            .iter()
-            .position(|entry| entry.equiv(&new_entry))
-        {
-            Some(num) => DataId(num as u32),
+        let existing = match &new_entry.name {
+            Some(name) => self
+                .value_pairs
+                .iter()
+                .position(|entry| entry.name.as_ref() == Some(name))
+                .map(|num| num as u32),
+            None => self.pool.get(&new_entry.pool_key()).copied(),
+        };
+        match existing {
+            Some(num) => DataId(num),
             None => {
-                self.value_pairs.push(new_entry);
                 // the index of the data section where the value is stored
-                DataId((self.value_pairs.len() - 1) as u32)
+                let num = self.value_pairs.len() as u32;
+                if new_entry.name.is_none() {
+                    self.pool.insert(new_entry.pool_key(), num);
+                }
+                self.value_pairs.push(new_entry);
+                DataId(num)
             }
         }
     }
@@ -290,3 +285,73 @@ impl fmt::Display for DataSection {
         write!(f, ".data:\n{data_buf}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_data_is_pooled() {
+        let entries = vec![
+            Entry::new_byte_array(b"fuel".to_vec(), None, None),
+            Entry::new_byte_array([7; 32].to_vec(), None, None),
+            Entry::new_word(1 << 40, None, None),
+            Entry::new_byte_array(b"fuel".to_vec(), None, None),
+            Entry::new_collection(
+                vec![
+                    Entry::new_word(0x0707070707070707, None, None),
+                    Entry::new_byte_array([7; 24].to_vec(), None, None),
+                ],
+                None,
+                None,
+            ),
+            Entry::new_word(1 << 40, None, None),
+            // The same bytes as the word, but loaded by address rather than by value.
+            Entry::new_byte_array((1u64 << 40).to_be_bytes().to_vec(), None, None),
+        ];
+        let unpooled_size: usize = entries.iter().map(|entry| entry.to_bytes().len()).sum();
+
+        let mut data_section = DataSection::default();
+        let ids = entries
+            .into_iter()
+            .map(|entry| data_section.insert_data_value(entry).0)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![0, 1, 2, 0, 1, 2, 3]);
+        assert_eq!(unpooled_size, 8 + 32 + 8 + 8 + 32 + 8 + 8);
+        assert_eq!(data_section.serialize_to_bytes().len(), 8 + 32 + 8 + 8);
+    }
+
+    #[test]
+    fn configurables_are_not_pooled() {
+        let mut data_section = DataSection::default();
+        let mut insert = |value, name: Option<&str>| {
+            let data_id =
+                data_section.insert_data_value(Entry::new_word(value, None, name.map(Into::into)));
+            if let Some(name) = name {
+                data_section.config_map.insert(name.into(), data_id.0);
+            }
+            data_id.0
+        };
+
+        assert_eq!(insert(1 << 40, None), 0);
+        assert_eq!(insert(1 << 40, Some("A")), 1);
+        assert_eq!(insert(1 << 40, Some("B")), 2);
+        assert_eq!(insert(1 << 20, Some("C")), 3);
+        // Each use of a configurable refers to the same entry.
+        assert_eq!(insert(1 << 40, Some("A")), 1);
+        assert_eq!(insert(1 << 20, None), 4);
+
+        // The recorded offsets each refer to a distinct word holding the configurable's value.
+        let bytes = data_section.serialize_to_bytes();
+        let offsets = data_section
+            .config_map
+            .iter()
+            .map(|(name, id)| (name.as_str(), data_section.raw_data_id_to_offset(*id)))
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![("A", 8), ("B", 16), ("C", 24)]);
+        for ((_, offset), value) in offsets.iter().zip([1u64 << 40, 1 << 40, 1 << 20]) {
+            assert_eq!(bytes[*offset..*offset + 8], value.to_be_bytes());
+        }
+    }
+}
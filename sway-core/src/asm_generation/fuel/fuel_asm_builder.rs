@@ -7,6 +7,7 @@ use crate::{
             compiler_constants,
             data_section::{DataId, DataSection, Entry},
             register_sequencer::RegisterSequencer,
+            switch::{Switch, SwitchOp, MIN_JUMP_TABLE_KEYS},
         },
         ProgramKind,
     },
@@ -23,6 +24,20 @@ use sway_types::{span::Span, Spanned};
 use either::Either;
 use std::collections::HashMap;
 
+/// A chain of blocks which each compare the same value with a constant and branch to a case
+/// block if they're equal, or to the next block of the chain otherwise.  The chain is compiled as
+/// a single [Switch].
+pub(super) struct SwitchChain {
+    /// The value compared with the keys.
+    pub(super) value: Value,
+    /// The keys and branches to the case blocks, in the order they are compared.
+    pub(super) cases: Vec<(u64, BranchToWithArgs)>,
+    /// The block branched to when no key is equal to the value.
+    pub(super) default: Block,
+    /// The blocks of the chain after its head, which aren't compiled themselves.
+    pub(super) blocks: Vec<Block>,
+}
+
 pub struct FuelAsmBuilder<'ir, 'eng> {
     pub(super) program_kind: ProgramKind,
 
@@ -142,12 +157,15 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         func_is_entry: bool,
     ) -> CompileResult<()> {
         let Some(instruction) = instr_val.get_instruction(self.context) else {
-            return err(vec![], vec![CompileError::Internal(
-                "Value not an instruction.",
-                self.md_mgr
-                    .val_to_span(self.context, *instr_val)
-                    .unwrap_or_else(Span::dummy),
-            )]);
+            return err(
+                vec![],
+                vec![CompileError::Internal(
+                    "Value not an instruction.",
+                    self.md_mgr
+                        .val_to_span(self.context, *instr_val)
+                        .unwrap_or_else(Span::dummy),
+                )],
+            );
         };
 
         // The only instruction whose compilation returns a CompileResult itself is AsmBlock, which
@@ -313,6 +331,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "copy const asm init to GP reg".into(),
                                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                                inlined_at: Vec::new(),
                             });
                             const_copy
                         }
@@ -378,6 +397,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: either::Either::Left(opcode),
                 comment: "asm block".into(),
                 owning_span: Some(op_span),
+                inlined_at: Vec::new(),
             });
         }
 
@@ -404,6 +424,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: Either::Left(VirtualOp::MOVE(instr_reg.clone(), ret_reg)),
                 comment: "return value from inline asm".into(),
                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                inlined_at: Vec::new(),
             });
             self.reg_map.insert(*instr_val, instr_reg);
         }
@@ -431,6 +452,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "convert to inversed boolean".into(),
                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                inlined_at: Vec::new(),
             });
             self.cur_bytecode.push(Op {
                 opcode: Either::Left(VirtualOp::XORI(
@@ -440,6 +462,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "invert boolean".into(),
                 owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+                inlined_at: Vec::new(),
             });
             res_reg
         } else {
@@ -466,6 +489,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode,
             comment: String::new(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, res_reg);
@@ -498,6 +522,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode,
             comment: String::new(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, res_reg);
@@ -531,6 +556,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::EQ(res_reg.clone(), lhs_reg, rhs_reg)),
                     comment,
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
             }
             Predicate::LessThan => {
@@ -538,6 +564,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::LT(res_reg.clone(), lhs_reg, rhs_reg)),
                     comment,
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
             }
             Predicate::GreaterThan => {
@@ -545,6 +572,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::GT(res_reg.clone(), lhs_reg, rhs_reg)),
                     comment,
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
             }
         }
@@ -581,6 +609,314 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         Ok(())
     }
 
+    /// Find the chains of blocks in `function` which compare a value with constants one by one,
+    /// keyed by the block which heads each chain.
+    ///
+    /// Every block after the head must be branched to only by the previous block, and hold only
+    /// the comparison and the branch, and perhaps a reload of the compared value from the same
+    /// memory, which matches reload the tag they switch on in every arm.  Only the branches to the
+    /// case blocks may pass arguments.
+    pub(super) fn find_switch_chains(&self, function: &Function) -> HashMap<Block, SwitchChain> {
+        let mut use_counts = HashMap::<Value, usize>::new();
+        for (_, inst) in function.instruction_iter(self.context) {
+            for opd in inst.get_instruction(self.context).unwrap().get_operands() {
+                *use_counts.entry(opd).or_default() += 1;
+            }
+        }
+
+        let mut chains = HashMap::new();
+        let mut chained_blocks = std::collections::HashSet::new();
+        let po = sway_ir::dominator::compute_post_order(self.context, function);
+        for head in po.po_to_block.iter().rev() {
+            if chained_blocks.contains(head) {
+                continue;
+            }
+            let Some((value, key, case, mut next_block)) =
+                self.switch_chain_link(head, &use_counts)
+            else {
+                continue;
+            };
+            // A value loaded just before it is compared may be reloaded by the rest of the chain,
+            // as no block after the head writes to memory.
+            let loaded_from = match value.get_instruction(self.context) {
+                Some(Instruction::Load(ptr))
+                    if head.instruction_iter(self.context).rev().nth(2) == Some(value) =>
+                {
+                    Some(*ptr)
+                }
+                _ => None,
+            };
+
+            let mut cases = vec![(key, case)];
+            let mut blocks = vec![];
+            loop {
+                let is_link = next_block != *head
+                    && !blocks.contains(&next_block)
+                    && next_block.num_args(self.context) == 0
+                    && next_block.num_predecessors(self.context) == 1
+                    && self.is_switch_chain_link_body(&next_block, &use_counts);
+                match self.switch_chain_link(&next_block, &use_counts) {
+                    Some((next_value, key, case, false_block))
+                        if is_link
+                            && (next_value == value || self.reloads(next_value, loaded_from)) =>
+                    {
+                        cases.push((key, case));
+                        blocks.push(next_block);
+                        next_block = false_block;
+                    }
+                    _ => break,
+                }
+            }
+            if cases.len() >= MIN_JUMP_TABLE_KEYS {
+                chained_blocks.extend(blocks.iter().copied());
+                chains.insert(
+                    *head,
+                    SwitchChain {
+                        value,
+                        cases,
+                        default: next_block,
+                        blocks,
+                    },
+                );
+            }
+        }
+        chains
+    }
+
+    /// If `block` ends by comparing a value with a constant and branching on the result, returns
+    /// the value, the constant, and the branches taken when they are and aren't equal.  The
+    /// branch taken when they aren't equal must not pass arguments.
+    fn switch_chain_link(
+        &self,
+        block: &Block,
+        use_counts: &HashMap<Value, usize>,
+    ) -> Option<(Value, u64, BranchToWithArgs, Block)> {
+        let Some(Instruction::ConditionalBranch {
+            cond_value,
+            true_block,
+            false_block,
+        }) = block.get_terminator(self.context)
+        else {
+            return None;
+        };
+        if !false_block.args.is_empty() || true_block.block == false_block.block {
+            return None;
+        }
+
+        // The comparison must be used only by the branch, just before it.
+        let num_instructions = block.num_instructions(self.context);
+        let cmp = block
+            .instruction_iter(self.context)
+            .nth(num_instructions.checked_sub(2)?)?;
+        if cmp != *cond_value || use_counts.get(&cmp) != Some(&1) {
+            return None;
+        }
+        let Some(Instruction::Cmp(Predicate::Equal, lhs, rhs)) = cmp.get_instruction(self.context)
+        else {
+            return None;
+        };
+        let key = |value: &Value| match value.get_constant(self.context) {
+            Some(Constant {
+                value: ConstantValue::Uint(key),
+                ..
+            }) => Some(*key),
+            _ => None,
+        };
+        let (value, key) = match (key(lhs), key(rhs)) {
+            (None, Some(key)) => (*lhs, key),
+            (Some(key), None) => (*rhs, key),
+            _ => return None,
+        };
+        Some((value, key, true_block.clone(), false_block.block))
+    }
+
+    /// Whether the instructions of `block` before its comparison only compute a pointer and load
+    /// from it, each used only by the next instruction.  The block isn't compiled when it's part
+    /// of a chain, so none of its values may be used anywhere else.
+    fn is_switch_chain_link_body(&self, block: &Block, use_counts: &HashMap<Value, usize>) -> bool {
+        let instrs = block.instruction_iter(self.context).collect::<Vec<_>>();
+        instrs.len() >= 2
+            && instrs[..instrs.len() - 2]
+                .iter()
+                .zip(&instrs[1..])
+                .all(|(instr, user)| {
+                    matches!(
+                        instr.get_instruction(self.context),
+                        Some(
+                            Instruction::GetLocal(_)
+                                | Instruction::GetElemPtr { .. }
+                                | Instruction::Load(_)
+                        )
+                    ) && use_counts.get(instr) == Some(&1)
+                        && user
+                            .get_instruction(self.context)
+                            .is_some_and(|user| user.get_operands().contains(instr))
+                })
+    }
+
+    /// Whether `value` loads from the same memory as `ptr`.
+    fn reloads(&self, value: Value, ptr: Option<Value>) -> bool {
+        match (value.get_instruction(self.context), ptr) {
+            (Some(Instruction::Load(reloaded_ptr)), Some(ptr)) => {
+                self.is_same_pointer(*reloaded_ptr, ptr)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `a` and `b` are the same pointer, or compute it in the same way from the same local.
+    fn is_same_pointer(&self, a: Value, b: Value) -> bool {
+        if a == b {
+            return true;
+        }
+        match (
+            a.get_instruction(self.context),
+            b.get_instruction(self.context),
+        ) {
+            (Some(Instruction::GetLocal(a_local)), Some(Instruction::GetLocal(b_local))) => {
+                a_local == b_local
+            }
+            (
+                Some(Instruction::GetElemPtr {
+                    base: a_base,
+                    elem_ptr_ty: a_ty,
+                    indices: a_indices,
+                }),
+                Some(Instruction::GetElemPtr {
+                    base: b_base,
+                    elem_ptr_ty: b_ty,
+                    indices: b_indices,
+                }),
+            ) => {
+                let index = |value: &Value| match value.get_constant(self.context) {
+                    Some(Constant {
+                        value: ConstantValue::Uint(index),
+                        ..
+                    }) => Some(*index),
+                    _ => None,
+                };
+                a_ty == b_ty
+                    && a_indices.len() == b_indices.len()
+                    && a_indices
+                        .iter()
+                        .zip(b_indices)
+                        .all(|(a, b)| a == b || index(a).is_some_and(|a| Some(a) == index(b)))
+                    && self.is_same_pointer(*a_base, *b_base)
+            }
+            _ => false,
+        }
+    }
+
+    /// Compile a chain of comparisons and branches as a [Switch].
+    pub(super) fn compile_switch(
+        &mut self,
+        chain: &SwitchChain,
+        owning_span: Option<Span>,
+    ) -> Result<(), CompileError> {
+        let value_reg = self.value_to_register(&chain.value)?;
+
+        // A case branch which passes arguments goes through a trampoline which moves them to the
+        // case block's arguments, after the switch.
+        let mut cases = Vec::new();
+        let mut trampolines = Vec::new();
+        for (key, case) in &chain.cases {
+            if cases.iter().any(|(case_key, _)| case_key == key) {
+                continue;
+            }
+            let label = if case.args.is_empty() {
+                self.block_to_label(&case.block)
+            } else {
+                let label = self.reg_seqr.get_label();
+                trampolines.push((label, case));
+                label
+            };
+            cases.push((*key, label));
+        }
+        let default = self.block_to_label(&chain.default);
+        let switch_ops = Switch::new(cases, default).lower(&mut self.reg_seqr);
+
+        for switch_op in switch_ops {
+            let (predicate, key, label) = match switch_op {
+                SwitchOp::Label(label) => {
+                    self.cur_bytecode.push(Op::unowned_jump_label(label));
+                    continue;
+                }
+                SwitchOp::Jump(label) => {
+                    self.cur_bytecode.push(Op::jump_to_label(label));
+                    continue;
+                }
+                SwitchOp::JumpTable { min, labels } => {
+                    let index_reg = if min == 0 {
+                        value_reg.clone()
+                    } else {
+                        let min_reg = self.reg_seqr.next();
+                        self.immediate_to_reg(
+                            min,
+                            min_reg.clone(),
+                            None,
+                            "jump table minimum key",
+                            owning_span.clone(),
+                        );
+                        let index_reg = self.reg_seqr.next();
+                        self.cur_bytecode.push(Op {
+                            opcode: Either::Left(VirtualOp::SUB(
+                                index_reg.clone(),
+                                value_reg.clone(),
+                                min_reg,
+                            )),
+                            comment: "jump table index".into(),
+                            owning_span: owning_span.clone(),
+                            inlined_at: Vec::new(),
+                        });
+                        index_reg
+                    };
+                    self.cur_bytecode.push(Op {
+                        owning_span: owning_span.clone(),
+                        inlined_at: Vec::new(),
+                        ..Op::jump_table(index_reg, labels)
+                    });
+                    continue;
+                }
+                SwitchOp::JumpIfEq(key, label) => (Predicate::Equal, key, label),
+                SwitchOp::JumpIfLt(key, label) => (Predicate::LessThan, key, label),
+                SwitchOp::JumpIfGt(key, label) => (Predicate::GreaterThan, key, label),
+            };
+
+            let key_reg = self.reg_seqr.next();
+            self.immediate_to_reg(
+                key,
+                key_reg.clone(),
+                None,
+                "switch key",
+                owning_span.clone(),
+            );
+            let cmp_reg = self.reg_seqr.next();
+            let opcode = match predicate {
+                Predicate::Equal => VirtualOp::EQ(cmp_reg.clone(), value_reg.clone(), key_reg),
+                Predicate::LessThan => VirtualOp::LT(cmp_reg.clone(), value_reg.clone(), key_reg),
+                Predicate::GreaterThan => {
+                    VirtualOp::GT(cmp_reg.clone(), value_reg.clone(), key_reg)
+                }
+            };
+            self.cur_bytecode.push(Op {
+                opcode: Either::Left(opcode),
+                comment: String::new(),
+                owning_span: owning_span.clone(),
+                inlined_at: Vec::new(),
+            });
+            self.cur_bytecode.push(Op::jump_if_not_zero(cmp_reg, label));
+        }
+
+        for (label, case) in trampolines {
+            self.cur_bytecode.push(Op::unowned_jump_label(label));
+            self.compile_branch_to_phi_value(case)?;
+            let case_label = self.block_to_label(&case.block);
+            self.cur_bytecode.push(Op::jump_to_label(case_label));
+        }
+
+        Ok(())
+    }
+
     fn compile_branch_to_phi_value(
         &mut self,
         to_block: &BranchToWithArgs,
@@ -630,6 +966,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "call external contract".into(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         // now, move the return value of the contract call to the return register.
@@ -763,6 +1100,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "get offset to array element".into(),
                                 owning_span: owning_span.clone(),
+                                inlined_at: Vec::new(),
                             });
                             self.cur_bytecode.push(Op {
                                 opcode: Either::Left(VirtualOp::ADD(
@@ -772,6 +1110,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "add to array base".into(),
                                 owning_span: owning_span.clone(),
+                                inlined_at: Vec::new(),
                             });
                             let member_type =
                                 elem_ty.get_array_elem_type(self.context).ok_or_else(|| {
@@ -847,6 +1186,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             )),
                             comment: "get byte offset to local from base".into(),
                             owning_span: owning_span.clone(),
+                            inlined_at: Vec::new(),
                         });
                         self.cur_bytecode.push(Op {
                             opcode: Either::Left(VirtualOp::ADD(
@@ -856,6 +1196,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             )),
                             comment: "get absolute byte offset to local".into(),
                             owning_span,
+                            inlined_at: Vec::new(),
                         });
                     } else {
                         self.immediate_to_reg(
@@ -876,6 +1217,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::LWDataId(instr_reg.clone(), data_id.clone())),
                     comment: "get local constant".into(),
                     owning_span,
+                    inlined_at: Vec::new(),
                 });
                 self.reg_map.insert(*instr_val, instr_reg);
 
@@ -908,6 +1250,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get transaction field".into(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
         self.reg_map.insert(*instr_val, instr_reg);
         Ok(())
@@ -936,6 +1279,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "load value".into(),
                 owning_span,
+                inlined_at: Vec::new(),
             });
 
             self.reg_map.insert(*instr_val, instr_reg);
@@ -970,12 +1314,14 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get length for mcp".into(),
             owning_span: owning_span.clone(),
+            inlined_at: Vec::new(),
         });
 
         self.cur_bytecode.push(Op {
             opcode: Either::Left(VirtualOp::MCP(dst_reg, src_reg, len_reg)),
             comment: "copy memory with mem_copy".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         Ok(())
@@ -1016,6 +1362,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         if !log_ty.is_ptr(self.context) {
             self.cur_bytecode.push(Op {
                 owning_span,
+                inlined_at: Vec::new(),
                 opcode: Either::Left(VirtualOp::LOG(
                     log_val_reg,
                     log_id_reg,
@@ -1041,6 +1388,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
             self.cur_bytecode.push(Op {
                 owning_span,
+                inlined_at: Vec::new(),
                 opcode: Either::Left(VirtualOp::LOGD(
                     VirtualRegister::Constant(ConstantRegister::Zero),
                     log_id_reg,
@@ -1078,6 +1426,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "move register into abi function".to_owned(),
             owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, instr_reg);
@@ -1099,6 +1448,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     ConstantRegister::Zero,
                 ))),
                 owning_span,
+                inlined_at: Vec::new(),
                 comment: "returning unit as zero".into(),
             });
         } else {
@@ -1107,6 +1457,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             if !ret_type.is_ptr(self.context) && !ret_type.is_slice(self.context) {
                 self.cur_bytecode.push(Op {
                     owning_span,
+                    inlined_at: Vec::new(),
                     opcode: Either::Left(VirtualOp::RET(ret_reg)),
                     comment: "".into(),
                 });
@@ -1125,6 +1476,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             VirtualImmediate12 { value: 1 },
                         )),
                         owning_span: owning_span.clone(),
+                        inlined_at: Vec::new(),
                         comment: "load size of returned slice".into(),
                     });
                     self.cur_bytecode.push(Op {
@@ -1134,6 +1486,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             VirtualImmediate12 { value: 0 },
                         )),
                         owning_span: owning_span.clone(),
+                        inlined_at: Vec::new(),
                         comment: "load ptr of returned slice".into(),
                     });
                 } else {
@@ -1151,6 +1504,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 }
                 self.cur_bytecode.push(Op {
                     owning_span,
+                    inlined_at: Vec::new(),
                     opcode: Either::Left(VirtualOp::RETD(ret_reg, size_reg)),
                     comment: "".into(),
                 });
@@ -1170,6 +1524,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
         self.cur_bytecode.push(Op {
             owning_span,
+            inlined_at: Vec::new(),
             opcode: Either::Left(VirtualOp::RVRT(revert_reg)),
             comment: "".into(),
         });
@@ -1193,6 +1548,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
         self.cur_bytecode.push(Op {
             owning_span,
+            inlined_at: Vec::new(),
             opcode: Either::Left(VirtualOp::SMO(
                 recipient_reg,
                 message_reg,
@@ -1241,6 +1597,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "clear a sequence of storage slots".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, was_slot_set_reg);
@@ -1295,6 +1652,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             }),
             comment: "access a sequence of storage slots".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, was_slot_set_reg);
@@ -1328,6 +1686,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode: Either::Left(VirtualOp::SRW(load_reg.clone(), was_slot_set_reg, key_reg)),
             comment: "single word state access".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, load_reg);
@@ -1365,6 +1724,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode: Either::Left(VirtualOp::SWW(key_reg, was_slot_set_reg.clone(), store_reg)),
             comment: "single word state access".into(),
             owning_span,
+            inlined_at: Vec::new(),
         });
 
         self.reg_map.insert(*instr_val, was_slot_set_reg);
@@ -1407,6 +1767,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "store value".into(),
                 owning_span,
+                inlined_at: Vec::new(),
             });
 
             Ok(())
@@ -1450,6 +1811,25 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 (VirtualRegister::Constant(ConstantRegister::One), None)
             }
 
+            // Other small integers fit in an immediate and don't need a data section entry.
+            ConstantValue::Uint(value)
+                if config_name.is_none() && *value <= compiler_constants::EIGHTEEN_BITS =>
+            {
+                let reg = self.reg_seqr.next();
+                self.cur_bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::MOVI(
+                        reg.clone(),
+                        VirtualImmediate18 {
+                            value: *value as u32,
+                        },
+                    )),
+                    comment: "literal instantiation".into(),
+                    owning_span: span,
+                    inlined_at: Vec::new(),
+                });
+                (reg, None)
+            }
+
             _otherwise => {
                 // Get the constant into the namespace.
                 let entry = Entry::from_constant(self.context, constant, config_name);
@@ -1461,6 +1841,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: either::Either::Left(VirtualOp::LWDataId(reg.clone(), data_id.clone())),
                     comment: "literal instantiation".into(),
                     owning_span: span,
+                    inlined_at: Vec::new(),
                 });
                 (reg, Some(data_id))
             }
@@ -1546,6 +1927,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: comment.into(),
                 owning_span: span,
+                inlined_at: Vec::new(),
             });
         } else if imm <= compiler_constants::EIGHTEEN_BITS {
             let comment = comment.into();
@@ -1556,12 +1938,14 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: comment.clone(),
                 owning_span: span.clone(),
+                inlined_at: Vec::new(),
             });
             if let Some(base_reg) = base {
                 self.cur_bytecode.push(Op {
                     opcode: Either::Left(VirtualOp::ADD(reg.clone(), base_reg.clone(), reg)),
                     comment,
                     owning_span: span,
+                    inlined_at: Vec::new(),
                 });
             }
         } else {
@@ -1572,6 +1956,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             self.cur_bytecode.push(Op {
                 opcode: Either::Left(VirtualOp::LWDataId(reg.clone(), data_id)),
                 owning_span: span.clone(),
+                inlined_at: Vec::new(),
                 comment: comment.clone(),
             });
             if let Some(base_reg) = base {
@@ -1579,6 +1964,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     opcode: Either::Left(VirtualOp::ADD(reg.clone(), base_reg.clone(), reg)),
                     comment,
                     owning_span: span,
+                    inlined_at: Vec::new(),
                 });
             }
         }
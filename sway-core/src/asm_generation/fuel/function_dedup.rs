@@ -0,0 +1,211 @@
+//! Merging of functions whose allocated instructions are identical.
+//!
+//! Monomorphization often produces several copies of a function which don't depend on their type
+//! parameters, e.g., `Vec<u64>::len()` and `Vec<b256>::len()`.  Once registers are allocated these
+//! copies differ only in the names of their own labels, so they are compared with those labels
+//! numbered in order of definition.  All but the first of each set of identical functions are
+//! removed and the calls to them are redirected to the first, which keeps its source spans.
+//!
+//! Merging functions may make their callers identical in turn, so this is repeated until nothing
+//! more is merged.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use either::Either;
+
+use super::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
+use crate::asm_lang::{ControlFlowOp, Label};
+
+/// A label used by an instruction, either one defined by the function itself, by its index in
+/// the order of definition, or one defined elsewhere.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum LabelRef {
+    Local(usize),
+    External(Label),
+}
+
+/// The instructions of a function, ignoring comments, spans and the names of its own labels.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct FunctionBody {
+    ops: Vec<String>,
+    labels: Vec<LabelRef>,
+}
+
+/// Merge the identical functions in `functions`.  The first `entry_count` functions are entries,
+/// which are never removed.
+pub(crate) fn dedup_functions(
+    mut functions: Vec<AllocatedAbstractInstructionSet>,
+    entry_count: usize,
+) -> Vec<AllocatedAbstractInstructionSet> {
+    loop {
+        let mut bodies: HashMap<FunctionBody, usize> = HashMap::new();
+        let mut label_map: HashMap<Label, Label> = HashMap::new();
+        let mut removed = vec![false; functions.len()];
+        for (idx, function) in functions.iter().enumerate().skip(entry_count) {
+            match bodies.entry(function_body(function)) {
+                Entry::Occupied(canonical) => {
+                    label_map.extend(
+                        defined_labels(function)
+                            .into_iter()
+                            .zip(defined_labels(&functions[*canonical.get()])),
+                    );
+                    removed[idx] = true;
+                }
+                Entry::Vacant(vacant) => {
+                    vacant.insert(idx);
+                }
+            }
+        }
+
+        if label_map.is_empty() {
+            return functions;
+        }
+
+        functions = functions
+            .into_iter()
+            .zip(removed)
+            .filter_map(|(mut function, removed)| {
+                (!removed).then(|| {
+                    for op in &mut function.ops {
+                        if let Either::Right(ctrl_op) = &op.opcode {
+                            op.opcode = Either::Right(ctrl_op.update_labels(|label| {
+                                label_map.get(&label).copied().unwrap_or(label)
+                            }));
+                        }
+                    }
+                    function
+                })
+            })
+            .collect();
+    }
+}
+
+/// The labels defined by `function`, in order.
+fn defined_labels(function: &AllocatedAbstractInstructionSet) -> Vec<Label> {
+    function
+        .ops
+        .iter()
+        .filter_map(|op| match &op.opcode {
+            Either::Right(ControlFlowOp::Label(label)) => Some(*label),
+            _ => None,
+        })
+        .collect()
+}
+
+fn function_body(function: &AllocatedAbstractInstructionSet) -> FunctionBody {
+    let local_labels: HashMap<Label, usize> = defined_labels(function)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, label)| (label, idx))
+        .collect();
+
+    let mut labels = Vec::new();
+    let ops = function
+        .ops
+        .iter()
+        .filter_map(|op| match &op.opcode {
+            Either::Left(opcode) => Some(format!("{opcode:?}")),
+            Either::Right(ControlFlowOp::Comment) => None,
+            Either::Right(ctrl_op) => {
+                // The labels are recorded separately and replaced with a placeholder.
+                let ctrl_op = ctrl_op.update_labels(|label| {
+                    labels.push(match local_labels.get(&label) {
+                        Some(idx) => LabelRef::Local(*idx),
+                        None => LabelRef::External(label),
+                    });
+                    Label(0)
+                });
+                Some(format!("{ctrl_op:?}"))
+            }
+        })
+        .collect();
+
+    FunctionBody { ops, labels }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{
+        allocated_ops::{AllocatedOpcode, AllocatedRegister},
+        AllocatedAbstractOp, ConstantRegister,
+    };
+
+    fn op(
+        opcode: Either<AllocatedOpcode, ControlFlowOp<AllocatedRegister>>,
+    ) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode,
+            comment: String::new(),
+            owning_span: None,
+            inlined_at: Vec::new(),
+        }
+    }
+
+    /// A function which calls `callees` in turn and then returns to its caller.
+    fn function(label: usize, callees: &[usize]) -> AllocatedAbstractInstructionSet {
+        let mut ops = vec![op(Either::Right(ControlFlowOp::Label(Label(label))))];
+        ops.extend(
+            callees
+                .iter()
+                .map(|callee| op(Either::Right(ControlFlowOp::Call(Label(*callee))))),
+        );
+        ops.push(op(Either::Right(ControlFlowOp::Label(Label(label + 1)))));
+        ops.push(op(Either::Left(AllocatedOpcode::JMP(
+            AllocatedRegister::Constant(ConstantRegister::CallReturnAddress),
+        ))));
+        AllocatedAbstractInstructionSet { ops }
+    }
+
+    fn calls(function: &AllocatedAbstractInstructionSet) -> Vec<Label> {
+        function
+            .ops
+            .iter()
+            .filter_map(|op| match &op.opcode {
+                Either::Right(ControlFlowOp::Call(label)) => Some(*label),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_functions_are_merged() {
+        // The entry calls two copies of the same leaf, and two callers which become identical once
+        // the leaves are merged.
+        let functions = vec![
+            function(0, &[10, 20, 30, 40]),
+            function(10, &[]),
+            function(20, &[]),
+            function(30, &[10]),
+            function(40, &[20]),
+        ];
+        let op_count: usize = functions.iter().map(|f| f.ops.len()).sum();
+
+        let functions = dedup_functions(functions, 1);
+        assert_eq!(functions.len(), 3);
+        assert_eq!(
+            calls(&functions[0]),
+            vec![Label(10), Label(10), Label(30), Label(30)]
+        );
+        assert_eq!(calls(&functions[2]), vec![Label(10)]);
+        assert!(functions.iter().map(|f| f.ops.len()).sum::<usize>() < op_count);
+    }
+
+    #[test]
+    fn functions_calling_different_functions_are_kept() {
+        let functions = vec![
+            function(0, &[10, 20, 30, 40]),
+            function(10, &[]),
+            function(20, &[0]),
+            function(30, &[10]),
+            function(40, &[20]),
+        ];
+        assert_eq!(dedup_functions(functions, 1).len(), 5);
+    }
+
+    #[test]
+    fn entries_are_kept() {
+        let functions = vec![function(0, &[]), function(10, &[])];
+        assert_eq!(dedup_functions(functions, 2).len(), 2);
+    }
+}
@@ -93,6 +93,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             opcode: Either::Right(OrganizationalOp::Call(fn_label)),
             comment: format!("call {}", function.get_name(self.context)),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.cur_bytecode.push(Op::unowned_jump_label(ret_label));
 
@@ -105,6 +106,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "copy the return value".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.reg_map.insert(*instr_val, ret_reg);
 
@@ -188,6 +190,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: Either::Right(OrganizationalOp::PushAll(start_label)),
                 comment: "save all regs".to_owned(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
         }
 
@@ -227,18 +230,55 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
         // Compile instructions. Traverse the IR blocks in reverse post order. This guarantees that
         // each block is processed after all its CFG predecessors have been processed.
+        //
+        // Chains of comparisons of a value with constants are compiled as a single switch from
+        // the block heading the chain, and the rest of the blocks of the chain are skipped.
+        let switch_chains = self.find_switch_chains(&function);
         let po = sway_ir::dominator::compute_post_order(self.context, &function);
         for block in po.po_to_block.iter().rev() {
+            if switch_chains
+                .values()
+                .any(|chain| chain.blocks.contains(block))
+            {
+                continue;
+            }
+
             let label = self.block_to_label(block);
             self.cur_bytecode.push(Op::unowned_jump_label(label));
 
-            for instr_val in block.instruction_iter(self.context) {
+            let switch_chain = switch_chains.get(block);
+            let mut instrs = block.instruction_iter(self.context).collect::<Vec<_>>();
+            if switch_chain.is_some() {
+                // The comparison and the branch which end the block are replaced by the switch.
+                instrs.truncate(instrs.len() - 2);
+            }
+            for instr_val in instrs {
+                let first_op = self.cur_bytecode.len();
                 check!(
                     self.compile_instruction(&instr_val, func_is_entry),
                     return err(warnings, errors),
                     warnings,
                     errors
                 );
+
+                // Every op compiled from an inlined instruction records the calls it was inlined
+                // from, for the source map.
+                let inlined_at = self.md_mgr.val_to_inlined_at(self.context, instr_val);
+                if !inlined_at.is_empty() {
+                    for op in &mut self.cur_bytecode[first_op..] {
+                        op.inlined_at = inlined_at.clone();
+                    }
+                }
+            }
+            if let Some(chain) = switch_chain {
+                let terminator = block.instruction_iter(self.context).next_back().unwrap();
+                let span = self.md_mgr.val_to_span(self.context, terminator);
+                check!(
+                    CompileResult::from(self.compile_switch(chain, span)),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
             }
         }
 
@@ -266,6 +306,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 opcode: Either::Right(OrganizationalOp::PopAll(start_label)),
                 comment: "restore all regs".to_owned(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
 
             // Jump to the return address.
@@ -350,6 +391,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: "load main fn parameter".into(),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                         }
                     }
@@ -394,6 +436,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: format!("get offset for arg {name}"),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                             self.cur_bytecode.push(Op {
                                 opcode: Either::Left(VirtualOp::LW(
@@ -403,6 +446,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: format!("get arg {name}"),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                         } else {
                             self.cur_bytecode.push(Op {
@@ -415,6 +459,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                                 )),
                                 comment: format!("get arg {name}"),
                                 owning_span: None,
+                                inlined_at: Vec::new(),
                             });
                         }
                     } else {
@@ -447,6 +492,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "base register for method parameter".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 
@@ -462,6 +508,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "base register for main fn parameter".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 
@@ -481,6 +528,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get predicate index".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Find the type of the "Input" using `GTF`. The returned value is one of three possible
@@ -500,6 +548,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get input type".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Label to jump to if the input type is *not* zero, i.e. not "coin". Then do the jump.
@@ -521,6 +570,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "get input coin predicate data pointer".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Now that we have the actual pointer, we can jump to the success label to continue
@@ -542,6 +592,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "register containing 2".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.cur_bytecode.push(Op {
             opcode: either::Either::Left(VirtualOp::EQ(
@@ -551,6 +602,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "input type is message(2)".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Invert `input_type_is_message` to use in `jnzi`
@@ -563,6 +615,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "input type is not message(2)".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Label to jump to if the input type is *not* 2, i.e. not "message" (and not "coin" since
@@ -585,6 +638,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             )),
             comment: "input message predicate data pointer".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         self.cur_bytecode.push(Op::jump_to_label(success_label));
 
@@ -599,6 +653,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 ConstantRegister::Zero,
             ))),
             owning_span: None,
+            inlined_at: Vec::new(),
             comment: "return false".into(),
         });
 
@@ -680,6 +735,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             })),
             comment: format!("allocate {locals_size} bytes for locals"),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Initialise that stack variables which require it.
@@ -692,6 +748,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 )),
                 comment: "load initializer from data section".to_owned(),
                 owning_span: None,
+                inlined_at: Vec::new(),
             });
 
             // Get the stack offset in bytes rather than words.
@@ -710,6 +767,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "calc local variable address".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             } else {
                 assert!(var_stack_off_bytes <= compiler_constants::EIGHTEEN_BITS);
@@ -723,6 +781,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "stack offset of local variable into register".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
                 self.cur_bytecode.push(Op {
                     opcode: Either::Left(VirtualOp::ADD(
@@ -732,6 +791,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "calc local variable address".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             }
 
@@ -745,6 +805,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "store initializer to local variable".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             } else {
                 // Initialise by reference.
@@ -760,6 +821,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                     )),
                     comment: "copy initializer from data section to local variable".to_owned(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 });
             }
         }
@@ -781,6 +843,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             })),
             comment: format!("free {locals_size} bytes for locals"),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 
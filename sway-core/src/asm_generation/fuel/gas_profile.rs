@@ -0,0 +1,494 @@
+//! A static estimate of the gas used by a program, made without running it.
+//!
+//! Each finalized instruction is charged its cost from the VM's default gas cost table.
+//! The cost of some instructions depends on the amount of memory or storage they touch, which is
+//! usually only known when running the program.  Their cost is a range starting at the base cost
+//! with no upper bound, flagged as dynamic.
+//!
+//! The instructions are grouped into basic blocks and the blocks into the functions they were
+//! compiled from.  The cost of a block or a function is the sum of the costs of its instructions,
+//! i.e., the cost of running each of its instructions once.  So the cost of a loop is the cost of
+//! a single iteration, and the blocks within loops are flagged as such.
+
+use std::{collections::BTreeSet, fmt, ops::AddAssign};
+
+use fuel_vm::gas::{DependentCost, GasCostsValues};
+use serde::Serialize;
+
+use crate::asm_lang::allocated_ops::{AllocatedOp, AllocatedOpcode};
+
+use super::data_section::DataSection;
+
+/// The name given to the code before the first function, i.e., the program preamble and the
+/// contract method selector switch.
+pub const PROLOGUE_NAME: &str = "(prologue)";
+
+/// The gas charged by the VM for an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GasCost {
+    /// A fixed cost.
+    Fixed(u64),
+    /// A base cost plus one unit of gas for every `dep_per_unit` bytes of memory or storage the
+    /// instruction touches.
+    Dependent(DependentCost),
+}
+
+/// The cost of each instruction, as given by the default gas cost table of the VM.
+fn gas_cost(costs: &GasCostsValues, opcode: &AllocatedOpcode) -> GasCost {
+    use AllocatedOpcode::*;
+    use GasCost::*;
+    match opcode {
+        /* Arithmetic/Logic (ALU) Instructions */
+        ADD(..) => Fixed(costs.add),
+        ADDI(..) => Fixed(costs.addi),
+        AND(..) => Fixed(costs.and),
+        ANDI(..) => Fixed(costs.andi),
+        DIV(..) => Fixed(costs.div),
+        DIVI(..) => Fixed(costs.divi),
+        EQ(..) => Fixed(costs.eq),
+        EXP(..) => Fixed(costs.exp),
+        EXPI(..) => Fixed(costs.expi),
+        GT(..) => Fixed(costs.gt),
+        LT(..) => Fixed(costs.lt),
+        MLOG(..) => Fixed(costs.mlog),
+        MOD(..) => Fixed(costs.mod_op),
+        MODI(..) => Fixed(costs.modi),
+        MOVE(..) => Fixed(costs.move_op),
+        MOVI(..) => Fixed(costs.movi),
+        MROO(..) => Fixed(costs.mroo),
+        MUL(..) => Fixed(costs.mul),
+        MULI(..) => Fixed(costs.muli),
+        NOOP => Fixed(costs.noop),
+        NOT(..) => Fixed(costs.not),
+        OR(..) => Fixed(costs.or),
+        ORI(..) => Fixed(costs.ori),
+        SLL(..) => Fixed(costs.sll),
+        SLLI(..) => Fixed(costs.slli),
+        SRL(..) => Fixed(costs.srl),
+        SRLI(..) => Fixed(costs.srli),
+        SUB(..) => Fixed(costs.sub),
+        SUBI(..) => Fixed(costs.subi),
+        XOR(..) => Fixed(costs.xor),
+        XORI(..) => Fixed(costs.xori),
+
+        /* Control Flow Instructions */
+        JMP(..) => Fixed(costs.jmp),
+        JI(..) => Fixed(costs.ji),
+        JNE(..) => Fixed(costs.jne),
+        JNEI(..) => Fixed(costs.jnei),
+        JNZI(..) => Fixed(costs.jnzi),
+        RET(..) => Fixed(costs.ret),
+
+        /* Memory Instructions */
+        ALOC(..) => Fixed(costs.aloc),
+        CFEI(..) => Fixed(costs.cfei),
+        CFSI(..) => Fixed(costs.cfsi),
+        LB(..) => Fixed(costs.lb),
+        LW(..) => Fixed(costs.lw),
+        SB(..) => Fixed(costs.sb),
+        SW(..) => Fixed(costs.sw),
+        MCL(..) => Dependent(costs.mcl),
+        // The number of bytes cleared is known.
+        MCLI(_, len) => Fixed(dependent_cost(costs.mcli, len.value as u64)),
+        MCP(..) => Dependent(costs.mcp),
+        MCPI(..) => Fixed(costs.mcpi),
+        MEQ(..) => Dependent(costs.meq),
+
+        /* Contract Instructions */
+        BAL(..) => Fixed(costs.bal),
+        BHEI(..) => Fixed(costs.bhei),
+        BHSH(..) => Fixed(costs.bhsh),
+        BURN(..) => Fixed(costs.burn),
+        CALL(..) => Dependent(costs.call),
+        CB(..) => Fixed(costs.cb),
+        CCP(..) => Dependent(costs.ccp),
+        CROO(..) => Fixed(costs.croo),
+        CSIZ(..) => Dependent(costs.csiz),
+        LDC(..) => Dependent(costs.ldc),
+        LOG(..) => Fixed(costs.log),
+        LOGD(..) => Dependent(costs.logd),
+        MINT(..) => Fixed(costs.mint),
+        RETD(..) => Dependent(costs.retd),
+        RVRT(..) => Fixed(costs.rvrt),
+        SMO(..) => Dependent(costs.smo),
+        SCWQ(..) => Fixed(costs.scwq),
+        SRW(..) => Fixed(costs.srw),
+        SRWQ(..) => Dependent(costs.srwq),
+        SWW(..) => Fixed(costs.sww),
+        SWWQ(..) => Fixed(costs.swwq),
+        TIME(..) => Fixed(costs.time),
+        TR(..) => Fixed(costs.tr),
+        TRO(..) => Fixed(costs.tro),
+
+        /* Cryptographic Instructions */
+        ECR(..) => Fixed(costs.ecr),
+        K256(..) => Fixed(costs.k256),
+        S256(..) => Fixed(costs.s256),
+
+        /* Other Instructions */
+        FLAG(..) => Fixed(costs.flag),
+        GM(..) => Fixed(costs.gm),
+        GTF(..) => Fixed(costs.gtf),
+
+        /* Non-VM Instructions */
+        BLOB(count) => Fixed(count.value as u64),
+        DataSectionRegisterLoadPlaceholder | LWDataId(..) => Fixed(costs.lw),
+        // The data section offset is data which is jumped over, never run.
+        DataSectionOffsetPlaceholder | Undefined => Fixed(0),
+    }
+}
+
+/// The cost of an instruction with a dependent cost touching `units` bytes, as charged by the VM.
+fn dependent_cost(cost: DependentCost, units: u64) -> u64 {
+    if cost.dep_per_unit == 0 {
+        cost.base
+    } else {
+        cost.base.saturating_add(units / cost.dep_per_unit)
+    }
+}
+
+/// An amount of gas, which is at least `min` and, if `dynamic`, has a further component which
+/// depends on the memory or storage touched at run time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GasRange {
+    pub min: u64,
+    pub dynamic: bool,
+}
+
+impl From<GasCost> for GasRange {
+    fn from(cost: GasCost) -> Self {
+        match cost {
+            GasCost::Fixed(min) => GasRange {
+                min,
+                dynamic: false,
+            },
+            GasCost::Dependent(cost) => GasRange {
+                min: cost.base,
+                dynamic: true,
+            },
+        }
+    }
+}
+
+impl AddAssign for GasRange {
+    fn add_assign(&mut self, other: Self) {
+        self.min += other.min;
+        self.dynamic |= other.dynamic;
+    }
+}
+
+impl fmt::Display for GasRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.dynamic {
+            write!(f, "{}+ (dynamic)", self.min)
+        } else {
+            write!(f, "{}", self.min)
+        }
+    }
+}
+
+/// A finalized instruction annotated with its cost.
+#[derive(Clone, Debug, Serialize)]
+pub struct InstructionGas {
+    /// The instruction offset from the start of the program.
+    pub offset: u64,
+    pub asm: String,
+    pub gas: GasRange,
+}
+
+/// A basic block, i.e., a run of instructions which is only entered at the start and left at the
+/// end.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockGas {
+    /// The instruction offset of the start of the block.
+    pub offset: u64,
+    /// Whether the block is part of a loop, whose cost is reported per iteration.
+    pub in_loop: bool,
+    pub gas: GasRange,
+    pub instructions: Vec<InstructionGas>,
+}
+
+/// The blocks of a function, attributed to it by the offset of the function's start label.
+#[derive(Clone, Debug, Serialize)]
+pub struct FunctionGas {
+    pub name: String,
+    /// The instruction offset of the start of the function.
+    pub offset: u64,
+    pub gas: GasRange,
+    pub blocks: Vec<BlockGas>,
+}
+
+/// The static gas estimate of a whole program.
+#[derive(Clone, Debug, Serialize)]
+pub struct GasProfile {
+    pub functions: Vec<FunctionGas>,
+}
+
+impl GasProfile {
+    /// Estimate the gas used by `ops`, attributing it to the `functions` starting at the given
+    /// instruction offsets.
+    pub(crate) fn new(
+        ops: &[AllocatedOp],
+        data_section: &DataSection,
+        functions: &[(String, u64)],
+    ) -> Self {
+        // Annotate each instruction with its offset and cost.
+        let costs = GasCostsValues::default();
+        let mut offset = 0;
+        let mut instructions = Vec::with_capacity(ops.len());
+        for op in ops {
+            let (width, gas) = match &op.opcode {
+                // A load of data larger than a word also adds the data section address.
+                AllocatedOpcode::LWDataId(_, data_id)
+                    if !data_section.has_copy_type(data_id).unwrap_or(true) =>
+                {
+                    (2, GasRange::from(GasCost::Fixed(costs.lw + costs.add)))
+                }
+                AllocatedOpcode::BLOB(count) => {
+                    (count.value as u64, gas_cost(&costs, &op.opcode).into())
+                }
+                AllocatedOpcode::DataSectionOffsetPlaceholder => {
+                    (2, gas_cost(&costs, &op.opcode).into())
+                }
+                opcode => (1, gas_cost(&costs, opcode).into()),
+            };
+            instructions.push(InstructionGas {
+                offset,
+                asm: op.opcode.to_string(),
+                gas,
+            });
+            offset += width;
+        }
+
+        // Blocks start at the start of each function, at jump destinations and after jumps.
+        let mut leaders = functions
+            .iter()
+            .map(|(_, offset)| *offset)
+            .chain(std::iter::once(0))
+            .collect::<BTreeSet<_>>();
+        let mut back_edges = Vec::new();
+        for (op, instruction) in ops.iter().zip(&instructions) {
+            if let Some(target) = jump_target(&op.opcode) {
+                leaders.insert(target);
+                if target <= instruction.offset {
+                    back_edges.push(target..=instruction.offset);
+                }
+            }
+            if ends_block(&op.opcode) {
+                leaders.insert(instruction.offset + 1);
+            }
+        }
+
+        let mut sorted_functions = functions.to_vec();
+        sorted_functions.sort_by_key(|(_, offset)| *offset);
+        let mut profile = GasProfile { functions: vec![] };
+        if sorted_functions
+            .first()
+            .map_or(true, |(_, offset)| *offset > 0)
+        {
+            profile.functions.push(FunctionGas {
+                name: PROLOGUE_NAME.to_owned(),
+                offset: 0,
+                gas: GasRange::default(),
+                blocks: vec![],
+            });
+        }
+        let mut sorted_functions = sorted_functions.into_iter().peekable();
+
+        for instruction in instructions {
+            // Move on to the function which starts here, if any.
+            while let Some((name, offset)) =
+                sorted_functions.next_if(|(_, offset)| *offset <= instruction.offset)
+            {
+                profile.functions.push(FunctionGas {
+                    name,
+                    offset,
+                    gas: GasRange::default(),
+                    blocks: vec![],
+                });
+            }
+            let function = profile.functions.last_mut().unwrap();
+            if function.blocks.is_empty() || leaders.contains(&instruction.offset) {
+                function.blocks.push(BlockGas {
+                    offset: instruction.offset,
+                    in_loop: back_edges
+                        .iter()
+                        .any(|back_edge| back_edge.contains(&instruction.offset)),
+                    gas: GasRange::default(),
+                    instructions: vec![],
+                });
+            }
+            let block = function.blocks.last_mut().unwrap();
+            block.gas += instruction.gas;
+            function.gas += instruction.gas;
+            block.instructions.push(instruction);
+        }
+
+        profile
+    }
+}
+
+/// The instruction offset an immediate jump goes to.
+fn jump_target(opcode: &AllocatedOpcode) -> Option<u64> {
+    match opcode {
+        AllocatedOpcode::JI(imm) => Some(imm.value as u64),
+        AllocatedOpcode::JNEI(_, _, imm) => Some(imm.value as u64),
+        AllocatedOpcode::JNZI(_, imm) => Some(imm.value as u64),
+        _ => None,
+    }
+}
+
+/// Whether the instruction may leave the block other than by continuing with the next one.
+fn ends_block(opcode: &AllocatedOpcode) -> bool {
+    matches!(
+        opcode,
+        AllocatedOpcode::JMP(..)
+            | AllocatedOpcode::JI(..)
+            | AllocatedOpcode::JNE(..)
+            | AllocatedOpcode::JNEI(..)
+            | AllocatedOpcode::JNZI(..)
+            | AllocatedOpcode::RET(..)
+            | AllocatedOpcode::RETD(..)
+            | AllocatedOpcode::RVRT(..)
+    )
+}
+
+impl fmt::Display for GasProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Static gas profile:")?;
+        for function in &self.functions {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "fn {} @{}: {}",
+                function.name, function.offset, function.gas
+            )?;
+            for block in &function.blocks {
+                let in_loop = if block.in_loop {
+                    " per loop iteration"
+                } else {
+                    ""
+                };
+                writeln!(f, "  block @{}{in_loop}: {}", block.offset, block.gas)?;
+                for instruction in &block.instructions {
+                    writeln!(
+                        f,
+                        "    {:>6}  {:<32} {}",
+                        instruction.offset, instruction.asm, instruction.gas
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{
+        allocated_ops::AllocatedRegister, ConstantRegister, VirtualImmediate12, VirtualImmediate18,
+        VirtualImmediate24,
+    };
+
+    fn op(opcode: AllocatedOpcode) -> AllocatedOp {
+        AllocatedOp {
+            opcode,
+            comment: String::new(),
+            owning_span: None,
+            inlined_at: Vec::new(),
+        }
+    }
+
+    fn reg(idx: u8) -> AllocatedRegister {
+        AllocatedRegister::Allocated(idx)
+    }
+
+    #[test]
+    fn trivial_function_cost_is_pinned() {
+        // fn main() -> u64 { 1 + 2 }
+        let ops = vec![
+            op(AllocatedOpcode::MOVI(
+                reg(0),
+                VirtualImmediate18 { value: 1 },
+            )),
+            op(AllocatedOpcode::ADDI(
+                reg(0),
+                reg(0),
+                VirtualImmediate12 { value: 2 },
+            )),
+            op(AllocatedOpcode::RET(reg(0))),
+        ];
+        let profile = GasProfile::new(&ops, &DataSection::default(), &[("main".into(), 0)]);
+
+        assert_eq!(profile.functions.len(), 1);
+        let main = &profile.functions[0];
+        assert_eq!(main.name, "main");
+        assert_eq!(
+            main.gas,
+            GasRange {
+                min: 63,
+                dynamic: false
+            }
+        );
+        assert_eq!(main.blocks.len(), 1);
+        let costs = main.blocks[0]
+            .instructions
+            .iter()
+            .map(|instruction| instruction.gas.min)
+            .collect::<Vec<_>>();
+        assert_eq!(costs, vec![1, 1, 61]);
+    }
+
+    #[test]
+    fn loops_and_dynamic_costs_are_flagged() {
+        let zero = AllocatedRegister::Constant(ConstantRegister::Zero);
+        let ops = vec![
+            // The prologue jumps to `main`.
+            op(AllocatedOpcode::JI(VirtualImmediate24 { value: 2 })),
+            op(AllocatedOpcode::NOOP),
+            // main: copy memory until the counter is zero.
+            op(AllocatedOpcode::MOVI(
+                reg(0),
+                VirtualImmediate18 { value: 3 },
+            )),
+            op(AllocatedOpcode::MCP(reg(1), reg(2), reg(3))),
+            op(AllocatedOpcode::SUBI(
+                reg(0),
+                reg(0),
+                VirtualImmediate12 { value: 1 },
+            )),
+            op(AllocatedOpcode::JNZI(
+                reg(0),
+                VirtualImmediate18 { value: 3 },
+            )),
+            op(AllocatedOpcode::RET(zero)),
+        ];
+        let profile = GasProfile::new(&ops, &DataSection::default(), &[("main".into(), 2)]);
+
+        let names = profile
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![PROLOGUE_NAME, "main"]);
+
+        let main = &profile.functions[1];
+        let blocks = main
+            .blocks
+            .iter()
+            .map(|block| (block.offset, block.in_loop, block.gas))
+            .collect::<Vec<_>>();
+        let gas = |min, dynamic| GasRange { min, dynamic };
+        assert_eq!(
+            blocks,
+            vec![
+                (2, false, gas(1, false)),
+                (3, true, gas(3, true)),
+                (6, false, gas(61, false)),
+            ]
+        );
+        assert_eq!(main.gas, gas(65, true));
+        assert_eq!(main.gas.to_string(), "65+ (dynamic)");
+    }
+}
@@ -1,11 +1,15 @@
 pub(crate) mod compiler_constants;
 pub(crate) mod data_section;
+pub mod gas_profile;
 pub(crate) mod register_allocator;
 
 pub(super) mod abstract_instruction_set;
 pub(super) mod allocated_abstract_instruction_set;
 pub(super) mod checks;
 pub(super) mod fuel_asm_builder;
+pub(super) mod function_dedup;
+pub(super) mod peephole;
 pub(super) mod register_sequencer;
+pub(super) mod switch;
 
 mod functions;
@@ -0,0 +1,322 @@
+//! Peephole optimization of allocated instructions.
+//!
+//! Once registers are allocated, a function still contains some instructions which do nothing:
+//! moves from a register to itself, stack frame extensions which are shrunk straight away, jumps
+//! to the label which follows them, comparisons which repeat the one before and PUSHA/POPA
+//! sections which save registers around nothing.  Each pattern below matches a short window of
+//! instructions and names those which can be removed without changing what the function does.
+//! Removing instructions may make a new match, so the patterns are applied until none match.
+//!
+//! The pass runs both before and after the PUSHA/POPA sections are expanded, as an empty section
+//! is only recognisable before its PUSHA and POPA are expanded into loads and stores.
+//!
+//! Jumps still refer to labels at this point, so their offsets are computed with the removed
+//! instructions already gone.  The source map is built from the spans of the instructions which
+//! are left, so it needs no fixing up either.
+
+use std::ops::Range;
+
+use either::Either;
+
+use super::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
+use crate::asm_lang::{allocated_ops::AllocatedOpcode, AllocatedAbstractOp, ControlFlowOp};
+
+/// A pattern is given the instructions from some index onwards and returns the range of those
+/// instructions, relative to that index, which can be removed.
+type Pattern = fn(&[AllocatedAbstractOp]) -> Option<Range<usize>>;
+
+const PATTERNS: &[Pattern] = &[
+    self_move,
+    empty_stack_frame,
+    jump_to_next,
+    repeated_comparison,
+    empty_push_pop,
+];
+
+/// Remove the instructions which do nothing from `function`.
+pub(crate) fn peephole_optimize(
+    mut function: AllocatedAbstractInstructionSet,
+) -> AllocatedAbstractInstructionSet {
+    loop {
+        let mut modified = false;
+        let mut idx = 0;
+        while idx < function.ops.len() {
+            let window = &function.ops[idx..];
+            match PATTERNS.iter().find_map(|pattern| pattern(window)) {
+                Some(range) => {
+                    function.ops.drain(idx + range.start..idx + range.end);
+                    modified = true;
+                }
+                None => idx += 1,
+            }
+        }
+        if !modified {
+            break;
+        }
+    }
+    function
+}
+
+/// `MOVE $r $r`.
+fn self_move(ops: &[AllocatedAbstractOp]) -> Option<Range<usize>> {
+    match &ops[0].opcode {
+        Either::Left(AllocatedOpcode::MOVE(dst, src)) if dst == src => Some(0..1),
+        _ => None,
+    }
+}
+
+/// `CFEI 0`, `CFSI 0`, or `CFEI n` immediately followed by `CFSI n`.
+fn empty_stack_frame(ops: &[AllocatedAbstractOp]) -> Option<Range<usize>> {
+    match (&ops[0].opcode, ops.get(1).map(|op| &op.opcode)) {
+        (Either::Left(AllocatedOpcode::CFEI(size) | AllocatedOpcode::CFSI(size)), _)
+            if size.value == 0 =>
+        {
+            Some(0..1)
+        }
+        (
+            Either::Left(AllocatedOpcode::CFEI(extend)),
+            Some(Either::Left(AllocatedOpcode::CFSI(shrink))),
+        ) if extend.value == shrink.value => Some(0..2),
+        _ => None,
+    }
+}
+
+/// A jump, conditional or not, to a label which follows it with only labels and comments in
+/// between.
+fn jump_to_next(ops: &[AllocatedAbstractOp]) -> Option<Range<usize>> {
+    let target = match &ops[0].opcode {
+        Either::Right(
+            ControlFlowOp::Jump(label)
+            | ControlFlowOp::JumpIfNotEq(_, _, label)
+            | ControlFlowOp::JumpIfNotZero(_, label),
+        ) => label,
+        _ => return None,
+    };
+    ops[1..]
+        .iter()
+        .map_while(|op| match &op.opcode {
+            Either::Right(ControlFlowOp::Label(label)) => Some(Some(label)),
+            Either::Right(ControlFlowOp::Comment) => Some(None),
+            _ => None,
+        })
+        .flatten()
+        .any(|label| label == target)
+        .then_some(0..1)
+}
+
+/// A comparison which is the same as the one before it, and whose result register is not one of
+/// its operands.
+fn repeated_comparison(ops: &[AllocatedAbstractOp]) -> Option<Range<usize>> {
+    let (Either::Left(first), Some(Either::Left(second))) =
+        (&ops[0].opcode, ops.get(1).map(|op| &op.opcode))
+    else {
+        return None;
+    };
+    let is_repeated = match (first, second) {
+        (AllocatedOpcode::EQ(d1, l1, r1), AllocatedOpcode::EQ(d2, l2, r2))
+        | (AllocatedOpcode::LT(d1, l1, r1), AllocatedOpcode::LT(d2, l2, r2))
+        | (AllocatedOpcode::GT(d1, l1, r1), AllocatedOpcode::GT(d2, l2, r2)) => {
+            d1 == d2 && l1 == l2 && r1 == r2 && d1 != l1 && d1 != r1
+        }
+        _ => false,
+    };
+    is_repeated.then_some(1..2)
+}
+
+/// `PUSHA l` followed by `POPA l` with only comments in between.
+fn empty_push_pop(ops: &[AllocatedAbstractOp]) -> Option<Range<usize>> {
+    let Either::Right(ControlFlowOp::PushAll(push_label)) = &ops[0].opcode else {
+        return None;
+    };
+    let pop_idx = ops[1..]
+        .iter()
+        .position(|op| !matches!(op.opcode, Either::Right(ControlFlowOp::Comment)))?
+        + 1;
+    match &ops[pop_idx].opcode {
+        Either::Right(ControlFlowOp::PopAll(pop_label)) if pop_label == push_label => {
+            Some(0..pop_idx + 1)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{
+        allocated_ops::AllocatedRegister, ConstantRegister, Label, VirtualImmediate24,
+    };
+
+    fn op(
+        opcode: Either<AllocatedOpcode, ControlFlowOp<AllocatedRegister>>,
+    ) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode,
+            comment: String::new(),
+            owning_span: None,
+            inlined_at: Vec::new(),
+        }
+    }
+
+    fn reg(idx: u8) -> AllocatedRegister {
+        AllocatedRegister::Allocated(idx)
+    }
+
+    fn ret() -> AllocatedAbstractOp {
+        op(Either::Left(AllocatedOpcode::RET(
+            AllocatedRegister::Constant(ConstantRegister::Zero),
+        )))
+    }
+
+    fn optimize(ops: Vec<AllocatedAbstractOp>) -> Vec<String> {
+        peephole_optimize(AllocatedAbstractInstructionSet { ops })
+            .ops
+            .iter()
+            .map(|op| op.to_string())
+            .collect()
+    }
+
+    fn asm(ops: Vec<AllocatedAbstractOp>) -> Vec<String> {
+        ops.iter().map(|op| op.to_string()).collect()
+    }
+
+    #[test]
+    fn self_moves_are_removed() {
+        let ops = vec![
+            op(Either::Left(AllocatedOpcode::MOVE(reg(0), reg(0)))),
+            op(Either::Left(AllocatedOpcode::MOVE(reg(1), reg(0)))),
+            ret(),
+        ];
+        assert_eq!(optimize(ops.clone()), asm(ops[1..].to_vec()));
+    }
+
+    #[test]
+    fn empty_stack_frames_are_removed() {
+        let cfei = |value| {
+            op(Either::Left(AllocatedOpcode::CFEI(VirtualImmediate24 {
+                value,
+            })))
+        };
+        let cfsi = |value| {
+            op(Either::Left(AllocatedOpcode::CFSI(VirtualImmediate24 {
+                value,
+            })))
+        };
+        let ops = vec![cfei(0), cfei(16), cfsi(16), cfsi(0), ret()];
+        assert_eq!(optimize(ops), asm(vec![ret()]));
+
+        // A frame which is used is kept, as is a frame shrunk by a different size.
+        let ops = vec![
+            cfei(8),
+            op(Either::Left(AllocatedOpcode::MOVE(reg(0), reg(1)))),
+            cfsi(8),
+            cfei(16),
+            cfsi(8),
+            ret(),
+        ];
+        assert_eq!(optimize(ops.clone()), asm(ops));
+    }
+
+    #[test]
+    fn jumps_to_next_label_are_removed() {
+        let label = |idx| op(Either::Right(ControlFlowOp::Label(Label(idx))));
+        let ops = vec![
+            op(Either::Right(ControlFlowOp::Jump(Label(1)))),
+            label(0),
+            op(Either::Right(ControlFlowOp::Comment)),
+            label(1),
+            op(Either::Right(ControlFlowOp::JumpIfNotZero(
+                reg(0),
+                Label(2),
+            ))),
+            label(2),
+            op(Either::Right(ControlFlowOp::Jump(Label(0)))),
+            ret(),
+        ];
+        let expected = vec![
+            ops[1].clone(),
+            ops[2].clone(),
+            ops[3].clone(),
+            ops[5].clone(),
+            ops[6].clone(),
+            ops[7].clone(),
+        ];
+        assert_eq!(optimize(ops), asm(expected));
+    }
+
+    #[test]
+    fn repeated_comparisons_are_removed() {
+        let eq = |dst, lhs, rhs| {
+            op(Either::Left(AllocatedOpcode::EQ(
+                reg(dst),
+                reg(lhs),
+                reg(rhs),
+            )))
+        };
+        let lt = |dst, lhs, rhs| {
+            op(Either::Left(AllocatedOpcode::LT(
+                reg(dst),
+                reg(lhs),
+                reg(rhs),
+            )))
+        };
+        let ops = vec![eq(0, 1, 2), eq(0, 1, 2), eq(0, 1, 2), ret()];
+        assert_eq!(optimize(ops), asm(vec![eq(0, 1, 2), ret()]));
+
+        // The second comparison differs when it reads the result of the first.
+        let ops = vec![lt(0, 0, 1), lt(0, 0, 1), lt(2, 1, 3), lt(2, 3, 1), ret()];
+        assert_eq!(optimize(ops.clone()), asm(ops));
+    }
+
+    #[test]
+    fn empty_push_pops_are_removed() {
+        let push = |idx| op(Either::Right(ControlFlowOp::PushAll(Label(idx))));
+        let pop = |idx| op(Either::Right(ControlFlowOp::PopAll(Label(idx))));
+        let ops = vec![
+            push(0),
+            op(Either::Right(ControlFlowOp::Comment)),
+            pop(0),
+            push(1),
+            push(2),
+            pop(2),
+            pop(1),
+            ret(),
+        ];
+        assert_eq!(optimize(ops), asm(vec![ret()]));
+
+        // A section around an instruction or a label is kept, as is a pair from different
+        // sections.
+        let ops = vec![
+            push(0),
+            op(Either::Left(AllocatedOpcode::MOVE(reg(0), reg(1)))),
+            pop(0),
+            push(1),
+            op(Either::Right(ControlFlowOp::Label(Label(3)))),
+            pop(1),
+            push(2),
+            pop(1),
+            ret(),
+        ];
+        assert_eq!(optimize(ops.clone()), asm(ops));
+    }
+
+    #[test]
+    fn removals_reach_a_fixpoint() {
+        // Removing the self move empties the stack frame, and removing the frame leaves a jump to
+        // the next label.
+        let ops = vec![
+            op(Either::Right(ControlFlowOp::Jump(Label(0)))),
+            op(Either::Left(AllocatedOpcode::CFEI(VirtualImmediate24 {
+                value: 8,
+            }))),
+            op(Either::Left(AllocatedOpcode::MOVE(reg(3), reg(3)))),
+            op(Either::Left(AllocatedOpcode::CFSI(VirtualImmediate24 {
+                value: 8,
+            }))),
+            op(Either::Right(ControlFlowOp::Label(Label(0)))),
+            ret(),
+        ];
+        let expected = vec![ops[4].clone(), ops[5].clone()];
+        assert_eq!(optimize(ops), asm(expected));
+    }
+}
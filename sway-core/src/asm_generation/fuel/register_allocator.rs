@@ -22,11 +22,9 @@ use super::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
 
 // Each node in the interference graph represents a VirtualRegister.
 // An edge from V1 -> V2 means that V2 was an open live range at the
-// the time V1 was defined. For spilling, incoming edges matter more
-// as it indicates how big the range is, and thus is better to spill.
-// An edge has a "bool" weight to indicate whether it was deleted
-// during colouring. We don't actually delete the edge because that's
-// required again during the actual assignment.
+// the time V1 was defined. An edge has a "bool" weight to indicate
+// whether it was deleted during colouring. We don't actually delete
+// the edge because that's required again during the actual assignment.
 pub type InterferenceGraph =
     petgraph::stable_graph::StableGraph<VirtualRegister, bool, petgraph::Directed>;
 
@@ -291,11 +289,14 @@ pub(crate) fn create_interference_graph(
 /// updated, as well as the immediate values for some or all jump instructions (`ji`, `jnei`, and
 /// `jnzi for now).
 ///
+/// MOVEs to or from the `temporaries` created by spilling are never removed.
+///
 pub(crate) fn coalesce_registers(
     ops: &[Op],
     live_out: Vec<FxHashSet<VirtualRegister>>,
     interference_graph: &mut InterferenceGraph,
     reg_to_node_map: &mut HashMap<VirtualRegister, NodeIndex>,
+    temporaries: &FxHashSet<VirtualRegister>,
 ) -> (Vec<Op>, Vec<FxHashSet<VirtualRegister>>) {
     // A map from the virtual registers that are removed to the virtual registers that they are
     // replaced with during the coalescing process.
@@ -333,6 +334,14 @@ pub(crate) fn coalesce_registers(
                             continue;
                         }
 
+                        // Coalescing a spill temporary would extend its live range, which must
+                        // stay short for the spill to relieve the register pressure.
+                        if temporaries.contains(r1) || temporaries.contains(r2) {
+                            reduced_ops.push(op.clone());
+                            reduced_live_out.push(live_out[op_idx].clone());
+                            continue;
+                        }
+
                         let r1_neighbours = interference_graph
                             .neighbors_undirected(*ix1)
                             .collect::<FxHashSet<_>>();
@@ -481,20 +490,33 @@ fn compute_def_use_points(ops: &[Op]) -> FxHashMap<VirtualRegister, (Vec<usize>,
 /// 2. Remove node n and all its edges from the graph
 ///    - This may make some new nodes have fewer than k neighbours which is nice.
 /// 3. If some vertex n still has k or more neighbors, then the graph may not be k colorable.
-///     We still add it to the stack as is, as a potential spill. When popping, if we still
-///     can't colour it, then it becomes an actual spill.
+///    We pick the node which is cheapest to spill and still add it to the stack as is, as a
+///    potential spill. When popping, if we still can't colour it, then it becomes an actual
+///    spill.
 /// ===============================================================================================
 ///
+/// Potential spills are chosen by their use density: the number of times they're defined or used
+/// relative to the length of their live range. Registers which are rarely touched over a long
+/// range are cheap to spill and free a register for many instructions. The `temporaries` created
+/// by earlier spills are only chosen once nothing else is left, as spilling them again can't
+/// shorten their live ranges.
+///
 pub(crate) fn color_interference_graph(
     interference_graph: &mut InterferenceGraph,
     ops: &[Op],
     live_out: &[FxHashSet<VirtualRegister>],
-) -> Result<Vec<NodeIndex>, FxHashSet<VirtualRegister>> {
+    temporaries: &FxHashSet<VirtualRegister>,
+) -> Vec<NodeIndex> {
     let mut stack = Vec::with_capacity(interference_graph.node_count());
     let mut on_stack = FxHashSet::default();
-    let mut spills = FxHashSet::default();
     let def_use_points = compute_def_use_points(ops);
 
+    // The number of instructions after which each register is live.
+    let mut live_range_lens = FxHashMap::<VirtualRegister, usize>::default();
+    for reg in live_out.iter().flatten() {
+        *live_range_lens.entry(reg.clone()).or_default() += 1;
+    }
+
     // Nodes with < k-degree before adding to the stack,
     // to have their neighbours processed.
     let mut worklist = vec![];
@@ -540,12 +562,8 @@ pub(crate) fn color_interference_graph(
         )
     }
 
-    // Mark edges to/from node satisfying the conditions as deleted.
-    fn delete_edges<P: Fn(&VirtualRegister, &VirtualRegister) -> bool>(
-        interference_graph: &mut InterferenceGraph,
-        node_index: NodeIndex,
-        should_delete: P,
-    ) {
+    // Mark all edges to/from node as deleted.
+    fn delete_edges(interference_graph: &mut InterferenceGraph, node_index: NodeIndex) {
         let edges: Vec<_> = interference_graph
             .edges_directed(node_index, Outgoing)
             .chain(interference_graph.edges_directed(node_index, Incoming))
@@ -553,15 +571,20 @@ pub(crate) fn color_interference_graph(
             .collect();
 
         for e in edges {
-            let (source, target) = interference_graph.edge_endpoints(e).unwrap();
-            {
-                if should_delete(&interference_graph[source], &interference_graph[target]) {
-                    interference_graph[e] = false;
-                }
-            }
+            interference_graph[e] = false;
         }
     }
 
+    // The (is temporary, def and use count, live range length) of a register, used to order
+    // spill candidates.
+    let spill_cost = |reg: &VirtualRegister| {
+        let num_def_uses = def_use_points
+            .get(reg)
+            .map_or(0, |(defs, uses)| defs.len() + uses.len());
+        let live_range_len = live_range_lens.get(reg).copied().unwrap_or(0) + 1;
+        (temporaries.contains(reg), num_def_uses, live_range_len)
+    };
+
     loop {
         while let Some(node_index) = worklist.pop() {
             // Ensure that we've not already processed this.
@@ -569,16 +592,12 @@ pub(crate) fn color_interference_graph(
                 continue;
             }
 
-            // This node is colourable.
+            // This node is colourable, or a potential spill.
             stack.push(node_index);
             on_stack.insert(node_index);
 
-            // When spilled, not all edges should be deleted, and the spilling
-            // code takes care of deleting the right edges.
-            if !spills.contains(&interference_graph[node_index]) {
-                // Delete all edges connected to node_index.
-                delete_edges(interference_graph, node_index, |_, _| true)
-            }
+            // Delete all edges connected to node_index.
+            delete_edges(interference_graph, node_index);
 
             let candidate_neighbors: Vec<_> = interference_graph
                 .neighbors_undirected(node_index)
@@ -594,45 +613,22 @@ pub(crate) fn color_interference_graph(
             }
         }
 
-        if let Some(&spill_reg_index) = pending.iter().max_by(|&&node1, &&node2| {
-            // At the moment, our spill priority function is just this,
-            // i.e., spill the register with more incoming interferences.
-            // (roughly indicating how long the interval is).
-            get_connected_incoming_neighbors(interference_graph, node1)
-                .count()
-                .cmp(&get_connected_incoming_neighbors(interference_graph, node2).count())
+        // Pick the pending node with the lowest use density, comparing the ratios of def and use
+        // counts to live range lengths by cross multiplying.  Ties go to the node with the most
+        // interferences, and then to the lowest index so that the choice is deterministic.
+        if let Some(&spill_reg_index) = pending.iter().min_by(|&&node1, &&node2| {
+            let (temp1, def_uses1, len1) = spill_cost(&interference_graph[node1]);
+            let (temp2, def_uses2, len2) = spill_cost(&interference_graph[node2]);
+            temp1
+                .cmp(&temp2)
+                .then((def_uses1 * len2).cmp(&(def_uses2 * len1)))
+                .then_with(|| {
+                    get_connected_neighbours(interference_graph, node2)
+                        .count()
+                        .cmp(&get_connected_neighbours(interference_graph, node1).count())
+                })
+                .then(node1.cmp(&node2))
         }) {
-            let spill_reg = interference_graph[spill_reg_index].clone();
-            spills.insert(spill_reg.clone());
-
-            // Update the interference graph that this is spilled.
-            // A spill implies a store right after a definition and
-            // a load right before a use, forming new tiny live ranges.
-            // So we retain only those interferences that correspond to
-            // these tiny live ranges and remove the rest.
-            let to_retain =
-                def_use_points
-                    .get(&spill_reg)
-                    .map_or(FxHashSet::default(), |(defs, uses)| {
-                        let mut retains = FxHashSet::default();
-                        for &def in defs {
-                            retains
-                                .extend(live_out[def].iter().filter(|reg| !spills.contains(*reg)));
-                        }
-                        for &r#use in uses.iter().filter(|&&r#use| r#use > 0) {
-                            retains.extend(
-                                live_out[r#use - 1]
-                                    .iter()
-                                    .filter(|reg| !spills.contains(*reg)),
-                            );
-                        }
-                        retains
-                    });
-
-            delete_edges(interference_graph, spill_reg_index, |source, target| {
-                !(to_retain.contains(source) || to_retain.contains(target))
-            });
-
             pending.remove(&spill_reg_index);
             worklist.push(spill_reg_index);
         } else {
@@ -640,34 +636,23 @@ pub(crate) fn color_interference_graph(
         }
     }
 
-    if spills.is_empty() {
-        Ok(stack)
-    } else {
-        Err(spills)
-    }
+    stack
 }
 
 /// Assigns an allocatable register to each virtual register used by some instruction in the
 /// list `self.ops`. The algorithm used is Chaitin's graph-coloring register allocation
 /// algorithm (https://en.wikipedia.org/wiki/Chaitin%27s_algorithm). The individual steps of
 /// the algorithm are thoroughly explained in register_allocator.rs.
-
+///
+/// Registers which can't be coloured are spilled to the stack and the allocation is retried,
+/// until every register is coloured.
 pub(crate) fn allocate_registers(
     ops: &[Op],
 ) -> Result<AllocatedAbstractInstructionSet, CompileError> {
-    enum ColouringResult {
-        Success {
-            updated_ops: Vec<Op>,
-            interference_graph: InterferenceGraph,
-            colouring_stack: Vec<NodeIndex>,
-        },
-        SpillsNeeded {
-            updated_ops: Vec<Op>,
-            spills: FxHashSet<VirtualRegister>,
-        },
-    }
-
-    fn try_color(ops: &[Op]) -> ColouringResult {
+    fn try_color(
+        ops: &[Op],
+        temporaries: &FxHashSet<VirtualRegister>,
+    ) -> (Vec<Op>, InterferenceGraph, Vec<NodeIndex>) {
         // Step 1: Liveness Analysis.
         let live_out = liveness_analysis(ops);
 
@@ -675,46 +660,64 @@ pub(crate) fn allocate_registers(
         let (mut interference_graph, mut reg_to_node_ix) =
             create_interference_graph(ops, &live_out);
 
-        // Step 3: Remove redundant MOVE instructions using the interference graph.
-        let (updated_ops, live_out) =
-            coalesce_registers(ops, live_out, &mut interference_graph, &mut reg_to_node_ix);
+        // Step 3: Remove redundant MOVE instructions using the interference graph.  Coalescing
+        // lowers the degree of the neighbours shared by the coalesced registers, which may make
+        // more MOVEs safe to remove, so repeat until no more are removed.
+        let (mut updated_ops, mut live_out) = coalesce_registers(
+            ops,
+            live_out,
+            &mut interference_graph,
+            &mut reg_to_node_ix,
+            temporaries,
+        );
+        loop {
+            let num_ops = updated_ops.len();
+            (updated_ops, live_out) = coalesce_registers(
+                &updated_ops,
+                live_out,
+                &mut interference_graph,
+                &mut reg_to_node_ix,
+                temporaries,
+            );
+            if updated_ops.len() == num_ops {
+                break;
+            }
+        }
 
         // Step 4: Simplify - i.e. color the interference graph and return a stack that contains
         // each colorable node and its neighbors.
-        match color_interference_graph(&mut interference_graph, &updated_ops, &live_out) {
-            Ok(colouring_stack) => ColouringResult::Success {
-                updated_ops,
-                interference_graph,
-                colouring_stack,
-            },
-            Err(spills) => ColouringResult::SpillsNeeded {
-                updated_ops,
-                spills,
-            },
-        }
+        let colouring_stack = color_interference_graph(
+            &mut interference_graph,
+            &updated_ops,
+            &live_out,
+            temporaries,
+        );
+        (updated_ops, interference_graph, colouring_stack)
     }
 
     // We start with the ops we're given.
     let mut updated_ops_ref = ops;
     // A placeholder for updated ops.
     let mut updated_ops;
-    // How many times to try spilling before we give up.
-    let mut try_count = 0;
-    // Try and assign registers. If we fail, spill. Repeat few times.
-    let (updated_ops, interference_graph, mut stack) = loop {
-        match try_color(updated_ops_ref) {
-            ColouringResult::Success {
-                updated_ops,
-                interference_graph,
-                colouring_stack,
-            } => {
-                break (updated_ops, interference_graph, colouring_stack);
-            }
-            ColouringResult::SpillsNeeded {
-                updated_ops: updated_ops_before_spill,
-                spills,
-            } => {
-                if try_count >= 4 {
+    // The registers created to refill and spill the spilled registers.
+    let mut temporaries = FxHashSet::default();
+    // Try and assign registers. If we fail, spill and try again.
+    let (updated_ops, pool) = loop {
+        let (updated_ops_before_spill, interference_graph, mut stack) =
+            try_color(updated_ops_ref, &temporaries);
+
+        // Step 5: Use the stack to assign a register for each virtual register.
+        match assign_registers(&interference_graph, &mut stack) {
+            Ok(pool) => break (updated_ops_before_spill, pool),
+            Err(uncoloured) => {
+                // Temporaries only live from a refill to its use, or from a def to its spill, so
+                // spilling them again doesn't help.  Spilling other registers reduces the
+                // pressure around them, so they're left to be coloured on the next attempt.
+                let spills = uncoloured
+                    .into_iter()
+                    .filter(|reg| !temporaries.contains(reg))
+                    .collect::<FxHashSet<_>>();
+                if spills.is_empty() {
                     let comment = updated_ops_before_spill
                         .iter()
                         .find_map(|op| {
@@ -737,15 +740,14 @@ pub(crate) fn allocate_registers(
                         Span::dummy(),
                     ));
                 }
-                try_count += 1;
-                updated_ops = spill(&updated_ops_before_spill, &spills);
+                let (spilled_ops, spill_temporaries) = spill(&updated_ops_before_spill, &spills);
+                temporaries.extend(spill_temporaries);
+                updated_ops = spilled_ops;
                 updated_ops_ref = &updated_ops;
             }
         }
     };
 
-    // Step 5: Use the stack to assign a register for each virtual register.
-    let pool = assign_registers(&interference_graph, &mut stack)?;
     // Step 6: Update all instructions to use the resulting register pool.
     let mut buf = vec![];
     for op in &updated_ops {
@@ -753,6 +755,7 @@ pub(crate) fn allocate_registers(
             opcode: op.allocate_registers(&pool),
             comment: op.comment.clone(),
             owning_span: op.owning_span.clone(),
+            inlined_at: op.inlined_at.clone(),
         })
     }
 
@@ -765,11 +768,14 @@ pub(crate) fn allocate_registers(
 /// the neighbors of v (available from the stack) and the list of virtual registers already used by
 /// r (available in the used_by field) is empty.
 ///
+/// Returns the virtual registers for which no register was available, if there are any.
+///
 fn assign_registers(
     interference_graph: &InterferenceGraph,
     stack: &mut Vec<NodeIndex>,
-) -> Result<RegisterPool, CompileError> {
+) -> Result<RegisterPool, FxHashSet<VirtualRegister>> {
     let mut pool = RegisterPool::init();
+    let mut uncoloured = FxHashSet::default();
 
     while let Some(node) = stack.pop() {
         let reg = interference_graph[node].clone();
@@ -788,23 +794,24 @@ fn assign_registers(
             if let Some(RegisterAllocationStatus { reg: _, used_by }) = available {
                 used_by.insert(reg.clone());
             } else {
-                return Err(CompileError::Internal(
-                    "The allocator cannot resolve a register mapping for this program. \
-                             Using #[inline(never)] on some functions may help.",
-                    Span::dummy(),
-                ));
+                uncoloured.insert(reg);
             }
         }
     }
 
-    Ok(pool)
+    if uncoloured.is_empty() {
+        Ok(pool)
+    } else {
+        Err(uncoloured)
+    }
 }
 
 /// Given a function, its locals info (stack frame usage details)
 /// and a set of virtual registers to be spilled, insert the actual spills
-/// and return the updated function and the updated stack info.
-fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
+/// and return the updated function along with the temporaries created for the spills.
+fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> (Vec<Op>, FxHashSet<VirtualRegister>) {
     let mut spilled: Vec<Op> = vec![];
+    let mut temporaries = FxHashSet::default();
 
     // Attempt to discover the current stack size and base register.
     let mut cfe_idx_opt = None;
@@ -854,6 +861,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 comment: op.comment.clone()
                     + &format!(" and {new_locals_byte_size} bytes for spills"),
                 owning_span: op.owning_span.clone(),
+                inlined_at: op.inlined_at.clone(),
             });
         } else if matches!(cfs_idx_opt, Some(cfs_idx) if cfs_idx == op_idx) {
             // This is the CFS instruction, use the new stack size.
@@ -864,6 +872,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 comment: op.comment.clone()
                     + &format!(" and {new_locals_byte_size} bytes for spills"),
                 owning_span: op.owning_span.clone(),
+                inlined_at: op.inlined_at.clone(),
             });
         } else {
             // For every other instruction:
@@ -872,6 +881,15 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
             let use_registers = op.use_registers();
             let def_registers = op.def_registers();
 
+            // Each instruction refills and spills through its own temporaries, so that a spilled
+            // register is only live from a refill to its use, or from a def to its spill.
+            let op_temporaries: HashMap<&VirtualRegister, VirtualRegister> = use_registers
+                .iter()
+                .chain(def_registers.iter())
+                .filter(|reg| spills.contains(**reg))
+                .map(|&reg| (reg, spill_temporary(reg, op_idx)))
+                .collect();
+
             // Calculate the address off a local in a register + imm word offset.
             fn calculate_offset_reg_wordimm(
                 inst_list: &mut Vec<Op>,
@@ -888,6 +906,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Set offset".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_mov_instr);
                     let offset_add_instr = Op {
@@ -898,6 +917,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Add offset to stack base".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_add_instr);
                     (
@@ -924,6 +944,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Offset computation".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_upper_mov_instr);
                     let offset_upper_shift_instr = Op {
@@ -934,6 +955,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Offset computation".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_upper_shift_instr);
                     let offset_add_instr = Op {
@@ -944,6 +966,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill/Refill: Offset computation".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     inst_list.push(offset_add_instr);
                     (
@@ -964,7 +987,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 if offset_bytes / 8 <= compiler_constants::TWELVE_BITS as u32 {
                     spilled.push(Op {
                         opcode: Either::Left(VirtualOp::LW(
-                            spilled_use.clone(),
+                            op_temporaries[spilled_use].clone(),
                             VirtualRegister::Constant(ConstantRegister::LocalsBase),
                             VirtualImmediate12 {
                                 // This will be multiplied by 8 by the VM
@@ -973,26 +996,32 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Refilling from spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                 } else {
                     let (offset_reg, offset_imm_word) =
                         calculate_offset_reg_wordimm(&mut spilled, offset_bytes);
                     let lw = Op {
                         opcode: Either::Left(VirtualOp::LW(
-                            spilled_use.clone(),
+                            op_temporaries[spilled_use].clone(),
                             offset_reg,
                             // This will be multiplied by 8 by the VM
                             offset_imm_word,
                         )),
                         comment: "Refilling from spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     spilled.push(lw);
                 }
             }
 
-            // The op itself.
-            spilled.push(op.clone());
+            // The op itself, using the temporaries.
+            let reg_to_reg_map = op_temporaries
+                .iter()
+                .map(|(&reg, temporary)| (reg, temporary))
+                .collect();
+            spilled.push(op.update_register(&reg_to_reg_map));
 
             // Take care of spills from the def registers.
             for &spilled_def in def_registers.iter().filter(|def| spills.contains(def)) {
@@ -1003,7 +1032,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                     spilled.push(Op {
                         opcode: Either::Left(VirtualOp::SW(
                             VirtualRegister::Constant(ConstantRegister::LocalsBase),
-                            spilled_def.clone(),
+                            op_temporaries[spilled_def].clone(),
                             VirtualImmediate12 {
                                 // This will be multiplied by 8 by the VM
                                 value: (offset_bytes / 8) as u16,
@@ -1011,6 +1040,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                         )),
                         comment: "Spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     });
                 } else {
                     let (offset_reg, offset_imm_word) =
@@ -1018,18 +1048,143 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                     let sw = Op {
                         opcode: Either::Left(VirtualOp::SW(
                             offset_reg,
-                            spilled_def.clone(),
+                            op_temporaries[spilled_def].clone(),
                             // This will be multiplied by 8 by the VM
                             offset_imm_word,
                         )),
                         comment: "Spill".to_string(),
                         owning_span: None,
+                        inlined_at: Vec::new(),
                     };
                     spilled.push(sw);
                 }
             }
+
+            temporaries.extend(op_temporaries.into_values());
+        }
+    }
+
+    (spilled, temporaries)
+}
+
+/// A fresh virtual register to refill or spill `reg` through around the instruction at `op_idx`.
+fn spill_temporary(reg: &VirtualRegister, op_idx: usize) -> VirtualRegister {
+    match reg {
+        VirtualRegister::Virtual(name) => VirtualRegister::Virtual(format!("{name}_spill{op_idx}")),
+        VirtualRegister::Constant(_) => unreachable!("Constant registers are never spilled."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::allocated_ops::AllocatedOpcode;
+
+    fn op(opcode: VirtualOp) -> Op {
+        Op::unowned_new_with_comment(opcode, "")
+    }
+
+    fn virt(name: impl Into<String>) -> VirtualRegister {
+        VirtualRegister::Virtual(name.into())
+    }
+
+    /// Run straight-line allocated code, returning the value it returns.
+    fn run(ops: &[AllocatedAbstractOp]) -> u64 {
+        let mut regs = FxHashMap::<AllocatedRegister, u64>::default();
+        let mut stack = vec![0u64; 1024];
+        let get = |regs: &FxHashMap<AllocatedRegister, u64>, reg: &AllocatedRegister| match reg {
+            AllocatedRegister::Constant(ConstantRegister::LocalsBase) => 0,
+            _ => regs[reg],
+        };
+        for op in ops {
+            let Either::Left(opcode) = &op.opcode else {
+                continue;
+            };
+            match opcode {
+                AllocatedOpcode::MOVI(dst, imm) => {
+                    regs.insert(dst.clone(), imm.value as u64);
+                }
+                AllocatedOpcode::MOVE(dst, src) => {
+                    regs.insert(dst.clone(), get(&regs, src));
+                }
+                AllocatedOpcode::ADD(dst, lhs, rhs) => {
+                    regs.insert(dst.clone(), get(&regs, lhs) + get(&regs, rhs));
+                }
+                AllocatedOpcode::SLLI(dst, src, imm) => {
+                    regs.insert(dst.clone(), get(&regs, src) << imm.value);
+                }
+                AllocatedOpcode::LW(dst, base, imm) => {
+                    let addr = get(&regs, base) as usize + imm.value as usize * 8;
+                    regs.insert(dst.clone(), stack[addr / 8]);
+                }
+                AllocatedOpcode::SW(base, src, imm) => {
+                    let addr = get(&regs, base) as usize + imm.value as usize * 8;
+                    stack[addr / 8] = get(&regs, src);
+                }
+                AllocatedOpcode::RET(reg) => return get(&regs, reg),
+                AllocatedOpcode::CFEI(_) | AllocatedOpcode::CFSI(_) => (),
+                opcode => panic!("Unexpected opcode {opcode}"),
+            }
         }
+        panic!("No RET");
     }
 
-    spilled
+    #[test]
+    fn high_register_pressure_is_spilled() {
+        // Keep 100 registers live at once, far more than there are to allocate.
+        let mut ops = vec![op(VirtualOp::CFEI(VirtualImmediate24 { value: 0 }))];
+        for i in 1..=100 {
+            ops.push(op(VirtualOp::MOVI(
+                virt(format!("v{i}")),
+                VirtualImmediate18 { value: i },
+            )));
+        }
+        ops.push(op(VirtualOp::MOVI(
+            virt("acc0"),
+            VirtualImmediate18 { value: 0 },
+        )));
+        for i in 1..=100 {
+            ops.push(op(VirtualOp::ADD(
+                virt(format!("acc{i}")),
+                virt(format!("acc{}", i - 1)),
+                virt(format!("v{i}")),
+            )));
+        }
+        ops.push(op(VirtualOp::CFSI(VirtualImmediate24 { value: 0 })));
+        ops.push(op(VirtualOp::RET(virt("acc100"))));
+
+        let allocated = allocate_registers(&ops).unwrap();
+        assert!(allocated.ops.iter().any(|op| op.comment == "Spill"));
+        for op in &allocated.ops {
+            if let Either::Left(opcode) = &op.opcode {
+                for reg in opcode.def_registers() {
+                    if let AllocatedRegister::Allocated(reg) = reg {
+                        assert!(*reg < compiler_constants::NUM_ALLOCATABLE_REGISTERS);
+                    }
+                }
+            }
+        }
+        assert_eq!(run(&allocated.ops), 5050);
+    }
+
+    #[test]
+    fn copies_are_coalesced() {
+        let ops = vec![
+            op(VirtualOp::CFEI(VirtualImmediate24 { value: 0 })),
+            op(VirtualOp::MOVI(virt("v0"), VirtualImmediate18 { value: 1 })),
+            op(VirtualOp::MOVE(virt("v1"), virt("v0"))),
+            op(VirtualOp::MOVE(virt("v2"), virt("v1"))),
+            op(VirtualOp::ADD(virt("v3"), virt("v2"), virt("v2"))),
+            op(VirtualOp::MOVE(virt("v4"), virt("v3"))),
+            op(VirtualOp::CFSI(VirtualImmediate24 { value: 0 })),
+            op(VirtualOp::RET(virt("v4"))),
+        ];
+
+        let allocated = allocate_registers(&ops).unwrap();
+        assert!(!allocated
+            .ops
+            .iter()
+            .any(|op| matches!(op.opcode, Either::Left(AllocatedOpcode::MOVE(..)))));
+        assert_eq!(run(&allocated.ops), 2);
+    }
 }
@@ -0,0 +1,195 @@
+//! Lowering of multi-way branches on the value of a register.
+//!
+//! A [Switch] is lowered to a jump table when its keys are dense, which dispatches in a constant
+//! number of instructions.  Otherwise its sorted keys are searched with a binary search, which
+//! uses a jump table for each dense half and compares a handful of keys one at a time.
+//!
+//! The lowering is expressed as [SwitchOp]s rather than VM ops, so that both the IR code
+//! generation, with virtual registers, and the contract selector dispatch, with allocated
+//! registers, can use it.
+
+use std::collections::BTreeMap;
+
+use super::register_sequencer::RegisterSequencer;
+use crate::asm_lang::Label;
+
+/// The fewest keys for which a jump table is used.
+pub(crate) const MIN_JUMP_TABLE_KEYS: usize = 4;
+
+/// The most entries in a jump table.
+const MAX_JUMP_TABLE_LEN: u64 = 1 << 12;
+
+/// The most keys which are compared one at a time, rather than searched.
+const MAX_LINEAR_SEARCH_KEYS: usize = 3;
+
+/// A branch to the label of the case whose key is equal to the value of a register, or to the
+/// default label if no case matches.
+pub(crate) struct Switch {
+    /// The cases, sorted by their unique keys.
+    cases: Vec<(u64, Label)>,
+    default: Label,
+}
+
+/// A step of a lowered [Switch], comparing the switched value with immediate keys.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum SwitchOp {
+    Label(Label),
+    Jump(Label),
+    JumpIfEq(u64, Label),
+    JumpIfLt(u64, Label),
+    JumpIfGt(u64, Label),
+    /// Jump to the label at index `value - min`, which must be in bounds.
+    JumpTable {
+        min: u64,
+        labels: Vec<Label>,
+    },
+}
+
+impl Switch {
+    /// Create a switch over the given cases.  When several cases have the same key, the first
+    /// of them is taken.
+    pub(crate) fn new(cases: impl IntoIterator<Item = (u64, Label)>, default: Label) -> Self {
+        let mut unique_cases = BTreeMap::new();
+        for (key, label) in cases {
+            unique_cases.entry(key).or_insert(label);
+        }
+        Switch {
+            cases: unique_cases.into_iter().collect(),
+            default,
+        }
+    }
+
+    /// Lower the switch, using `reg_seqr` for the labels between the steps of a binary search.
+    pub(crate) fn lower(&self, reg_seqr: &mut RegisterSequencer) -> Vec<SwitchOp> {
+        let mut ops = Vec::new();
+        lower_cases(&self.cases, self.default, reg_seqr, &mut ops);
+        ops
+    }
+}
+
+fn lower_cases(
+    cases: &[(u64, Label)],
+    default: Label,
+    reg_seqr: &mut RegisterSequencer,
+    ops: &mut Vec<SwitchOp>,
+) {
+    if cases.len() <= MAX_LINEAR_SEARCH_KEYS {
+        ops.extend(
+            cases
+                .iter()
+                .map(|(key, label)| SwitchOp::JumpIfEq(*key, *label)),
+        );
+        ops.push(SwitchOp::Jump(default));
+    } else if let Some(table_len) = dense_table_len(cases) {
+        let min = cases[0].0;
+        let max = cases[cases.len() - 1].0;
+        if min > 0 {
+            ops.push(SwitchOp::JumpIfLt(min, default));
+        }
+        if max < u64::MAX {
+            ops.push(SwitchOp::JumpIfGt(max, default));
+        }
+        // Keys in the range without a case go to the default.
+        let mut labels = vec![default; table_len as usize];
+        for (key, label) in cases {
+            labels[(key - min) as usize] = *label;
+        }
+        ops.push(SwitchOp::JumpTable { min, labels });
+    } else {
+        // Search the upper half first, jumping to the lower half when the value is less than
+        // the smallest key of the upper half.
+        let (lower, upper) = cases.split_at(cases.len() / 2);
+        let lower_label = reg_seqr.get_label();
+        ops.push(SwitchOp::JumpIfLt(upper[0].0, lower_label));
+        lower_cases(upper, default, reg_seqr, ops);
+        ops.push(SwitchOp::Label(lower_label));
+        lower_cases(lower, default, reg_seqr, ops);
+    }
+}
+
+/// The length of a jump table for the sorted `cases`, if at least half of its entries would have
+/// a case.
+fn dense_table_len(cases: &[(u64, Label)]) -> Option<u64> {
+    if cases.len() < MIN_JUMP_TABLE_KEYS {
+        return None;
+    }
+    let table_len = (cases[cases.len() - 1].0 - cases[0].0).checked_add(1)?;
+    (table_len <= MAX_JUMP_TABLE_LEN && table_len <= 2 * cases.len() as u64).then_some(table_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(count: usize) -> Vec<Label> {
+        (100..100 + count).map(Label).collect()
+    }
+
+    #[test]
+    fn few_keys_are_compared_linearly() {
+        let ls = labels(3);
+        let switch = Switch::new([(7, ls[0]), (3, ls[1]), (7, ls[2])], Label(0));
+        assert_eq!(
+            switch.lower(&mut RegisterSequencer::new()),
+            vec![
+                SwitchOp::JumpIfEq(3, ls[1]),
+                SwitchOp::JumpIfEq(7, ls[0]),
+                SwitchOp::Jump(Label(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dense_keys_use_a_jump_table() {
+        let ls = labels(4);
+        let switch = Switch::new([(0, ls[0]), (1, ls[1]), (2, ls[2]), (4, ls[3])], Label(0));
+        assert_eq!(
+            switch.lower(&mut RegisterSequencer::new()),
+            vec![
+                SwitchOp::JumpIfGt(4, Label(0)),
+                SwitchOp::JumpTable {
+                    min: 0,
+                    labels: vec![ls[0], ls[1], ls[2], Label(0), ls[3]],
+                },
+            ]
+        );
+
+        let switch = Switch::new((10..60).zip(labels(50)), Label(0));
+        let ops = switch.lower(&mut RegisterSequencer::new());
+        assert_eq!(ops[0], SwitchOp::JumpIfLt(10, Label(0)));
+        assert_eq!(ops[1], SwitchOp::JumpIfGt(59, Label(0)));
+        assert_eq!(
+            ops[2],
+            SwitchOp::JumpTable {
+                min: 10,
+                labels: labels(50),
+            }
+        );
+    }
+
+    #[test]
+    fn sparse_keys_are_searched() {
+        let ls = labels(6);
+        let default = Label(99);
+        let keys: [u64; 6] = [1 << 40, 5, 1 << 20, 17, 1 << 30, 1 << 50];
+        let switch = Switch::new(keys.into_iter().zip(ls.iter().copied()), default);
+        let mut reg_seqr = RegisterSequencer::new();
+        let lower_label = Label(0);
+        let ops = switch.lower(&mut reg_seqr);
+        assert_eq!(
+            ops,
+            vec![
+                SwitchOp::JumpIfLt(1 << 30, lower_label),
+                SwitchOp::JumpIfEq(1 << 30, ls[4]),
+                SwitchOp::JumpIfEq(1 << 40, ls[0]),
+                SwitchOp::JumpIfEq(1 << 50, ls[5]),
+                SwitchOp::Jump(default),
+                SwitchOp::Label(lower_label),
+                SwitchOp::JumpIfEq(5, ls[1]),
+                SwitchOp::JumpIfEq(17, ls[3]),
+                SwitchOp::JumpIfEq(1 << 20, ls[2]),
+                SwitchOp::Jump(default),
+            ]
+        );
+    }
+}
@@ -64,6 +64,7 @@ pub(super) enum FinalProgram {
         data_section: DataSection,
         ops: Vec<AllocatedOp>,
         entries: Vec<(SelectorOpt, ImmOffset, FnName, Option<DeclRefFunction>)>,
+        functions: Vec<(FnName, ImmOffset)>,
     },
     Evm {
         ops: Vec<etk_asm::ops::AbstractOp>,
@@ -6,7 +6,10 @@ use crate::{
         allocated_abstract_instruction_set::AllocatedAbstractInstructionSet,
         compiler_constants,
         data_section::{DataSection, Entry},
+        function_dedup::dedup_functions,
+        peephole::peephole_optimize,
         register_sequencer::RegisterSequencer,
+        switch::{Switch, SwitchOp},
     },
     asm_lang::{
         allocated_ops::{AllocatedOpcode, AllocatedRegister},
@@ -60,6 +63,7 @@ impl AbstractProgram {
             .collect();
 
         // Gather all the functions together, optimise and then verify the instructions.
+        let entry_count = self.entries.len();
         let abstract_functions = self
             .entries
             .into_iter()
@@ -69,16 +73,23 @@ impl AbstractProgram {
             .map(AbstractInstructionSet::verify)
             .collect::<Result<Vec<_>, _>>()?;
 
-        // Allocate the registers for each function.
+        // Allocate the registers for each function, then remove the instructions left doing
+        // nothing, both before and after the PUSHA/POPA sections are expanded.
         let functions = abstract_functions
             .into_iter()
             .map(|fn_ops| {
                 fn_ops
                     .allocate_registers()
+                    .map(peephole_optimize)
                     .map(AllocatedAbstractInstructionSet::emit_pusha_popa)
+                    .map(peephole_optimize)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        // Merge the functions which were compiled to identical code, typically monomorphized
+        // copies which don't depend on their type parameters.
+        let functions = dedup_functions(functions, entry_count);
+
         // XXX need to verify that the stack use for each function is balanced.
 
         Ok(AllocatedProgram {
@@ -110,29 +121,34 @@ impl AbstractProgram {
                     opcode: Either::Right(ControlFlowOp::Jump(label)),
                     comment: String::new(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 1.5
                 AllocatedAbstractOp {
                     opcode: Either::Left(AllocatedOpcode::NOOP),
                     comment: "".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 2 -- full word u64 placeholder
                 AllocatedAbstractOp {
                     opcode: Either::Right(ControlFlowOp::DataSectionOffsetPlaceholder),
                     comment: "data section offset".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 AllocatedAbstractOp {
                     opcode: Either::Right(ControlFlowOp::Label(label)),
                     comment: "end of metadata".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 3 -- load the data offset into $ds
                 AllocatedAbstractOp {
                     opcode: Either::Left(AllocatedOpcode::DataSectionRegisterLoadPlaceholder),
                     comment: "".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
                 // word 3.5 -- add $ds $ds $is
                 AllocatedAbstractOp {
@@ -143,6 +159,7 @@ impl AbstractProgram {
                     )),
                     comment: "".into(),
                     owning_span: None,
+                    inlined_at: Vec::new(),
                 },
             ]
             .to_vec(),
@@ -164,6 +181,7 @@ impl AbstractProgram {
             opcode: Either::Right(ControlFlowOp::Comment),
             comment: "Begin contract ABI selector switch".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
         // Load the selector from the call frame.
@@ -178,52 +196,119 @@ impl AbstractProgram {
             )),
             comment: "load input function selector".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
 
-        // Add a 'case' for each entry with a selector.
-        for entry in &self.entries {
-            let selector = match entry.selector {
-                Some(sel) => sel,
-                // Skip entries that don't have a selector - they're probably tests.
-                None => continue,
-            };
-
-            // Put the selector in the data section.
-            let data_label = self.data_section.insert_data_value(Entry::new_word(
-                u32::from_be_bytes(selector) as u64,
-                None,
-                None,
-            ));
+        // Switch on the selectors of the entries, skipping entries that don't have a selector -
+        // they're probably tests.  Selectors are hashes, so this is usually a binary search.
+        let mismatch_label = self.reg_seqr.get_label();
+        let cases = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let selector = entry.selector?;
+                Some((u32::from_be_bytes(selector) as u64, entry.label))
+            })
+            .collect::<Vec<_>>();
+        let switch_ops = Switch::new(cases, mismatch_label).lower(&mut self.reg_seqr);
 
-            // Load the data into a register for comparison.
-            asm_buf.ops.push(AllocatedAbstractOp {
-                opcode: Either::Left(AllocatedOpcode::LWDataId(PROG_SELECTOR_REG, data_label)),
-                comment: "load fn selector for comparison".into(),
-                owning_span: None,
-            });
+        for switch_op in switch_ops {
+            match switch_op {
+                SwitchOp::Label(label) => asm_buf.ops.push(AllocatedAbstractOp {
+                    opcode: Either::Right(ControlFlowOp::Label(label)),
+                    comment: String::new(),
+                    owning_span: None,
+                    inlined_at: Vec::new(),
+                }),
+                SwitchOp::Jump(label) => asm_buf.ops.push(AllocatedAbstractOp {
+                    opcode: Either::Right(ControlFlowOp::Jump(label)),
+                    comment: String::new(),
+                    owning_span: None,
+                    inlined_at: Vec::new(),
+                }),
+                SwitchOp::JumpTable { min, labels } => {
+                    let index_reg = if min == 0 {
+                        INPUT_SELECTOR_REG
+                    } else {
+                        self.load_selector(asm_buf, PROG_SELECTOR_REG, min);
+                        asm_buf.ops.push(AllocatedAbstractOp {
+                            opcode: Either::Left(AllocatedOpcode::SUB(
+                                CMP_RESULT_REG,
+                                INPUT_SELECTOR_REG,
+                                PROG_SELECTOR_REG,
+                            )),
+                            comment: "function selector jump table index".into(),
+                            owning_span: None,
+                            inlined_at: Vec::new(),
+                        });
+                        CMP_RESULT_REG
+                    };
+                    asm_buf.ops.push(AllocatedAbstractOp {
+                        opcode: Either::Right(ControlFlowOp::JumpTable(index_reg, labels)),
+                        comment: "jump to selected function".into(),
+                        owning_span: None,
+                        inlined_at: Vec::new(),
+                    });
+                }
+                SwitchOp::JumpIfEq(selector, label)
+                | SwitchOp::JumpIfLt(selector, label)
+                | SwitchOp::JumpIfGt(selector, label) => {
+                    // Load the selector into a register for comparison.
+                    self.load_selector(asm_buf, PROG_SELECTOR_REG, selector);
 
-            // Compare with the input selector.
-            asm_buf.ops.push(AllocatedAbstractOp {
-                opcode: Either::Left(AllocatedOpcode::EQ(
-                    CMP_RESULT_REG,
-                    INPUT_SELECTOR_REG,
-                    PROG_SELECTOR_REG,
-                )),
-                comment: "function selector comparison".into(),
-                owning_span: None,
-            });
+                    // Compare with the input selector.
+                    let (opcode, comment) = match switch_op {
+                        SwitchOp::JumpIfEq(..) => (
+                            AllocatedOpcode::EQ(
+                                CMP_RESULT_REG,
+                                INPUT_SELECTOR_REG,
+                                PROG_SELECTOR_REG,
+                            ),
+                            "function selector comparison",
+                        ),
+                        SwitchOp::JumpIfLt(..) => (
+                            AllocatedOpcode::LT(
+                                CMP_RESULT_REG,
+                                INPUT_SELECTOR_REG,
+                                PROG_SELECTOR_REG,
+                            ),
+                            "function selector search",
+                        ),
+                        _ => (
+                            AllocatedOpcode::GT(
+                                CMP_RESULT_REG,
+                                INPUT_SELECTOR_REG,
+                                PROG_SELECTOR_REG,
+                            ),
+                            "function selector search",
+                        ),
+                    };
+                    asm_buf.ops.push(AllocatedAbstractOp {
+                        opcode: Either::Left(opcode),
+                        comment: comment.into(),
+                        owning_span: None,
+                        inlined_at: Vec::new(),
+                    });
 
-            // Jump to the function label if the selector was equal.
-            asm_buf.ops.push(AllocatedAbstractOp {
-                // If the comparison result is _not_ equal to 0, then it was indeed equal.
-                opcode: Either::Right(ControlFlowOp::JumpIfNotZero(CMP_RESULT_REG, entry.label)),
-                comment: "jump to selected function".into(),
-                owning_span: None,
-            });
+                    // Jump to the label if the comparison succeeded, i.e., is not zero.
+                    asm_buf.ops.push(AllocatedAbstractOp {
+                        opcode: Either::Right(ControlFlowOp::JumpIfNotZero(CMP_RESULT_REG, label)),
+                        comment: "jump to selected function".into(),
+                        owning_span: None,
+                        inlined_at: Vec::new(),
+                    });
+                }
+            }
         }
 
         // If none of the selectors matched, then revert.  This may change in the future, see
         // https://github.com/FuelLabs/sway/issues/444
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Right(ControlFlowOp::Label(mismatch_label)),
+            comment: String::new(),
+            owning_span: None,
+            inlined_at: Vec::new(),
+        });
         asm_buf.ops.push(AllocatedAbstractOp {
             opcode: Either::Left(AllocatedOpcode::MOVI(
                 AllocatedRegister::Constant(ConstantRegister::Scratch),
@@ -233,6 +318,7 @@ impl AbstractProgram {
             )),
             comment: "special code for mismatched selector".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         });
         asm_buf.ops.push(AllocatedAbstractOp {
             opcode: Either::Left(AllocatedOpcode::RVRT(AllocatedRegister::Constant(
@@ -240,6 +326,35 @@ impl AbstractProgram {
             ))),
             comment: "revert if no selectors matched".into(),
             owning_span: None,
+            inlined_at: Vec::new(),
+        });
+    }
+
+    /// Load a selector, or a key of the search for one, into `reg`.
+    fn load_selector(
+        &mut self,
+        asm_buf: &mut AllocatedAbstractInstructionSet,
+        reg: AllocatedRegister,
+        selector: u64,
+    ) {
+        let opcode = if selector <= compiler_constants::EIGHTEEN_BITS {
+            AllocatedOpcode::MOVI(
+                reg,
+                VirtualImmediate18 {
+                    value: selector as u32,
+                },
+            )
+        } else {
+            let data_label = self
+                .data_section
+                .insert_data_value(Entry::new_word(selector, None, None));
+            AllocatedOpcode::LWDataId(reg, data_label)
+        };
+        asm_buf.ops.push(AllocatedAbstractOp {
+            opcode: Either::Left(opcode),
+            comment: "load fn selector for comparison".into(),
+            owning_span: None,
+            inlined_at: Vec::new(),
         });
     }
 }
@@ -1,9 +1,28 @@
 use super::{AllocatedProgram, FinalProgram};
 
 use crate::asm_generation::fuel::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
+use crate::asm_lang::ControlFlowOp;
+
+use either::Either;
 
 impl AllocatedProgram {
     pub(crate) fn into_final_program(mut self) -> Result<FinalProgram, crate::CompileError> {
+        // Find the start label of each function, for attributing the final ops to them.
+        let function_labels = self
+            .functions
+            .iter()
+            .filter_map(|f| {
+                f.ops.iter().find_map(|op| match &op.opcode {
+                    Either::Right(ControlFlowOp::Label(label)) => op
+                        .comment
+                        .strip_prefix("--- start of function: ")
+                        .and_then(|name| name.strip_suffix(" ---"))
+                        .map(|name| (name.to_owned(), *label)),
+                    _ => None,
+                })
+            })
+            .collect::<Vec<_>>();
+
         // Concat the prologue and all the functions together.
         let abstract_ops = AllocatedAbstractInstructionSet {
             ops: std::iter::once(self.prologue.ops)
@@ -17,6 +36,11 @@ impl AllocatedProgram {
             .realize_labels(&mut self.data_section)?;
         let ops = realized_ops.pad_to_even();
 
+        let functions = function_labels
+            .into_iter()
+            .filter_map(|(name, label)| label_offsets.get(&label).map(|block| (name, block.offs)))
+            .collect();
+
         // Collect the entry point offsets.
         let entries = self
             .entries
@@ -35,6 +59,7 @@ impl AllocatedProgram {
             data_section: self.data_section,
             ops,
             entries,
+            functions,
         })
     }
 }
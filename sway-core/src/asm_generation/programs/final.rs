@@ -15,6 +15,7 @@ impl FinalProgram {
                 data_section,
                 ops,
                 entries,
+                functions,
             } => FinalizedAsm {
                 data_section,
                 program_section: InstructionSet::Fuel { ops },
@@ -28,25 +29,24 @@ impl FinalProgram {
                         test_decl_ref,
                     })
                     .collect(),
+                functions,
                 abi: None,
             },
             FinalProgram::Evm { ops, abi } => FinalizedAsm {
-                data_section: DataSection {
-                    ..Default::default()
-                },
+                data_section: DataSection::default(),
                 program_section: InstructionSet::Evm { ops },
                 program_kind: super::ProgramKind::Script,
                 entries: vec![],
+                functions: vec![],
                 abi: Some(ProgramABI::Evm(abi)),
             },
             FinalProgram::MidenVM { ops } => FinalizedAsm {
-                data_section: DataSection {
-                    ..Default::default()
-                },
+                data_section: DataSection::default(),
                 program_section: InstructionSet::MidenVM { ops },
                 // should this be a script? :think:
                 program_kind: super::ProgramKind::Script,
                 entries: vec![],
+                functions: vec![],
                 abi: None, /* TODO? */
             },
         }
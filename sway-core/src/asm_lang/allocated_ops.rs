@@ -428,6 +428,8 @@ pub struct AllocatedOp {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The spans of the calls this op was inlined from, innermost first
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 impl fmt::Display for AllocatedOp {
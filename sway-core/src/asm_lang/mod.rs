@@ -46,6 +46,8 @@ pub(crate) struct Op {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The spans of the calls this op was inlined from, innermost first
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 #[derive(Clone, Debug)]
@@ -54,6 +56,8 @@ pub(crate) struct AllocatedAbstractOp {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The spans of the calls this op was inlined from, innermost first
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 #[derive(Clone, Debug)]
@@ -62,6 +66,8 @@ pub(crate) struct RealizedOp {
     /// A descriptive comment for ASM readability
     pub(crate) comment: String,
     pub(crate) owning_span: Option<Span>,
+    /// The spans of the calls this op was inlined from, innermost first
+    pub(crate) inlined_at: Vec<Span>,
 }
 
 impl Op {
@@ -77,6 +83,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::SW(destination_address, value_to_write, offset)),
             comment: String::new(),
             owning_span: Some(span),
+            inlined_at: Vec::new(),
         }
     }
     /// Write value in given [VirtualRegister] `value_to_write` to given memory address that is held within the
@@ -92,6 +99,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::SW(destination_address, value_to_write, offset)),
             comment: comment.into(),
             owning_span: Some(span),
+            inlined_at: Vec::new(),
         }
     }
     /// Moves the stack pointer by the given amount (i.e. allocates stack memory)
@@ -102,6 +110,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::CFEI(size_to_allocate_in_bytes)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
     pub(crate) fn unowned_new_with_comment(opcode: VirtualOp, comment: impl Into<String>) -> Self {
@@ -109,6 +118,7 @@ impl Op {
             opcode: Either::Left(opcode),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
     pub(crate) fn new(opcode: VirtualOp, owning_span: Span) -> Self {
@@ -116,6 +126,7 @@ impl Op {
             opcode: Either::Left(opcode),
             comment: String::new(),
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
     pub(crate) fn new_with_comment(
@@ -128,6 +139,7 @@ impl Op {
             opcode: Either::Left(opcode),
             comment,
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
 
@@ -137,6 +149,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: String::new(),
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
     /// Loads the data from [DataId] `data` into [VirtualRegister] `reg`.
@@ -149,6 +162,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::LWDataId(reg, data)),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -159,6 +173,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -173,6 +188,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: comment.into(),
             owning_span: Some(owning_span),
+            inlined_at: Vec::new(),
         }
     }
 
@@ -182,6 +198,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Label(label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -196,6 +213,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::MoveAddress(reg, label)),
             comment: comment.into(),
             owning_span,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -210,6 +228,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::MOVE(r1, r2)),
             comment: comment.into(),
             owning_span,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -218,6 +237,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Comment),
             comment: comm.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -226,6 +246,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Jump(label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -234,6 +255,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::Jump(label)),
             comment: comment.into(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -247,6 +269,7 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::JumpIfNotEq(reg0, reg1, label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -256,6 +279,18 @@ impl Op {
             opcode: Either::Right(OrganizationalOp::JumpIfNotZero(reg0, label)),
             comment: String::new(),
             owning_span: None,
+            inlined_at: Vec::new(),
+        }
+    }
+
+    /// Jumps to the [Label] in `labels` at the index held in [VirtualRegister] `index_reg`, which
+    /// must be in bounds.
+    pub(crate) fn jump_table(index_reg: VirtualRegister, labels: Vec<Label>) -> Self {
+        Op {
+            opcode: Either::Right(OrganizationalOp::JumpTable(index_reg, labels)),
+            comment: String::new(),
+            owning_span: None,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -269,6 +304,7 @@ impl Op {
             opcode: Either::Left(VirtualOp::JMP(reg)),
             comment: comment.into(),
             owning_span,
+            inlined_at: Vec::new(),
         }
     }
 
@@ -1065,6 +1101,7 @@ impl Op {
             },
             comment: self.comment.clone(),
             owning_span: self.owning_span.clone(),
+            inlined_at: self.inlined_at.clone(),
         }
     }
 
@@ -1554,6 +1591,9 @@ pub(crate) enum ControlFlowOp<Reg> {
     JumpIfNotEq(Reg, Reg, Label),
     // Jumps to a label if the register is not equal to zero
     JumpIfNotZero(Reg, Label),
+    // Jumps to the label at the index held in the register, through a table of jumps which
+    // follows the computed jump.  The index must be in bounds.
+    JumpTable(Reg, Vec<Label>),
     // Jumps to a label, similarly to Jump, though semantically expecting to return.
     Call(Label),
     // Save a label address in a register.
@@ -1582,6 +1622,13 @@ impl<Reg: fmt::Display> fmt::Display for ControlFlowOp<Reg> {
                 Comment => "".into(),
                 JumpIfNotEq(r1, r2, lab) => format!("jnei {r1} {r2} {lab}"),
                 JumpIfNotZero(r1, lab) => format!("jnzi {r1} {lab}"),
+                JumpTable(r1, labs) => format!(
+                    "jmptbl {r1} {}",
+                    labs.iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
                 Call(lab) => format!("fncall {lab}"),
                 MoveAddress(r1, lab) => format!("mova {r1} {lab}"),
                 DataSectionOffsetPlaceholder =>
@@ -1607,7 +1654,9 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
             | PopAll(_) => vec![],
 
             JumpIfNotEq(r1, r2, _) => vec![r1, r2],
-            JumpIfNotZero(r1, _) | MoveAddress(r1, _) | LoadLabel(r1, _) => vec![r1],
+            JumpIfNotZero(r1, _) | JumpTable(r1, _) | MoveAddress(r1, _) | LoadLabel(r1, _) => {
+                vec![r1]
+            }
         })
         .into_iter()
         .collect()
@@ -1626,7 +1675,7 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
             | PushAll(_)
             | PopAll(_) => vec![],
 
-            JumpIfNotZero(r1, _) => vec![r1],
+            JumpIfNotZero(r1, _) | JumpTable(r1, _) => vec![r1],
             JumpIfNotEq(r1, r2, _) => vec![r1, r2],
         })
         .into_iter()
@@ -1643,6 +1692,7 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
             | Jump(_)
             | JumpIfNotEq(..)
             | JumpIfNotZero(..)
+            | JumpTable(..)
             | Call(_)
             | DataSectionOffsetPlaceholder
             | PushAll(_)
@@ -1667,11 +1717,33 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
 
             JumpIfNotEq(r1, r2, label) => Self::JumpIfNotEq(update_reg(r1), update_reg(r2), *label),
             JumpIfNotZero(r1, label) => Self::JumpIfNotZero(update_reg(r1), *label),
+            JumpTable(r1, labels) => Self::JumpTable(update_reg(r1), labels.clone()),
             MoveAddress(r1, label) => Self::MoveAddress(update_reg(r1), *label),
             LoadLabel(r1, label) => Self::LoadLabel(update_reg(r1), *label),
         }
     }
 
+    pub(crate) fn update_labels(&self, mut update_label: impl FnMut(Label) -> Label) -> Self {
+        use ControlFlowOp::*;
+        match self {
+            Comment | DataSectionOffsetPlaceholder => self.clone(),
+
+            Label(label) => Label(update_label(*label)),
+            Jump(label) => Jump(update_label(*label)),
+            Call(label) => Call(update_label(*label)),
+            PushAll(label) => PushAll(update_label(*label)),
+            PopAll(label) => PopAll(update_label(*label)),
+            JumpIfNotEq(r1, r2, label) => JumpIfNotEq(r1.clone(), r2.clone(), update_label(*label)),
+            JumpIfNotZero(r1, label) => JumpIfNotZero(r1.clone(), update_label(*label)),
+            JumpTable(r1, labels) => JumpTable(
+                r1.clone(),
+                labels.iter().map(|label| update_label(*label)).collect(),
+            ),
+            MoveAddress(r1, label) => MoveAddress(r1.clone(), update_label(*label)),
+            LoadLabel(r1, label) => LoadLabel(r1.clone(), update_label(*label)),
+        }
+    }
+
     pub(crate) fn successors(
         &self,
         index: usize,
@@ -1682,7 +1754,7 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
 
         let mut next_ops = Vec::new();
 
-        if index + 1 < ops.len() && !matches!(self, Jump(_)) {
+        if index + 1 < ops.len() && !matches!(self, Jump(_) | JumpTable(..)) {
             next_ops.push(index + 1);
         };
 
@@ -1699,6 +1771,10 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
             Jump(jump_label) | JumpIfNotEq(_, _, jump_label) | JumpIfNotZero(_, jump_label) => {
                 next_ops.push(label_to_index[jump_label]);
             }
+
+            JumpTable(_, jump_labels) => {
+                next_ops.extend(jump_labels.iter().map(|label| label_to_index[label]));
+            }
         };
 
         next_ops
@@ -1753,6 +1829,7 @@ impl ControlFlowOp<VirtualRegister> {
 
             JumpIfNotEq(r1, r2, label) => JumpIfNotEq(map_reg(r1), map_reg(r2), *label),
             JumpIfNotZero(r1, label) => JumpIfNotZero(map_reg(r1), *label),
+            JumpTable(r1, labels) => JumpTable(map_reg(r1), labels.clone()),
             MoveAddress(r1, label) => MoveAddress(map_reg(r1), *label),
             LoadLabel(r1, label) => LoadLabel(map_reg(r1), *label),
         }
@@ -32,6 +32,17 @@ pub enum BuildTarget {
     MidenVM,
 }
 
+/// The format of the static gas profile written after compiling to finalized ASM.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum GasProfileFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 /// Configuration for the overall build and compilation process.
 #[derive(Clone)]
 pub struct BuildConfig {
@@ -45,7 +56,11 @@ pub struct BuildConfig {
     pub(crate) print_intermediate_asm: bool,
     pub(crate) print_finalized_asm: bool,
     pub(crate) print_ir: bool,
+    // Whether to verify the IR after every optimization pass.
+    pub(crate) verify_ir: bool,
     pub(crate) include_tests: bool,
+    // Whether to run the optimization passes reserved for release builds.
+    pub(crate) optimize: bool,
     pub time_phases: bool,
     pub metrics_outfile: Option<String>,
 }
@@ -89,7 +104,9 @@ impl BuildConfig {
             print_intermediate_asm: false,
             print_finalized_asm: false,
             print_ir: false,
+            verify_ir: false,
             include_tests: false,
+            optimize: false,
             time_phases: false,
             metrics_outfile: None,
         }
@@ -130,6 +147,32 @@ impl BuildConfig {
         }
     }
 
+    /// Whether or not to verify the IR after every optimization pass which modifies it, failing
+    /// with the name of the pass which left it invalid.
+    ///
+    /// This is always done in debug builds of the compiler.
+    ///
+    /// Default: `false`
+    pub fn verify_ir(self, a: bool) -> Self {
+        Self {
+            verify_ir: a,
+            ..self
+        }
+    }
+
+    /// Whether or not to run the optimization passes reserved for release builds, e.g. common
+    /// subexpression elimination.
+    ///
+    /// This should be set to `true` when building with the `release` profile.
+    ///
+    /// Default: `false`
+    pub fn optimize(self, a: bool) -> Self {
+        Self {
+            optimize: a,
+            ..self
+        }
+    }
+
     pub fn time_phases(self, a: bool) -> Self {
         Self {
             time_phases: a,
@@ -6,14 +6,22 @@ use std::{
 
 use sway_types::SourceEngine;
 
-use crate::{decl_engine::DeclEngine, query_engine::QueryEngine, type_system::TypeEngine};
+use crate::{
+    decl_engine::DeclEngine, language::LiteralEngine, query_engine::QueryEngine,
+    type_system::TypeEngine,
+};
 
-#[derive(Debug, Default)]
+/// The engines that hold the types, declarations, literals and sources of a compilation.
+///
+/// Cloning the engines copies all of their contents, so that the clone can be extended without
+/// affecting the original, while the IDs handed out by the original stay valid in the clone.
+#[derive(Debug, Default, Clone)]
 pub struct Engines {
     type_engine: TypeEngine,
     decl_engine: DeclEngine,
     query_engine: QueryEngine,
     source_engine: SourceEngine,
+    literal_engine: LiteralEngine,
 }
 
 impl Engines {
@@ -28,6 +36,7 @@ impl Engines {
             decl_engine,
             query_engine,
             source_engine,
+            literal_engine: LiteralEngine::default(),
         }
     }
 
@@ -47,6 +56,10 @@ impl Engines {
         &self.source_engine
     }
 
+    pub fn le(&self) -> &LiteralEngine {
+        &self.literal_engine
+    }
+
     /// Helps out some `thing: T` by adding `self` as context.
     pub fn help_out<T>(&self, thing: T) -> WithEngines<'_, T> {
         WithEngines {
@@ -135,12 +135,46 @@ pub(super) fn compile_contract(
         namespace,
         declarations,
     )?;
+    // Calls are dispatched on the selector alone, so two methods with the same selector could
+    // never both be reached.
+    let mut selectors: HashMap<[u8; 4], &ty::TyFunctionDecl> = HashMap::new();
     for decl in abi_entries {
+        let selector = abi_method_selector(decl, engines)?;
+        if let Some(other) = selectors.insert(selector, decl) {
+            // The methods aren't necessarily compiled in source order, so report the one declared
+            // first as the first, pointing at the later one. Methods from a superabi may be
+            // declared in another file, whose offsets can't be compared with those of this one,
+            // so the files are ordered by their source ids first.
+            let position = |decl: &ty::TyFunctionDecl| {
+                let span = decl.name.span();
+                (span.source_id().copied(), span.start())
+            };
+            let (first, second) = if position(other) <= position(decl) {
+                (other, decl)
+            } else {
+                (decl, other)
+            };
+            let signature = |decl: &ty::TyFunctionDecl| {
+                decl.to_selector_name(engines)
+                    .value
+                    .unwrap_or_else(|| decl.name.as_str().to_string())
+            };
+            return Err(CompileError::AbiMethodSelectorCollision {
+                first_signature: signature(first),
+                second_signature: signature(second),
+                selector: selector
+                    .iter()
+                    .fold("0x".to_string(), |hex, byte| format!("{hex}{byte:02x}")),
+                first_span: first.name.span(),
+                second_span: second.name.span(),
+            });
+        }
         compile_abi_method(
             context,
             &mut md_mgr,
             module,
             decl,
+            selector,
             logged_types_map,
             messages_types_map,
             engines,
@@ -520,16 +554,10 @@ fn compile_fn(
     Ok(func)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn compile_abi_method(
-    context: &mut Context,
-    md_mgr: &mut MetadataManager,
-    module: Module,
+fn abi_method_selector(
     ast_fn_decl: &ty::TyFunctionDecl,
-    logged_types_map: &HashMap<TypeId, LogId>,
-    messages_types_map: &HashMap<TypeId, MessageId>,
     engines: &Engines,
-) -> Result<Function, CompileError> {
+) -> Result<[u8; 4], CompileError> {
     // Use the error from .to_fn_selector_value() if possible, else make an CompileError::Internal.
     let get_selector_result = ast_fn_decl.to_fn_selector_value(engines);
     let mut warnings = Vec::new();
@@ -550,7 +578,20 @@ fn compile_abi_method(
             };
         }
     };
+    Ok(selector)
+}
 
+#[allow(clippy::too_many_arguments)]
+fn compile_abi_method(
+    context: &mut Context,
+    md_mgr: &mut MetadataManager,
+    module: Module,
+    ast_fn_decl: &ty::TyFunctionDecl,
+    selector: [u8; 4],
+    logged_types_map: &HashMap<TypeId, LogId>,
+    messages_types_map: &HashMap<TypeId, MessageId>,
+    engines: &Engines,
+) -> Result<Function, CompileError> {
     // An ABI method is always an entry point.
     let is_entry = true;
 
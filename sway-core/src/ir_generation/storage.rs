@@ -16,7 +16,7 @@ use sway_types::state::StateIndex;
 /// Hands out storage keys using a state index and a list of subfield indices.
 /// Basically returns sha256("storage_<state_index>_<idx1>_<idx2>_..")
 ///
-pub(super) fn get_storage_key<T>(ix: &StateIndex, indices: &[T]) -> Bytes32
+pub fn get_storage_key<T>(ix: &StateIndex, indices: &[T]) -> Bytes32
 where
     T: std::fmt::Display,
 {
@@ -1,5 +1,11 @@
+mod engine;
+pub mod fold;
+
+pub use engine::*;
+
 use crate::{type_system::*, Engines};
 
+use num_bigint::BigUint;
 use sway_error::error::CompileError;
 use sway_types::{integer_bits::IntegerBits, span};
 
@@ -124,6 +130,178 @@ impl Literal {
         }
     }
 
+    /// Returns the largest value of an unsigned integer with the given width.
+    pub fn max_for(bits: IntegerBits) -> BigUint {
+        match bits {
+            IntegerBits::Eight => u8::MAX.into(),
+            IntegerBits::Sixteen => u16::MAX.into(),
+            IntegerBits::ThirtyTwo => u32::MAX.into(),
+            IntegerBits::SixtyFour => u64::MAX.into(),
+        }
+    }
+
+    /// Returns the smallest value of an unsigned integer with the given width, which is always zero.
+    pub fn min_for(_bits: IntegerBits) -> BigUint {
+        BigUint::from(0u8)
+    }
+
+    /// Returns `value` as a literal of the unsigned integer type with the given width, or
+    /// [IntErrorKind::PosOverflow] if it doesn't fit.
+    pub fn from_biguint_for(value: BigUint, bits: IntegerBits) -> Result<Literal, IntErrorKind> {
+        match bits {
+            IntegerBits::Eight => Literal::u8_checked(value),
+            IntegerBits::Sixteen => Literal::u16_checked(value),
+            IntegerBits::ThirtyTwo => Literal::u32_checked(value),
+            IntegerBits::SixtyFour => Literal::u64_checked(value),
+        }
+    }
+
+    /// Returns `value` as a `u8` literal, or [IntErrorKind::PosOverflow] if it is too large.
+    pub fn u8_checked(value: BigUint) -> Result<Literal, IntErrorKind> {
+        u8::try_from(value)
+            .map(Literal::U8)
+            .map_err(|_| IntErrorKind::PosOverflow)
+    }
+
+    /// Returns `value` as a `u16` literal, or [IntErrorKind::PosOverflow] if it is too large.
+    pub fn u16_checked(value: BigUint) -> Result<Literal, IntErrorKind> {
+        u16::try_from(value)
+            .map(Literal::U16)
+            .map_err(|_| IntErrorKind::PosOverflow)
+    }
+
+    /// Returns `value` as a `u32` literal, or [IntErrorKind::PosOverflow] if it is too large.
+    pub fn u32_checked(value: BigUint) -> Result<Literal, IntErrorKind> {
+        u32::try_from(value)
+            .map(Literal::U32)
+            .map_err(|_| IntErrorKind::PosOverflow)
+    }
+
+    /// Returns `value` as a `u64` literal, or [IntErrorKind::PosOverflow] if it is too large.
+    pub fn u64_checked(value: BigUint) -> Result<Literal, IntErrorKind> {
+        u64::try_from(value)
+            .map(Literal::U64)
+            .map_err(|_| IntErrorKind::PosOverflow)
+    }
+
+    /// Returns `value` as a `b256` literal holding its 32-byte big-endian encoding, or
+    /// [IntErrorKind::PosOverflow] if it needs more than 32 bytes.
+    pub fn b256_checked(value: BigUint) -> Result<Literal, IntErrorKind> {
+        let value_bytes = value.to_bytes_be();
+        if value_bytes.len() > 32 {
+            return Err(IntErrorKind::PosOverflow);
+        }
+        let mut bytes = [0u8; 32];
+        bytes[32 - value_bytes.len()..].copy_from_slice(&value_bytes);
+        Ok(Literal::B256(bytes))
+    }
+
+    /// Returns a numeric literal as a `b256` literal holding its big-endian encoding, as when a
+    /// `b256` is written as a decimal.
+    ///
+    /// Returns `None` for literals that aren't [Literal::Numeric], or whose value needs more than
+    /// 32 bytes.
+    pub fn numeric_to_b256(&self) -> Option<Literal> {
+        match self {
+            Literal::Numeric(value) => Literal::b256_checked(BigUint::from(*value)).ok(),
+            Literal::U8(_)
+            | Literal::U16(_)
+            | Literal::U32(_)
+            | Literal::U64(_)
+            | Literal::String(_)
+            | Literal::Boolean(_)
+            | Literal::B256(_) => None,
+        }
+    }
+
+    /// Returns the bitwise complement of an unsigned integer literal within its width, as in
+    /// `!U8(0) == U8(255)`.
+    ///
+    /// Returns `None` for literals that aren't sized unsigned integers, including [Literal::Numeric],
+    /// whose width isn't known yet.
+    pub fn bitnot(&self) -> Option<Literal> {
+        match self {
+            Literal::U8(value) => Some(Literal::U8(!value)),
+            Literal::U16(value) => Some(Literal::U16(!value)),
+            Literal::U32(value) => Some(Literal::U32(!value)),
+            Literal::U64(value) => Some(Literal::U64(!value)),
+            Literal::Numeric(_) | Literal::String(_) | Literal::Boolean(_) | Literal::B256(_) => {
+                None
+            }
+        }
+    }
+
+    /// Returns the smallest unsigned integer width whose largest value is at least the value of
+    /// the literal, as in `Numeric(300)` fitting in [IntegerBits::Sixteen].
+    ///
+    /// Returns `None` for literals that aren't integers.
+    pub fn minimal_integer_bits(&self) -> Option<IntegerBits> {
+        let value = match self {
+            Literal::U8(value) => u64::from(*value),
+            Literal::U16(value) => u64::from(*value),
+            Literal::U32(value) => u64::from(*value),
+            Literal::U64(value) | Literal::Numeric(value) => *value,
+            Literal::String(_) | Literal::Boolean(_) | Literal::B256(_) => return None,
+        };
+        let value = BigUint::from(value);
+        [
+            IntegerBits::Eight,
+            IntegerBits::Sixteen,
+            IntegerBits::ThirtyTwo,
+            IntegerBits::SixtyFour,
+        ]
+        .into_iter()
+        .find(|bits| Literal::max_for(*bits) >= value)
+    }
+
+    /// Returns the literal truncated to an unsigned integer of the given width, keeping its low
+    /// bits as in `U64(256).truncate_to(Eight) == U8(0)`.
+    ///
+    /// Unlike [Literal::from_biguint_for], which rejects values that don't fit, this never fails.
+    /// The value of a literal that isn't an integer is read from its [Literal::to_bytes] as a
+    /// big-endian integer, so `true` truncates to `1` and a `b256` to its last bytes.
+    pub fn truncate_to(&self, bits: IntegerBits) -> Literal {
+        let bytes = self.to_bytes();
+        let low_bytes = &bytes[bytes.len().saturating_sub(8)..];
+        let mut word = [0u8; 8];
+        word[8 - low_bytes.len()..].copy_from_slice(low_bytes);
+        let value = u64::from_be_bytes(word);
+        match bits {
+            IntegerBits::Eight => Literal::U8(value as u8),
+            IntegerBits::Sixteen => Literal::U16(value as u16),
+            IntegerBits::ThirtyTwo => Literal::U32(value as u32),
+            IntegerBits::SixtyFour => Literal::U64(value),
+        }
+    }
+
+    /// Returns the bytes of the literal as the FuelVM lays them out in memory and as the ABI
+    /// encodes them.
+    ///
+    /// Integers and booleans take up a full big-endian word, `b256` values are emitted as-is and
+    /// strings are right-padded with zeros to a whole number of words.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Literal::U8(value) => u64::from(*value).to_be_bytes().to_vec(),
+            Literal::U16(value) => u64::from(*value).to_be_bytes().to_vec(),
+            Literal::U32(value) => u64::from(*value).to_be_bytes().to_vec(),
+            Literal::U64(value) | Literal::Numeric(value) => value.to_be_bytes().to_vec(),
+            Literal::Boolean(value) => u64::from(*value).to_be_bytes().to_vec(),
+            Literal::B256(bytes) => bytes.to_vec(),
+            Literal::String(span) => {
+                let mut bytes = span.as_str().as_bytes().to_vec();
+                bytes.resize((bytes.len() + 7) / 8 * 8, 0);
+                bytes
+            }
+        }
+    }
+
+    /// Returns whether the literal is a `b256` with the given bytes.
+    ///
+    /// Returns `false` for literals that aren't `b256` values.
+    pub fn b256_eq(&self, bytes: &[u8; 32]) -> bool {
+        matches!(self, Literal::B256(value) if value == bytes)
+    }
+
     pub(crate) fn to_typeinfo(&self) -> TypeInfo {
         match self {
             Literal::String(s) => TypeInfo::Str(Length::new(s.as_str().len(), s.clone())),
@@ -137,3 +315,248 @@ impl Literal {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_bounds_for_each_width() {
+        let widths = [
+            (IntegerBits::Eight, "255"),
+            (IntegerBits::Sixteen, "65535"),
+            (IntegerBits::ThirtyTwo, "4294967295"),
+            (IntegerBits::SixtyFour, "18446744073709551615"),
+        ];
+        for (bits, max) in widths {
+            assert_eq!(Literal::max_for(bits).to_string(), max);
+            assert_eq!(Literal::min_for(bits), BigUint::from(0u8));
+        }
+    }
+
+    #[test]
+    fn checked_constructors_reject_overflow() {
+        assert_eq!(Literal::u8_checked(BigUint::from(0u8)), Ok(Literal::U8(0)));
+        assert_eq!(
+            Literal::u8_checked(BigUint::from(255u8)),
+            Ok(Literal::U8(255))
+        );
+        assert_eq!(
+            Literal::u8_checked(BigUint::from(256u16)),
+            Err(IntErrorKind::PosOverflow)
+        );
+
+        assert_eq!(
+            Literal::u32_checked(BigUint::from(u32::MAX)),
+            Ok(Literal::U32(u32::MAX))
+        );
+        assert_eq!(
+            Literal::u32_checked(BigUint::from(u32::MAX) + 1u8),
+            Err(IntErrorKind::PosOverflow)
+        );
+
+        assert_eq!(
+            Literal::from_biguint_for(Literal::max_for(IntegerBits::Sixteen), IntegerBits::Sixteen),
+            Ok(Literal::U16(u16::MAX))
+        );
+        assert_eq!(
+            Literal::from_biguint_for(
+                Literal::max_for(IntegerBits::SixtyFour) + 1u8,
+                IntegerBits::SixtyFour
+            ),
+            Err(IntErrorKind::PosOverflow)
+        );
+    }
+
+    #[test]
+    fn numeric_converts_to_b256() {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert_eq!(
+            Literal::Numeric(u64::MAX).numeric_to_b256(),
+            Some(Literal::B256(bytes))
+        );
+        assert_eq!(
+            Literal::Numeric(0).numeric_to_b256(),
+            Some(Literal::B256([0; 32]))
+        );
+        assert_eq!(Literal::U64(1).numeric_to_b256(), None);
+        assert_eq!(Literal::Boolean(true).numeric_to_b256(), None);
+
+        let max = (BigUint::from(1u8) << 256u32) - 1u8;
+        assert_eq!(
+            Literal::b256_checked(max.clone()),
+            Ok(Literal::B256([0xff; 32]))
+        );
+        assert_eq!(
+            Literal::b256_checked(max + 1u8),
+            Err(IntErrorKind::PosOverflow)
+        );
+    }
+
+    #[test]
+    fn bitnot_complements_within_width() {
+        assert_eq!(Literal::U8(0).bitnot(), Some(Literal::U8(u8::MAX)));
+        assert_eq!(Literal::U16(0x00ff).bitnot(), Some(Literal::U16(0xff00)));
+        assert_eq!(Literal::U32(u32::MAX).bitnot(), Some(Literal::U32(0)));
+        assert_eq!(Literal::U64(0).bitnot(), Some(Literal::U64(u64::MAX)));
+
+        assert_eq!(Literal::Numeric(0).bitnot(), None);
+        assert_eq!(Literal::Boolean(false).bitnot(), None);
+        assert_eq!(Literal::B256([0; 32]).bitnot(), None);
+    }
+
+    #[test]
+    fn minimal_integer_bits_fits_value() {
+        assert_eq!(
+            Literal::Numeric(200).minimal_integer_bits(),
+            Some(IntegerBits::Eight)
+        );
+        assert_eq!(
+            Literal::Numeric(300).minimal_integer_bits(),
+            Some(IntegerBits::Sixteen)
+        );
+        assert_eq!(
+            Literal::Numeric(u64::from(u32::MAX) + 1).minimal_integer_bits(),
+            Some(IntegerBits::SixtyFour)
+        );
+        assert_eq!(
+            Literal::Numeric(u64::MAX).minimal_integer_bits(),
+            Some(IntegerBits::SixtyFour)
+        );
+        // The width of sized literals is ignored, only their value matters.
+        assert_eq!(
+            Literal::U64(255).minimal_integer_bits(),
+            Some(IntegerBits::Eight)
+        );
+        assert_eq!(
+            Literal::U32(65536).minimal_integer_bits(),
+            Some(IntegerBits::ThirtyTwo)
+        );
+
+        assert_eq!(Literal::Boolean(true).minimal_integer_bits(), None);
+        assert_eq!(Literal::B256([0; 32]).minimal_integer_bits(), None);
+    }
+
+    #[test]
+    fn truncate_to_keeps_low_bits() {
+        assert_eq!(
+            Literal::U64(256).truncate_to(IntegerBits::Eight),
+            Literal::U8(0)
+        );
+        assert_eq!(
+            Literal::U64(0x0102_0304_0506_0708).truncate_to(IntegerBits::Sixteen),
+            Literal::U16(0x0708)
+        );
+        assert_eq!(
+            Literal::Numeric(u64::MAX).truncate_to(IntegerBits::ThirtyTwo),
+            Literal::U32(u32::MAX)
+        );
+        // Values which fit are kept, and narrow values widen to the sized variant.
+        assert_eq!(
+            Literal::U8(255).truncate_to(IntegerBits::Eight),
+            Literal::U8(255)
+        );
+        assert_eq!(
+            Literal::U16(300).truncate_to(IntegerBits::SixtyFour),
+            Literal::U64(300)
+        );
+
+        assert_eq!(
+            Literal::Boolean(true).truncate_to(IntegerBits::Eight),
+            Literal::U8(1)
+        );
+        let mut bytes = [0xff; 32];
+        bytes[31] = 0x2a;
+        assert_eq!(
+            Literal::B256(bytes).truncate_to(IntegerBits::Eight),
+            Literal::U8(0x2a)
+        );
+    }
+
+    #[test]
+    fn to_bytes_matches_abi_encoding() {
+        // The arguments and the encoding of `test_main_encoding_success` in forc-client.
+        let bytes: Vec<u8> = [Literal::U8(2), Literal::Boolean(true)]
+            .iter()
+            .flat_map(Literal::to_bytes)
+            .collect();
+        assert_eq!(bytes, vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        assert_eq!(
+            Literal::U32(0x0102_0304).to_bytes(),
+            vec![0, 0, 0, 0, 1, 2, 3, 4]
+        );
+        assert_eq!(Literal::U64(u64::MAX).to_bytes(), vec![0xff; 8]);
+        assert_eq!(Literal::B256([7; 32]).to_bytes(), vec![7; 32]);
+        let string = Literal::String(span::Span::from_string("fuel".to_string()));
+        assert_eq!(string.to_bytes(), b"fuel\0\0\0\0".to_vec());
+    }
+
+    #[test]
+    fn b256_eq_compares_bytes() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xab;
+        bytes[31] = 0x01;
+        let literal = Literal::B256(bytes);
+        assert!(literal.b256_eq(&bytes));
+
+        let mut other = bytes;
+        other[31] = 0x02;
+        assert!(!literal.b256_eq(&other));
+        assert!(!literal.b256_eq(&[0; 32]));
+
+        // Only `b256` literals are ever equal to bytes.
+        assert!(!Literal::U64(0).b256_eq(&[0; 32]));
+        assert!(!Literal::Boolean(false).b256_eq(&[0; 32]));
+    }
+
+    /// Records the bytes written to it, so the tag written first by a hash can be checked.
+    #[derive(Default)]
+    struct RecordingHasher(Vec<u8>);
+
+    impl Hasher for RecordingHasher {
+        fn finish(&self) -> u64 {
+            0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+    }
+
+    #[test]
+    fn hash_tags_are_stable_and_distinct() {
+        // Adding a variant fails to compile here until it is given a tag.
+        let expected_tag = |literal: &Literal| match literal {
+            Literal::U8(_) => 1,
+            Literal::U16(_) => 2,
+            Literal::U32(_) => 3,
+            Literal::U64(_) => 4,
+            Literal::Numeric(_) => 5,
+            Literal::String(_) => 6,
+            Literal::Boolean(_) => 7,
+            Literal::B256(_) => 8,
+        };
+        let literals = [
+            Literal::U8(0),
+            Literal::U16(0),
+            Literal::U32(0),
+            Literal::U64(0),
+            Literal::Numeric(0),
+            Literal::String(span::Span::from_string(String::new())),
+            Literal::Boolean(false),
+            Literal::B256([0; 32]),
+        ];
+        let mut tags = Vec::new();
+        for literal in &literals {
+            let mut hasher = RecordingHasher::default();
+            literal.hash(&mut hasher);
+            assert_eq!(hasher.0[0], expected_tag(literal), "tag of {literal:?}");
+            tags.push(hasher.0[0]);
+        }
+        tags.sort();
+        tags.dedup();
+        assert_eq!(tags.len(), literals.len());
+    }
+}
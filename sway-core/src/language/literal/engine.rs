@@ -0,0 +1,88 @@
+//! Interning of [Literal]s, so that equal literals share a single allocation.
+
+use super::Literal;
+
+use crate::concurrent_slab::ConcurrentSlab;
+
+use std::{collections::HashMap, sync::RwLock};
+
+/// A stable handle to a [Literal] interned in a [LiteralEngine].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Ord, PartialOrd, Debug)]
+pub struct LiteralId(usize);
+
+impl LiteralId {
+    pub(crate) fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Hands out the same [LiteralId] for every [Literal] that is equal to one inserted before.
+///
+/// Literals are keyed on their type and value, so `U64(5)` and `Numeric(5)` get different IDs.
+/// String literals are keyed on their contents and [LiteralEngine::get] returns the span of the
+/// first occurrence.
+#[derive(Debug, Default)]
+pub struct LiteralEngine {
+    slab: ConcurrentSlab<Literal>,
+    id_map: RwLock<HashMap<Literal, LiteralId>>,
+}
+
+impl Clone for LiteralEngine {
+    fn clone(&self) -> Self {
+        LiteralEngine {
+            slab: self.slab.clone(),
+            id_map: RwLock::new(self.id_map.read().unwrap().clone()),
+        }
+    }
+}
+
+impl LiteralEngine {
+    /// Inserts a [Literal] into the [LiteralEngine] and returns a [LiteralId] referring to it,
+    /// which is the same as the one of any equal literal inserted before.
+    pub fn insert(&self, literal: Literal) -> LiteralId {
+        let mut id_map = self.id_map.write().unwrap();
+        if let Some(id) = id_map.get(&literal) {
+            return *id;
+        }
+        let id = LiteralId(self.slab.insert(literal.clone()));
+        id_map.insert(literal, id);
+        id
+    }
+
+    /// Performs a lookup of `id` into the [LiteralEngine].
+    pub fn get(&self, id: LiteralId) -> Literal {
+        self.slab.get(id.index())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_literals_share_an_id() {
+        let engine = LiteralEngine::default();
+        let five = engine.insert(Literal::U64(5));
+        assert_eq!(engine.insert(Literal::U64(5)), five);
+        assert_eq!(engine.get(five), Literal::U64(5));
+
+        assert_ne!(engine.insert(Literal::U64(6)), five);
+        assert_ne!(engine.insert(Literal::Numeric(5)), five);
+        assert_ne!(engine.insert(Literal::U32(5)), five);
+    }
+
+    #[test]
+    fn string_literals_are_keyed_on_contents() {
+        let engine = LiteralEngine::default();
+        let first = sway_types::Span::from_string("hello".to_string());
+        let second = sway_types::Span::from_string("hello".to_string());
+        let hello = engine.insert(Literal::String(first));
+        assert_eq!(engine.insert(Literal::String(second)), hello);
+        assert_ne!(
+            engine.insert(Literal::String(sway_types::Span::from_string(
+                "world".to_string()
+            ))),
+            hello
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Constant folding of binary operators over integer [Literal]s.
+//!
+//! Every operation takes two literals of the same integer type and produces a literal of that
+//! same type, except for comparisons which produce a [Literal::Boolean]. A [Literal::Numeric]
+//! operand is first cast to the type of a sized operand, as type inference does for numerics.
+//! Overflow and division by zero are reported as [CompileError]s rather than wrapping, mirroring
+//! the checked semantics used by the constant evaluator.
+
+use super::Literal;
+
+use sway_error::error::CompileError;
+use sway_types::Span;
+
+/// Applies a checked method (returning an `Option`) to two literals of the same integer type.
+macro_rules! fold_checked {
+    ($op:literal, $method:ident, $lhs:expr, $rhs:expr, $span:expr) => {
+        match ($lhs, $rhs) {
+            (Literal::U8(l), Literal::U8(r)) => l.$method(*r).map(Literal::U8),
+            (Literal::U16(l), Literal::U16(r)) => l.$method(*r).map(Literal::U16),
+            (Literal::U32(l), Literal::U32(r)) => l.$method(*r).map(Literal::U32),
+            (Literal::U64(l), Literal::U64(r)) => l.$method(*r).map(Literal::U64),
+            (Literal::Numeric(l), Literal::Numeric(r)) => l.$method(*r).map(Literal::Numeric),
+            _ => return Err(operands_mismatch($op, $lhs, $rhs, $span)),
+        }
+    };
+}
+
+/// Applies an infallible method to two literals of the same integer type.
+macro_rules! fold_total {
+    ($op:literal, $method:ident, $lhs:expr, $rhs:expr, $span:expr) => {
+        match ($lhs, $rhs) {
+            (Literal::U8(l), Literal::U8(r)) => Literal::U8(l.$method(*r)),
+            (Literal::U16(l), Literal::U16(r)) => Literal::U16(l.$method(*r)),
+            (Literal::U32(l), Literal::U32(r)) => Literal::U32(l.$method(*r)),
+            (Literal::U64(l), Literal::U64(r)) => Literal::U64(l.$method(*r)),
+            (Literal::Numeric(l), Literal::Numeric(r)) => Literal::Numeric(l.$method(*r)),
+            _ => return Err(operands_mismatch($op, $lhs, $rhs, $span)),
+        }
+    };
+}
+
+/// Compares two literals of the same integer type with a [PartialOrd] or [PartialEq] method.
+macro_rules! fold_compare {
+    ($op:literal, $method:ident, $lhs:expr, $rhs:expr, $span:expr) => {
+        match ($lhs, $rhs) {
+            (Literal::U8(l), Literal::U8(r)) => Literal::Boolean(l.$method(r)),
+            (Literal::U16(l), Literal::U16(r)) => Literal::Boolean(l.$method(r)),
+            (Literal::U32(l), Literal::U32(r)) => Literal::Boolean(l.$method(r)),
+            (Literal::U64(l), Literal::U64(r)) => Literal::Boolean(l.$method(r)),
+            (Literal::Numeric(l), Literal::Numeric(r)) => Literal::Boolean(l.$method(r)),
+            _ => return Err(operands_mismatch($op, $lhs, $rhs, $span)),
+        }
+    };
+}
+
+/// Shifts an integer literal by an amount given as an integer literal of any width.
+macro_rules! fold_shift {
+    ($op:literal, $method:ident, $lhs:expr, $rhs:expr, $span:expr) => {{
+        let Some(amount) = integer_value($rhs).and_then(|amount| u32::try_from(amount).ok()) else {
+            return Err(operands_mismatch($op, $lhs, $rhs, $span));
+        };
+        match $lhs {
+            Literal::U8(l) => l.$method(amount).map(Literal::U8),
+            Literal::U16(l) => l.$method(amount).map(Literal::U16),
+            Literal::U32(l) => l.$method(amount).map(Literal::U32),
+            Literal::U64(l) => l.$method(amount).map(Literal::U64),
+            Literal::Numeric(l) => l.$method(amount).map(Literal::Numeric),
+            _ => return Err(operands_mismatch($op, $lhs, $rhs, $span)),
+        }
+    }};
+}
+
+pub fn add(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    fold_checked!("+", checked_add, lhs, rhs, span).ok_or_else(|| overflow(lhs, span))
+}
+
+pub fn sub(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    fold_checked!("-", checked_sub, lhs, rhs, span).ok_or_else(|| overflow(lhs, span))
+}
+
+pub fn mul(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    fold_checked!("*", checked_mul, lhs, rhs, span).ok_or_else(|| overflow(lhs, span))
+}
+
+pub fn div(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    // A `None` from a checked division can only mean a zero divisor for unsigned integers.
+    fold_checked!("/", checked_div, lhs, rhs, span)
+        .ok_or_else(|| CompileError::DivisionByZero { span: span.clone() })
+}
+
+pub fn rem(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    fold_checked!("%", checked_rem, lhs, rhs, span)
+        .ok_or_else(|| CompileError::DivisionByZero { span: span.clone() })
+}
+
+pub fn bitand(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    use std::ops::BitAnd;
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_total!("&", bitand, lhs, rhs, span))
+}
+
+pub fn bitor(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    use std::ops::BitOr;
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_total!("|", bitor, lhs, rhs, span))
+}
+
+pub fn bitxor(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    use std::ops::BitXor;
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_total!("^", bitxor, lhs, rhs, span))
+}
+
+/// Shifts `lhs` left by `rhs` bits. The shift amount may be an integer literal of any width.
+/// Shifting set bits out of `lhs`, like multiplying by a power of two past its maximum, is
+/// reported as an overflow, as is shifting by the bit width of `lhs` or more.
+pub fn shl(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let shifted =
+        fold_shift!("<<", checked_shl, lhs, rhs, span).ok_or_else(|| overflow(lhs, span))?;
+    // No bits were lost if shifting back restores `lhs`.
+    if fold_shift!(">>", checked_shr, &shifted, rhs, span).as_ref() != Some(lhs) {
+        return Err(overflow(lhs, span));
+    }
+    Ok(shifted)
+}
+
+/// Shifts `lhs` right by `rhs` bits. The shift amount may be an integer literal of any width,
+/// and shifting by the bit width of `lhs` or more is reported as an overflow.
+pub fn shr(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    fold_shift!(">>", checked_shr, lhs, rhs, span).ok_or_else(|| overflow(lhs, span))
+}
+
+pub fn lt(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_compare!("<", lt, lhs, rhs, span))
+}
+
+pub fn le(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_compare!("<=", le, lhs, rhs, span))
+}
+
+pub fn gt(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_compare!(">", gt, lhs, rhs, span))
+}
+
+pub fn ge(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_compare!(">=", ge, lhs, rhs, span))
+}
+
+pub fn eq(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_compare!("==", eq, lhs, rhs, span))
+}
+
+pub fn ne(lhs: &Literal, rhs: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let (lhs, rhs) = &coerce_numeric(lhs, rhs, span)?;
+    Ok(fold_compare!("!=", ne, lhs, rhs, span))
+}
+
+/// Casts a [Literal::Numeric] operand to the type of the other operand if that one is a sized
+/// integer, leaving the operands as they are otherwise.
+fn coerce_numeric(
+    lhs: &Literal,
+    rhs: &Literal,
+    span: &Span,
+) -> Result<(Literal, Literal), CompileError> {
+    match (lhs, rhs) {
+        (Literal::Numeric(value), sized) if is_sized_integer(sized) => {
+            Ok((cast(*value, sized, span)?, sized.clone()))
+        }
+        (sized, Literal::Numeric(value)) if is_sized_integer(sized) => {
+            Ok((sized.clone(), cast(*value, sized, span)?))
+        }
+        _ => Ok((lhs.clone(), rhs.clone())),
+    }
+}
+
+fn is_sized_integer(literal: &Literal) -> bool {
+    matches!(
+        literal,
+        Literal::U8(_) | Literal::U16(_) | Literal::U32(_) | Literal::U64(_)
+    )
+}
+
+/// Casts the value of a numeric literal to the sized integer type of `ty`, reporting an
+/// overflow if it doesn't fit.
+fn cast(value: u64, ty: &Literal, span: &Span) -> Result<Literal, CompileError> {
+    let literal = match ty {
+        Literal::U8(_) => u8::try_from(value).ok().map(Literal::U8),
+        Literal::U16(_) => u16::try_from(value).ok().map(Literal::U16),
+        Literal::U32(_) => u32::try_from(value).ok().map(Literal::U32),
+        _ => Some(Literal::U64(value)),
+    };
+    literal.ok_or_else(|| overflow(ty, span))
+}
+
+fn integer_value(literal: &Literal) -> Option<u64> {
+    match literal {
+        Literal::U8(x) => Some(u64::from(*x)),
+        Literal::U16(x) => Some(u64::from(*x)),
+        Literal::U32(x) => Some(u64::from(*x)),
+        Literal::U64(x) | Literal::Numeric(x) => Some(*x),
+        Literal::String(_) | Literal::Boolean(_) | Literal::B256(_) => None,
+    }
+}
+
+fn type_name(literal: &Literal) -> &'static str {
+    match literal {
+        Literal::U8(_) => "u8",
+        Literal::U16(_) => "u16",
+        Literal::U32(_) => "u32",
+        Literal::U64(_) => "u64",
+        Literal::Numeric(_) => "numeric",
+        Literal::String(_) => "str",
+        Literal::Boolean(_) => "bool",
+        Literal::B256(_) => "b256",
+    }
+}
+
+fn overflow(literal: &Literal, span: &Span) -> CompileError {
+    CompileError::ArithmeticOverflow {
+        ty: type_name(literal).to_string(),
+        span: span.clone(),
+    }
+}
+
+fn operands_mismatch(op: &'static str, lhs: &Literal, rhs: &Literal, span: &Span) -> CompileError {
+    CompileError::LiteralOperandsMismatch {
+        op,
+        lhs: type_name(lhs).to_string(),
+        rhs: type_name(rhs).to_string(),
+        span: span.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type FoldFn = fn(&Literal, &Literal, &Span) -> Result<Literal, CompileError>;
+    type LiteralCtor = fn(u64) -> Literal;
+
+    fn fold(f: FoldFn, lhs: Literal, rhs: Literal) -> Result<Literal, CompileError> {
+        f(&lhs, &rhs, &Span::dummy())
+    }
+
+    /// The largest value of every integer width, paired with a constructor for that width.
+    fn widths() -> Vec<(u64, LiteralCtor)> {
+        vec![
+            (u8::MAX.into(), |x| Literal::U8(x as u8)),
+            (u16::MAX.into(), |x| Literal::U16(x as u16)),
+            (u32::MAX.into(), |x| Literal::U32(x as u32)),
+            (u64::MAX, Literal::U64),
+            (u64::MAX, Literal::Numeric),
+        ]
+    }
+
+    #[test]
+    fn fold_in_range_all_widths() {
+        for (_, lit) in widths() {
+            assert_eq!(fold(add, lit(6), lit(3)).unwrap(), lit(9));
+            assert_eq!(fold(sub, lit(6), lit(3)).unwrap(), lit(3));
+            assert_eq!(fold(mul, lit(6), lit(3)).unwrap(), lit(18));
+            assert_eq!(fold(div, lit(6), lit(3)).unwrap(), lit(2));
+            assert_eq!(fold(rem, lit(7), lit(3)).unwrap(), lit(1));
+            assert_eq!(fold(bitand, lit(6), lit(3)).unwrap(), lit(2));
+            assert_eq!(fold(bitor, lit(6), lit(3)).unwrap(), lit(7));
+            assert_eq!(fold(bitxor, lit(6), lit(3)).unwrap(), lit(5));
+            assert_eq!(fold(shl, lit(6), lit(3)).unwrap(), lit(48));
+            assert_eq!(fold(shr, lit(48), lit(3)).unwrap(), lit(6));
+        }
+    }
+
+    #[test]
+    fn fold_at_bounds_all_widths() {
+        for (max, lit) in widths() {
+            assert_eq!(fold(add, lit(max - 1), lit(1)).unwrap(), lit(max));
+            assert_eq!(fold(sub, lit(max), lit(max)).unwrap(), lit(0));
+            assert_eq!(fold(mul, lit(max), lit(1)).unwrap(), lit(max));
+            assert_eq!(fold(bitxor, lit(max), lit(max)).unwrap(), lit(0));
+        }
+    }
+
+    #[test]
+    fn fold_overflow_all_widths() {
+        for (max, lit) in widths() {
+            for (f, lhs, rhs) in [
+                (add as FoldFn, max, 1),
+                (sub, 0, 1),
+                (mul, max, 2),
+                (shl, max, 1),
+                (shl, 1, 64),
+                (shr, 1, 64),
+            ] {
+                assert!(matches!(
+                    fold(f, lit(lhs), lit(rhs)),
+                    Err(CompileError::ArithmeticOverflow { .. })
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn fold_division_by_zero_all_widths() {
+        for (max, lit) in widths() {
+            for f in [div as FoldFn, rem] {
+                assert!(matches!(
+                    fold(f, lit(max), lit(0)),
+                    Err(CompileError::DivisionByZero { .. })
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn fold_shift_amount_of_any_width() {
+        assert_eq!(
+            fold(shl, Literal::U8(1), Literal::U64(7)).unwrap(),
+            Literal::U8(128)
+        );
+        assert_eq!(
+            fold(shr, Literal::U64(256), Literal::U8(8)).unwrap(),
+            Literal::U64(1)
+        );
+        assert!(matches!(
+            fold(shl, Literal::U8(1), Literal::U8(8)),
+            Err(CompileError::ArithmeticOverflow { .. })
+        ));
+        assert!(matches!(
+            fold(shl, Literal::U8(255), Literal::U64(1)),
+            Err(CompileError::ArithmeticOverflow { .. })
+        ));
+    }
+
+    #[test]
+    fn fold_comparisons_all_widths() {
+        let (t, f) = (Literal::Boolean(true), Literal::Boolean(false));
+        for (max, lit) in widths() {
+            for (cmp, lhs, rhs, expected) in [
+                (lt as FoldFn, 1, 2, &t),
+                (lt, 2, 2, &f),
+                (le, 2, 2, &t),
+                (le, max, 2, &f),
+                (gt, max, 2, &t),
+                (gt, 2, 2, &f),
+                (ge, 2, 2, &t),
+                (ge, 1, 2, &f),
+                (eq, max, max, &t),
+                (eq, 1, 2, &f),
+                (ne, 1, 2, &t),
+                (ne, max, max, &f),
+            ] {
+                assert_eq!(&fold(cmp, lit(lhs), lit(rhs)).unwrap(), expected);
+            }
+        }
+        assert_eq!(fold(lt, Literal::U64(1), Literal::U64(2)).unwrap(), t);
+        assert_eq!(
+            fold(ge, Literal::Numeric(300), Literal::U16(299)).unwrap(),
+            t
+        );
+    }
+
+    #[test]
+    fn fold_comparison_operands_mismatch() {
+        let err = fold(lt, Literal::U64(1), Literal::U8(2)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Operator \"<\" cannot be applied to literals of type u64 and u8."
+        );
+        assert!(matches!(
+            fold(eq, Literal::Boolean(true), Literal::Boolean(true)),
+            Err(CompileError::LiteralOperandsMismatch { op: "==", .. })
+        ));
+    }
+
+    #[test]
+    fn fold_numeric_with_sized_integer() {
+        assert_eq!(
+            fold(add, Literal::Numeric(5), Literal::U64(10)).unwrap(),
+            Literal::U64(15)
+        );
+        assert_eq!(
+            fold(sub, Literal::U8(10), Literal::Numeric(4)).unwrap(),
+            Literal::U8(6)
+        );
+        let err = fold(add, Literal::Numeric(u64::MAX), Literal::U8(1)).unwrap_err();
+        assert_eq!(err.to_string(), "Arithmetic operation overflows type u8.");
+    }
+
+    #[test]
+    fn fold_operands_mismatch() {
+        let err = fold(add, Literal::U8(1), Literal::U16(1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Operator \"+\" cannot be applied to literals of type u8 and u16."
+        );
+        assert!(matches!(
+            fold(bitand, Literal::Boolean(true), Literal::Boolean(false)),
+            Err(CompileError::LiteralOperandsMismatch { op: "&", .. })
+        ));
+        assert!(matches!(
+            fold(shl, Literal::U64(1), Literal::Boolean(true)),
+            Err(CompileError::LiteralOperandsMismatch { op: "<<", .. })
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use super::{lexed::LexedProgram, parsed::ParseProgram, ty::TyProgram};
 
 /// Contains the lexed, parsed, and typed compilation stages of a program.
+#[derive(Clone, Debug)]
 pub struct Programs {
     pub lexed: LexedProgram,
     pub parsed: ParseProgram,
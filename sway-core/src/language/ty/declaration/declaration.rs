@@ -859,7 +859,7 @@ impl TyDecl {
         ok(type_id, warnings, errors)
     }
 
-    pub(crate) fn visibility(&self, decl_engine: &DeclEngine) -> Visibility {
+    pub fn visibility(&self, decl_engine: &DeclEngine) -> Visibility {
         match self {
             TyDecl::TraitDecl(TraitDecl { decl_id, .. }) => {
                 let TyTraitDecl { visibility, .. } = decl_engine.get_trait(decl_id);
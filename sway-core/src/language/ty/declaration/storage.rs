@@ -4,8 +4,8 @@ use sway_error::error::CompileError;
 use sway_types::{state::StateIndex, Ident, Named, Span, Spanned};
 
 use crate::{
-    decl_engine::DeclEngine, engine_threading::*, error::*, language::ty::*, transform,
-    type_system::*,
+    decl_engine::DeclEngine, engine_threading::*, error::*, fuel_prelude::fuel_types::Bytes32,
+    ir_generation::storage::get_storage_key, language::ty::*, transform, type_system::*,
 };
 
 #[derive(Clone, Debug)]
@@ -145,6 +145,15 @@ impl TyStorageDecl {
         )
     }
 
+    /// Returns the index of the field named `name` and the key of the first storage slot the
+    /// field is stored in, or `None` if there is no such field.
+    pub fn field_storage_key(&self, name: &Ident) -> Option<(StateIndex, Bytes32)> {
+        let ix = self.fields.iter().position(|field| &field.name == name)?;
+        let ix = StateIndex::new(ix);
+        let key = get_storage_key::<u64>(&ix, &[]);
+        Some((ix, key))
+    }
+
     pub(crate) fn fields_as_typed_struct_fields(&self) -> Vec<TyStructField> {
         self.fields
             .iter()
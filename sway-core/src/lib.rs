@@ -26,7 +26,7 @@ use crate::{error::*, source_map::SourceMap};
 pub use asm_generation::from_ir::compile_ir_to_asm;
 use asm_generation::FinalizedAsm;
 pub use asm_generation::{CompiledBytecode, FinalizedEntry};
-pub use build_config::{BuildConfig, BuildTarget};
+pub use build_config::{BuildConfig, BuildTarget, GasProfileFormat};
 use control_flow_analysis::ControlFlowGraph;
 use metadata::MetadataManager;
 use std::collections::HashMap;
@@ -36,8 +36,8 @@ use sway_ast::AttributeDecl;
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_ir::{
     create_o1_pass_group, register_known_passes, Context, Kind, Module, PassManager,
-    ARGDEMOTION_NAME, CONSTDEMOTION_NAME, DCE_NAME, MEMCPYOPT_NAME, MISCDEMOTION_NAME,
-    MODULEPRINTER_NAME, RETDEMOTION_NAME,
+    ARGDEMOTION_NAME, CONSTDEMOTION_NAME, CSE_NAME, DCE_NAME, LICM_NAME, MEMCPYOPT_NAME,
+    MISCDEMOTION_NAME, MODULEPRINTER_NAME, RETDEMOTION_NAME, STORAGE_ACCESS_NAME,
 };
 use sway_types::constants::DOC_COMMENT_ATTRIBUTE_NAME;
 use sway_types::SourceEngine;
@@ -605,8 +605,17 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     // Initialize the pass manager and register known passes.
     let mut pass_mgr = PassManager::default();
     register_known_passes(&mut pass_mgr);
+    pass_mgr.set_verify_between_passes(build_config.verify_ir);
     let mut pass_group = create_o1_pass_group();
 
+    // Release builds additionally hoist loop-invariant code and eliminate redundant computations
+    // and storage reads.
+    if build_config.optimize {
+        pass_group.append_pass(LICM_NAME);
+        pass_group.append_pass(STORAGE_ACCESS_NAME);
+        pass_group.append_pass(CSE_NAME);
+    }
+
     // Target specific transforms should be moved into something more configured.
     if build_config.build_target == BuildTarget::Fuel {
         // FuelVM target specific transforms.
@@ -646,6 +655,14 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     });
     check!(res, return err(warnings, errors), warnings, errors);
 
+    // Report which calls were inlined and why, along with the IR.
+    if build_config.print_ir && !ir.inline_decisions().is_empty() {
+        println!("// --- INLINING DECISIONS ---\n");
+        for decision in ir.inline_decisions() {
+            println!("// {decision}");
+        }
+    }
+
     let final_asm = check!(
         compile_ir_to_asm(&ir, Some(build_config)),
         return err(warnings, errors),
@@ -41,11 +41,53 @@ pub(crate) enum StorageOperation {
 }
 
 impl MetadataManager {
+    /// The span of the metadata, or for inlined code without a span of its own, the span of the
+    /// call it was inlined from.
     pub(crate) fn md_to_span(
         &mut self,
         context: &Context,
         md_idx: Option<MetadataIndex>,
     ) -> Option<Span> {
+        self.md_to_own_span(context, md_idx).or_else(|| {
+            let call_site_md_idx = Self::md_to_inlined_call_sites(context, md_idx)
+                .into_iter()
+                .next()?;
+            self.md_to_span(context, Some(call_site_md_idx))
+        })
+    }
+
+    /// The spans of the calls the metadata was inlined from, innermost first.
+    pub(crate) fn md_to_inlined_at(
+        &mut self,
+        context: &Context,
+        md_idx: Option<MetadataIndex>,
+    ) -> Vec<Span> {
+        let mut spans = Vec::new();
+        for call_site_md_idx in Self::md_to_inlined_call_sites(context, md_idx) {
+            spans.extend(self.md_to_own_span(context, Some(call_site_md_idx)));
+            spans.extend(self.md_to_inlined_at(context, Some(call_site_md_idx)));
+        }
+        spans
+    }
+
+    fn md_to_inlined_call_sites(
+        context: &Context,
+        md_idx: Option<MetadataIndex>,
+    ) -> Vec<MetadataIndex> {
+        let mut call_sites = Vec::new();
+        Self::for_each_md_idx(context, md_idx, |md_idx| {
+            call_sites.extend(
+                md_idx
+                    .get_content(context)
+                    .unwrap_struct("inlined", 1)
+                    .and_then(|fields| fields[0].unwrap_index()),
+            );
+            None::<()>
+        });
+        call_sites
+    }
+
+    fn md_to_own_span(&mut self, context: &Context, md_idx: Option<MetadataIndex>) -> Option<Span> {
         Self::for_each_md_idx(context, md_idx, |md_idx| {
             self.md_span_cache.get(&md_idx).cloned().or_else(|| {
                 // Create a new span and save it in the cache.
@@ -208,6 +250,10 @@ impl MetadataManager {
         self.md_to_span(context, value.get_metadata(context))
     }
 
+    pub(crate) fn val_to_inlined_at(&mut self, context: &Context, value: Value) -> Vec<Span> {
+        self.md_to_inlined_at(context, value.get_metadata(context))
+    }
+
     pub(crate) fn span_to_md(
         &mut self,
         context: &mut Context,
@@ -1,6 +1,7 @@
 use sway_types::Spanned;
 
 use crate::{
+    engine_threading::*,
     error::*,
     language::{parsed, ty, Visibility},
     semantic_analysis::{Mode, TypeCheckContext},
@@ -88,4 +89,17 @@ impl ty::TyTraitFn {
             where_clause: vec![],
         }
     }
+
+    /// Returns the name the selector of this function is derived from when it is part of an ABI,
+    /// e.g. `transfer(u64,b256)`.
+    pub fn to_selector_name(&self, engines: &Engines) -> CompileResult<String> {
+        self.to_dummy_func(Mode::ImplAbiFn)
+            .to_selector_name(engines)
+    }
+
+    /// Returns the selector of this function when it is part of an ABI.
+    pub fn to_fn_selector_value(&self, engines: &Engines) -> CompileResult<[u8; 4]> {
+        self.to_dummy_func(Mode::ImplAbiFn)
+            .to_fn_selector_value(engines)
+    }
 }
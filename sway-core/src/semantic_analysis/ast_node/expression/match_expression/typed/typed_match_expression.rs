@@ -1,4 +1,5 @@
-use sway_types::Span;
+use sway_ast::Intrinsic;
+use sway_types::{integer_bits::IntegerBits, Span};
 
 use crate::{
     error::{err, ok},
@@ -184,38 +185,36 @@ impl ty::TyMatchExpression {
                 // "None". This is because the user has not provided any
                 // branches in the match expression because the type cannot be
                 // constructed or matched upon. In this case, we manually create
-                // a typed expression that is equivalent to
-                // "if true { implicit_return }" where the implicit_return type is manually set
-                // to be the return type of this typed match expression object.
+                // a typed expression that is equivalent to "__revert(0)" where
+                // the type is manually set to be the return type of this typed
+                // match expression object.
                 //
-                // NOTE: This manual construction of the expression can (and
-                // most likely will) lead to an otherwise improperly typed
-                // expression, in most cases.
+                // The match can never be reached, as no value of the matched type exists.
+                // Reverting, rather than producing a unit value, keeps the IR well typed
+                // whatever the return type is.
                 if !type_engine
                     .get(self.value_type_id)
                     .has_valid_constructor(decl_engine)
                 {
-                    let condition = ty::TyExpression {
-                        expression: ty::TyExpressionVariant::Literal(Literal::Boolean(true)),
-                        return_type: type_engine.insert(engines, TypeInfo::Boolean),
+                    let revert_code = ty::TyExpression {
+                        expression: ty::TyExpressionVariant::Literal(Literal::U64(0)),
+                        return_type: type_engine
+                            .insert(engines, TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)),
                         span: self.span.clone(),
                     };
-                    let then_exp = ty::TyExpression {
-                        expression: ty::TyExpressionVariant::Tuple { fields: vec![] },
-                        return_type: self.return_type_id,
-                        span: self.span.clone(),
-                    };
-                    let inner_exp = ty::TyExpressionVariant::IfExp {
-                        condition: Box::new(condition),
-                        then: Box::new(then_exp.clone()),
-                        r#else: Option::Some(Box::new(then_exp)),
-                    };
-                    let typed_if_exp = ty::TyExpression {
-                        expression: inner_exp,
+                    let typed_revert_exp = ty::TyExpression {
+                        expression: ty::TyExpressionVariant::IntrinsicFunction(
+                            ty::TyIntrinsicFunctionKind {
+                                kind: Intrinsic::Revert,
+                                arguments: vec![revert_code],
+                                type_arguments: vec![],
+                                span: self.span.clone(),
+                            },
+                        ),
                         return_type: self.return_type_id,
                         span: self.span,
                     };
-                    return ok(typed_if_exp, warnings, errors);
+                    return ok(typed_revert_exp, warnings, errors);
                 }
 
                 errors.push(CompileError::Internal(
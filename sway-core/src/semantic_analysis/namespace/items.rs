@@ -95,6 +95,23 @@ impl Items {
         self.symbols().keys()
     }
 
+    /// The path of the item that `symbol` was imported as, relative to the root of the namespace,
+    /// if it was brought into scope by a `use` statement.
+    ///
+    /// For example, in `use ::foo::bar::Baz as Qux;`, the path of the symbol `Qux` is
+    /// `foo::bar::Baz`.
+    pub fn use_synonym_path(&self, symbol: &Ident) -> Option<Vec<Ident>> {
+        let (mod_path, ..) = self.use_synonyms.get(symbol)?;
+        let name = self.use_aliases.get(symbol.as_str()).unwrap_or(symbol);
+        Some(
+            mod_path
+                .iter()
+                .chain(std::iter::once(name))
+                .cloned()
+                .collect(),
+        )
+    }
+
     pub(crate) fn insert_symbol(&mut self, name: Ident, item: ty::TyDecl) -> CompileResult<()> {
         let mut errors = vec![];
 
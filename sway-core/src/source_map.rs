@@ -1,5 +1,5 @@
 use dirs::home_dir;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use sway_types::SourceEngine;
 
@@ -12,85 +12,353 @@ use sway_types::span::Span;
 #[serde(transparent)]
 pub struct PathIndex(usize);
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// A mapping from instruction offsets to the source locations they were compiled from.
+///
+/// The map is serialized to JSON next to the bytecode as `<package>-source-map.json`.  Paths to
+/// the package's own files are relative to the package root, and paths to dependencies in the
+/// `~/.forc` directory are relative to that directory, so the map doesn't depend on where the
+/// package was built.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceMap {
+    /// The version of the source map format, [SourceMap::VERSION] when written by this compiler.
+    pub version: u32,
     /// Paths of dependencies in the `~/.forc` directory, with the prefix stripped.
     /// This makes inverse source mapping work on any machine with deps downloaded.
     dependency_paths: Vec<PathBuf>,
     /// Paths to source code files, defined separately to avoid repetition.
     paths: Vec<PathBuf>,
-    /// Mapping from opcode index to source location
-    map: HashMap<usize, SourceMapSpan>,
+    /// Mapping from instruction offset to source location
+    map: BTreeMap<usize, SourceMapSpan>,
+    /// The root of the package, which paths are made relative to.
+    #[serde(skip)]
+    root: Option<PathBuf>,
+    /// Locations already computed for spans, as finding their lines and columns means scanning
+    /// the source.
+    #[serde(skip)]
+    locations: HashMap<Span, SourceMapLocation>,
 }
+
+impl Default for SourceMap {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            dependency_paths: Vec::new(),
+            paths: Vec::new(),
+            map: BTreeMap::new(),
+            root: None,
+            locations: HashMap::new(),
+        }
+    }
+}
+
 impl SourceMap {
+    /// The version of the source map format.  Bumped on any change to its serialized form.
+    pub const VERSION: u32 = 1;
+
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// A source map whose paths are relative to the package at `root`.
+    pub fn for_package<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: Some(root.as_ref().to_owned()),
+            ..Self::default()
+        }
+    }
+
     /// Inserts dependency path. Unsupported locations are ignored for now.
     pub fn insert_dependency<P: AsRef<Path>>(&mut self, path: P) {
-        if let Some(home) = home_dir() {
-            let forc = home.join(".forc/");
-            if let Ok(unprefixed) = path.as_ref().strip_prefix(forc) {
-                self.dependency_paths.push(unprefixed.to_owned());
+        if let Some(unprefixed) = strip_forc_home(path.as_ref()) {
+            if !self.dependency_paths.contains(&unprefixed) {
+                self.dependency_paths.push(unprefixed);
             }
         }
         // TODO: Only dependencies in ~/.forc are supported for now
     }
 
-    pub fn insert(&mut self, source_engine: &SourceEngine, pc: usize, span: &Span) {
-        if let Some(source_id) = span.source_id() {
-            let path = source_engine.get_path(source_id);
-            let path_index = self
-                .paths
+    /// Maps the instruction at offset `pc` to `span`, with `inlined_at` the spans of the calls it
+    /// was inlined from, innermost first.
+    pub fn insert(
+        &mut self,
+        source_engine: &SourceEngine,
+        pc: usize,
+        span: &Span,
+        inlined_at: &[Span],
+    ) {
+        if let Some(location) = self.location_of(source_engine, span) {
+            let inlined_at = inlined_at
                 .iter()
-                .position(|p| *p == *path)
-                .unwrap_or_else(|| {
-                    self.paths.push((*path).to_owned());
-                    self.paths.len() - 1
-                });
+                .filter_map(|span| self.location_of(source_engine, span))
+                .collect();
             self.map.insert(
                 pc,
                 SourceMapSpan {
-                    path: PathIndex(path_index),
-                    range: LocationRange {
-                        start: span.start(),
-                        end: span.end(),
-                    },
+                    location,
+                    inlined_at,
                 },
             );
         }
     }
 
+    /// The source location of the instruction at offset `pc`.
+    pub fn location(&self, pc: usize) -> Option<SourceLocation> {
+        self.map
+            .get(&pc)
+            .map(|sms| self.resolve_location(&sms.location))
+    }
+
+    /// The source locations of the calls the instruction at offset `pc` was inlined from,
+    /// innermost first.  Empty if the instruction wasn't inlined.
+    pub fn inlined_at(&self, pc: usize) -> Vec<SourceLocation> {
+        self.map
+            .get(&pc)
+            .map(|sms| {
+                sms.inlined_at
+                    .iter()
+                    .map(|location| self.resolve_location(location))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Where a breakpoint on `line` of the file at `path` stops, with `path` as given by
+    /// [SourceMap::location].
+    ///
+    /// A breakpoint on a line without instructions moves to the next line which has some.  It
+    /// stops at the first instruction of each run of instructions mapped to the line, so that it's
+    /// hit once each time the line runs.
+    pub fn breakpoint(&self, path: &Path, line: usize) -> Option<Breakpoint> {
+        let path_index = (0..self.paths.len())
+            .map(PathIndex)
+            .find(|index| self.resolve_path(*index) == path)?;
+        let line = self
+            .map
+            .values()
+            .filter(|sms| sms.location.path == path_index)
+            .map(|sms| sms.location.start.line)
+            .filter(|mapped_line| *mapped_line >= line)
+            .min()?;
+        let is_on_line = |pc: usize| {
+            self.map.get(&pc).map_or(false, |sms| {
+                sms.location.path == path_index && sms.location.start.line == line
+            })
+        };
+        let offsets = self
+            .map
+            .keys()
+            .copied()
+            .filter(|pc| is_on_line(*pc) && (*pc == 0 || !is_on_line(pc - 1)))
+            .collect();
+        Some(Breakpoint { line, offsets })
+    }
+
     /// Inverse source mapping
     pub fn addr_to_span(&self, pc: usize) -> Option<(PathBuf, LocationRange)> {
-        self.map.get(&pc).map(|sms| {
-            let p = &self.paths[sms.path.0];
-            for dep in &self.dependency_paths {
-                if p.starts_with(dep.file_name().unwrap()) {
-                    let mut path = home_dir().expect("Could not get homedir").join(".forc");
-
-                    if let Some(dp) = dep.parent() {
-                        path = path.join(dp);
-                    }
+        self.location(pc)
+            .map(|location| (location.path, location.range))
+    }
+
+    fn location_of(
+        &mut self,
+        source_engine: &SourceEngine,
+        span: &Span,
+    ) -> Option<SourceMapLocation> {
+        if let Some(location) = self.locations.get(span) {
+            return Some(location.clone());
+        }
+        let source_id = span.source_id()?;
+        let path = self.relative_path(&source_engine.get_path(source_id));
+        let path_index = self
+            .paths
+            .iter()
+            .position(|p| *p == path)
+            .unwrap_or_else(|| {
+                self.paths.push(path);
+                self.paths.len() - 1
+            });
+        let (start, end) = span.line_col();
+        let location = SourceMapLocation {
+            path: PathIndex(path_index),
+            range: LocationRange {
+                start: span.start(),
+                end: span.end(),
+            },
+            start: LineCol {
+                line: start.line,
+                col: start.col,
+            },
+            end: LineCol {
+                line: end.line,
+                col: end.col,
+            },
+        };
+        self.locations.insert(span.clone(), location.clone());
+        Some(location)
+    }
 
-                    return (path.join(p), sms.range);
+    /// The path to store for `path`: relative to the package root if it's within the package, or
+    /// relative to `~/.forc` if it's a dependency there.
+    fn relative_path(&mut self, path: &Path) -> PathBuf {
+        if let Some(relative) = self
+            .root
+            .as_ref()
+            .and_then(|root| path.strip_prefix(root).ok())
+        {
+            return relative.to_owned();
+        }
+        match strip_forc_home(path) {
+            Some(unprefixed) => {
+                if let Some(dir) = unprefixed.parent() {
+                    if !self.dependency_paths.iter().any(|dep| dir.starts_with(dep)) {
+                        self.dependency_paths.push(dir.to_owned());
+                    }
                 }
+                unprefixed
             }
+            None => path.to_owned(),
+        }
+    }
+
+    fn resolve_path(&self, index: PathIndex) -> PathBuf {
+        let p = &self.paths[index.0];
+        if self.dependency_paths.iter().any(|dep| p.starts_with(dep)) {
+            home_dir()
+                .expect("Could not get homedir")
+                .join(".forc")
+                .join(p)
+        } else {
+            p.to_owned()
+        }
+    }
 
-            (p.to_owned(), sms.range)
-        })
+    fn resolve_location(&self, location: &SourceMapLocation) -> SourceLocation {
+        SourceLocation {
+            path: self.resolve_path(location.path),
+            range: location.range,
+            start: location.start,
+            end: location.end,
+        }
     }
 }
 
+/// `path` relative to the `~/.forc` directory, if it's within it.
+fn strip_forc_home(path: &Path) -> Option<PathBuf> {
+    let forc = home_dir()?.join(".forc/");
+    path.strip_prefix(forc).ok().map(Path::to_owned)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceMapSpan {
+    #[serde(flatten)]
+    pub location: SourceMapLocation,
+    /// The locations of the calls the instruction was inlined from, innermost first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inlined_at: Vec<SourceMapLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceMapLocation {
     pub path: PathIndex,
     pub range: LocationRange,
+    pub start: LineCol,
+    pub end: LineCol,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Where a breakpoint stops, see [SourceMap::breakpoint].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// The line the breakpoint was moved to, if the requested line has no instructions.
+    pub line: usize,
+    /// The offsets of the instructions the breakpoint stops at.
+    pub offsets: Vec<usize>,
+}
+
+/// A location in a source file, with its path resolved.
+///
+/// Paths within the package are relative to the package root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    pub range: LocationRange,
+    pub start: LineCol,
+    pub end: LineCol,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LocationRange {
     pub start: usize,
     pub end: usize,
 }
+
+/// A line and column, both starting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn round_trips_through_json() {
+        let source_engine = SourceEngine::default();
+        let root = PathBuf::from("/pkg");
+        let path = root.join("src/main.sw");
+        let source_id = source_engine.get_source_id(&path);
+        let src: Arc<str> = "fn f() {\n    g();\n}\nfn g() {\n    revert(0);\n}\n".into();
+        let span = |start, end| Span::new(src.clone(), start, end, Some(source_id)).unwrap();
+        let call = span(13, 16);
+        let revert = span(33, 42);
+
+        let mut source_map = SourceMap::for_package(&root);
+        source_map.insert(&source_engine, 4, &call, &[]);
+        source_map.insert(&source_engine, 5, &revert, std::slice::from_ref(&call));
+
+        let json = serde_json::to_string(&source_map).unwrap();
+        let source_map: SourceMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(source_map.version, SourceMap::VERSION);
+
+        let location = source_map.location(5).unwrap();
+        assert_eq!(location.path, PathBuf::from("src/main.sw"));
+        assert_eq!(location.range, LocationRange { start: 33, end: 42 });
+        assert_eq!(location.start, LineCol { line: 5, col: 5 });
+        assert_eq!(location.end, LineCol { line: 5, col: 14 });
+
+        let inlined_at = source_map.inlined_at(5);
+        assert_eq!(inlined_at, vec![source_map.location(4).unwrap()]);
+        assert_eq!(inlined_at[0].start, LineCol { line: 2, col: 5 });
+        assert!(source_map.inlined_at(4).is_empty());
+        assert!(source_map.location(6).is_none());
+    }
+    #[test]
+    fn breakpoints_move_to_mapped_lines() {
+        let source_engine = SourceEngine::default();
+        let root = PathBuf::from("/pkg");
+        let source_id = source_engine.get_source_id(&root.join("src/main.sw"));
+        let src: Arc<str> = "fn f() {\n    let a = 1;\n\n    g(a);\n}\n".into();
+        let span = |start, end| Span::new(src.clone(), start, end, Some(source_id)).unwrap();
+        let let_a = span(13, 23);
+        let call = span(29, 33);
+
+        // The call's line runs twice, once after the other line, and once on its own.
+        let mut source_map = SourceMap::for_package(&root);
+        for (pc, span) in [(0, &let_a), (1, &call), (2, &call), (3, &let_a), (5, &call)] {
+            source_map.insert(&source_engine, pc, span, &[]);
+        }
+
+        let path = Path::new("src/main.sw");
+        let breakpoint = source_map.breakpoint(path, 2).unwrap();
+        assert_eq!(breakpoint.line, 2);
+        assert_eq!(breakpoint.offsets, [0, 3]);
+        let breakpoint = source_map.breakpoint(path, 3).unwrap();
+        assert_eq!(breakpoint.line, 4);
+        assert_eq!(breakpoint.offsets, [1, 5]);
+        assert_eq!(source_map.breakpoint(path, 5), None);
+        assert_eq!(source_map.breakpoint(Path::new("src/lib.sw"), 2), None);
+    }
+}
@@ -20,6 +20,7 @@ use sway_ast::{
     Statement, StatementLet, Submodule, Traits, Ty, TypeField, UseTree, WhereClause,
 };
 use sway_error::convert_parse_tree_error::ConvertParseTreeError;
+use sway_error::error::CompileError;
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_error::warning::{CompileWarning, Warning};
 use sway_types::{
@@ -2816,6 +2817,14 @@ fn literal_to_literal(
                                 full_bytes[(32 - bytes.len())..].copy_from_slice(&bytes);
                                 Literal::B256(full_bytes)
                             }
+                            // Too many digits for a `u64`, so this is meant to be a `b256`.
+                            17.. => {
+                                let error = CompileError::B256LiteralLength {
+                                    found: num_digits,
+                                    span,
+                                };
+                                return Err(handler.emit_err(error));
+                            }
                             _ => {
                                 let error = ConvertParseTreeError::HexLiteralLength { span };
                                 return Err(handler.emit_err(error.into()));
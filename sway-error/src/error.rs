@@ -520,8 +520,32 @@ pub enum CompileError {
     IntegerTooSmall { span: Span, ty: String },
     #[error("Literal value contains digits which are not valid for type {ty}.")]
     IntegerContainsInvalidDigit { span: Span, ty: String },
+    #[error("b256 literals must have exactly 64 hex digits, found {found}.")]
+    B256LiteralLength { found: usize, span: Span },
+    #[error("Arithmetic operation overflows type {ty}.")]
+    ArithmeticOverflow { span: Span, ty: String },
+    #[error("Attempt to divide by zero.")]
+    DivisionByZero { span: Span },
+    #[error("Operator \"{op}\" cannot be applied to literals of type {lhs} and {rhs}.")]
+    LiteralOperandsMismatch {
+        op: &'static str,
+        lhs: String,
+        rhs: String,
+        span: Span,
+    },
     #[error("A trait cannot be a subtrait of an ABI.")]
     AbiAsSupertrait { span: Span },
+    #[error(
+        "Methods \"{first_signature}\" and \"{second_signature}\" have the same selector \
+        {selector}, so calls to one of them could never be dispatched. Rename one of the methods."
+    )]
+    AbiMethodSelectorCollision {
+        first_signature: String,
+        second_signature: String,
+        selector: String,
+        first_span: Span,
+        second_span: Span,
+    },
     #[error(
         "Implementation of trait \"{supertrait_name}\" is required by this bound in \"{trait_name}\""
     )]
@@ -788,7 +812,12 @@ impl Spanned for CompileError {
             IntegerTooLarge { span, .. } => span.clone(),
             IntegerTooSmall { span, .. } => span.clone(),
             IntegerContainsInvalidDigit { span, .. } => span.clone(),
+            B256LiteralLength { span, .. } => span.clone(),
+            ArithmeticOverflow { span, .. } => span.clone(),
+            DivisionByZero { span } => span.clone(),
+            LiteralOperandsMismatch { span, .. } => span.clone(),
             AbiAsSupertrait { span, .. } => span.clone(),
+            AbiMethodSelectorCollision { second_span, .. } => second_span.clone(),
             SupertraitImplRequired { span, .. } => span.clone(),
             ContractCallParamRepeated { span, .. } => span.clone(),
             UnrecognizedContractParam { span, .. } => span.clone(),
@@ -835,6 +864,23 @@ impl CompileError {
     pub fn source_id(&self) -> Option<SourceId> {
         self.span().source_id().cloned()
     }
+
+    /// Other places in the source which are part of the error, each with a label saying what is
+    /// there. They are shown as notes next to the [Spanned::span] of the error.
+    pub fn related_spans(&self) -> Vec<(Span, String)> {
+        use CompileError::*;
+        match self {
+            AbiMethodSelectorCollision {
+                first_signature,
+                first_span,
+                ..
+            } => vec![(
+                first_span.clone(),
+                format!("\"{first_signature}\" is declared here"),
+            )],
+            _ => vec![],
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
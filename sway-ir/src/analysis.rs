@@ -4,3 +4,5 @@ pub mod dominator;
 pub use dominator::*;
 pub mod escaped_symbols;
 pub use escaped_symbols::*;
+pub mod loops;
+pub use loops::*;
@@ -51,3 +51,25 @@ pub fn callee_first_order(cg: &CallGraph) -> Vec<Function> {
 
     res
 }
+
+/// Given a call graph, return the functions which can call themselves, either directly or through
+/// other functions.
+pub fn recursive_functions(cg: &CallGraph) -> FxHashSet<Function> {
+    cg.keys()
+        .copied()
+        .filter(|function| {
+            // Look for a path from the callees of the function back to the function.
+            let mut visited = FxHashSet::<Function>::default();
+            let mut worklist: Vec<Function> = cg[function].iter().copied().collect();
+            while let Some(callee) = worklist.pop() {
+                if callee == *function {
+                    return true;
+                }
+                if visited.insert(callee) {
+                    worklist.extend(cg.get(&callee).into_iter().flatten().copied());
+                }
+            }
+            false
+        })
+        .collect()
+}
@@ -103,17 +103,21 @@ pub fn create_dominators_pass() -> Pass {
         name: DOMINATORS_NAME,
         descr: "Dominator tree computation",
         deps: vec![POSTORDER_NAME],
-        runner: ScopedPass::FunctionPass(PassMutability::Analysis(compute_dom_tree)),
+        runner: ScopedPass::FunctionPass(PassMutability::Analysis(compute_dom_tree_pass)),
     }
 }
 
-/// Compute the dominator tree for the CFG.
-fn compute_dom_tree(
+fn compute_dom_tree_pass(
     context: &Context,
     analyses: &AnalysisResults,
     function: Function,
 ) -> Result<AnalysisResult, IrError> {
     let po: &PostOrder = analyses.get_analysis_result(function);
+    Ok(Box::new(compute_dom_tree(context, po, function)))
+}
+
+/// Compute the dominator tree for the CFG, given its post-order traversal.
+pub fn compute_dom_tree(context: &Context, po: &PostOrder, function: Function) -> DomTree {
     let mut dom_tree = DomTree::default();
     let entry = function.get_entry_block(context);
 
@@ -193,7 +197,7 @@ fn compute_dom_tree(
         dom_tree.get_mut(&parent).unwrap().children.push(child);
     }
 
-    Ok(Box::new(dom_tree))
+    dom_tree
 }
 
 pub const DOMFRONTS_NAME: &str = "dominance_frontiers";
@@ -239,6 +243,19 @@ fn compute_dom_fronts(
     Ok(Box::new(res))
 }
 
+/// Whether `dominator` dominates `block`, i.e., every path from the entry to `block` goes through
+/// `dominator`.  A block dominates itself and unreachable blocks aren't dominated by any block.
+pub fn dominates(dom_tree: &DomTree, dominator: Block, block: Block) -> bool {
+    let mut runner = dom_tree.contains_key(&block).then_some(block);
+    while let Some(b) = runner {
+        if b == dominator {
+            return true;
+        }
+        runner = dom_tree[&b].parent;
+    }
+    false
+}
+
 /// Print dominator tree in the graphviz dot format.
 pub fn print_dot(context: &Context, func_name: &str, dom_tree: &DomTree) -> String {
     let mut res = format!("digraph {func_name} {{\n");
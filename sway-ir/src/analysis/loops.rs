@@ -0,0 +1,89 @@
+//! Natural loop detection.
+//!
+//! A back edge is a CFG edge whose target dominates its source.  The natural loop of a back edge
+//! is its target, the loop header, together with every block which can reach the source of the
+//! back edge without going through the header.  Back edges sharing a header form a single loop.
+use rustc_hash::FxHashSet;
+
+use crate::{
+    dominates, AnalysisResult, AnalysisResultT, AnalysisResults, Block, BranchToWithArgs, Context,
+    DomTree, Function, IrError, Pass, PassMutability, ScopedPass, DOMINATORS_NAME,
+};
+
+pub const LOOPS_NAME: &str = "loops";
+
+pub fn create_loops_pass() -> Pass {
+    Pass {
+        name: LOOPS_NAME,
+        descr: "Natural loop detection",
+        deps: vec![DOMINATORS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Analysis(compute_loops_pass)),
+    }
+}
+
+/// A natural loop in the CFG.
+pub struct NaturalLoop {
+    /// The single entry of the loop, which dominates all of its blocks.
+    pub header: Block,
+    /// The blocks of the loop, including the header.
+    pub blocks: FxHashSet<Block>,
+    /// The blocks which branch back to the header.
+    pub latches: Vec<Block>,
+}
+
+impl NaturalLoop {
+    pub fn contains(&self, block: &Block) -> bool {
+        self.blocks.contains(block)
+    }
+}
+
+/// The natural loops of a function, in no particular nesting order.
+pub type NaturalLoops = Vec<NaturalLoop>;
+impl AnalysisResultT for NaturalLoops {}
+
+fn compute_loops_pass(
+    context: &Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<AnalysisResult, IrError> {
+    let dom_tree: &DomTree = analyses.get_analysis_result(function);
+    Ok(Box::new(compute_loops(context, &function, dom_tree)))
+}
+
+/// Find the natural loops of a function.
+pub fn compute_loops(context: &Context, function: &Function, dom_tree: &DomTree) -> NaturalLoops {
+    let mut loops = NaturalLoops::new();
+    for latch in function.block_iter(context) {
+        // Unreachable blocks aren't part of any loop.
+        if !dom_tree.contains_key(&latch) {
+            continue;
+        }
+        for BranchToWithArgs { block: header, .. } in latch.successors(context) {
+            if !dominates(dom_tree, header, latch) {
+                continue;
+            }
+            let lp = match loops.iter().position(|lp| lp.header == header) {
+                Some(idx) => &mut loops[idx],
+                None => {
+                    loops.push(NaturalLoop {
+                        header,
+                        blocks: FxHashSet::from_iter([header]),
+                        latches: vec![],
+                    });
+                    loops.last_mut().unwrap()
+                }
+            };
+            if !lp.latches.contains(&latch) {
+                lp.latches.push(latch);
+            }
+            // Walk backwards from the latch, the header stops the walk as it's already a member.
+            let mut worklist = vec![latch];
+            while let Some(block) = worklist.pop() {
+                if dom_tree.contains_key(&block) && lp.blocks.insert(block) {
+                    worklist.extend(block.pred_iter(context).copied());
+                }
+            }
+        }
+    }
+    loops
+}
@@ -13,7 +13,7 @@ use sway_types::SourceEngine;
 use crate::{
     asm::AsmBlockContent, block::BlockContent, function::FunctionContent,
     local_var::LocalVarContent, metadata::Metadatum, module::Kind, module::ModuleContent,
-    module::ModuleIterator, value::ValueContent, Type, TypeContent,
+    module::ModuleIterator, optimize::InlineDecision, value::ValueContent, Type, TypeContent,
 };
 
 /// The main IR context handle.
@@ -35,6 +35,9 @@ pub struct Context<'eng> {
 
     pub program_kind: Kind,
 
+    /// The decisions made by the inliner, for reporting which calls were inlined and why.
+    pub(crate) inline_decisions: Vec<InlineDecision>,
+
     next_unique_sym_tag: u64,
 }
 
@@ -53,6 +56,7 @@ impl<'eng> Context<'eng> {
             metadata: Default::default(),
             next_unique_sym_tag: Default::default(),
             program_kind: Kind::Contract,
+            inline_decisions: Default::default(),
         };
         Type::create_basic_types(&mut def);
         def
@@ -67,6 +71,11 @@ impl<'eng> Context<'eng> {
         ModuleIterator::new(self)
     }
 
+    /// The decisions made by the inliner so far, in the order they were made.
+    pub fn inline_decisions(&self) -> &[InlineDecision] {
+        &self.inline_decisions
+    }
+
     /// Get a globally unique symbol.
     ///
     /// The name will be in the form `"anon_N"`, where `N` is an incrementing decimal.
@@ -15,6 +15,7 @@ pub enum IrError {
     RemoveMissingBlock(String),
     ValueNotFound(String),
 
+    VerifyAfterPass(String, Box<IrError>),
     VerifyArgumentValueIsNotArgument(String),
     VerifyUnaryOpIncorrectArgType,
     VerifyBinaryOpIncorrectArgType,
@@ -30,6 +31,7 @@ pub enum IrError {
     VerifyCmpUnknownTypes,
     VerifyConditionExprNotABool,
     VerifyContractCallBadTypes(String),
+    VerifyFunction(String, Box<IrError>),
     VerifyGepElementTypeNonPointer,
     VerifyGepFromNonPointer(String),
     VerifyGepInconsistentTypes,
@@ -37,6 +39,7 @@ pub enum IrError {
     VerifyGetNonExistentPointer,
     VerifyInsertElementOfIncorrectType,
     VerifyInsertValueOfIncorrectType,
+    VerifyInstruction(String, usize, Box<IrError>),
     VerifyIntToPtrFromNonIntegerType(String),
     VerifyIntToPtrToNonPointer(String),
     VerifyIntToPtrUnknownSourceType,
@@ -62,9 +65,11 @@ pub enum IrError {
     VerifyStateDestBadType(String),
     VerifyStateKeyBadType,
     VerifyStateKeyNonPointer(String),
+    VerifyStorageAccessNotPermitted(String, String),
     VerifyStoreMismatchedTypes,
     VerifyStoreToNonPointer(String),
     VerifyUntypedValuePassedToFunction,
+    VerifyUseNotDominatedByDef,
 }
 
 impl std::error::Error for IrError {}
@@ -104,6 +109,13 @@ impl fmt::Display for IrError {
             IrError::ValueNotFound(reason) => {
                 write!(f, "Invalid value: {reason}.")
             }
+            IrError::VerifyAfterPass(pass_str, err) => {
+                write!(f, "After pass '{pass_str}': {err}")
+            }
+            IrError::VerifyFunction(fn_str, err) => write!(f, "In function '{fn_str}': {err}"),
+            IrError::VerifyInstruction(blk_str, idx, err) => {
+                write!(f, "In block '{blk_str}', instruction {idx}: {err}")
+            }
             IrError::VerifyArgumentValueIsNotArgument(callee) => write!(
                 f,
                 "Verification failed: Argument specifier for function '{callee}' is not an \
@@ -305,6 +317,13 @@ impl fmt::Display for IrError {
                     "Verification failed: State access operation must be to a {ty} pointer."
                 )
             }
+            IrError::VerifyStorageAccessNotPermitted(access, attr) => {
+                write!(
+                    f,
+                    "Verification failed: Storage {access} not permitted in a function with \
+                    storage attribute '{attr}'."
+                )
+            }
             IrError::VerifyStoreMismatchedTypes => {
                 write!(
                     f,
@@ -318,6 +337,10 @@ impl fmt::Display for IrError {
                 f,
                 "Verification failed: An untyped/void value has been passed to a function call."
             ),
+            IrError::VerifyUseNotDominatedByDef => write!(
+                f,
+                "Verification failed: An operand is not dominated by its definition."
+            ),
             IrError::VerifyInvalidGtfIndexType => write!(
                 f,
                 "Verification failed: An non-integer value has been passed to a 'gtf' instruction."
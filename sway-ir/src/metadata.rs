@@ -61,6 +61,23 @@ pub fn combine(
     }
 }
 
+/// The `storage` attribute attached to a function, as emitted for `#[storage(..)]`, i.e. one of
+/// `reads`, `writes` or `readswrites`.
+pub(crate) fn storage_attribute<'a>(
+    context: &'a Context,
+    md_idx: Option<MetadataIndex>,
+) -> Option<&'a str> {
+    let md_idx = md_idx?;
+    match md_idx.get_content(context) {
+        Metadatum::List(md_idcs) => md_idcs
+            .iter()
+            .find_map(|md_idx| storage_attribute(context, Some(*md_idx))),
+        content => content
+            .unwrap_struct("storage", 1)
+            .and_then(|fields| fields[0].unwrap_string()),
+    }
+}
+
 impl MetadataIndex {
     pub fn new_integer(context: &mut Context, int: u64) -> Self {
         MetadataIndex(context.metadata.insert(Metadatum::Integer(int)))
@@ -19,10 +19,14 @@ pub mod const_demotion;
 pub use const_demotion::*;
 pub mod constants;
 pub use constants::*;
+pub mod cse;
+pub use cse::*;
 pub mod dce;
 pub use dce::*;
 pub mod inline;
 pub use inline::*;
+pub mod licm;
+pub use licm::*;
 pub mod mem2reg;
 pub use mem2reg::*;
 pub mod memcpyopt;
@@ -33,6 +37,10 @@ pub mod ret_demotion;
 pub use ret_demotion::*;
 pub mod simplify_cfg;
 pub use simplify_cfg::*;
+pub mod sroa;
+pub use sroa::*;
+pub mod storage_access;
+pub use storage_access::*;
 
 mod target_fuel;
 
@@ -0,0 +1,232 @@
+//! ## Common Subexpression Elimination
+//!
+//! This optimization replaces redundant computations with a previously computed, equivalent value.
+//!
+//! Pure instructions (arithmetic, comparisons, casts, `get_local` and `get_elem_ptr` with constant
+//! indices) are hashed by their opcode and operands, where constant operands are compared by their
+//! contents rather than by their value handle.  The function's dominator tree is then walked in
+//! preorder with a scoped table of available expressions, so an instruction is only ever replaced
+//! by an equivalent one in a dominating position.
+//!
+//! Memory reads (`load` and `state_load_word`) are only reused within a single block and only
+//! while no instruction which may have a side effect (stores, calls, storage writes, `asm` blocks,
+//! etc.) is between them.
+
+use crate::{
+    AnalysisResults, BinaryOpKind, Block, ConstantValue, Context, DomTree, FuelVmInstruction,
+    Function, Instruction, IrError, LocalVar, Pass, PassMutability, Predicate, ScopedPass, Type,
+    UnaryOpKind, Value, DOMINATORS_NAME, POSTORDER_NAME,
+};
+
+use rustc_hash::FxHashMap;
+
+pub const CSE_NAME: &str = "cse";
+
+pub fn create_cse_pass() -> Pass {
+    Pass {
+        name: CSE_NAME,
+        descr: "Common subexpression elimination.",
+        deps: vec![POSTORDER_NAME, DOMINATORS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(cse)),
+    }
+}
+
+/// A scalar constant, compared by its contents.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum ConstantKey {
+    Unit,
+    Bool(bool),
+    Uint(Type, u64),
+    B256([u8; 32]),
+}
+
+/// An instruction operand, after redundant values have been replaced.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Operand {
+    Value(Value),
+    Constant(ConstantKey),
+}
+
+/// A pure expression which may be reused anywhere it is dominated by its first occurrence.
+#[derive(PartialEq, Eq, Hash)]
+enum PureExpr {
+    UnaryOp(UnaryOpKind, Operand),
+    BinaryOp(BinaryOpKind, Operand, Operand),
+    Cmp(Predicate, Operand, Operand),
+    BitCast(Operand, Type),
+    CastPtr(Operand, Type),
+    IntToPtr(Operand, Type),
+    PtrToInt(Operand, Type),
+    GetLocal(LocalVar),
+    GetElemPtr(Operand, Type, Vec<ConstantKey>),
+}
+
+/// A memory read which may only be reused until the next side effect in the same block.
+#[derive(PartialEq, Eq, Hash)]
+enum MemoryExpr {
+    Load(Operand),
+    StateLoadWord(Operand),
+}
+
+enum WalkStep {
+    Enter(Block),
+    Exit,
+}
+
+pub fn cse(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let dom_tree: &DomTree = analyses.get_analysis_result(function);
+
+    let mut replacements = FxHashMap::<Value, Value>::default();
+    let mut redundant = Vec::new();
+
+    // Walk the dominator tree in preorder, pushing a new scope of available expressions when
+    // entering a block and popping it once all of the blocks it dominates have been visited.
+    let mut scopes: Vec<FxHashMap<PureExpr, Value>> = Vec::new();
+    let mut worklist = vec![WalkStep::Enter(function.get_entry_block(context))];
+    while let Some(step) = worklist.pop() {
+        match step {
+            WalkStep::Enter(block) => {
+                scopes.push(FxHashMap::default());
+                cse_block(
+                    context,
+                    block,
+                    &mut scopes,
+                    &mut replacements,
+                    &mut redundant,
+                );
+                worklist.push(WalkStep::Exit);
+                if let Some(node) = dom_tree.get(&block) {
+                    worklist.extend(node.children.iter().rev().map(|b| WalkStep::Enter(*b)));
+                }
+            }
+            WalkStep::Exit => {
+                scopes.pop();
+            }
+        }
+    }
+
+    if replacements.is_empty() {
+        return Ok(false);
+    }
+
+    function.replace_values(context, &replacements, None);
+    for (block, inst) in redundant {
+        block.remove_instruction(context, inst);
+    }
+
+    Ok(true)
+}
+
+fn cse_block(
+    context: &Context,
+    block: Block,
+    scopes: &mut [FxHashMap<PureExpr, Value>],
+    replacements: &mut FxHashMap<Value, Value>,
+    redundant: &mut Vec<(Block, Value)>,
+) {
+    let mut available_reads = FxHashMap::<MemoryExpr, Value>::default();
+
+    for inst in block.instruction_iter(context) {
+        let instruction = inst.get_instruction(context).unwrap();
+
+        if let Some(expr) = pure_expr(context, replacements, instruction) {
+            match scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(&expr).copied())
+            {
+                Some(available) => {
+                    replacements.insert(inst, available);
+                    redundant.push((block, inst));
+                }
+                None => {
+                    scopes.last_mut().unwrap().insert(expr, inst);
+                }
+            }
+        } else if let Some(expr) = memory_expr(context, replacements, instruction) {
+            match available_reads.get(&expr).copied() {
+                Some(available) => {
+                    replacements.insert(inst, available);
+                    redundant.push((block, inst));
+                }
+                None => {
+                    available_reads.insert(expr, inst);
+                }
+            }
+        } else if instruction.may_have_side_effect() {
+            available_reads.clear();
+        }
+    }
+}
+
+fn pure_expr(
+    context: &Context,
+    replacements: &FxHashMap<Value, Value>,
+    instruction: &Instruction,
+) -> Option<PureExpr> {
+    let operand = |value: &Value| to_operand(context, replacements, *value);
+    Some(match instruction {
+        Instruction::UnaryOp { op, arg } => PureExpr::UnaryOp(*op, operand(arg)),
+        Instruction::BinaryOp { op, arg1, arg2 } => {
+            PureExpr::BinaryOp(*op, operand(arg1), operand(arg2))
+        }
+        Instruction::Cmp(pred, lhs, rhs) => PureExpr::Cmp(*pred, operand(lhs), operand(rhs)),
+        Instruction::BitCast(value, ty) => PureExpr::BitCast(operand(value), *ty),
+        Instruction::CastPtr(value, ty) => PureExpr::CastPtr(operand(value), *ty),
+        Instruction::IntToPtr(value, ty) => PureExpr::IntToPtr(operand(value), *ty),
+        Instruction::PtrToInt(value, ty) => PureExpr::PtrToInt(operand(value), *ty),
+        Instruction::GetLocal(local_var) => PureExpr::GetLocal(*local_var),
+        Instruction::GetElemPtr {
+            base,
+            elem_ptr_ty,
+            indices,
+        } => PureExpr::GetElemPtr(
+            operand(base),
+            *elem_ptr_ty,
+            indices
+                .iter()
+                .map(|idx| constant_key(context, *idx))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
+
+fn memory_expr(
+    context: &Context,
+    replacements: &FxHashMap<Value, Value>,
+    instruction: &Instruction,
+) -> Option<MemoryExpr> {
+    match instruction {
+        Instruction::Load(ptr) => Some(MemoryExpr::Load(to_operand(context, replacements, *ptr))),
+        Instruction::FuelVm(FuelVmInstruction::StateLoadWord(key)) => Some(
+            MemoryExpr::StateLoadWord(to_operand(context, replacements, *key)),
+        ),
+        _ => None,
+    }
+}
+
+fn to_operand(context: &Context, replacements: &FxHashMap<Value, Value>, value: Value) -> Operand {
+    match constant_key(context, value) {
+        Some(key) => Operand::Constant(key),
+        None => Operand::Value(replacements.get(&value).copied().unwrap_or(value)),
+    }
+}
+
+fn constant_key(context: &Context, value: Value) -> Option<ConstantKey> {
+    let constant = value.get_constant(context)?;
+    match &constant.value {
+        ConstantValue::Unit => Some(ConstantKey::Unit),
+        ConstantValue::Bool(b) => Some(ConstantKey::Bool(*b)),
+        ConstantValue::Uint(n) => Some(ConstantKey::Uint(constant.ty, *n)),
+        ConstantValue::B256(bytes) => Some(ConstantKey::B256(*bytes)),
+        ConstantValue::Undef
+        | ConstantValue::String(_)
+        | ConstantValue::Array(_)
+        | ConstantValue::Struct(_) => None,
+    }
+}
@@ -4,7 +4,7 @@
 
 use std::{cell::RefCell, collections::HashMap};
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     asm::AsmArg,
@@ -16,7 +16,7 @@ use crate::{
     instruction::{FuelVmInstruction, Instruction},
     irtype::Type,
     local_var::LocalVar,
-    metadata::{combine, MetadataIndex},
+    metadata::{combine, MetadataIndex, Metadatum},
     value::{Value, ValueContent, ValueDatum},
     AnalysisResults, BlockArgument, Module, Pass, PassMutability, ScopedPass,
 };
@@ -88,6 +88,171 @@ fn metadata_to_inline(context: &Context, md_idx: Option<MetadataIndex>) -> Optio
     })
 }
 
+/// The number of instructions that a call costs in the caller, on top of the instructions of the
+/// callee.  Inlining a callee which is no bigger than this doesn't grow the code.
+const CALL_OVERHEAD_INSTRS_COUNT: usize = 4;
+
+/// The number of instructions which are expected to fold away for each constant argument of a
+/// call once the callee is inlined.
+const CONSTANT_ARG_SAVED_INSTRS_COUNT: usize = 1;
+
+/// Why a call was or wasn't inlined by [inline_in_module].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineReason {
+    /// The callee is marked with `#[inline(always)]`.
+    AttributeAlways,
+    /// The callee is marked with `#[inline(never)]`.
+    AttributeNever,
+    /// The callee calls itself, directly or through other functions, so inlining it would never
+    /// end.
+    Recursive,
+    /// The callee has more arguments than can be passed in registers.
+    TooManyArgs,
+    /// The callee has arguments which aren't passed by value and must be inlined, see
+    /// https://github.com/FuelLabs/sway/issues/2819.
+    NonValueArgs,
+    /// The callee is only called from a single call site, so inlining doesn't duplicate it.
+    SingleCallSite,
+    /// The callee costs no more instructions than the call and the constant arguments that fold
+    /// away once it is inlined.
+    Cheap { instrs_count: usize, budget: usize },
+    /// The callee costs more instructions than the call and the constant arguments that fold
+    /// away once it is inlined.
+    Expensive { instrs_count: usize, budget: usize },
+}
+
+impl InlineReason {
+    /// Whether the call is inlined for this reason.
+    pub fn is_inlined(&self) -> bool {
+        !matches!(
+            self,
+            InlineReason::AttributeNever | InlineReason::Recursive | InlineReason::Expensive { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for InlineReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InlineReason::AttributeAlways => write!(f, "marked #[inline(always)]"),
+            InlineReason::AttributeNever => write!(f, "marked #[inline(never)]"),
+            InlineReason::Recursive => write!(f, "recursive"),
+            InlineReason::TooManyArgs => write!(f, "too many args to pass in registers"),
+            InlineReason::NonValueArgs => write!(f, "has args which aren't passed by value"),
+            InlineReason::SingleCallSite => write!(f, "called from a single call site"),
+            InlineReason::Cheap {
+                instrs_count,
+                budget,
+            } => write!(f, "{instrs_count} instructions, within budget of {budget}"),
+            InlineReason::Expensive {
+                instrs_count,
+                budget,
+            } => write!(f, "{instrs_count} instructions, over budget of {budget}"),
+        }
+    }
+}
+
+/// A decision made by [inline_in_module] for a call, kept so that it can be reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineDecision {
+    pub caller: String,
+    pub callee: String,
+    pub reason: InlineReason,
+}
+
+impl std::fmt::Display for InlineDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verb = if self.reason.is_inlined() {
+            "inlined"
+        } else {
+            "not inlined"
+        };
+        write!(
+            f,
+            "call to {} in {} {verb}: {}",
+            self.callee, self.caller, self.reason
+        )
+    }
+}
+
+/// Decide whether to inline a call to `callee`, made at `call_site`.
+///
+/// Attributes on the callee and the constraints of the target come first.  Otherwise the callee is
+/// inlined if that doesn't duplicate it or grow the code, counting the constant arguments of the
+/// call as instructions that fold away.
+fn inline_decision(
+    context: &Context,
+    callee: &Function,
+    call_site: &Value,
+    call_counts: &HashMap<Function, u64>,
+    recursive_functions: &FxHashSet<Function>,
+) -> InlineReason {
+    if recursive_functions.contains(callee) {
+        return InlineReason::Recursive;
+    }
+    match metadata_to_inline(context, callee.get_metadata(context)) {
+        Some(Inline::Always) => return InlineReason::AttributeAlways,
+        Some(Inline::Never) => return InlineReason::AttributeNever,
+        None => {}
+    }
+
+    let ret_type = callee.get_return_type(context);
+    let num_args = {
+        callee.args_iter(context).count()
+            + if super::target_fuel::is_demotable_type(context, &ret_type) {
+                // The return type will be demoted to memory,
+                // which means that there'll be an additional return arg.
+                1
+            } else {
+                0
+            }
+    };
+
+    // For now, pending improvements to ASMgen for calls, we must inline any function which has
+    // too many args.
+    if num_args as u8 > NUM_ARG_REGISTERS {
+        return InlineReason::TooManyArgs;
+    }
+
+    // As per https://github.com/FuelLabs/sway/issues/2819 we can hit problems if a function
+    // argument is used as a pointer (probably because it has a ref type) although it actually
+    // isn't one.  Ref type args which aren't pointers need to be inlined.
+    if callee.args_iter(context).any(|(_name, arg_val)| {
+        arg_val.get_type(context).map_or(false, |ty| {
+            ty.is_ptr(context) || !(ty.is_unit(context) | ty.is_bool(context) | ty.is_uint(context))
+        })
+    }) {
+        return InlineReason::NonValueArgs;
+    }
+
+    // If the function is called only once then definitely inline it.
+    if call_counts.get(callee).copied().unwrap_or(0) == 1 {
+        return InlineReason::SingleCallSite;
+    }
+
+    // Otherwise inline it if it is no bigger than the call, once its constant arguments are
+    // folded.
+    let num_constant_args = match call_site.get_instruction(context) {
+        Some(Instruction::Call(_, args)) => {
+            args.iter().filter(|arg| arg.is_constant(context)).count()
+        }
+        _ => 0,
+    };
+    let budget = CALL_OVERHEAD_INSTRS_COUNT + num_constant_args * CONSTANT_ARG_SAVED_INSTRS_COUNT;
+    let instrs_count = callee.num_instructions(context);
+    if instrs_count <= budget {
+        InlineReason::Cheap {
+            instrs_count,
+            budget,
+        }
+    } else {
+        InlineReason::Expensive {
+            instrs_count,
+            budget,
+        }
+    }
+}
+
 pub fn inline_in_module(
     context: &mut Context,
     _: &AnalysisResults,
@@ -109,69 +274,43 @@ pub fn inline_in_module(
                 counts
             });
 
-    let inline_heuristic = |ctx: &Context, func: &Function, _call_site: &Value| {
-        let attributed_inline = metadata_to_inline(ctx, func.get_metadata(ctx));
-        match attributed_inline {
-            Some(Inline::Always) => {
-                // TODO: check if inlining of function is possible
-                // return true;
-            }
-            Some(Inline::Never) => {
-                return false;
-            }
-            None => {}
-        }
-
-        let ret_type = func.get_return_type(ctx);
-        let num_args = {
-            func.args_iter(ctx).count()
-                + if super::target_fuel::is_demotable_type(ctx, &ret_type) {
-                    // The return type will be demoted to memory,
-                    // which means that there'll be an additional return arg.
-                    1
-                } else {
-                    0
-                }
-        };
-
-        // For now, pending improvements to ASMgen for calls, we must inline any function which has
-        // too many args.
-        if num_args as u8 > NUM_ARG_REGISTERS {
-            return true;
-        }
-
-        // If the function is called only once then definitely inline it.
-        if call_counts.get(func).copied().unwrap_or(0) == 1 {
-            return true;
-        }
-
-        // If the function is (still) small then also inline it.
-        const MAX_INLINE_INSTRS_COUNT: usize = 4;
-        if func.num_instructions(ctx) <= MAX_INLINE_INSTRS_COUNT {
-            return true;
-        }
-
-        // As per https://github.com/FuelLabs/sway/issues/2819 we can hit problems if a function
-        // argument is used as a pointer (probably because it has a ref type) although it actually
-        // isn't one.  Ref type args which aren't pointers need to be inlined.
-        if func.args_iter(ctx).any(|(_name, arg_val)| {
-            arg_val.get_type(ctx).map_or(false, |ty| {
-                ty.is_ptr(ctx) || !(ty.is_unit(ctx) | ty.is_bool(ctx) | ty.is_uint(ctx))
-            })
-        }) {
-            return true;
-        }
-
-        false
-    };
-
     let cg =
         call_graph::build_call_graph(context, &module.function_iter(context).collect::<Vec<_>>());
+    let recursive_functions = call_graph::recursive_functions(&cg);
     let functions = call_graph::callee_first_order(&cg);
     let mut modified = false;
 
     for function in functions {
-        modified |= inline_some_function_calls(context, &function, inline_heuristic)?;
+        // The calls of each function are decided on once the calls of its callees have been
+        // inlined, so that the sizes of the callees are up to date.
+        let caller = function.get_name(context).to_string();
+        let mut inlined_call_sites = FxHashSet::default();
+        let mut decisions = Vec::new();
+        for (_block, call_site) in function.instruction_iter(context) {
+            let Some(Instruction::Call(callee, _args)) = call_site.get_instruction(context) else {
+                continue;
+            };
+            let reason = inline_decision(
+                context,
+                callee,
+                &call_site,
+                &call_counts,
+                &recursive_functions,
+            );
+            if reason.is_inlined() {
+                inlined_call_sites.insert(call_site);
+            }
+            let decision = InlineDecision {
+                caller: caller.clone(),
+                callee: callee.get_name(context).to_string(),
+                reason,
+            };
+            decisions.push(decision);
+        }
+        context.inline_decisions.extend(decisions);
+        modified |= inline_some_function_calls(context, &function, |_, _, call_site| {
+            inlined_call_sites.contains(call_site)
+        })?;
     }
     Ok(modified)
 }
@@ -360,9 +499,12 @@ pub fn inline_function_call(
         }
     }
 
-    // Get the metadata attached to the function call which may need to be propagated to the
-    // inlined instructions.
-    let metadata = context.values[call_site.0].metadata;
+    // Get the metadata attached to the function call which needs to be propagated to the
+    // inlined instructions.  It's wrapped in an `inlined` struct so that the inlined instructions
+    // keep their own spans, with the call site recorded as where they were inlined.
+    let metadata = context.values[call_site.0].metadata.map(|md_idx| {
+        MetadataIndex::new_struct(context, "inlined", vec![Metadatum::Index(md_idx)])
+    });
 
     // Now remove the call altogether.
     context.values.remove(call_site.0);
@@ -441,7 +583,7 @@ fn inline_instruction(
     block_map: &HashMap<Block, Block>,
     value_map: &mut HashMap<Value, Value>,
     local_map: &HashMap<LocalVar, LocalVar>,
-    fn_metadata: Option<MetadataIndex>,
+    inlined_metadata: Option<MetadataIndex>,
 ) {
     // Util to translate old blocks to new.  If an old block isn't in the map then we panic, since
     // it should be guaranteed to be there...that's a bug otherwise.
@@ -464,9 +606,10 @@ fn inline_instruction(
         metadata: val_metadata,
     } = context.values[instruction.0].clone()
     {
-        // Combine the function metadata with this instruction metadata so we don't lose the
-        // function metadata after inlining.
-        let metadata = combine(context, &fn_metadata, &val_metadata);
+        // Combine this instruction metadata with the call site metadata so we don't lose the
+        // call site after inlining.  The instruction metadata comes first so that its own span is
+        // found before any call site's, and calls inlined earlier stay innermost.
+        let metadata = combine(context, &val_metadata, &inlined_metadata);
 
         let new_ins = match old_ins {
             Instruction::AsmBlock(asm, args) => {
@@ -0,0 +1,254 @@
+//! ## Loop-Invariant Code Motion
+//!
+//! This optimization hoists instructions whose result is the same in every iteration of a loop
+//! into a preheader, a new block which branches to the loop header and which becomes the only
+//! entry into the loop from outside of it.
+//!
+//! An instruction is loop-invariant when all of its operands are defined outside of the loop or
+//! are themselves loop-invariant.  Since the preheader runs even when the loop body doesn't,
+//! only instructions which can't fault are hoisted from anywhere in the loop.  Arithmetic which
+//! may overflow or divide by zero is only hoisted from the loop header, which always runs at least
+//! once.
+//!
+//! Loads are hoisted only from local variables which don't escape the function, and only when
+//! nothing in the loop may write to the same local.  Calls, `asm` blocks and writes through
+//! pointers which can't be traced back to a local or an argument prevent hoisting any loads.
+//!
+//! Loops are processed from the outermost in, so an instruction which is invariant in several
+//! nested loops is hoisted out of all of them.
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    get_symbols, AnalysisResults, BinaryOpKind, Block, Context, EscapedSymbols, FuelVmInstruction,
+    Function, Instruction, IrError, NaturalLoop, NaturalLoops, Pass, PassMutability, ScopedPass,
+    Symbol, Value, ESCAPED_SYMBOLS_NAME, LOOPS_NAME,
+};
+
+pub const LICM_NAME: &str = "licm";
+
+pub fn create_licm_pass() -> Pass {
+    Pass {
+        name: LICM_NAME,
+        descr: "Loop-invariant code motion.",
+        deps: vec![LOOPS_NAME, ESCAPED_SYMBOLS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(licm)),
+    }
+}
+
+pub fn licm(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let loops: &NaturalLoops = analyses.get_analysis_result(function);
+    let escaped_symbols: &EscapedSymbols = analyses.get_analysis_result(function);
+
+    // An enclosing loop always has more blocks than the loops nested within it.
+    let mut loops = loops.iter().collect::<Vec<_>>();
+    loops.sort_by_key(|lp| std::cmp::Reverse(lp.blocks.len()));
+
+    let mut modified = false;
+    for lp in loops {
+        // The entry block can't have predecessors, so it can't be given a preheader.
+        if lp.header == function.get_entry_block(context) {
+            continue;
+        }
+        let invariants = find_invariants(context, lp, escaped_symbols);
+        if invariants.is_empty() {
+            continue;
+        }
+        let Some(preheader) = create_preheader(context, function, lp)? else {
+            continue;
+        };
+        for block in &lp.blocks {
+            block.remove_instructions(context, |inst| invariants.contains(&inst));
+        }
+        let preheader_instrs = &mut context.blocks[preheader.0].instructions;
+        let terminator = preheader_instrs.pop().unwrap();
+        preheader_instrs.extend(invariants);
+        preheader_instrs.push(terminator);
+        modified = true;
+    }
+
+    Ok(modified)
+}
+
+/// Find the loop-invariant instructions of the loop, in an order in which they can be hoisted.
+fn find_invariants(
+    context: &Context,
+    lp: &NaturalLoop,
+    escaped_symbols: &EscapedSymbols,
+) -> Vec<Value> {
+    // The function's block order is used rather than the set's, to keep the output stable.
+    let function = lp.header.get_function(context);
+    let loop_blocks = function
+        .block_iter(context)
+        .filter(|block| lp.contains(block))
+        .collect::<Vec<_>>();
+
+    let mut defined_in_loop = FxHashSet::<Value>::default();
+    let mut written_symbols = FxHashSet::<Symbol>::default();
+    // Whether the loop may write to memory we can't trace back to a symbol, in which case no
+    // loads are hoisted.
+    let mut writes_untraced_memory = false;
+    for block in &loop_blocks {
+        defined_in_loop.extend(block.arg_iter(context).copied());
+        for inst in block.instruction_iter(context) {
+            defined_in_loop.insert(inst);
+            match inst.get_instruction(context).unwrap() {
+                Instruction::AsmBlock(..)
+                | Instruction::Call(..)
+                | Instruction::ContractCall { .. } => writes_untraced_memory = true,
+                instruction => {
+                    if let Some(dst) = written_pointer(instruction) {
+                        let symbols = get_symbols(context, dst);
+                        writes_untraced_memory |= symbols.is_empty();
+                        written_symbols.extend(symbols);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut invariants = Vec::new();
+    let mut hoisted = FxHashSet::<Value>::default();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in &loop_blocks {
+            for inst in block.instruction_iter(context) {
+                if hoisted.contains(&inst) {
+                    continue;
+                }
+                let instruction = inst.get_instruction(context).unwrap();
+                let is_hoistable = match instruction {
+                    Instruction::Load(ptr) => {
+                        !writes_untraced_memory
+                            && is_unaliased_local(context, *ptr, escaped_symbols, &written_symbols)
+                    }
+                    _ => can_hoist(instruction, *block == lp.header),
+                };
+                let is_invariant = instruction
+                    .get_operands()
+                    .iter()
+                    .all(|opd| !defined_in_loop.contains(opd) || hoisted.contains(opd));
+                if is_hoistable && is_invariant {
+                    hoisted.insert(inst);
+                    invariants.push(inst);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    invariants
+}
+
+/// Whether a pure instruction may be executed speculatively, before the loop.
+fn can_hoist(instruction: &Instruction, in_header: bool) -> bool {
+    match instruction {
+        Instruction::BinaryOp { op, .. } => match op {
+            BinaryOpKind::And
+            | BinaryOpKind::Or
+            | BinaryOpKind::Xor
+            | BinaryOpKind::Lsh
+            | BinaryOpKind::Rsh => true,
+            // These may overflow or divide by zero, which makes the VM panic.
+            BinaryOpKind::Add
+            | BinaryOpKind::Sub
+            | BinaryOpKind::Mul
+            | BinaryOpKind::Div
+            | BinaryOpKind::Mod => in_header,
+        },
+        Instruction::UnaryOp { .. }
+        | Instruction::BitCast(..)
+        | Instruction::CastPtr(..)
+        | Instruction::Cmp(..)
+        | Instruction::GetElemPtr { .. }
+        | Instruction::GetLocal(_)
+        | Instruction::IntToPtr(..)
+        | Instruction::PtrToInt(..) => true,
+        _ => false,
+    }
+}
+
+/// The pointer which an instruction writes to, if any.
+fn written_pointer(instruction: &Instruction) -> Option<Value> {
+    match instruction {
+        Instruction::Store { dst_val_ptr, .. }
+        | Instruction::MemCopyBytes { dst_val_ptr, .. }
+        | Instruction::MemCopyVal { dst_val_ptr, .. } => Some(*dst_val_ptr),
+        Instruction::FuelVm(FuelVmInstruction::StateLoadQuadWord { load_val, .. }) => {
+            Some(*load_val)
+        }
+        _ => None,
+    }
+}
+
+/// Whether `ptr` only points into local variables which don't escape the function and aren't
+/// written to in the loop.
+fn is_unaliased_local(
+    context: &Context,
+    ptr: Value,
+    escaped_symbols: &EscapedSymbols,
+    written_symbols: &FxHashSet<Symbol>,
+) -> bool {
+    let symbols = get_symbols(context, ptr);
+    !symbols.is_empty()
+        && symbols.iter().all(|sym| {
+            matches!(sym, Symbol::Local(_))
+                && !escaped_symbols.contains(sym)
+                && !written_symbols.contains(sym)
+        })
+}
+
+/// Create a block which branches to the loop header and redirect all of the branches into the
+/// loop from outside of it to the new block.
+///
+/// Returns `None` if a block enters the loop on both edges of a conditional branch, since those
+/// edges may pass different arguments to the header.
+fn create_preheader(
+    context: &mut Context,
+    function: Function,
+    lp: &NaturalLoop,
+) -> Result<Option<Block>, IrError> {
+    let header = lp.header;
+    let entering_blocks = header
+        .pred_iter(context)
+        .filter(|pred| !lp.contains(pred))
+        .copied()
+        .collect::<Vec<_>>();
+    let enters_on_both_edges = |block: &Block| {
+        matches!(
+            block.get_terminator(context),
+            Some(Instruction::ConditionalBranch { true_block, false_block, .. })
+                if true_block.block == header && false_block.block == header
+        )
+    };
+    if entering_blocks.iter().any(enters_on_both_edges) {
+        return Ok(None);
+    }
+
+    let label = format!("{}_preheader", header.get_label(context));
+    let preheader = function.create_block_before(context, &header, Some(label))?;
+    let header_arg_types = header
+        .arg_iter(context)
+        .map(|arg| arg.get_type(context).unwrap())
+        .collect::<Vec<_>>();
+    let preheader_args = header_arg_types
+        .into_iter()
+        .map(|ty| {
+            let idx = preheader.new_arg(context, ty);
+            preheader.get_arg(context, idx).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    for block in entering_blocks {
+        let params = block.get_succ_params(context, &header);
+        block.replace_successor(context, header, preheader, params);
+    }
+    preheader.ins(context).branch(header, preheader_args);
+
+    Ok(Some(preheader))
+}
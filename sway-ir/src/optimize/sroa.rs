@@ -0,0 +1,254 @@
+//! ## Scalar Replacement of Aggregates
+//!
+//! This optimization splits local structs and arrays into a local per field, so that mem2reg can
+//! then promote the fields to SSA registers.
+//!
+//! A local is only split when it's solely accessed through `get_elem_ptr` with a constant first
+//! index, and those field pointers are only loaded from, stored to, copied or indexed further.
+//! Any other use of the local's address, such as loading or copying the whole aggregate, passing
+//! it to a call or indexing an array with a non-constant value, keeps the local intact.
+//!
+//! Nested aggregates are split one level at a time until no more locals can be split.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    AnalysisResults, Block, Constant, ConstantValue, Context, Function, Instruction, IrError,
+    LocalVar, Pass, PassMutability, ScopedPass, Type, Value, ValueDatum,
+};
+
+pub const SROA_NAME: &str = "sroa";
+
+pub fn create_sroa_pass() -> Pass {
+    Pass {
+        name: SROA_NAME,
+        descr: "Scalar replacement of aggregates.",
+        deps: vec![],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(sroa)),
+    }
+}
+
+pub fn sroa(
+    context: &mut Context,
+    _: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let mut modified = false;
+    loop {
+        let candidates = splittable_locals(context, &function);
+        if candidates.is_empty() {
+            break;
+        }
+        for (name, local_var) in candidates {
+            split_local(context, &function, name, local_var);
+        }
+        modified = true;
+    }
+    Ok(modified)
+}
+
+/// The number of fields of a struct or elements of an array.
+fn num_fields(context: &Context, ty: Type) -> Option<u64> {
+    if ty.is_struct(context) {
+        Some(ty.get_field_types(context).len() as u64)
+    } else if ty.is_array(context) {
+        ty.get_array_len(context)
+    } else {
+        None
+    }
+}
+
+/// The constant value of a `get_elem_ptr` index.
+fn constant_index(context: &Context, idx: &Value) -> Option<u64> {
+    match idx.get_constant(context)?.value {
+        ConstantValue::Uint(idx) => Some(idx),
+        _ => None,
+    }
+}
+
+/// The initializer of the field at `idx`, if the aggregate has one.
+fn field_initializer(initializer: &Constant, idx: u64) -> Option<Constant> {
+    match &initializer.value {
+        ConstantValue::Struct(fields) | ConstantValue::Array(fields) => {
+            fields.get(idx as usize).cloned()
+        }
+        _ => None,
+    }
+}
+
+// Returns those aggregate locals which are only accessed field-wise.
+fn splittable_locals(context: &Context, function: &Function) -> Vec<(String, LocalVar)> {
+    let mut candidates: FxHashMap<LocalVar, String> = function
+        .locals_iter(context)
+        .filter(|(_, var)| {
+            let ty = var.get_inner_type(context);
+            let is_splittable_initializer = var.get_initializer(context).map_or(true, |init| {
+                matches!(
+                    init.value,
+                    ConstantValue::Struct(_) | ConstantValue::Array(_)
+                )
+            });
+            num_fields(context, ty).is_some() && is_splittable_initializer
+        })
+        .map(|(name, var)| (*var, name.clone()))
+        .collect();
+
+    // Map the pointers to the candidates, and to their fields, back to the candidates.
+    let mut local_ptrs = FxHashMap::<Value, LocalVar>::default();
+    for (_, inst) in function.instruction_iter(context) {
+        if let Some(Instruction::GetLocal(local_var)) = inst.get_instruction(context) {
+            if candidates.contains_key(local_var) {
+                local_ptrs.insert(inst, *local_var);
+            }
+        }
+    }
+    let mut field_ptrs = FxHashMap::<Value, LocalVar>::default();
+    for (_, inst) in function.instruction_iter(context) {
+        if let Some(Instruction::GetElemPtr { base, .. }) = inst.get_instruction(context) {
+            if let Some(local_var) = local_ptrs.get(base) {
+                field_ptrs.insert(inst, *local_var);
+            }
+        }
+    }
+
+    let mut rejected = FxHashSet::<LocalVar>::default();
+    for (_, inst) in function.instruction_iter(context) {
+        let instruction = inst.get_instruction(context).unwrap();
+        match instruction {
+            // The local itself may only be indexed with a constant first index.
+            Instruction::GetElemPtr { base, indices, .. } if local_ptrs.contains_key(base) => {
+                let local_var = local_ptrs[base];
+                let ty = local_var.get_inner_type(context);
+                let is_in_bounds = indices
+                    .first()
+                    .and_then(|idx| constant_index(context, idx))
+                    .zip(num_fields(context, ty))
+                    .map_or(false, |(idx, len)| idx < len);
+                if !is_in_bounds {
+                    rejected.insert(local_var);
+                }
+            }
+            // Field pointers may be loaded from, stored to, copied or indexed further.
+            Instruction::Load(_) | Instruction::MemCopyVal { .. } => {
+                for opd in instruction.get_operands() {
+                    if let Some(local_var) = local_ptrs.get(&opd) {
+                        rejected.insert(*local_var);
+                    }
+                }
+            }
+            Instruction::Store { stored_val, .. } => {
+                if let Some(local_var) = local_ptrs
+                    .get(stored_val)
+                    .or_else(|| field_ptrs.get(stored_val))
+                {
+                    rejected.insert(*local_var);
+                }
+                for opd in instruction.get_operands() {
+                    if let Some(local_var) = local_ptrs.get(&opd) {
+                        rejected.insert(*local_var);
+                    }
+                }
+            }
+            Instruction::GetElemPtr { .. } => (),
+            // Any other use of the local or its fields lets their address escape.
+            _ => {
+                for opd in instruction.get_operands() {
+                    if let Some(local_var) = local_ptrs.get(&opd).or_else(|| field_ptrs.get(&opd)) {
+                        rejected.insert(*local_var);
+                    }
+                }
+            }
+        }
+    }
+
+    candidates.retain(|local_var, _| !rejected.contains(local_var));
+    let mut candidates = candidates
+        .into_iter()
+        .map(|(local_var, name)| (name, local_var))
+        .collect::<Vec<_>>();
+    // Keep the names of the new locals stable.
+    candidates.sort_by(|(a, _), (b, _)| a.cmp(b));
+    candidates
+}
+
+fn split_local(context: &mut Context, function: &Function, name: String, local_var: LocalVar) {
+    let aggregate_ty = local_var.get_inner_type(context);
+    let initializer = local_var.get_initializer(context).cloned();
+    let mutable = local_var.is_mutable(context);
+
+    let mut field_locals = FxHashMap::<u64, LocalVar>::default();
+    let field_ptrs = function
+        .instruction_iter(context)
+        .filter(|(_, inst)| {
+            matches!(
+                inst.get_instruction(context),
+                Some(Instruction::GetElemPtr { base, .. })
+                    if matches!(
+                        base.get_instruction(context),
+                        Some(Instruction::GetLocal(base_var)) if *base_var == local_var
+                    )
+            )
+        })
+        .collect::<Vec<(Block, Value)>>();
+
+    for (block, field_ptr) in field_ptrs {
+        let Some(Instruction::GetElemPtr {
+            elem_ptr_ty,
+            indices,
+            ..
+        }) = field_ptr.get_instruction(context).cloned()
+        else {
+            unreachable!("Field pointers are get_elem_ptr instructions.");
+        };
+        let idx = constant_index(context, &indices[0]).unwrap();
+        let field_local = match field_locals.get(&idx) {
+            Some(field_local) => *field_local,
+            None => {
+                let field_ty = aggregate_ty.get_indexed_type(context, &[idx]).unwrap();
+                let field_init = initializer
+                    .as_ref()
+                    .and_then(|init| field_initializer(init, idx));
+                let field_local = function.new_unique_local_var(
+                    context,
+                    format!("{name}_{idx}"),
+                    field_ty,
+                    field_init,
+                    mutable,
+                );
+                field_locals.insert(idx, field_local);
+                field_local
+            }
+        };
+
+        // Rewrite the field pointer in place, so its uses don't need to be updated.
+        let new_ptr = if indices.len() == 1 {
+            Instruction::GetLocal(field_local)
+        } else {
+            let field_base = Value::new_instruction(context, Instruction::GetLocal(field_local));
+            let block_instrs = &mut context.blocks[block.0].instructions;
+            let pos = block_instrs.iter().position(|v| *v == field_ptr).unwrap();
+            block_instrs.insert(pos, field_base);
+            Instruction::GetElemPtr {
+                base: field_base,
+                elem_ptr_ty,
+                indices: indices[1..].to_vec(),
+            }
+        };
+        context.values[field_ptr.0].value = ValueDatum::Instruction(new_ptr);
+    }
+
+    // The aggregate's own pointers are now unused.
+    let local_ptrs = function
+        .instruction_iter(context)
+        .filter(|(_, inst)| {
+            matches!(
+                inst.get_instruction(context),
+                Some(Instruction::GetLocal(var)) if *var == local_var
+            )
+        })
+        .collect::<Vec<_>>();
+    for (block, local_ptr) in local_ptrs {
+        block.remove_instruction(context, local_ptr);
+    }
+    function.remove_locals(context, &vec![name]);
+}
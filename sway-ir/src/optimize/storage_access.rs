@@ -0,0 +1,285 @@
+//! ## Redundant Storage Access Elimination
+//!
+//! Each `state_load_word` costs an `SRW`, so a function which reads the same storage slot several
+//! times pays for it several times.  This optimization replaces a read of a slot with the word
+//! already known to be in it, either from an earlier read of the slot or from the last write to
+//! it, as long as nothing between them may have written to storage.
+//!
+//! Slots are identified by the constant value of their key.  A key is constant when it points to
+//! a local variable which is only ever initialized to, or stored, a single constant `b256` and is
+//! otherwise only used as a storage key, which is how keys are emitted for storage accesses.
+//!
+//! The words known to be in each slot are tracked through the whole function, keeping a word at a
+//! join only if it is known along every incoming edge.  Writes to slots with non-constant keys,
+//! quad word writes and clears, contract calls and `asm` blocks which may write to storage
+//! forget every known word.  So do calls to functions declared `#[storage(write)]`, and calls to
+//! any other function which writes to storage or calls a contract, itself or through a function
+//! it calls.
+
+use crate::{
+    metadata::storage_attribute, AnalysisResults, AsmBlock, Block, ConstantValue, Context,
+    FuelVmInstruction, Function, Instruction, IrError, LocalVar, Pass, PassMutability, PostOrder,
+    ScopedPass, Value, POSTORDER_NAME,
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+pub const STORAGE_ACCESS_NAME: &str = "storageaccess";
+
+pub fn create_storage_access_pass() -> Pass {
+    Pass {
+        name: STORAGE_ACCESS_NAME,
+        descr: "Redundant storage access elimination.",
+        deps: vec![POSTORDER_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(storage_access)),
+    }
+}
+
+/// The words known to be in storage slots, by the value of the slot's key.
+type KnownSlots = FxHashMap<[u8; 32], Value>;
+
+pub fn storage_access(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let po: &PostOrder = analyses.get_analysis_result(function);
+    let key_locals = find_key_locals(context, function);
+    if key_locals.is_empty() {
+        return Ok(false);
+    }
+    let rpo = po.po_to_block.iter().rev().copied().collect::<Vec<_>>();
+    let mut writers = FxHashMap::<Function, bool>::default();
+
+    // Find the slots known on entry to each block, iterating until they no longer shrink.
+    let mut entry_slots = FxHashMap::<Block, KnownSlots>::default();
+    let mut exit_slots = FxHashMap::<Block, KnownSlots>::default();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in &rpo {
+            let slots = meet_preds(context, *block, &exit_slots);
+            let mut exit = slots.clone();
+            transfer_block(context, *block, &key_locals, &mut writers, &mut exit, None);
+            entry_slots.insert(*block, slots);
+            if exit_slots.get(block) != Some(&exit) {
+                exit_slots.insert(*block, exit);
+                changed = true;
+            }
+        }
+    }
+
+    let mut replacements = FxHashMap::<Value, Value>::default();
+    for block in &rpo {
+        let mut slots = entry_slots.remove(block).unwrap_or_default();
+        transfer_block(
+            context,
+            *block,
+            &key_locals,
+            &mut writers,
+            &mut slots,
+            Some(&mut replacements),
+        );
+    }
+
+    if replacements.is_empty() {
+        return Ok(false);
+    }
+
+    function.replace_values(context, &replacements, None);
+    for block in rpo {
+        block.remove_instructions(context, |inst| replacements.contains_key(&inst));
+    }
+
+    Ok(true)
+}
+
+/// The slots known along every edge into the block whose predecessor has been visited.
+fn meet_preds(
+    context: &Context,
+    block: Block,
+    exit_slots: &FxHashMap<Block, KnownSlots>,
+) -> KnownSlots {
+    let mut pred_slots = block
+        .pred_iter(context)
+        .filter_map(|pred| exit_slots.get(pred));
+    let Some(first) = pred_slots.next() else {
+        return KnownSlots::default();
+    };
+    let mut slots = first.clone();
+    for other in pred_slots {
+        slots.retain(|key, word| other.get(key) == Some(word));
+    }
+    slots
+}
+
+/// Update `slots` with the storage accesses of the block.  When `replacements` is given, reads of
+/// known slots are recorded there to be replaced with the known word.
+fn transfer_block(
+    context: &Context,
+    block: Block,
+    key_locals: &FxHashMap<LocalVar, [u8; 32]>,
+    writers: &mut FxHashMap<Function, bool>,
+    slots: &mut KnownSlots,
+    mut replacements: Option<&mut FxHashMap<Value, Value>>,
+) {
+    let key_value = |key: &Value| match key.get_instruction(context) {
+        Some(Instruction::GetLocal(local_var)) => key_locals.get(local_var).copied(),
+        _ => None,
+    };
+    for inst in block.instruction_iter(context) {
+        match inst.get_instruction(context).unwrap() {
+            Instruction::FuelVm(FuelVmInstruction::StateLoadWord(key)) => {
+                let Some(key) = key_value(key) else {
+                    continue;
+                };
+                match slots.get(&key) {
+                    Some(word) => {
+                        if let Some(replacements) = replacements.as_deref_mut() {
+                            replacements.insert(inst, *word);
+                        }
+                    }
+                    None => {
+                        slots.insert(key, inst);
+                    }
+                }
+            }
+            Instruction::FuelVm(FuelVmInstruction::StateStoreWord { stored_val, key }) => {
+                match key_value(key) {
+                    Some(key) => {
+                        slots.insert(key, *stored_val);
+                    }
+                    None => slots.clear(),
+                }
+            }
+            Instruction::FuelVm(FuelVmInstruction::StateStoreQuadWord { .. })
+            | Instruction::FuelVm(FuelVmInstruction::StateClear { .. })
+            | Instruction::ContractCall { .. } => slots.clear(),
+            Instruction::AsmBlock(asm, _) if asm_may_write_storage(context, asm) => slots.clear(),
+            Instruction::Call(callee, _) if may_write_storage(context, *callee, writers) => {
+                slots.clear()
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Find the local variables which are only used as storage keys and always hold the same
+/// constant, along with that constant.
+fn find_key_locals(context: &Context, function: Function) -> FxHashMap<LocalVar, [u8; 32]> {
+    let mut keys = FxHashMap::<LocalVar, Option<[u8; 32]>>::default();
+    for (_, local_var) in function.locals_iter(context) {
+        if local_var.get_inner_type(context).is_b256(context) {
+            let initializer =
+                local_var
+                    .get_initializer(context)
+                    .and_then(|constant| match constant.value {
+                        ConstantValue::B256(bytes) => Some(bytes),
+                        _ => None,
+                    });
+            keys.insert(*local_var, initializer);
+        }
+    }
+
+    let local_of = |value: &Value| match value.get_instruction(context) {
+        Some(Instruction::GetLocal(local_var)) => Some(*local_var),
+        _ => None,
+    };
+    let mut disqualified = FxHashSet::<LocalVar>::default();
+    for (_, inst) in function.instruction_iter(context) {
+        let instruction = inst.get_instruction(context).unwrap();
+        let (allowed_key, stored) = match instruction {
+            // Loading storage into the key itself overwrites it.
+            Instruction::FuelVm(FuelVmInstruction::StateLoadQuadWord { load_val, key, .. }) => {
+                (Some(*key).filter(|key| key != load_val), None)
+            }
+            Instruction::FuelVm(
+                FuelVmInstruction::StateLoadWord(key)
+                | FuelVmInstruction::StateStoreWord { key, .. }
+                | FuelVmInstruction::StateStoreQuadWord { key, .. }
+                | FuelVmInstruction::StateClear { key, .. },
+            ) => (Some(*key), None),
+            Instruction::Store {
+                dst_val_ptr,
+                stored_val,
+            } => (None, Some((*dst_val_ptr, *stored_val))),
+            _ => (None, None),
+        };
+
+        if let Some((dst_val_ptr, stored_val)) = stored {
+            if let Some(local_var) = local_of(&dst_val_ptr) {
+                let constant = stored_val
+                    .get_constant(context)
+                    .and_then(|constant| match constant.value {
+                        ConstantValue::B256(bytes) => Some(bytes),
+                        _ => None,
+                    });
+                match (keys.get_mut(&local_var), constant) {
+                    (Some(key @ None), Some(bytes)) => *key = Some(bytes),
+                    (Some(Some(key)), Some(bytes)) if *key == bytes => (),
+                    _ => {
+                        disqualified.insert(local_var);
+                    }
+                }
+            }
+        }
+
+        // Any other use of a pointer to the local may write to it or let it escape.
+        for operand in instruction.get_operands() {
+            let is_allowed = Some(operand) == allowed_key
+                || stored.map_or(false, |(dst_val_ptr, _)| dst_val_ptr == operand);
+            if !is_allowed {
+                if let Some(local_var) = local_of(&operand) {
+                    disqualified.insert(local_var);
+                }
+            }
+        }
+    }
+
+    keys.into_iter()
+        .filter(|(local_var, _)| !disqualified.contains(local_var))
+        .filter_map(|(local_var, key)| key.map(|key| (local_var, key)))
+        .collect()
+}
+
+/// Whether calling `function` may write to storage, caching the answer in `writers`.
+fn may_write_storage(
+    context: &Context,
+    function: Function,
+    writers: &mut FxHashMap<Function, bool>,
+) -> bool {
+    if let Some(writes) = writers.get(&function) {
+        return *writes;
+    }
+    // Assume a recursive call may write until the callee has been checked.
+    writers.insert(function, true);
+
+    // Functions which are only declared to read storage are still checked for contract calls,
+    // which may call back into this contract and write to its storage.
+    let writes = match storage_attribute(context, function.get_metadata(context)) {
+        Some("writes" | "readswrites") => true,
+        _ => function.instruction_iter(context).any(|(_, inst)| {
+            match inst.get_instruction(context).unwrap() {
+                Instruction::FuelVm(
+                    FuelVmInstruction::StateStoreWord { .. }
+                    | FuelVmInstruction::StateStoreQuadWord { .. }
+                    | FuelVmInstruction::StateClear { .. },
+                )
+                | Instruction::ContractCall { .. } => true,
+                Instruction::AsmBlock(asm, _) => asm_may_write_storage(context, asm),
+                Instruction::Call(callee, _) => may_write_storage(context, *callee, writers),
+                _ => false,
+            }
+        }),
+    };
+    writers.insert(function, writes);
+    writes
+}
+
+/// Whether an `asm` block contains an instruction which may write to storage.
+fn asm_may_write_storage(context: &Context, asm: &AsmBlock) -> bool {
+    asm.get_content(context)
+        .body
+        .iter()
+        .any(|asm_inst| matches!(asm_inst.name.as_str(), "sww" | "swwq" | "scwq" | "call"))
+}
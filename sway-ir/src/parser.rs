@@ -31,14 +31,11 @@ mod ir_builder {
     peg::parser! {
         pub(in crate::parser) grammar parser() for str {
             pub(in crate::parser) rule ir_descrs() -> IrAstModule
-                = _ sop:script_or_predicate() eoi() {
-                    sop
-                }
-                / _ c:contract() eoi() {
-                    c
+                = _ m:module() eoi() {
+                    m
                 }
 
-            rule script_or_predicate() -> IrAstModule
+            rule module() -> IrAstModule
                 = kind:module_kind() "{" _ configs:init_config()* _ fn_decls:fn_decl()* "}" _
                   metadata:metadata_decls() {
                     IrAstModule {
@@ -50,20 +47,10 @@ mod ir_builder {
                 }
 
             rule module_kind() -> Kind
-                = "script" _ { Kind::Script }
+                = "contract" _ { Kind::Contract }
+                / "library" _ { Kind::Library }
                 / "predicate" _ { Kind::Predicate }
-
-            rule contract() -> IrAstModule
-                = "contract" _ "{" _
-                  configs:init_config()* fn_decls:fn_decl()* "}" _
-                  metadata:metadata_decls() {
-                    IrAstModule {
-                        kind: crate::module::Kind::Contract,
-                        configs,
-                        fn_decls,
-                        metadata
-                    }
-                }
+                / "script" _ { Kind::Script }
 
             rule init_config() -> IrAstConfig
                 = value_name:value_assign() "config" _ val_ty:ast_ty() cv:constant()
@@ -200,6 +187,7 @@ mod ir_builder {
                 / op_ret()
                 / op_revert()
                 / op_smo()
+                / op_state_clear()
                 / op_state_load_quad_word()
                 / op_state_load_word()
                 / op_state_store_quad_word()
@@ -262,7 +250,13 @@ mod ir_builder {
                 }
 
             rule op_const() -> IrAstOperation
-                = "const" _ val_ty:ast_ty() cv:constant() {
+                = "const" _ val_ty:ast_ty() "undef" _ {
+                    IrAstOperation::Const(val_ty.clone(), IrAstConst {
+                        value: IrAstConstValue::Undef(val_ty),
+                        meta_idx: None,
+                    })
+                }
+                / "const" _ val_ty:ast_ty() cv:constant() {
                     IrAstOperation::Const(val_ty, cv)
                 }
 
@@ -471,10 +465,8 @@ mod ir_builder {
                 }
 
             rule array_const() -> IrAstConstValue
-                = "[" _ els:(field_or_element_const() ++ comma()) "]" _ {
-                    let el_ty = els[0].0.clone();
-                    let els = els.into_iter().map(|(_, cv)| cv).collect::<Vec<_>>();
-                    IrAstConstValue::Array(el_ty, els)
+                = "[" _ els:(field_or_element_const() ** comma()) "]" _ {
+                    IrAstConstValue::Array(els)
                 }
 
             rule struct_const() -> IrAstConstValue
@@ -490,16 +482,21 @@ mod ir_builder {
                     (ty.clone(), IrAstConst { value: IrAstConstValue::Undef(ty), meta_idx: None })
                 }
 
+            // Type keywords must not be followed by an identifier character, so that names which
+            // merely start with one, like `bool_val` or `ptr_offset`, are still identifiers.
             rule ast_ty() -> IrAstTy
-                = ("unit" / "()") _ { IrAstTy::Unit }
-                / "bool" _ { IrAstTy::Bool }
-                / "u64" _ { IrAstTy::U64 }
-                / "b256" _ { IrAstTy::B256 }
+                = ("unit" !id_char() / "()") _ { IrAstTy::Unit }
+                / "bool" !id_char() _ { IrAstTy::Bool }
+                / "u" width:$("8" / "16" / "32" / "64") !id_char() _ {
+                    IrAstTy::Uint(width.parse().unwrap())
+                }
+                / "b256" !id_char() _ { IrAstTy::B256 }
                 / "string" _ "<" _ sz:decimal() ">" _ { IrAstTy::String(sz) }
+                / "slice" !id_char() _ { IrAstTy::Slice }
                 / array_ty()
                 / struct_ty()
                 / union_ty()
-                / "ptr" _ ty:ast_ty() { IrAstTy::Ptr(Box::new(ty)) }
+                / "ptr" !id_char() _ ty:ast_ty() { IrAstTy::Ptr(Box::new(ty)) }
 
             rule array_ty() -> IrAstTy
                 = "[" _ ty:ast_ty() ";" _ c:decimal() "]" _ {
@@ -557,9 +554,9 @@ mod ir_builder {
                 / "!" idx:dec_digits() __ {
                     IrMetadatum::Index(idx)
                 }
-                / ['"'] s:$(([^ '"' | '\\'] / ['\\'] ['\\' | '"' ])+) ['"'] __ {
-                    // Metadata strings are printed with '\\' escaped on parsing we unescape it.
-                    IrMetadatum::String(s.to_owned().replace("\\\\", "\\"))
+                / ['"'] s:$(([^ '"' | '\\'] / ['\\'] [_])*) ['"'] __ {
+                    // Metadata strings are printed with their `Debug` escapes, which we undo here.
+                    IrMetadatum::String(unescape_md_string(s))
                 }
                 / tag:$(id_char0() id_char()*) __ els:metadata_item()* {
                     IrMetadatum::Struct(tag.to_owned(), els)
@@ -726,7 +723,7 @@ mod ir_builder {
         B256([u8; 32]),
         Number(u64),
         String(Vec<u8>),
-        Array(IrAstTy, Vec<IrAstConst>),
+        Array(Vec<(IrAstTy, IrAstConst)>),
         Struct(Vec<(IrAstTy, IrAstConst)>),
     }
 
@@ -753,10 +750,10 @@ mod ir_builder {
                 IrAstConstValue::B256(bs) => ConstantValue::B256(*bs),
                 IrAstConstValue::Number(n) => ConstantValue::Uint(*n),
                 IrAstConstValue::String(bs) => ConstantValue::String(bs.clone()),
-                IrAstConstValue::Array(el_ty, els) => {
+                IrAstConstValue::Array(els) => {
                     let els: Vec<_> = els
                         .iter()
-                        .map(|cv| cv.value.as_constant(context, el_ty.clone()))
+                        .map(|(el_ty, cv)| cv.value.as_constant(context, el_ty.clone()))
                         .collect();
                     ConstantValue::Array(els)
                 }
@@ -779,11 +776,17 @@ mod ir_builder {
 
         fn as_value(&self, context: &mut Context, val_ty: IrAstTy) -> Value {
             match self {
-                IrAstConstValue::Undef(_) => unreachable!("Can't convert 'undef' to a value."),
+                IrAstConstValue::Undef(_) => {
+                    let undef_const = Constant::get_undef(val_ty.to_ir_type(context));
+                    Value::new_constant(context, undef_const)
+                }
                 IrAstConstValue::Unit => Constant::get_unit(context),
                 IrAstConstValue::Bool(b) => Constant::get_bool(context, *b),
                 IrAstConstValue::B256(bs) => Constant::get_b256(context, *bs),
-                IrAstConstValue::Number(n) => Constant::get_uint(context, 64, *n),
+                IrAstConstValue::Number(n) => match val_ty {
+                    IrAstTy::Uint(width) => Constant::get_uint(context, width, *n),
+                    _ => Constant::get_uint(context, 64, *n),
+                },
                 IrAstConstValue::String(s) => Constant::get_string(context, s.clone()),
                 IrAstConstValue::Array(..) => {
                     let array_const = self.as_constant(context, val_ty);
@@ -801,12 +804,13 @@ mod ir_builder {
     enum IrAstTy {
         Unit,
         Bool,
-        U64,
+        Uint(u8),
         B256,
         String(u64),
         Array(Box<IrAstTy>, u64),
         Union(Vec<IrAstTy>),
         Struct(Vec<IrAstTy>),
+        Slice,
         Ptr(Box<IrAstTy>),
     }
 
@@ -815,7 +819,7 @@ mod ir_builder {
             match self {
                 IrAstTy::Unit => Type::get_unit(context),
                 IrAstTy::Bool => Type::get_bool(context),
-                IrAstTy::U64 => Type::get_uint64(context),
+                IrAstTy::Uint(width) => Type::new_uint(context, *width),
                 IrAstTy::B256 => Type::get_b256(context),
                 IrAstTy::String(n) => Type::new_string(context, *n),
                 IrAstTy::Array(el_ty, count) => {
@@ -830,6 +834,7 @@ mod ir_builder {
                     let tys = tys.iter().map(|ty| ty.to_ir_type(context)).collect();
                     Type::new_struct(context, tys)
                 }
+                IrAstTy::Slice => Type::get_slice(context),
                 IrAstTy::Ptr(ty) => {
                     let inner_ty = ty.to_ir_type(context);
                     Type::new_ptr(context, inner_ty)
@@ -1009,9 +1014,11 @@ mod ir_builder {
                                         val_map.get(&var).cloned().unwrap()
                                     }
                                     IrAstAsmArgInit::Imm(cv) => {
-                                        cv.value.as_value(context, IrAstTy::U64).add_metadatum(
+                                        cv.value.as_value(context, IrAstTy::Uint(64)).add_metadatum(
                                             context,
-                                            self.md_map.get(cv.meta_idx.as_ref().unwrap()).copied(),
+                                            cv.meta_idx
+                                                .as_ref()
+                                                .and_then(|mdi| self.md_map.get(mdi).copied()),
                                         )
                                     }
                                 }),
@@ -1213,7 +1220,10 @@ mod ir_builder {
                             *val_map.get(&src_name).unwrap(),
                         )
                         .add_metadatum(context, opt_metadata),
-                    IrAstOperation::Nop => block.ins(context).nop(),
+                    IrAstOperation::Nop => block
+                        .ins(context)
+                        .nop()
+                        .add_metadatum(context, opt_metadata),
                     IrAstOperation::PtrToInt(val, ty) => {
                         let to_ty = ty.to_ir_type(context);
                         block
@@ -1407,6 +1417,36 @@ mod ir_builder {
         md_map
     }
 
+    /// Undo the escapes in a metadata string printed with its `Debug` representation.
+    fn unescape_md_string(s: &str) -> String {
+        let mut unescaped = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                unescaped.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some('t') => unescaped.push('\t'),
+                Some('0') => unescaped.push('\0'),
+                Some('u') => {
+                    // Unicode escapes are of the form `\u{7f}`.
+                    let code = chars
+                        .by_ref()
+                        .skip(1)
+                        .take_while(|ch| *ch != '}')
+                        .collect::<String>();
+                    unescaped.extend(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32));
+                }
+                Some(ch) => unescaped.push(ch),
+                None => (),
+            }
+        }
+        unescaped
+    }
+
     fn string_to_hex<const N: usize>(s: &str) -> [u8; N] {
         let mut bytes: [u8; N] = [0; N];
         let mut cur_byte: u8 = 0;
@@ -1,11 +1,13 @@
 use crate::{
     create_arg_demotion_pass, create_const_combine_pass, create_const_demotion_pass,
-    create_dce_pass, create_dom_fronts_pass, create_dominators_pass, create_escaped_symbols_pass,
-    create_func_dce_pass, create_inline_in_main_pass, create_inline_in_module_pass,
-    create_mem2reg_pass, create_memcpyopt_pass, create_misc_demotion_pass,
-    create_module_printer_pass, create_module_verifier_pass, create_postorder_pass,
-    create_ret_demotion_pass, create_simplify_cfg_pass, Context, Function, IrError, Module,
-    CONSTCOMBINE_NAME, DCE_NAME, FUNC_DCE_NAME, INLINE_MODULE_NAME, MEM2REG_NAME, SIMPLIFYCFG_NAME,
+    create_cse_pass, create_dce_pass, create_dom_fronts_pass, create_dominators_pass,
+    create_escaped_symbols_pass, create_func_dce_pass, create_inline_in_main_pass,
+    create_inline_in_module_pass, create_licm_pass, create_loops_pass, create_mem2reg_pass,
+    create_memcpyopt_pass, create_misc_demotion_pass, create_module_printer_pass,
+    create_module_verifier_pass, create_postorder_pass, create_ret_demotion_pass,
+    create_simplify_cfg_pass, create_sroa_pass, create_storage_access_pass, Context, Function,
+    IrError, Module, CONSTCOMBINE_NAME, DCE_NAME, FUNC_DCE_NAME, INLINE_MODULE_NAME, MEM2REG_NAME,
+    SIMPLIFYCFG_NAME, SROA_NAME,
 };
 use downcast_rs::{impl_downcast, Downcast};
 use rustc_hash::FxHashMap;
@@ -144,6 +146,7 @@ impl AnalysisResults {
 pub struct PassManager {
     passes: FxHashMap<&'static str, Pass>,
     analyses: AnalysisResults,
+    verify_between_passes: bool,
 }
 
 impl PassManager {
@@ -176,6 +179,12 @@ impl PassManager {
         pass_name
     }
 
+    /// Verify the IR after every transformation which modifies it, reporting the pass which broke
+    /// it.  This is always done in debug builds.
+    pub fn set_verify_between_passes(&mut self, verify: bool) {
+        self.verify_between_passes = verify;
+    }
+
     fn actually_run(&mut self, ir: &mut Context, pass: &'static str) -> Result<bool, IrError> {
         let mut modified = false;
         let pass_t = self.passes.get(pass).expect("Unregistered pass");
@@ -228,6 +237,13 @@ impl PassManager {
                 }
             }
         }
+
+        if modified && (cfg!(debug_assertions) || self.verify_between_passes) {
+            for m in ir.module_iter() {
+                ir.verify_module(&ir.modules[m.0])
+                    .map_err(|err| IrError::VerifyAfterPass(pass.to_owned(), Box::new(err)))?;
+            }
+        }
         Ok(modified)
     }
 
@@ -302,9 +318,11 @@ pub fn register_known_passes(pm: &mut PassManager) {
     pm.register(create_dominators_pass());
     pm.register(create_dom_fronts_pass());
     pm.register(create_escaped_symbols_pass());
+    pm.register(create_loops_pass());
     pm.register(create_module_printer_pass());
     pm.register(create_module_verifier_pass());
     // Optimization passes.
+    pm.register(create_sroa_pass());
     pm.register(create_mem2reg_pass());
     pm.register(create_inline_in_module_pass());
     pm.register(create_inline_in_main_pass());
@@ -317,12 +335,16 @@ pub fn register_known_passes(pm: &mut PassManager) {
     pm.register(create_ret_demotion_pass());
     pm.register(create_misc_demotion_pass());
     pm.register(create_memcpyopt_pass());
+    pm.register(create_cse_pass());
+    pm.register(create_licm_pass());
+    pm.register(create_storage_access_pass());
 }
 
 pub fn create_o1_pass_group() -> PassGroup {
     // Create a configuration to specify which passes we want to run now.
     let mut o1 = PassGroup::default();
     // Configure to run our passes.
+    o1.append_pass(SROA_NAME);
     o1.append_pass(MEM2REG_NAME);
     o1.append_pass(INLINE_MODULE_NAME);
     o1.append_pass(CONSTCOMBINE_NAME);
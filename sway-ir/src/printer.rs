@@ -882,12 +882,10 @@ fn asm_block_to_doc(
                 .append(
                     return_name
                         .as_ref()
-                        .map(|rn| {
-                            Doc::text(format!(" -> {} {rn}", return_type.as_string(context)))
-                                .append(md_namer.md_idx_to_doc(context, metadata))
-                        })
+                        .map(|rn| Doc::text(format!(" -> {} {rn}", return_type.as_string(context))))
                         .unwrap_or(Doc::Empty),
                 )
+                .append(md_namer.md_idx_to_doc(context, metadata))
                 .append(Doc::text(" {")),
         ))
         .append(Doc::indent(
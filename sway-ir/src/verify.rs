@@ -4,20 +4,23 @@
 //! consistent valid state, using the functions in this module.
 
 use crate::{
-    block::BlockContent,
+    analysis::dominator::{compute_dom_tree, compute_post_order, dominates, DomTree},
+    block::{Block, BlockContent},
     context::Context,
     error::IrError,
     function::{Function, FunctionContent},
     instruction::{FuelVmInstruction, Instruction, Predicate},
     irtype::Type,
     local_var::LocalVar,
-    metadata::{MetadataIndex, Metadatum},
+    metadata::{storage_attribute, MetadataIndex, Metadatum},
     module::ModuleContent,
     value::{Value, ValueDatum},
     AnalysisResult, AnalysisResultT, AnalysisResults, BinaryOpKind, BlockArgument,
     BranchToWithArgs, Module, Pass, PassMutability, ScopedPass, TypeOption, UnaryOpKind,
 };
 
+use rustc_hash::FxHashMap;
+
 pub struct ModuleVerifierResult;
 impl AnalysisResultT for ModuleVerifierResult {}
 
@@ -51,9 +54,11 @@ impl<'eng> Context<'eng> {
         Ok(self)
     }
 
-    fn verify_module(&self, module: &ModuleContent) -> Result<(), IrError> {
+    pub(crate) fn verify_module(&self, module: &ModuleContent) -> Result<(), IrError> {
         for function in &module.functions {
-            self.verify_function(module, function)?;
+            self.verify_function(module, function).map_err(|err| {
+                IrError::VerifyFunction(function.get_name(self).to_owned(), Box::new(err))
+            })?;
         }
         Ok(())
     }
@@ -74,9 +79,25 @@ impl<'eng> Context<'eng> {
             }
         }
 
+        let mut def_sites = FxHashMap::default();
+        for block in function.block_iter(self) {
+            for arg in block.arg_iter(self) {
+                def_sites.insert(*arg, (block, None));
+            }
+            for (idx, ins) in block.instruction_iter(self).enumerate() {
+                def_sites.insert(ins, (block, Some(idx)));
+            }
+        }
+        let po = compute_post_order(self, function);
+        let facts = FunctionFacts {
+            dom_tree: compute_dom_tree(self, &po, *function),
+            def_sites,
+            storage_attr: storage_attribute(self, function.get_metadata(self)),
+        };
+
         let function = &self.functions[function.0];
         for block in &function.blocks {
-            self.verify_block(cur_module, function, &self.blocks[block.0])?;
+            self.verify_block(cur_module, function, *block, &facts)?;
         }
         self.verify_metadata(function.metadata)?;
         Ok(())
@@ -86,10 +107,16 @@ impl<'eng> Context<'eng> {
         &self,
         cur_module: &ModuleContent,
         cur_function: &FunctionContent,
-        block: &BlockContent,
+        block_handle: Block,
+        facts: &FunctionFacts,
     ) -> Result<(), IrError> {
-        if block.instructions.len() <= 1 && block.preds.is_empty() {
-            // Empty unreferenced blocks are a harmless artefact.
+        let block = &self.blocks[block_handle.0];
+        // Empty unreferenced blocks are a harmless artefact. The entry block has no predecessors
+        // either, but is always executed, so its instructions are checked even when there's
+        // only one.
+        let is_entry = cur_function.blocks.first() == Some(&block_handle);
+        let max_unchecked = if is_entry { 0 } else { 1 };
+        if block.instructions.len() <= max_unchecked && block.preds.is_empty() {
             return Ok(());
         }
 
@@ -105,6 +132,8 @@ impl<'eng> Context<'eng> {
             cur_module,
             cur_function,
             cur_block: block,
+            cur_block_handle: block_handle,
+            facts,
         }
         .verify_instructions()?;
 
@@ -159,119 +188,189 @@ impl<'eng> Context<'eng> {
     }
 }
 
+/// What needs to be known about a function to verify its instructions beyond their types.
+struct FunctionFacts<'a> {
+    dom_tree: DomTree,
+    /// The block defining each argument and instruction, and the instruction's index in it.
+    def_sites: FxHashMap<Value, (Block, Option<usize>)>,
+    /// The `storage` attribute of the function, if it has one.
+    storage_attr: Option<&'a str>,
+}
+
 struct InstructionVerifier<'a, 'eng> {
     context: &'a Context<'eng>,
     cur_module: &'a ModuleContent,
     cur_function: &'a FunctionContent,
     cur_block: &'a BlockContent,
+    cur_block_handle: Block,
+    facts: &'a FunctionFacts<'a>,
 }
 
 impl<'a, 'eng> InstructionVerifier<'a, 'eng> {
     fn verify_instructions(&self) -> Result<(), IrError> {
-        for ins in &self.cur_block.instructions {
-            let value_content = &self.context.values[ins.0];
-            if let ValueDatum::Instruction(instruction) = &value_content.value {
-                match instruction {
-                    Instruction::AsmBlock(..) => (),
-                    Instruction::BitCast(value, ty) => self.verify_bitcast(value, ty)?,
-                    Instruction::UnaryOp { op, arg } => self.verify_unary_op(op, arg)?,
-                    Instruction::BinaryOp { op, arg1, arg2 } => {
-                        self.verify_binary_op(op, arg1, arg2)?
-                    }
-                    Instruction::Branch(block) => self.verify_br(block)?,
-                    Instruction::Call(func, args) => self.verify_call(func, args)?,
-                    Instruction::CastPtr(val, ty) => self.verify_cast_ptr(val, ty)?,
-                    Instruction::Cmp(pred, lhs_value, rhs_value) => {
-                        self.verify_cmp(pred, lhs_value, rhs_value)?
+        for (idx, ins) in self.cur_block.instructions.iter().enumerate() {
+            self.verify_instruction(idx, ins).map_err(|err| {
+                IrError::VerifyInstruction(self.cur_block.label.clone(), idx, Box::new(err))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn verify_instruction(&self, idx: usize, ins: &Value) -> Result<(), IrError> {
+        let value_content = &self.context.values[ins.0];
+        if let ValueDatum::Instruction(instruction) = &value_content.value {
+            self.verify_operands_dominated(idx, instruction)?;
+            self.verify_storage_access(instruction)?;
+            match instruction {
+                Instruction::AsmBlock(..) => (),
+                Instruction::BitCast(value, ty) => self.verify_bitcast(value, ty)?,
+                Instruction::UnaryOp { op, arg } => self.verify_unary_op(op, arg)?,
+                Instruction::BinaryOp { op, arg1, arg2 } => {
+                    self.verify_binary_op(op, arg1, arg2)?
+                }
+                Instruction::Branch(block) => self.verify_br(block)?,
+                Instruction::Call(func, args) => self.verify_call(func, args)?,
+                Instruction::CastPtr(val, ty) => self.verify_cast_ptr(val, ty)?,
+                Instruction::Cmp(pred, lhs_value, rhs_value) => {
+                    self.verify_cmp(pred, lhs_value, rhs_value)?
+                }
+                Instruction::ConditionalBranch {
+                    cond_value,
+                    true_block,
+                    false_block,
+                } => self.verify_cbr(cond_value, true_block, false_block)?,
+                Instruction::ContractCall {
+                    params,
+                    coins,
+                    asset_id,
+                    gas,
+                    ..
+                } => self.verify_contract_call(params, coins, asset_id, gas)?,
+
+                // XXX move the fuelvm verification into a module
+                Instruction::FuelVm(fuel_vm_instr) => match fuel_vm_instr {
+                    FuelVmInstruction::Gtf { index, tx_field_id } => {
+                        self.verify_gtf(index, tx_field_id)?
                     }
-                    Instruction::ConditionalBranch {
-                        cond_value,
-                        true_block,
-                        false_block,
-                    } => self.verify_cbr(cond_value, true_block, false_block)?,
-                    Instruction::ContractCall {
-                        params,
+                    FuelVmInstruction::Log {
+                        log_val,
+                        log_ty,
+                        log_id,
+                    } => self.verify_log(log_val, log_ty, log_id)?,
+                    FuelVmInstruction::ReadRegister(_) => (),
+                    FuelVmInstruction::Revert(val) => self.verify_revert(val)?,
+                    FuelVmInstruction::Smo {
+                        recipient,
+                        message,
+                        message_size,
                         coins,
-                        asset_id,
-                        gas,
-                        ..
-                    } => self.verify_contract_call(params, coins, asset_id, gas)?,
-
-                    // XXX move the fuelvm verification into a module
-                    Instruction::FuelVm(fuel_vm_instr) => match fuel_vm_instr {
-                        FuelVmInstruction::Gtf { index, tx_field_id } => {
-                            self.verify_gtf(index, tx_field_id)?
-                        }
-                        FuelVmInstruction::Log {
-                            log_val,
-                            log_ty,
-                            log_id,
-                        } => self.verify_log(log_val, log_ty, log_id)?,
-                        FuelVmInstruction::ReadRegister(_) => (),
-                        FuelVmInstruction::Revert(val) => self.verify_revert(val)?,
-                        FuelVmInstruction::Smo {
-                            recipient,
-                            message,
-                            message_size,
-                            coins,
-                        } => self.verify_smo(recipient, message, message_size, coins)?,
-                        FuelVmInstruction::StateClear {
-                            key,
-                            number_of_slots,
-                        } => self.verify_state_clear(key, number_of_slots)?,
-                        FuelVmInstruction::StateLoadWord(key) => {
-                            self.verify_state_load_word(key)?
-                        }
-                        FuelVmInstruction::StateLoadQuadWord {
-                            load_val: dst_val,
-                            key,
-                            number_of_slots,
-                        }
-                        | FuelVmInstruction::StateStoreQuadWord {
-                            stored_val: dst_val,
-                            key,
-                            number_of_slots,
-                        } => self.verify_state_access_quad(dst_val, key, number_of_slots)?,
-                        FuelVmInstruction::StateStoreWord {
-                            stored_val: dst_val,
-                            key,
-                        } => self.verify_state_store_word(dst_val, key)?,
-                    },
-                    Instruction::GetElemPtr {
-                        base,
-                        elem_ptr_ty,
-                        indices,
-                    } => self.verify_get_elem_ptr(base, elem_ptr_ty, indices)?,
-                    Instruction::GetLocal(local_var) => self.verify_get_local(local_var)?,
-                    Instruction::IntToPtr(value, ty) => self.verify_int_to_ptr(value, ty)?,
-                    Instruction::Load(ptr) => self.verify_load(ptr)?,
-                    Instruction::MemCopyBytes {
-                        dst_val_ptr,
-                        src_val_ptr,
-                        byte_len,
-                    } => self.verify_mem_copy_bytes(dst_val_ptr, src_val_ptr, byte_len)?,
-                    Instruction::MemCopyVal {
-                        dst_val_ptr,
-                        src_val_ptr,
-                    } => self.verify_mem_copy_val(dst_val_ptr, src_val_ptr)?,
-                    Instruction::Nop => (),
-                    Instruction::PtrToInt(val, ty) => self.verify_ptr_to_int(val, ty)?,
-                    Instruction::Ret(val, ty) => self.verify_ret(val, ty)?,
-                    Instruction::Store {
-                        dst_val_ptr,
-                        stored_val,
-                    } => self.verify_store(dst_val_ptr, stored_val)?,
-                };
-
-                // Verify the instruction metadata too.
-                self.context.verify_metadata(value_content.metadata)?;
-            } else {
-                unreachable!("Verify instruction is not an instruction.");
+                    } => self.verify_smo(recipient, message, message_size, coins)?,
+                    FuelVmInstruction::StateClear {
+                        key,
+                        number_of_slots,
+                    } => self.verify_state_clear(key, number_of_slots)?,
+                    FuelVmInstruction::StateLoadWord(key) => self.verify_state_load_word(key)?,
+                    FuelVmInstruction::StateLoadQuadWord {
+                        load_val: dst_val,
+                        key,
+                        number_of_slots,
+                    }
+                    | FuelVmInstruction::StateStoreQuadWord {
+                        stored_val: dst_val,
+                        key,
+                        number_of_slots,
+                    } => self.verify_state_access_quad(dst_val, key, number_of_slots)?,
+                    FuelVmInstruction::StateStoreWord {
+                        stored_val: dst_val,
+                        key,
+                    } => self.verify_state_store_word(dst_val, key)?,
+                },
+                Instruction::GetElemPtr {
+                    base,
+                    elem_ptr_ty,
+                    indices,
+                } => self.verify_get_elem_ptr(base, elem_ptr_ty, indices)?,
+                Instruction::GetLocal(local_var) => self.verify_get_local(local_var)?,
+                Instruction::IntToPtr(value, ty) => self.verify_int_to_ptr(value, ty)?,
+                Instruction::Load(ptr) => self.verify_load(ptr)?,
+                Instruction::MemCopyBytes {
+                    dst_val_ptr,
+                    src_val_ptr,
+                    byte_len,
+                } => self.verify_mem_copy_bytes(dst_val_ptr, src_val_ptr, byte_len)?,
+                Instruction::MemCopyVal {
+                    dst_val_ptr,
+                    src_val_ptr,
+                } => self.verify_mem_copy_val(dst_val_ptr, src_val_ptr)?,
+                Instruction::Nop => (),
+                Instruction::PtrToInt(val, ty) => self.verify_ptr_to_int(val, ty)?,
+                Instruction::Ret(val, ty) => self.verify_ret(val, ty)?,
+                Instruction::Store {
+                    dst_val_ptr,
+                    stored_val,
+                } => self.verify_store(dst_val_ptr, stored_val)?,
+            };
+
+            // Verify the instruction metadata too.
+            self.context.verify_metadata(value_content.metadata)?;
+        } else {
+            unreachable!("Verify instruction is not an instruction.");
+        }
+        Ok(())
+    }
+
+    fn verify_operands_dominated(
+        &self,
+        idx: usize,
+        instruction: &Instruction,
+    ) -> Result<(), IrError> {
+        // Instructions in unreachable blocks are never executed, so don't need checking.
+        if !self.facts.dom_tree.contains_key(&self.cur_block_handle) {
+            return Ok(());
+        }
+        for operand in instruction.get_operands() {
+            if matches!(
+                self.context.values[operand.0].value,
+                ValueDatum::Constant(_) | ValueDatum::Configurable(_)
+            ) {
+                continue;
+            }
+            let is_dominated = match self.facts.def_sites.get(&operand) {
+                Some((def_block, def_idx)) if *def_block == self.cur_block_handle => {
+                    *def_idx < Some(idx)
+                }
+                Some((def_block, _)) => {
+                    dominates(&self.facts.dom_tree, *def_block, self.cur_block_handle)
+                }
+                None => false,
+            };
+            if !is_dominated {
+                return Err(IrError::VerifyUseNotDominatedByDef);
             }
         }
         Ok(())
     }
 
+    fn verify_storage_access(&self, instruction: &Instruction) -> Result<(), IrError> {
+        // Functions without a storage attribute, e.g., those written by hand, aren't checked.
+        let Some(storage_attr) = self.facts.storage_attr else {
+            return Ok(());
+        };
+        match instruction {
+            Instruction::FuelVm(
+                FuelVmInstruction::StateStoreWord { .. }
+                | FuelVmInstruction::StateStoreQuadWord { .. }
+                | FuelVmInstruction::StateClear { .. },
+            ) if !matches!(storage_attr, "writes" | "readswrites") => {
+                Err(IrError::VerifyStorageAccessNotPermitted(
+                    "write".to_owned(),
+                    storage_attr.to_owned(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn verify_bitcast(&self, value: &Value, ty: &Type) -> Result<(), IrError> {
         // The bitsize of bools and unit is 1 which obviously won't match a typical uint.  LLVM
         // would use `trunc` or `zext` to make types match sizes before casting.  Until we have
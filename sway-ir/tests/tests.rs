@@ -2,10 +2,12 @@ use std::path::PathBuf;
 
 use sway_ir::{
     create_arg_demotion_pass, create_const_combine_pass, create_const_demotion_pass,
-    create_dce_pass, create_dom_fronts_pass, create_dominators_pass, create_escaped_symbols_pass,
+    create_cse_pass, create_dce_pass, create_dom_fronts_pass, create_dominators_pass,
+    create_escaped_symbols_pass, create_inline_in_module_pass, create_licm_pass, create_loops_pass,
     create_mem2reg_pass, create_memcpyopt_pass, create_misc_demotion_pass, create_postorder_pass,
-    create_ret_demotion_pass, create_simplify_cfg_pass, optimize as opt, Context, PassGroup,
-    PassManager,
+    create_ret_demotion_pass, create_simplify_cfg_pass, create_sroa_pass,
+    create_storage_access_pass, optimize as opt, AnalysisResults, Context, Function, IrError, Pass,
+    PassGroup, PassManager, PassMutability, ScopedPass,
 };
 use sway_types::SourceEngine;
 
@@ -82,7 +84,14 @@ fn inline() {
             .flat_map(|module| module.function_iter(ir))
             .collect::<Vec<_>>();
 
-        if params.iter().any(|&p| p == "all") {
+        if params.iter().any(|&p| p == "module") {
+            // Inline with the heuristics of the pass, as in the compiler's pipeline.
+            let mut pass_mgr = PassManager::default();
+            let mut pass_group = PassGroup::default();
+            let pass = pass_mgr.register(create_inline_in_module_pass());
+            pass_group.append_pass(pass);
+            pass_mgr.run(ir, &pass_group).unwrap()
+        } else if params.iter().any(|&p| p == "all") {
             // Just inline everything, replacing all CALL instructions.
             funcs.into_iter().fold(false, |acc, func| {
                 opt::inline_all_function_calls(ir, &func).unwrap() || acc
@@ -117,6 +126,50 @@ fn inline() {
     })
 }
 
+#[test]
+fn inline_decisions() {
+    let source_engine = SourceEngine::default();
+    let path = format!("{}/tests/inline/heuristics.ir", env!("CARGO_MANIFEST_DIR"));
+    let input = std::fs::read_to_string(path).unwrap();
+    let mut ir = sway_ir::parser::parse(&input, &source_engine).unwrap();
+    let mut pass_mgr = PassManager::default();
+    let mut pass_group = PassGroup::default();
+    let pass = pass_mgr.register(create_inline_in_module_pass());
+    pass_group.append_pass(pass);
+    pass_mgr.run(&mut ir, &pass_group).unwrap();
+
+    let reasons = ir
+        .inline_decisions()
+        .iter()
+        .filter(|decision| decision.caller == "main")
+        .map(|decision| (decision.callee.as_str(), decision.reason))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reasons,
+        [
+            ("recurse", opt::InlineReason::Recursive),
+            ("never", opt::InlineReason::AttributeNever),
+            ("never", opt::InlineReason::AttributeNever),
+            ("always", opt::InlineReason::AttributeAlways),
+            ("always", opt::InlineReason::AttributeAlways),
+            (
+                "add_four",
+                opt::InlineReason::Expensive {
+                    instrs_count: 5,
+                    budget: 4
+                }
+            ),
+            (
+                "add_four",
+                opt::InlineReason::Cheap {
+                    instrs_count: 5,
+                    budget: 5
+                }
+            ),
+        ]
+    );
+}
+
 // -------------------------------------------------------------------------------------------------
 
 // Clippy suggests using the map iterator below directly instead of collecting from it first, but
@@ -164,6 +217,23 @@ fn dce() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[test]
+fn licm() {
+    run_tests("licm", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        pass_mgr.register(create_dominators_pass());
+        pass_mgr.register(create_loops_pass());
+        pass_mgr.register(create_escaped_symbols_pass());
+        let pass = pass_mgr.register(create_licm_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[allow(clippy::needless_collect)]
 #[test]
 fn mem2reg() {
@@ -252,6 +322,53 @@ fn memcpyopt() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[test]
+fn cse() {
+    run_tests("cse", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        pass_mgr.register(create_dominators_pass());
+        let pass = pass_mgr.register(create_cse_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn sroa() {
+    run_tests("sroa", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        pass_mgr.register(create_dominators_pass());
+        pass_mgr.register(create_dom_fronts_pass());
+        pass_mgr.register(create_escaped_symbols_pass());
+        pass_group.append_pass(pass_mgr.register(create_sroa_pass()));
+        pass_group.append_pass(pass_mgr.register(create_mem2reg_pass()));
+        pass_group.append_pass(pass_mgr.register(create_dce_pass()));
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn storage_access() {
+    run_tests("storage_access", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        let pass = pass_mgr.register(create_storage_access_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[test]
 fn serialize() {
     // This isn't running a pass, it's just confirming that the IR can be loaded and printed, and
@@ -259,4 +376,117 @@ fn serialize() {
     run_tests("serialize", |_, _: &mut Context| true)
 }
 
+#[test]
+fn round_trip() {
+    // Printing parsed IR and parsing it again must give back exactly the same IR, for every test.
+    let source_engine = SourceEngine::default();
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let tests_dir: PathBuf = format!("{manifest_dir}/tests").into();
+    for sub_dir in std::fs::read_dir(tests_dir).unwrap() {
+        let sub_dir = sub_dir.unwrap().path();
+        // The `verify` tests are invalid IR and can't be parsed at all.
+        if !sub_dir.is_dir() || sub_dir.ends_with("verify") {
+            continue;
+        }
+        for entry in std::fs::read_dir(sub_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().map_or(true, |ext| ext != "ir") {
+                continue;
+            }
+
+            let input = std::fs::read_to_string(&path).unwrap();
+            let ir = sway_ir::parser::parse(&input, &source_engine)
+                .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+            let printed = sway_ir::printer::to_string(&ir);
+
+            let reparsed = sway_ir::parser::parse(&printed, &source_engine)
+                .unwrap_or_else(|err| panic!("{}: {err}\n{printed}", path.display()));
+            let reprinted = sway_ir::printer::to_string(&reparsed);
+            assert_eq!(
+                printed,
+                reprinted,
+                "{} failed IR (de)serialization.",
+                path.display()
+            );
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn verify() {
+    // Each test is invalid IR, expected to fail verification with the error in its first line.
+    let source_engine = SourceEngine::default();
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let dir: PathBuf = format!("{manifest_dir}/tests/verify").into();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        let input = std::fs::read_to_string(&path).unwrap();
+        let expected = input
+            .split('\n')
+            .next()
+            .and_then(|first_line| first_line.strip_prefix("// error: "))
+            .unwrap_or_else(|| panic!("{}: missing expected error.", path.display()));
+
+        match sway_ir::parser::parse(&input, &source_engine) {
+            Ok(_) => panic!("{}: verification unexpectedly passed.", path.display()),
+            Err(err) => assert!(
+                err.to_string().contains(expected),
+                "{}: expected error '{expected}', found '{err}'.",
+                path.display()
+            ),
+        }
+    }
+}
+
+#[test]
+fn verify_between_passes() {
+    // A broken pass which removes the first instruction even though its value is still used.
+    fn remove_first(
+        context: &mut Context,
+        _: &AnalysisResults,
+        function: Function,
+    ) -> Result<bool, IrError> {
+        let entry = function.get_entry_block(context);
+        let first = entry.instruction_iter(context).next().unwrap();
+        entry.remove_instruction(context, first);
+        Ok(true)
+    }
+
+    let source_engine = SourceEngine::default();
+    let mut ir = sway_ir::parser::parse(
+        "script {
+            fn main() -> u64 {
+                entry():
+                v0 = const u64 1
+                v1 = add v0, v0
+                v2 = add v1, v1
+                ret u64 v2
+            }
+        }",
+        &source_engine,
+    )
+    .unwrap();
+
+    let mut pass_mgr = PassManager::default();
+    pass_mgr.set_verify_between_passes(true);
+    let mut pass_group = PassGroup::default();
+    let pass = pass_mgr.register(Pass {
+        name: "removefirst",
+        descr: "Remove the first instruction.",
+        deps: vec![],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(remove_first)),
+    });
+    pass_group.append_pass(pass);
+
+    let err = pass_mgr.run(&mut ir, &pass_group).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "After pass 'removefirst': In function 'main': In block 'entry', instruction 0: \
+        Verification failed: An operand is not dominated by its definition."
+    );
+}
+
 // -------------------------------------------------------------------------------------------------
@@ -0,0 +1,298 @@
+use crate::{
+    capabilities::references::{declaration_span, method_decl_spans, span_location},
+    core::{
+        session::Session,
+        token::{get_range_from_span, SymbolKind, Token, TypedAstToken},
+    },
+};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, Location, Position,
+    Range, Url,
+};
+use std::sync::Arc;
+use sway_core::{language::ty, Engines};
+use sway_types::{Ident, Span, Spanned};
+
+/// The detail of the items of intrinsics, which have no declaration to navigate to.
+const INTRINSIC_DETAIL: &str = "intrinsic";
+/// The detail of the items of ABI methods that are called through a contract caller.
+const CONTRACT_CALL_DETAIL: &str = "contract call";
+
+/// Returns the call hierarchy item of the function that the token at `position` declares or
+/// calls.
+pub fn prepare_call_hierarchy(
+    session: Arc<Session>,
+    url: &Url,
+    position: Position,
+) -> Option<Vec<CallHierarchyItem>> {
+    let engines = session.engines.read();
+    let (ident, token) = session
+        .token_map()
+        .token_at_position(engines.se(), url, position)?;
+    // The names of calls keep the kind they were parsed with, which may be ambiguous.
+    if token.kind != SymbolKind::Function && call_expression_span(&token).is_none() {
+        return None;
+    }
+    let decl_span = declaration_span(&engines, &ident, &token)?;
+    let (decl_ident, decl_token) = session
+        .token_map()
+        .iter()
+        .find(|(ident, _)| ident.span() == decl_span)?;
+    let item = function_item(&session, &engines, &decl_ident, &decl_token)?;
+    Some(vec![item])
+}
+
+/// Returns the locations of the declarations that calls to the function of `item` resolve to.
+///
+/// Calls resolve to the declaration in the interface surface of a trait or an ABI when the
+/// implementation isn't known, as for contract calls, so the declarations of ABI methods are
+/// linked to all of their implementations. Trait methods are only linked to their
+/// implementations when `item` is the declaration in the trait itself.
+pub fn callee_locations(session: Arc<Session>, item: &CallHierarchyItem) -> Vec<Location> {
+    let engines = session.engines.read();
+    let Some((ident, token)) = function_decl_at(&session, &engines, item) else {
+        return vec![];
+    };
+    let method_decls = method_decl_spans(&session, &engines, ident.span());
+    let is_interface_fn = matches!(token.typed, Some(TypedAstToken::TypedTraitFn(_)));
+    let decl_spans = if method_decls.is_abi_method || is_interface_fn {
+        method_decls.spans
+    } else {
+        vec![ident.span()]
+    };
+    decl_spans
+        .iter()
+        .filter_map(|span| span_location(&session, &engines, span))
+        .collect()
+}
+
+/// Returns the calls in the session to the functions declared at `decl_locations`, grouped by
+/// the function they are made from.
+pub fn incoming_calls(
+    session: Arc<Session>,
+    decl_locations: &[Location],
+) -> Vec<CallHierarchyIncomingCall> {
+    let engines = session.engines.read();
+    let mut calls: Vec<CallHierarchyIncomingCall> = vec![];
+    for (ident, token) in session.token_map().iter() {
+        let Some(call_span) = call_expression_span(&token) else {
+            continue;
+        };
+        let is_call_to_decl = declaration_span(&engines, &ident, &token)
+            .and_then(|decl_span| span_location(&session, &engines, &decl_span))
+            .is_some_and(|location| decl_locations.contains(&location));
+        if !is_call_to_decl {
+            continue;
+        }
+        let Some(from) = enclosing_function(&session, &engines, &ident.span())
+            .and_then(|(ident, token)| function_item(&session, &engines, &ident, &token))
+        else {
+            continue;
+        };
+        let range = get_range_from_span(&call_span);
+        match calls.iter_mut().find(|call| same_item(&call.from, &from)) {
+            Some(call) => call.from_ranges.push(range),
+            None => calls.push(CallHierarchyIncomingCall {
+                from,
+                from_ranges: vec![range],
+            }),
+        }
+    }
+    calls
+}
+
+/// Returns the calls made from the function of `item`, grouped by the function they call.
+///
+/// Intrinsics and contract calls are told apart from the other functions by the detail of their
+/// items.
+pub fn outgoing_calls(
+    session: Arc<Session>,
+    item: &CallHierarchyItem,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let engines = session.engines.read();
+    let (_, token) = function_decl_at(&session, &engines, item)?;
+    let Some(TypedAstToken::TypedFunctionDeclaration(decl)) = &token.typed else {
+        return Some(vec![]);
+    };
+
+    let mut tokens: Vec<_> = session
+        .token_map()
+        .iter()
+        .filter(|(ident, _)| encloses(&decl.span, &ident.span()))
+        .collect();
+    tokens.sort_by_key(|(ident, _)| ident.span().start());
+
+    let mut calls: Vec<CallHierarchyOutgoingCall> = vec![];
+    for (ident, token) in tokens {
+        let Some(call_span) = call_expression_span(&token) else {
+            continue;
+        };
+        let Some(to) = callee_item(&session, &engines, &ident, &token, &item.uri) else {
+            continue;
+        };
+        let range = get_range_from_span(&call_span);
+        match calls.iter_mut().find(|call| same_item(&call.to, &to)) {
+            Some(call) => call.from_ranges.push(range),
+            None => calls.push(CallHierarchyOutgoingCall {
+                to,
+                from_ranges: vec![range],
+            }),
+        }
+    }
+    Some(calls)
+}
+
+/// Returns the span of the whole call expression if the token is the name of a called function
+/// or intrinsic.
+fn call_expression_span(token: &Token) -> Option<Span> {
+    match &token.typed {
+        Some(TypedAstToken::TypedExpression(expr)) => match expr.expression {
+            ty::TyExpressionVariant::FunctionApplication { .. }
+            | ty::TyExpressionVariant::IntrinsicFunction(_) => Some(expr.span.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the item of the function called by the call whose name is `ident`.
+///
+/// Intrinsics have no declaration, so their items point at the call in the document at `url`.
+fn callee_item(
+    session: &Session,
+    engines: &Engines,
+    ident: &Ident,
+    token: &Token,
+    url: &Url,
+) -> Option<CallHierarchyItem> {
+    let Some(TypedAstToken::TypedExpression(expr)) = &token.typed else {
+        return None;
+    };
+    match &expr.expression {
+        ty::TyExpressionVariant::IntrinsicFunction(_) => Some(call_hierarchy_item(
+            ident.as_str(),
+            lsp_types::SymbolKind::OPERATOR,
+            Some(INTRINSIC_DETAIL),
+            Location::new(url.clone(), get_range_from_span(&expr.span)),
+            get_range_from_span(&ident.span()),
+        )),
+        ty::TyExpressionVariant::FunctionApplication { fn_ref, .. } => {
+            let decl = engines.de().get_function(fn_ref);
+            let decl_span = declaration_span(engines, ident, token)?;
+            let location = span_location(session, engines, &decl.span)?;
+            let (kind, detail) = if decl.is_contract_call {
+                (lsp_types::SymbolKind::METHOD, Some(CONTRACT_CALL_DETAIL))
+            } else if decl.implementing_type.is_some() {
+                (lsp_types::SymbolKind::METHOD, None)
+            } else {
+                (lsp_types::SymbolKind::FUNCTION, None)
+            };
+            Some(call_hierarchy_item(
+                decl.name.as_str(),
+                kind,
+                detail,
+                location,
+                get_range_from_span(&decl_span),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the item of the function or trait method declared with the name `ident`.
+fn function_item(
+    session: &Session,
+    engines: &Engines,
+    ident: &Ident,
+    token: &Token,
+) -> Option<CallHierarchyItem> {
+    let (decl_span, kind) = match &token.typed {
+        Some(TypedAstToken::TypedFunctionDeclaration(decl)) => {
+            let kind = if decl.implementing_type.is_some() {
+                lsp_types::SymbolKind::METHOD
+            } else {
+                lsp_types::SymbolKind::FUNCTION
+            };
+            (decl.span.clone(), kind)
+        }
+        Some(TypedAstToken::TypedTraitFn(trait_fn)) => {
+            (trait_fn.span(), lsp_types::SymbolKind::METHOD)
+        }
+        _ => return None,
+    };
+    Some(call_hierarchy_item(
+        ident.as_str(),
+        kind,
+        None,
+        span_location(session, engines, &decl_span)?,
+        get_range_from_span(&ident.span()),
+    ))
+}
+
+fn call_hierarchy_item(
+    name: &str,
+    kind: lsp_types::SymbolKind,
+    detail: Option<&str>,
+    location: Location,
+    selection_range: Range,
+) -> CallHierarchyItem {
+    CallHierarchyItem {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        detail: detail.map(str::to_string),
+        uri: location.uri,
+        range: location.range,
+        selection_range,
+        data: None,
+    }
+}
+
+/// Returns the declaration of the function or trait method whose name is at the selection range
+/// of `item`.
+fn function_decl_at(
+    session: &Session,
+    engines: &Engines,
+    item: &CallHierarchyItem,
+) -> Option<(Ident, Token)> {
+    let location = Location::new(item.uri.clone(), item.selection_range);
+    session.token_map().iter().find(|(ident, token)| {
+        matches!(
+            token.typed,
+            Some(TypedAstToken::TypedFunctionDeclaration(_) | TypedAstToken::TypedTraitFn(_))
+        ) && declaration_span(engines, ident, token).as_ref() == Some(&ident.span())
+            && span_location(session, engines, &ident.span()).as_ref() == Some(&location)
+    })
+}
+
+/// Returns the name and the token of the innermost function declaration that contains `span`.
+fn enclosing_function(session: &Session, engines: &Engines, span: &Span) -> Option<(Ident, Token)> {
+    session
+        .token_map()
+        .iter()
+        .filter_map(|(ident, token)| match &token.typed {
+            Some(TypedAstToken::TypedFunctionDeclaration(decl))
+                if encloses(&decl.span, span)
+                    && declaration_span(engines, &ident, &token).as_ref()
+                        == Some(&ident.span()) =>
+            {
+                let size = decl.span.end() - decl.span.start();
+                Some((size, ident, token))
+            }
+            _ => None,
+        })
+        .min_by_key(|(size, ..)| *size)
+        .map(|(_, ident, token)| (ident, token))
+}
+
+/// Returns `true` if `span` lies within `outer`.
+fn encloses(outer: &Span, span: &Span) -> bool {
+    outer.source_id() == span.source_id()
+        && outer.start() <= span.start()
+        && span.end() <= outer.end()
+}
+
+/// Returns `true` if both items refer to the same function.
+fn same_item(a: &CallHierarchyItem, b: &CallHierarchyItem) -> bool {
+    a.name == b.name && a.uri == b.uri && a.selection_range == b.selection_range
+}
@@ -0,0 +1,281 @@
+use crate::core::{
+    session::Session,
+    token::{get_range_from_span, Token, TypedAstToken},
+};
+use forc_pkg::PackageManifestFile;
+use lsp_types::{
+    CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, Position, Range,
+    TextEdit, Url, WorkspaceEdit,
+};
+use std::{collections::HashMap, fmt, path::Path, sync::Arc};
+use sway_ast::{ItemKind, Module, UseTree};
+use sway_core::{language::ty, Engines};
+use sway_error::handler::Handler;
+use sway_types::{Ident, Spanned};
+
+pub(crate) const CODE_ACTION_IMPORT_TITLE: &str = "Import";
+
+/// The beginnings of the messages of the diagnostics for unresolved names, up to the quoted name.
+const UNRESOLVED_NAME_MESSAGES: [&str; 4] = [
+    "Could not find symbol \"",
+    "Variable \"",
+    "Unknown type name \"",
+    "Cannot find trait \"",
+];
+
+/// The path that an item is imported with.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ImportPath {
+    is_absolute: bool,
+    module_path: Vec<String>,
+    name: String,
+}
+
+impl fmt::Display for ImportPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_absolute {
+            write!(f, "::")?;
+        }
+        write!(f, "{}::{}", self.module_path.join("::"), self.name)
+    }
+}
+
+/// Returns a code action for each item that one of the unresolved names in `diagnostics` can be
+/// imported from. Names that match several items get one code action per item.
+pub(crate) fn code_actions(
+    session: &Session,
+    temp_uri: &Url,
+    uri: &Url,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeActionOrCommand> {
+    let (Ok(document), Ok(path)) = (session.get_text_document(temp_uri), temp_uri.to_file_path())
+    else {
+        return vec![];
+    };
+    let Ok(manifest) = PackageManifestFile::from_dir(&path) else {
+        return vec![];
+    };
+    let src = document.get_text();
+    let Ok(module) = sway_parse::parse_file(&Handler::default(), Arc::from(src.as_str()), None)
+    else {
+        return vec![];
+    };
+
+    let mut actions = vec![];
+    for diagnostic in diagnostics {
+        let Some(name) = unresolved_name(&diagnostic.message) else {
+            continue;
+        };
+        for import_path in import_paths(session, &manifest, &path, name) {
+            let changes =
+                HashMap::from([(uri.clone(), vec![import_edit(&module.value, &import_path)])]);
+            actions.push(CodeActionOrCommand::CodeAction(LspCodeAction {
+                title: format!("{CODE_ACTION_IMPORT_TITLE} `{import_path}`"),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }));
+        }
+    }
+    actions
+}
+
+/// Returns the name that the diagnostic `message` reports as unresolved.
+fn unresolved_name(message: &str) -> Option<&str> {
+    UNRESOLVED_NAME_MESSAGES.iter().find_map(|start| {
+        let rest = message.strip_prefix(start)?;
+        rest.split_once('"').map(|(name, _)| name)
+    })
+}
+
+/// Returns the paths of the public items named `name` that the file at `path` can import, from
+/// its own package and from the package's direct dependencies.
+fn import_paths(
+    session: &Session,
+    manifest: &PackageManifestFile,
+    path: &Path,
+    name: &str,
+) -> Vec<ImportPath> {
+    let engines = session.engines.read();
+    let mut import_paths: Vec<ImportPath> = session
+        .token_map()
+        .iter()
+        .filter(|(ident, token)| ident.as_str() == name && is_importable(&engines, ident, token))
+        .filter_map(|(ident, _)| {
+            let decl_path = engines.se().get_path(ident.span().source_id()?);
+            (decl_path != path).then_some(())?;
+            let (is_absolute, module_path) = module_path(manifest, path, &decl_path)?;
+            Some(ImportPath {
+                is_absolute,
+                module_path,
+                name: name.to_string(),
+            })
+        })
+        .collect();
+    import_paths.sort();
+    import_paths.dedup();
+    import_paths
+}
+
+/// Returns `true` if the token is the name of a public item declared at module level.
+fn is_importable(engines: &Engines, ident: &Ident, token: &Token) -> bool {
+    if token.declared_token_span(engines) != Some(ident.span()) {
+        return false;
+    }
+    match &token.typed {
+        Some(TypedAstToken::TypedDeclaration(
+            decl @ (ty::TyDecl::StructDecl(_)
+            | ty::TyDecl::EnumDecl(_)
+            | ty::TyDecl::TraitDecl(_)
+            | ty::TyDecl::AbiDecl(_)
+            | ty::TyDecl::ConstantDecl(_)
+            | ty::TyDecl::FunctionDecl(_)
+            | ty::TyDecl::TypeAliasDecl(_)),
+        )) => decl.visibility(engines.de()).is_public(),
+        Some(TypedAstToken::TypedFunctionDeclaration(decl)) => {
+            decl.implementing_type.is_none() && decl.visibility.is_public()
+        }
+        _ => false,
+    }
+}
+
+/// Returns the path of the module declared by the file at `decl_path`, as seen from the file at
+/// `path` in the package of `manifest`, and whether the path is absolute.
+fn module_path(
+    manifest: &PackageManifestFile,
+    path: &Path,
+    decl_path: &Path,
+) -> Option<(bool, Vec<String>)> {
+    let decl_manifest = PackageManifestFile::from_dir(decl_path).ok()?;
+    let entry_path = decl_manifest.entry_path();
+    let mut module_path = vec![];
+    if decl_path != entry_path {
+        let relative_path = decl_path
+            .with_extension("")
+            .strip_prefix(entry_path.parent()?)
+            .ok()?
+            .to_path_buf();
+        for component in relative_path.components() {
+            module_path.push(component.as_os_str().to_str()?.to_string());
+        }
+    }
+
+    if decl_manifest.dir() == manifest.dir() {
+        // Items of the file's own package are imported relative to the package root.
+        return (!module_path.is_empty()).then_some((path != manifest.entry_path(), module_path));
+    }
+    let dep_name = manifest
+        .dependencies
+        .iter()
+        .flatten()
+        .map(|(dep_name, _)| dep_name)
+        .find(|dep_name| {
+            manifest
+                .dep_path(dep_name)
+                .is_some_and(|dep_dir| dep_dir == decl_manifest.dir())
+        })?;
+    module_path.insert(0, dep_name.clone());
+    Some((false, module_path))
+}
+
+/// Returns the [TextEdit] that imports the item at `import_path` into `module`.
+///
+/// The item is added to an existing `use` statement of its module if there is one. Otherwise, a
+/// new `use` statement is inserted in order among the existing ones, or after the module kind and
+/// submodule declarations if the module has no `use` statements yet.
+fn import_edit(module: &Module, import_path: &ImportPath) -> TextEdit {
+    let item_uses: Vec<_> = module
+        .items
+        .iter()
+        .filter_map(|item| match &item.value {
+            ItemKind::Use(item_use) => Some((item, item_use)),
+            _ => None,
+        })
+        .collect();
+
+    for (_, item_use) in &item_uses {
+        if item_use.root_import.is_some() != import_path.is_absolute {
+            continue;
+        }
+        let mut prefixes = vec![];
+        let mut tree = &item_use.tree;
+        while let UseTree::Path { prefix, suffix, .. } = tree {
+            prefixes.push(prefix.as_str());
+            tree = suffix;
+        }
+        if prefixes != import_path.module_path {
+            continue;
+        }
+        if let Some(edit) = merge_edit(tree, &import_path.name) {
+            return edit;
+        }
+    }
+
+    let new_text = format!("use {import_path};\n");
+    let key = import_path.to_string();
+    let next_use = item_uses.iter().find(|(_, item_use)| {
+        let root = if item_use.root_import.is_some() {
+            "::"
+        } else {
+            ""
+        };
+        format!("{root}{}", item_use.tree.span().as_str()) > key
+    });
+    if let Some((item, _)) = next_use {
+        let line = get_range_from_span(&item.span()).start.line;
+        return insert_edit(Position::new(line, 0), new_text);
+    }
+    if let Some((item, _)) = item_uses.last() {
+        let line = get_range_from_span(&item.span()).end.line + 1;
+        return insert_edit(Position::new(line, 0), new_text);
+    }
+
+    let last_header_span = module
+        .items
+        .iter()
+        .rfind(|item| matches!(item.value, ItemKind::Submodule(_)))
+        .map(|item| item.span())
+        .unwrap_or_else(|| module.semicolon_token.span());
+    let line = get_range_from_span(&last_header_span).end.line + 1;
+    insert_edit(Position::new(line, 0), format!("\n{new_text}"))
+}
+
+/// Returns the [TextEdit] that adds `name` to the imports of a `use` statement ending in `tree`.
+fn merge_edit(tree: &UseTree, name: &str) -> Option<TextEdit> {
+    match tree {
+        UseTree::Group { imports } => {
+            let imports: Vec<_> = imports.get().into_iter().collect();
+            match imports.iter().find(|import| import.span().as_str() > name) {
+                Some(next) => {
+                    let position = get_range_from_span(&next.span()).start;
+                    Some(insert_edit(position, format!("{name}, ")))
+                }
+                None => {
+                    let last = imports.last()?;
+                    let position = get_range_from_span(&last.span()).end;
+                    Some(insert_edit(position, format!(", {name}")))
+                }
+            }
+        }
+        UseTree::Name { name: existing } if existing.as_str() != name => {
+            let mut names = [existing.as_str(), name];
+            names.sort();
+            Some(TextEdit {
+                range: get_range_from_span(&existing.span()),
+                new_text: format!("{{{}}}", names.join(", ")),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn insert_edit(position: Position, new_text: String) -> TextEdit {
+    TextEdit {
+        range: Range::new(position, position),
+        new_text,
+    }
+}
@@ -1,4 +1,5 @@
 pub mod abi_decl;
+pub mod auto_import;
 pub mod common;
 pub mod constant_decl;
 pub mod enum_decl;
@@ -17,7 +18,8 @@ use crate::core::{
 pub use crate::error::DocumentError;
 use lsp_types::{
     CodeAction as LspCodeAction, CodeActionDisabled, CodeActionKind, CodeActionOrCommand,
-    CodeActionResponse, Position, Range, TextDocumentIdentifier, TextEdit, Url, WorkspaceEdit,
+    CodeActionResponse, Diagnostic, Position, Range, TextDocumentIdentifier, TextEdit, Url,
+    WorkspaceEdit,
 };
 use serde_json::Value;
 use std::{collections::HashMap, sync::Arc};
@@ -41,6 +43,25 @@ pub(crate) fn code_actions(
     range: &Range,
     text_document: TextDocumentIdentifier,
     temp_uri: &Url,
+    diagnostics: &[Diagnostic],
+) -> Option<CodeActionResponse> {
+    let mut actions =
+        token_code_actions(&session, range, &text_document, temp_uri).unwrap_or_default();
+    actions.extend(auto_import::code_actions(
+        &session,
+        temp_uri,
+        &text_document.uri,
+        diagnostics,
+    ));
+    (!actions.is_empty()).then_some(actions)
+}
+
+/// Returns the code actions for the declaration at the start of `range`.
+fn token_code_actions(
+    session: &Session,
+    range: &Range,
+    text_document: &TextDocumentIdentifier,
+    temp_uri: &Url,
 ) -> Option<CodeActionResponse> {
     let engines = session.engines.read();
     let (_, token) = session
@@ -1,7 +1,12 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
 use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
 use sway_error::warning::CompileWarning;
 use sway_error::{error::CompileError, warning::Warning};
-use sway_types::{LineCol, Spanned};
+use sway_types::{LineCol, SourceEngine, Span, Spanned};
 
 #[derive(Debug, Default, Clone)]
 pub struct Diagnostics {
@@ -9,30 +14,63 @@ pub struct Diagnostics {
     pub errors: Vec<Diagnostic>,
 }
 
-fn get_error_diagnostics(errors: &[CompileError]) -> Vec<Diagnostic> {
-    Vec::from_iter(errors.iter().map(|error| Diagnostic {
+impl Diagnostics {
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// The [Diagnostics] of each file, by the path of the file.
+pub type DiagnosticMap = HashMap<PathBuf, Diagnostics>;
+
+fn get_error_diagnostic(error: &CompileError) -> Diagnostic {
+    Diagnostic {
         range: get_range(error.span().line_col()),
         severity: Some(DiagnosticSeverity::ERROR),
         message: format!("{error}"),
         ..Default::default()
-    }))
+    }
 }
 
-fn get_warning_diagnostics(warnings: &[CompileWarning]) -> Vec<Diagnostic> {
-    Vec::from_iter(warnings.iter().map(|warning| Diagnostic {
+fn get_warning_diagnostic(warning: &CompileWarning) -> Diagnostic {
+    Diagnostic {
         range: get_range(warning.span().line_col()),
         severity: Some(DiagnosticSeverity::WARNING),
         message: warning.to_friendly_warning_string(),
         tags: get_warning_diagnostic_tags(&warning.warning_content),
         ..Default::default()
-    }))
+    }
 }
 
-pub fn get_diagnostics(warnings: &[CompileWarning], errors: &[CompileError]) -> Diagnostics {
-    Diagnostics {
-        warnings: get_warning_diagnostics(warnings),
-        errors: get_error_diagnostics(errors),
+/// Returns the diagnostics of the warnings and errors by the file they are in. Diagnostics whose
+/// span isn't part of a file are given to the file at `fallback_path`.
+pub fn get_diagnostics(
+    warnings: &[CompileWarning],
+    errors: &[CompileError],
+    source_engine: &SourceEngine,
+    fallback_path: &Path,
+) -> DiagnosticMap {
+    let path_of = |span: Span| {
+        span.source_id()
+            .map(|source_id| source_engine.get_path(source_id))
+            .unwrap_or_else(|| fallback_path.to_path_buf())
+    };
+    let mut diagnostics = DiagnosticMap::new();
+    for warning in warnings {
+        diagnostics
+            .entry(path_of(warning.span()))
+            .or_default()
+            .warnings
+            .push(get_warning_diagnostic(warning));
+    }
+    for error in errors {
+        diagnostics
+            .entry(path_of(error.span()))
+            .or_default()
+            .errors
+            .push(get_error_diagnostic(error));
     }
+    diagnostics
 }
 
 fn get_range((start, end): (LineCol, LineCol)) -> Range {
@@ -1,19 +1,321 @@
 use crate::core::token::{get_range_from_span, SymbolKind, Token};
-use lsp_types::{self, Location, SymbolInformation, Url};
-use sway_types::{Ident, Spanned};
-
-pub fn to_symbol_information<I>(tokens: I, url: Url) -> Vec<SymbolInformation>
-where
-    I: Iterator<Item = (Ident, Token)>,
-{
-    let mut symbols: Vec<SymbolInformation> = vec![];
-
-    for (ident, token) in tokens {
-        let symbol = symbol_info(&ident, &token, url.clone());
-        symbols.push(symbol)
+use lsp_types::{self, DocumentSymbol, Location, SymbolInformation, Url};
+use sway_core::{
+    language::ty::{self, TyAstNodeContent, TyTraitInterfaceItem, TyTraitItem},
+    Engines,
+};
+use sway_types::{Ident, Span, Spanned};
+
+/// The name of the container symbol of the storage fields.
+const STORAGE_SYMBOL_NAME: &str = "storage";
+/// The name of the container symbol of the configurable constants.
+const CONFIGURABLE_SYMBOL_NAME: &str = "configurable";
+
+/// Returns the symbol tree of the module declared in the file at `url`.
+///
+/// Top level items are ordered by their position in the file. Fields, variants and methods are
+/// nested under the declaration they belong to, and storage fields and configurable constants
+/// are nested under `storage` and `configurable` symbols.
+pub fn document_symbols(
+    typed_program: &ty::TyProgram,
+    engines: &Engines,
+    url: &Url,
+) -> Option<Vec<DocumentSymbol>> {
+    let module = std::iter::once(&typed_program.root)
+        .chain(
+            typed_program
+                .root
+                .submodules_recursive()
+                .map(|(_, submodule)| &submodule.module),
+        )
+        .find(|module| is_in_file(engines, &module.span, url))?;
+
+    let mut symbols: Vec<DocumentSymbol> = module
+        .submodules
+        .iter()
+        .filter(|(_, submodule)| is_in_file(engines, &submodule.mod_name_span, url))
+        .map(|(name, submodule)| {
+            let span = &submodule.mod_name_span;
+            document_symbol(
+                name.as_str(),
+                lsp_types::SymbolKind::MODULE,
+                span,
+                span,
+                vec![],
+            )
+        })
+        .collect();
+
+    let mut configurables = vec![];
+    for node in &module.all_nodes {
+        let TyAstNodeContent::Declaration(decl) = &node.content else {
+            continue;
+        };
+        match decl {
+            ty::TyDecl::ConstantDecl(ty::ConstantDecl { decl_id, .. }) => {
+                let constant = engines.de().get_constant(decl_id);
+                let symbol = constant_symbol(&constant);
+                if constant.is_configurable {
+                    configurables.push((constant.span, symbol));
+                } else {
+                    symbols.push(symbol);
+                }
+            }
+            _ => symbols.extend(decl_symbol(engines, decl)),
+        }
+    }
+
+    // The typed tree doesn't keep the span of the `configurable` block, so the container symbol
+    // spans from the first to the last configurable constant.
+    if let Some(span) = configurables
+        .iter()
+        .map(|(span, _)| span.clone())
+        .reduce(Span::join)
+    {
+        let children = configurables
+            .into_iter()
+            .map(|(_, symbol)| symbol)
+            .collect();
+        symbols.push(document_symbol(
+            CONFIGURABLE_SYMBOL_NAME,
+            lsp_types::SymbolKind::STRUCT,
+            &span,
+            &span,
+            children,
+        ));
+    }
+
+    symbols.sort_by_key(|symbol| symbol.range.start);
+    Some(symbols)
+}
+
+/// Returns the symbol of a declaration, with the symbols of its members as children.
+fn decl_symbol(engines: &Engines, decl: &ty::TyDecl) -> Option<DocumentSymbol> {
+    let decl_engine = engines.de();
+    let symbol = match decl {
+        ty::TyDecl::FunctionDecl(ty::FunctionDecl { decl_id, .. }) => {
+            let func = decl_engine.get_function(decl_id);
+            function_symbol(&func, lsp_types::SymbolKind::FUNCTION)
+        }
+        ty::TyDecl::StructDecl(ty::StructDecl { decl_id, .. }) => {
+            let struct_decl = decl_engine.get_struct(decl_id);
+            let fields = struct_decl
+                .fields
+                .iter()
+                .map(|field| {
+                    document_symbol(
+                        field.name.as_str(),
+                        lsp_types::SymbolKind::FIELD,
+                        &field.span,
+                        &field.name.span(),
+                        vec![],
+                    )
+                })
+                .collect();
+            let name = &struct_decl.call_path.suffix;
+            document_symbol(
+                name.as_str(),
+                lsp_types::SymbolKind::STRUCT,
+                &struct_decl.span,
+                &name.span(),
+                fields,
+            )
+        }
+        ty::TyDecl::EnumDecl(ty::EnumDecl { decl_id, .. }) => {
+            let enum_decl = decl_engine.get_enum(decl_id);
+            let variants = enum_decl
+                .variants
+                .iter()
+                .map(|variant| {
+                    document_symbol(
+                        variant.name.as_str(),
+                        lsp_types::SymbolKind::ENUM_MEMBER,
+                        &variant.span,
+                        &variant.name.span(),
+                        vec![],
+                    )
+                })
+                .collect();
+            let name = &enum_decl.call_path.suffix;
+            document_symbol(
+                name.as_str(),
+                lsp_types::SymbolKind::ENUM,
+                &enum_decl.span,
+                &name.span(),
+                variants,
+            )
+        }
+        ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id, .. }) => {
+            let trait_decl = decl_engine.get_trait(decl_id);
+            let children = interface_symbols(engines, &trait_decl.interface_surface)
+                .chain(item_symbols(engines, &trait_decl.items))
+                .collect();
+            document_symbol(
+                trait_decl.name.as_str(),
+                lsp_types::SymbolKind::INTERFACE,
+                &trait_decl.span,
+                &trait_decl.name.span(),
+                children,
+            )
+        }
+        ty::TyDecl::AbiDecl(ty::AbiDecl { decl_id, .. }) => {
+            let abi_decl = decl_engine.get_abi(decl_id);
+            let children = interface_symbols(engines, &abi_decl.interface_surface)
+                .chain(item_symbols(engines, &abi_decl.items))
+                .collect();
+            document_symbol(
+                abi_decl.name.as_str(),
+                lsp_types::SymbolKind::INTERFACE,
+                &abi_decl.span,
+                &abi_decl.name.span(),
+                children,
+            )
+        }
+        ty::TyDecl::ImplTrait(ty::ImplTrait { decl_id, .. }) => {
+            let impl_trait = decl_engine.get_impl_trait(decl_id);
+            let implementing_for = impl_trait.implementing_for.span.as_str();
+            // Impl self blocks have no trait declaration.
+            let (name, selection_span) = match impl_trait.trait_decl_ref {
+                Some(_) => (
+                    format!(
+                        "impl {} for {implementing_for}",
+                        impl_trait.trait_name.suffix
+                    ),
+                    impl_trait.trait_name.suffix.span(),
+                ),
+                None => (
+                    format!("impl {implementing_for}"),
+                    impl_trait.implementing_for.span.clone(),
+                ),
+            };
+            let children = item_symbols(engines, &impl_trait.items).collect();
+            document_symbol(
+                &name,
+                lsp_types::SymbolKind::OBJECT,
+                &impl_trait.span,
+                &selection_span,
+                children,
+            )
+        }
+        ty::TyDecl::StorageDecl(ty::StorageDecl { decl_id, .. }) => {
+            let storage = decl_engine.get_storage(decl_id);
+            let fields = storage
+                .fields
+                .iter()
+                .map(|field| {
+                    document_symbol(
+                        field.name.as_str(),
+                        lsp_types::SymbolKind::FIELD,
+                        &field.span(),
+                        &field.name.span(),
+                        vec![],
+                    )
+                })
+                .collect();
+            document_symbol(
+                STORAGE_SYMBOL_NAME,
+                lsp_types::SymbolKind::STRUCT,
+                &storage.span,
+                &storage.storage_keyword.span(),
+                fields,
+            )
+        }
+        ty::TyDecl::TypeAliasDecl(ty::TypeAliasDecl { decl_id, .. }) => {
+            let type_alias = decl_engine.get_type_alias(decl_id);
+            document_symbol(
+                type_alias.name.as_str(),
+                lsp_types::SymbolKind::TYPE_PARAMETER,
+                &type_alias.span,
+                &type_alias.name.span(),
+                vec![],
+            )
+        }
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+/// Returns the symbols of the required functions and constants of a trait or an ABI.
+fn interface_symbols<'a>(
+    engines: &'a Engines,
+    interface_surface: &'a [TyTraitInterfaceItem],
+) -> impl 'a + Iterator<Item = DocumentSymbol> {
+    interface_surface.iter().map(|item| match item {
+        TyTraitInterfaceItem::TraitFn(decl_ref) => {
+            let trait_fn = engines.de().get_trait_fn(decl_ref);
+            document_symbol(
+                trait_fn.name.as_str(),
+                lsp_types::SymbolKind::METHOD,
+                &trait_fn.span(),
+                &trait_fn.name.span(),
+                vec![],
+            )
+        }
+        TyTraitInterfaceItem::Constant(decl_ref) => {
+            constant_symbol(&engines.de().get_constant(decl_ref))
+        }
+    })
+}
+
+/// Returns the symbols of the methods and constants implemented in a trait, an ABI or an impl.
+fn item_symbols<'a>(
+    engines: &'a Engines,
+    items: &'a [TyTraitItem],
+) -> impl 'a + Iterator<Item = DocumentSymbol> {
+    items.iter().map(|item| match item {
+        TyTraitItem::Fn(decl_ref) => function_symbol(
+            &engines.de().get_function(decl_ref),
+            lsp_types::SymbolKind::METHOD,
+        ),
+        TyTraitItem::Constant(decl_ref) => constant_symbol(&engines.de().get_constant(decl_ref)),
+    })
+}
+
+fn function_symbol(func: &ty::TyFunctionDecl, kind: lsp_types::SymbolKind) -> DocumentSymbol {
+    document_symbol(
+        func.name.as_str(),
+        kind,
+        &func.span,
+        &func.name.span(),
+        vec![],
+    )
+}
+
+fn constant_symbol(constant: &ty::TyConstantDecl) -> DocumentSymbol {
+    let name = &constant.call_path.suffix;
+    document_symbol(
+        name.as_str(),
+        lsp_types::SymbolKind::CONSTANT,
+        &constant.span,
+        &name.span(),
+        vec![],
+    )
+}
+
+#[allow(deprecated)]
+// The "deprecated" field is deprecated in favor of "tags", but has no default.
+fn document_symbol(
+    name: &str,
+    kind: lsp_types::SymbolKind,
+    span: &Span,
+    selection_span: &Span,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: name.to_string(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: get_range_from_span(span),
+        selection_range: get_range_from_span(selection_span),
+        children: (!children.is_empty()).then_some(children),
     }
+}
 
-    symbols
+fn is_in_file(engines: &Engines, span: &Span, url: &Url) -> bool {
+    span.source_id()
+        .is_some_and(|source_id| engines.se().get_path(source_id).to_str() == Some(url.path()))
 }
 
 /// Given a `token::SymbolKind`, return the `lsp_types::SymbolKind` that corresponds to it.
@@ -47,7 +349,7 @@ pub(crate) fn symbol_kind(symbol_kind: &SymbolKind) -> lsp_types::SymbolKind {
 
 #[allow(warnings)]
 // TODO: the "deprecated: None" field is deprecated according to this library
-fn symbol_info(ident: &Ident, token: &Token, url: Url) -> SymbolInformation {
+pub(crate) fn symbol_info(ident: &Ident, token: &Token, url: Url) -> SymbolInformation {
     let range = get_range_from_span(&ident.span());
     SymbolInformation {
         name: ident.as_str().to_string(),
@@ -11,8 +11,10 @@ use crate::{
 };
 use std::sync::Arc;
 use sway_core::{
+    decl_engine::InterfaceDeclId,
     language::{ty, Visibility},
-    Engines, TypeId,
+    transform::{AttributeKind, AttributesMap},
+    CompileResult, Engines, TypeId,
 };
 
 use lsp_types::{self, Position, Url};
@@ -99,6 +101,100 @@ fn format_doc_attributes(token: &Token) -> String {
     doc_comment
 }
 
+/// Returns the `#[storage(...)]` and `#[payable]` attributes in `attributes`, one per line.
+fn format_gas_attributes(attributes: &AttributesMap) -> String {
+    [AttributeKind::Storage, AttributeKind::Payable]
+        .iter()
+        .filter_map(|kind| attributes.get(kind))
+        .flatten()
+        .map(|attribute| {
+            let args = attribute
+                .args
+                .iter()
+                .map(|arg| arg.name.as_str())
+                .collect::<Vec<_>>();
+            if args.is_empty() {
+                format!("#[{}]\n", attribute.name)
+            } else {
+                format!("#[{}({})]\n", attribute.name, args.join(", "))
+            }
+        })
+        .collect()
+}
+
+/// Returns the selector of an ABI method, both as 4 bytes and as the word that is passed to the
+/// contract, along with the ABI signature the selector is derived from.
+fn format_abi_selector(
+    selector_name: CompileResult<String>,
+    selector: CompileResult<[u8; 4]>,
+) -> Option<String> {
+    let selector_name = selector_name.value?;
+    let selector: String = selector
+        .value?
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Some(format!(
+        "Selector: `0x{selector}` (word `0x00000000{selector}`)\n\nABI signature: `{selector_name}`"
+    ))
+}
+
+/// Returns the index of the storage field named `name` and the key of its first storage slot.
+fn format_storage_slot(session: &Session, engines: &Engines, name: &Ident) -> Option<String> {
+    let compiled_program = session.compiled_program.read();
+    let storage_decl = compiled_program
+        .typed
+        .as_ref()?
+        .declarations
+        .iter()
+        .find_map(|decl| match decl {
+            ty::TyDecl::StorageDecl(ty::StorageDecl { decl_id, .. }) => {
+                Some(engines.de().get_storage(decl_id))
+            }
+            _ => None,
+        })?;
+    let (ix, key) = storage_decl.field_storage_key(name)?;
+    Some(format!(
+        "Storage slot key: `{key:#x}`\n\nStorage field index: `{}`",
+        ix.to_usize()
+    ))
+}
+
+/// Returns `true` if `func` is a method of an `impl` of an ABI.
+fn is_abi_method(engines: &Engines, func: &ty::TyFunctionDecl) -> bool {
+    let Some(ty::TyDecl::ImplTrait(ty::ImplTrait { decl_id, .. })) = &func.implementing_type else {
+        return false;
+    };
+    engines
+        .de()
+        .get_impl_trait(decl_id)
+        .trait_decl_ref
+        .is_some_and(|decl_ref| matches!(decl_ref.id(), InterfaceDeclId::Abi(_)))
+}
+
+/// Returns `true` if `trait_fn` is declared in the interface surface of an ABI.
+fn is_abi_interface_fn(session: &Session, engines: &Engines, trait_fn: &ty::TyTraitFn) -> bool {
+    session.token_map().iter().any(|(_, token)| {
+        let Some(TypedAstToken::TypedDeclaration(ty::TyDecl::AbiDecl(ty::AbiDecl {
+            decl_id, ..
+        }))) = &token.typed
+        else {
+            return false;
+        };
+        engines
+            .de()
+            .get_abi(decl_id)
+            .interface_surface
+            .iter()
+            .any(|item| match item {
+                ty::TyTraitInterfaceItem::TraitFn(decl_ref) => {
+                    engines.de().get_trait_fn(decl_ref).name.span() == trait_fn.name.span()
+                }
+                ty::TyTraitInterfaceItem::Constant(_) => false,
+            })
+    })
+}
+
 fn format_visibility_hover(visibility: Visibility, decl_name: &str, token_name: &str) -> String {
     format!(
         "{}{} {}",
@@ -138,8 +234,11 @@ fn hover_format(
         format!("{name}: {type_name}")
     };
 
+    // Selectors and storage slots that are shown below the signature.
+    let mut details = None;
+
     // Used to collect all the information we need to generate links for the hover component.
-    let mut hover_link_contents = HoverLinkContents::new(session, engines);
+    let mut hover_link_contents = HoverLinkContents::new(session.clone(), engines);
 
     let sway_block = token
         .typed
@@ -191,7 +290,39 @@ fn hover_format(
             },
             TypedAstToken::TypedFunctionDeclaration(func) => {
                 hover_link_contents.add_related_types(&func.return_type.type_id);
-                Some(extract_fn_signature(&func.span()))
+                if is_abi_method(engines, func) {
+                    details = format_abi_selector(
+                        func.to_selector_name(engines),
+                        func.to_fn_selector_value(engines),
+                    );
+                }
+                Some(format!(
+                    "{}{}",
+                    format_gas_attributes(&func.attributes),
+                    extract_fn_signature(&func.span())
+                ))
+            }
+            TypedAstToken::TypedTraitFn(trait_fn) => {
+                hover_link_contents.add_related_types(&trait_fn.return_type.type_id);
+                if is_abi_interface_fn(&session, engines, trait_fn) {
+                    details = format_abi_selector(
+                        trait_fn.to_selector_name(engines),
+                        trait_fn.to_fn_selector_value(engines),
+                    );
+                }
+                Some(format!(
+                    "{}{}",
+                    format_gas_attributes(&trait_fn.attributes),
+                    extract_fn_signature(&trait_fn.span()).trim_end_matches(';')
+                ))
+            }
+            TypedAstToken::TypedStorageField(field) => {
+                hover_link_contents.add_related_types(&field.type_argument.type_id);
+                details = format_storage_slot(&session, engines, &field.name);
+                Some(format_name_with_type(
+                    field.name.as_str(),
+                    &field.type_argument.type_id,
+                ))
             }
             TypedAstToken::TypedFunctionParameter(param) => {
                 hover_link_contents.add_related_types(&param.type_argument.type_id);
@@ -221,6 +352,7 @@ fn hover_format(
 
     let content = Markup::new()
         .maybe_add_sway_block(sway_block)
+        .maybe_add_text(details)
         .text(&doc_comment)
         .maybe_add_links(
             engines.se(),
@@ -7,13 +7,16 @@ use crate::{
 };
 use lsp_types::{self, Range, Url};
 use std::sync::Arc;
-use sway_core::{language::ty::TyDecl, type_system::TypeInfo};
+use sway_core::{
+    language::ty::{TyDecl, TyExpressionVariant},
+    type_system::TypeInfo,
+};
 use sway_types::Spanned;
 
-// Future PR's will add more kinds
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum InlayKind {
     TypeHint,
+    ParameterHint,
 }
 
 #[derive(Debug)]
@@ -29,19 +32,47 @@ pub(crate) fn inlay_hints(
     range: &Range,
     config: &InlayHintsConfig,
 ) -> Option<Vec<lsp_types::InlayHint>> {
+    if !config.type_hints && !config.parameter_hints {
+        return None;
+    }
+
+    let mut hints = vec![];
+    if config.type_hints {
+        hints.extend(type_hints(&session, uri, range, config.max_length));
+    }
+    if config.parameter_hints {
+        hints.extend(parameter_hints(&session, uri, range));
+    }
+    hints.sort_by_key(|hint| hint.range.start);
+
+    Some(
+        hints
+            .into_iter()
+            .map(|hint| self::inlay_hint(config.render_colons, hint))
+            .collect(),
+    )
+}
+
+fn is_in_range(inner: &Range, outer: &Range) -> bool {
+    inner.start >= outer.start && inner.end <= outer.end
+}
+
+/// Returns the inferred types of the `let` bindings in `range` that have no type ascription.
+fn type_hints(
+    session: &Session,
+    uri: &Url,
+    range: &Range,
+    max_length: Option<usize>,
+) -> Vec<InlayHint> {
     // 1. Loop through all our tokens and filter out all tokens that aren't TypedVariableDeclaration tokens
     // 2. Also filter out all tokens that have a span that fall outside of the provided range
     // 3. Filter out all variable tokens that have a type_ascription
     // 4. Look up the type id for the remaining tokens
     // 5. Convert the type into a string
-    if !config.type_hints {
-        return None;
-    }
-
     let engines = session.engines.read();
     let type_engine = engines.te();
 
-    let hints: Vec<lsp_types::InlayHint> = session
+    session
         .token_map()
         .tokens_for_file(engines.se(), uri)
         .filter_map(|(_, token)| {
@@ -51,11 +82,7 @@ pub(crate) fn inlay_hints(
                         Some(_) => None,
                         None => {
                             let var_range = get_range_from_span(&var_decl.name.span());
-                            if var_range.start >= range.start && var_range.end <= range.end {
-                                Some(var_decl.clone())
-                            } else {
-                                None
-                            }
+                            is_in_range(&var_range, range).then(|| var_decl.clone())
                         }
                     }
                 }
@@ -73,12 +100,57 @@ pub(crate) fn inlay_hints(
             let range = get_range_from_span(&var.name.span());
             let kind = InlayKind::TypeHint;
             let label = format!("{}", engines.help_out(var.type_ascription));
-            let inlay_hint = InlayHint { range, kind, label };
-            self::inlay_hint(config.render_colons, inlay_hint)
+            let label = elide_type_label(label, max_length);
+            InlayHint { range, kind, label }
         })
-        .collect();
+        .collect()
+}
 
-    Some(hints)
+/// Returns the parameter names of the literal arguments of the function calls in `range`.
+fn parameter_hints(session: &Session, uri: &Url, range: &Range) -> Vec<InlayHint> {
+    let engines = session.engines.read();
+
+    session
+        .token_map()
+        .tokens_for_file(engines.se(), uri)
+        .filter_map(|(_, token)| match token.typed {
+            Some(TypedAstToken::TypedExpression(expr)) => match expr.expression {
+                TyExpressionVariant::FunctionApplication { arguments, .. } => Some(arguments),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flatten()
+        .filter(|(param, arg)| {
+            param.as_str() != "self"
+                && matches!(arg.expression, TyExpressionVariant::Literal(_))
+                && is_in_range(&get_range_from_span(&arg.span), range)
+        })
+        .map(|(param, arg)| InlayHint {
+            range: get_range_from_span(&arg.span),
+            kind: InlayKind::ParameterHint,
+            label: param.as_str().to_string(),
+        })
+        .collect()
+}
+
+/// Shortens a type label that is longer than `max_length` by eliding its outermost type arguments,
+/// falling back to truncating the label if that isn't enough.
+fn elide_type_label(label: String, max_length: Option<usize>) -> String {
+    let Some(max_length) = max_length else {
+        return label;
+    };
+    if label.chars().count() <= max_length {
+        return label;
+    }
+    if let (Some(open), Some(close)) = (label.find('<'), label.rfind('>')) {
+        let elided = format!("{}<…>{}", &label[..open], &label[close + 1..]);
+        if elided.chars().count() <= max_length {
+            return elided;
+        }
+    }
+    let truncated: String = label.chars().take(max_length.saturating_sub(1)).collect();
+    format!("{truncated}…")
 }
 
 fn inlay_hint(render_colons: bool, inlay_hint: InlayHint) -> lsp_types::InlayHint {
@@ -86,22 +158,50 @@ fn inlay_hint(render_colons: bool, inlay_hint: InlayHint) -> lsp_types::InlayHin
         position: match inlay_hint.kind {
             // after annotated thing
             InlayKind::TypeHint => inlay_hint.range.end,
+            // before the argument
+            InlayKind::ParameterHint => inlay_hint.range.start,
         },
         label: lsp_types::InlayHintLabel::String(match inlay_hint.kind {
             InlayKind::TypeHint if render_colons => format!(": {}", inlay_hint.label),
+            InlayKind::ParameterHint if render_colons => format!("{}:", inlay_hint.label),
             _ => inlay_hint.label,
         }),
         kind: match inlay_hint.kind {
             InlayKind::TypeHint => Some(lsp_types::InlayHintKind::TYPE),
+            InlayKind::ParameterHint => Some(lsp_types::InlayHintKind::PARAMETER),
         },
         tooltip: None,
         padding_left: Some(match inlay_hint.kind {
             InlayKind::TypeHint => !render_colons,
+            InlayKind::ParameterHint => false,
         }),
         padding_right: Some(match inlay_hint.kind {
             InlayKind::TypeHint => false,
+            InlayKind::ParameterHint => true,
         }),
         text_edits: None,
         data: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elides_long_type_labels() {
+        assert_eq!(elide_type_label("u64".to_string(), Some(5)), "u64");
+        assert_eq!(
+            elide_type_label("Option<Vec<u64>>".to_string(), Some(10)),
+            "Option<…>"
+        );
+        assert_eq!(
+            elide_type_label("Option<Vec<u64>>".to_string(), None),
+            "Option<Vec<u64>>"
+        );
+        assert_eq!(
+            elide_type_label("VeryLongStructName<u64>".to_string(), Some(10)),
+            "VeryLongS…"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod call_hierarchy;
 pub mod code_actions;
 pub mod completion;
 pub mod diagnostic;
@@ -7,9 +8,12 @@ pub mod highlight;
 pub mod hover;
 pub mod inlay_hints;
 pub mod on_enter;
+pub mod references;
 pub mod rename;
 pub mod runnable;
 pub mod semantic_tokens;
+pub mod signature_help;
+pub mod workspace_symbol;
 
 pub(crate) use code_actions::code_actions;
 pub(crate) use on_enter::on_enter;
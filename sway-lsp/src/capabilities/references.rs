@@ -0,0 +1,252 @@
+use crate::{
+    core::{
+        session::Session,
+        token::{get_range_from_span, SymbolKind, Token, TypedAstToken},
+    },
+    utils::document::get_url_from_path,
+};
+use lsp_types::{Location, Position, Url};
+use std::sync::Arc;
+use sway_core::{decl_engine::InterfaceDeclId, language::ty, Engines};
+use sway_types::{Ident, Span, Spanned};
+
+/// Returns the workspace locations of the declarations that the token at `position` refers to.
+///
+/// For methods, this includes the method's declarations in the trait or ABI it belongs to and
+/// in all of their implementations.
+pub fn declaration_locations(
+    session: Arc<Session>,
+    url: &Url,
+    position: Position,
+) -> Option<Vec<Location>> {
+    let engines = session.engines.read();
+    let (ident, token) = session
+        .token_map()
+        .token_at_position(engines.se(), url, position)?;
+    let decl_span = declaration_span(&engines, &ident, &token)?;
+    let decl_spans = if token.kind == SymbolKind::Function {
+        method_decl_spans(&session, &engines, decl_span).spans
+    } else {
+        vec![decl_span]
+    };
+    Some(
+        decl_spans
+            .iter()
+            .filter_map(|span| span_location(&session, &engines, span))
+            .collect(),
+    )
+}
+
+/// Returns the locations of all references in the session to the declarations at `decl_locations`.
+///
+/// The declarations themselves are only included if `include_declaration` is `true`.
+pub fn references(
+    session: Arc<Session>,
+    decl_locations: &[Location],
+    include_declaration: bool,
+) -> Vec<Location> {
+    let engines = session.engines.read();
+    // The session may contain implementations of a method that weren't visible to the session the
+    // declarations were found in, so link the methods again within this session.
+    let mut decl_locations = decl_locations.to_vec();
+    for (ident, token) in session.token_map().iter() {
+        if token.kind != SymbolKind::Function {
+            continue;
+        }
+        match span_location(&session, &engines, &ident.span()) {
+            Some(location) if decl_locations.contains(&location) => {}
+            _ => continue,
+        }
+        for span in method_decl_spans(&session, &engines, ident.span()).spans {
+            if let Some(location) = span_location(&session, &engines, &span) {
+                if !decl_locations.contains(&location) {
+                    decl_locations.push(location);
+                }
+            }
+        }
+    }
+
+    session
+        .token_map()
+        .iter()
+        .filter_map(|(ident, token)| {
+            let decl_span = declaration_span(&engines, &ident, &token)?;
+            let decl_location = span_location(&session, &engines, &decl_span)?;
+            if !decl_locations.contains(&decl_location) {
+                return None;
+            }
+            let location = span_location(&session, &engines, &ident.span())?;
+            (include_declaration || !decl_locations.contains(&location)).then_some(location)
+        })
+        .collect()
+}
+
+/// Returns the location of the span in the user's workspace.
+pub(crate) fn span_location(session: &Session, engines: &Engines, span: &Span) -> Option<Location> {
+    let path = engines.se().get_path(span.source_id()?);
+    let url = session
+        .sync
+        .to_workspace_url(get_url_from_path(&path).ok()?)?;
+    Some(Location::new(url, get_range_from_span(span)))
+}
+
+/// Returns the [Span] of the declaration that the token refers to.
+///
+/// Struct fields, enum variants and function parameters use their type as the type definition,
+/// so the declaration of these tokens is the ident itself.
+pub(crate) fn declaration_span(engines: &Engines, ident: &Ident, token: &Token) -> Option<Span> {
+    match token.typed {
+        Some(
+            TypedAstToken::TypedStructField(_)
+            | TypedAstToken::TypedEnumVariant(_)
+            | TypedAstToken::TypedFunctionParameter(_),
+        ) => Some(ident.span()),
+        _ => token.declared_token_span(engines),
+    }
+}
+
+/// Returns the `Ident`s of the declarations at `decl_spans` and of all references to them.
+pub(crate) fn references_of_decls(
+    session: &Session,
+    engines: &Engines,
+    decl_spans: &[Span],
+) -> Vec<Ident> {
+    session
+        .token_map()
+        .iter()
+        .filter(|(ident, token)| {
+            declaration_span(engines, ident, token).is_some_and(|span| decl_spans.contains(&span))
+        })
+        .map(|(ident, _)| ident)
+        .collect()
+}
+
+/// Returns the [Span] of the innermost declaration that encloses `span`, ignoring the
+/// declaration that `span` is the name of. Returns `None` for module level declarations.
+pub(crate) fn enclosing_decl_span(
+    session: &Session,
+    engines: &Engines,
+    span: &Span,
+) -> Option<Span> {
+    session
+        .token_map()
+        .iter()
+        .filter_map(|(ident, token)| {
+            let decl_span = match &token.typed {
+                Some(TypedAstToken::TypedDeclaration(decl)) => decl.span(),
+                Some(TypedAstToken::TypedFunctionDeclaration(decl)) => decl.span(),
+                _ => return None,
+            };
+            let is_own_decl = ident.span() == *span
+                || declaration_span(engines, &ident, &token).as_ref() == Some(span);
+            let encloses = decl_span.source_id() == span.source_id()
+                && decl_span.start() <= span.start()
+                && span.end() <= decl_span.end();
+            (!is_own_decl && encloses).then_some(decl_span)
+        })
+        .min_by_key(|decl_span| decl_span.end() - decl_span.start())
+}
+
+/// The declarations of a method that are linked through the trait or ABI declaring the method.
+pub(crate) struct MethodDecls {
+    /// The spans of the method's names in the trait or ABI declaration and all of its implementations.
+    pub(crate) spans: Vec<Span>,
+    /// Whether the method is declared by an ABI.
+    pub(crate) is_abi_method: bool,
+}
+
+/// Returns the declarations linked to the function declared at `decl_span`.
+///
+/// Functions that are not methods of a trait or an ABI are only linked to themselves.
+pub(crate) fn method_decl_spans(
+    session: &Session,
+    engines: &Engines,
+    decl_span: Span,
+) -> MethodDecls {
+    let enclosing_decl = enclosing_decl_span(session, engines, &decl_span).and_then(|span| {
+        session
+            .token_map()
+            .iter()
+            .find_map(|(_, token)| match token.typed {
+                Some(TypedAstToken::TypedDeclaration(decl)) if decl.span() == span => Some(decl),
+                _ => None,
+            })
+    });
+    let interface_decl_id = match enclosing_decl {
+        Some(ty::TyDecl::AbiDecl(ty::AbiDecl { decl_id, .. })) => InterfaceDeclId::Abi(decl_id),
+        Some(ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id, .. })) => {
+            InterfaceDeclId::Trait(decl_id)
+        }
+        Some(ty::TyDecl::ImplTrait(ty::ImplTrait { decl_id, .. })) => {
+            match engines.de().get_impl_trait(&decl_id).trait_decl_ref {
+                Some(decl_ref) => decl_ref.id().clone(),
+                None => {
+                    return MethodDecls {
+                        spans: vec![decl_span],
+                        is_abi_method: false,
+                    }
+                }
+            }
+        }
+        _ => {
+            return MethodDecls {
+                spans: vec![decl_span],
+                is_abi_method: false,
+            }
+        }
+    };
+
+    let mut methods = match &interface_decl_id {
+        InterfaceDeclId::Abi(decl_id) => {
+            trait_interface_idents(&engines.de().get_abi(decl_id).interface_surface)
+        }
+        InterfaceDeclId::Trait(decl_id) => {
+            trait_interface_idents(&engines.de().get_trait(decl_id).interface_surface)
+        }
+    };
+    for (_, token) in session.token_map().iter() {
+        if let Some(TypedAstToken::TypedDeclaration(ty::TyDecl::ImplTrait(ty::ImplTrait {
+            decl_id,
+            ..
+        }))) = &token.typed
+        {
+            let impl_trait = engines.de().get_impl_trait(decl_id);
+            let implements_interface = impl_trait
+                .trait_decl_ref
+                .as_ref()
+                .is_some_and(|decl_ref| *decl_ref.id() == interface_decl_id);
+            if implements_interface {
+                methods.extend(impl_trait.items.iter().filter_map(|item| match item {
+                    ty::TyTraitItem::Fn(fn_decl) => Some(fn_decl.name().clone()),
+                    _ => None,
+                }));
+            }
+        }
+    }
+
+    let mut spans = vec![decl_span.clone()];
+    for span in methods
+        .iter()
+        .filter(|method| method.as_str() == decl_span.as_str())
+        .map(|method| method.span())
+    {
+        if !spans.contains(&span) {
+            spans.push(span);
+        }
+    }
+    MethodDecls {
+        spans,
+        is_abi_method: matches!(interface_decl_id, InterfaceDeclId::Abi(_)),
+    }
+}
+
+/// Returns a `Vec<Ident>` containing the identifiers of all trait functions found.
+fn trait_interface_idents(interface_surface: &[ty::TyTraitInterfaceItem]) -> Vec<Ident> {
+    interface_surface
+        .iter()
+        .flat_map(|item| match item {
+            ty::TyTraitInterfaceItem::TraitFn(fn_decl) => Some(fn_decl.name().clone()),
+            _ => None,
+        })
+        .collect()
+}
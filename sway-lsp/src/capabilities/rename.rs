@@ -1,16 +1,24 @@
 use crate::{
+    capabilities::references::{
+        declaration_span, enclosing_decl_span, method_decl_spans, references_of_decls,
+    },
     core::{
         session::Session,
-        token::{get_range_from_span, SymbolKind, Token, TypedAstToken},
-        token_map::TokenMapExt,
+        token::{get_range_from_span, SymbolKind, Token},
     },
     error::{LanguageServerError, RenameError},
     utils::document::get_url_from_path,
 };
-use lsp_types::{Position, PrepareRenameResponse, TextEdit, Url, WorkspaceEdit};
-use std::{collections::HashMap, sync::Arc};
-use sway_core::{language::ty, Engines};
-use sway_types::{Ident, Spanned};
+use lsp_types::{
+    AnnotatedTextEdit, DocumentChangeOperation, DocumentChanges, OneOf,
+    OptionalVersionedTextDocumentIdentifier, Position, PrepareRenameResponse, Range, RenameFile,
+    ResourceOp, TextDocumentEdit, TextEdit, Url, WorkspaceEdit,
+};
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
+use sway_ast::token::TokenTree;
+use sway_core::Engines;
+use sway_error::handler::Handler;
+use sway_types::{Ident, Span, Spanned};
 
 const RAW_IDENTIFIER: &str = "r#";
 
@@ -20,8 +28,9 @@ pub fn rename(
     url: Url,
     position: Position,
 ) -> Result<WorkspaceEdit, LanguageServerError> {
-    // Make sure the new name is not a keyword or a literal int type
-    if sway_parse::RESERVED_KEYWORDS.contains(&new_name)
+    // Make sure the new name is an identifier that is not a keyword or a literal int type
+    if !is_identifier(&new_name)
+        || sway_parse::RESERVED_KEYWORDS.contains(&new_name)
         || sway_parse::parse_int_suffix(&new_name).is_some()
     {
         return Err(LanguageServerError::RenameError(RenameError::InvalidName {
@@ -37,66 +46,56 @@ pub fn rename(
 
     let engines = session.engines.read();
     // Get the token at the current cursor position
-    let (_, token) = session
+    let (ident, token) = session
         .token_map()
         .token_at_position(engines.se(), &url, position)
         .ok_or(RenameError::TokenNotFound)?;
 
-    // We don't currently allow renaming of module names.
     if token.kind == SymbolKind::Module {
-        return Err(LanguageServerError::RenameError(
-            RenameError::UnableToRenameModule { path: new_name },
-        ));
+        return rename_module(&session, &engines, &token, new_name);
     }
 
-    let engines = session.engines.read();
+    let decl_span = declaration_span(&engines, &ident, &token).ok_or(RenameError::TokenNotFound)?;
 
-    // If the token is a function, find the parent declaration
-    // and collect idents for all methods of ABI Decl, Trait Decl, and Impl Trait
-    let map_of_changes: HashMap<Url, Vec<TextEdit>> = (if token.kind == SymbolKind::Function {
-        find_all_methods_for_decl(&session, &engines, &url, position)?
+    // If the token is a function, rename it together with the methods of the trait
+    // declaration and implementations it belongs to.
+    let decl_spans = if token.kind == SymbolKind::Function {
+        function_decl_spans(&session, &engines, decl_span)?
     } else {
-        // otherwise, just find all references of the token in the token map
-        session
-            .token_map()
-            .iter()
-            .all_references_of_token(&token, &engines)
-            .map(|(ident, _)| ident)
-            .collect::<Vec<Ident>>()
-    })
-    .into_iter()
-    .filter(|ident| {
-        // we want to rename the type that self refers to, not the self ident itself
-        ident.as_str() != "self"
-    })
-    .filter_map(|ident| {
-        let mut range = get_range_from_span(&ident.span());
-        if ident.is_raw_ident() {
-            // Make sure the start char starts at the begining,
-            // taking the r# tokens into account.
-            range.start.character -= RAW_IDENTIFIER.len() as u32;
-        }
-        if let Some(source_id) = ident.span().source_id() {
-            let path = engines.se().get_path(source_id);
-            let url = get_url_from_path(&path).ok()?;
-            if let Some(url) = session.sync.to_workspace_url(url) {
-                let edit = TextEdit::new(range, new_name.clone());
-                return Some((url, vec![edit]));
-            };
-        }
-        None
-    })
-    .fold(HashMap::new(), |mut map, (k, mut v)| {
-        map.entry(k)
-            .and_modify(|existing| {
-                existing.append(&mut v);
-                // Sort the TextEdits by their range in reverse order so the client applies edits
-                // from the end of the document to the beginning, preventing issues with offset changes.
-                existing.sort_unstable_by(|a, b| b.range.start.cmp(&a.range.start))
+        vec![decl_span]
+    };
+    check_name_collisions(&session, &engines, &token.kind, &decl_spans, &new_name)?;
+
+    let map_of_changes: HashMap<Url, Vec<TextEdit>> =
+        references_of_decls(&session, &engines, &decl_spans)
+            .into_iter()
+            .filter(|ident| {
+                // we want to rename the type that self refers to, not the self ident itself
+                ident.as_str() != "self"
             })
-            .or_insert(v);
-        map
-    });
+            .filter_map(|ident| {
+                let range = ident_range(&ident);
+                if let Some(source_id) = ident.span().source_id() {
+                    let path = engines.se().get_path(source_id);
+                    let url = get_url_from_path(&path).ok()?;
+                    if let Some(url) = session.sync.to_workspace_url(url) {
+                        let edit = TextEdit::new(range, new_name.clone());
+                        return Some((url, vec![edit]));
+                    };
+                }
+                None
+            })
+            .fold(HashMap::new(), |mut map, (k, mut v)| {
+                map.entry(k)
+                    .and_modify(|existing| {
+                        existing.append(&mut v);
+                        // Sort the TextEdits by their range in reverse order so the client applies edits
+                        // from the end of the document to the beginning, preventing issues with offset changes.
+                        existing.sort_unstable_by(|a, b| b.range.start.cmp(&a.range.start))
+                    })
+                    .or_insert(v);
+                map
+            });
 
     Ok(WorkspaceEdit::new(map_of_changes))
 }
@@ -165,64 +164,206 @@ fn is_token_in_workspace(
     Ok(true)
 }
 
-/// Returns a `Vec<Ident>` containing the identifiers of all trait functions found.
-fn trait_interface_idents(interface_surface: &[ty::TyTraitInterfaceItem]) -> Vec<Ident> {
-    interface_surface
-        .iter()
-        .flat_map(|item| match item {
-            ty::TyTraitInterfaceItem::TraitFn(fn_decl) => Some(fn_decl.name().clone()),
-            _ => None,
-        })
-        .collect()
+/// Returns `true` if `name` lexes to a single identifier, such as `foo` or `r#struct`.
+fn is_identifier(name: &str) -> bool {
+    let src: Arc<str> = Arc::from(name);
+    let handler = Handler::default();
+    let is_single_ident = sway_parse::lex(&handler, &src, 0, src.len(), None)
+        .is_ok_and(|stream| matches!(stream.token_trees(), [TokenTree::Ident(_)]));
+    is_single_ident && handler.consume().0.is_empty()
 }
 
-/// Returns the `Ident`s of all methods found for an `AbiDecl`, `TraitDecl`, or `ImplTrait`.
-fn find_all_methods_for_decl(
+/// Returns the range of the identifier, taking the r# tokens of raw identifiers into account.
+fn ident_range(ident: &Ident) -> Range {
+    let mut range = get_range_from_span(&ident.span());
+    if ident.is_raw_ident() {
+        // Make sure the start char starts at the begining,
+        // taking the r# tokens into account.
+        range.start.character -= RAW_IDENTIFIER.len() as u32;
+    }
+    range
+}
+
+/// Returns `true` if declarations of the two kinds share a namespace and can't have the same name.
+fn same_namespace(a: &SymbolKind, b: &SymbolKind) -> bool {
+    let is_type = |kind: &SymbolKind| {
+        matches!(
+            kind,
+            SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Trait | SymbolKind::TypeAlias
+        )
+    };
+    let is_value = |kind: &SymbolKind| {
+        matches!(
+            kind,
+            SymbolKind::Variable | SymbolKind::ValueParam | SymbolKind::Const
+        )
+    };
+    a == b || (is_type(a) && is_type(b)) || (is_value(a) && is_value(b))
+}
+
+/// Returns an error if a declaration named `new_name` of the same kind already exists in the
+/// scope of any of the declarations being renamed.
+fn check_name_collisions(
     session: &Session,
     engines: &Engines,
-    url: &Url,
-    position: Position,
-) -> Result<Vec<Ident>, LanguageServerError> {
-    // Find the parent declaration
-    let (_, decl_token) = session
+    kind: &SymbolKind,
+    decl_spans: &[Span],
+    new_name: &str,
+) -> Result<(), LanguageServerError> {
+    let name = new_name.trim_start_matches(RAW_IDENTIFIER);
+    let candidates: Vec<Span> = session
         .token_map()
-        .parent_decl_at_position(engines.se(), url, position)
-        .ok_or(RenameError::TokenNotFound)?;
+        .iter()
+        .filter(|(ident, token)| {
+            ident.as_str() == name
+                && same_namespace(kind, &token.kind)
+                && !decl_spans.contains(&ident.span())
+                && declaration_span(engines, ident, token) == Some(ident.span())
+        })
+        .map(|(ident, _)| ident.span())
+        .collect();
+    if candidates.is_empty() {
+        return Ok(());
+    }
 
-    let idents = session
-        .token_map()
+    let scope_of = |span: &Span| {
+        (
+            span.source_id().cloned(),
+            enclosing_decl_span(session, engines, span),
+        )
+    };
+    let scopes: Vec<_> = decl_spans.iter().map(scope_of).collect();
+    if candidates
         .iter()
-        .all_references_of_token(&decl_token, engines)
-        .filter_map(|(_, token)| {
-            token.typed.as_ref().and_then(|typed| match typed {
-                TypedAstToken::TypedDeclaration(decl) => match decl {
-                    ty::TyDecl::AbiDecl(ty::AbiDecl { decl_id, .. }) => {
-                        let abi_decl = engines.de().get_abi(decl_id);
-                        Some(trait_interface_idents(&abi_decl.interface_surface))
-                    }
-                    ty::TyDecl::TraitDecl(ty::TraitDecl { decl_id, .. }) => {
-                        let trait_decl = engines.de().get_trait(decl_id);
-                        Some(trait_interface_idents(&trait_decl.interface_surface))
-                    }
-                    ty::TyDecl::ImplTrait(ty::ImplTrait { decl_id, .. }) => {
-                        let impl_trait = engines.de().get_impl_trait(decl_id);
-                        Some(
-                            impl_trait
-                                .items
-                                .iter()
-                                .filter_map(|item| match item {
-                                    ty::TyTraitItem::Fn(fn_decl) => Some(fn_decl.name().clone()),
-                                    _ => None,
-                                })
-                                .collect::<Vec<Ident>>(),
-                        )
-                    }
-                    _ => None,
-                },
-                _ => None,
+        .any(|candidate| scopes.contains(&scope_of(candidate)))
+    {
+        return Err(LanguageServerError::RenameError(
+            RenameError::NameCollision {
+                name: new_name.to_string(),
+            },
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the spans of all declarations that have to be renamed together with the function
+/// declared at `decl_span`.
+///
+/// Methods of a trait are renamed in the trait declaration and in all implementations of the trait.
+/// Methods of an ABI can't be renamed as that would change their function selectors.
+fn function_decl_spans(
+    session: &Session,
+    engines: &Engines,
+    decl_span: Span,
+) -> Result<Vec<Span>, LanguageServerError> {
+    let method_decls = method_decl_spans(session, engines, decl_span.clone());
+    if method_decls.is_abi_method {
+        return Err(LanguageServerError::RenameError(RenameError::AbiMethod {
+            name: decl_span.as_str().to_string(),
+        }));
+    }
+    // Methods of traits declared outside of the user's workspace can't be renamed.
+    let temp_path = session.sync.temp_dir()?;
+    let is_in_workspace = |span: &Span| {
+        span.source_id()
+            .map_or(true, |id| engines.se().get_path(id).starts_with(&temp_path))
+    };
+    if !method_decls.spans.iter().all(is_in_workspace) {
+        return Err(LanguageServerError::RenameError(
+            RenameError::TokenNotPartOfWorkspace,
+        ));
+    }
+    Ok(method_decls.spans)
+}
+
+/// Renames a module of the user's workspace by renaming its file and all references to it.
+fn rename_module(
+    session: &Session,
+    engines: &Engines,
+    token: &Token,
+    new_name: String,
+) -> Result<WorkspaceEdit, LanguageServerError> {
+    let unable_to_rename = || {
+        LanguageServerError::RenameError(RenameError::UnableToRenameModule {
+            path: new_name.clone(),
+        })
+    };
+    // Only modules declared with `mod` statements in the user's workspace have a declaration.
+    let module_span = token
+        .declared_token_span(engines)
+        .ok_or_else(unable_to_rename)?;
+    let source_id = module_span.source_id().ok_or_else(unable_to_rename)?;
+    let temp_path = engines.se().get_path(source_id);
+    if !temp_path.starts_with(session.sync.temp_dir()?) {
+        return Err(unable_to_rename());
+    }
+    let old_url = session
+        .sync
+        .to_workspace_url(get_url_from_path(&temp_path)?)
+        .ok_or_else(unable_to_rename)?;
+    let old_path = old_url.to_file_path().map_err(|_| unable_to_rename())?;
+    let new_path = old_path.with_file_name(format!(
+        "{}.{}",
+        new_name.trim_start_matches(RAW_IDENTIFIER),
+        sway_utils::constants::SWAY_EXTENSION
+    ));
+    if new_path.exists() {
+        return Err(LanguageServerError::RenameError(
+            RenameError::FileAlreadyExists {
+                path: new_path.to_string_lossy().to_string(),
+            },
+        ));
+    }
+    let new_url = get_url_from_path(&new_path)?;
+
+    let mut edits: HashMap<Url, Vec<OneOf<TextEdit, AnnotatedTextEdit>>> = HashMap::new();
+    for ident in references_of_decls(session, engines, &[module_span]) {
+        let Some(url) = ident
+            .span()
+            .source_id()
+            .and_then(|source_id| get_url_from_path(&engines.se().get_path(source_id)).ok())
+            .and_then(|url| session.sync.to_workspace_url(url))
+        else {
+            continue;
+        };
+        edits
+            .entry(url)
+            .or_default()
+            .push(OneOf::Left(TextEdit::new(
+                ident_range(&ident),
+                new_name.clone(),
+            )));
+    }
+
+    // Apply the text edits before renaming the file, as they may refer to the module's own file.
+    let mut operations: Vec<DocumentChangeOperation> = edits
+        .into_iter()
+        .map(|(uri, mut edits)| {
+            edits.sort_unstable_by_key(|edit| Reverse(text_edit_start(edit)));
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                edits,
             })
         })
-        .flatten()
         .collect();
-    Ok(idents)
+    operations.push(DocumentChangeOperation::Op(ResourceOp::Rename(
+        RenameFile {
+            old_uri: old_url,
+            new_uri: new_url,
+            options: None,
+            annotation_id: None,
+        },
+    )));
+
+    Ok(WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(operations)),
+        ..Default::default()
+    })
+}
+
+fn text_edit_start(edit: &OneOf<TextEdit, AnnotatedTextEdit>) -> Position {
+    match edit {
+        OneOf::Left(edit) => edit.range.start,
+        OneOf::Right(edit) => edit.text_edit.range.start,
+    }
 }
@@ -1,6 +1,7 @@
 use crate::core::token::get_range_from_span;
 use lsp_types::{Command, Range};
 use serde_json::{json, Value};
+use std::path::PathBuf;
 use sway_core::language::parsed::TreeType;
 use sway_types::Span;
 
@@ -18,8 +19,11 @@ pub struct RunnableTestFn {
     pub span: Span,
     /// The program kind of the current file.
     pub tree_type: TreeType,
-    /// Additional arguments to use with the runnable command.
+    /// Additional arguments to use with the runnable command. `None` runs all the tests of the
+    /// package.
     pub test_name: Option<String>,
+    /// The manifest directory of the package that the test belongs to.
+    pub package_path: Option<PathBuf>,
 }
 
 /// A runnable is a sway function that can be executed in the editor.
@@ -66,12 +70,20 @@ impl Runnable for RunnableTestFn {
         "sway.runTests".to_string()
     }
     fn label_string(&self) -> String {
-        "▶\u{fe0e} Run Test".to_string()
+        match self.test_name {
+            Some(_) => "▶\u{fe0e} Run Test".to_string(),
+            None => "▶\u{fe0e} Run All Tests".to_string(),
+        }
     }
     fn arguments(&self) -> Option<Vec<Value>> {
-        self.test_name
-            .as_ref()
-            .map(|test_name| vec![json!({ "name": test_name })])
+        let mut args = serde_json::Map::new();
+        if let Some(test_name) = &self.test_name {
+            args.insert("name".to_string(), json!(test_name));
+        }
+        if let Some(package_path) = &self.package_path {
+            args.insert("packagePath".to_string(), json!(package_path));
+        }
+        (!args.is_empty()).then(|| vec![Value::Object(args)])
     }
     fn span(&self) -> &Span {
         &self.span
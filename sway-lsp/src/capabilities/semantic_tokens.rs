@@ -1,14 +1,25 @@
 use crate::core::{
     session::Session,
-    token::{get_range_from_span, SymbolKind, Token},
+    token::{get_range_from_span, AstToken, SymbolKind, Token, TypedAstToken},
 };
 use lsp_types::{
-    Range, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
+    Position, Range, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
     SemanticTokensResult, Url,
 };
-use std::sync::{
-    atomic::{AtomicU32, Ordering},
-    Arc,
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+use sway_core::{
+    language::{
+        parsed::{Declaration, ImplItem, TraitItem},
+        ty,
+    },
+    transform::AttributeKind,
+    Engines, TypeInfo,
 };
 use sway_types::{Span, Spanned};
 
@@ -27,7 +38,7 @@ pub fn semantic_tokens_full(session: Arc<Session>, url: &Url) -> Option<Semantic
         a.cmp(&b)
     });
 
-    let semantic_tokens = semantic_tokens(&tokens_sorted);
+    let semantic_tokens = semantic_tokens(&engines, &tokens_sorted);
 
     Some(semantic_tokens.into())
 }
@@ -82,6 +93,32 @@ impl SemanticTokensBuilder {
         self.prev_char = range.start.character;
     }
 
+    /// Push a token that may span multiple lines, such as a multi-line string literal, as one
+    /// token per line.
+    pub fn push_span(&mut self, span: &Span, token_index: u32, modifier_bitset: u32) {
+        let start = get_range_from_span(span).start;
+        for (offset, line) in span.as_str().split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            let line_start = if offset == 0 {
+                start
+            } else {
+                Position::new(start.line + offset as u32, 0)
+            };
+            let line_end = Position::new(
+                line_start.line,
+                line_start.character + line.chars().count() as u32,
+            );
+            self.push(
+                Range::new(line_start, line_end),
+                token_index,
+                modifier_bitset,
+            );
+        }
+    }
+
     pub fn build(self) -> SemanticTokens {
         SemanticTokens {
             result_id: Some(self.id),
@@ -90,21 +127,22 @@ impl SemanticTokensBuilder {
     }
 }
 
-pub fn semantic_tokens(tokens_sorted: &[(Span, Token)]) -> SemanticTokens {
+pub fn semantic_tokens(engines: &Engines, tokens_sorted: &[(Span, Token)]) -> SemanticTokens {
     static TOKEN_RESULT_COUNTER: AtomicU32 = AtomicU32::new(1);
     let id = TOKEN_RESULT_COUNTER
         .fetch_add(1, Ordering::SeqCst)
         .to_string();
     let mut builder = SemanticTokensBuilder::new(id);
+    let abi_fn_spans = abi_fn_spans(engines, tokens_sorted);
 
     for (span, token) in tokens_sorted.iter() {
         let ty = semantic_token_type(&token.kind);
         let token_index = type_index(ty);
-        // TODO - improve with modifiers
-        let modifier_bitset = 0;
-        let range = get_range_from_span(span);
+        let modifier_bitset = semantic_token_modifiers(engines, span, token, &abi_fn_spans)
+            .into_iter()
+            .fold(0, |bitset, modifier| bitset | 1 << modifier_index(modifier));
 
-        builder.push(range, token_index, modifier_bitset);
+        builder.push_span(span, token_index, modifier_bitset);
     }
     builder.build()
 }
@@ -146,6 +184,16 @@ pub(crate) const SUPPORTED_MODIFIERS: &[SemanticTokenModifier] = &[
     SemanticTokenModifier::DOCUMENTATION,
     // for symbols that are part of stdlib
     SemanticTokenModifier::DEFAULT_LIBRARY,
+    // for storage fields and accesses to them
+    SemanticTokenModifier::new("storage"),
+    // for configurable constants and their uses
+    SemanticTokenModifier::new("configurable"),
+    // for ABI methods, their implementations and contract calls
+    SemanticTokenModifier::new("abi"),
+    // for compiler intrinsics such as `__size_of`
+    SemanticTokenModifier::new("intrinsic"),
+    // for functions annotated with `#[test]`
+    SemanticTokenModifier::new("test"),
 ];
 
 /// Get the semantic token type from the symbol kind.
@@ -175,6 +223,128 @@ fn semantic_token_type(kind: &SymbolKind) -> SemanticTokenType {
     }
 }
 
+/// Get the semantic token modifiers from the parsed and typed AST nodes of the token.
+///
+/// References are classified by the declaration they refer to, so that a use of a configurable
+/// constant has the same modifiers as its declaration.
+fn semantic_token_modifiers(
+    engines: &Engines,
+    span: &Span,
+    token: &Token,
+    abi_fn_spans: &HashSet<Span>,
+) -> Vec<SemanticTokenModifier> {
+    let mut modifiers = vec![];
+    if token.kind == SymbolKind::Intrinsic {
+        modifiers.push(SemanticTokenModifier::new("intrinsic"));
+    }
+    match &token.parsed {
+        AstToken::StorageField(_) => modifiers.push(SemanticTokenModifier::new("storage")),
+        AstToken::Declaration(Declaration::ConstantDeclaration(decl)) if decl.is_configurable => {
+            modifiers.push(SemanticTokenModifier::new("configurable"));
+        }
+        AstToken::Declaration(Declaration::FunctionDeclaration(decl))
+            if decl.attributes.contains_key(&AttributeKind::Test) =>
+        {
+            modifiers.push(SemanticTokenModifier::new("test"));
+        }
+        AstToken::TraitFn(_) | AstToken::Declaration(Declaration::FunctionDeclaration(_))
+            if abi_fn_spans.contains(span) =>
+        {
+            modifiers.push(SemanticTokenModifier::new("abi"));
+        }
+        _ => {}
+    }
+    match &token.typed {
+        Some(TypedAstToken::TyStorageAccessDescriptor(_)) => {
+            modifiers.push(SemanticTokenModifier::new("storage"));
+        }
+        Some(TypedAstToken::TypedConstantDeclaration(decl)) if decl.is_configurable => {
+            modifiers.push(SemanticTokenModifier::new("configurable"));
+        }
+        Some(TypedAstToken::TypedFunctionDeclaration(decl)) if decl.is_contract_call => {
+            modifiers.push(SemanticTokenModifier::new("abi"));
+        }
+        Some(TypedAstToken::TypedExpression(ty::TyExpression {
+            expression: ty::TyExpressionVariant::FunctionApplication { fn_ref, .. },
+            ..
+        })) => {
+            let decl = engines.de().get_function(fn_ref);
+            if decl.is_contract_call {
+                modifiers.push(SemanticTokenModifier::new("abi"));
+            }
+            if decl.is_test() {
+                modifiers.push(SemanticTokenModifier::new("test"));
+            }
+        }
+        _ => {}
+    }
+    modifiers
+}
+
+/// Collect the spans of the names of the methods declared by the ABIs among the tokens, and of
+/// their implementations for the contract.
+fn abi_fn_spans(engines: &Engines, tokens: &[(Span, Token)]) -> HashSet<Span> {
+    let mut spans = HashSet::new();
+    for (_, token) in tokens {
+        match &token.parsed {
+            AstToken::Declaration(Declaration::AbiDeclaration(abi_decl)) => {
+                spans.extend(
+                    abi_decl
+                        .interface_surface
+                        .iter()
+                        .filter_map(|item| match item {
+                            TraitItem::TraitFn(trait_fn) => Some(trait_fn.name.span()),
+                            TraitItem::Constant(_) => None,
+                        }),
+                );
+            }
+            // Only ABIs can be implemented for the contract.
+            AstToken::Declaration(Declaration::ImplTrait(impl_trait))
+                if matches!(
+                    engines.te().get(impl_trait.implementing_for.type_id),
+                    TypeInfo::Contract
+                ) =>
+            {
+                spans.extend(impl_trait.items.iter().filter_map(|item| match item {
+                    ImplItem::Fn(fn_decl) => Some(fn_decl.name.span()),
+                    ImplItem::Constant(_) => None,
+                }));
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
 fn type_index(ty: SemanticTokenType) -> u32 {
     SUPPORTED_TYPES.iter().position(|it| *it == ty).unwrap() as u32
 }
+
+fn modifier_index(modifier: SemanticTokenModifier) -> u32 {
+    SUPPORTED_MODIFIERS
+        .iter()
+        .position(|it| *it == modifier)
+        .unwrap() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_multiline_tokens_per_line() {
+        let src = "let a = 1;\nlet s = \"ab\n  cde\";";
+        let span = Span::new(src.into(), 19, 29, None).unwrap();
+        assert_eq!(span.as_str(), "\"ab\n  cde\"");
+        let mut builder = SemanticTokensBuilder::new("1".to_string());
+        builder.push(Range::new(Position::new(0, 4), Position::new(0, 5)), 12, 0);
+        builder.push_span(&span, 0, 0);
+        let deltas: Vec<_> = builder
+            .build()
+            .data
+            .iter()
+            .map(|token| (token.delta_line, token.delta_start, token.length))
+            .collect();
+        assert_eq!(deltas, vec![(0, 4, 1), (1, 8, 3), (1, 0, 6)]);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::core::{
+    session::Session,
+    token::{to_ident_key, Token, TypedAstToken},
+};
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, Position,
+    SignatureHelp, SignatureInformation, Url,
+};
+use std::sync::Arc;
+use sway_core::{
+    language::ty::{self, TyExpressionVariant},
+    transform::AttributeKind,
+    type_system::TypeInfo,
+    Engines,
+};
+
+/// A function call whose argument list encloses the cursor.
+#[derive(Debug, PartialEq, Eq)]
+struct Call {
+    /// The byte offset of the name of the called function.
+    name_offset: usize,
+    /// The index of the argument the cursor is in.
+    active_argument: usize,
+    /// Whether the function is called with method syntax, with the receiver before a `.`.
+    is_method_call: bool,
+    /// Whether generic arguments are given with `::<...>` after the name of the called function.
+    has_type_arguments: bool,
+}
+
+/// Returns the signature of the innermost function call whose argument list contains `position`,
+/// with the parameter the cursor is in as the active parameter.
+pub fn signature_help(
+    session: Arc<Session>,
+    uri: &Url,
+    position: Position,
+) -> Option<SignatureHelp> {
+    let text = session.get_text_document(uri).ok()?.get_text();
+    let offset = position_offset(&text, position)?;
+    let engines = session.engines.read();
+
+    enclosing_calls(&text[..offset])
+        .into_iter()
+        .find_map(|call| {
+            let name_position = offset_position(&text, call.name_offset);
+            let (_, token) =
+                session
+                    .token_map()
+                    .token_at_position(engines.se(), uri, name_position)?;
+            let fn_decl = called_function(&session, &engines, &token, call.has_type_arguments)?;
+
+            let has_self = fn_decl
+                .parameters
+                .first()
+                .is_some_and(|param| param.name.as_str() == "self");
+            let active_parameter = if call.is_method_call && has_self {
+                call.active_argument + 1
+            } else {
+                call.active_argument
+            } as u32;
+
+            Some(SignatureHelp {
+                signatures: vec![signature_information(&engines, &fn_decl, active_parameter)],
+                active_signature: Some(0),
+                active_parameter: Some(active_parameter),
+            })
+        })
+}
+
+/// Returns the declaration of the function that `token` calls.
+///
+/// Calls with generic arguments get the declaration with the generic arguments applied, other
+/// calls get the generic declaration of the function.
+fn called_function(
+    session: &Session,
+    engines: &Engines,
+    token: &Token,
+    has_type_arguments: bool,
+) -> Option<ty::TyFunctionDecl> {
+    let Some(TypedAstToken::TypedExpression(expr)) = &token.typed else {
+        return None;
+    };
+    let TyExpressionVariant::FunctionApplication { fn_ref, .. } = &expr.expression else {
+        return None;
+    };
+    let applied_decl = engines.de().get_function(fn_ref);
+    if has_type_arguments {
+        return Some(applied_decl);
+    }
+
+    let decl_token = token.declared_token_ident(engines).and_then(|ident| {
+        session
+            .token_map()
+            .try_get(&to_ident_key(&ident))
+            .try_unwrap()
+    });
+    let generic_decl = decl_token.and_then(|decl_token| match &decl_token.value().typed {
+        Some(TypedAstToken::TypedFunctionDeclaration(decl)) => Some(decl.clone()),
+        Some(TypedAstToken::TypedDeclaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl {
+            decl_id,
+            ..
+        }))) => Some(engines.de().get_function(decl_id)),
+        _ => None,
+    });
+    Some(generic_decl.unwrap_or(applied_decl))
+}
+
+fn signature_information(
+    engines: &Engines,
+    fn_decl: &ty::TyFunctionDecl,
+    active_parameter: u32,
+) -> SignatureInformation {
+    let mut label = format!("fn {}", fn_decl.name.as_str());
+    if !fn_decl.type_parameters.is_empty() {
+        let type_parameters = fn_decl
+            .type_parameters
+            .iter()
+            .map(|type_param| engines.help_out(type_param.type_id).to_string())
+            .collect::<Vec<_>>();
+        label.push_str(&format!("<{}>", type_parameters.join(", ")));
+    }
+
+    label.push('(');
+    let mut parameters = vec![];
+    for (i, param) in fn_decl.parameters.iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+        let start = label.len() as u32;
+        if param.is_reference {
+            label.push_str("ref ");
+        }
+        if param.is_mutable {
+            label.push_str("mut ");
+        }
+        label.push_str(param.name.as_str());
+        if param.name.as_str() != "self" {
+            label.push_str(&format!(
+                ": {}",
+                engines.help_out(param.type_argument.type_id)
+            ));
+        }
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, label.len() as u32]),
+            documentation: None,
+        });
+    }
+    label.push(')');
+    if !matches!(
+        engines.te().get(fn_decl.return_type.type_id),
+        TypeInfo::Tuple(fields) if fields.is_empty()
+    ) {
+        label.push_str(&format!(
+            " -> {}",
+            engines.help_out(fn_decl.return_type.type_id)
+        ));
+    }
+
+    let doc_comment = fn_decl
+        .attributes
+        .get(&AttributeKind::DocComment)
+        .map(|attributes| {
+            attributes
+                .iter()
+                .filter_map(|attribute| attribute.args.first())
+                .map(|arg| arg.name.as_str().trim())
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+
+    SignatureInformation {
+        label,
+        documentation: doc_comment.map(|value| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            })
+        }),
+        parameters: Some(parameters),
+        active_parameter: Some(active_parameter),
+    }
+}
+
+/// Returns the function calls whose argument lists are open at the end of `text`, innermost first.
+fn enclosing_calls(text: &str) -> Vec<Call> {
+    let bytes = text.as_bytes();
+    let mut calls = vec![];
+    let mut depth = 0usize;
+    let mut commas = 0;
+    let mut i = bytes.len();
+    while i > 0 {
+        i -= 1;
+        match bytes[i] {
+            b')' | b']' | b'}' => depth += 1,
+            b'[' | b'{' if depth > 0 => depth -= 1,
+            b'(' if depth > 0 => depth -= 1,
+            b',' if depth == 0 => commas += 1,
+            // Statements and blocks end the search for enclosing calls.
+            b';' | b'{' | b'[' if depth == 0 => {
+                if bytes[i] != b'[' {
+                    break;
+                }
+                commas = 0;
+            }
+            b'(' => {
+                if let Some(call) = call_before_paren(bytes, i, commas) {
+                    calls.push(call);
+                }
+                commas = 0;
+            }
+            _ => {}
+        }
+    }
+    calls
+}
+
+/// Returns the call whose argument list starts with the parenthesis at `paren`, if the parenthesis
+/// follows the name of a function.
+fn call_before_paren(bytes: &[u8], paren: usize, active_argument: usize) -> Option<Call> {
+    let mut end = skip_whitespace_back(bytes, paren);
+    let mut has_type_arguments = false;
+    if end > 0 && bytes[end - 1] == b'>' {
+        // Skip the generic arguments in `name::<...>`.
+        let mut depth = 0;
+        let mut i = end;
+        loop {
+            i = i.checked_sub(1)?;
+            match bytes[i] {
+                b'>' => depth += 1,
+                b'<' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let colons = skip_whitespace_back(bytes, i);
+        if colons < 2 || &bytes[colons - 2..colons] != b"::" {
+            return None;
+        }
+        end = skip_whitespace_back(bytes, colons - 2);
+        has_type_arguments = true;
+    }
+
+    let mut start = end;
+    while start > 0 && (bytes[start - 1].is_ascii_alphanumeric() || bytes[start - 1] == b'_') {
+        start -= 1;
+    }
+    if start == end || bytes[start].is_ascii_digit() {
+        return None;
+    }
+    let before_name = skip_whitespace_back(bytes, start);
+    Some(Call {
+        name_offset: start,
+        active_argument,
+        is_method_call: before_name > 0 && bytes[before_name - 1] == b'.',
+        has_type_arguments,
+    })
+}
+
+/// Returns the offset of the first of the whitespace characters that end at `end`.
+fn skip_whitespace_back(bytes: &[u8], mut end: usize) -> usize {
+    while end > 0 && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    end
+}
+
+/// Returns the byte offset of `position` in `text`.
+fn position_offset(text: &str, position: Position) -> Option<usize> {
+    let mut offset = 0;
+    for (line_number, line) in text.split_inclusive('\n').enumerate() {
+        if line_number == position.line as usize {
+            let character = line
+                .char_indices()
+                .nth(position.character as usize)
+                .map_or(line.len(), |(i, _)| i);
+            return Some(offset + character);
+        }
+        offset += line.len();
+    }
+    (position.line as usize == text.split_inclusive('\n').count()).then_some(offset)
+}
+
+/// Returns the [Position] of the byte `offset` in `text`.
+fn offset_position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() as u32;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character = before[line_start..].chars().count() as u32;
+    Position { line, character }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name_offset: usize, active_argument: usize, is_method_call: bool) -> Call {
+        Call {
+            name_offset,
+            active_argument,
+            is_method_call,
+            has_type_arguments: false,
+        }
+    }
+
+    #[test]
+    fn finds_enclosing_calls_innermost_first() {
+        let text = "let x = p.shift(add(1, 2), ";
+        assert_eq!(enclosing_calls(text), vec![call(10, 1, true)]);
+
+        let text = "let x = p.shift(add(1, ";
+        assert_eq!(
+            enclosing_calls(text),
+            vec![call(16, 1, false), call(10, 0, true)]
+        );
+
+        let text = "foo((1, 2), [3, 4], ";
+        assert_eq!(enclosing_calls(text), vec![call(0, 2, false)]);
+
+        let text = "let x = id::<Vec<u64>>(";
+        assert_eq!(
+            enclosing_calls(text),
+            vec![Call {
+                has_type_arguments: true,
+                ..call(8, 0, false)
+            }]
+        );
+
+        assert_eq!(enclosing_calls("foo(1);\nbar"), vec![]);
+    }
+}
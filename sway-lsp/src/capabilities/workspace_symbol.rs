@@ -0,0 +1,98 @@
+use crate::{
+    capabilities::document_symbol::symbol_info,
+    core::{session::Session, token::SymbolKind},
+    utils::document::get_url_from_path,
+};
+use lsp_types::SymbolInformation;
+use std::sync::Arc;
+use sway_types::Spanned;
+
+/// The maximum number of symbols returned for a query.
+const MAX_WORKSPACE_SYMBOLS: usize = 128;
+
+/// Returns the declarations in the packages of `sessions` whose names fuzzy match `query`.
+///
+/// Exact matches come first, then prefix, substring and subsequence matches, with shorter names
+/// first for matches of the same quality.
+pub(crate) fn workspace_symbols(sessions: &[Arc<Session>], query: &str) -> Vec<SymbolInformation> {
+    let query = query.to_lowercase();
+    let mut matches = vec![];
+    for session in sessions {
+        let engines = session.engines.read();
+        for (ident, token) in session.token_map().iter() {
+            let is_symbol = matches!(
+                token.kind,
+                SymbolKind::Struct
+                    | SymbolKind::Enum
+                    | SymbolKind::Variant
+                    | SymbolKind::Field
+                    | SymbolKind::Trait
+                    | SymbolKind::Function
+                    | SymbolKind::Const
+                    | SymbolKind::TypeAlias
+            );
+            if !is_symbol || token.declared_token_span(&engines) != Some(ident.span()) {
+                continue;
+            }
+            let Some(score) = match_score(&ident.as_str().to_lowercase(), &query) else {
+                continue;
+            };
+            // Only declarations in the package itself are listed, not those of its dependencies.
+            let Some(url) = ident
+                .span()
+                .source_id()
+                .and_then(|source_id| get_url_from_path(&engines.se().get_path(source_id)).ok())
+                .filter(|url| session.sync.is_path_in_temp_workspace(url))
+                .and_then(|url| session.sync.to_workspace_url(url))
+            else {
+                continue;
+            };
+            matches.push((score, symbol_info(&ident, &token, url)));
+        }
+    }
+
+    matches.sort_by(|(score, a), (other_score, b)| {
+        (score, a.name.len(), &a.name, a.location.uri.as_str())
+            .cmp(&(other_score, b.name.len(), &b.name, b.location.uri.as_str()))
+            .then(a.location.range.start.cmp(&b.location.range.start))
+    });
+    matches.dedup_by(|(_, a), (_, b)| a.name == b.name && a.location == b.location);
+    matches
+        .into_iter()
+        .take(MAX_WORKSPACE_SYMBOLS)
+        .map(|(_, symbol)| symbol)
+        .collect()
+}
+
+/// Returns how well the lowercase `name` matches the lowercase `query`, lower being better, or
+/// `None` if the characters of `query` don't appear in order in `name`.
+fn match_score(name: &str, query: &str) -> Option<u8> {
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else {
+        let mut name_chars = name.chars();
+        query
+            .chars()
+            .all(|c| name_chars.any(|name_char| name_char == c))
+            .then_some(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_fuzzy_matches() {
+        assert_eq!(match_score("area", "area"), Some(0));
+        assert_eq!(match_score("area_of", "area"), Some(1));
+        assert_eq!(match_score("unit_area", "area"), Some(2));
+        assert_eq!(match_score("unit_area", "uar"), Some(3));
+        assert_eq!(match_score("unit_area", "rau"), None);
+        assert_eq!(match_score("square", ""), Some(1));
+    }
+}
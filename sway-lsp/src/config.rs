@@ -93,12 +93,14 @@ pub enum Warnings {
 
 // Options for configuring inlay hints.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct InlayHintsConfig {
     /// Whether to render leading colons for type hints, and trailing colons for parameter hints.
     pub render_colons: bool,
     /// Whether to show inlay type hints for variables.
     pub type_hints: bool,
+    /// Whether to show inlay parameter name hints for literal arguments of function calls.
+    pub parameter_hints: bool,
     /// Maximum length for inlay hints. Set to null to have an unlimited length.
     pub max_length: Option<usize>,
 }
@@ -108,6 +110,7 @@ impl Default for InlayHintsConfig {
         Self {
             render_colons: true,
             type_hints: true,
+            parameter_hints: true,
             max_length: Some(25),
         }
     }
@@ -1,7 +1,7 @@
 use crate::{
     capabilities::{
         self,
-        diagnostic::{get_diagnostics, Diagnostics},
+        diagnostic::{get_diagnostics, DiagnosticMap},
         formatting::get_page_text_edit,
         runnable::{Runnable, RunnableMainFn, RunnableTestFn},
     },
@@ -19,30 +19,33 @@ use crate::{
 use dashmap::DashMap;
 use forc_pkg as pkg;
 use lsp_types::{
-    CompletionItem, GotoDefinitionResponse, Location, Position, Range, SymbolInformation,
+    CompletionItem, DocumentSymbol, GotoDefinitionResponse, Location, Position, Range,
     TextDocumentContentChangeEvent, TextEdit, Url,
 };
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use pkg::manifest::ManifestFile;
 use std::{
+    collections::HashSet,
     fs::File,
     io::Write,
     path::PathBuf,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     vec,
 };
 use sway_core::{
     decl_engine::DeclEngine,
     language::{
         lexed::LexedProgram,
-        parsed::{AstNode, ParseProgram},
+        parsed::{AstNode, ParseModule, ParseProgram},
         ty,
     },
     BuildTarget, CompileResult, Engines, Namespace, Programs,
 };
 use sway_types::{Span, Spanned};
 use sway_utils::helpers::get_sway_files;
-use tokio::sync::Semaphore;
 
 pub type Documents = DashMap<String, TextDocument>;
 pub type ProjectDirectory = PathBuf;
@@ -52,6 +55,8 @@ pub struct CompiledProgram {
     pub lexed: Option<LexedProgram>,
     pub parsed: Option<ParseProgram>,
     pub typed: Option<ty::TyProgram>,
+    /// The typed programs of the dependencies of the package.
+    pub dependencies: Vec<ty::TyProgram>,
 }
 
 /// A `Session` is used to store information about a single member in a workspace.
@@ -66,11 +71,16 @@ pub struct Session {
     pub compiled_program: RwLock<CompiledProgram>,
     pub engines: RwLock<Engines>,
     pub sync: SyncWorkspace,
-    // Limit the number of threads that can wait to parse at the same time. One thread can be parsing
-    // and one thread can be waiting to start parsing. All others will return the cached diagnostics.
-    parse_permits: Arc<Semaphore>,
+    // The cancellation flag of the most recently started compilation. It is set as soon as a newer
+    // compilation starts, so that outdated compilations stop early and don't publish diagnostics.
+    cancel_compilation: Mutex<Arc<AtomicBool>>,
+    // The dependencies checked by previous compilations, which are reused while they don't change.
+    check_cache: Mutex<pkg::CheckCache>,
     // Cached diagnostic results that require a lock to access. Readers will wait for writers to complete.
-    diagnostics: Arc<RwLock<Diagnostics>>,
+    diagnostics: Arc<RwLock<DiagnosticMap>>,
+    // The files which were given diagnostics since the last compilation that completed, which have
+    // to be cleared by the next one if they no longer have any.
+    reported_files: Mutex<HashSet<PathBuf>>,
 }
 
 impl Session {
@@ -82,8 +92,10 @@ impl Session {
             compiled_program: RwLock::new(Default::default()),
             engines: <_>::default(),
             sync: SyncWorkspace::new(),
-            parse_permits: Arc::new(Semaphore::new(2)),
-            diagnostics: Arc::new(RwLock::new(Diagnostics::default())),
+            cancel_compilation: Mutex::new(Arc::new(AtomicBool::new(false))),
+            check_cache: Mutex::new(Default::default()),
+            diagnostics: Arc::new(RwLock::new(DiagnosticMap::default())),
+            reported_files: Mutex::new(HashSet::new()),
         }
     }
 
@@ -123,22 +135,39 @@ impl Session {
         &self.token_map
     }
 
-    /// Wait for the cached [Diagnostics] to be unlocked after parsing and return a copy.
-    pub fn wait_for_parsing(&self) -> Diagnostics {
+    /// Wait for the cached [DiagnosticMap] to be unlocked after parsing and return a copy.
+    pub fn wait_for_parsing(&self) -> DiagnosticMap {
         self.diagnostics.read().clone()
     }
 
-    /// Parses the project and returns true if the compiler diagnostics are new and should be published.
-    pub fn parse_project(&self, uri: &Url) -> Result<bool, LanguageServerError> {
-        // Acquire a permit to parse the project. If there are none available, return false. This way,
-        // we avoid publishing the same diagnostics multiple times.
-        let permit = self.parse_permits.try_acquire();
-        if permit.is_err() {
-            return Ok(false);
-        }
+    /// Cancels the compilation in progress, if any, and returns the cancellation flag of a new
+    /// compilation, which is set once the new compilation is superseded in turn.
+    pub fn start_compilation(&self) -> Arc<AtomicBool> {
+        let mut cancel_compilation = self.cancel_compilation.lock();
+        cancel_compilation.store(true, Ordering::Relaxed);
+        *cancel_compilation = Arc::new(AtomicBool::new(false));
+        cancel_compilation.clone()
+    }
 
+    /// Parses the project and returns true if the compiler diagnostics are new and should be published.
+    ///
+    /// `on_dependency_checked` is given the diagnostics of each dependency that is checked, as soon
+    /// as it is checked. Once parsing completes, the session holds the diagnostics of every file of
+    /// the project and its dependencies, along with empty diagnostics for the files which no longer
+    /// have any.
+    ///
+    /// Returns false without updating the session if `cancel` is set before parsing completes.
+    pub fn parse_project(
+        &self,
+        uri: &Url,
+        cancel: &AtomicBool,
+        mut on_dependency_checked: impl FnMut(DiagnosticMap),
+    ) -> Result<bool, LanguageServerError> {
         // Lock the diagnostics result to prevent multiple threads from parsing the project at the same time.
         let mut diagnostics = self.diagnostics.write();
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
 
         let manifest_dir = PathBuf::from(uri.path());
         let locked = false;
@@ -177,17 +206,55 @@ impl Session {
         )
         .map_err(LanguageServerError::BuildPlanFailed)?;
 
-        let new_engines = Engines::default();
         let tests_enabled = true;
 
-        let results = pkg::check(
+        // Diagnostics which aren't part of any file are given to the file being parsed.
+        let fallback_path = &manifest_dir;
+        let mut reported_files = self.reported_files.lock();
+        let Some((new_engines, results)) = pkg::check_with_cache(
             &plan,
             BuildTarget::default(),
             true,
             tests_enabled,
-            &new_engines,
+            &mut self.check_cache.lock(),
+            cancel,
+            |engines, res| {
+                let mut dependency_diagnostics =
+                    get_diagnostics(&res.warnings, &res.errors, engines.se(), fallback_path);
+                // The file being parsed is part of the project, whose diagnostics aren't known yet.
+                dependency_diagnostics.remove(fallback_path);
+                reported_files.extend(dependency_diagnostics.keys().cloned());
+                on_dependency_checked(dependency_diagnostics);
+            },
         )
-        .map_err(LanguageServerError::FailedToCompile)?;
+        .map_err(LanguageServerError::FailedToCompile)?
+        else {
+            return Ok(false);
+        };
+
+        // The file being parsed always gets diagnostics, even if it has none, to clear its former
+        // ones.
+        let mut new_diagnostics = DiagnosticMap::new();
+        new_diagnostics.insert(fallback_path.clone(), Default::default());
+        for res in &results {
+            let package_diagnostics =
+                get_diagnostics(&res.warnings, &res.errors, new_engines.se(), fallback_path);
+            for (path, file_diagnostics) in package_diagnostics {
+                let entry = new_diagnostics.entry(path).or_default();
+                entry.warnings.extend(file_diagnostics.warnings);
+                entry.errors.extend(file_diagnostics.errors);
+            }
+        }
+        for path in reported_files.drain() {
+            new_diagnostics.entry(path).or_default();
+        }
+        reported_files.extend(
+            new_diagnostics
+                .iter()
+                .filter(|(_, file_diagnostics)| !file_diagnostics.is_empty())
+                .map(|(path, _)| path.clone()),
+        );
+        *diagnostics = new_diagnostics;
 
         // Acquire locks for the engines before clearing anything.
         let mut engines = self.engines.write();
@@ -200,6 +267,7 @@ impl Session {
         self.runnables.clear();
 
         let results_len = results.len();
+        let mut dependency_programs = vec![];
         for (i, res) in results.into_iter().enumerate() {
             // We can convert these destructured elements to a Vec<Diagnostic> later on.
             let CompileResult {
@@ -208,25 +276,24 @@ impl Session {
                 errors,
             } = res;
 
-            if value.is_none() {
-                // If there was an unrecoverable error in the parser
-                // make sure to still return the diagnostics.
-                *diagnostics = get_diagnostics(&warnings, &errors);
-                continue;
-            }
-            let Programs {
+            // If there was an unrecoverable error in the parser, the diagnostics are still
+            // returned.
+            let Some(Programs {
                 lexed,
                 parsed,
                 typed,
-            } = value.unwrap();
+            }) = value
+            else {
+                continue;
+            };
 
             let ast_res = CompileResult::new(typed, warnings, errors);
 
             // Get a reference to the typed program AST.
-            let typed_program = ast_res.value.as_ref().ok_or_else(|| {
-                *diagnostics = get_diagnostics(&ast_res.warnings, &ast_res.errors);
-                LanguageServerError::FailedToParse
-            })?;
+            let typed_program = ast_res
+                .value
+                .as_ref()
+                .ok_or(LanguageServerError::FailedToParse)?;
 
             // Create context with write guards to make readers wait until the update to token_map is complete.
             // This operation is fast because we already have the compile results.
@@ -254,8 +321,6 @@ impl Session {
                 self.save_lexed_program(lexed.to_owned().clone());
                 self.save_parsed_program(parsed.to_owned().clone());
                 self.save_typed_program(typed_program.to_owned().clone());
-
-                *diagnostics = get_diagnostics(&ast_res.warnings, &ast_res.errors);
             } else {
                 // Collect tokens from dependencies and the standard library prelude.
                 self.parse_ast_to_tokens(&parsed, &ctx, |an, ctx| {
@@ -265,8 +330,10 @@ impl Session {
                 self.parse_ast_to_typed_tokens(typed_program, &ctx, |node, ctx| {
                     dependency::collect_typed_declaration(node, ctx)
                 });
+                dependency_programs.push(typed_program.to_owned());
             }
         }
+        self.compiled_program.write().dependencies = dependency_programs;
         Ok(true)
     }
 
@@ -350,12 +417,18 @@ impl Session {
         Some(program.root.namespace)
     }
 
-    pub fn symbol_information(&self, url: &Url) -> Option<Vec<SymbolInformation>> {
+    /// Returns the symbol tree of the file at `url`, which is either part of the package or of
+    /// one of its dependencies.
+    pub fn document_symbols(&self, url: &Url) -> Option<Vec<DocumentSymbol>> {
         let engines = self.engines.read();
-        let tokens = self.token_map.tokens_for_file(engines.se(), url);
-        self.sync
-            .to_workspace_url(url.clone())
-            .map(|url| capabilities::document_symbol::to_symbol_information(tokens, url))
+        let compiled_program = &*self.compiled_program.read();
+        compiled_program
+            .typed
+            .iter()
+            .chain(&compiled_program.dependencies)
+            .find_map(|typed_program| {
+                capabilities::document_symbol::document_symbols(typed_program, &engines, url)
+            })
     }
 
     pub fn format_text(&self, url: &Url) -> Result<Vec<TextEdit>, LanguageServerError> {
@@ -457,14 +530,18 @@ impl Session {
         ctx: &ParseContext,
         f: impl Fn(&AstNode, &ParseContext),
     ) {
-        let root_nodes = parse_program.root.tree.root_nodes.iter();
-        let sub_nodes = parse_program
-            .root
-            .submodules
-            .iter()
-            .flat_map(|(_, submodule)| &submodule.module.tree.root_nodes);
-
-        root_nodes.chain(sub_nodes).for_each(|n| f(n, ctx));
+        // Traverse the root module first, followed by all of its submodules, depth-first.
+        fn traverse_module(
+            module: &ParseModule,
+            ctx: &ParseContext,
+            f: &impl Fn(&AstNode, &ParseContext),
+        ) {
+            module.tree.root_nodes.iter().for_each(|n| f(n, ctx));
+            for (_, submodule) in &module.submodules {
+                traverse_module(&submodule.module, ctx, f);
+            }
+        }
+        traverse_module(&parse_program.root, ctx, &f);
     }
 
     /// Parse the [ty::TyProgram] AST to populate the [TokenMap] with typed AST nodes.
@@ -477,8 +554,7 @@ impl Session {
         let root_nodes = typed_program.root.all_nodes.iter();
         let sub_nodes = typed_program
             .root
-            .submodules
-            .iter()
+            .submodules_recursive()
             .flat_map(|(_, submodule)| submodule.module.all_nodes.iter());
 
         root_nodes.chain(sub_nodes).for_each(|n| f(n, ctx));
@@ -486,19 +562,54 @@ impl Session {
 
     /// Create runnables if the `TyProgramKind` of the `TyProgram` is a script.
     fn create_runnables(&self, typed_program: &ty::TyProgram, decl_engine: &DeclEngine) {
-        // Insert runnable test functions.
-        for (decl, _) in typed_program.test_fns(decl_engine) {
-            // Get the span of the first attribute if it exists, otherwise use the span of the function name.
-            let span = decl
-                .attributes
-                .first()
-                .map_or_else(|| decl.name.span(), |(_, attr)| attr.span.clone());
-            let runnable = Box::new(RunnableTestFn {
-                span,
-                tree_type: typed_program.kind.tree_type(),
-                test_name: Some(decl.name.to_string()),
-            });
-            self.runnables.insert(runnable.span().clone(), runnable);
+        let package_path = self.sync.manifest_dir().ok();
+        let modules = std::iter::once(&typed_program.root).chain(
+            typed_program
+                .root
+                .submodules_recursive()
+                .map(|(_, submodule)| &submodule.module),
+        );
+        for module in modules {
+            let test_fns: Vec<_> = module.test_fns(decl_engine).collect();
+            if test_fns.is_empty() {
+                continue;
+            }
+
+            // Insert a runnable for all the tests of the package on the first line of the module.
+            let src = module.span.src().clone();
+            let first_line_end = src[module.span.start()..]
+                .find('\n')
+                .map_or(src.len(), |i| module.span.start() + i);
+            if let Some(span) = Span::new(
+                src,
+                module.span.start(),
+                first_line_end,
+                module.span.source_id().cloned(),
+            ) {
+                let runnable = Box::new(RunnableTestFn {
+                    span,
+                    tree_type: typed_program.kind.tree_type(),
+                    test_name: None,
+                    package_path: package_path.clone(),
+                });
+                self.runnables.insert(runnable.span().clone(), runnable);
+            }
+
+            // Insert runnable test functions.
+            for (decl, _) in test_fns {
+                // Get the span of the first attribute if it exists, otherwise use the span of the function name.
+                let span = decl
+                    .attributes
+                    .first()
+                    .map_or_else(|| decl.name.span(), |(_, attr)| attr.span.clone());
+                let runnable = Box::new(RunnableTestFn {
+                    span,
+                    tree_type: typed_program.kind.tree_type(),
+                    test_name: Some(decl.name.to_string()),
+                    package_path: package_path.clone(),
+                });
+                self.runnables.insert(runnable.span().clone(), runnable);
+            }
         }
 
         // Insert runnable main function if the program is a script.
@@ -575,8 +686,8 @@ mod tests {
         let session = Session::new();
         let dir = get_absolute_path("sway-lsp/tests/fixtures");
         let uri = get_url(&dir);
-        let result =
-            Session::parse_project(&session, &uri).expect_err("expected ManifestFileNotFound");
+        let result = Session::parse_project(&session, &uri, &AtomicBool::new(false), |_| {})
+            .expect_err("expected ManifestFileNotFound");
         assert!(matches!(
             result,
             LanguageServerError::DocumentError(
@@ -585,4 +696,18 @@ mod tests {
             if test_dir == dir
         ));
     }
+
+    #[test]
+    fn parse_project_returns_false_once_cancelled() {
+        let session = Session::new();
+        let cancel = session.start_compilation();
+        let newer_cancel = session.start_compilation();
+        assert!(cancel.load(Ordering::Relaxed));
+        assert!(!newer_cancel.load(Ordering::Relaxed));
+
+        // A cancelled compilation stops before even looking for the manifest.
+        let uri = get_url(&get_absolute_path("sway-lsp/tests/fixtures"));
+        let result = Session::parse_project(&session, &uri, &cancel, |_| {});
+        assert_eq!(result.ok(), Some(false));
+    }
 }
@@ -3,7 +3,11 @@ use crate::{
     utils::document::{get_path_from_url, get_url_from_path, get_url_from_span},
 };
 use dashmap::DashMap;
-use forc_pkg::{manifest::Dependency, PackageManifestFile};
+use forc_pkg::{
+    manifest::Dependency,
+    source::git::{Reference, SourceIndex},
+    PackageManifestFile,
+};
 use lsp_types::Url;
 use notify::RecursiveMode;
 use notify_debouncer_mini::new_debouncer;
@@ -13,17 +17,27 @@ use std::{
     fs::{self, File},
     io::{Read, Write},
     path::{Path, PathBuf},
-    sync::{atomic::AtomicBool, mpsc, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
     thread::JoinHandle,
     time::Duration,
 };
 use sway_types::{SourceEngine, Span};
 use tempfile::Builder;
 
+/// The scheme of the URLs of the read-only documents that files of dependencies are shown as,
+/// e.g. `sway-lib:///std@v0.40.1/src/option.sw`.
+pub const DEPENDENCY_DOCUMENT_SCHEME: &str = "sway-lib";
+
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub enum Directory {
     Manifest,
     Temp,
+    /// The directory of the read-only copies of dependency files, for clients that can't show
+    /// documents with the [DEPENDENCY_DOCUMENT_SCHEME].
+    Dependencies,
 }
 
 #[derive(Debug)]
@@ -32,16 +46,28 @@ pub struct SyncWorkspace {
     pub notify_join_handle: RwLock<Option<JoinHandle<()>>>,
     // if we should shutdown the thread watching the manifest file
     pub should_end: Arc<AtomicBool>,
+    /// Whether the client can show documents with the [DEPENDENCY_DOCUMENT_SCHEME]. Otherwise,
+    /// dependency files are shown as read-only copies.
+    pub virtual_documents: AtomicBool,
+    /// The paths of the dependency files that have been shown to the client, by the URLs of
+    /// their documents.
+    dependency_paths: DashMap<Url, PathBuf>,
+    /// The URLs that files outside of the temp directory are shown at, by their paths.
+    dependency_urls: DashMap<PathBuf, Url>,
 }
 
 impl SyncWorkspace {
     pub const LSP_TEMP_PREFIX: &'static str = "SWAY_LSP_TEMP_DIR";
+    pub const LSP_DEPENDENCIES_PREFIX: &'static str = "SWAY_LSP_DEPENDENCIES";
 
     pub(crate) fn new() -> Self {
         Self {
             directories: DashMap::new(),
             notify_join_handle: RwLock::new(None),
             should_end: Arc::new(AtomicBool::new(false)),
+            virtual_documents: AtomicBool::new(false),
+            dependency_paths: DashMap::new(),
+            dependency_urls: DashMap::new(),
         }
     }
 
@@ -51,6 +77,9 @@ impl SyncWorkspace {
         if let Ok(dir) = self.temp_dir() {
             dir.parent().map(fs::remove_dir);
         }
+        if let Some(dir) = self.directories.get(&Directory::Dependencies) {
+            let _ = fs::remove_dir_all(dir.value());
+        }
     }
 
     pub(crate) fn create_temp_dir_from_workspace(
@@ -149,13 +178,108 @@ impl SyncWorkspace {
     }
 
     /// If path is part of the users workspace, then convert URL from temp to workspace dir.
-    /// Otherwise, pass through if it points to a dependency path
+    /// Otherwise, convert the URL to the read-only document of the file if it points to a
+    /// dependency path, see [SyncWorkspace::dependency_document_url].
     pub(crate) fn to_workspace_url(&self, url: Url) -> Option<Url> {
         if self.is_path_in_temp_workspace(&url) {
             Some(self.temp_to_workspace_url(&url).ok()?)
         } else {
-            Some(url)
+            Some(self.dependency_document_url(url))
+        }
+    }
+
+    /// Returns the path of the dependency file shown in the document at `url`, if `url` was
+    /// handed out by [SyncWorkspace::to_workspace_url].
+    pub(crate) fn dependency_path(&self, url: &Url) -> Option<PathBuf> {
+        self.dependency_paths
+            .get(url)
+            .map(|item| item.value().clone())
+    }
+
+    /// Returns the URL of the read-only document that the file at `url` is shown as, if the file
+    /// is part of a dependency.
+    ///
+    /// Files of the package itself and of the other members of its workspace are returned as-is,
+    /// as they can be edited by the user.
+    fn dependency_document_url(&self, url: Url) -> Url {
+        let Ok(path) = url.to_file_path() else {
+            return url;
+        };
+        if let Some(document_url) = self.dependency_urls.get(&path) {
+            return document_url.value().clone();
+        }
+        let document_url = self.create_dependency_document(&path).unwrap_or(url);
+        self.dependency_urls.insert(path, document_url.clone());
+        document_url
+    }
+
+    /// Creates the read-only document of the dependency file at `path` and returns its URL, or
+    /// `None` if the file isn't part of a dependency.
+    ///
+    /// The document is shown at a path made of the package name, with the git reference of forc
+    /// managed checkouts, followed by the path of the file in the package.
+    fn create_dependency_document(&self, path: &Path) -> Option<Url> {
+        let manifest = PackageManifestFile::from_dir(path).ok()?;
+        if self.is_workspace_member(manifest.dir()) {
+            return None;
+        }
+        let document_path = Path::new(&package_label(
+            &manifest,
+            &forc_util::git_checkouts_directory(),
+        ))
+        .join(path.strip_prefix(manifest.dir()).ok()?);
+
+        let url = if self.virtual_documents.load(Ordering::Relaxed) {
+            let mut url = Url::parse(&format!("{DEPENDENCY_DOCUMENT_SCHEME}:///")).ok()?;
+            url.set_path(document_path.to_str()?);
+            url
+        } else {
+            let copy_path = self.dependencies_dir()?.join(document_path);
+            fs::create_dir_all(copy_path.parent()?).ok()?;
+            fs::copy(path, &copy_path).ok()?;
+            let mut permissions = fs::metadata(&copy_path).ok()?.permissions();
+            permissions.set_readonly(true);
+            fs::set_permissions(&copy_path, permissions).ok()?;
+            get_url_from_path(&copy_path).ok()?
+        };
+        self.dependency_paths
+            .insert(url.clone(), path.to_path_buf());
+        Some(url)
+    }
+
+    /// Returns `true` if `dir` is the directory of the package or of a member of its workspace.
+    fn is_workspace_member(&self, dir: &Path) -> bool {
+        let Ok(manifest_dir) = self.manifest_dir() else {
+            return false;
+        };
+        if dir == manifest_dir {
+            return true;
+        }
+        let Some(workspace) = PackageManifestFile::from_dir(&manifest_dir)
+            .ok()
+            .and_then(|manifest| manifest.workspace().ok()?)
+        else {
+            return false;
+        };
+        workspace.member_pkg_manifests().is_ok_and(|mut members| {
+            members.any(|member| member.is_ok_and(|member| member.dir() == dir))
+        })
+    }
+
+    /// Returns the directory of the read-only copies of dependency files, creating it if it
+    /// doesn't exist yet.
+    fn dependencies_dir(&self) -> Option<PathBuf> {
+        if let Some(dir) = self.directories.get(&Directory::Dependencies) {
+            return Some(dir.value().clone());
         }
+        let dir = Builder::new()
+            .prefix(SyncWorkspace::LSP_DEPENDENCIES_PREFIX)
+            .tempdir()
+            .ok()?
+            .into_path();
+        self.directories
+            .insert(Directory::Dependencies, dir.clone());
+        Some(dir)
     }
 
     pub(crate) fn temp_manifest_path(&self) -> Option<PathBuf> {
@@ -283,6 +407,34 @@ pub(crate) fn edit_manifest_dependency_paths(
     }
 }
 
+/// Returns the name of the package of `manifest`, followed by the git reference it is checked
+/// out at if it is part of a forc managed checkout in `checkouts_dir`, e.g. `std@v0.40.1`.
+fn package_label(manifest: &PackageManifestFile, checkouts_dir: &Path) -> String {
+    let name = &manifest.project.name;
+    match checkout_reference(manifest.dir(), checkouts_dir) {
+        Some(reference) => format!("{name}@{reference}"),
+        None => name.clone(),
+    }
+}
+
+/// Returns the git reference of the forc managed checkout in `checkouts_dir` that `dir` is part
+/// of. Revisions and default branches are given by the short hash of the checked out commit.
+fn checkout_reference(dir: &Path, checkouts_dir: &Path) -> Option<String> {
+    // Checkouts are stored in `<repo dir name>/<commit hash>`, with an index of the reference.
+    let mut components = dir.strip_prefix(checkouts_dir).ok()?.components();
+    let commit_dir = checkouts_dir
+        .join(components.next()?)
+        .join(components.next()?);
+    let index = fs::read_to_string(commit_dir.join(".forc_index")).ok()?;
+    let index: SourceIndex = serde_json::from_str(&index).ok()?;
+    let (commit_hash, _) = index.head_with_time;
+    match index.git_reference {
+        Reference::Tag(tag) => Some(tag),
+        Reference::Branch(branch) => Some(branch),
+        Reference::Rev(_) | Reference::DefaultBranch => Some(commit_hash.chars().take(7).collect()),
+    }
+}
+
 /// Copy the contents of the current workspace folder into the target directory
 fn copy_dir_contents(
     src_dir: impl AsRef<Path>,
@@ -300,3 +452,46 @@ fn copy_dir_contents(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkout_reference_of_forc_managed_checkouts() {
+        let checkouts_dir = tempfile::tempdir().unwrap();
+        let commit_hash = "0123456789abcdef0123456789abcdef01234567";
+        let commit_dir = checkouts_dir.path().join("sway-1a2b3c").join(commit_hash);
+        let package_dir = commit_dir.join("sway-lib-std");
+        fs::create_dir_all(&package_dir).unwrap();
+
+        let index = SourceIndex::new(0, Reference::Tag("v0.40.1".into()), commit_hash.into());
+        fs::write(
+            commit_dir.join(".forc_index"),
+            serde_json::to_string(&index).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            checkout_reference(&package_dir, checkouts_dir.path()),
+            Some("v0.40.1".to_string())
+        );
+
+        let index = SourceIndex::new(0, Reference::DefaultBranch, commit_hash.into());
+        fs::write(
+            commit_dir.join(".forc_index"),
+            serde_json::to_string(&index).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            checkout_reference(&package_dir, checkouts_dir.path()),
+            Some("0123456".to_string())
+        );
+
+        // Packages outside of the checkouts directory have no reference.
+        let other_dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            checkout_reference(other_dir.path(), checkouts_dir.path()),
+            None
+        );
+    }
+}
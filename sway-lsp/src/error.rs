@@ -82,4 +82,14 @@ pub enum RenameError {
     FileAlreadyExists { path: String },
     #[error("The module {:?}: cannot be renamed", path)]
     UnableToRenameModule { path: String },
+    #[error(
+        "The ABI method {:?} cannot be renamed, as that would change its function selector",
+        name
+    )]
+    AbiMethod { name: String },
+    #[error(
+        "Unable to rename to {:?}: a declaration with that name already exists in the same scope",
+        name
+    )]
+    NameCollision { name: String },
 }
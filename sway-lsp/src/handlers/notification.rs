@@ -5,22 +5,24 @@ use crate::{capabilities, core::sync, server_state::ServerState};
 use forc_pkg::PackageManifestFile;
 use lsp_types::{
     DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, FileChangeType,
+    DidSaveTextDocumentParams, FileChangeType, Url,
 };
 
 pub(crate) async fn handle_did_open_text_document(
     state: &ServerState,
     params: DidOpenTextDocumentParams,
 ) {
+    // The documents of dependency files are read-only, and don't require the project to be parsed.
+    if is_dependency_document(state, &params.text_document.uri) {
+        return;
+    }
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
     {
         Ok((uri, session)) => {
             session.handle_open_file(&uri);
-            state
-                .parse_project(uri, params.text_document.uri, session.clone())
-                .await;
+            state.parse_project(uri, session.clone()).await;
         }
         Err(err) => tracing::error!("{}", err.to_string()),
     }
@@ -30,6 +32,9 @@ pub(crate) async fn handle_did_change_text_document(
     state: &ServerState,
     params: DidChangeTextDocumentParams,
 ) {
+    if is_dependency_document(state, &params.text_document.uri) {
+        return;
+    }
     let config = state.config.read().on_enter.clone();
     match state
         .sessions
@@ -42,9 +47,7 @@ pub(crate) async fn handle_did_change_text_document(
             // update this file with the new changes and write to disk
             match session.write_changes_to_file(&uri, params.content_changes) {
                 Ok(_) => {
-                    state
-                        .parse_project(uri, params.text_document.uri.clone(), session)
-                        .await;
+                    state.parse_project_debounced(uri, session).await;
                 }
                 Err(err) => tracing::error!("{}", err.to_string()),
             }
@@ -57,6 +60,9 @@ pub(crate) async fn handle_did_save_text_document(
     state: &ServerState,
     params: DidSaveTextDocumentParams,
 ) {
+    if is_dependency_document(state, &params.text_document.uri) {
+        return;
+    }
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
@@ -77,9 +83,7 @@ pub(crate) async fn handle_did_save_text_document(
                         sync::edit_manifest_dependency_paths(&manifest, temp_manifest_path)
                     }
                 });
-            state
-                .parse_project(uri, params.text_document.uri, session)
-                .await;
+            state.parse_project(uri, session).await;
         }
         Err(err) => tracing::error!("{}", err.to_string()),
     }
@@ -100,3 +104,8 @@ pub(crate) async fn handle_did_change_watched_files(
         }
     }
 }
+
+/// Returns `true` if `uri` is the read-only document of a dependency file.
+fn is_dependency_document(state: &ServerState, uri: &Url) -> bool {
+    state.sessions.dependency_path_and_session(uri).is_some()
+}
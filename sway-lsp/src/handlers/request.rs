@@ -1,22 +1,33 @@
 //! This module is responsible for implementing handlers for Language Server
 //! Protocol. This module specifically handles requests.
 
-use crate::{capabilities, lsp_ext, server_state::ServerState, utils::debug};
+use crate::{
+    capabilities, core::session::Session, lsp_ext, server_state::ServerState, utils::debug,
+};
+use forc_pkg::PackageManifestFile;
 use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
 use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
     CodeLens, CompletionResponse, DocumentFormattingParams, DocumentSymbolResponse,
-    InitializeResult, InlayHint, InlayHintParams, PrepareRenameResponse, RenameParams,
-    SemanticTokensParams, SemanticTokensResult, TextDocumentIdentifier, Url, WorkspaceEdit,
+    InitializeResult, InlayHint, InlayHintParams, Location, PrepareRenameResponse, ReferenceParams,
+    RenameParams, SemanticTokensParams, SemanticTokensResult, SymbolInformation,
+    TextDocumentIdentifier, Url, WorkspaceEdit, WorkspaceSymbolParams,
 };
 use std::{
     fs::File,
     io::Write,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use sway_types::{Ident, Spanned};
 use tower_lsp::jsonrpc::Result;
 use tracing::metadata::LevelFilter;
 
+/// The experimental capability of clients and the server for the `workspace/textDocumentContent`
+/// request.
+pub(crate) const TEXT_DOCUMENT_CONTENT_CAPABILITY: &str = "textDocumentContent";
+
 pub(crate) fn handle_initialize(
     state: &ServerState,
     params: lsp_types::InitializeParams,
@@ -27,6 +38,15 @@ pub(crate) fn handle_initialize(
             .ok()
             .unwrap_or_default();
     }
+    // Clients that can request the contents of documents show dependency files as virtual
+    // documents, other clients are given read-only copies of the files.
+    let virtual_documents = params
+        .capabilities
+        .experimental
+        .as_ref()
+        .and_then(|experimental| experimental.get(TEXT_DOCUMENT_CONTENT_CAPABILITY))
+        .is_some_and(|capability| capability.is_object() || capability.as_bool() == Some(true));
+    state.sessions.set_virtual_documents(virtual_documents);
     // Initalizing tracing library based on the user's config
     let config = state.config.read();
     if config.logging.level != LevelFilter::OFF {
@@ -54,8 +74,8 @@ pub(crate) fn handle_document_symbol(
         .uri_and_session_from_workspace(&params.text_document.uri)
     {
         Ok((uri, session)) => Ok(session
-            .symbol_information(&uri)
-            .map(DocumentSymbolResponse::Flat)),
+            .document_symbols(&uri)
+            .map(DocumentSymbolResponse::Nested)),
         Err(err) => {
             tracing::error!("{}", err.to_string());
             Ok(None)
@@ -130,6 +150,27 @@ pub(crate) fn handle_hover(
     }
 }
 
+pub(crate) fn handle_signature_help(
+    state: &ServerState,
+    params: lsp_types::SignatureHelpParams,
+) -> Result<Option<lsp_types::SignatureHelp>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::signature_help::signature_help(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
 pub(crate) fn handle_prepare_rename(
     state: &ServerState,
     params: lsp_types::TextDocumentPositionParams,
@@ -228,6 +269,7 @@ pub(crate) fn handle_code_action(
             &params.range,
             params.text_document,
             &temp_uri,
+            &params.context.diagnostics,
         )),
         Err(err) => {
             tracing::error!("{}", err.to_string());
@@ -245,10 +287,17 @@ pub(crate) fn handle_code_lens(
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
     {
-        Ok((_, session)) => {
-            // Construct code lenses for runnable functions
+        Ok((uri, session)) => {
+            let engines = session.engines.read();
+            // Construct code lenses for the runnable functions of the file
             session.runnables.iter().for_each(|item| {
                 let runnable = item.value();
+                let in_file = runnable.span().source_id().is_some_and(|source_id| {
+                    engines.se().get_path(source_id).to_str() == Some(uri.path())
+                });
+                if !in_file {
+                    return;
+                }
                 result.push(CodeLens {
                     range: runnable.range(),
                     command: Some(runnable.command()),
@@ -310,6 +359,171 @@ pub(crate) fn handle_inlay_hints(
     }
 }
 
+pub(crate) async fn handle_references(
+    state: &ServerState,
+    params: ReferenceParams,
+) -> Result<Option<Vec<Location>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position.position;
+            let Some(decl_locations) =
+                capabilities::references::declaration_locations(session.clone(), &uri, position)
+            else {
+                return Ok(None);
+            };
+
+            let sessions = sessions_depending_on_locations(state, session, &decl_locations).await;
+            let include_declaration = params.context.include_declaration;
+            let mut locations: Vec<Location> = vec![];
+            for session in sessions {
+                for location in capabilities::references::references(
+                    session,
+                    &decl_locations,
+                    include_declaration,
+                ) {
+                    if !locations.contains(&location) {
+                        locations.push(location);
+                    }
+                }
+            }
+            locations.sort_by(|a, b| {
+                (a.uri.as_str(), a.range.start).cmp(&(b.uri.as_str(), b.range.start))
+            });
+            Ok(Some(locations))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Returns `session` along with the sessions of the workspace members that declare the items at
+/// `decl_locations` and of the members depending on them.
+async fn sessions_depending_on_locations(
+    state: &ServerState,
+    session: Arc<Session>,
+    decl_locations: &[Location],
+) -> Vec<Arc<Session>> {
+    let mut pkg_dirs: Vec<PathBuf> = vec![];
+    for decl_location in decl_locations {
+        if let Ok(path) = decl_location.uri.to_file_path() {
+            if let Ok(manifest) = PackageManifestFile::from_dir(&path) {
+                if !pkg_dirs.iter().any(|dir| dir == manifest.dir()) {
+                    pkg_dirs.push(manifest.dir().to_path_buf());
+                }
+            }
+        }
+    }
+    let mut sessions = vec![session];
+    for pkg_dir in &pkg_dirs {
+        for member_session in state.member_sessions_depending_on(pkg_dir).await {
+            if !sessions
+                .iter()
+                .any(|session| Arc::ptr_eq(session, &member_session))
+            {
+                sessions.push(member_session);
+            }
+        }
+    }
+    sessions
+}
+
+pub(crate) fn handle_prepare_call_hierarchy(
+    state: &ServerState,
+    params: CallHierarchyPrepareParams,
+) -> Result<Option<Vec<CallHierarchyItem>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::call_hierarchy::prepare_call_hierarchy(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub(crate) async fn handle_incoming_calls(
+    state: &ServerState,
+    params: CallHierarchyIncomingCallsParams,
+) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.item.uri)
+    {
+        Ok((_, session)) => {
+            let decl_locations =
+                capabilities::call_hierarchy::callee_locations(session.clone(), &params.item);
+            if decl_locations.is_empty() {
+                return Ok(None);
+            }
+
+            // Contract calls resolve to the ABI declaration, so the callers of ABI methods are
+            // found in the members depending on the ABI.
+            let sessions = sessions_depending_on_locations(state, session, &decl_locations).await;
+            let mut calls: Vec<CallHierarchyIncomingCall> = vec![];
+            for session in sessions {
+                for mut call in
+                    capabilities::call_hierarchy::incoming_calls(session, &decl_locations)
+                {
+                    match calls.iter_mut().find(|existing| {
+                        existing.from.uri == call.from.uri
+                            && existing.from.selection_range == call.from.selection_range
+                    }) {
+                        Some(existing) => {
+                            call.from_ranges
+                                .retain(|range| !existing.from_ranges.contains(range));
+                            existing.from_ranges.extend(call.from_ranges);
+                        }
+                        None => calls.push(call),
+                    }
+                }
+            }
+            for call in &mut calls {
+                call.from_ranges.sort_by_key(|range| range.start);
+            }
+            calls.sort_by(|a, b| {
+                (a.from.uri.as_str(), a.from.selection_range.start)
+                    .cmp(&(b.from.uri.as_str(), b.from.selection_range.start))
+            });
+            Ok(Some(calls))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub(crate) fn handle_outgoing_calls(
+    state: &ServerState,
+    params: CallHierarchyOutgoingCallsParams,
+) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.item.uri)
+    {
+        Ok((_, session)) => Ok(capabilities::call_hierarchy::outgoing_calls(
+            session,
+            &params.item,
+        )),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
 /// This method is triggered by a command palette request in VScode
 /// The 3 commands are: "show lexed ast", "show parsed ast" or "show typed ast"
 ///
@@ -413,3 +627,32 @@ pub(crate) fn handle_show_ast(
         }
     }
 }
+
+/// Returns the contents of the read-only document of a dependency file.
+pub(crate) fn handle_text_document_content(
+    state: &ServerState,
+    params: lsp_ext::TextDocumentContentParams,
+) -> Result<Option<lsp_ext::TextDocumentContentResult>> {
+    let Some((path, _)) = state.sessions.dependency_path_and_session(&params.uri) else {
+        tracing::error!("No dependency document found at {}", params.uri);
+        return Ok(None);
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(lsp_ext::TextDocumentContentResult { text })),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub(crate) async fn handle_workspace_symbol(
+    state: &ServerState,
+    params: WorkspaceSymbolParams,
+) -> Result<Option<Vec<SymbolInformation>>> {
+    let sessions = state.workspace_member_sessions().await;
+    Ok(Some(capabilities::workspace_symbol::workspace_symbols(
+        &sessions,
+        &params.query,
+    )))
+}
@@ -10,3 +10,17 @@ pub struct ShowAstParams {
     pub ast_kind: String,
     pub save_path: Url,
 }
+
+/// The parameters of the `workspace/textDocumentContent` request, which returns the contents of
+/// the read-only documents of dependency files.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDocumentContentParams {
+    pub uri: Url,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDocumentContentResult {
+    pub text: String,
+}
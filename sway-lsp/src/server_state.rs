@@ -1,18 +1,28 @@
 //! The context or environment in which the language server functions.
 
 use crate::{
+    capabilities::diagnostic::DiagnosticMap,
     config::{Config, Warnings},
     core::session::Session,
     error::{DirectoryError, DocumentError, LanguageServerError},
     utils::debug,
-    utils::keyword_docs::KeywordDocs,
+    utils::{document::get_url_from_path, keyword_docs::KeywordDocs},
 };
 use dashmap::DashMap;
 use forc_pkg::PackageManifestFile;
-use lsp_types::Url;
+use lsp_types::{Diagnostic, Url};
 use parking_lot::RwLock;
-use std::{path::PathBuf, sync::Arc};
-use tokio::task;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
 use tower_lsp::{jsonrpc, Client};
 
 /// `ServerState` is the primary mutable state of the language server
@@ -25,7 +35,7 @@ pub struct ServerState {
 
 impl ServerState {
     pub fn new(client: Client) -> ServerState {
-        let sessions = Arc::new(Sessions(DashMap::new()));
+        let sessions = Arc::new(Sessions::default());
         let config = Arc::new(RwLock::new(Default::default()));
         let keyword_docs = Arc::new(KeywordDocs::new());
         ServerState {
@@ -45,81 +55,299 @@ impl ServerState {
         Ok(())
     }
 
-    async fn publish_diagnostics(&self, uri: &Url, workspace_uri: &Url, session: Arc<Session>) {
-        let diagnostics_res = {
-            let mut diagnostics_to_publish = vec![];
-            let config = &self.config.read();
-            let engines = session.engines.read();
-            let tokens = session.token_map().tokens_for_file(engines.se(), uri);
-            match config.debug.show_collected_tokens_as_warnings {
-                // If collected_tokens_as_warnings is Parsed or Typed,
-                // take over the normal error and warning display behavior
-                // and instead show the either the parsed or typed tokens as warnings.
-                // This is useful for debugging the lsp parser.
-                Warnings::Parsed => diagnostics_to_publish
-                    .extend(debug::generate_warnings_for_parsed_tokens(tokens)),
-                Warnings::Typed => {
-                    diagnostics_to_publish.extend(debug::generate_warnings_for_typed_tokens(tokens))
+    /// Publishes the diagnostics of each file in `diagnostics` to the document of the file.
+    async fn publish_diagnostics(&self, session: &Session, diagnostics: DiagnosticMap) {
+        let diagnostics_res =
+            {
+                let mut diagnostics_to_publish: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+                let config = &self.config.read();
+                let engines = session.engines.read();
+                for (path, diagnostics) in diagnostics {
+                    let Ok(url) = Url::from_file_path(&path) else {
+                        continue;
+                    };
+                    let Some(workspace_url) = session.sync.to_workspace_url(url.clone()) else {
+                        continue;
+                    };
+                    let file_diagnostics = diagnostics_to_publish.entry(workspace_url).or_default();
+                    let tokens = session.token_map().tokens_for_file(engines.se(), &url);
+                    match config.debug.show_collected_tokens_as_warnings {
+                        // If collected_tokens_as_warnings is Parsed or Typed,
+                        // take over the normal error and warning display behavior
+                        // and instead show the either the parsed or typed tokens as warnings.
+                        // This is useful for debugging the lsp parser.
+                        Warnings::Parsed => file_diagnostics
+                            .extend(debug::generate_warnings_for_parsed_tokens(tokens)),
+                        Warnings::Typed => file_diagnostics
+                            .extend(debug::generate_warnings_for_typed_tokens(tokens)),
+                        Warnings::Default => {}
+                    }
+                    if config.diagnostic.show_warnings {
+                        file_diagnostics.extend(diagnostics.warnings);
+                    }
+                    if config.diagnostic.show_errors {
+                        file_diagnostics.extend(diagnostics.errors);
+                    }
                 }
-                Warnings::Default => {}
+                diagnostics_to_publish
+            };
+
+        // Note: Even if the computed diagnostics vec is empty, we still have to push the empty Vec
+        // in order to clear former diagnostics. Newly pushed diagnostics always replace previously pushed diagnostics.
+        for (url, diagnostics) in diagnostics_res {
+            self.client
+                .publish_diagnostics(url, diagnostics, None)
+                .await;
+        }
+    }
+
+    /// Returns the sessions of the workspace member in `pkg_dir` and of all workspace members that
+    /// depend on it, either directly or through other members.
+    ///
+    /// Members that haven't been parsed yet are parsed first, so that their tokens can be searched.
+    pub(crate) async fn member_sessions_depending_on(&self, pkg_dir: &Path) -> Vec<Arc<Session>> {
+        let Some((manifest, workspace)) = PackageManifestFile::from_dir(pkg_dir)
+            .ok()
+            .and_then(|manifest| Some((manifest.clone(), manifest.workspace().ok()??)))
+        else {
+            return vec![];
+        };
+        let Ok(members) = workspace.member_pkg_manifests() else {
+            return vec![];
+        };
+        let members: Vec<_> = members.filter_map(Result::ok).collect();
+
+        // Follow the path dependencies between members until no more dependents are found.
+        let mut dependent_dirs = vec![manifest.dir().to_path_buf()];
+        loop {
+            let new_dependent_dirs: Vec<_> = members
+                .iter()
+                .filter(|member| !dependent_dirs.iter().any(|dir| dir == member.dir()))
+                .filter(|member| {
+                    member.dependencies.iter().flatten().any(|(name, _)| {
+                        member
+                            .dep_path(name)
+                            .is_some_and(|dep_dir| dependent_dirs.contains(&dep_dir))
+                    })
+                })
+                .map(|member| member.dir().to_path_buf())
+                .collect();
+            if new_dependent_dirs.is_empty() {
+                break;
             }
-            let diagnostics = session.wait_for_parsing();
-            if config.diagnostic.show_warnings {
-                diagnostics_to_publish.extend(diagnostics.warnings);
+            dependent_dirs.extend(new_dependent_dirs);
+        }
+
+        let mut sessions = vec![];
+        for member in &members {
+            if !dependent_dirs.iter().any(|dir| dir == member.dir()) {
+                continue;
             }
-            if config.diagnostic.show_errors {
-                diagnostics_to_publish.extend(diagnostics.errors);
+            sessions.extend(self.parsed_session(member).await);
+        }
+        sessions
+    }
+
+    /// Returns the sessions of the packages that are open in the editor, along with the sessions
+    /// of all the other members of their workspaces.
+    ///
+    /// Members that haven't been parsed yet are parsed first, so that their tokens can be searched.
+    pub(crate) async fn workspace_member_sessions(&self) -> Vec<Arc<Session>> {
+        let open_dirs: Vec<PathBuf> = self
+            .sessions
+            .iter()
+            .map(|item| item.key().clone())
+            .collect();
+        let mut package_dirs: Vec<PathBuf> = vec![];
+        let mut manifests = vec![];
+        for dir in open_dirs {
+            let Ok(manifest) = PackageManifestFile::from_dir(&dir) else {
+                continue;
+            };
+            let members = match manifest.workspace() {
+                Ok(Some(workspace)) => workspace
+                    .member_pkg_manifests()
+                    .map(|members| members.filter_map(Result::ok).collect())
+                    .unwrap_or_default(),
+                _ => vec![manifest],
+            };
+            for member in members {
+                if !package_dirs.iter().any(|dir| dir == member.dir()) {
+                    package_dirs.push(member.dir().to_path_buf());
+                    manifests.push(member);
+                }
             }
-            diagnostics_to_publish
-        };
+        }
 
-        // Note: Even if the computed diagnostics vec is empty, we still have to push the empty Vec
-        // in order to clear former diagnostics. Newly pushed diagnostics always replace previously pushed diagnostics.
-        self.client
-            .publish_diagnostics(workspace_uri.clone(), diagnostics_res, None)
-            .await;
+        let mut sessions = vec![];
+        for manifest in &manifests {
+            sessions.extend(self.parsed_session(manifest).await);
+        }
+        sessions
     }
 
-    pub(crate) async fn parse_project(&self, uri: Url, workspace_uri: Url, session: Arc<Session>) {
-        let should_publish = run_blocking_parse_project(uri.clone(), session.clone()).await;
-        if should_publish {
-            self.publish_diagnostics(&uri, &workspace_uri, session)
-                .await;
+    /// Returns the session of the package of `manifest`, parsing the package if it hasn't been
+    /// parsed yet.
+    async fn parsed_session(&self, manifest: &PackageManifestFile) -> Option<Arc<Session>> {
+        let entry_url = get_url_from_path(&manifest.entry_path()).ok()?;
+        match self.sessions.uri_and_session_from_workspace(&entry_url) {
+            Ok((uri, session)) => {
+                if session.token_map().is_empty() {
+                    // This compilation doesn't cancel the one in progress, as that one still has to
+                    // publish its diagnostics.
+                    let cancel = Arc::new(AtomicBool::new(false));
+                    run_blocking_parse_project(uri, session.clone(), cancel, |_| {}).await;
+                }
+                Some(session)
+            }
+            Err(err) => {
+                tracing::error!("{}", err.to_string());
+                None
+            }
         }
     }
-}
 
-/// Runs parse_project in a blocking thread, because parsing is not async.
-async fn run_blocking_parse_project(uri: Url, session: Arc<Session>) -> bool {
-    task::spawn_blocking(move || match session.parse_project(&uri) {
-        Ok(should_publish) => should_publish,
-        Err(err) => {
-            tracing::error!("{}", err);
-            matches!(err, LanguageServerError::FailedToParse)
+    /// Parses the project and publishes its diagnostics, cancelling the compilation of the project
+    /// that is in progress, if any.
+    pub(crate) async fn parse_project(&self, uri: Url, session: Arc<Session>) {
+        let cancel = session.start_compilation();
+        self.parse_project_and_publish(uri, session, cancel).await;
+    }
+
+    /// Like [ServerState::parse_project], but only parses the project once it hasn't changed for
+    /// [COMPILATION_DEBOUNCE], so that a burst of changes leads to a single compilation.
+    pub(crate) async fn parse_project_debounced(&self, uri: Url, session: Arc<Session>) {
+        let cancel = session.start_compilation();
+        tokio::time::sleep(COMPILATION_DEBOUNCE).await;
+        if cancel.load(Ordering::Relaxed) {
+            return;
         }
-    })
-    .await
-    .unwrap_or_default()
+        self.parse_project_and_publish(uri, session, cancel).await;
+    }
+
+    /// Parses the project and publishes the diagnostics of each package as soon as the package is
+    /// checked.
+    ///
+    /// The diagnostics of the dependencies are published while the packages depending on them are
+    /// still being checked, and those of the project once its tokens are collected. Nothing is
+    /// published once the compilation is superseded, as its diagnostics are outdated.
+    async fn parse_project_and_publish(
+        &self,
+        uri: Url,
+        session: Arc<Session>,
+        cancel: Arc<AtomicBool>,
+    ) {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let parse =
+            run_blocking_parse_project(uri, session.clone(), cancel.clone(), move |diagnostics| {
+                let _ = sender.send(diagnostics);
+            });
+        let publish_dependencies = async {
+            let mut published = HashSet::new();
+            // The channel is closed once parsing completes.
+            while let Some(diagnostics) = receiver.recv().await {
+                if !cancel.load(Ordering::Relaxed) {
+                    published.extend(diagnostics.keys().cloned());
+                    self.publish_diagnostics(&session, diagnostics).await;
+                }
+            }
+            published
+        };
+        let (should_publish, published) = tokio::join!(parse, publish_dependencies);
+        if should_publish && !cancel.load(Ordering::Relaxed) {
+            let mut diagnostics = session.wait_for_parsing();
+            diagnostics.retain(|path, _| !published.contains(path));
+            self.publish_diagnostics(&session, diagnostics).await;
+        }
+    }
+}
+
+/// How long the project has to stay unchanged before it is parsed again after a change.
+const COMPILATION_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The stack size of the threads that parse projects. Type checking the standard library recurses
+/// deeply, which leaves little room on the default stack of blocking tasks.
+const PARSE_THREAD_STACK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Runs parse_project in a separate thread, because parsing is not async.
+async fn run_blocking_parse_project(
+    uri: Url,
+    session: Arc<Session>,
+    cancel: Arc<AtomicBool>,
+    on_dependency_checked: impl FnMut(DiagnosticMap) + Send + 'static,
+) -> bool {
+    let (sender, receiver) = oneshot::channel();
+    let spawned = thread::Builder::new()
+        .name("sway-lsp-parse".into())
+        .stack_size(PARSE_THREAD_STACK_SIZE)
+        .spawn(move || {
+            let should_publish = match session.parse_project(&uri, &cancel, on_dependency_checked) {
+                Ok(should_publish) => should_publish,
+                Err(err) => {
+                    tracing::error!("{}", err);
+                    matches!(err, LanguageServerError::FailedToParse)
+                }
+            };
+            let _ = sender.send(should_publish);
+        });
+    if let Err(err) = spawned {
+        tracing::error!("{}", err);
+        return false;
+    }
+    receiver.await.unwrap_or_default()
 }
 
 /// `Sessions` is a collection of [Session]s, each of which represents a project
 /// that has been opened in the users workspace.
-pub(crate) struct Sessions(DashMap<PathBuf, Arc<Session>>);
+#[derive(Default)]
+pub(crate) struct Sessions {
+    sessions: DashMap<PathBuf, Arc<Session>>,
+    /// Whether the client can show the virtual documents of dependency files, see
+    /// [crate::core::sync::DEPENDENCY_DOCUMENT_SCHEME].
+    virtual_documents: AtomicBool,
+}
 
 impl Sessions {
     fn init(&self, uri: &Url) -> Result<(), LanguageServerError> {
         let session = Arc::new(Session::new());
+        session.sync.virtual_documents.store(
+            self.virtual_documents.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
         let project_name = session.init(uri)?;
         self.insert(project_name, session);
         Ok(())
     }
 
+    /// Sets whether the client can show the virtual documents of dependency files, rather than
+    /// read-only copies of the files.
+    pub(crate) fn set_virtual_documents(&self, virtual_documents: bool) {
+        self.virtual_documents
+            .store(virtual_documents, Ordering::Relaxed);
+    }
+
+    /// Returns the path of the dependency file shown in the document at `url`, along with the
+    /// session that showed the document.
+    pub(crate) fn dependency_path_and_session(&self, url: &Url) -> Option<(PathBuf, Arc<Session>)> {
+        self.iter().find_map(|item| {
+            let session = item.value();
+            let path = session.sync.dependency_path(url)?;
+            Some((path, session.clone()))
+        })
+    }
+
     /// Constructs and returns a tuple of `(Url, Arc<Session>)` from a given workspace URI.
     /// The returned URL represents the temp directory workspace.
+    ///
+    /// For the read-only documents of dependency files, the returned URL is the one of the
+    /// dependency file itself, and the session is the one that showed the document.
     pub(crate) fn uri_and_session_from_workspace(
         &self,
         workspace_uri: &Url,
     ) -> Result<(Url, Arc<Session>), LanguageServerError> {
+        if let Some((path, session)) = self.dependency_path_and_session(workspace_uri) {
+            return Ok((get_url_from_path(&path)?, session));
+        }
         let session = self.url_to_session(workspace_uri)?;
         let uri = session.sync.workspace_to_temp_url(workspace_uri)?;
         Ok((uri, session))
@@ -158,6 +386,6 @@ impl Sessions {
 impl std::ops::Deref for Sessions {
     type Target = DashMap<PathBuf, Arc<Session>>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.sessions
     }
 }
@@ -120,40 +120,41 @@ impl Parse for ty::TySideEffect {
                 }
                 match &import_type {
                     ImportType::Item(item) => {
+                        let mut symbol_kind = SymbolKind::Unknown;
+                        let mut type_def = None;
+                        if let Some(decl_ident) = ctx
+                            .namespace
+                            .submodule(call_path)
+                            .and_then(|module| module.symbols().get(item))
+                            .and_then(|decl| decl.get_decl_ident())
+                        {
+                            // Update the symbol kind to match the declarations symbol kind
+                            if let Some(decl) =
+                                ctx.tokens.try_get(&to_ident_key(&decl_ident)).try_unwrap()
+                            {
+                                symbol_kind = decl.value().kind.clone();
+                            }
+                            type_def = Some(TypeDefinition::Ident(decl_ident));
+                        }
+                        // The tokens are updated one at a time, as holding a token locks the
+                        // tokens that share its shard of the map.
                         if let Some(mut token) =
                             ctx.tokens.try_get_mut(&to_ident_key(item)).try_unwrap()
                         {
                             token.typed =
                                 Some(TypedAstToken::TypedUseStatement(use_statement.clone()));
-                            let mut symbol_kind = SymbolKind::Unknown;
-                            let mut type_def = None;
-                            if let Some(decl_ident) = ctx
-                                .namespace
-                                .submodule(call_path)
-                                .and_then(|module| module.symbols().get(item))
-                                .and_then(|decl| decl.get_decl_ident())
-                            {
-                                // Update the symbol kind to match the declarations symbol kind
-                                if let Some(decl) =
-                                    ctx.tokens.try_get(&to_ident_key(&decl_ident)).try_unwrap()
-                                {
-                                    symbol_kind = decl.value().kind.clone();
-                                }
-                                type_def = Some(TypeDefinition::Ident(decl_ident));
-                            }
                             token.kind = symbol_kind.clone();
                             token.type_def = type_def.clone();
-                            // the alias should take on the same symbol kind and type definition
-                            if let Some(alias) = alias {
-                                if let Some(mut token) =
-                                    ctx.tokens.try_get_mut(&to_ident_key(alias)).try_unwrap()
-                                {
-                                    token.typed = Some(TypedAstToken::TypedUseStatement(
-                                        use_statement.clone(),
-                                    ));
-                                    token.kind = symbol_kind;
-                                    token.type_def = type_def;
-                                }
+                        }
+                        // the alias should take on the same symbol kind and type definition
+                        if let Some(alias) = alias {
+                            if let Some(mut token) =
+                                ctx.tokens.try_get_mut(&to_ident_key(alias)).try_unwrap()
+                            {
+                                token.typed =
+                                    Some(TypedAstToken::TypedUseStatement(use_statement.clone()));
+                                token.kind = symbol_kind;
+                                token.type_def = type_def;
                             }
                         }
                     }
@@ -326,23 +327,15 @@ impl Parse for ty::TyExpression {
                     });
                 collect_call_path_prefixes(ctx, &call_path_binding.inner.prefixes);
                 fields.iter().for_each(|field| {
+                    let field_decl_ident = struct_field_ident(ctx, self.return_type, &field.name);
                     if let Some(mut token) = ctx
                         .tokens
                         .try_get_mut(&to_ident_key(&field.name))
                         .try_unwrap()
                     {
                         token.typed = Some(TypedAstToken::TypedExpression(field.value.clone()));
-
-                        if let Some(struct_decl) = &ctx
-                            .tokens
-                            .struct_declaration_of_type_id(ctx.engines, &self.return_type)
-                        {
-                            struct_decl.fields.iter().for_each(|decl_field| {
-                                if decl_field.name == field.name {
-                                    token.type_def =
-                                        Some(TypeDefinition::Ident(decl_field.name.clone()));
-                                }
-                            });
+                        if let Some(ident) = field_decl_ident {
+                            token.type_def = Some(TypeDefinition::Ident(ident));
                         }
                     }
                     field.value.parse(ctx);
@@ -470,17 +463,50 @@ impl Parse for ty::TyExpression {
                 address.parse(ctx);
             }
             ty::TyExpressionVariant::StorageAccess(storage_access) => {
-                storage_access.fields.iter().for_each(|field| {
+                // The first field refers to a storage field, each subsequent field refers
+                // to a field of the struct type of the field that precedes it.
+                let storage_fields = ctx
+                    .namespace
+                    .get_declared_storage(ctx.engines.de())
+                    .map(|storage_decl| storage_decl.fields)
+                    .unwrap_or_default();
+                let mut parent_type_id: Option<TypeId> = None;
+                for field in &storage_access.fields {
+                    let field_decl_ident = match parent_type_id {
+                        None => storage_fields
+                            .iter()
+                            .find(|storage_field| storage_field.name == field.name)
+                            .map(|storage_field| storage_field.name.clone()),
+                        Some(type_id) => struct_field_ident(ctx, type_id, &field.name),
+                    };
                     if let Some(mut token) = ctx
                         .tokens
                         .try_get_mut(&to_ident_key(&field.name))
                         .try_unwrap()
                     {
                         token.typed = Some(TypedAstToken::TyStorageAccessDescriptor(field.clone()));
+                        if let Some(ident) = field_decl_ident {
+                            token.type_def = Some(TypeDefinition::Ident(ident));
+                        }
                     }
-                });
+                    parent_type_id = Some(field.type_id);
+                }
             }
             ty::TyExpressionVariant::IntrinsicFunction(kind) => {
+                // The expression starts with the name of the intrinsic, as in `__size_of`.
+                let name_span = Span::new(
+                    self.span.src().clone(),
+                    self.span.start(),
+                    self.span.start() + format!("__{}", kind.kind).len(),
+                    self.span.source_id().cloned(),
+                );
+                if let Some(mut token) = name_span.and_then(|span| {
+                    ctx.tokens
+                        .try_get_mut(&to_ident_key(&Ident::new(span)))
+                        .try_unwrap()
+                }) {
+                    token.typed = Some(TypedAstToken::TypedExpression(self.clone()));
+                }
                 kind.parse(ctx);
             }
             ty::TyExpressionVariant::AbiName { .. } => {}
@@ -1166,6 +1192,10 @@ fn collect_const_decl(ctx: &ParseContext, const_decl: &ty::TyConstantDecl, span:
     {
         token.typed = Some(TypedAstToken::TypedConstantDeclaration(const_decl.clone()));
         token.type_def = Some(TypeDefinition::Ident(const_decl.call_path.suffix.clone()));
+        // Uses of constants are parsed as ambiguous variable expressions.
+        if token.kind == SymbolKind::Unknown {
+            token.kind = SymbolKind::Const;
+        }
     }
     if let Some(call_path_tree) = &const_decl.type_ascription.call_path_tree {
         collect_call_path_tree(ctx, call_path_tree, &const_decl.type_ascription);
@@ -1351,3 +1381,18 @@ fn collect_enum(ctx: &ParseContext, decl_id: &DeclId<ty::TyEnumDecl>, declaratio
         variant.parse(ctx);
     });
 }
+
+/// Returns the [Ident] of the field named `field_name` declared in the struct that `type_id` refers to.
+fn struct_field_ident(ctx: &ParseContext, type_id: TypeId, field_name: &Ident) -> Option<Ident> {
+    match ctx.engines.te().get(type_id) {
+        TypeInfo::Struct(decl_ref) => ctx
+            .engines
+            .de()
+            .get_struct(&decl_ref)
+            .fields
+            .iter()
+            .find(|field| &field.name == field_name)
+            .map(|field| field.name.clone()),
+        _ => None,
+    }
+}
@@ -55,6 +55,14 @@ impl Markup {
         }
     }
 
+    /// If contents is `Some`, add the contents as text.
+    pub fn maybe_add_text(self, contents: Option<String>) -> Self {
+        match contents {
+            Some(contents) => self.text(&contents),
+            None => self,
+        }
+    }
+
     fn quoted_tooltip(&self, text: String) -> String {
         format!("\"{}\"", text)
     }
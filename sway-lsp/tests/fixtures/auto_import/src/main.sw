@@ -0,0 +1,20 @@
+contract;
+
+mod utils;
+
+use std::context::{msg_amount, this_balance};
+use std::hash::keccak256;
+
+abi Api {
+    #[storage(read)]
+    fn hash() -> b256;
+}
+
+impl Api for Contract {
+    #[storage(read)]
+    fn hash() -> b256 {
+        let _ = balance_of(BASE_ASSET_ID, BASE_ASSET_ID) + msg_amount();
+        let _ = Bytes::new();
+        keccak256(sha256(ZERO_B256))
+    }
+}
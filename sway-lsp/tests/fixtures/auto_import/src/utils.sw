@@ -0,0 +1,11 @@
+//! Utilities shared by the contract.
+library;
+
+pub struct Bytes {
+    len: u64,
+}
+
+/// Returns the hash of zero.
+pub fn zero_hash() -> b256 {
+    sha256(0)
+}
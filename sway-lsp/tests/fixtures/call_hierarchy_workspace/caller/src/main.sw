@@ -0,0 +1,12 @@
+script;
+
+use counter_abi::Counter;
+
+fn call_counter() -> u64 {
+    let counter = abi(Counter, 0x0000000000000000000000000000000000000000000000000000000000000000);
+    counter.increment() + counter.increment()
+}
+
+fn main() -> u64 {
+    call_counter()
+}
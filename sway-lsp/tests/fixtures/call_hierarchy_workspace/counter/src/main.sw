@@ -0,0 +1,25 @@
+contract;
+
+use counter_abi::Counter;
+
+storage {
+    count: u64 = 0,
+}
+
+fn add_one(value: u64) -> u64 {
+    value + 1
+}
+
+#[storage(read, write)]
+fn bump() -> u64 {
+    let count = add_one(storage.count.read()) + __size_of::<u64>();
+    storage.count.write(count);
+    count
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        bump()
+    }
+}
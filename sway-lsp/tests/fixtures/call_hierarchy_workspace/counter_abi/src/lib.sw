@@ -0,0 +1,6 @@
+library;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
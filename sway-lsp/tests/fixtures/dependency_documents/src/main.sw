@@ -0,0 +1,9 @@
+script;
+
+fn first(value: Option<u64>) -> u64 {
+    value.unwrap_or(0)
+}
+
+fn main() -> u64 {
+    first(Some(1))
+}
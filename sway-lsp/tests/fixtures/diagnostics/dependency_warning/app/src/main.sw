@@ -0,0 +1,7 @@
+script;
+
+use lib::double;
+
+fn main() -> u64 {
+    double(2)
+}
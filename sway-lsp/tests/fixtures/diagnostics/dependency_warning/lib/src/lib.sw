@@ -0,0 +1,9 @@
+library;
+
+pub fn double(x: u64) -> u64 {
+    x * 2
+}
+
+fn not_used() -> u64 {
+    1
+}
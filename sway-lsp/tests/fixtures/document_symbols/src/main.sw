@@ -0,0 +1,66 @@
+contract;
+
+mod utils;
+
+const MAX: u64 = 10;
+
+configurable {
+    OWNER: u64 = 1,
+    LIMIT: u64 = 2,
+}
+
+storage {
+    counter: u64 = 0,
+    total: u64 = 0,
+}
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+enum Shape {
+    Circle: u64,
+    Square: u64,
+}
+
+trait Area {
+    fn area(self) -> u64;
+}
+
+impl Point {
+    fn new(x: u64, y: u64) -> Point {
+        Point { x, y }
+    }
+
+    fn sum(self) -> u64 {
+        self.x + self.y
+    }
+
+    fn is_origin(self) -> bool {
+        self.x == 0 && self.y == 0
+    }
+}
+
+impl Area for Shape {
+    fn area(self) -> u64 {
+        match self {
+            Shape::Circle(radius) => radius * radius * 3,
+            Shape::Square(side) => side * side,
+        }
+    }
+}
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        let count = storage.counter.read() + utils::double(LIMIT) + OWNER;
+        storage.counter.write(count);
+        count
+    }
+}
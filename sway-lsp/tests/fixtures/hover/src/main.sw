@@ -0,0 +1,27 @@
+contract;
+
+abi Counter {
+    /// Adds `amount` to the count `times` times.
+    #[storage(read, write)]
+    fn increment(amount: u64, times: u64) -> u64;
+
+    #[payable]
+    fn deposit();
+}
+
+storage {
+    owner: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+    count: u64 = 0,
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment(amount: u64, times: u64) -> u64 {
+        let new_count = storage.count.read() + amount * times;
+        storage.count.write(new_count);
+        new_count
+    }
+
+    #[payable]
+    fn deposit() {}
+}
@@ -0,0 +1,22 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+fn origin() -> Point {
+    Point { x: 0, y: 0 }
+}
+
+fn main() {
+    let sum = add(1, 2);
+    let explicit: u64 = add(sum, 3);
+    let point = origin();
+    let maybe = Some(Some(Some(point)));
+    let flag = true;
+}
@@ -0,0 +1,14 @@
+script;
+
+use lib_a::shapes::{square as make_square, Shape};
+
+fn total<T>(shape: T) -> u64
+where
+    T: Shape,
+{
+    shape.area()
+}
+
+fn main() -> u64 {
+    total(make_square(3))
+}
@@ -0,0 +1,14 @@
+library;
+
+pub mod shapes;
+
+use shapes::{square, Shape};
+
+pub fn unit_area() -> u64 {
+    square(1).area()
+}
+
+#[test]
+fn test_square_area() {
+    assert(square(2).area() == 4);
+}
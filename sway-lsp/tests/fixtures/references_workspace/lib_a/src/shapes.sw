@@ -0,0 +1,19 @@
+library;
+
+pub trait Shape {
+    fn area(self) -> u64;
+}
+
+pub struct Square {
+    side: u64,
+}
+
+impl Shape for Square {
+    fn area(self) -> u64 {
+        self.side * self.side
+    }
+}
+
+pub fn square(side: u64) -> Square {
+    Square { side }
+}
@@ -47,3 +47,38 @@ impl MyContract for Contract {
         Empty{}
     }
 }
+
+struct Account {
+    owner: u64,
+    balance: u64,
+}
+
+storage {
+    account: Account = Account {
+        owner: 0,
+        balance: 0,
+    },
+}
+
+#[storage(read)]
+fn stored_balance() -> u64 {
+    storage.account.balance.read()
+}
+
+fn balance_of(account: Account) -> u64 {
+    let Account { owner: _, balance: amount } = account;
+    match account {
+        Account { owner: _, balance: 0 } => 0,
+        _ => amount,
+    }
+}
+
+trait Shape {
+    fn area(self) -> u32;
+}
+
+impl Shape for Point {
+    fn area(self) -> u32 {
+        self.x * self.y
+    }
+}
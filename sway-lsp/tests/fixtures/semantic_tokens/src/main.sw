@@ -0,0 +1,30 @@
+contract;
+
+configurable {
+    MAX_COUNT: u64 = 10,
+}
+
+storage {
+    count: u64 = 0,
+}
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        let count = storage.count.read() + __size_of::<u64>();
+        if count < MAX_COUNT {
+            storage.count.write(count);
+        }
+        count
+    }
+}
+
+#[test]
+fn test_size() {
+    assert(__size_of::<u64>() == 8);
+}
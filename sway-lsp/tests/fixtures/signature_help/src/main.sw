@@ -0,0 +1,43 @@
+contract;
+
+use std::hash::sha256;
+
+abi Counter {
+    /// Increments the counter `times` times by `amount`.
+    fn increment(amount: u64, times: u64) -> u64;
+}
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+impl Point {
+    /// Returns the point moved by `dx` and `dy`.
+    fn shift(self, dx: u64, dy: u64) -> Point {
+        Point {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+}
+
+/// Adds `a` and `b`.
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+fn identity<T>(value: T) -> T {
+    value
+}
+
+impl Counter for Contract {
+    fn increment(amount: u64, times: u64) -> u64 {
+        let point = Point { x: 0, y: 0 };
+        let moved = point.shift(add(1, 2), 3);
+        let total = identity::<u64>(add(amount, times));
+        let _hash = sha256(moved.x);
+        let _ = abi(Counter, 0x0000000000000000000000000000000000000000000000000000000000000000).increment(1, 2);
+        total
+    }
+}
@@ -507,3 +507,53 @@ pub(crate) async fn code_action_struct_existing_impl_request(
     assert_json_eq!(expected, response.ok().unwrap());
     code_action
 }
+
+/// Requests the code actions for a diagnostic with the given message at `line` and `character`,
+/// and returns the title and text edits of each code action.
+pub(crate) async fn code_action_auto_import_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    line: u32,
+    character: u32,
+    message: &str,
+) -> Vec<(String, Vec<TextEdit>)> {
+    let range = json!({
+        "start": {
+            "line": line,
+            "character": character
+        },
+        "end": {
+            "line": line,
+            "character": character
+        },
+    });
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+        "range": range,
+        "context": {
+            "diagnostics": [{
+                "range": range,
+                "severity": 1,
+                "message": message
+            }],
+            "triggerKind": 1
+        }
+    });
+    let code_action = build_request_with_id("textDocument/codeAction", params, 1);
+    let response = call_request(service, code_action).await.unwrap().unwrap();
+    let actions: Option<CodeActionResponse> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+    actions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) => {
+                let mut changes = action.edit.unwrap().changes.unwrap();
+                (action.title, changes.remove(uri).unwrap())
+            }
+            CodeActionOrCommand::Command(_) => panic!("expected a code action"),
+        })
+        .collect()
+}
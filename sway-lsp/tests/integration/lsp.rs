@@ -31,7 +31,15 @@ pub(crate) async fn call_request(
 }
 
 pub(crate) async fn initialize_request(service: &mut LspService<ServerState>) -> Request {
-    let params = json!({ "capabilities": sway_lsp::server_capabilities() });
+    initialize_request_with_capabilities(service, json!(sway_lsp::server_capabilities())).await
+}
+
+/// Initializes the server for a client with the given capabilities.
+pub(crate) async fn initialize_request_with_capabilities(
+    service: &mut LspService<ServerState>,
+    capabilities: serde_json::Value,
+) -> Request {
+    let params = json!({ "capabilities": capabilities });
     let initialize = build_request_with_id("initialize", params, 1);
     let response = call_request(service, initialize.clone()).await;
     let expected = Response::from_ok(
@@ -117,6 +125,87 @@ pub(crate) async fn did_change_request(
     did_change
 }
 
+pub(crate) async fn did_change_text_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    version: i32,
+    range: Range,
+    text: &str,
+) -> Request {
+    let did_change = did_change_text_notification(uri, version, range, text);
+    let response = call_request(service, did_change.clone()).await;
+    assert_eq!(response, Ok(None));
+    did_change
+}
+
+/// Builds a notification that replaces `range` with `text`, without sending it.
+pub(crate) fn did_change_text_notification(
+    uri: &Url,
+    version: i32,
+    range: Range,
+    text: &str,
+) -> Request {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+            "version": version
+        },
+        "contentChanges": [
+            {
+                "range": range,
+                "text": text,
+            }
+        ]
+    });
+    Request::build("textDocument/didChange")
+        .params(params)
+        .finish()
+}
+
+pub(crate) async fn code_lenses(service: &mut LspService<ServerState>, uri: &Url) -> Vec<CodeLens> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+    });
+    let code_lens = build_request_with_id("textDocument/codeLens", params, 1);
+    let response = call_request(service, code_lens).await.unwrap().unwrap();
+    serde_json::from_value(response.result().unwrap().clone()).unwrap()
+}
+
+pub(crate) async fn document_symbols(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+) -> Vec<DocumentSymbol> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+    });
+    let document_symbol = build_request_with_id("textDocument/documentSymbol", params, 1);
+    let response = call_request(service, document_symbol)
+        .await
+        .unwrap()
+        .unwrap();
+    match serde_json::from_value(response.result().unwrap().clone()).unwrap() {
+        Some(DocumentSymbolResponse::Nested(symbols)) => symbols,
+        response => panic!("expected nested document symbols, got {response:?}"),
+    }
+}
+
+pub(crate) async fn workspace_symbols(
+    service: &mut LspService<ServerState>,
+    query: &str,
+) -> Vec<SymbolInformation> {
+    let params = json!({ "query": query });
+    let workspace_symbol = build_request_with_id("workspace/symbol", params, 1);
+    let response = call_request(service, workspace_symbol)
+        .await
+        .unwrap()
+        .unwrap();
+    serde_json::from_value(response.result().unwrap().clone()).unwrap()
+}
+
 pub(crate) async fn did_close_notification(service: &mut LspService<ServerState>) {
     let exit = Request::build("textDocument/didClose").finish();
     let response = call_request(service, exit.clone()).await;
@@ -166,6 +255,26 @@ pub(crate) async fn semantic_tokens_request(
     semantic_tokens
 }
 
+pub(crate) async fn semantic_tokens(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+) -> Vec<SemanticToken> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+    });
+    let semantic_tokens = build_request_with_id("textDocument/semanticTokens/full", params, 1);
+    let response = call_request(service, semantic_tokens)
+        .await
+        .unwrap()
+        .unwrap();
+    match serde_json::from_value(response.result().unwrap().clone()).unwrap() {
+        Some(SemanticTokensResult::Tokens(tokens)) => tokens.data,
+        response => panic!("expected semantic tokens, got {response:?}"),
+    }
+}
+
 pub(crate) async fn document_symbol_request(
     service: &mut LspService<ServerState>,
     uri: &Url,
@@ -193,6 +302,34 @@ pub(crate) fn definition_request(uri: &Url, token_line: i32, token_char: i32, id
     build_request_with_id("textDocument/definition", params, id)
 }
 
+/// Returns the location of the definition of the token at `line` and `character`.
+pub(crate) async fn definition_location(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    line: i32,
+    character: i32,
+) -> Location {
+    let definition = definition_request(uri, line, character, 1);
+    let response = call_request(service, definition).await.unwrap().unwrap();
+    match serde_json::from_value(response.result().unwrap().clone()).unwrap() {
+        Some(GotoDefinitionResponse::Scalar(location)) => location,
+        response => panic!("expected a single definition, got {response:?}"),
+    }
+}
+
+/// Returns the contents of the read-only document of a dependency file.
+pub(crate) async fn text_document_content_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+) -> Option<String> {
+    let params = json!({ "uri": uri });
+    let content = build_request_with_id("workspace/textDocumentContent", params, 1);
+    let response = call_request(service, content).await.unwrap().unwrap();
+    let result: Option<serde_json::Value> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+    result.map(|result| result["text"].as_str().unwrap().to_string())
+}
+
 pub(crate) async fn format_request(service: &mut LspService<ServerState>, uri: &Url) -> Request {
     let params = json!({
         "textDocument": {
@@ -262,12 +399,36 @@ pub(crate) async fn code_lens_request(service: &mut LspService<ServerState>, uri
     let code_lens = build_request_with_id("textDocument/codeLens", params, 1);
     let response = call_request(service, code_lens.clone()).await;
     let actual_results = extract_result_array(response);
+    let package_path = uri.to_file_path().unwrap();
+    let package_path = package_path.parent().unwrap().parent().unwrap();
     let expected_results = vec![
         json!({
           "command": {
             "arguments": [
               {
-                "name": "test_bar"
+                "packagePath": package_path
+              }
+            ],
+            "command": "sway.runTests",
+            "title": "▶︎ Run All Tests"
+          },
+          "range": {
+            "end": {
+              "character": 7,
+              "line": 0
+            },
+            "start": {
+              "character": 0,
+              "line": 0
+            }
+          }
+        }),
+        json!({
+          "command": {
+            "arguments": [
+              {
+                "name": "test_bar",
+                "packagePath": package_path
               }
             ],
             "command": "sway.runTests",
@@ -288,7 +449,8 @@ pub(crate) async fn code_lens_request(service: &mut LspService<ServerState>, uri
           "command": {
             "arguments": [
               {
-                "name": "test_foo"
+                "name": "test_foo",
+                "packagePath": package_path
               }
             ],
             "command": "sway.runTests",
@@ -495,6 +657,133 @@ pub(crate) async fn hover_request<'a>(
     hover
 }
 
+pub(crate) async fn inlay_hints_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+) -> Vec<InlayHint> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+        "range": {
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": 100, "character": 0 }
+        }
+    });
+    let inlay_hints = build_request_with_id("textDocument/inlayHint", params, 1);
+    let response = call_request(service, inlay_hints.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let value = response.result().unwrap().clone();
+    let hints: Option<Vec<InlayHint>> = serde_json::from_value(value).unwrap();
+    hints.unwrap_or_default()
+}
+
+pub(crate) async fn signature_help_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    line: u32,
+    character: u32,
+) -> Option<SignatureHelp> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+        "position": {
+            "line": line,
+            "character": character,
+        }
+    });
+    let signature_help = build_request_with_id("textDocument/signatureHelp", params, 1);
+    let response = call_request(service, signature_help.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    serde_json::from_value(response.result().unwrap().clone()).unwrap()
+}
+
+pub(crate) async fn references_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    line: u32,
+    character: u32,
+    include_declaration: bool,
+) -> Vec<Location> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+        "position": {
+            "line": line,
+            "character": character
+        },
+        "context": {
+            "includeDeclaration": include_declaration
+        }
+    });
+    let references = build_request_with_id("textDocument/references", params, 1);
+    let response = call_request(service, references.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let value = response.result().unwrap().clone();
+    let locations: Option<Vec<Location>> = serde_json::from_value(value).unwrap();
+    locations.unwrap_or_default()
+}
+
+pub(crate) async fn prepare_call_hierarchy_request(
+    service: &mut LspService<ServerState>,
+    uri: &Url,
+    line: u32,
+    character: u32,
+) -> Vec<CallHierarchyItem> {
+    let params = json!({
+        "textDocument": {
+            "uri": uri,
+        },
+        "position": {
+            "line": line,
+            "character": character
+        }
+    });
+    let prepare = build_request_with_id("textDocument/prepareCallHierarchy", params, 1);
+    let response = call_request(service, prepare).await.unwrap().unwrap();
+    let value = response.result().unwrap().clone();
+    let items: Option<Vec<CallHierarchyItem>> = serde_json::from_value(value).unwrap();
+    items.unwrap_or_default()
+}
+
+pub(crate) async fn incoming_calls_request(
+    service: &mut LspService<ServerState>,
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyIncomingCall> {
+    let params = json!({ "item": item });
+    let incoming_calls = build_request_with_id("callHierarchy/incomingCalls", params, 1);
+    let response = call_request(service, incoming_calls)
+        .await
+        .unwrap()
+        .unwrap();
+    let value = response.result().unwrap().clone();
+    let calls: Option<Vec<CallHierarchyIncomingCall>> = serde_json::from_value(value).unwrap();
+    calls.unwrap_or_default()
+}
+
+pub(crate) async fn outgoing_calls_request(
+    service: &mut LspService<ServerState>,
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyOutgoingCall> {
+    let params = json!({ "item": item });
+    let outgoing_calls = build_request_with_id("callHierarchy/outgoingCalls", params, 1);
+    let response = call_request(service, outgoing_calls)
+        .await
+        .unwrap()
+        .unwrap();
+    let value = response.result().unwrap().clone();
+    let calls: Option<Vec<CallHierarchyOutgoingCall>> = serde_json::from_value(value).unwrap();
+    calls.unwrap_or_default()
+}
+
 pub(crate) async fn prepare_rename_request<'a>(
     service: &mut LspService<ServerState>,
     rename: &'a Rename<'a>,
@@ -523,7 +812,7 @@ pub(crate) async fn rename_request<'a>(
     service: &mut LspService<ServerState>,
     rename: &'a Rename<'a>,
     ids: &mut impl Iterator<Item = i64>,
-) -> WorkspaceEdit {
+) -> Option<WorkspaceEdit> {
     let params = json!({
         "textDocument": {
             "uri": rename.req_uri,
@@ -540,6 +829,5 @@ pub(crate) async fn rename_request<'a>(
         .unwrap()
         .unwrap();
     let value = response.result().unwrap().clone();
-    let worspace_edit: Option<WorkspaceEdit> = serde_json::from_value(value).unwrap();
-    worspace_edit.unwrap()
+    serde_json::from_value(value).unwrap()
 }
@@ -1,13 +1,19 @@
 pub mod integration;
 
 use crate::integration::{code_actions, lsp};
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
 use sway_lsp::server_state::ServerState;
 use sway_lsp_test_utils::{
-    assert_server_requests, dir_contains_forc_manifest, doc_comments_dir, e2e_language_dir,
-    e2e_test_dir, generic_impl_self_dir, get_fixture, load_sway_example, runnables_test_dir,
-    self_impl_reassignment_dir, sway_workspace_dir, test_fixtures_dir,
+    assert_server_requests, collect_server_requests, dir_contains_forc_manifest, doc_comments_dir,
+    e2e_language_dir, e2e_test_dir, generic_impl_self_dir, get_fixture, load_sway_example,
+    runnables_test_dir, self_impl_reassignment_dir, sway_workspace_dir, test_fixtures_dir,
 };
+use tower::{Service, ServiceExt};
 use tower_lsp::{
     jsonrpc::{self, Response},
     lsp_types::*,
@@ -50,6 +56,14 @@ async fn init_and_open(service: &mut LspService<ServerState>, entry_point: PathB
     uri
 }
 
+/// Returns the start positions of the text edits a rename makes in the document at `uri`.
+fn rename_edit_positions(edit: &WorkspaceEdit, uri: &Url) -> Vec<(u32, u32)> {
+    edit.changes.as_ref().unwrap()[uri]
+        .iter()
+        .map(|edit| (edit.range.start.line, edit.range.start.character))
+        .collect()
+}
+
 async fn shutdown_and_exit(service: &mut LspService<ServerState>) {
     let _ = lsp::shutdown_request(service).await;
     lsp::exit_notification(service).await;
@@ -207,6 +221,90 @@ async fn show_ast() {
     shutdown_and_exit(&mut service).await;
 }
 
+#[tokio::test]
+async fn dependency_documents() {
+    let (mut service, _) = LspService::build(ServerState::new)
+        .custom_method(
+            "workspace/textDocumentContent",
+            ServerState::text_document_content,
+        )
+        .finish();
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("dependency_documents/src/main.sw"),
+    )
+    .await;
+
+    // `Option` is declared in std, which is shown as a virtual document named after the package.
+    let definition = lsp::definition_location(&mut service, &uri, 2, 17).await;
+    let std_uri = definition.uri;
+    assert_eq!(std_uri.as_str(), "sway-lib:///std/src/option.sw");
+    assert_eq!(
+        definition.range,
+        Range::new(Position::new(81, 9), Position::new(81, 15))
+    );
+    let text = lsp::text_document_content_request(&mut service, &std_uri)
+        .await
+        .unwrap();
+    assert!(text.contains("pub enum Option<T> {"));
+
+    // Opening the virtual document doesn't add it to the project.
+    lsp::did_open_notification(&mut service, &std_uri, &text).await;
+
+    // Hover, outline and definitions work inside the virtual document.
+    let hover = HoverDocumentation {
+        req_uri: &std_uri,
+        req_line: 81,
+        req_char: 10,
+        documentation: vec!["```sway\npub enum Option\n```"],
+    };
+    let _ = lsp::hover_request(&mut service, &hover, &mut (0..)).await;
+    let symbols = lsp::document_symbols(&mut service, &std_uri).await;
+    assert!(symbols.iter().any(|symbol| symbol.name == "Option"));
+    let definition = lsp::definition_location(&mut service, &std_uri, 81, 10).await;
+    assert_eq!(definition.uri, std_uri);
+
+    // Find references leads back from the virtual document to the user's code.
+    let references = lsp::references_request(&mut service, &std_uri, 81, 10, false).await;
+    assert!(
+        references.contains(&Location::new(
+            uri.clone(),
+            Range::new(Position::new(2, 16), Position::new(2, 22))
+        )),
+        "{references:?}"
+    );
+    assert!(references.iter().all(|location| location.uri != std_uri));
+    shutdown_and_exit(&mut service).await;
+}
+
+#[tokio::test]
+async fn dependency_documents_read_only_copies() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    // The client can't request the contents of virtual documents.
+    let _ = lsp::initialize_request_with_capabilities(&mut service, serde_json::json!({})).await;
+    lsp::initialized_notification(&mut service).await;
+    let (uri, sway_program) =
+        load_sway_example(test_fixtures_dir().join("dependency_documents/src/main.sw"));
+    lsp::did_open_notification(&mut service, &uri, &sway_program).await;
+
+    let definition = lsp::definition_location(&mut service, &uri, 2, 17).await;
+    let copy_uri = definition.uri;
+    assert_eq!(copy_uri.scheme(), "file");
+    assert!(copy_uri.path().ends_with("/std/src/option.sw"));
+    assert!(copy_uri.path().contains("SWAY_LSP_DEPENDENCIES"));
+    let copy_path = copy_uri.to_file_path().unwrap();
+    assert!(fs::metadata(copy_path).unwrap().permissions().readonly());
+
+    let hover = HoverDocumentation {
+        req_uri: &copy_uri,
+        req_line: 81,
+        req_char: 10,
+        documentation: vec!["```sway\npub enum Option\n```"],
+    };
+    let _ = lsp::hover_request(&mut service, &hover, &mut (0..)).await;
+    shutdown_and_exit(&mut service).await;
+}
+
 //------------------- GO TO DEFINITION -------------------//
 
 #[tokio::test]
@@ -256,7 +354,7 @@ async fn go_to_definition_for_fields() {
         def_line: 81,
         def_start_char: 9,
         def_end_char: 15,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // Option
     let _ = lsp::definition_check(&mut service, &opt_go_to, &mut i).await;
@@ -312,7 +410,7 @@ async fn go_to_definition_inside_turbofish() {
         def_line: 81,
         def_start_char: 9,
         def_end_char: 15,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // option.sw
     let _ = lsp::definition_check(&mut service, &opt_go_to, &mut i).await;
@@ -332,7 +430,7 @@ async fn go_to_definition_inside_turbofish() {
         def_line: 61,
         def_start_char: 9,
         def_end_char: 15,
-        def_path: "sway-lib-std/src/result.sw",
+        def_path: "sway-lib:///std/src/result.sw",
     };
     // result.sw
     let _ = lsp::definition_check(&mut service, &res_go_to, &mut i).await;
@@ -390,7 +488,7 @@ async fn go_to_definition_for_matches() {
         def_line: 81,
         def_start_char: 9,
         def_end_char: 15,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // Option
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -408,7 +506,7 @@ async fn go_to_definition_for_matches() {
         def_line: 85,
         def_start_char: 4,
         def_end_char: 8,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // Some
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -423,7 +521,7 @@ async fn go_to_definition_for_matches() {
         def_line: 83,
         def_start_char: 4,
         def_end_char: 8,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // None
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -518,7 +616,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/lib.sw",
+        def_path: "sway-lib:///std/src/lib.sw",
     };
     // std
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -534,7 +632,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // option
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -546,7 +644,7 @@ async fn go_to_definition_for_paths() {
         def_line: 81,
         def_start_char: 9,
         def_end_char: 15,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // Option
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -559,7 +657,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/vm.sw",
+        def_path: "sway-lib:///std/src/vm.sw",
     };
     // vm
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -571,7 +669,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/vm/evm.sw",
+        def_path: "sway-lib:///std/src/vm/evm.sw",
     };
     // evm
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -583,7 +681,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/vm/evm/evm_address.sw",
+        def_path: "sway-lib:///std/src/vm/evm/evm_address.sw",
     };
     // evm_address
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -595,7 +693,7 @@ async fn go_to_definition_for_paths() {
         def_line: 7,
         def_start_char: 11,
         def_end_char: 21,
-        def_path: "sway-lib-std/src/vm/evm/evm_address.sw",
+        def_path: "sway-lib:///std/src/vm/evm/evm_address.sw",
     };
     // EvmAddress
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -738,7 +836,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/assert.sw",
+        def_path: "sway-lib:///std/src/assert.sw",
     };
     // assert
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -750,7 +848,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-core/src/lib.sw",
+        def_path: "sway-lib:///core/src/lib.sw",
     };
     // core
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -762,7 +860,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-core/src/primitives.sw",
+        def_path: "sway-lib:///core/src/primitives.sw",
     };
     // primitives
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -813,7 +911,7 @@ async fn go_to_definition_for_paths() {
         def_line: 0,
         def_start_char: 0,
         def_end_char: 0,
-        def_path: "sway-lib-std/src/constants.sw",
+        def_path: "sway-lib:///std/src/constants.sw",
     };
     // constants
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -827,7 +925,7 @@ async fn go_to_definition_for_paths() {
         def_line: 9,
         def_start_char: 10,
         def_end_char: 19,
-        def_path: "sway-lib-std/src/constants.sw",
+        def_path: "sway-lib:///std/src/constants.sw",
     };
     // ZERO_B256
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -840,7 +938,7 @@ async fn go_to_definition_for_paths() {
         def_line: 2,
         def_start_char: 5,
         def_end_char: 8,
-        def_path: "sway-lib-core/src/primitives.sw",
+        def_path: "sway-lib:///core/src/primitives.sw",
     };
     // u64
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -852,7 +950,7 @@ async fn go_to_definition_for_paths() {
         def_line: 74,
         def_start_char: 5,
         def_end_char: 9,
-        def_path: "sway-lib-core/src/primitives.sw",
+        def_path: "sway-lib:///core/src/primitives.sw",
     };
     // b256
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -983,12 +1081,12 @@ async fn go_to_definition_for_variables() {
     go_to.def_line = 61;
     go_to.def_start_char = 9;
     go_to.def_end_char = 15;
-    go_to.def_path = "sway-lib-std/src/result.sw";
+    go_to.def_path = "sway-lib:///std/src/result.sw";
     definition_check_with_req_offset(&mut service, &mut go_to, 56, 22, &mut i).await;
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 31, &mut i).await;
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 60, &mut i).await;
     go_to.def_line = 81;
-    go_to.def_path = "sway-lib-std/src/option.sw";
+    go_to.def_path = "sway-lib:///std/src/option.sw";
     definition_check_with_req_offset(&mut service, &mut go_to, 56, 28, &mut i).await;
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 39, &mut i).await;
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 68, &mut i).await;
@@ -1023,7 +1121,7 @@ async fn go_to_definition_for_consts() {
         def_line: 17,
         def_start_char: 5,
         def_end_char: 9,
-        def_path: "sway-lib-std/src/contract_id.sw",
+        def_path: "sway-lib:///std/src/contract_id.sw",
     };
     let _ = lsp::definition_check(&mut service, &contract_go_to, &mut i).await;
 
@@ -1075,7 +1173,7 @@ async fn go_to_definition_for_consts() {
     go_to.def_line = 81;
     go_to.def_start_char = 9;
     go_to.def_end_char = 15;
-    go_to.def_path = "sway-lib-std/src/option.sw";
+    go_to.def_path = "sway-lib:///std/src/option.sw";
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 17, &mut i).await;
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 24, &mut i).await;
     definition_check_with_req_offset(&mut service, &mut go_to, 11, 38, &mut i).await;
@@ -1168,7 +1266,7 @@ async fn go_to_definition_for_structs() {
         def_line: 81,
         def_start_char: 9,
         def_end_char: 15,
-        def_path: "sway-lib-std/src/option.sw",
+        def_path: "sway-lib:///std/src/option.sw",
     };
     // Type Params
     let _ = lsp::definition_check(&mut service, &go_to, &mut i).await;
@@ -1486,6 +1584,48 @@ async fn hover_docs_for_abis() {
     let _ = lsp::hover_request(&mut service, &hover, &mut i).await;
 }
 
+#[tokio::test]
+async fn hover_docs_for_abi_selectors_and_storage_slots() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let uri = init_and_open(&mut service, test_fixtures_dir().join("hover/src/main.sw")).await;
+    let selector =
+        "Selector: `0xdae61e49` (word `0x00000000dae61e49`)\n\nABI signature: `increment(u64,u64)`";
+
+    let mut i = 0..;
+    let mut hover = HoverDocumentation {
+        req_uri: &uri,
+        req_line: 5,
+        req_char: 7,
+        documentation: vec![
+            "```sway\n#[storage(read, write)]\nfn increment(amount: u64, times: u64) -> u64\n```",
+            selector,
+            " Adds `amount` to the count `times` times.",
+        ],
+    };
+    let _ = lsp::hover_request(&mut service, &hover, &mut i).await;
+    hover.req_line = 18;
+    hover.documentation = vec![
+        "```sway\n#[storage(read, write)]\nfn increment(amount: u64, times: u64) -> u64\n```",
+        selector,
+    ];
+    let _ = lsp::hover_request(&mut service, &hover, &mut i).await;
+    hover.req_line = 8;
+    hover.documentation = vec![
+        "```sway\n#[payable]\nfn deposit()\n```",
+        "ABI signature: `deposit()`",
+    ];
+    let _ = lsp::hover_request(&mut service, &hover, &mut i).await;
+
+    let storage_slot = "```sway\ncount: u64\n```\n---\nStorage slot key: `0xde9090cb50e71c2588c773487d1da7066d0c719849a7e58dc8b6397a25c567c0`\n\nStorage field index: `1`";
+    hover.req_line = 13;
+    hover.req_char = 4;
+    hover.documentation = vec![storage_slot];
+    let _ = lsp::hover_request(&mut service, &hover, &mut i).await;
+    hover.req_line = 19;
+    hover.req_char = 32;
+    let _ = lsp::hover_request(&mut service, &hover, &mut i).await;
+}
+
 #[tokio::test]
 async fn hover_docs_for_variables() {
     let (mut service, _) = LspService::new(ServerState::new);
@@ -1627,25 +1767,146 @@ async fn rename() {
     let _ = lsp::prepare_rename_request(&mut service, &rename, &mut i).await;
     let _ = lsp::rename_request(&mut service, &rename, &mut i).await;
 
-    // Function method in ABI declaration
+    // Fail to rename a method in an ABI declaration, as that would change its selector
     let rename = Rename {
         req_uri: &uri,
         req_line: 41,
         req_char: 16,
         new_name: "name_func_name", // from test_function
     };
-    let _ = lsp::prepare_rename_request(&mut service, &rename, &mut i).await;
-    let _ = lsp::rename_request(&mut service, &rename, &mut i).await;
+    assert_eq!(
+        lsp::rename_request(&mut service, &rename, &mut i).await,
+        None
+    );
 
-    // Function method in ABI implementation
+    // Fail to rename a method in an ABI implementation, as that would change its selector
     let rename = Rename {
         req_uri: &uri,
         req_line: 45,
         req_char: 16,
         new_name: "name_func_name", // from test_function
     };
-    let _ = lsp::prepare_rename_request(&mut service, &rename, &mut i).await;
-    let _ = lsp::rename_request(&mut service, &rename, &mut i).await;
+    assert_eq!(
+        lsp::rename_request(&mut service, &rename, &mut i).await,
+        None
+    );
+
+    // Struct field used in storage access and in pattern positions
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 52,
+        req_char: 4,
+        new_name: "funds", // from balance
+    };
+    let edit = lsp::rename_request(&mut service, &rename, &mut i)
+        .await
+        .unwrap();
+    assert_eq!(
+        rename_edit_positions(&edit, &uri),
+        vec![(70, 28), (68, 28), (64, 20), (58, 8), (52, 4)]
+    );
+
+    // Storage field
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 64,
+        req_char: 12,
+        new_name: "wallet", // from account
+    };
+    let edit = lsp::rename_request(&mut service, &rename, &mut i)
+        .await
+        .unwrap();
+    assert_eq!(rename_edit_positions(&edit, &uri), vec![(64, 12), (56, 4)]);
+
+    // Trait method is renamed in the trait declaration and its implementations
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 80,
+        req_char: 7,
+        new_name: "surface", // from area
+    };
+    let edit = lsp::rename_request(&mut service, &rename, &mut i)
+        .await
+        .unwrap();
+    assert_eq!(rename_edit_positions(&edit, &uri), vec![(80, 7), (76, 7)]);
+
+    // Fail to rename a struct field to the name of another field of the struct
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 52,
+        req_char: 4,
+        new_name: "owner", // from balance
+    };
+    assert_eq!(
+        lsp::rename_request(&mut service, &rename, &mut i).await,
+        None
+    );
+
+    // Fail to rename a function parameter to the name of another parameter
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 16,
+        req_char: 7,
+        new_name: "y", // from x
+    };
+    assert_eq!(
+        lsp::rename_request(&mut service, &rename, &mut i).await,
+        None
+    );
+
+    // Fail to rename to a name that is not an identifier
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 16,
+        req_char: 7,
+        new_name: "not an ident", // from x
+    };
+    assert_eq!(
+        lsp::rename_request(&mut service, &rename, &mut i).await,
+        None
+    );
+
+    // Module declared in the workspace
+    let rename = Rename {
+        req_uri: &uri,
+        req_line: 2,
+        req_char: 5,
+        new_name: "renamed_mod", // from test_mod
+    };
+    let edit = lsp::rename_request(&mut service, &rename, &mut i)
+        .await
+        .unwrap();
+    let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+        panic!("expected document change operations");
+    };
+    let Some(DocumentChangeOperation::Op(ResourceOp::Rename(rename_file))) = operations.last()
+    else {
+        panic!("expected the module file to be renamed");
+    };
+    assert!(rename_file
+        .old_uri
+        .path()
+        .ends_with("renaming/src/test_mod.sw"));
+    assert!(rename_file
+        .new_uri
+        .path()
+        .ends_with("renaming/src/renamed_mod.sw"));
+    let DocumentChangeOperation::Edit(edit) = &operations[0] else {
+        panic!("expected text edits before the file rename");
+    };
+    assert_eq!(edit.text_document.uri, uri);
+    let positions: Vec<_> = edit
+        .edits
+        .iter()
+        .map(|edit| match edit {
+            OneOf::Left(edit) => (edit.range.start.line, edit.range.start.character),
+            OneOf::Right(edit) => (
+                edit.text_edit.range.start.line,
+                edit.text_edit.range.start.character,
+            ),
+        })
+        .collect();
+    assert_eq!(positions, vec![(33, 12), (3, 4), (2, 4)]);
 
     // Fail to rename keyword
     let rename = Rename {
@@ -1684,6 +1945,706 @@ async fn rename() {
     );
 }
 
+#[tokio::test]
+async fn inlay_hints() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("inlay_hints/src/main.sw"),
+    )
+    .await;
+    let hints = lsp::inlay_hints_request(&mut service, &uri).await;
+    let hints: Vec<_> = hints
+        .iter()
+        .map(|hint| {
+            let label = match &hint.label {
+                InlayHintLabel::String(label) => label.as_str(),
+                InlayHintLabel::LabelParts(_) => panic!("expected a string label"),
+            };
+            (
+                hint.position.line,
+                hint.position.character,
+                label,
+                hint.kind.unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        hints,
+        vec![
+            (16, 11, ": u64", InlayHintKind::TYPE),
+            (16, 18, "a:", InlayHintKind::PARAMETER),
+            (16, 21, "b:", InlayHintKind::PARAMETER),
+            (17, 33, "b:", InlayHintKind::PARAMETER),
+            (18, 13, ": Point", InlayHintKind::TYPE),
+            (19, 13, ": Option<…>", InlayHintKind::TYPE),
+            (20, 12, ": bool", InlayHintKind::TYPE),
+        ]
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
+#[tokio::test]
+async fn semantic_tokens_for_contract_items() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("semantic_tokens/src/main.sw"),
+    )
+    .await;
+    let legend = match sway_lsp::server_capabilities().semantic_tokens_provider {
+        Some(SemanticTokensServerCapabilities::SemanticTokensOptions(options)) => options.legend,
+        provider => panic!("expected semantic tokens options, got {provider:?}"),
+    };
+    // The tokens are delta-encoded, relative to the start of the previous token.
+    let mut line = 0;
+    let mut character = 0;
+    let mut tokens = vec![];
+    for token in lsp::semantic_tokens(&mut service, &uri).await {
+        if token.delta_line == 0 {
+            character += token.delta_start;
+        } else {
+            line += token.delta_line;
+            character = token.delta_start;
+        }
+        let modifiers: Vec<_> = legend
+            .token_modifiers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| token.token_modifiers_bitset & (1 << index) != 0)
+            .map(|(_, modifier)| modifier.as_str())
+            .collect();
+        tokens.push((
+            (line, character),
+            token.length,
+            legend.token_types[token.token_type as usize].as_str(),
+            modifiers,
+        ));
+    }
+    let token_at = |position: (u32, u32)| {
+        tokens
+            .iter()
+            .find(|(token_position, ..)| *token_position == position)
+            .map(|(_, length, token_type, modifiers)| (*length, *token_type, modifiers.clone()))
+            .unwrap_or_else(|| panic!("no semantic token at {position:?}"))
+    };
+
+    assert_eq!(token_at((3, 4)), (9, "variable", vec!["configurable"]));
+    assert_eq!(token_at((7, 4)), (5, "property", vec!["storage"]));
+    assert_eq!(token_at((12, 7)), (9, "function", vec!["abi"]));
+    assert_eq!(token_at((17, 7)), (9, "function", vec!["abi"]));
+    assert_eq!(token_at((18, 28)), (5, "property", vec!["storage"]));
+    assert_eq!(token_at((18, 43)), (9, "function", vec!["intrinsic"]));
+    assert_eq!(token_at((19, 19)), (9, "variable", vec!["configurable"]));
+    assert_eq!(token_at((20, 20)), (5, "property", vec!["storage"]));
+    assert_eq!(token_at((27, 3)), (9, "function", vec!["test"]));
+    assert_eq!(token_at((28, 11)), (9, "function", vec!["intrinsic"]));
+    // Locals have no modifiers.
+    assert_eq!(token_at((18, 12)), (5, "variable", vec![]));
+    shutdown_and_exit(&mut service).await;
+}
+
+/// Renders a symbol tree as one line per symbol, with the kind, the name and the line and
+/// character of the selection range, indented by depth.
+fn symbol_tree_lines(symbols: &[DocumentSymbol], depth: usize, lines: &mut Vec<String>) {
+    for symbol in symbols {
+        lines.push(format!(
+            "{}{:?} {} {}:{}",
+            "  ".repeat(depth),
+            symbol.kind,
+            symbol.name,
+            symbol.selection_range.start.line,
+            symbol.selection_range.start.character,
+        ));
+        symbol_tree_lines(
+            symbol.children.as_deref().unwrap_or_default(),
+            depth + 1,
+            lines,
+        );
+    }
+}
+
+#[tokio::test]
+async fn document_symbol_hierarchy() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("document_symbols/src/main.sw"),
+    )
+    .await;
+    let symbols = lsp::document_symbols(&mut service, &uri).await;
+    let mut lines = vec![];
+    symbol_tree_lines(&symbols, 0, &mut lines);
+    assert_eq!(
+        lines,
+        vec![
+            "Module utils 2:4",
+            "Constant MAX 4:6",
+            "Struct configurable 7:4",
+            "  Constant OWNER 7:4",
+            "  Constant LIMIT 8:4",
+            "Struct storage 11:0",
+            "  Field counter 12:4",
+            "  Field total 13:4",
+            "Struct Point 16:7",
+            "  Field x 17:4",
+            "  Field y 18:4",
+            "Enum Shape 21:5",
+            "  EnumMember Circle 22:4",
+            "  EnumMember Square 23:4",
+            "Interface Area 26:6",
+            "  Method area 27:7",
+            "Object impl Point 30:5",
+            "  Method new 31:7",
+            "  Method sum 35:7",
+            "  Method is_origin 39:7",
+            "Object impl Area for Shape 44:5",
+            "  Method area 45:7",
+            "Interface Counter 53:4",
+            "  Method increment 55:7",
+            "Object impl Counter for Contract 58:5",
+            "  Method increment 60:7",
+        ]
+    );
+
+    // The methods of an impl block are nested within its range, in order.
+    let impl_point = symbols
+        .iter()
+        .find(|symbol| symbol.name == "impl Point")
+        .unwrap();
+    assert_eq!(impl_point.range.start, Position::new(30, 0));
+    assert_eq!(impl_point.range.end, Position::new(42, 1));
+    let methods = impl_point.children.as_ref().unwrap();
+    let method_ranges: Vec<_> = methods
+        .iter()
+        .map(|method| (method.range.start.line, method.range.end.line))
+        .collect();
+    assert_eq!(method_ranges, vec![(31, 33), (35, 37), (39, 41)]);
+    shutdown_and_exit(&mut service).await;
+}
+
+/// Returns the name, the path relative to `workspace_dir` and the line of the workspace symbols
+/// matching `query`.
+async fn workspace_symbol_locations(
+    service: &mut LspService<ServerState>,
+    workspace_dir: &Path,
+    query: &str,
+) -> Vec<(String, PathBuf, u32)> {
+    lsp::workspace_symbols(service, query)
+        .await
+        .into_iter()
+        .map(|symbol| {
+            let path = symbol.location.uri.to_file_path().unwrap();
+            let file = path.strip_prefix(workspace_dir).unwrap().to_path_buf();
+            (symbol.name, file, symbol.location.range.start.line)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn workspace_symbol_search() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let workspace_dir = test_fixtures_dir().join("references_workspace");
+    init_and_open(&mut service, workspace_dir.join("app_b/src/main.sw")).await;
+    let shapes = PathBuf::from("lib_a/src/shapes.sw");
+    let lib = PathBuf::from("lib_a/src/lib.sw");
+    let main = PathBuf::from("app_b/src/main.sw");
+    // Members that aren't open in the editor are searched too, best matches first.
+    assert_eq!(
+        workspace_symbol_locations(&mut service, &workspace_dir, "area").await,
+        vec![
+            ("area".to_string(), shapes.clone(), 3),
+            ("area".to_string(), shapes.clone(), 11),
+            ("unit_area".to_string(), lib.clone(), 6),
+            ("test_square_area".to_string(), lib.clone(), 11),
+        ]
+    );
+    assert_eq!(
+        workspace_symbol_locations(&mut service, &workspace_dir, "sq").await,
+        vec![
+            ("Square".to_string(), shapes.clone(), 6),
+            ("square".to_string(), shapes.clone(), 16),
+            ("test_square_area".to_string(), lib.clone(), 11),
+        ]
+    );
+    assert_eq!(
+        workspace_symbol_locations(&mut service, &workspace_dir, "ttl").await,
+        vec![("total".to_string(), main, 4)]
+    );
+    assert!(
+        workspace_symbol_locations(&mut service, &workspace_dir, "xyz")
+            .await
+            .is_empty()
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
+/// Returns the title, range and arguments of each code lens, ordered by range.
+fn code_lens_summaries(lenses: Vec<CodeLens>) -> Vec<(String, Range, serde_json::Value)> {
+    let mut summaries: Vec<_> = lenses
+        .into_iter()
+        .map(|lens| {
+            let command = lens.command.unwrap();
+            let arguments = command.arguments.unwrap_or_default();
+            (
+                command.title,
+                lens.range,
+                serde_json::Value::Array(arguments),
+            )
+        })
+        .collect();
+    summaries.sort_by_key(|(_, range, _)| range.start);
+    summaries
+}
+
+#[tokio::test]
+async fn code_lens_tests_of_workspace_member() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let workspace_dir = test_fixtures_dir().join("references_workspace");
+    let lib_dir = workspace_dir.join("lib_a");
+    let lib_uri = init_and_open(&mut service, lib_dir.join("src/lib.sw")).await;
+    let run_all = |line| {
+        (
+            "▶\u{fe0e} Run All Tests".to_string(),
+            Range::new(Position::new(line, 0), Position::new(line, 8)),
+            serde_json::json!([{ "packagePath": lib_dir }]),
+        )
+    };
+    let run_test = |name: &str, line| {
+        (
+            "▶\u{fe0e} Run Test".to_string(),
+            Range::new(Position::new(line, 0), Position::new(line, 7)),
+            serde_json::json!([{ "name": name, "packagePath": lib_dir }]),
+        )
+    };
+    assert_eq!(
+        code_lens_summaries(lsp::code_lenses(&mut service, &lib_uri).await),
+        vec![run_all(0), run_test("test_square_area", 10)]
+    );
+
+    // Files and members without tests get no test lenses.
+    let shapes_uri = Url::from_file_path(lib_dir.join("src/shapes.sw")).unwrap();
+    assert!(lsp::code_lenses(&mut service, &shapes_uri).await.is_empty());
+    let (app_uri, app_text) = load_sway_example(workspace_dir.join("app_b/src/main.sw"));
+    lsp::did_open_notification(&mut service, &app_uri, &app_text).await;
+    let app_lenses = lsp::code_lenses(&mut service, &app_uri).await;
+    assert!(app_lenses
+        .iter()
+        .all(|lens| lens.command.as_ref().unwrap().command != "sway.runTests"));
+
+    // Lenses follow tests being added and removed.
+    let end_of_file = Range::new(Position::new(14, 0), Position::new(14, 0));
+    let new_test = "\n#[test]\nfn test_unit_area() {\n    assert(unit_area() == 1);\n}\n";
+    lsp::did_change_text_request(&mut service, &lib_uri, 2, end_of_file, new_test).await;
+    assert_eq!(
+        code_lens_summaries(lsp::code_lenses(&mut service, &lib_uri).await),
+        vec![
+            run_all(0),
+            run_test("test_square_area", 10),
+            run_test("test_unit_area", 15),
+        ]
+    );
+    let new_test_range = Range::new(Position::new(14, 0), Position::new(19, 0));
+    lsp::did_change_text_request(&mut service, &lib_uri, 3, new_test_range, "").await;
+    assert_eq!(
+        code_lens_summaries(lsp::code_lenses(&mut service, &lib_uri).await),
+        vec![run_all(0), run_test("test_square_area", 10)]
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
+#[tokio::test]
+async fn signature_help() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("signature_help/src/main.sw"),
+    )
+    .await;
+    let mut signatures = vec![];
+    for (line, character) in [(36, 39), (36, 43), (37, 36), (38, 27), (39, 110)] {
+        let help = lsp::signature_help_request(&mut service, &uri, line, character)
+            .await
+            .unwrap();
+        let signature = &help.signatures[0];
+        let active_parameter = help.active_parameter.unwrap() as usize;
+        let ParameterLabel::LabelOffsets([start, end]) =
+            signature.parameters.as_ref().unwrap()[active_parameter].label
+        else {
+            panic!("expected parameter label offsets");
+        };
+        let documentation = signature.documentation.as_ref().map(|doc| match doc {
+            Documentation::MarkupContent(markup) => markup.value.clone(),
+            Documentation::String(value) => value.clone(),
+        });
+        signatures.push((
+            signature.label.clone(),
+            signature.label[start as usize..end as usize].to_string(),
+            documentation,
+        ));
+    }
+    assert_eq!(
+        signatures,
+        vec![
+            // The active parameter belongs to the inner call in `point.shift(add(1, 2), 3)`.
+            (
+                "fn add(a: u64, b: u64) -> u64".to_string(),
+                "b: u64".to_string(),
+                Some("Adds `a` and `b`.".to_string()),
+            ),
+            (
+                "fn shift(self, dx: u64, dy: u64) -> Point".to_string(),
+                "dy: u64".to_string(),
+                Some("Returns the point moved by `dx` and `dy`.".to_string()),
+            ),
+            (
+                "fn identity<u64>(value: u64) -> u64".to_string(),
+                "value: u64".to_string(),
+                None,
+            ),
+            (
+                "fn sha256<T>(param: T) -> b256".to_string(),
+                "param: T".to_string(),
+                Some("Returns the `SHA-2-256` hash of `param`.".to_string()),
+            ),
+            (
+                "fn increment(amount: u64, times: u64) -> u64".to_string(),
+                "times: u64".to_string(),
+                Some("Increments the counter `times` times by `amount`.".to_string()),
+            ),
+        ]
+    );
+    assert!(lsp::signature_help_request(&mut service, &uri, 40, 13)
+        .await
+        .is_none());
+    shutdown_and_exit(&mut service).await;
+}
+
+#[tokio::test]
+async fn code_action_auto_import() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("auto_import/src/main.sw"),
+    )
+    .await;
+    let insert = |line, character, new_text: &str| TextEdit {
+        range: Range::new(
+            Position::new(line, character),
+            Position::new(line, character),
+        ),
+        new_text: new_text.to_string(),
+    };
+
+    // Added to an existing group of imports from the same module, in order
+    let actions = code_actions::code_action_auto_import_request(
+        &mut service,
+        &uri,
+        15,
+        16,
+        "Could not find symbol \"balance_of\" in this scope.",
+    )
+    .await;
+    assert_eq!(
+        actions,
+        vec![(
+            "Import `std::context::balance_of`".to_string(),
+            vec![insert(4, 19, "balance_of, ")]
+        )]
+    );
+
+    // Merged with a single import from the same module into a group
+    let actions = code_actions::code_action_auto_import_request(
+        &mut service,
+        &uri,
+        17,
+        18,
+        "Could not find symbol \"sha256\" in this scope.",
+    )
+    .await;
+    assert_eq!(
+        actions,
+        vec![(
+            "Import `std::hash::sha256`".to_string(),
+            vec![TextEdit {
+                range: Range::new(Position::new(5, 15), Position::new(5, 24)),
+                new_text: "{keccak256, sha256}".to_string(),
+            }]
+        )]
+    );
+
+    // Inserted before the first import that sorts after it
+    let actions = code_actions::code_action_auto_import_request(
+        &mut service,
+        &uri,
+        17,
+        25,
+        "Variable \"ZERO_B256\" does not exist in this scope.",
+    )
+    .await;
+    assert_eq!(
+        actions,
+        vec![(
+            "Import `std::constants::ZERO_B256`".to_string(),
+            vec![insert(4, 0, "use std::constants::ZERO_B256;\n")]
+        )]
+    );
+
+    // One code action for each item with the name
+    let actions = code_actions::code_action_auto_import_request(
+        &mut service,
+        &uri,
+        16,
+        16,
+        "Unknown type name \"Bytes\".",
+    )
+    .await;
+    assert_eq!(
+        actions,
+        vec![
+            (
+                "Import `std::bytes::Bytes`".to_string(),
+                vec![insert(4, 0, "use std::bytes::Bytes;\n")]
+            ),
+            (
+                "Import `utils::Bytes`".to_string(),
+                vec![insert(6, 0, "use utils::Bytes;\n")]
+            ),
+        ]
+    );
+
+    // Inserted after the module kind and its doc comments if there are no imports yet
+    let (utils_uri, utils_program) =
+        load_sway_example(test_fixtures_dir().join("auto_import/src/utils.sw"));
+    lsp::did_open_notification(&mut service, &utils_uri, &utils_program).await;
+    let actions = code_actions::code_action_auto_import_request(
+        &mut service,
+        &utils_uri,
+        9,
+        4,
+        "Could not find symbol \"sha256\" in this scope.",
+    )
+    .await;
+    assert_eq!(
+        actions,
+        vec![(
+            "Import `std::hash::sha256`".to_string(),
+            vec![insert(2, 0, "\nuse std::hash::sha256;\n")]
+        )]
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
+/// Returns the file name and start position of each reference location.
+fn reference_positions(locations: &[Location]) -> Vec<(String, u32, u32)> {
+    locations
+        .iter()
+        .map(|location| {
+            let path = location.uri.to_file_path().unwrap();
+            let member = path
+                .parent()
+                .unwrap()
+                .parent()
+                .unwrap()
+                .file_name()
+                .unwrap();
+            (
+                format!(
+                    "{}/{}",
+                    member.to_str().unwrap(),
+                    path.file_name().unwrap().to_str().unwrap()
+                ),
+                location.range.start.line,
+                location.range.start.character,
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn references_across_workspace() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let workspace_dir = test_fixtures_dir().join("references_workspace");
+    let uri = init_and_open(&mut service, workspace_dir.join("app_b/src/main.sw")).await;
+
+    // Function used through an import alias, in another module and in a test
+    let references = lsp::references_request(&mut service, &uri, 12, 10, true).await;
+    assert_eq!(
+        reference_positions(&references),
+        vec![
+            ("app_b/main.sw".to_string(), 2, 20),
+            ("app_b/main.sw".to_string(), 2, 30),
+            ("app_b/main.sw".to_string(), 12, 10),
+            ("lib_a/lib.sw".to_string(), 4, 13),
+            ("lib_a/lib.sw".to_string(), 7, 4),
+            ("lib_a/lib.sw".to_string(), 12, 11),
+            ("lib_a/shapes.sw".to_string(), 16, 7),
+        ]
+    );
+
+    // Trait method called on a generic receiver
+    let references = lsp::references_request(&mut service, &uri, 8, 10, true).await;
+    assert_eq!(
+        reference_positions(&references),
+        vec![
+            ("app_b/main.sw".to_string(), 8, 10),
+            ("lib_a/lib.sw".to_string(), 7, 14),
+            ("lib_a/lib.sw".to_string(), 12, 21),
+            ("lib_a/shapes.sw".to_string(), 3, 7),
+            ("lib_a/shapes.sw".to_string(), 11, 7),
+        ]
+    );
+    let references = lsp::references_request(&mut service, &uri, 8, 10, false).await;
+    assert_eq!(
+        reference_positions(&references),
+        vec![
+            ("app_b/main.sw".to_string(), 8, 10),
+            ("lib_a/lib.sw".to_string(), 7, 14),
+            ("lib_a/lib.sw".to_string(), 12, 21),
+        ]
+    );
+
+    // Requests from the library also find the uses in the members depending on it
+    let (lib_uri, lib_program) = load_sway_example(workspace_dir.join("lib_a/src/shapes.sw"));
+    lsp::did_open_notification(&mut service, &lib_uri, &lib_program).await;
+    let references = lsp::references_request(&mut service, &lib_uri, 3, 7, false).await;
+    assert_eq!(
+        reference_positions(&references),
+        vec![
+            ("app_b/main.sw".to_string(), 8, 10),
+            ("lib_a/lib.sw".to_string(), 7, 14),
+            ("lib_a/lib.sw".to_string(), 12, 21),
+        ]
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
+/// Renders the file of a call hierarchy item as the name of its package and of the file.
+fn item_file(item: &CallHierarchyItem) -> String {
+    reference_positions(&[Location::new(item.uri.clone(), item.range)])
+        .remove(0)
+        .0
+}
+
+/// Renders the ranges of calls as their start and end lines and characters.
+fn call_ranges(ranges: &[Range]) -> Vec<(u32, u32, u32, u32)> {
+    ranges
+        .iter()
+        .map(|range| {
+            (
+                range.start.line,
+                range.start.character,
+                range.end.line,
+                range.end.character,
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn call_hierarchy() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let workspace_dir = test_fixtures_dir().join("call_hierarchy_workspace");
+    let uri = init_and_open(&mut service, workspace_dir.join("counter/src/main.sw")).await;
+
+    // Prepared from a call, the item is the declaration of the called function.
+    let items = lsp::prepare_call_hierarchy_request(&mut service, &uri, 22, 9).await;
+    assert_eq!(items.len(), 1);
+    let bump = items[0].clone();
+    assert_eq!(bump.name, "bump");
+    assert_eq!(bump.kind, SymbolKind::FUNCTION);
+    assert_eq!(bump.selection_range.start, Position::new(13, 3));
+
+    // `bump` is called by the ABI method, which is called from the script through contract calls.
+    let incoming = lsp::incoming_calls_request(&mut service, &bump).await;
+    let callers: Vec<_> = incoming
+        .iter()
+        .map(|call| {
+            (
+                item_file(&call.from),
+                call.from.name.as_str(),
+                call_ranges(&call.from_ranges),
+            )
+        })
+        .collect();
+    assert_eq!(
+        callers,
+        vec![(
+            "counter/main.sw".to_string(),
+            "increment",
+            vec![(22, 8, 22, 14)]
+        )]
+    );
+    let increment = incoming[0].from.clone();
+    assert_eq!(increment.kind, SymbolKind::METHOD);
+
+    let incoming = lsp::incoming_calls_request(&mut service, &increment).await;
+    let callers: Vec<_> = incoming
+        .iter()
+        .map(|call| {
+            (
+                item_file(&call.from),
+                call.from.name.as_str(),
+                call_ranges(&call.from_ranges),
+            )
+        })
+        .collect();
+    assert_eq!(
+        callers,
+        vec![(
+            "caller/main.sw".to_string(),
+            "call_counter",
+            vec![(6, 4, 6, 23), (6, 26, 6, 45)]
+        )]
+    );
+    let call_counter = incoming[0].from.clone();
+
+    // Intrinsics and contract calls are told apart by the details of the called items.
+    let outgoing = lsp::outgoing_calls_request(&mut service, &bump).await;
+    let callees: Vec<_> = outgoing
+        .iter()
+        .map(|call| {
+            (
+                call.to.name.as_str(),
+                call.to.detail.as_deref(),
+                call_ranges(&call.from_ranges),
+            )
+        })
+        .collect();
+    assert_eq!(
+        callees,
+        vec![
+            ("add_one", None, vec![(14, 16, 14, 45)]),
+            ("read", None, vec![(14, 24, 14, 44)]),
+            ("__size_of", Some("intrinsic"), vec![(14, 48, 14, 66)]),
+            ("write", None, vec![(15, 4, 15, 30)]),
+        ]
+    );
+
+    let outgoing = lsp::outgoing_calls_request(&mut service, &call_counter).await;
+    let callees: Vec<_> = outgoing
+        .iter()
+        .map(|call| {
+            (
+                item_file(&call.to),
+                call.to.name.as_str(),
+                call.to.detail.as_deref(),
+                call_ranges(&call.from_ranges),
+            )
+        })
+        .collect();
+    assert_eq!(
+        callees,
+        vec![(
+            "counter_abi/lib.sw".to_string(),
+            "increment",
+            Some("contract call"),
+            vec![(6, 4, 6, 23), (6, 26, 6, 45)]
+        )]
+    );
+    shutdown_and_exit(&mut service).await;
+}
+
 #[tokio::test]
 async fn publish_diagnostics_dead_code_warning() {
     let (mut service, socket) = LspService::new(ServerState::new);
@@ -1701,6 +2662,94 @@ async fn publish_diagnostics_dead_code_warning() {
     shutdown_and_exit(&mut service).await;
 }
 
+#[tokio::test]
+async fn publish_diagnostics_only_for_latest_change() {
+    let (mut service, socket) = LspService::new(ServerState::new);
+    let published = collect_server_requests(socket, "textDocument/publishDiagnostics");
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("diagnostics/dead_code/src/main.sw"),
+    )
+    .await;
+    wait_for_requests(&published, 1).await;
+
+    // The first change adds a function with a type error, and the second one removes it again
+    // before the first one is compiled.
+    let end_of_file = Range::new(Position::new(50, 0), Position::new(50, 0));
+    let broken_fn = "fn broken() -> u64 {\n    true\n}\n";
+    let broken_fn_range = Range::new(Position::new(50, 0), Position::new(53, 0));
+    let add_broken_fn = lsp::did_change_text_notification(&uri, 2, end_of_file, broken_fn);
+    let remove_broken_fn = lsp::did_change_text_notification(&uri, 3, broken_fn_range, "");
+    let first_change = tokio::spawn(service.ready().await.unwrap().call(add_broken_fn));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let second_change = service.ready().await.unwrap().call(remove_broken_fn);
+    assert_eq!(second_change.await, Ok(None));
+    assert_eq!(first_change.await.unwrap(), Ok(None));
+    wait_for_requests(&published, 2).await;
+
+    // Only the diagnostics of the latest text are published, which has no errors.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let published = published.lock().unwrap().clone();
+    assert_eq!(published.len(), 2);
+    let diagnostics: PublishDiagnosticsParams =
+        serde_json::from_value(published[1].clone()).unwrap();
+    assert!(!diagnostics.diagnostics.is_empty());
+    assert!(diagnostics
+        .diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity != Some(DiagnosticSeverity::ERROR)));
+    shutdown_and_exit(&mut service).await;
+}
+
+#[tokio::test]
+async fn publish_diagnostics_of_dependency_to_its_own_document() {
+    let (mut service, socket) = LspService::new(ServerState::new);
+    let published = collect_server_requests(socket, "textDocument/publishDiagnostics");
+    let uri = init_and_open(
+        &mut service,
+        test_fixtures_dir().join("diagnostics/dependency_warning/app/src/main.sw"),
+    )
+    .await;
+    wait_for_requests(&published, 2).await;
+
+    // The warning of `lib` is published to its own document before the diagnostics of `app`.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let published: Vec<PublishDiagnosticsParams> = published
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|params| serde_json::from_value(params.clone()).unwrap())
+        .collect();
+    assert_eq!(published.len(), 2, "{published:?}");
+    assert_eq!(published[0].uri.as_str(), "sway-lib:///lib/src/lib.sw");
+    assert_eq!(
+        published[0]
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.range))
+            .collect::<Vec<_>>(),
+        vec![(
+            "This function is never called.",
+            Range::new(Position::new(6, 3), Position::new(6, 11))
+        )]
+    );
+    assert_eq!(published[1].uri, uri);
+    assert!(published[1].diagnostics.is_empty());
+    shutdown_and_exit(&mut service).await;
+}
+
+/// Waits until the server has sent at least `count` of the collected requests.
+async fn wait_for_requests(requests: &Mutex<Vec<serde_json::Value>>, count: usize) {
+    let wait = async {
+        while requests.lock().unwrap().len() < count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(60), wait)
+        .await
+        .expect("Timed out waiting for requests from server");
+}
+
 // This macro allows us to spin up a server / client for testing
 // It initializes and performs the necessary handshake and then loads
 // the sway example that was passed into `example_dir`.
@@ -6,6 +6,7 @@ use std::{
     env, fs,
     io::Read,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use tokio::task::JoinHandle;
@@ -123,6 +124,26 @@ pub async fn assert_server_requests(
     })
 }
 
+/// Collects the params of the `method` requests that the server sends, for as long as the
+/// server is running.
+pub fn collect_server_requests(
+    socket: ClientSocket,
+    method: &'static str,
+) -> Arc<Mutex<Vec<Value>>> {
+    let requests = Arc::new(Mutex::new(vec![]));
+    let collected = requests.clone();
+    tokio::spawn(async move {
+        let mut socket = socket;
+        while let Some(request) = socket.next().await {
+            if request.method() == method {
+                let params = request.params().cloned().unwrap_or_default();
+                collected.lock().unwrap().push(params);
+            }
+        }
+    });
+    requests
+}
+
 pub fn extract_result_array(response: Result<Option<Response>, ExitedError>) -> Vec<Value> {
     response
         .unwrap()
@@ -18,6 +18,16 @@ pub struct SourceEngine {
     path_map: RwLock<HashMap<SourceId, PathBuf>>,
 }
 
+impl Clone for SourceEngine {
+    fn clone(&self) -> Self {
+        SourceEngine {
+            next_id: RwLock::new(*self.next_id.read().unwrap()),
+            source_map: RwLock::new(self.source_map.read().unwrap().clone()),
+            path_map: RwLock::new(self.path_map.read().unwrap().clone()),
+        }
+    }
+}
+
 impl SourceEngine {
     /// This function retrieves an integer-based source ID for a provided path buffer.
     /// If an ID already exists for the given path, the function will return that
@@ -0,0 +1,151 @@
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An unsigned 256-bit integer, backed by a [BigUint] that is always kept below 2^256.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct U256(BigUint);
+
+impl U256 {
+    pub const BITS: u64 = 256;
+
+    pub fn min() -> Self {
+        Self(BigUint::from(0u8))
+    }
+
+    pub fn max() -> Self {
+        Self((BigUint::from(1u8) << Self::BITS) - 1u8)
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Self {
+        Self(BigUint::from_bytes_be(bytes))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let bytes = self.0.to_bytes_be();
+        let mut be_bytes = [0u8; 32];
+        be_bytes[32 - bytes.len()..].copy_from_slice(&bytes);
+        be_bytes
+    }
+
+    pub fn checked_add(&self, other: &U256) -> Option<U256> {
+        Self::checked(&self.0 + &other.0)
+    }
+
+    pub fn checked_sub(&self, other: &U256) -> Option<U256> {
+        (self.0 >= other.0).then(|| Self(&self.0 - &other.0))
+    }
+
+    pub fn checked_mul(&self, other: &U256) -> Option<U256> {
+        Self::checked(&self.0 * &other.0)
+    }
+
+    /// Adds `other` modulo 2^256, as the VM does.
+    pub fn wrapping_add(&self, other: &U256) -> U256 {
+        Self::wrapping(&self.0 + &other.0)
+    }
+
+    /// Multiplies by `other` modulo 2^256, as the VM does.
+    pub fn wrapping_mul(&self, other: &U256) -> U256 {
+        Self::wrapping(&self.0 * &other.0)
+    }
+
+    /// Renders the value in decimal with an underscore between every group of three digits,
+    /// e.g. `1_000_000`. [Display](fmt::Display) stays ungrouped.
+    pub fn to_grouped_string(&self) -> String {
+        let digits = self.0.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push('_');
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    fn checked(value: BigUint) -> Option<U256> {
+        (value.bits() <= Self::BITS).then_some(Self(value))
+    }
+
+    fn wrapping(value: BigUint) -> U256 {
+        Self(value % (BigUint::from(1u8) << Self::BITS))
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self(BigUint::from(value))
+    }
+}
+
+impl TryFrom<BigUint> for U256 {
+    type Error = BigUint;
+
+    /// Fails with the original value if it does not fit in 256 bits.
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        if value.bits() <= Self::BITS {
+            Ok(Self(value))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl From<U256> for BigUint {
+    fn from(value: U256) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapping_add_wraps_around() {
+        let one = U256::from(1);
+        assert_eq!(U256::max().wrapping_add(&one), U256::min());
+        assert_eq!(U256::max().checked_add(&one), None);
+        assert_eq!(U256::max().wrapping_add(&U256::from(3)), U256::from(2));
+        assert_eq!(U256::from(2).wrapping_add(&U256::from(3)), U256::from(5));
+    }
+
+    #[test]
+    fn wrapping_mul_wraps_around() {
+        let two = U256::from(2);
+        // (2^256 - 1) * 2 = 2^257 - 2, which is 2^256 - 2 modulo 2^256.
+        assert_eq!(
+            U256::max().wrapping_mul(&two),
+            U256::max().checked_sub(&U256::from(1)).unwrap()
+        );
+        assert_eq!(U256::max().checked_mul(&two), None);
+        assert_eq!(U256::max().wrapping_mul(&U256::min()), U256::min());
+        assert_eq!(U256::from(6).wrapping_mul(&U256::from(7)), U256::from(42));
+    }
+
+    #[test]
+    fn be_bytes_round_trip() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0x80;
+        bytes[31] = 0x01;
+        assert_eq!(U256::from_be_bytes(&bytes).to_be_bytes(), bytes);
+        assert_eq!(U256::max().to_be_bytes(), [0xff; 32]);
+        assert_eq!(U256::min().to_be_bytes(), [0; 32]);
+    }
+
+    #[test]
+    fn to_grouped_string_groups_by_three() {
+        assert_eq!(U256::from(1000000).to_grouped_string(), "1_000_000");
+        assert_eq!(U256::from(1000000).to_string(), "1000000");
+        assert_eq!(U256::from(12345).to_grouped_string(), "12_345");
+        assert_eq!(U256::from(999).to_grouped_string(), "999");
+        assert_eq!(U256::min().to_grouped_string(), "0");
+    }
+}
@@ -0,0 +1,17 @@
+//! Configuration options related to formatting asm blocks.
+use crate::config::user_opts::AsmOptions;
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Asm {
+    /// Align the mnemonics and each operand column of the instructions within an asm block.
+    pub align_operands: bool,
+}
+
+impl Asm {
+    pub fn from_opts(opts: &AsmOptions) -> Self {
+        let default = Self::default();
+        Self {
+            align_operands: opts.align_operands.unwrap_or(default.align_operands),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Configuration options related to formatting doc comments.
+use crate::{config::user_opts::DocCommentsOptions, constants::DEFAULT_MAX_COMMENT_WIDTH};
+
+#[derive(Debug, Clone)]
+pub struct DocComments {
+    /// Re-wrap the prose of `///` and `//!` comments to fit within `width`.
+    /// Code blocks, tables, list items and other markdown structure are left untouched.
+    pub wrap: bool,
+    /// Maximum length of a doc comment line, including indentation. No effect unless wrap = true.
+    pub width: usize,
+    /// Convert /** */ doc comments to /// doc comments where possible.
+    pub normalize: bool,
+}
+
+impl Default for DocComments {
+    fn default() -> Self {
+        Self {
+            wrap: false,
+            width: DEFAULT_MAX_COMMENT_WIDTH,
+            normalize: false,
+        }
+    }
+}
+
+impl DocComments {
+    pub fn from_opts(opts: &DocCommentsOptions) -> Self {
+        let default = Self::default();
+        Self {
+            wrap: opts.wrap.unwrap_or(default.wrap),
+            width: opts.width.unwrap_or(default.width),
+            normalize: opts.normalize.unwrap_or(default.normalize),
+        }
+    }
+}
@@ -1,9 +1,9 @@
 pub use crate::error::FormatterError;
 use crate::{
     config::{
-        comments::Comments, expr::Expressions, heuristics::Heuristics, imports::Imports,
-        items::Items, lists::Lists, literals::Literals, ordering::Ordering, user_def::Structures,
-        user_opts::*, whitespace::Whitespace,
+        asm::Asm, comments::Comments, doc_comments::DocComments, expr::Expressions,
+        heuristics::Heuristics, imports::Imports, items::Items, lists::Lists, literals::Literals,
+        ordering::Ordering, user_def::Structures, user_opts::*, whitespace::Whitespace,
     },
     constants::SWAY_FORMAT_FILE_NAME,
     error::ConfigError,
@@ -26,6 +26,8 @@ pub struct Config {
     pub heuristics: Heuristics,
     pub structures: Structures,
     pub comments: Comments,
+    pub doc_comments: DocComments,
+    pub asm: Asm,
 }
 
 /// A direct mapping to an optional `swayfmt.toml`.
@@ -42,6 +44,8 @@ pub struct ConfigOptions {
     pub heuristics: Option<HeuristicsOptions>,
     pub structures: Option<StructuresOptions>,
     pub comments: Option<CommentsOptions>,
+    pub doc_comments: Option<DocCommentsOptions>,
+    pub asm: Option<AsmOptions>,
 }
 
 impl Config {
@@ -99,6 +103,12 @@ impl Config {
                 .as_ref()
                 .map(Comments::from_opts)
                 .unwrap_or_default(),
+            doc_comments: opts
+                .doc_comments
+                .as_ref()
+                .map(DocComments::from_opts)
+                .unwrap_or_default(),
+            asm: opts.asm.as_ref().map(Asm::from_opts).unwrap_or_default(),
         }
     }
     /// Given a directory to a forc project containing a `swayfmt.toml`, read and
@@ -1,4 +1,6 @@
+pub mod asm;
 pub mod comments;
+pub mod doc_comments;
 pub mod expr;
 pub mod heuristics;
 pub mod imports;
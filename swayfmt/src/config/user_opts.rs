@@ -88,3 +88,15 @@ pub struct CommentsOptions {
     pub comment_width: Option<usize>,
     pub normalize_comments: Option<bool>,
 }
+/// See parent struct [DocComments].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct DocCommentsOptions {
+    pub wrap: Option<bool>,
+    pub width: Option<usize>,
+    pub normalize: Option<bool>,
+}
+/// See parent struct [Asm].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct AsmOptions {
+    pub align_operands: Option<bool>,
+}
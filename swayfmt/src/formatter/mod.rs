@@ -2,7 +2,11 @@ use self::shape::Shape;
 use crate::comments::{write_comments, CommentsContext};
 use crate::parse::parse_file;
 use crate::utils::map::comments::CommentMap;
-use crate::utils::map::{newline::handle_newlines, newline_style::apply_newline_style};
+use crate::utils::map::{
+    doc_comments::{normalize_doc_comments, wrap_doc_comments},
+    newline::handle_newlines,
+    newline_style::apply_newline_style,
+};
 pub use crate::{
     config::manifest::Config,
     error::{ConfigError, FormatterError},
@@ -57,6 +61,14 @@ impl Formatter {
                 .to_width_heuristics(self.config.whitespace.max_width),
         );
         let src = src.trim();
+        // Convert block doc comments to line doc comments before anything else
+        let normalized_src;
+        let src = if self.config.doc_comments.normalize {
+            normalized_src = normalize_doc_comments(&self.source_engine, src)?;
+            normalized_src.as_str()
+        } else {
+            src
+        };
 
         let path = build_config.map(|build_config| build_config.canonical_root_module());
         // Formatted code will be pushed here with raw newline stlye.
@@ -91,6 +103,10 @@ impl Formatter {
             &mut formatted_code,
             self,
         )?;
+        // Re-wrap doc comments
+        if self.config.doc_comments.wrap {
+            wrap_doc_comments(&self.config.doc_comments, &mut formatted_code)?;
+        }
         // Replace newlines with specified `NewlineStyle`
         apply_newline_style(
             self.config.whitespace.newline_style,
@@ -1,6 +1,9 @@
 use crate::{
     comments::rewrite_with_comments,
-    formatter::{shape::LineStyle, *},
+    formatter::{
+        shape::{ExprKind, LineStyle},
+        *,
+    },
     utils::{
         map::byte_span::{ByteSpan, LeafSpans},
         CurlyBrace, Parenthesis,
@@ -60,13 +63,46 @@ fn format_asm_block(
     write!(formatted_code, "{}", asm_block.asm_token.span().as_str())?;
 
     formatter.with_shape(
-        formatter.shape.with_default_code_line(),
+        formatter
+            .shape
+            .with_code_line_from(LineStyle::Normal, ExprKind::Function),
         |formatter| -> Result<(), FormatterError> {
-            AsmBlock::open_parenthesis(formatted_code, formatter)?;
+            // The register list wraps like the arguments of a function call.
+            let mut registers = FormattedCode::new();
+            let mut temp_formatter = Formatter::default();
             asm_block
                 .registers
                 .get()
-                .format(formatted_code, formatter)?;
+                .format(&mut registers, &mut temp_formatter)?;
+            let registers_width = registers.chars().count();
+            // add the width of `asm(` and `) {`
+            formatter
+                .shape
+                .code_line
+                .update_width(formatter.shape.indent.block_indent + registers_width + 7);
+            formatter
+                .shape
+                .get_line_style(None, Some(registers_width), &formatter.config);
+
+            AsmBlock::open_parenthesis(formatted_code, formatter)?;
+            if formatter.shape.code_line.line_style == LineStyle::Multiline {
+                formatter.shape.block_indent(&formatter.config);
+                asm_block
+                    .registers
+                    .get()
+                    .format(formatted_code, formatter)?;
+                formatter.shape.block_unindent(&formatter.config);
+                write!(
+                    formatted_code,
+                    "{}",
+                    formatter.shape.indent.to_string(&formatter.config)?
+                )?;
+            } else {
+                asm_block
+                    .registers
+                    .get()
+                    .format(formatted_code, formatter)?;
+            }
             AsmBlock::close_parenthesis(formatted_code, formatter)?;
 
             Ok(())
@@ -168,19 +204,75 @@ impl Format for Instruction {
     }
 }
 
+/// The mnemonic followed by the register and immediate operands of an instruction.
+fn instruction_columns(instruction: &Instruction) -> Vec<String> {
+    std::iter::once(instruction.op_code_ident())
+        .chain(instruction.register_arg_idents())
+        .chain(instruction.immediate_idents())
+        .map(|ident| ident.as_str().to_string())
+        .collect()
+}
+
+/// The width of each column over all the instructions, used to align the mnemonics and operands.
+fn column_widths(instructions: &[Vec<String>]) -> Vec<usize> {
+    let mut widths = Vec::new();
+    for columns in instructions {
+        for (i, column) in columns.iter().enumerate() {
+            let width = column.chars().count();
+            match widths.get_mut(i) {
+                Some(max_width) if *max_width < width => *max_width = width,
+                Some(_) => {}
+                None => widths.push(width),
+            }
+        }
+    }
+    widths
+}
+
+fn format_aligned_instruction(
+    columns: &[String],
+    widths: &[usize],
+    formatted_code: &mut FormattedCode,
+) -> Result<(), FormatterError> {
+    let mut columns = columns.iter().zip(widths).peekable();
+    while let Some((column, width)) = columns.next() {
+        if columns.peek().is_some() {
+            write!(formatted_code, "{column:<width$} ")?;
+        } else {
+            // do not pad the last column, to avoid trailing whitespace
+            write!(formatted_code, "{column}")?;
+        }
+    }
+    Ok(())
+}
+
 impl Format for AsmBlockContents {
     fn format(
         &self,
         formatted_code: &mut FormattedCode,
         formatter: &mut Formatter,
     ) -> Result<(), FormatterError> {
-        for (instruction, semicolon_token) in self.instructions.iter() {
+        let aligned_instructions = formatter.config.asm.align_operands.then(|| {
+            let columns = self
+                .instructions
+                .iter()
+                .map(|(instruction, _)| instruction_columns(instruction))
+                .collect::<Vec<_>>();
+            let widths = column_widths(&columns);
+            (columns, widths)
+        });
+        for (i, (instruction, semicolon_token)) in self.instructions.iter().enumerate() {
             write!(
                 formatted_code,
                 "{}",
                 formatter.shape.indent.to_string(&formatter.config)?
             )?;
-            instruction.format(formatted_code, formatter)?;
+            match &aligned_instructions {
+                Some((columns, widths)) => {
+                    format_aligned_instruction(&columns[i], widths, formatted_code)?
+                }
+                None => instruction.format(formatted_code, formatter)?,
+            }
             writeln!(formatted_code, "{}", semicolon_token.span().as_str())?
         }
         if let Some(final_expr) = &self.final_expr_opt {
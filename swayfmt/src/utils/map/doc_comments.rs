@@ -0,0 +1,433 @@
+//! Re-wrapping and normalization of doc comments.
+//!
+//! Normalization is applied to the unformatted code, so that the resulting `///` comments are formatted
+//! like any other doc comment. Re-wrapping is applied as a final pass over the formatted code, since it
+//! changes the number of doc comment lines and would otherwise throw off the leaf span based comment
+//! and newline handling.
+//!
+//! Markdown structure is detected conservatively: anything that is not clearly a line of plain prose
+//! (code blocks, tables, list items, headings, quotes, html, etc.) is left exactly as it was written.
+use crate::{
+    config::doc_comments::DocComments,
+    formatter::FormattedCode,
+    parse::{lex, parse_file},
+    FormatterError,
+};
+use std::{ops::Range, sync::Arc};
+use sway_ast::token::{CommentedTokenTree, CommentedTree, DocComment, DocStyle};
+use sway_types::SourceEngine;
+
+const OUTER_DOC_PREFIX: &str = "///";
+const INNER_DOC_PREFIX: &str = "//!";
+
+/// Converts `/** */` comments that are the only thing on their lines to `///` comments in the
+/// unformatted code, so that they are formatted as regular doc comments afterwards.
+///
+/// Line doc comments are only allowed in front of items, so the code must still parse after each
+/// conversion. Otherwise that block doc comment is kept as it is.
+pub(crate) fn normalize_doc_comments(
+    source_engine: &SourceEngine,
+    src: &str,
+) -> Result<String, FormatterError> {
+    let mut normalized = src.to_string();
+    // Going backwards keeps the ranges of the remaining comments valid.
+    for (range, replacement) in block_doc_comment_replacements(src)?.into_iter().rev() {
+        let mut candidate = normalized.clone();
+        candidate.replace_range(range, &replacement);
+        if parse_file(source_engine, Arc::from(candidate.as_str()), None).is_ok() {
+            normalized = candidate;
+        }
+    }
+    Ok(normalized)
+}
+
+/// Re-wraps the prose of the doc comments in the formatted code to fit within the configured width.
+pub(crate) fn wrap_doc_comments(
+    config: &DocComments,
+    formatted_code: &mut FormattedCode,
+) -> Result<(), FormatterError> {
+    *formatted_code = wrap_doc_comment_runs(formatted_code, config.width)?;
+    Ok(())
+}
+
+fn block_doc_comment_replacements(
+    code: &str,
+) -> Result<Vec<(Range<usize>, String)>, FormatterError> {
+    let tts = lex(&Arc::from(code))?;
+    let mut comments = Vec::new();
+    collect_comment_spans(tts.token_trees(), &mut comments);
+
+    let mut replacements = Vec::new();
+    for range in comments {
+        let comment = &code[range.clone()];
+        if !is_block_doc_comment(comment) {
+            continue;
+        }
+        let Some(indent) = line_indent(code, range.start) else {
+            continue;
+        };
+        let rest_of_line = code[range.end..].split('\n').next().unwrap_or_default();
+        if !rest_of_line.trim().is_empty() {
+            continue;
+        }
+        let lines = block_doc_comment_lines(comment);
+        if lines.is_empty() {
+            continue;
+        }
+        let replacement = lines
+            .iter()
+            .map(|line| format!("{OUTER_DOC_PREFIX}{line}"))
+            .collect::<Vec<_>>()
+            .join(&format!("\n{indent}"));
+        replacements.push((range, replacement));
+    }
+
+    Ok(replacements)
+}
+
+/// Re-wraps the prose in every run of consecutive `///` or `//!` lines to fit within `width`.
+fn wrap_doc_comment_runs(code: &str, width: usize) -> Result<String, FormatterError> {
+    let tts = lex(&Arc::from(code))?;
+    let mut doc_comments = Vec::new();
+    collect_doc_comments(tts.token_trees(), &mut doc_comments);
+
+    let mut runs: Vec<Vec<DocComment>> = Vec::new();
+    for doc_comment in doc_comments {
+        match runs.last_mut() {
+            Some(run) if continues_run(code, run.last().unwrap(), &doc_comment) => {
+                run.push(doc_comment)
+            }
+            _ => runs.push(vec![doc_comment]),
+        }
+    }
+
+    let mut replacements = Vec::new();
+    for run in runs {
+        let first = &run[0];
+        let Some(indent) = line_indent(code, first.span.start()) else {
+            continue;
+        };
+        let prefix = match first.doc_style {
+            DocStyle::Outer => OUTER_DOC_PREFIX,
+            DocStyle::Inner => INNER_DOC_PREFIX,
+        };
+        let lines = run
+            .iter()
+            .map(|doc_comment| doc_comment.content_span.as_str())
+            .collect::<Vec<_>>();
+        let available_width = width.saturating_sub(indent.chars().count() + prefix.len());
+        let wrapped = rewrap(&lines, available_width);
+        if wrapped.iter().map(String::as_str).eq(lines.iter().copied()) {
+            continue;
+        }
+        let replacement = wrapped
+            .iter()
+            .map(|line| format!("{prefix}{line}"))
+            .collect::<Vec<_>>()
+            .join(&format!("\n{indent}"));
+        let range = first.span.start()..run.last().unwrap().span.end();
+        replacements.push((range, replacement));
+    }
+
+    Ok(apply_replacements(code, replacements))
+}
+
+fn collect_comment_spans(tts: &[CommentedTokenTree], comments: &mut Vec<Range<usize>>) {
+    for tt in tts {
+        match tt {
+            CommentedTokenTree::Comment(comment) => {
+                comments.push(comment.span.start()..comment.span.end())
+            }
+            CommentedTokenTree::Tree(CommentedTree::Group(group)) => {
+                collect_comment_spans(group.token_stream.token_trees(), comments)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_doc_comments(tts: &[CommentedTokenTree], doc_comments: &mut Vec<DocComment>) {
+    for tt in tts {
+        match tt {
+            CommentedTokenTree::Tree(CommentedTree::DocComment(doc_comment)) => {
+                doc_comments.push(doc_comment.clone())
+            }
+            CommentedTokenTree::Tree(CommentedTree::Group(group)) => {
+                collect_doc_comments(group.token_stream.token_trees(), doc_comments)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `next` is on the line directly below `prev`, with the same style and indentation.
+fn continues_run(code: &str, prev: &DocComment, next: &DocComment) -> bool {
+    let between = &code[prev.span.end()..next.span.start()];
+    prev.doc_style == next.doc_style
+        && between.trim().is_empty()
+        && between.matches('\n').count() == 1
+        && line_indent(code, prev.span.start()) == line_indent(code, next.span.start())
+}
+
+/// The whitespace in front of `pos` on its line, or `None` if anything else precedes it.
+fn line_indent(code: &str, pos: usize) -> Option<&str> {
+    let line_start = code[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &code[line_start..pos];
+    indent.trim().is_empty().then_some(indent)
+}
+
+fn is_block_doc_comment(comment: &str) -> bool {
+    comment.starts_with("/**")
+        && !comment.starts_with("/***")
+        && comment != "/**/"
+        // The lexer merges nested block comments into one, leave those alone.
+        && !comment[2..].contains("/*")
+}
+
+/// The content of a `/** */` comment as a list of `///` line contents. A leading `*` decoration
+/// is removed only if every line after the first one has it.
+fn block_doc_comment_lines(comment: &str) -> Vec<String> {
+    let body = &comment[3..comment.len() - 2];
+    let mut body_lines = body.lines();
+    let first = body_lines.next().unwrap_or_default().trim();
+    let rest = body_lines.collect::<Vec<_>>();
+
+    let decorated = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .all(|line| line.trim_start().starts_with('*'));
+    let rest = if decorated {
+        rest.iter()
+            .map(|line| line.trim_start().strip_prefix('*').unwrap_or_default())
+            .collect::<Vec<_>>()
+    } else {
+        let common_indent = rest
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or_default();
+        rest.iter()
+            .map(|line| line.get(common_indent..).unwrap_or_default())
+            .collect()
+    };
+
+    let mut lines = std::iter::once(first)
+        .chain(rest)
+        .map(|line| {
+            let line = line.trim_end();
+            if line.is_empty() || (decorated && line.starts_with(' ')) {
+                line.to_string()
+            } else {
+                format!(" {line}")
+            }
+        })
+        .collect::<Vec<_>>();
+    while lines.first().is_some_and(|line| line.is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Re-wraps each paragraph of plain prose lines to fit within `width`, leaving every other line
+/// untouched.
+fn rewrap(lines: &[&str], width: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    let mut paragraph = Vec::new();
+    let mut code_fence: Option<char> = None;
+    for line in lines {
+        let fence = fence_char(line);
+        if let Some(open) = code_fence {
+            if fence == Some(open) {
+                code_fence = None;
+            }
+            wrapped.push(line.to_string());
+            continue;
+        }
+        if fence.is_none() && is_prose(line) {
+            paragraph.push(*line);
+            continue;
+        }
+        // A paragraph directly followed by a setext underline is a heading, keep it as it is.
+        let is_heading = is_thematic_break(line.trim());
+        flush_paragraph(&mut paragraph, &mut wrapped, width, !is_heading);
+        code_fence = fence;
+        wrapped.push(line.to_string());
+    }
+    flush_paragraph(&mut paragraph, &mut wrapped, width, true);
+    wrapped
+}
+
+fn flush_paragraph(paragraph: &mut Vec<&str>, wrapped: &mut Vec<String>, width: usize, fill: bool) {
+    if fill {
+        wrapped.extend(fill_paragraph(paragraph, width));
+    } else {
+        wrapped.extend(paragraph.iter().map(|line| line.to_string()));
+    }
+    paragraph.clear();
+}
+
+/// Greedily fills lines with the words of the paragraph. Words are never split, and a line is never
+/// broken in front of a word that would turn the new line into markdown structure.
+fn fill_paragraph(paragraph: &[&str], width: usize) -> Vec<String> {
+    let mut filled = Vec::new();
+    let mut current = String::new();
+    for word in paragraph.iter().flat_map(|line| line.split_whitespace()) {
+        if !current.is_empty()
+            && current.chars().count() + 1 + word.chars().count() > width
+            && !starts_block(word)
+        {
+            filled.push(std::mem::take(&mut current));
+        }
+        current.push(' ');
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        filled.push(current);
+    }
+    filled
+}
+
+/// Whether the doc comment line is plain prose that can safely be re-wrapped.
+fn is_prose(line: &str) -> bool {
+    // Lines that are indented further are code blocks or list item continuations.
+    let Some(text) = line.strip_prefix(' ') else {
+        return false;
+    };
+    let Some(first_word) = text.split_whitespace().next() else {
+        return false;
+    };
+    !text.starts_with(char::is_whitespace)
+        // Hard line breaks.
+        && !text.ends_with('\\')
+        && !text.ends_with("  ")
+        && !starts_block(first_word)
+        // Tables.
+        && !text.contains('|')
+        // Html.
+        && !text.starts_with('<')
+        // Link reference definitions.
+        && !(text.starts_with('[') && text.contains("]:"))
+}
+
+/// Whether a line starting with `word` would be markdown structure rather than prose.
+fn starts_block(word: &str) -> bool {
+    matches!(word, "-" | "+" | "*")
+        || word.starts_with('#')
+        || word.starts_with('>')
+        || word.starts_with("```")
+        || word.starts_with("~~~")
+        || is_ordered_list_marker(word)
+        || is_thematic_break(word)
+}
+
+fn is_ordered_list_marker(word: &str) -> bool {
+    let digits = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    digits.len() < word.len() && matches!(digits, "." | ")")
+}
+
+/// Thematic breaks and setext heading underlines, e.g. `---` or `===`.
+fn is_thematic_break(text: &str) -> bool {
+    let text = text.replace(' ', "");
+    text.len() >= 3
+        && ['-', '=', '_', '*']
+            .iter()
+            .any(|c| text.chars().all(|text_char| text_char == *c))
+}
+
+/// The fence character if the line opens or closes a fenced code block.
+fn fence_char(line: &str) -> Option<char> {
+    let text = line.trim_start();
+    if text.starts_with("```") {
+        Some('`')
+    } else if text.starts_with("~~~") {
+        Some('~')
+    } else {
+        None
+    }
+}
+
+fn apply_replacements(code: &str, replacements: Vec<(Range<usize>, String)>) -> String {
+    let mut result = code.to_string();
+    for (range, replacement) in replacements.into_iter().rev() {
+        result.replace_range(range, &replacement);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{block_doc_comment_lines, normalize_doc_comments, rewrap};
+    use sway_types::SourceEngine;
+
+    #[test]
+    fn rewrap_joins_and_splits_prose() {
+        let lines = [
+            " The quick brown fox jumps over the lazy dog.",
+            " It was",
+            " not amused.",
+        ];
+        assert_eq!(
+            rewrap(&lines, 24),
+            vec![
+                " The quick brown fox",
+                " jumps over the lazy",
+                " dog. It was not amused.",
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrap_leaves_markdown_structure_alone() {
+        let lines = [
+            " # Examples",
+            "",
+            " - a list item that is long enough to need wrapping",
+            " 1. an ordered item that is long enough to need wrapping",
+            " | a | table |",
+            " > a quote that is long enough to need wrapping",
+            "     indented code that is long enough to need wrapping",
+            " Heading text",
+            " ============",
+        ];
+        assert_eq!(rewrap(&lines, 20), lines);
+    }
+
+    #[test]
+    fn rewrap_never_starts_a_line_with_a_block_marker() {
+        let lines = [" Values are added - not multiplied."];
+        assert_eq!(
+            rewrap(&lines, 17),
+            vec![" Values are added -", " not multiplied."]
+        );
+    }
+
+    #[test]
+    fn block_doc_comment_lines_strip_decoration() {
+        assert_eq!(
+            block_doc_comment_lines("/** Single line. */"),
+            vec![" Single line."]
+        );
+        assert_eq!(
+            block_doc_comment_lines("/**\n     * Decorated\n     *\n     *     code\n     */"),
+            vec![" Decorated", "", "     code"]
+        );
+        assert_eq!(
+            block_doc_comment_lines("/** Plain\n    lines\n      indented\n*/"),
+            vec![" Plain", " lines", "   indented"]
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_block_doc_comments_not_in_front_of_items() {
+        let src =
+            "library;\n\n/** Foo. */\nfn foo() {\n    /** Not an item. */\n    let x = 1;\n}\n";
+        assert_eq!(
+            normalize_doc_comments(&SourceEngine::default(), src).unwrap(),
+            "library;\n\n/// Foo.\nfn foo() {\n    /** Not an item. */\n    let x = 1;\n}\n"
+        );
+    }
+}
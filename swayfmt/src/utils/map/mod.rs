@@ -1,4 +1,5 @@
 pub(crate) mod byte_span;
 pub(crate) mod comments;
+pub(crate) mod doc_comments;
 pub(crate) mod newline;
 pub(crate) mod newline_style;
@@ -1588,3 +1588,254 @@ impl MyContract for Contract {
 "#,
     );
 }
+
+#[test]
+fn doc_comments_wrap_prose_and_keep_code_fences() {
+    let mut formatter = Formatter::default();
+    formatter.config.doc_comments.wrap = true;
+    formatter.config.doc_comments.width = 50;
+    check_with_formatter(
+        r#"library;
+
+/// Adds two numbers together, returning the sum of both of them without checking for overflow.
+///
+/// # Examples
+///
+/// ```sway
+/// let sum = add(1, 2); // a trailing comment that is long enough to go past the width
+/// assert(sum == 3);
+/// ```
+///
+/// - a list item that is long enough to go past the configured width
+/// | a table | that is long enough to go past the configured width |
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+struct Foo {
+    /// A field whose documentation
+    /// is split over
+    /// several short lines.
+    bar: u64,
+}
+"#,
+        r#"library;
+
+/// Adds two numbers together, returning the sum
+/// of both of them without checking for overflow.
+///
+/// # Examples
+///
+/// ```sway
+/// let sum = add(1, 2); // a trailing comment that is long enough to go past the width
+/// assert(sum == 3);
+/// ```
+///
+/// - a list item that is long enough to go past the configured width
+/// | a table | that is long enough to go past the configured width |
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+struct Foo {
+    /// A field whose documentation is split over
+    /// several short lines.
+    bar: u64,
+}
+"#,
+        &mut formatter,
+    );
+}
+
+#[test]
+fn doc_comments_wrap_disabled_by_default() {
+    check(
+        r#"library;
+
+/// Adds two numbers together, returning the sum of both of them without checking for overflow.
+/// ```sway
+/// fn add(a: u64, b: u64) -> u64 { a + b }
+/// ```
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+"#,
+        r#"library;
+
+/// Adds two numbers together, returning the sum of both of them without checking for overflow.
+/// ```sway
+/// fn add(a: u64, b: u64) -> u64 { a + b }
+/// ```
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+"#,
+    );
+}
+
+#[test]
+fn doc_comments_normalize_block_comments() {
+    let mut formatter = Formatter::default();
+    formatter.config.doc_comments.normalize = true;
+    check_with_formatter(
+        r#"library;
+
+/**
+ * Adds two numbers together.
+ *
+ * ```sway
+ * let sum = add(1, 2);
+ * ```
+ */
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+/** Subtracts `b` from `a`. */
+pub fn sub(a: u64, b: u64) -> u64 {
+    a - b
+}
+"#,
+        r#"library;
+
+/// Adds two numbers together.
+///
+/// ```sway
+/// let sum = add(1, 2);
+/// ```
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+/// Subtracts `b` from `a`.
+pub fn sub(a: u64, b: u64) -> u64 {
+    a - b
+}
+"#,
+        &mut formatter,
+    );
+}
+
+#[test]
+fn asm_block_registers_wrap() {
+    check(
+        r#"library;
+
+fn foo() -> u64 {
+        asm(  first_register : first_value,second_register: second_value, third_register) {
+  add first_register   second_register third_register;
+     first_register : u64
+    }
+}
+"#,
+        r#"library;
+
+fn foo() -> u64 {
+    asm(
+        first_register: first_value,
+        second_register: second_value,
+        third_register,
+    ) {
+        add  first_register second_register third_register;
+        first_register: u64
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn asm_block_align_operands() {
+    let mut formatter = Formatter::default();
+    formatter.config.asm.align_operands = true;
+    check_with_formatter(
+        r#"library;
+
+fn foo() -> bool {
+    asm(r1: self, r2: other, result, ptr) {
+        addi ptr zero i32; // allocate
+        meq result r1 r2 ptr;
+        lw r1 ptr i0;
+        result: bool
+    }
+}
+"#,
+        r#"library;
+
+fn foo() -> bool {
+    asm(r1: self, r2: other, result, ptr) {
+        addi ptr    zero i32; // allocate
+        meq  result r1   r2  ptr;
+        lw   r1     ptr  i0;
+        result: bool
+    }
+}
+"#,
+        &mut formatter,
+    );
+}
+
+/// Parses the given code and serializes its AST without any of the spans and trailing separators,
+/// so that the ASTs of the unformatted and formatted code can be compared.
+fn ast_without_spans(src: &str) -> serde_json::Value {
+    fn strip_spans(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                fields.remove("span");
+                fields.values_mut().for_each(strip_spans);
+                // A `Punctuated` becomes the list of its values.
+                if let (Some(serde_json::Value::Array(pairs)), Some(final_value)) = (
+                    fields.get("value_separator_pairs"),
+                    fields.get("final_value_opt"),
+                ) {
+                    let mut values = pairs.iter().map(|pair| pair[0].clone()).collect::<Vec<_>>();
+                    if !final_value.is_null() {
+                        values.push(final_value.clone());
+                    }
+                    *value = serde_json::Value::Array(values);
+                }
+            }
+            serde_json::Value::Array(values) => values.iter_mut().for_each(strip_spans),
+            _ => {}
+        }
+    }
+    let handler = <_>::default();
+    let module = sway_parse::parse_file(&handler, Arc::from(src), None).unwrap();
+    let mut value = serde_json::to_value(module).unwrap();
+    strip_spans(&mut value);
+    value
+}
+
+#[test]
+fn asm_block_round_trip() {
+    let sources = [
+        r#"library;
+
+fn foo() -> bool {
+        asm(  r1 : self,r2: other, r3,    r4) {
+  addi r3   zero i32; // add
+            meq r4 r1 r2 r3; lw r3 r4 i0;
+     r4 : bool
+    }
+}
+"#,
+        r#"library;
+
+fn foo() {
+    let x = asm(first_register: first_value, second_register: second_value, third_register) { first_register: u64 };
+    asm(r1: 0x1111111111111111111111111111111111111111111111111111111111111111, r2) {
+        mcpi r2 r1 i32; /* copy */
+        sww r1 r2 r2;
+    }
+}
+"#,
+    ];
+    for align_operands in [false, true] {
+        let mut formatter = Formatter::default();
+        formatter.config.asm.align_operands = align_operands;
+        for src in sources {
+            let formatted = Formatter::format(&mut formatter, Arc::from(src), None).unwrap();
+            assert_eq!(ast_without_spans(src), ast_without_spans(&formatted));
+        }
+    }
+}
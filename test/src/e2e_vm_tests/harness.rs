@@ -257,6 +257,7 @@ pub(crate) async fn compile_and_run_unit_tests(
     file_name: &str,
     run_config: &RunConfig,
     capture_output: bool,
+    release: bool,
 ) -> (Result<Vec<forc_test::TestedPackage>>, String) {
     run_and_capture_output(|| async {
         tracing::info!("Compiling {} ...", file_name.bold());
@@ -277,6 +278,7 @@ pub(crate) async fn compile_and_run_unit_tests(
                 terse: !(capture_output || run_config.verbose),
                 ..Default::default()
             },
+            release,
             ..Default::default()
         })?;
         let test_filter = None;
@@ -66,6 +66,7 @@ struct TestDescription {
     contract_paths: Vec<String>,
     validate_abi: bool,
     validate_storage_slots: bool,
+    compare_optimized_gas: bool,
     supported_targets: HashSet<BuildTarget>,
     checker: filecheck::Checker,
 }
@@ -101,6 +102,7 @@ impl TestContext {
             contract_paths,
             validate_abi,
             validate_storage_slots,
+            compare_optimized_gas,
             checker,
             ..
         } = test;
@@ -316,10 +318,12 @@ impl TestContext {
 
             TestCategory::UnitTestsPass => {
                 let (result, out) =
-                    harness::compile_and_run_unit_tests(&name, &context.run_config, true).await;
+                    harness::compile_and_run_unit_tests(&name, &context.run_config, true, false)
+                        .await;
                 *output = out;
 
                 let tested_pkgs = result.expect("failed to compile and run unit tests");
+                let gas_used = total_gas_used(&tested_pkgs);
                 let failed: Vec<String> = tested_pkgs
                     .into_iter()
                     .flat_map(|tested_pkg| {
@@ -346,6 +350,30 @@ impl TestContext {
                         failed.into_iter().collect::<String>()
                     );
                 }
+
+                if compare_optimized_gas {
+                    let (result, out) =
+                        harness::compile_and_run_unit_tests(&name, &context.run_config, true, true)
+                            .await;
+                    output.push_str(&out);
+
+                    let tested_pkgs =
+                        result.expect("failed to compile and run optimized unit tests");
+                    if tested_pkgs
+                        .iter()
+                        .flat_map(|tested_pkg| &tested_pkg.tests)
+                        .any(|test| !test.passed())
+                    {
+                        panic!("For {name}\nunit tests failed in the release build");
+                    }
+                    let optimized_gas_used = total_gas_used(&tested_pkgs);
+                    if optimized_gas_used >= gas_used {
+                        panic!(
+                            "For {name}\nthe release build used {optimized_gas_used} gas, \
+                            expected less than the {gas_used} gas used by the debug build"
+                        );
+                    }
+                }
                 Ok(())
             }
 
@@ -665,6 +693,11 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         .map(|v| v.as_bool().unwrap_or(false))
         .unwrap_or(false);
 
+    let compare_optimized_gas = toml_content
+        .get("compare_optimized_gas")
+        .map(|v| v.as_bool().unwrap_or(false))
+        .unwrap_or(false);
+
     // We need to adjust the path to start relative to `test_programs`.
     let name = path
         .iter()
@@ -706,11 +739,21 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         contract_paths,
         validate_abi,
         validate_storage_slots,
+        compare_optimized_gas,
         supported_targets,
         checker,
     })
 }
 
+/// The gas used by all of the unit tests of `tested_pkgs`.
+fn total_gas_used(tested_pkgs: &[forc_test::TestedPackage]) -> u64 {
+    tested_pkgs
+        .iter()
+        .flat_map(|tested_pkg| &tested_pkg.tests)
+        .map(|test| test.gas_used)
+        .sum()
+}
+
 fn get_test_abi_from_value(value: &toml::Value) -> Result<BuildTarget> {
     match value.as_str() {
         Some(target) => match BuildTarget::from_str(target) {
@@ -0,0 +1,11 @@
+contract;
+
+abi Colliding {
+    fn collide_53001();
+    fn collide_115065();
+}
+
+impl Colliding for Contract {
+    fn collide_53001() {}
+    fn collide_115065() {}
+}
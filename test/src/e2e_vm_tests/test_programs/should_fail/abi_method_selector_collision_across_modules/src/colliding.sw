@@ -0,0 +1,7 @@
+library;
+
+abi Colliding {
+    fn collide_53001();
+} {
+    fn collide_115065() {}
+}
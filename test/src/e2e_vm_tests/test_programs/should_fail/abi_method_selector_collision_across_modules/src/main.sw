@@ -0,0 +1,9 @@
+contract;
+
+mod colliding;
+
+use colliding::Colliding;
+
+impl Colliding for Contract {
+    fn collide_53001() {}
+}
@@ -0,0 +1,5 @@
+script;
+
+fn main() {
+   let _x: b256 = 0x00000000000000000000000000000000000000000000000000000000000001;
+}